@@ -24,6 +24,10 @@ ALTER TABLE books ADD COLUMN IF NOT EXISTS published_date DATE NULL;
 ALTER TABLE books ADD COLUMN IF NOT EXISTS page_count INT NULL;
 ALTER TABLE books ADD COLUMN IF NOT EXISTS google_id VARCHAR(255) NULL;
 ALTER TABLE books ALTER COLUMN image_url TYPE VARCHAR(1000);
+-- Última vez em que os dados vieram do Google Books; nulo força a atualização
+ALTER TABLE books ADD COLUMN IF NOT EXISTS metadata_fetched_at TIMESTAMP NULL;
+-- Última tentativa de consulta, mesmo sem sucesso; adia a próxima até o prazo passar
+ALTER TABLE books ADD COLUMN IF NOT EXISTS metadata_checked_at TIMESTAMP NULL;
 
 
 
@@ -43,6 +47,69 @@ CREATE TABLE IF NOT EXISTS books_offered (
     PRIMARY KEY (book_id, user_id)
 );
 
+ALTER TABLE books_offered ADD COLUMN IF NOT EXISTS condition VARCHAR(20) NOT NULL DEFAULT 'good'
+    CHECK (condition IN ('new', 'like_new', 'good', 'fair', 'poor'));
+ALTER TABLE books_offered ADD COLUMN IF NOT EXISTS language VARCHAR(10) NULL;
+ALTER TABLE books_offered ADD COLUMN IF NOT EXISTS notes TEXT NULL;
+ALTER TABLE books_offered ADD COLUMN IF NOT EXISTS available BOOLEAN NOT NULL DEFAULT TRUE;
+
+CREATE TABLE IF NOT EXISTS books_offered_photos (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    book_id UUID NOT NULL,
+    user_id UUID NOT NULL,
+    storage_key VARCHAR(255) NOT NULL UNIQUE,
+    content_type VARCHAR(50) NOT NULL,
+    size_bytes INT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (book_id, user_id) REFERENCES books_offered(book_id, user_id) ON DELETE CASCADE
+);
+
+-- Cada linha de books_offered passa a ser um exemplar, com id próprio,
+-- permitindo que o mesmo usuário ofereça várias cópias de um livro
+ALTER TABLE books_offered ADD COLUMN IF NOT EXISTS id UUID NOT NULL DEFAULT uuid_generate_v4();
+ALTER TABLE books_offered_photos DROP CONSTRAINT IF EXISTS books_offered_photos_book_id_user_id_fkey;
+
+-- As consultas ao catálogo se restringem ao current_schema(), para que o script
+-- também possa montar os schemas isolados usados pelos testes
+DO $$
+BEGIN
+    IF EXISTS (
+        SELECT 1 FROM information_schema.key_column_usage
+        WHERE table_schema = current_schema()
+          AND table_name = 'books_offered'
+          AND constraint_name = 'books_offered_pkey'
+          AND column_name = 'book_id'
+    ) THEN
+        ALTER TABLE books_offered DROP CONSTRAINT books_offered_pkey;
+        ALTER TABLE books_offered ADD PRIMARY KEY (id);
+    END IF;
+END $$;
+
+CREATE INDEX IF NOT EXISTS books_offered_user_book_idx ON books_offered (user_id, book_id);
+
+-- As fotos passam a pertencer a um exemplar específico
+ALTER TABLE books_offered_photos ADD COLUMN IF NOT EXISTS copy_id UUID NULL
+    REFERENCES books_offered(id) ON DELETE CASCADE;
+
+DO $$
+BEGIN
+    IF EXISTS (
+        SELECT 1 FROM information_schema.columns
+        WHERE table_schema = current_schema()
+          AND table_name = 'books_offered_photos' AND column_name = 'book_id'
+    ) THEN
+        UPDATE books_offered_photos p
+        SET copy_id = o.id
+        FROM books_offered o
+        WHERE p.copy_id IS NULL AND o.book_id = p.book_id AND o.user_id = p.user_id;
+
+        DELETE FROM books_offered_photos WHERE copy_id IS NULL;
+        ALTER TABLE books_offered_photos DROP COLUMN book_id;
+    END IF;
+END $$;
+
+ALTER TABLE books_offered_photos ALTER COLUMN copy_id SET NOT NULL;
+
 CREATE TABLE IF NOT EXISTS trades (
     id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
     book_offered_id UUID NOT NULL REFERENCES books(id),
@@ -51,4 +118,132 @@ CREATE TABLE IF NOT EXISTS trades (
     updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
     status VARCHAR(255) NOT NULL,
     user_id UUID NOT NULL REFERENCES users(id)
-);
\ No newline at end of file
+);
+
+-- Propostas de troca referenciam exemplares específicos
+ALTER TABLE trades ADD COLUMN IF NOT EXISTS partner_id UUID NULL REFERENCES users(id);
+ALTER TABLE trades ADD COLUMN IF NOT EXISTS offered_copy_id UUID NULL
+    REFERENCES books_offered(id) ON DELETE SET NULL;
+ALTER TABLE trades ADD COLUMN IF NOT EXISTS wanted_copy_id UUID NULL
+    REFERENCES books_offered(id) ON DELETE SET NULL;
+
+-- Um exemplar só pode estar prometido em uma troca em andamento por vez
+CREATE TABLE IF NOT EXISTS trade_copy_reservations (
+    copy_id UUID PRIMARY KEY REFERENCES books_offered(id),
+    trade_id UUID NOT NULL REFERENCES trades(id) ON DELETE CASCADE,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Prioridade dos livros desejados (1 = pouco interesse, 5 = muito desejado)
+ALTER TABLE books_wanted ADD COLUMN IF NOT EXISTS priority SMALLINT NOT NULL DEFAULT 3
+    CHECK (priority BETWEEN 1 AND 5);
+
+-- Localização opcional dos usuários, usada para sugerir trocas próximas
+ALTER TABLE users ADD COLUMN IF NOT EXISTS latitude DOUBLE PRECISION NULL
+    CHECK (latitude BETWEEN -90 AND 90);
+ALTER TABLE users ADD COLUMN IF NOT EXISTS longitude DOUBLE PRECISION NULL
+    CHECK (longitude BETWEEN -180 AND 180);
+
+-- Contas suspensas continuam no banco, mas não são aceitas nas rotas autenticadas
+ALTER TABLE users ADD COLUMN IF NOT EXISTS banned_at TIMESTAMP NULL;
+
+-- Cada livro do Google Books é cadastrado uma única vez. Cadastros duplicados
+-- são unificados no mais antigo antes da criação do índice único
+DO $$
+BEGIN
+    IF NOT EXISTS (
+        SELECT 1 FROM pg_indexes
+        WHERE schemaname = current_schema() AND tablename = 'books' AND indexname = 'books_google_id_key'
+    ) THEN
+        CREATE TEMPORARY TABLE duplicated_books ON COMMIT DROP AS
+        SELECT id, keep_id
+        FROM (
+            SELECT id, first_value(id) OVER (PARTITION BY google_id ORDER BY created_at, id) AS keep_id
+            FROM books
+            WHERE google_id IS NOT NULL
+        ) ranked
+        WHERE id <> keep_id;
+
+        -- Quem oferecia um cadastro e desejava outro do mesmo livro deixa de
+        -- desejá-lo, já que um livro não pode estar nas duas listas
+        DELETE FROM books_wanted w
+        USING books_offered o
+        LEFT JOIN duplicated_books od ON od.id = o.book_id
+        WHERE o.user_id = w.user_id
+          AND COALESCE(od.keep_id, o.book_id) = COALESCE(
+              (SELECT wd.keep_id FROM duplicated_books wd WHERE wd.id = w.book_id), w.book_id
+          );
+
+        -- Um usuário que desejava mais de um cadastro do mesmo livro fica com a
+        -- entrada de maior prioridade
+        DELETE FROM books_wanted w
+        USING (
+            SELECT w2.book_id, w2.user_id,
+                   row_number() OVER (
+                       PARTITION BY COALESCE(d.keep_id, w2.book_id), w2.user_id
+                       ORDER BY w2.priority DESC, w2.created_at
+                   ) AS position
+            FROM books_wanted w2
+            LEFT JOIN duplicated_books d ON d.id = w2.book_id
+        ) ranked
+        WHERE w.book_id = ranked.book_id AND w.user_id = ranked.user_id AND ranked.position > 1;
+
+        UPDATE books_wanted w SET book_id = d.keep_id FROM duplicated_books d WHERE w.book_id = d.id;
+        UPDATE books_offered o SET book_id = d.keep_id FROM duplicated_books d WHERE o.book_id = d.id;
+        UPDATE trades t SET book_offered_id = d.keep_id FROM duplicated_books d WHERE t.book_offered_id = d.id;
+        UPDATE trades t SET book_wanted_id = d.keep_id FROM duplicated_books d WHERE t.book_wanted_id = d.id;
+        DELETE FROM books b USING duplicated_books d WHERE b.id = d.id;
+
+        CREATE UNIQUE INDEX books_google_id_key ON books (google_id);
+    END IF;
+END $$;
+
+-- Contadores de tentativas usados na limitação de requisições, quando o
+-- backend configurado é o Postgres (RATE_LIMIT_BACKEND=postgres)
+CREATE TABLE IF NOT EXISTS rate_limits (
+    key VARCHAR(255) PRIMARY KEY,
+    hits INTEGER NOT NULL,
+    window_ends_at TIMESTAMPTZ NOT NULL
+);
+
+-- Contas de provedores externos (Google, GitHub) vinculadas aos usuários
+CREATE TABLE IF NOT EXISTS user_identities (
+    provider VARCHAR(50) NOT NULL,
+    subject VARCHAR(255) NOT NULL,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    email VARCHAR(255) NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (provider, subject)
+);
+
+CREATE INDEX IF NOT EXISTS user_identities_user_id_idx ON user_identities (user_id);
+
+-- Logins externos em andamento: state, nonce e verificador PKCE de cada
+-- autorização, consumidos uma única vez no retorno do provedor
+CREATE TABLE IF NOT EXISTS oauth_login_states (
+    state VARCHAR(255) PRIMARY KEY,
+    provider VARCHAR(50) NOT NULL,
+    code_verifier VARCHAR(255) NOT NULL,
+    nonce VARCHAR(255) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+-- Verificação em duas etapas (TOTP). O segredo é gravado no início da
+-- ativação e só passa a ser exigido no login depois de confirmado
+CREATE TABLE IF NOT EXISTS user_two_factor (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    secret VARCHAR(64) NOT NULL,
+    enabled_at TIMESTAMP,
+    -- Último intervalo de 30 segundos aceito, para que um código não seja usado duas vezes
+    last_used_step BIGINT,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Códigos de recuperação (hash SHA-256), cada um aceito uma única vez
+CREATE TABLE IF NOT EXISTS user_recovery_codes (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    code_hash VARCHAR(64) NOT NULL,
+    used_at TIMESTAMP,
+    UNIQUE (user_id, code_hash)
+);
@@ -1,89 +1,76 @@
 use axum::{
-    http::{
-        header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE},
-        Method,
-    },
-    Extension, Router,
+    extract::DefaultBodyLimit,
+    middleware::{from_fn, from_fn_with_state},
+    Router,
+};
+use sqlx::{
+    postgres::{PgConnectOptions, PgPoolOptions},
+    PgPool,
 };
-use sqlx::{postgres::PgPoolOptions, PgPool};
-use std::sync::Arc;
-use tower_http::cors::{Any, CorsLayer};
-use utoipa::OpenApi;
-use utoipa_swagger_ui::SwaggerUi;
+use std::{str::FromStr, sync::Arc};
+use tower_http::trace::TraceLayer;
 
 use crate::{
-    config::Config,
-    docs::ApiDoc,
-    repositories::user_repository::PgUserRepository,
-    routes::{
-        auth_routes::auth_routes, 
-        book_offered_routes::book_offered_routes,
-        book_routes::book_routes,
-        book_wanted_routes::book_wanted_routes,
-        google_book_routes::google_book_routes,
-        trade_routes::trade_routes,
-    },
-    services::{
-        auth_service::AuthServiceImpl, 
-        password_service::create_password_service,
+    config::DatabaseConfig,
+    docs::{api_doc, docs_routes},
+    middleware::{
+        locale::locale_middleware,
+        metrics::metrics_middleware,
+        request_id::request_id_middleware,
+        request_trace::{record_response, request_span},
+        security::{cors_layer, security_headers_middleware, SecurityHeaders},
     },
+    routes::api_routes,
+    state::AppState,
 };
 
 /// Configura e retorna o pool de conexão com o banco de dados
-pub async fn create_database_pool(database_url: &str) -> Arc<PgPool> {
+pub async fn create_database_pool(database_url: &str, config: &DatabaseConfig) -> Result<Arc<PgPool>, sqlx::Error> {
+    let mut connect_options = PgConnectOptions::from_str(database_url)?;
+    if let Some(timeout) = config.statement_timeout {
+        connect_options = connect_options.options([("statement_timeout", timeout.as_millis())]);
+    }
+
     let pool = PgPoolOptions::new()
-        .max_connections(10)
-        .connect(database_url)
-        .await
-        .expect("Falha ao conectar ao banco de dados");
+        .max_connections(config.max_connections)
+        .acquire_timeout(config.acquire_timeout)
+        .connect_with(connect_options)
+        .await?;
 
-    Arc::new(pool)
+    Ok(Arc::new(pool))
 }
 
-/// Cria e configura o aplicativo Axum com todas as rotas
+/// Cria o aplicativo sobre dependências já montadas
 ///
-/// Esta função recebe a URL do banco de dados e configura o aplicativo
-/// É usada tanto pela aplicação principal quanto pelos testes
-pub async fn create_app(database_url: &str) -> Router {
-    let pool = create_database_pool(database_url).await;
-    let config = Config::from_env().expect("Falha ao carregar configuração");
-
-    // Criar serviço de autenticação compartilhado para todas as rotas protegidas
-    let user_repository = Arc::new(PgUserRepository::new(pool.as_ref().clone()));
-    let password_service = create_password_service();
-
-    let auth_service = Arc::new(AuthServiceImpl::new(
-        user_repository,
-        password_service,
-        config,
-    ));
-
-    // Configurar CORS
-    let cors = CorsLayer::new()
-        .allow_methods([Method::GET, Method::POST, Method::PUT, Method::DELETE])
-        .allow_headers([AUTHORIZATION, ACCEPT, CONTENT_TYPE])
-        .allow_origin(Any);
-
-    // Definir rotas públicas (sem autenticação)
-    let public_routes = Router::new().merge(auth_routes(pool.clone()));
-
-    // Definir rotas protegidas (com autenticação)
-    let protected_routes = Router::new()
-        .merge(google_book_routes())
-        .merge(book_offered_routes(pool.clone()))
-        .merge(book_wanted_routes(pool.clone()))
-        .merge(book_routes(pool.clone()))
-        .merge(trade_routes(pool.clone()))
-        .layer(Extension(auth_service));
+/// Permite que os testes troquem repositórios ou serviços externos sem banco ou rede
+pub fn create_app_with_state(state: AppState) -> Router {
+    // Configurar CORS e headers de segurança
+    let cors = cors_layer(&state.config.cors);
+    let security_headers = SecurityHeaders::from_config(&state.config);
+    let json_body_limit = state.config.json_body_limit_bytes;
 
-    // Inicializar o router básico
-    let mut app = Router::new()
-        .merge(public_routes)
-        .merge(protected_routes)
-        .layer(cors);
+    // O router e a especificação OpenAPI saem da mesma tabela de rotas
+    let (api_router, paths) = api_routes(&state).into_parts();
 
-    let openapi = ApiDoc::openapi();
-    app = app.merge(SwaggerUi::new("/docs").url("/api-docs/openapi.json", openapi));
+    let app = api_router
+        // Aplicado só às rotas registradas, que já conhecem o padrão da rota
+        .route_layer(from_fn(metrics_middleware))
+        // Limite dos corpos JSON; as rotas de fotos e de importação definem o próprio
+        .layer(DefaultBodyLimit::max(json_body_limit))
+        .layer(cors)
+        // Um span por requisição; fica dentro do request_id para já conhecer o identificador
+        .layer(
+            TraceLayer::new_for_http()
+                .make_span_with(request_span)
+                .on_request(())
+                .on_response(record_response)
+                .on_failure(()),
+        )
+        .layer(from_fn(locale_middleware))
+        .layer(from_fn(request_id_middleware))
+        .with_state(state)
+        .merge(docs_routes(api_doc(paths)));
 
-    app
+    // Depois do merge, para valer também para o Swagger UI
+    app.layer(from_fn_with_state(security_headers, security_headers_middleware))
 }
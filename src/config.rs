@@ -1,13 +1,169 @@
 use std::env;
+use std::net::IpAddr;
+use std::time::Duration;
+
+use axum::http::{HeaderName, HeaderValue, Method};
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
 pub struct Config {
     pub database_url: String,
+    /// Segredo dos tokens HS256; não é usado quando há `jwt_keys_dir`
     pub jwt_secret: String,
     pub jwt_expires_in: String,
+    pub jwt_issuer: String,
+    pub jwt_audience: String,
+    /// Diretório com as chaves privadas (`<kid>.pem`) para tokens RS256/EdDSA
+    pub jwt_keys_dir: Option<String>,
+    /// Chave que assina os novos tokens, quando há mais de uma no diretório
+    pub jwt_active_kid: Option<String>,
     pub port: u16,
+    pub photo_storage_dir: String,
+    pub photo_max_bytes: usize,
+    pub rate_limit_backend: RateLimitBackend,
+    /// Proxies reversos cujo `X-Forwarded-For` identifica o cliente nos limites por IP
+    pub trusted_proxies: Vec<IpAddr>,
+    /// Memória usada por cálculo de hash de senha, em KiB
+    pub argon2_memory_kib: u32,
+    pub argon2_iterations: u32,
+    pub argon2_parallelism: u32,
+    /// Quantidade máxima de hashes de senha calculados ao mesmo tempo
+    pub password_hash_concurrency: usize,
+    /// Endereço público da API, usado nas URLs de retorno do login externo
+    pub oauth_redirect_base_url: String,
+    /// Cliente OAuth do Google; sem ele o login com Google fica desativado
+    pub google_oauth: Option<OAuthClientConfig>,
+    /// Cliente OAuth do GitHub; sem ele o login com GitHub fica desativado
+    pub github_oauth: Option<OAuthClientConfig>,
+    /// Se a prontidão (`/ready`) também depende de a API do Google Books responder
+    pub readiness_check_google_books: bool,
+    pub log_format: LogFormat,
+    pub cors: CorsConfig,
+    /// Validade do HSTS, em segundos; 0 desativa o header
+    pub hsts_max_age_secs: u64,
+    /// Tamanho máximo do corpo das requisições JSON, em bytes
+    pub json_body_limit_bytes: usize,
+    pub database: DatabaseConfig,
+    /// Quanto tempo as requisições em andamento têm para terminar no desligamento
+    pub shutdown_timeout: Duration,
+    /// Idade a partir da qual os dados de um livro são atualizados no Google Books
+    pub book_metadata_max_age: Duration,
+}
+
+/// Pool de conexões com o Postgres
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DatabaseConfig {
+    pub max_connections: u32,
+    /// Espera máxima por uma conexão livre do pool
+    pub acquire_timeout: Duration,
+    /// Duração máxima de cada comando no banco; `None` não limita
+    pub statement_timeout: Option<Duration>,
+}
+
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        Self {
+            max_connections: DEFAULT_DATABASE_MAX_CONNECTIONS,
+            acquire_timeout: Duration::from_secs(DEFAULT_DATABASE_ACQUIRE_TIMEOUT_SECS),
+            statement_timeout: None,
+        }
+    }
+}
+
+const DEFAULT_DATABASE_MAX_CONNECTIONS: u32 = 10;
+const DEFAULT_DATABASE_ACQUIRE_TIMEOUT_SECS: u64 = 30;
+const DEFAULT_SHUTDOWN_TIMEOUT_SECS: u64 = 30;
+const DEFAULT_BOOK_METADATA_MAX_AGE_SECS: u64 = 7 * 24 * 60 * 60;
+
+/// Credenciais de um cliente registrado em um provedor OAuth
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OAuthClientConfig {
+    pub client_id: String,
+    pub client_secret: String,
+}
+
+/// Onde são guardados os contadores da limitação de requisições
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RateLimitBackend {
+    /// Memória do processo, suficiente para uma única instância
+    #[default]
+    Memory,
+    /// Tabela rate_limits, compartilhada entre instâncias
+    Postgres,
+}
+
+/// Regras de CORS para os navegadores que chamam a API
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CorsConfig {
+    /// Origens aceitas; `None` aceita qualquer origem
+    pub allowed_origins: Option<Vec<HeaderValue>>,
+    pub allowed_methods: Vec<Method>,
+    pub allowed_headers: Vec<HeaderName>,
+    /// Se o navegador pode enviar cookies e credenciais; exige origens explícitas
+    pub allow_credentials: bool,
+    /// Por quanto tempo o navegador pode reaproveitar a resposta do preflight
+    pub max_age: Duration,
+}
+
+impl Default for CorsConfig {
+    fn default() -> Self {
+        Self {
+            allowed_origins: None,
+            allowed_methods: DEFAULT_CORS_METHODS.iter().map(|m| m.parse().unwrap()).collect(),
+            allowed_headers: DEFAULT_CORS_HEADERS.iter().map(|h| HeaderName::from_static(h)).collect(),
+            allow_credentials: false,
+            max_age: Duration::from_secs(DEFAULT_CORS_MAX_AGE_SECS),
+        }
+    }
+}
+
+impl CorsConfig {
+    /// Recusa curingas junto com credenciais: navegadores ignoram a combinação e
+    /// o tower-http entra em pânico ao montar a camada
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if !self.allow_credentials {
+            return Ok(());
+        }
+
+        if self.allowed_origins.is_none() {
+            return Err(ConfigError::ParseError(
+                "CORS_ALLOW_CREDENTIALS exige CORS_ALLOWED_ORIGINS com origens explícitas".to_string(),
+            ));
+        }
+        if self.allowed_methods.iter().any(|method| method.as_str() == "*") {
+            return Err(ConfigError::ParseError(
+                "CORS_ALLOW_CREDENTIALS exige CORS_ALLOWED_METHODS com métodos explícitos".to_string(),
+            ));
+        }
+        if self.allowed_headers.iter().any(|header| header.as_str() == "*") {
+            return Err(ConfigError::ParseError(
+                "CORS_ALLOW_CREDENTIALS exige CORS_ALLOWED_HEADERS com headers explícitos".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+const DEFAULT_CORS_METHODS: &[&str] = &["GET", "POST", "PUT", "PATCH", "DELETE", "OPTIONS"];
+const DEFAULT_CORS_HEADERS: &[&str] = &["authorization", "accept", "accept-language", "content-type", "x-request-id"];
+const DEFAULT_CORS_MAX_AGE_SECS: u64 = 600;
+
+/// Um ano, o valor recomendado para o HSTS
+const DEFAULT_HSTS_MAX_AGE_SECS: u64 = 31_536_000;
+
+/// Limite padrão do corpo das requisições JSON (256 KiB)
+const DEFAULT_JSON_BODY_LIMIT_BYTES: usize = 256 * 1024;
+
+/// Formato das linhas de log
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogFormat {
+    /// Texto legível, para desenvolvimento
+    #[default]
+    Text,
+    /// Um objeto JSON por linha, para coletores de log
+    Json,
 }
 
 #[derive(Error, Debug)]
@@ -24,21 +180,216 @@ impl Config {
         let database_url = env::var("DATABASE_URL")
             .map_err(|_| ConfigError::NotFound("DATABASE_URL".to_string()))?;
 
-        let jwt_secret =
-            env::var("JWT_SECRET").map_err(|_| ConfigError::NotFound("JWT_SECRET".to_string()))?;
+        // Com chaves assimétricas o segredo compartilhado não é necessário
+        let jwt_keys_dir = env::var("JWT_KEYS_DIR").ok().filter(|dir| !dir.trim().is_empty());
+        let jwt_active_kid = env::var("JWT_ACTIVE_KID").ok().filter(|kid| !kid.trim().is_empty());
+
+        let jwt_secret = match env::var("JWT_SECRET") {
+            Ok(secret) => secret,
+            Err(_) if jwt_keys_dir.is_some() => String::new(),
+            Err(_) => return Err(ConfigError::NotFound("JWT_SECRET".to_string())),
+        };
 
         let jwt_expires_in = env::var("JWT_EXPIRES_IN").unwrap_or_else(|_| "24h".to_string());
+        let jwt_issuer = env::var("JWT_ISSUER").unwrap_or_else(|_| "troca-livros-api".to_string());
+        let jwt_audience = env::var("JWT_AUDIENCE").unwrap_or_else(|_| "troca-livros".to_string());
 
         let port = env::var("PORT")
             .unwrap_or_else(|_| "50001".to_string())
             .parse::<u16>()
             .map_err(|_| ConfigError::ParseError("PORT".to_string()))?;
 
+        let photo_storage_dir =
+            env::var("PHOTO_STORAGE_DIR").unwrap_or_else(|_| "uploads".to_string());
+
+        let photo_max_bytes = env::var("PHOTO_MAX_BYTES")
+            .unwrap_or_else(|_| (5 * 1024 * 1024).to_string())
+            .parse::<usize>()
+            .map_err(|_| ConfigError::ParseError("PHOTO_MAX_BYTES".to_string()))?;
+
+        let rate_limit_backend = match env::var("RATE_LIMIT_BACKEND").as_deref() {
+            Err(_) | Ok("memory") => RateLimitBackend::Memory,
+            Ok("postgres") => RateLimitBackend::Postgres,
+            Ok(_) => return Err(ConfigError::ParseError("RATE_LIMIT_BACKEND".to_string())),
+        };
+
+        let argon2_memory_kib = parse_env("ARGON2_MEMORY_KIB", argon2::Params::DEFAULT_M_COST)?;
+        let argon2_iterations = parse_env("ARGON2_ITERATIONS", argon2::Params::DEFAULT_T_COST)?;
+        let argon2_parallelism = parse_env("ARGON2_PARALLELISM", argon2::Params::DEFAULT_P_COST)?;
+        argon2::Params::new(argon2_memory_kib, argon2_iterations, argon2_parallelism, None)
+            .map_err(|e| ConfigError::ParseError(format!("ARGON2_*: {}", e)))?;
+
+        let default_concurrency = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+        let password_hash_concurrency = parse_env("PASSWORD_HASH_CONCURRENCY", default_concurrency)?;
+        if password_hash_concurrency == 0 {
+            return Err(ConfigError::ParseError("PASSWORD_HASH_CONCURRENCY".to_string()));
+        }
+
+        let oauth_redirect_base_url = env::var("OAUTH_REDIRECT_BASE_URL")
+            .unwrap_or_else(|_| format!("http://localhost:{}", port))
+            .trim_end_matches('/')
+            .to_string();
+        let google_oauth = oauth_client_from_env("GOOGLE")?;
+        let github_oauth = oauth_client_from_env("GITHUB")?;
+        let readiness_check_google_books = parse_env("READINESS_CHECK_GOOGLE_BOOKS", false)?;
+
+        let log_format = match env::var("LOG_FORMAT").as_deref() {
+            Err(_) | Ok("text") => LogFormat::Text,
+            Ok("json") => LogFormat::Json,
+            Ok(_) => return Err(ConfigError::ParseError("LOG_FORMAT".to_string())),
+        };
+
+        let trusted_proxies = list_env("TRUSTED_PROXIES")
+            .unwrap_or_default()
+            .iter()
+            .map(|proxy| proxy.parse())
+            .collect::<Result<_, _>>()
+            .map_err(|_| ConfigError::ParseError("TRUSTED_PROXIES".to_string()))?;
+
+        let cors = cors_from_env()?;
+        let hsts_max_age_secs = parse_env("HSTS_MAX_AGE_SECS", DEFAULT_HSTS_MAX_AGE_SECS)?;
+        let json_body_limit_bytes = parse_env("JSON_BODY_LIMIT_BYTES", DEFAULT_JSON_BODY_LIMIT_BYTES)?;
+        let database = database_from_env()?;
+        let shutdown_timeout =
+            Duration::from_secs(parse_env("SHUTDOWN_TIMEOUT_SECS", DEFAULT_SHUTDOWN_TIMEOUT_SECS)?);
+        let book_metadata_max_age = Duration::from_secs(parse_env(
+            "BOOK_METADATA_MAX_AGE_SECS",
+            DEFAULT_BOOK_METADATA_MAX_AGE_SECS,
+        )?);
+
         Ok(Self {
             database_url,
             jwt_secret,
             jwt_expires_in,
+            jwt_issuer,
+            jwt_audience,
+            jwt_keys_dir,
+            jwt_active_kid,
             port,
+            photo_storage_dir,
+            photo_max_bytes,
+            rate_limit_backend,
+            trusted_proxies,
+            argon2_memory_kib,
+            argon2_iterations,
+            argon2_parallelism,
+            password_hash_concurrency,
+            oauth_redirect_base_url,
+            google_oauth,
+            github_oauth,
+            readiness_check_google_books,
+            log_format,
+            cors,
+            hsts_max_age_secs,
+            json_body_limit_bytes,
+            database,
+            shutdown_timeout,
+            book_metadata_max_age,
         })
     }
 }
+
+// Lê uma variável numérica, usando o valor padrão quando ela não está definida
+fn parse_env<T: std::str::FromStr>(name: &str, default: T) -> Result<T, ConfigError> {
+    match env::var(name) {
+        Ok(value) => value
+            .trim()
+            .parse()
+            .map_err(|_| ConfigError::ParseError(name.to_string())),
+        Err(_) => Ok(default),
+    }
+}
+
+// Lê <PREFIXO>_CLIENT_ID e <PREFIXO>_CLIENT_SECRET; o provedor só é ativado
+// quando o ID está definido, e nesse caso o segredo é obrigatório
+fn oauth_client_from_env(prefix: &str) -> Result<Option<OAuthClientConfig>, ConfigError> {
+    let Some(client_id) = env::var(format!("{}_CLIENT_ID", prefix)).ok().filter(|id| !id.trim().is_empty()) else {
+        return Ok(None);
+    };
+
+    let secret_name = format!("{}_CLIENT_SECRET", prefix);
+    let client_secret = env::var(&secret_name).map_err(|_| ConfigError::NotFound(secret_name))?;
+
+    Ok(Some(OAuthClientConfig { client_id, client_secret }))
+}
+
+// Lê as variáveis CORS_*; listas são separadas por vírgula, e a origem "*" (ou
+// a ausência de CORS_ALLOWED_ORIGINS) aceita qualquer origem
+fn cors_from_env() -> Result<CorsConfig, ConfigError> {
+    let defaults = CorsConfig::default();
+
+    let allowed_origins = match list_env("CORS_ALLOWED_ORIGINS") {
+        None => None,
+        Some(origins) if origins.iter().any(|origin| origin == "*") => None,
+        Some(origins) => Some(
+            origins
+                .iter()
+                .map(|origin| HeaderValue::from_str(origin.trim_end_matches('/')))
+                .collect::<Result<_, _>>()
+                .map_err(|_| ConfigError::ParseError("CORS_ALLOWED_ORIGINS".to_string()))?,
+        ),
+    };
+
+    let allowed_methods = match list_env("CORS_ALLOWED_METHODS") {
+        None => defaults.allowed_methods,
+        Some(methods) => methods
+            .iter()
+            .map(|method| Method::from_bytes(method.to_uppercase().as_bytes()))
+            .collect::<Result<_, _>>()
+            .map_err(|_| ConfigError::ParseError("CORS_ALLOWED_METHODS".to_string()))?,
+    };
+
+    let allowed_headers = match list_env("CORS_ALLOWED_HEADERS") {
+        None => defaults.allowed_headers,
+        Some(headers) => headers
+            .iter()
+            .map(|header| HeaderName::from_bytes(header.to_lowercase().as_bytes()))
+            .collect::<Result<_, _>>()
+            .map_err(|_| ConfigError::ParseError("CORS_ALLOWED_HEADERS".to_string()))?,
+    };
+
+    let allow_credentials = parse_env("CORS_ALLOW_CREDENTIALS", false)?;
+    let max_age = Duration::from_secs(parse_env("CORS_MAX_AGE_SECS", DEFAULT_CORS_MAX_AGE_SECS)?);
+
+    let cors = CorsConfig {
+        allowed_origins,
+        allowed_methods,
+        allowed_headers,
+        allow_credentials,
+        max_age,
+    };
+    cors.validate()?;
+
+    Ok(cors)
+}
+
+// Lê as variáveis DATABASE_* do pool; um statement timeout 0 não limita os comandos
+fn database_from_env() -> Result<DatabaseConfig, ConfigError> {
+    let max_connections = parse_env("DATABASE_MAX_CONNECTIONS", DEFAULT_DATABASE_MAX_CONNECTIONS)?;
+    if max_connections == 0 {
+        return Err(ConfigError::ParseError("DATABASE_MAX_CONNECTIONS".to_string()));
+    }
+
+    let acquire_timeout =
+        Duration::from_secs(parse_env("DATABASE_ACQUIRE_TIMEOUT_SECS", DEFAULT_DATABASE_ACQUIRE_TIMEOUT_SECS)?);
+    let statement_timeout_ms: u64 = parse_env("DATABASE_STATEMENT_TIMEOUT_MS", 0)?;
+    let statement_timeout = (statement_timeout_ms > 0).then(|| Duration::from_millis(statement_timeout_ms));
+
+    Ok(DatabaseConfig {
+        max_connections,
+        acquire_timeout,
+        statement_timeout,
+    })
+}
+
+// Lê uma lista separada por vírgulas; variáveis ausentes ou vazias viram `None`
+fn list_env(name: &str) -> Option<Vec<String>> {
+    let items: Vec<String> = env::var(name)
+        .ok()?
+        .split(',')
+        .map(|item| item.trim().to_string())
+        .filter(|item| !item.is_empty())
+        .collect();
+
+    (!items.is_empty()).then_some(items)
+}
@@ -0,0 +1,67 @@
+#[cfg(test)]
+mod tests {
+    use axum::http::{HeaderName, HeaderValue, Method};
+
+    use crate::config::{ConfigError, CorsConfig};
+
+    fn with_credentials() -> CorsConfig {
+        CorsConfig {
+            allowed_origins: Some(vec![HeaderValue::from_static("https://app.example.com")]),
+            allow_credentials: true,
+            ..CorsConfig::default()
+        }
+    }
+
+    fn expect_rejected(cors: CorsConfig, variable: &str) {
+        match cors.validate() {
+            Err(ConfigError::ParseError(message)) => assert!(message.contains(variable), "{}", message),
+            other => panic!("Esperava erro em {}, obteve {:?}", variable, other),
+        }
+    }
+
+    #[test]
+    fn test_credentials_with_explicit_rules_are_accepted() {
+        assert!(with_credentials().validate().is_ok());
+    }
+
+    #[test]
+    fn test_wildcards_without_credentials_are_accepted() {
+        let cors = CorsConfig {
+            allowed_methods: vec![Method::from_bytes(b"*").unwrap()],
+            allowed_headers: vec![HeaderName::from_static("*")],
+            ..CorsConfig::default()
+        };
+
+        assert!(cors.validate().is_ok());
+    }
+
+    #[test]
+    fn test_credentials_reject_any_origin() {
+        let cors = CorsConfig {
+            allowed_origins: None,
+            ..with_credentials()
+        };
+
+        expect_rejected(cors, "CORS_ALLOWED_ORIGINS");
+    }
+
+    #[test]
+    fn test_credentials_reject_wildcard_methods() {
+        let cors = CorsConfig {
+            allowed_methods: vec![Method::GET, Method::from_bytes(b"*").unwrap()],
+            ..with_credentials()
+        };
+
+        expect_rejected(cors, "CORS_ALLOWED_METHODS");
+    }
+
+    #[test]
+    fn test_credentials_reject_wildcard_headers() {
+        let cors = CorsConfig {
+            allowed_headers: vec![HeaderName::from_static("*")],
+            ..with_credentials()
+        };
+
+        expect_rejected(cors, "CORS_ALLOWED_HEADERS");
+    }
+}
@@ -1,21 +1,37 @@
-pub mod auth_docs;
-pub mod book_docs;
-pub mod book_offered_docs;
-pub mod google_book_docs;
-pub mod book_wanted_docs;
-pub mod trade_docs;
-
-use crate::handlers::book_offered_handler::AddBookRequest;
-use crate::models::book::{BookOffered, BookWanted, BookSearchRequest, GoogleBookDto};
-use crate::models::user::{CreateUserDto, LoginUserDto, TokenResponse, UserResponse};
-use crate::models::trade::PossibleTrade;
-use crate::error::AppError;
-use crate::docs::book_docs::UserBooksResponse;
-use crate::docs::book_offered_docs::{BookOfferedResponse, SuccessMessage as OfferedSuccessMessage};
-use crate::docs::book_wanted_docs::{BookWantedResponse, SuccessMessage as WantedSuccessMessage};
-use crate::services::book_service::UserBooks;
+use crate::handlers::book_offered_handler::{AddBookRequest, BatchAddOfferedRequest};
+use crate::handlers::book_wanted_handler::{BatchAddWantedRequest, UpdatePriorityRequest};
+use crate::models::book::{
+    BatchItemResult, BatchItemStatus, BatchRemoveRequest, BookCondition, BookDetails, BookOffer, BookOffered, BookPhoto, BookWanted, BookSearchRequest, GoogleBookDto,
+    OfferedBookItem, OfferedCopy, OfferedCopyAttributes, WantedBookItem,
+};
+use crate::models::shelf::{ImportReport, ImportRowResult, ImportStatus, ShelfEntry, ShelfList};
+use crate::models::health::{CheckStatus, ReadinessReport};
+use crate::models::jwk::{Jwk, JwkSet};
+use crate::models::response::{
+    AccessTokenResponse, BatchResultsResponse, BookDetailsResponse, BookOfferedCopiesResponse, BookOfferedResponse, BookOffersResponse,
+    BookPhotoResponse, BookWantedResponse, GoogleBooksResponse, ImportReportResponse, MessageResponse,
+    RecoveryCodesResponse, RegisteredUserResponse, TradeResponse, TradesResponse, TwoFactorChallengeResponse,
+    TwoFactorSetupResponse, UserBooksResponse, UserLocationResponse,
+};
+use crate::models::two_factor::{
+    DisableTwoFactorDto, EnableTwoFactorDto, RecoveryCodes, TwoFactorSetup, VerifyTwoFactorDto,
+};
+use crate::models::user::{CreateUserDto, LoginUserDto, TokenResponse, TwoFactorChallenge, UserLocation, UserResponse};
+use crate::models::trade::{PossibleTrade, ProposeTradeRequest, Trade, TradeStatus};
+use crate::error::{ErrorBody, ErrorCode, ErrorResponse, FieldError};
+use crate::services::book_service::{OfferedBookWithId, UserBooks};
+use axum::{http::header::CONTENT_TYPE, routing::get, Router};
+use utoipa::openapi::path::Paths;
 use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
 use utoipa::{Modify, OpenApi};
+use utoipa_swagger_ui::SwaggerUi;
+
+use crate::middleware::security::DOCS_PATH;
+
+/// Especificação OpenAPI em JSON
+pub const OPENAPI_JSON_PATH: &str = "/api-docs/openapi.json";
+/// A mesma especificação em YAML
+pub const OPENAPI_YAML_PATH: &str = "/api-docs/openapi.yaml";
 
 // Define um modificador para adicionar esquema de segurança
 struct SecurityAddon;
@@ -43,46 +59,88 @@ impl Modify for SecurityAddon {
 
 #[derive(OpenApi)]
 #[openapi(
-    paths(
-        crate::docs::auth_docs::register,
-        crate::docs::auth_docs::login,
-        crate::docs::book_docs::get_user_books,
-        crate::docs::google_book_docs::search_books,
-        crate::docs::book_offered_docs::add_book_to_offered,
-        crate::docs::book_offered_docs::remove_book_from_offered,
-        crate::docs::book_wanted_docs::add_book_to_wanted,
-        crate::docs::book_wanted_docs::remove_book_from_wanted,
-        crate::docs::trade_docs::get_possible_trades,
-    ),
     components(
         schemas(
             CreateUserDto, 
             LoginUserDto, 
             TokenResponse, 
+            AccessTokenResponse,
+            TwoFactorChallenge,
+            TwoFactorChallengeResponse,
+            TwoFactorSetup,
+            TwoFactorSetupResponse,
+            RecoveryCodes,
+            RecoveryCodesResponse,
+            EnableTwoFactorDto,
+            DisableTwoFactorDto,
+            VerifyTwoFactorDto,
+            Jwk,
+            JwkSet,
+            CheckStatus,
+            ReadinessReport,
             UserResponse, 
+            RegisteredUserResponse,
             BookSearchRequest, 
             GoogleBookDto, 
+            GoogleBooksResponse,
             BookOffered, 
             BookWanted,
             AddBookRequest,
+            UpdatePriorityRequest,
+            OfferedBookItem,
+            WantedBookItem,
+            BatchAddOfferedRequest,
+            BatchAddWantedRequest,
+            BatchRemoveRequest,
+            BatchItemStatus,
+            BatchItemResult,
+            BatchResultsResponse,
             BookOfferedResponse,
+            BookOfferedCopiesResponse,
             BookWantedResponse,
             UserBooksResponse,
             UserBooks,
-            OfferedSuccessMessage,
-            WantedSuccessMessage,
+            OfferedBookWithId,
+            BookCondition,
+            OfferedCopyAttributes,
+            OfferedCopy,
+            BookPhoto,
+            BookPhotoResponse,
+            BookDetails,
+            BookDetailsResponse,
+            BookOffer,
+            BookOffersResponse,
+            ShelfEntry,
+            ShelfList,
+            ImportStatus,
+            ImportRowResult,
+            ImportReport,
+            ImportReportResponse,
+            MessageResponse,
             PossibleTrade,
-            AppError
+            Trade,
+            TradeStatus,
+            ProposeTradeRequest,
+            TradeResponse,
+            TradesResponse,
+            UserLocation,
+            UserLocationResponse,
+            ErrorResponse,
+            ErrorBody,
+            ErrorCode,
+            FieldError
         )
     ),
     modifiers(&SecurityAddon),
     tags(
         (name = "auth", description = "API de autenticação"),
+        (name = "health", description = "Verificações de saúde e métricas"),
         (name = "books", description = "API de livros do usuário"),
         (name = "google_books", description = "API de livros do Google"),
         (name = "books_offered", description = "API de livros possuídos"),
         (name = "books_wanted", description = "API de livros desejados"),
-        (name = "trades", description = "API de trocas de livros")
+        (name = "trades", description = "API de trocas de livros"),
+        (name = "users", description = "API de dados do usuário")
     ),
     info(
         title = "API Troca Livros",
@@ -90,9 +148,41 @@ impl Modify for SecurityAddon {
         description = "API para o sistema de troca de livros.\n\n\
                       **Autenticação**:\n\
                       A maioria dos endpoints requer autenticação usando Bearer Token.\n\
-                      Para obter um token, faça login usando o endpoint `/api/auth/login`.\n\
+                      Para obter um token, faça login usando o endpoint `/api/v1/auth/login`.\n\
                       Em seguida, inclua o token em todas as requisições no cabeçalho:\n\
-                      `Authorization: Bearer <seu-token>`",
+                      `Authorization: Bearer <seu-token>`\n\
+                      Tokens de contas removidas retornam 401 e os de contas suspensas, 403.\n\n\
+                      **Corpo das requisições**:\n\
+                      JSON malformado retorna 400 (`MALFORMED_BODY`); campos ausentes ou de tipo\n\
+                      errado retornam 422 (`INVALID_BODY`); regras de validação violadas retornam\n\
+                      400 (`VALIDATION_FAILED`), com os erros de cada campo em `details`.\n\n\
+                      **Idioma**:\n\
+                      As mensagens são retornadas em pt-BR ou em inglês, conforme o cabeçalho\n\
+                      `Accept-Language` (por exemplo, `Accept-Language: en`). Sem o cabeçalho,\n\
+                      ou com um idioma não suportado, as mensagens são retornadas em pt-BR.",
     ),
 )]
 pub struct ApiDoc;
+
+/// Especificação completa: os caminhos vêm das rotas registradas no router
+///
+/// Veja `routes::api_routes`.
+pub fn api_doc(paths: Paths) -> utoipa::openapi::OpenApi {
+    let mut openapi = ApiDoc::openapi();
+    openapi.paths = paths;
+    openapi
+}
+
+/// Swagger UI e a especificação gerada a partir das anotações dos handlers
+pub fn docs_routes(openapi: utoipa::openapi::OpenApi) -> Router {
+    let yaml = openapi
+        .to_yaml()
+        .expect("a especificação OpenAPI deve ser serializável em YAML");
+
+    Router::new()
+        .merge(SwaggerUi::new(DOCS_PATH).url(OPENAPI_JSON_PATH, openapi))
+        .route(
+            OPENAPI_YAML_PATH,
+            get(move || async move { ([(CONTENT_TYPE, "application/yaml")], yaml) }),
+        )
+}
@@ -1,26 +0,0 @@
-#[allow(unused_imports)]
-use crate::error::AppError;
-#[allow(unused_imports)]
-use crate::models::trade::PossibleTrade;
-
-/// Buscar trocas possíveis para o usuário autenticado
-/// 
-/// Retorna uma lista de trocas possíveis onde:
-/// - O usuário oferece um livro que outro usuário quer
-/// - O outro usuário oferece um livro que o usuário quer
-/// 
-/// O usuário é identificado automaticamente através do token JWT.
-#[utoipa::path(
-    get,
-    path = "/api/trades/possible",
-    tag = "trades",
-    responses(
-        (status = 200, description = "Lista de trocas possíveis encontradas", body = [PossibleTrade]),
-        (status = 401, description = "Não autorizado - Token inválido ou ausente", body = AppError),
-        (status = 500, description = "Erro interno do servidor", body = AppError),
-    ),
-    security(
-        ("bearerAuth" = [])
-    )
-)]
-pub fn get_possible_trades() {} 
\ No newline at end of file
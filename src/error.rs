@@ -1,21 +1,104 @@
+use std::error::Error as _;
+
 use axum::{
-    http::StatusCode,
+    extract::rejection::JsonRejection,
+    http::{header::RETRY_AFTER, HeaderValue, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
 use serde::{Serialize, Deserialize};
-use serde_json::json;
 use thiserror::Error;
 use utoipa::ToSchema;
+use validator::{ValidationErrors, ValidationErrorsKind};
+
+use crate::i18n::messages;
+use crate::middleware::request_id::current_request_id;
+
+/// Código estável de um erro, usado pelos clientes para decidir o que exibir
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    ValidationFailed,
+    MalformedBody,
+    InvalidBody,
+    BodyTooLarge,
+    Unauthorized,
+    InvalidCredentials,
+    Forbidden,
+    NotFound,
+    BookAlreadyOffered,
+    BookAlreadyWanted,
+    EmailAlreadyInUse,
+    CopyReserved,
+    TradeStatusConflict,
+    TwoFactorAlreadyEnabled,
+    BookNotOffered,
+    BookNotWanted,
+    CopyNotOffered,
+    CopyNotAvailable,
+    TradeWithSelf,
+    PhotoLimitReached,
+    TwoFactorNotStarted,
+    TwoFactorNotEnabled,
+    TwoFactorCodeInvalid,
+    RateLimited,
+    UpstreamUnavailable,
+    DatabaseError,
+    InternalError,
+}
+
+/// Erro de validação de um campo da requisição
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct FieldError {
+    /// Caminho do campo (por exemplo, "email" ou "items[0].priority")
+    pub field: String,
+    /// Código da regra violada (por exemplo, "name_required")
+    pub code: String,
+    pub message: String,
+}
 
 #[derive(Error, Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub enum AppError {
     #[error("Erro de autenticação: {0}")]
     AuthError(String),
 
+    #[error("Credenciais inválidas")]
+    InvalidCredentials,
+
     #[error("Erro de validação: {0}")]
     ValidationError(String),
 
+    #[error("Erro de validação: {}", join_messages(.0))]
+    InvalidFields(Vec<FieldError>),
+
+    /// Pedido bem formado, mas recusado por uma regra do domínio, identificada pelo código
+    #[error("Operação recusada: {1}")]
+    Rejected(ErrorCode, String),
+
+    /// Corpo que não pôde ser lido como JSON
+    #[error("Corpo da requisição inválido: {0}")]
+    MalformedBody(String),
+
+    /// JSON válido, mas com campos ausentes ou de tipo diferente do esperado
+    #[error("Corpo da requisição inválido: {0}")]
+    UnprocessableBody(String),
+
+    #[error("Corpo da requisição muito grande: {0}")]
+    PayloadTooLarge(String),
+
+    #[error("Acesso negado: {0}")]
+    Forbidden(String),
+
+    #[error("Conflito: {1}")]
+    Conflict(ErrorCode, String),
+
+    /// Limite de tentativas atingido; o segundo campo é o tempo de espera, em segundos
+    #[error("Muitas requisições: {0}")]
+    TooManyRequests(String, u64),
+
+    #[error("Serviço externo indisponível: {0}")]
+    BadGateway(String),
+
     #[error("Erro de banco de dados: {0}")]
     DatabaseError(String),
 
@@ -26,24 +109,115 @@ pub enum AppError {
     InternalServerError(String),
 }
 
+/// Corpo das respostas de erro
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ErrorResponse {
+    pub error: ErrorBody,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ErrorBody {
+    pub code: ErrorCode,
+    pub message: String,
+    pub status: u16,
+    /// Identificador da requisição, também enviado no header x-request-id
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    /// Erros de cada campo, presentes apenas em erros de validação
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub details: Vec<FieldError>,
+}
+
+impl AppError {
+    pub fn status(&self) -> StatusCode {
+        match self {
+            AppError::AuthError(_) | AppError::InvalidCredentials => StatusCode::UNAUTHORIZED,
+            AppError::ValidationError(_)
+            | AppError::InvalidFields(_)
+            | AppError::Rejected(_, _)
+            | AppError::MalformedBody(_) => StatusCode::BAD_REQUEST,
+            AppError::UnprocessableBody(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            AppError::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            AppError::Forbidden(_) => StatusCode::FORBIDDEN,
+            AppError::Conflict(_, _) => StatusCode::CONFLICT,
+            AppError::TooManyRequests(_, _) => StatusCode::TOO_MANY_REQUESTS,
+            AppError::BadGateway(_) => StatusCode::BAD_GATEWAY,
+            AppError::NotFoundError(_) => StatusCode::NOT_FOUND,
+            AppError::DatabaseError(_) | AppError::InternalServerError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            AppError::AuthError(_) => ErrorCode::Unauthorized,
+            AppError::InvalidCredentials => ErrorCode::InvalidCredentials,
+            AppError::ValidationError(_) | AppError::InvalidFields(_) => ErrorCode::ValidationFailed,
+            AppError::MalformedBody(_) => ErrorCode::MalformedBody,
+            AppError::UnprocessableBody(_) => ErrorCode::InvalidBody,
+            AppError::PayloadTooLarge(_) => ErrorCode::BodyTooLarge,
+            AppError::Forbidden(_) => ErrorCode::Forbidden,
+            AppError::Rejected(code, _) | AppError::Conflict(code, _) => *code,
+            AppError::TooManyRequests(_, _) => ErrorCode::RateLimited,
+            AppError::BadGateway(_) => ErrorCode::UpstreamUnavailable,
+            AppError::NotFoundError(_) => ErrorCode::NotFound,
+            AppError::DatabaseError(_) => ErrorCode::DatabaseError,
+            AppError::InternalServerError(_) => ErrorCode::InternalError,
+        }
+    }
+
+    /// Mensagem que pode ser exibida ao cliente, no idioma da requisição
+    ///
+    /// Os detalhes de erros de banco e erros internos ficam apenas no log.
+    pub fn public_message(&self) -> String {
+        match self {
+            AppError::AuthError(message)
+            | AppError::ValidationError(message)
+            | AppError::MalformedBody(message)
+            | AppError::UnprocessableBody(message)
+            | AppError::PayloadTooLarge(message)
+            | AppError::Forbidden(message)
+            | AppError::Rejected(_, message)
+            | AppError::Conflict(_, message)
+            | AppError::TooManyRequests(message, _)
+            | AppError::BadGateway(message)
+            | AppError::NotFoundError(message) => message.clone(),
+            AppError::InvalidCredentials => messages::INVALID_CREDENTIALS.text(),
+            AppError::InvalidFields(fields) => join_messages(fields),
+            AppError::DatabaseError(_) | AppError::InternalServerError(_) => messages::INTERNAL_ERROR.text(),
+        }
+    }
+}
+
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
-        let (status, error_message) = match self {
-            AppError::AuthError(message) => (StatusCode::UNAUTHORIZED, message),
-            AppError::ValidationError(message) => (StatusCode::BAD_REQUEST, message),
-            AppError::DatabaseError(message) => (StatusCode::INTERNAL_SERVER_ERROR, message),
-            AppError::NotFoundError(message) => (StatusCode::NOT_FOUND, message),
-            AppError::InternalServerError(message) => (StatusCode::INTERNAL_SERVER_ERROR, message),
+        let status = self.status();
+        let request_id = current_request_id();
+
+        if status.is_server_error() {
+            tracing::error!(request_id = request_id.as_deref().unwrap_or("-"), "{}", self);
+        }
+
+        let details = match &self {
+            AppError::InvalidFields(fields) => fields.clone(),
+            _ => Vec::new(),
         };
 
-        let body = Json(json!({
-            "error": {
-                "message": error_message,
-                "status": status.as_u16()
-            }
-        }));
+        let body = Json(ErrorResponse {
+            error: ErrorBody {
+                code: self.code(),
+                message: self.public_message(),
+                status: status.as_u16(),
+                request_id,
+                details,
+            },
+        });
 
-        (status, body).into_response()
+        let mut response = (status, body).into_response();
+        if let AppError::TooManyRequests(_, retry_after) = self {
+            response.headers_mut().insert(RETRY_AFTER, HeaderValue::from(retry_after));
+        }
+
+        response
     }
 }
 
@@ -51,9 +225,73 @@ impl From<sqlx::Error> for AppError {
     fn from(error: sqlx::Error) -> Self {
         match error {
             sqlx::Error::RowNotFound => {
-                AppError::NotFoundError("Registro não encontrado".to_string())
+                AppError::NotFoundError(messages::RECORD_NOT_FOUND.text())
             }
             _ => AppError::DatabaseError(error.to_string()),
         }
     }
 }
+
+impl From<JsonRejection> for AppError {
+    fn from(rejection: JsonRejection) -> Self {
+        // O detalhe do serde aponta o campo e a posição do problema no corpo
+        let detail = rejection
+            .source()
+            .and_then(|inner| inner.source())
+            .map(|cause| cause.to_string())
+            .unwrap_or_else(|| rejection.body_text());
+
+        match rejection {
+            JsonRejection::JsonDataError(_) => AppError::UnprocessableBody(messages::BODY_INVALID.format(&[&detail])),
+            JsonRejection::JsonSyntaxError(_) => AppError::MalformedBody(messages::BODY_MALFORMED.format(&[&detail])),
+            JsonRejection::MissingJsonContentType(_) => AppError::MalformedBody(messages::BODY_CONTENT_TYPE.text()),
+            JsonRejection::BytesRejection(rejection) if rejection.status() == StatusCode::PAYLOAD_TOO_LARGE => {
+                AppError::PayloadTooLarge(messages::BODY_TOO_LARGE.text())
+            }
+            _ => AppError::MalformedBody(messages::BODY_UNREADABLE.text()),
+        }
+    }
+}
+
+impl From<ValidationErrors> for AppError {
+    fn from(errors: ValidationErrors) -> Self {
+        let mut fields = Vec::new();
+        collect_field_errors(&errors, "", &mut fields);
+        // A ordem dos campos no ValidationErrors não é estável
+        fields.sort_by(|a, b| a.field.cmp(&b.field));
+        AppError::InvalidFields(fields)
+    }
+}
+
+// Achata os erros de validação, incluindo os de structs e listas aninhadas; a
+// mensagem de cada erro vem do catálogo, pelo código da regra
+fn collect_field_errors(errors: &ValidationErrors, prefix: &str, fields: &mut Vec<FieldError>) {
+    for (field, kind) in errors.errors() {
+        let path = if prefix.is_empty() { field.to_string() } else { format!("{}.{}", prefix, field) };
+        match kind {
+            ValidationErrorsKind::Field(field_errors) => {
+                fields.extend(field_errors.iter().map(|error| FieldError {
+                    field: path.clone(),
+                    code: error.code.to_string(),
+                    message: messages::find(&error.code)
+                        .map(|message| message.text())
+                        .unwrap_or_else(|| messages::FIELD_INVALID.format(&[&path])),
+                }));
+            }
+            ValidationErrorsKind::Struct(nested) => collect_field_errors(nested, &path, fields),
+            ValidationErrorsKind::List(items) => {
+                for (index, nested) in items {
+                    collect_field_errors(nested, &format!("{}[{}]", path, index), fields);
+                }
+            }
+        }
+    }
+}
+
+fn join_messages(fields: &[FieldError]) -> String {
+    fields
+        .iter()
+        .map(|field| field.message.as_str())
+        .collect::<Vec<_>>()
+        .join("; ")
+}
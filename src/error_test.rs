@@ -0,0 +1,87 @@
+#[cfg(test)]
+mod tests {
+    use axum::http::{header::RETRY_AFTER, StatusCode};
+    use axum::response::IntoResponse;
+    use validator::Validate;
+
+    use crate::error::{AppError, ErrorCode};
+    use crate::models::user::CreateUserDto;
+
+    #[test]
+    fn test_validation_errors_become_field_errors() {
+        let dto = CreateUserDto {
+            name: "".to_string(),
+            email: "email_invalido".to_string(),
+            password: "12345".to_string(),
+        };
+
+        let error = AppError::from(dto.validate().unwrap_err());
+
+        match &error {
+            AppError::InvalidFields(fields) => {
+                let fields: Vec<(&str, &str)> =
+                    fields.iter().map(|f| (f.field.as_str(), f.code.as_str())).collect();
+                assert_eq!(
+                    fields,
+                    vec![("email", "email_invalid"), ("name", "name_required"), ("password", "password_too_short")]
+                );
+            }
+            other => panic!("Erro inesperado: {:?}", other),
+        }
+        assert_eq!(error.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(error.code(), ErrorCode::ValidationFailed);
+    }
+
+    #[test]
+    fn test_status_and_code_of_each_variant() {
+        let cases = vec![
+            (AppError::InvalidCredentials, StatusCode::UNAUTHORIZED, ErrorCode::InvalidCredentials),
+            (AppError::Forbidden("x".to_string()), StatusCode::FORBIDDEN, ErrorCode::Forbidden),
+            (
+                AppError::Rejected(ErrorCode::CopyNotOffered, "x".to_string()),
+                StatusCode::BAD_REQUEST,
+                ErrorCode::CopyNotOffered,
+            ),
+            (
+                AppError::Conflict(ErrorCode::CopyReserved, "x".to_string()),
+                StatusCode::CONFLICT,
+                ErrorCode::CopyReserved,
+            ),
+            (AppError::TooManyRequests("x".to_string(), 30), StatusCode::TOO_MANY_REQUESTS, ErrorCode::RateLimited),
+            (AppError::BadGateway("x".to_string()), StatusCode::BAD_GATEWAY, ErrorCode::UpstreamUnavailable),
+            (AppError::NotFoundError("x".to_string()), StatusCode::NOT_FOUND, ErrorCode::NotFound),
+            (AppError::MalformedBody("x".to_string()), StatusCode::BAD_REQUEST, ErrorCode::MalformedBody),
+            (
+                AppError::UnprocessableBody("x".to_string()),
+                StatusCode::UNPROCESSABLE_ENTITY,
+                ErrorCode::InvalidBody,
+            ),
+            (
+                AppError::PayloadTooLarge("x".to_string()),
+                StatusCode::PAYLOAD_TOO_LARGE,
+                ErrorCode::BodyTooLarge,
+            ),
+        ];
+
+        for (error, status, code) in cases {
+            assert_eq!(error.status(), status, "Status inesperado para {:?}", error);
+            assert_eq!(error.code(), code, "Código inesperado para {:?}", error);
+        }
+    }
+
+    #[test]
+    fn test_too_many_requests_sets_retry_after() {
+        let response = AppError::TooManyRequests("x".to_string(), 42).into_response();
+
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers().get(RETRY_AFTER).unwrap(), "42");
+    }
+
+    #[test]
+    fn test_database_details_are_not_public() {
+        let error = AppError::DatabaseError("duplicate key value violates unique constraint".to_string());
+
+        assert!(!error.public_message().contains("constraint"));
+        assert_eq!(error.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
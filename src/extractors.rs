@@ -0,0 +1,84 @@
+use async_trait::async_trait;
+use axum::{
+    extract::{rejection::JsonRejection, FromRequest, FromRequestParts, Request},
+    http::request::Parts,
+    Json,
+};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::error::AppError;
+use crate::i18n::messages;
+use crate::models::user::User;
+use crate::state::AppState;
+
+/// Usuário autenticado da requisição
+///
+/// O token é validado e o usuário é carregado pelo `auth_middleware`, que já
+/// recusa contas removidas (401) ou suspensas (403).
+#[derive(Debug, Clone)]
+pub struct AuthUser {
+    pub id: Uuid,
+    pub user: User,
+}
+
+#[async_trait]
+impl FromRequestParts<AppState> for AuthUser {
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &AppState) -> Result<Self, Self::Rejection> {
+        // Sem o usuário, a rota não passou pelo auth_middleware
+        let user = parts
+            .extensions
+            .get::<User>()
+            .cloned()
+            .ok_or_else(|| AppError::AuthError(messages::TOKEN_MISSING.text()))?;
+
+        Ok(Self { id: user.id, user })
+    }
+}
+
+/// Corpo JSON da requisição, com as falhas de leitura no formato de erro da API
+///
+/// JSON malformado responde 400 e campos ausentes ou de tipo errado, 422.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonBody<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequest<S> for JsonBody<T>
+where
+    Json<T>: FromRequest<S, Rejection = JsonRejection>,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(value) = Json::<T>::from_request(request, state).await?;
+
+        Ok(Self(value))
+    }
+}
+
+/// Corpo JSON que também passa pelas regras do `validator`
+///
+/// Além dos erros do `JsonBody`, regras violadas respondem 400 com os erros de
+/// cada campo em `details`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ValidatedJson<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequest<S> for ValidatedJson<T>
+where
+    T: Validate,
+    JsonBody<T>: FromRequest<S, Rejection = AppError>,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let JsonBody(value) = JsonBody::<T>::from_request(request, state).await?;
+        value.validate()?;
+
+        Ok(Self(value))
+    }
+}
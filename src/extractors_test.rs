@@ -0,0 +1,102 @@
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::extract::FromRequest;
+    use axum::http::{header, Request, StatusCode};
+    use serde::Deserialize;
+    use validator::Validate;
+
+    use crate::error::{AppError, ErrorCode};
+    use crate::extractors::{JsonBody, ValidatedJson};
+    use crate::models::book::BookSearchRequest;
+
+    #[derive(Debug, Deserialize)]
+    struct Priority {
+        priority: i16,
+    }
+
+    fn json_request(body: &'static str) -> Request<Body> {
+        Request::builder()
+            .method("POST")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    async fn extract<T>(request: Request<Body>) -> Result<T, AppError>
+    where
+        T: FromRequest<(), Rejection = AppError>,
+    {
+        T::from_request(request, &()).await
+    }
+
+    #[tokio::test]
+    async fn test_json_body_reads_valid_json() {
+        let JsonBody(body) = extract::<JsonBody<Priority>>(json_request(r#"{"priority": 4}"#))
+            .await
+            .unwrap();
+
+        assert_eq!(body.priority, 4);
+    }
+
+    #[tokio::test]
+    async fn test_syntax_error_is_malformed_body() {
+        let error = extract::<JsonBody<Priority>>(json_request(r#"{"priority": "#))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, AppError::MalformedBody(_)));
+        assert_eq!(error.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(error.code(), ErrorCode::MalformedBody);
+    }
+
+    #[tokio::test]
+    async fn test_wrong_type_is_unprocessable_and_names_the_field() {
+        let error = extract::<JsonBody<Priority>>(json_request(r#"{"priority": "alta"}"#))
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(error.code(), ErrorCode::InvalidBody);
+        assert!(error.public_message().contains("priority"), "{}", error.public_message());
+    }
+
+    #[tokio::test]
+    async fn test_missing_content_type_is_malformed_body() {
+        let request = Request::builder()
+            .method("POST")
+            .body(Body::from(r#"{"priority": 4}"#))
+            .unwrap();
+
+        let error = extract::<JsonBody<Priority>>(request).await.unwrap_err();
+
+        assert!(matches!(error, AppError::MalformedBody(_)));
+    }
+
+    #[tokio::test]
+    async fn test_validated_json_applies_validation_rules() {
+        let error = extract::<ValidatedJson<BookSearchRequest>>(json_request(r#"{"query": ""}"#))
+            .await
+            .unwrap_err();
+
+        match error {
+            AppError::InvalidFields(fields) => {
+                assert_eq!(fields.len(), 1);
+                assert_eq!(fields[0].field, "query");
+                assert_eq!(fields[0].code, "search_query_empty");
+            }
+            other => panic!("Erro inesperado: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_validated_json_accepts_valid_body() {
+        let ValidatedJson(request) =
+            extract::<ValidatedJson<BookSearchRequest>>(json_request(r#"{"query": "Dom Casmurro"}"#))
+                .await
+                .unwrap();
+
+        assert_eq!(request.query, "Dom Casmurro");
+        assert!(request.validate().is_ok());
+    }
+}
@@ -1,50 +1,104 @@
-use std::sync::Arc;
-
-use axum::{http::StatusCode, response::IntoResponse, Json};
-use serde_json::json;
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
 
 use crate::error::AppError;
-use crate::models::user::{CreateUserDto, LoginUserDto};
-use crate::services::auth_service::AuthService;
-
-pub struct AuthHandler {
-    auth_service: Arc<dyn AuthService>,
-}
+#[allow(unused_imports)]
+use crate::error::ErrorResponse;
+use crate::extractors::ValidatedJson;
+use crate::i18n::messages;
+use crate::models::response::{
+    AccessTokenResponse, ApiResponse, RegisteredUserResponse, TwoFactorChallengeResponse,
+};
+use crate::models::user::{CreateUserDto, LoginResponse, LoginUserDto};
+use crate::state::AppState;
 
-impl AuthHandler {
-    pub fn new(auth_service: Arc<dyn AuthService>) -> Self {
-        Self { auth_service }
-    }
+/// Registra um novo usuário
+///
+/// Cria um novo usuário no sistema com os dados fornecidos.
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/register",
+    request_body(
+        content = CreateUserDto,
+        example = json!({
+            "name": "Usuário Teste",
+            "email": "usuario@teste.com",
+            "password": "senha123"
+        })
+    ),
+    responses(
+        (status = 201, description = "Usuário registrado com sucesso", body = RegisteredUserResponse),
+        (status = 400, description = "Dados de entrada inválidos (VALIDATION_FAILED)", body = ErrorResponse),
+        (status = 409, description = "Email já está em uso (EMAIL_ALREADY_IN_USE)", body = ErrorResponse),
+        (status = 429, description = "Muitos cadastros a partir do mesmo IP (RATE_LIMITED); o header Retry-After informa a espera em segundos", body = ErrorResponse,
+            headers(("Retry-After" = u64, description = "Segundos até uma nova tentativa ser aceita"))),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse)
+    ),
+    tag = "auth"
+)]
+pub async fn register(
+    State(state): State<AppState>,
+    ValidatedJson(create_user_dto): ValidatedJson<CreateUserDto>,
+) -> Result<(StatusCode, Json<RegisteredUserResponse>), AppError> {
+    let user = state.auth_service.register(create_user_dto).await?;
 
-    pub async fn register(
-        &self,
-        Json(create_user_dto): Json<CreateUserDto>,
-    ) -> Result<impl IntoResponse, AppError> {
-        let user = self.auth_service.register(create_user_dto).await?;
+    Ok((StatusCode::CREATED, Json(ApiResponse::new(messages::USER_REGISTERED, user))))
+}
 
-        Ok((
-            StatusCode::CREATED,
-            Json(json!({
-                "status": "success",
-                "message": "Usuário registrado com sucesso",
-                "data": user
-            })),
-        ))
-    }
+/// Realiza login de usuário
+///
+/// Autentica um usuário e retorna um token JWT, com os campos `iss`, `aud` e
+/// `jti` e o `kid` da chave que o assinou no cabeçalho. Após 5 tentativas com a senha
+/// errada, o login da conta fica bloqueado por 15 minutos.
+///
+/// Se o usuário ativou a verificação em duas etapas, a resposta é 202 com um
+/// desafio, que deve ser enviado com o código do autenticador para
+/// `/api/v1/auth/2fa/verify`.
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/login",
+    request_body(
+        content = LoginUserDto,
+        example = json!({
+            "email": "usuario@teste.com",
+            "password": "senha123"
+        })
+    ),
+    responses(
+        (status = 200, description = "Login realizado com sucesso", body = AccessTokenResponse),
+        (status = 202, description = "Senha correta; falta o código da verificação em duas etapas", body = TwoFactorChallengeResponse),
+        (status = 401, description = "Credenciais inválidas (INVALID_CREDENTIALS)", body = ErrorResponse),
+        (status = 403, description = "Conta suspensa (FORBIDDEN)", body = ErrorResponse),
+        (status = 429, description = "Muitas tentativas a partir do mesmo IP ou conta bloqueada temporariamente após várias senhas erradas (RATE_LIMITED)", body = ErrorResponse,
+            headers(("Retry-After" = u64, description = "Segundos até uma nova tentativa ser aceita"))),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse)
+    ),
+    tag = "auth"
+)]
+pub async fn login(
+    State(state): State<AppState>,
+    ValidatedJson(login_dto): ValidatedJson<LoginUserDto>,
+) -> Result<Response, AppError> {
+    let response = state.auth_service.login(login_dto).await?;
 
-    pub async fn login(
-        &self,
-        Json(login_dto): Json<LoginUserDto>,
-    ) -> Result<impl IntoResponse, AppError> {
-        let token = self.auth_service.login(login_dto).await?;
+    Ok(login_response(response))
+}
 
-        Ok((
-            StatusCode::OK,
-            Json(json!({
-                "status": "success",
-                "message": "Login realizado com sucesso",
-                "data": token
-            })),
-        ))
+/// Resposta do login, também usada pelo login com provedores externos
+pub fn login_response(response: LoginResponse) -> Response {
+    match response {
+        LoginResponse::Authenticated(token) => {
+            let body: AccessTokenResponse = ApiResponse::new(messages::LOGIN_SUCCEEDED, token);
+            (StatusCode::OK, Json(body)).into_response()
+        }
+        // A senha está correta, mas falta o código do segundo fator
+        LoginResponse::TwoFactorRequired(challenge) => {
+            let body: TwoFactorChallengeResponse = ApiResponse::new(messages::TWO_FACTOR_REQUIRED, challenge);
+            (StatusCode::ACCEPTED, Json(body)).into_response()
+        }
     }
 }
@@ -1,43 +1,96 @@
 use axum::{
-    extract::Extension,
-    http::StatusCode,
-    response::IntoResponse,
+    extract::{Path, State},
     Json,
 };
-use serde_json::json;
-use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::error::AppError;
-use crate::services::book_service::BookService;
+#[allow(unused_imports)]
+use crate::error::ErrorResponse;
+use crate::extractors::AuthUser;
+use crate::i18n::messages;
+use crate::models::response::{ApiResponse, BookDetailsResponse, BookOffersResponse, UserBooksResponse};
+use crate::state::AppState;
 
-pub struct BookHandler {
-    book_service: Arc<dyn BookService>,
+/// Buscar livros do usuário (possuídos e desejados)
+#[utoipa::path(
+    get,
+    path = "/api/v1/books",
+    tag = "books",
+    responses(
+        (status = 200, description = "Livros recuperados com sucesso", body = UserBooksResponse),
+        (status = 401, description = "Não autorizado", body = ErrorResponse),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse),
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn get_user_books(
+    State(state): State<AppState>,
+    AuthUser { id: user_id, .. }: AuthUser,
+) -> Result<Json<UserBooksResponse>, AppError> {
+    let user_books = state.book_service.get_user_books(&user_id).await?;
+
+    Ok(Json(ApiResponse::new(messages::USER_BOOKS_FOUND, user_books)))
 }
 
-impl BookHandler {
-    pub fn new(book_service: Arc<dyn BookService>) -> Self {
-        Self {
-            book_service,
-        }
-    }
+/// Buscar um livro cadastrado
+///
+/// Retorna os dados gravados do livro, quantos exemplares estão oferecidos,
+/// quantos usuários o desejam e em qual lista o usuário autenticado o tem.
+/// A resposta não espera o Google Books: quando os dados passam da idade
+/// configurada em `BOOK_METADATA_MAX_AGE_SECS`, eles são atualizados em segundo
+/// plano e aparecem nas consultas seguintes.
+#[utoipa::path(
+    get,
+    path = "/api/v1/books/{book_id}",
+    tag = "books",
+    params(
+        ("book_id", description = "ID do livro")
+    ),
+    responses(
+        (status = 200, description = "Livro encontrado", body = BookDetailsResponse),
+        (status = 401, description = "Não autorizado", body = ErrorResponse),
+        (status = 404, description = "Livro não encontrado", body = ErrorResponse),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse),
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn get_book_details(
+    State(state): State<AppState>,
+    AuthUser { id: user_id, .. }: AuthUser,
+    Path(book_id): Path<Uuid>,
+) -> Result<Json<BookDetailsResponse>, AppError> {
+    let details = state.book_service.get_book_details(&book_id, &user_id).await?;
+
+    Ok(Json(ApiResponse::new(messages::BOOK_FOUND, details)))
+}
 
-    pub async fn get_user_books(
-        &self,
-        Extension(user_id): Extension<Uuid>,
-    ) -> Result<impl IntoResponse, AppError> {
-        let user_books = self
-            .book_service
-            .get_user_books(&user_id)
-            .await?;
+/// Listar quem oferece um livro, com os detalhes de cada exemplar
+#[utoipa::path(
+    get,
+    path = "/api/v1/books/{book_id}/offers",
+    tag = "books",
+    params(
+        ("book_id", description = "ID do livro")
+    ),
+    responses(
+        (status = 200, description = "Exemplares recuperados com sucesso", body = BookOffersResponse),
+        (status = 401, description = "Não autorizado", body = ErrorResponse),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse),
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn get_book_offers(
+    State(state): State<AppState>,
+    Path(book_id): Path<Uuid>,
+) -> Result<Json<BookOffersResponse>, AppError> {
+    let offers = state.book_service.get_book_offers(&book_id).await?;
 
-        Ok((
-            StatusCode::OK,
-            Json(json!({
-                "status": "success",
-                "message": "Livros do usuário recuperados com sucesso",
-                "data": user_books
-            })),
-        ))
-    }
-} 
\ No newline at end of file
+    Ok(Json(ApiResponse::new(messages::OFFERED_COPIES_FOUND, offers)))
+}
@@ -1,69 +1,244 @@
 use axum::{
-    extract::{Extension, Path},
+    extract::{Path, State},
     http::StatusCode,
-    response::IntoResponse,
     Json,
 };
 use serde::{Deserialize, Serialize};
-use serde_json::json;
-use std::sync::Arc;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
 use crate::error::AppError;
-use crate::services::book_offered_service::BookOfferedService;
+#[allow(unused_imports)]
+use crate::error::ErrorResponse;
+use crate::extractors::{AuthUser, JsonBody, ValidatedJson};
+use crate::i18n::messages;
+use crate::models::book::{BatchRemoveRequest, OfferedBookItem, OfferedCopyAttributes};
+use crate::models::response::{
+    ApiResponse, BatchResultsResponse, BookOfferedCopiesResponse, BookOfferedResponse, MessageResponse,
+};
+use crate::state::AppState;
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct AddBookRequest {
     pub google_id: String,
+    /// Número de exemplares idênticos a adicionar (padrão 1)
+    #[serde(default = "default_quantity")]
+    pub quantity: i32,
+    #[serde(flatten)]
+    pub attributes: OfferedCopyAttributes,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BatchAddOfferedRequest {
+    pub books: Vec<OfferedBookItem>,
+}
+
+fn default_quantity() -> i32 {
+    1
+}
+
+/// Adicionar um livro à lista de possuídos
+///
+/// Cria `quantity` exemplares do livro (1 por padrão), cada um com o seu próprio ID.
+/// Adicionar um livro que já está na lista cria novos exemplares.
+#[utoipa::path(
+    post,
+    path = "/api/v1/books/offered",
+    tag = "books_offered",
+    request_body = AddBookRequest,
+    responses(
+        (status = 201, description = "Exemplares adicionados com sucesso", body = BookOfferedCopiesResponse),
+        (status = 400, description = "Erro de validação", body = ErrorResponse),
+        (status = 401, description = "Não autorizado", body = ErrorResponse),
+        (status = 409, description = "O livro está na lista de desejados (BOOK_ALREADY_WANTED)", body = ErrorResponse),
+        (status = 404, description = "Livro não encontrado", body = ErrorResponse),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse),
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn add_book_to_offered(
+    State(state): State<AppState>,
+    AuthUser { id: user_id, .. }: AuthUser,
+    JsonBody(add_book_request): JsonBody<AddBookRequest>,
+) -> Result<(StatusCode, Json<BookOfferedCopiesResponse>), AppError> {
+    let copies = state
+        .book_offered_service
+        .add_book_to_offered(
+            &add_book_request.google_id,
+            &user_id,
+            add_book_request.attributes,
+            add_book_request.quantity,
+        )
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(ApiResponse::new(messages::OFFERED_BOOK_ADDED, copies))))
+}
+
+/// Atualizar os atributos de um exemplar da lista de possuídos
+#[utoipa::path(
+    put,
+    path = "/api/v1/books/offered/copies/{copy_id}",
+    tag = "books_offered",
+    params(
+        ("copy_id", description = "ID do exemplar")
+    ),
+    request_body = OfferedCopyAttributes,
+    responses(
+        (status = 200, description = "Exemplar atualizado com sucesso", body = BookOfferedResponse),
+        (status = 400, description = "Erro de validação ou exemplar fora da sua lista (COPY_NOT_OFFERED)", body = ErrorResponse),
+        (status = 401, description = "Não autorizado", body = ErrorResponse),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse),
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn update_book_offered(
+    State(state): State<AppState>,
+    AuthUser { id: user_id, .. }: AuthUser,
+    Path(copy_id): Path<Uuid>,
+    ValidatedJson(attributes): ValidatedJson<OfferedCopyAttributes>,
+) -> Result<Json<BookOfferedResponse>, AppError> {
+    let book_offered = state
+        .book_offered_service
+        .update_book_offered(&copy_id, &user_id, attributes)
+        .await?;
+
+    Ok(Json(ApiResponse::new(messages::OFFERED_COPY_UPDATED, book_offered)))
+}
+
+/// Remover um livro da lista de possuídos
+///
+/// Remove todos os exemplares do livro. Falha se algum deles estiver reservado
+/// em uma troca em andamento.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/books/offered/{book_id}",
+    tag = "books_offered",
+    params(
+        ("book_id", description = "ID do livro a ser removido da lista")
+    ),
+    responses(
+        (status = 200, description = "Livro removido com sucesso", body = MessageResponse),
+        (status = 400, description = "O livro não está na sua lista de possuídos (BOOK_NOT_OFFERED)", body = ErrorResponse),
+        (status = 401, description = "Não autorizado", body = ErrorResponse),
+        (status = 409, description = "O livro tem exemplares reservados em trocas (COPY_RESERVED)", body = ErrorResponse),
+        (status = 404, description = "Livro não encontrado", body = ErrorResponse),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse),
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn remove_book_from_offered(
+    State(state): State<AppState>,
+    AuthUser { id: user_id, .. }: AuthUser,
+    Path(book_id): Path<Uuid>,
+) -> Result<Json<MessageResponse>, AppError> {
+    state
+        .book_offered_service
+        .remove_book_from_offered(&book_id, &user_id)
+        .await?;
+
+    Ok(Json(MessageResponse::new(messages::OFFERED_BOOK_REMOVED)))
 }
 
-pub struct BookOfferedHandler {
-    book_offered_service: Arc<dyn BookOfferedService>,
+/// Remover um exemplar da lista de possuídos
+#[utoipa::path(
+    delete,
+    path = "/api/v1/books/offered/copies/{copy_id}",
+    tag = "books_offered",
+    params(
+        ("copy_id", description = "ID do exemplar")
+    ),
+    responses(
+        (status = 200, description = "Exemplar removido com sucesso", body = MessageResponse),
+        (status = 400, description = "Exemplar fora da sua lista de possuídos (COPY_NOT_OFFERED)", body = ErrorResponse),
+        (status = 409, description = "Exemplar reservado em uma troca (COPY_RESERVED)", body = ErrorResponse),
+        (status = 401, description = "Não autorizado", body = ErrorResponse),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse),
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn remove_copy_from_offered(
+    State(state): State<AppState>,
+    AuthUser { id: user_id, .. }: AuthUser,
+    Path(copy_id): Path<Uuid>,
+) -> Result<Json<MessageResponse>, AppError> {
+    state
+        .book_offered_service
+        .remove_copy_from_offered(&copy_id, &user_id)
+        .await?;
+
+    Ok(Json(MessageResponse::new(messages::OFFERED_COPY_REMOVED)))
 }
 
-impl BookOfferedHandler {
-    pub fn new(book_offered_service: Arc<dyn BookOfferedService>) -> Self {
-        Self {
-            book_offered_service,
-        }
-    }
-
-    pub async fn add_book_to_offered(
-        &self,
-        Extension(user_id): Extension<Uuid>,
-        Json(add_book_request): Json<AddBookRequest>,
-    ) -> Result<impl IntoResponse, AppError> {
-        let book_offered = self
-            .book_offered_service
-            .add_book_to_offered(&add_book_request.google_id, &user_id)
-            .await?;
-
-        Ok((
-            StatusCode::CREATED,
-            Json(json!({
-                "status": "success",
-                "message": "Livro adicionado à lista de possuídos com sucesso",
-                "data": book_offered
-            })),
-        ))
-    }
-
-    pub async fn remove_book_from_offered(
-        &self,
-        Extension(user_id): Extension<Uuid>,
-        Path(book_id): Path<Uuid>,
-    ) -> Result<impl IntoResponse, AppError> {
-        self.book_offered_service
-            .remove_book_from_offered(&book_id, &user_id)
-            .await?;
-
-        Ok((
-            StatusCode::OK,
-            Json(json!({
-                    "status": "success",
-                    "message": "Livro removido da lista de possuídos com sucesso"
-            })),
-        ))
-    }
+/// Adicionar vários livros à lista de possuídos
+///
+/// Aceita até 50 livros por requisição. Os livros ainda não cadastrados são
+/// buscados no Google Books em paralelo e todos os exemplares são criados em
+/// uma única transação. A resposta traz o resultado de cada item, na ordem da
+/// requisição; itens com falha não impedem a inclusão dos demais.
+#[utoipa::path(
+    post,
+    path = "/api/v1/books/offered/batch",
+    tag = "books_offered",
+    request_body = BatchAddOfferedRequest,
+    responses(
+        (status = 200, description = "Resultado de cada livro", body = BatchResultsResponse),
+        (status = 400, description = "Quantidade de livros inválida", body = ErrorResponse),
+        (status = 401, description = "Não autorizado", body = ErrorResponse),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse),
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn add_books_to_offered(
+    State(state): State<AppState>,
+    AuthUser { id: user_id, .. }: AuthUser,
+    JsonBody(request): JsonBody<BatchAddOfferedRequest>,
+) -> Result<Json<BatchResultsResponse>, AppError> {
+    let results = state
+        .book_offered_service
+        .add_books_to_offered(&user_id, request.books)
+        .await?;
+
+    Ok(Json(ApiResponse::new(messages::BOOKS_PROCESSED, results)))
+}
+
+/// Remover vários livros da lista de possuídos
+///
+/// Remove todos os exemplares de cada livro informado. Livros com exemplares
+/// reservados em trocas em andamento são mantidos e aparecem como falha.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/books/offered/batch",
+    tag = "books_offered",
+    request_body = BatchRemoveRequest,
+    responses(
+        (status = 200, description = "Resultado de cada livro", body = BatchResultsResponse),
+        (status = 400, description = "Quantidade de livros inválida", body = ErrorResponse),
+        (status = 401, description = "Não autorizado", body = ErrorResponse),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse),
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn remove_books_from_offered(
+    State(state): State<AppState>,
+    AuthUser { id: user_id, .. }: AuthUser,
+    JsonBody(request): JsonBody<BatchRemoveRequest>,
+) -> Result<Json<BatchResultsResponse>, AppError> {
+    let results = state
+        .book_offered_service
+        .remove_books_from_offered(&user_id, request.book_ids)
+        .await?;
+
+    Ok(Json(ApiResponse::new(messages::BOOKS_PROCESSED, results)))
 }
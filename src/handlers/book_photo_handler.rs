@@ -0,0 +1,120 @@
+use axum::{
+    body::Bytes,
+    extract::{Path, State},
+    http::{header, HeaderMap, StatusCode},
+    response::IntoResponse,
+    Json,
+};
+use uuid::Uuid;
+
+use crate::error::AppError;
+#[allow(unused_imports)]
+use crate::error::ErrorResponse;
+use crate::extractors::AuthUser;
+use crate::i18n::messages;
+use crate::models::response::{ApiResponse, BookPhotoResponse, MessageResponse};
+use crate::state::AppState;
+
+/// Enviar uma foto de um exemplar da lista de possuídos
+///
+/// O corpo da requisição é o conteúdo da imagem (JPEG, PNG ou WebP) e o
+/// cabeçalho `Content-Type` deve indicar o seu tipo.
+#[utoipa::path(
+    post,
+    path = "/api/v1/books/offered/copies/{copy_id}/photos",
+    tag = "books_offered",
+    params(
+        ("copy_id", description = "ID do exemplar")
+    ),
+    request_body(content = Vec<u8>, content_type = "image/jpeg"),
+    responses(
+        (status = 201, description = "Foto adicionada com sucesso", body = BookPhotoResponse),
+        (status = 400, description = "Foto inválida ou acima do tamanho permitido; exemplar fora da sua lista (COPY_NOT_OFFERED) ou com o máximo de fotos (PHOTO_LIMIT_REACHED)", body = ErrorResponse),
+        (status = 401, description = "Não autorizado", body = ErrorResponse),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse),
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn add_photo(
+    State(state): State<AppState>,
+    AuthUser { id: user_id, .. }: AuthUser,
+    Path(copy_id): Path<Uuid>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<(StatusCode, Json<BookPhotoResponse>), AppError> {
+    let content_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+
+    let photo = state
+        .book_photo_service
+        .add_photo(&copy_id, &user_id, &content_type, body.to_vec())
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(ApiResponse::new(messages::PHOTO_ADDED, photo))))
+}
+
+/// Baixar uma foto de um exemplar
+#[utoipa::path(
+    get,
+    path = "/api/v1/photos/{photo_id}",
+    tag = "books_offered",
+    params(
+        ("photo_id", description = "ID da foto")
+    ),
+    responses(
+        (status = 200, description = "Conteúdo da foto", content_type = "image/*", body = Vec<u8>),
+        (status = 401, description = "Não autorizado", body = ErrorResponse),
+        (status = 404, description = "Foto não encontrada", body = ErrorResponse),
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn get_photo(
+    State(state): State<AppState>,
+    Path(photo_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let photo = state.book_photo_service.get_photo(&photo_id).await?;
+
+    Ok((
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, photo.content_type)],
+        photo.content,
+    ))
+}
+
+/// Remover uma foto de um exemplar
+#[utoipa::path(
+    delete,
+    path = "/api/v1/photos/{photo_id}",
+    tag = "books_offered",
+    params(
+        ("photo_id", description = "ID da foto")
+    ),
+    responses(
+        (status = 200, description = "Foto removida com sucesso", body = MessageResponse),
+        (status = 401, description = "Não autorizado", body = ErrorResponse),
+        (status = 404, description = "Foto não encontrada", body = ErrorResponse),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse),
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn remove_photo(
+    State(state): State<AppState>,
+    AuthUser { id: user_id, .. }: AuthUser,
+    Path(photo_id): Path<Uuid>,
+) -> Result<Json<MessageResponse>, AppError> {
+    state
+        .book_photo_service
+        .remove_photo(&photo_id, &user_id)
+        .await?;
+
+    Ok(Json(MessageResponse::new(messages::PHOTO_REMOVED)))
+}
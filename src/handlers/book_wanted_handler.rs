@@ -1,69 +1,241 @@
 use axum::{
-    extract::{Extension, Path},
+    extract::{Path, State},
     http::StatusCode,
-    response::IntoResponse,
     Json,
 };
 use serde::{Deserialize, Serialize};
-use serde_json::json;
-use std::sync::Arc;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
 use crate::error::AppError;
-use crate::services::book_wanted_service::BookWantedService;
+#[allow(unused_imports)]
+use crate::error::ErrorResponse;
+use crate::extractors::{AuthUser, JsonBody};
+use crate::i18n::messages;
+use crate::models::book::{BatchRemoveRequest, OfferedCopyAttributes, WantedBookItem, DEFAULT_WANTED_PRIORITY};
+use crate::models::response::{
+    ApiResponse, BatchResultsResponse, BookOfferedResponse, BookWantedResponse, MessageResponse,
+};
+use crate::state::AppState;
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct AddBookRequest {
     pub google_id: String,
+    /// Prioridade do livro, de 1 (pouco interesse) a 5 (muito desejado); 3 por padrão
+    #[serde(default = "default_priority")]
+    pub priority: i16,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UpdatePriorityRequest {
+    /// Nova prioridade do livro, de 1 (pouco interesse) a 5 (muito desejado)
+    pub priority: i16,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BatchAddWantedRequest {
+    pub books: Vec<WantedBookItem>,
+}
+
+fn default_priority() -> i16 {
+    DEFAULT_WANTED_PRIORITY
+}
+
+/// Adicionar um livro à lista de desejados
+#[utoipa::path(
+    post,
+    path = "/api/v1/books/wanted",
+    tag = "books_wanted",
+    request_body = AddBookRequest,
+    responses(
+        (status = 201, description = "Livro adicionado com sucesso", body = BookWantedResponse),
+        (status = 400, description = "Erro de validação", body = ErrorResponse),
+        (status = 401, description = "Não autorizado", body = ErrorResponse),
+        (status = 409, description = "O livro já está em uma das listas (BOOK_ALREADY_WANTED, BOOK_ALREADY_OFFERED)", body = ErrorResponse),
+        (status = 404, description = "Livro não encontrado", body = ErrorResponse),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse),
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn add_book_to_wanted(
+    State(state): State<AppState>,
+    AuthUser { id: user_id, .. }: AuthUser,
+    JsonBody(add_book_request): JsonBody<AddBookRequest>,
+) -> Result<(StatusCode, Json<BookWantedResponse>), AppError> {
+    let book_wanted = state
+        .book_wanted_service
+        .add_book_to_wanted(&add_book_request.google_id, &user_id, add_book_request.priority)
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(ApiResponse::new(messages::WANTED_BOOK_ADDED, book_wanted))))
 }
 
-pub struct BookWantedHandler {
-    book_wanted_service: Arc<dyn BookWantedService>,
+/// Alterar a prioridade de um livro da lista de desejados
+///
+/// A prioridade vai de 1 (pouco interesse) a 5 (muito desejado) e é usada para
+/// ordenar as sugestões de troca.
+#[utoipa::path(
+    put,
+    path = "/api/v1/books/wanted/{book_id}",
+    tag = "books_wanted",
+    params(
+        ("book_id", description = "ID do livro desejado")
+    ),
+    request_body = UpdatePriorityRequest,
+    responses(
+        (status = 200, description = "Prioridade atualizada com sucesso", body = BookWantedResponse),
+        (status = 400, description = "Erro de validação ou livro fora da sua lista (BOOK_NOT_WANTED)", body = ErrorResponse),
+        (status = 401, description = "Não autorizado", body = ErrorResponse),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse),
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn update_priority(
+    State(state): State<AppState>,
+    AuthUser { id: user_id, .. }: AuthUser,
+    Path(book_id): Path<Uuid>,
+    JsonBody(request): JsonBody<UpdatePriorityRequest>,
+) -> Result<Json<BookWantedResponse>, AppError> {
+    let book_wanted = state
+        .book_wanted_service
+        .update_priority(&book_id, &user_id, request.priority)
+        .await?;
+
+    Ok(Json(ApiResponse::new(messages::WANTED_PRIORITY_UPDATED, book_wanted)))
+}
+
+/// Remover um livro da lista de desejados
+#[utoipa::path(
+    delete,
+    path = "/api/v1/books/wanted/{book_id}",
+    tag = "books_wanted",
+    params(
+        ("book_id", description = "ID do livro a ser removido da lista")
+    ),
+    responses(
+        (status = 200, description = "Livro removido com sucesso", body = MessageResponse),
+        (status = 400, description = "O livro não está na sua lista de desejados (BOOK_NOT_WANTED)", body = ErrorResponse),
+        (status = 401, description = "Não autorizado", body = ErrorResponse),
+        (status = 404, description = "Livro não encontrado", body = ErrorResponse),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse),
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn remove_book_from_wanted(
+    State(state): State<AppState>,
+    AuthUser { id: user_id, .. }: AuthUser,
+    Path(book_id): Path<Uuid>,
+) -> Result<Json<MessageResponse>, AppError> {
+    state
+        .book_wanted_service
+        .remove_book_from_wanted(&book_id, &user_id)
+        .await?;
+
+    Ok(Json(MessageResponse::new(messages::WANTED_BOOK_REMOVED)))
+}
+
+/// Marcar um livro desejado como adquirido
+///
+/// O livro sai da lista de desejados e entra na lista de possuídos na mesma
+/// transação. O corpo é opcional e descreve o exemplar recebido.
+#[utoipa::path(
+    post,
+    path = "/api/v1/books/wanted/{book_id}/acquired",
+    tag = "books_wanted",
+    params(
+        ("book_id", description = "ID do livro desejado")
+    ),
+    request_body(content = OfferedCopyAttributes, description = "Atributos do exemplar recebido", content_type = "application/json"),
+    responses(
+        (status = 201, description = "Livro movido para a lista de possuídos", body = BookOfferedResponse),
+        (status = 400, description = "Erro de validação ou livro fora da sua lista (BOOK_NOT_WANTED)", body = ErrorResponse),
+        (status = 401, description = "Não autorizado", body = ErrorResponse),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse),
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn mark_as_acquired(
+    State(state): State<AppState>,
+    AuthUser { id: user_id, .. }: AuthUser,
+    Path(book_id): Path<Uuid>,
+    attributes: Option<Json<OfferedCopyAttributes>>,
+) -> Result<(StatusCode, Json<BookOfferedResponse>), AppError> {
+    let attributes = attributes.map(|Json(attributes)| attributes).unwrap_or_default();
+
+    let copy = state
+        .book_wanted_service
+        .mark_as_acquired(&book_id, &user_id, attributes)
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(ApiResponse::new(messages::WANTED_BOOK_ACQUIRED, copy))))
+}
+
+/// Adicionar vários livros à lista de desejados
+///
+/// Aceita até 50 livros por requisição. Os livros ainda não cadastrados são
+/// buscados no Google Books em paralelo e todos são adicionados em uma única
+/// transação. A resposta traz o resultado de cada item, na ordem da requisição.
+#[utoipa::path(
+    post,
+    path = "/api/v1/books/wanted/batch",
+    tag = "books_wanted",
+    request_body = BatchAddWantedRequest,
+    responses(
+        (status = 200, description = "Resultado de cada livro", body = BatchResultsResponse),
+        (status = 400, description = "Quantidade de livros inválida", body = ErrorResponse),
+        (status = 401, description = "Não autorizado", body = ErrorResponse),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse),
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn add_books_to_wanted(
+    State(state): State<AppState>,
+    AuthUser { id: user_id, .. }: AuthUser,
+    JsonBody(request): JsonBody<BatchAddWantedRequest>,
+) -> Result<Json<BatchResultsResponse>, AppError> {
+    let results = state
+        .book_wanted_service
+        .add_books_to_wanted(&user_id, request.books)
+        .await?;
+
+    Ok(Json(ApiResponse::new(messages::BOOKS_PROCESSED, results)))
 }
 
-impl BookWantedHandler {
-    pub fn new(book_wanted_service: Arc<dyn BookWantedService>) -> Self {
-        Self {
-            book_wanted_service,
-        }
-    }
-
-    pub async fn add_book_to_wanted(
-        &self,
-        Extension(user_id): Extension<Uuid>,
-        Json(add_book_request): Json<AddBookRequest>,
-    ) -> Result<impl IntoResponse, AppError> {
-        let book_wanted = self
-            .book_wanted_service
-            .add_book_to_wanted(&add_book_request.google_id, &user_id)
-            .await?;
-
-        Ok((
-            StatusCode::CREATED,
-            Json(json!({
-                "status": "success",
-                "message": "Livro adicionado à lista de desejados com sucesso",
-                "data": book_wanted
-            })),
-        ))
-    }
-
-    pub async fn remove_book_from_wanted(
-        &self,
-        Extension(user_id): Extension<Uuid>,
-        Path(book_id): Path<Uuid>,
-    ) -> Result<impl IntoResponse, AppError> {
-        self.book_wanted_service
-            .remove_book_from_wanted(&book_id, &user_id)
-            .await?;
-
-        Ok((
-            StatusCode::OK,
-            Json(json!({
-                    "status": "success",
-                    "message": "Livro removido da lista de desejados com sucesso"
-            })),
-        ))
-    }
+/// Remover vários livros da lista de desejados
+#[utoipa::path(
+    delete,
+    path = "/api/v1/books/wanted/batch",
+    tag = "books_wanted",
+    request_body = BatchRemoveRequest,
+    responses(
+        (status = 200, description = "Resultado de cada livro", body = BatchResultsResponse),
+        (status = 400, description = "Quantidade de livros inválida", body = ErrorResponse),
+        (status = 401, description = "Não autorizado", body = ErrorResponse),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse),
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn remove_books_from_wanted(
+    State(state): State<AppState>,
+    AuthUser { id: user_id, .. }: AuthUser,
+    JsonBody(request): JsonBody<BatchRemoveRequest>,
+) -> Result<Json<BatchResultsResponse>, AppError> {
+    let results = state
+        .book_wanted_service
+        .remove_books_from_wanted(&user_id, request.book_ids)
+        .await?;
+
+    Ok(Json(ApiResponse::new(messages::BOOKS_PROCESSED, results)))
 }
@@ -1,44 +1,47 @@
-use axum::{http::StatusCode, response::IntoResponse, Json};
-use serde_json::json;
-use std::sync::Arc;
+use axum::{extract::State, Json};
 
 use crate::error::AppError;
+#[allow(unused_imports)]
+use crate::error::ErrorResponse;
+use crate::extractors::ValidatedJson;
+use crate::i18n::messages;
 use crate::models::book::BookSearchRequest;
-use crate::services::google_book_service::GoogleBookService;
+use crate::models::response::{ApiResponse, GoogleBooksResponse};
+use crate::state::AppState;
 
-pub struct GoogleBookHandler {
-    google_book_service: Arc<dyn GoogleBookService>,
-}
-
-impl GoogleBookHandler {
-    pub fn new(book_service: Arc<dyn GoogleBookService>) -> Self {
-        Self {
-            google_book_service: book_service,
-        }
-    }
-
-    pub async fn search_books(
-        &self,
-        Json(search_request): Json<BookSearchRequest>,
-    ) -> Result<impl IntoResponse, AppError> {
-        if search_request.query.is_empty() {
-            return Err(AppError::ValidationError(
-                "A consulta não pode estar vazia".to_string(),
-            ));
-        }
-
-        let books = self
-            .google_book_service
-            .search_books(&search_request.query)
-            .await?;
+/// Buscar livros no Google Books
+#[utoipa::path(
+    post,
+    path = "/api/v1/books/search",
+    request_body(
+        content = BookSearchRequest,
+        example = json!({
+            "query": "Clean Code"
+        })
+    ),
+    responses(
+        (status = 200, description = "Busca de livros realizada com sucesso", body = GoogleBooksResponse),
+        (status = 400, description = "Erro de validação", body = ErrorResponse),
+        (status = 401, description = "Não autenticado", body = ErrorResponse),
+        (status = 403, description = "Conta suspensa (FORBIDDEN)", body = ErrorResponse),
+        (status = 429, description = "Limite de buscas do usuário atingido (RATE_LIMITED)", body = ErrorResponse,
+            headers(("Retry-After" = u64, description = "Segundos até uma nova busca ser aceita"))),
+        (status = 502, description = "Google Books indisponível (UPSTREAM_UNAVAILABLE)", body = ErrorResponse),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse)
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "google_books"
+)]
+pub async fn search_books(
+    State(state): State<AppState>,
+    ValidatedJson(search_request): ValidatedJson<BookSearchRequest>,
+) -> Result<Json<GoogleBooksResponse>, AppError> {
+    let books = state
+        .google_book_service
+        .search_books(&search_request.query)
+        .await?;
 
-        Ok((
-            StatusCode::OK,
-            Json(json!({
-                "status": "success",
-                "message": "Livros encontrados com sucesso",
-                "data": books
-            })),
-        ))
-    }
+    Ok(Json(ApiResponse::new(messages::BOOKS_FOUND, books)))
 }
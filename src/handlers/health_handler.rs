@@ -0,0 +1,79 @@
+use axum::{
+    extract::State,
+    http::{header::CONTENT_TYPE, StatusCode},
+    response::IntoResponse,
+    Json,
+};
+use serde_json::{json, Value};
+
+use crate::models::health::ReadinessReport;
+use crate::services::metrics;
+use crate::state::AppState;
+
+/// Content-Type do formato de texto do Prometheus
+const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Verifica se a API está no ar
+///
+/// Responde enquanto o processo estiver atendendo requisições, sem consultar
+/// o banco de dados. Usado como liveness probe.
+#[utoipa::path(
+    get,
+    path = "/health",
+    responses(
+        (status = 200, description = "A API está respondendo", body = Object, example = json!({ "status": "ok" }))
+    ),
+    tag = "health"
+)]
+pub async fn live() -> Json<Value> {
+    Json(json!({ "status": "ok" }))
+}
+
+/// Verifica se a API está pronta para receber tráfego
+///
+/// Consulta o banco de dados e, com `READINESS_CHECK_GOOGLE_BOOKS=true`, a API
+/// do Google Books. Cada verificação tem até 2 segundos. Usado como
+/// readiness probe.
+#[utoipa::path(
+    get,
+    path = "/ready",
+    responses(
+        (status = 200, description = "Todas as dependências responderam", body = ReadinessReport,
+            example = json!({ "status": "ok", "checks": { "database": "ok" } })),
+        (status = 503, description = "Alguma dependência está indisponível", body = ReadinessReport,
+            example = json!({ "status": "unavailable", "checks": { "database": "unavailable" } }))
+    ),
+    tag = "health"
+)]
+pub async fn ready(State(state): State<AppState>) -> (StatusCode, Json<ReadinessReport>) {
+    let report = state.health_service.readiness().await;
+    let status = if report.is_ready() {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    (status, Json(report))
+}
+
+/// Métricas para o Prometheus
+///
+/// Formato de texto do Prometheus com:
+/// - `http_request_duration_seconds`: duração das requisições por método, rota e status
+/// - `upstream_requests_total` e `upstream_request_duration_seconds`: chamadas ao
+///   Google Books e aos provedores de login, por resultado
+/// - `trade_matcher_duration_seconds`: duração da busca de trocas possíveis
+/// - `db_pool_connections` e `db_pool_max_connections`: uso do pool de conexões
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    responses(
+        (status = 200, description = "Métricas no formato de texto do Prometheus", body = String, content_type = "text/plain")
+    ),
+    tag = "health"
+)]
+pub async fn metrics(State(state): State<AppState>) -> impl IntoResponse {
+    let body = metrics::metrics().render(Some(state.health_service.pool_stats()));
+
+    ([(CONTENT_TYPE, PROMETHEUS_CONTENT_TYPE)], body)
+}
@@ -0,0 +1,38 @@
+use axum::{
+    extract::State,
+    http::{header, HeaderValue},
+    response::IntoResponse,
+    Json,
+};
+
+use crate::models::jwk::JwkSet;
+use crate::state::AppState;
+
+/// Tempo, em segundos, que outros serviços podem manter o JWKS em cache
+const JWKS_MAX_AGE: u64 = 300;
+
+/// Chaves públicas dos tokens
+///
+/// Retorna, no formato JWK Set (RFC 7517), as chaves públicas aceitas na
+/// verificação dos tokens, para que outros serviços possam validá-los. O
+/// campo `kid` do cabeçalho do token indica a chave usada. Com tokens HS256 a
+/// lista é vazia.
+///
+/// A resposta não usa o envelope das demais, para que bibliotecas de JWT
+/// possam consumi-la diretamente.
+#[utoipa::path(
+    get,
+    path = "/.well-known/jwks.json",
+    responses(
+        (status = 200, description = "Chaves públicas aceitas na verificação dos tokens", body = JwkSet)
+    ),
+    tag = "auth"
+)]
+pub async fn jwks(State(state): State<AppState>) -> impl IntoResponse {
+    let cache_control = HeaderValue::from_str(&format!("public, max-age={}", JWKS_MAX_AGE))
+        .unwrap_or_else(|_| HeaderValue::from_static("no-cache"));
+
+    let keys: JwkSet = state.token_service.jwks();
+
+    ([(header::CACHE_CONTROL, cache_control)], Json(keys))
+}
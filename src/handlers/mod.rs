@@ -1,6 +1,13 @@
 pub mod auth_handler;
 pub mod book_handler;
 pub mod book_offered_handler;
+pub mod book_photo_handler;
 pub mod book_wanted_handler;
 pub mod google_book_handler;
+pub mod health_handler;
+pub mod jwks_handler;
+pub mod oauth_handler;
+pub mod shelf_handler;
 pub mod trade_handler;
+pub mod two_factor_handler;
+pub mod user_handler;
@@ -0,0 +1,100 @@
+use axum::{
+    extract::{Path, Query, State},
+    response::{Redirect, Response},
+};
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use crate::error::AppError;
+#[allow(unused_imports)]
+use crate::error::ErrorResponse;
+use crate::handlers::auth_handler::login_response;
+use crate::i18n::messages;
+#[allow(unused_imports)]
+use crate::models::response::{AccessTokenResponse, TwoFactorChallengeResponse};
+use crate::state::AppState;
+
+/// Parâmetros com que o provedor redireciona o usuário de volta
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct OAuthCallbackQuery {
+    /// Código de autorização emitido pelo provedor
+    pub code: Option<String>,
+    /// Valor gerado no início do login
+    pub state: Option<String>,
+    /// Motivo informado pelo provedor quando o usuário nega o acesso
+    pub error: Option<String>,
+}
+
+/// Inicia o login com um provedor externo
+///
+/// Redireciona o navegador para a página de autorização do provedor (`google`
+/// ou `github`), usando o fluxo authorization code com PKCE. A autorização
+/// deve ser concluída em até 10 minutos.
+#[utoipa::path(
+    get,
+    path = "/api/v1/auth/oauth/{provider}/authorize",
+    params(
+        ("provider", description = "Provedor de login: google ou github")
+    ),
+    responses(
+        (status = 303, description = "Redirecionamento para a página de autorização do provedor",
+            headers(("Location" = String, description = "URL de autorização do provedor"))),
+        (status = 404, description = "Provedor desconhecido ou não configurado (NOT_FOUND)", body = ErrorResponse),
+        (status = 429, description = "Muitas tentativas a partir do mesmo IP (RATE_LIMITED)", body = ErrorResponse,
+            headers(("Retry-After" = u64, description = "Segundos até uma nova tentativa ser aceita"))),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse)
+    ),
+    tag = "auth"
+)]
+pub async fn authorize(
+    State(state): State<AppState>,
+    Path(provider): Path<String>,
+) -> Result<Redirect, AppError> {
+    let url = state.oauth_service.authorization_url(&provider).await?;
+
+    Ok(Redirect::to(&url))
+}
+
+/// Conclui o login com um provedor externo
+///
+/// Endereço de retorno registrado no provedor. A conta do provedor é vinculada
+/// ao usuário com o mesmo email quando o provedor confirmou o email; sem
+/// usuário com o email, um novo é criado. Retorna o mesmo token do login com
+/// senha, ou o desafio da verificação em duas etapas.
+#[utoipa::path(
+    get,
+    path = "/api/v1/auth/oauth/{provider}/callback",
+    params(
+        ("provider", description = "Provedor de login: google ou github")
+    ),
+    responses(
+        (status = 200, description = "Login realizado com sucesso", body = AccessTokenResponse),
+        (status = 202, description = "Falta o código da verificação em duas etapas", body = TwoFactorChallengeResponse),
+        (status = 400, description = "Retorno sem code ou state (VALIDATION_FAILED)", body = ErrorResponse),
+        (status = 401, description = "Login expirado, state já usado, acesso negado ou código recusado pelo provedor (UNAUTHORIZED)", body = ErrorResponse),
+        (status = 403, description = "O provedor não confirmou o email da conta, ou a conta está suspensa (FORBIDDEN)", body = ErrorResponse),
+        (status = 404, description = "Provedor desconhecido ou não configurado (NOT_FOUND)", body = ErrorResponse),
+        (status = 429, description = "Muitas tentativas a partir do mesmo IP (RATE_LIMITED)", body = ErrorResponse,
+            headers(("Retry-After" = u64, description = "Segundos até uma nova tentativa ser aceita"))),
+        (status = 502, description = "Falha na comunicação com o provedor (UPSTREAM_UNAVAILABLE)", body = ErrorResponse)
+    ),
+    tag = "auth"
+)]
+pub async fn callback(
+    State(state): State<AppState>,
+    Path(provider): Path<String>,
+    Query(query): Query<OAuthCallbackQuery>,
+) -> Result<Response, AppError> {
+    if let Some(error) = query.error {
+        return Err(AppError::AuthError(messages::OAUTH_AUTHORIZATION_DENIED.format(&[&error])));
+    }
+
+    let (Some(code), Some(oauth_state)) = (query.code, query.state) else {
+        return Err(AppError::ValidationError(messages::OAUTH_CALLBACK_INVALID.text()));
+    };
+
+    let response = state.oauth_service.login(&provider, &code, &oauth_state).await?;
+
+    Ok(login_response(response))
+}
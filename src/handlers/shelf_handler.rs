@@ -0,0 +1,122 @@
+use axum::{
+    extract::{Query, State},
+    http::header,
+    response::IntoResponse,
+    Json,
+};
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use crate::error::AppError;
+#[allow(unused_imports)]
+use crate::error::ErrorResponse;
+use crate::extractors::AuthUser;
+use crate::i18n::messages;
+use crate::models::response::{ApiResponse, ImportReportResponse};
+use crate::models::shelf::{ShelfFormat, ShelfList};
+use crate::state::AppState;
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ImportQuery {
+    /// csv, goodreads ou json (deduzido do conteúdo quando ausente)
+    pub format: Option<String>,
+    /// Lista das linhas que não informam a sua: offered (padrão) ou wanted
+    pub list: Option<String>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ExportQuery {
+    /// csv (padrão), goodreads ou json
+    pub format: Option<String>,
+}
+
+/// Importar uma estante
+///
+/// O arquivo é enviado diretamente no corpo da requisição. São aceitos o CSV
+/// da aplicação (colunas list, google_id, isbn, title, author, condition,
+/// language, notes, priority), a exportação da biblioteca do Goodreads e uma
+/// lista JSON com os mesmos campos do CSV. Cada livro é identificado pelo
+/// google_id, pelo ISBN ou pelo título e autor, e o relatório indica o que
+/// aconteceu com cada linha. Reimportar o mesmo arquivo não duplica livros.
+#[utoipa::path(
+    post,
+    path = "/api/v1/books/import",
+    tag = "books",
+    request_body(content = String, description = "Conteúdo do arquivo", content_type = "text/csv"),
+    responses(
+        (status = 200, description = "Relatório da importação", body = ImportReportResponse),
+        (status = 400, description = "Arquivo ou parâmetros inválidos", body = ErrorResponse),
+        (status = 401, description = "Não autorizado", body = ErrorResponse),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse),
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn import_shelf(
+    State(state): State<AppState>,
+    AuthUser { id: user_id, .. }: AuthUser,
+    Query(query): Query<ImportQuery>,
+    body: String,
+) -> Result<Json<ImportReportResponse>, AppError> {
+    let format = query.format.as_deref().map(str::parse::<ShelfFormat>).transpose()?;
+    let list = query
+        .list
+        .as_deref()
+        .map(str::parse::<ShelfList>)
+        .transpose()?
+        .unwrap_or_default();
+
+    let report = state
+        .shelf_service
+        .import_shelf(&user_id, format, list, &body)
+        .await?;
+
+    Ok(Json(ApiResponse::new(messages::SHELF_IMPORTED, report)))
+}
+
+/// Exportar a estante
+///
+/// Gera um arquivo com os exemplares possuídos e os livros desejados do
+/// usuário, que pode ser importado novamente.
+#[utoipa::path(
+    get,
+    path = "/api/v1/books/export",
+    tag = "books",
+    responses(
+        (status = 200, description = "Arquivo da estante", body = String, content_type = "text/csv"),
+        (status = 400, description = "Formato inválido", body = ErrorResponse),
+        (status = 401, description = "Não autorizado", body = ErrorResponse),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse),
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn export_shelf(
+    State(state): State<AppState>,
+    AuthUser { id: user_id, .. }: AuthUser,
+    Query(query): Query<ExportQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let format = query
+        .format
+        .as_deref()
+        .map(str::parse::<ShelfFormat>)
+        .transpose()?
+        .unwrap_or(ShelfFormat::Csv);
+
+    let content = state.shelf_service.export_shelf(&user_id, format).await?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"estante.{}\"", format.file_extension()),
+            ),
+        ],
+        content,
+    ))
+}
@@ -1,45 +1,228 @@
 use axum::{
-    extract::Extension,
+    extract::{Path, State},
     http::StatusCode,
-    response::IntoResponse,
     Json,
 };
-use std::sync::Arc;
 use uuid::Uuid;
 
+#[allow(unused_imports)]
+use crate::error::ErrorResponse;
 use crate::{
-    error::AppError, 
-    services::trade_service::TradeService
+    error::AppError,
+    extractors::{AuthUser, JsonBody},
+    i18n::{messages, Message},
+    models::response::{ApiResponse, TradeResponse, TradesResponse},
+    models::trade::{PossibleTrade, ProposeTradeRequest, Trade},
+    state::AppState,
 };
 
-/// Handler para operações relacionadas a trocas
-pub struct TradeHandler {
-    trade_service: Arc<dyn TradeService>,
+/// Buscar trocas possíveis para o usuário autenticado
+///
+/// Retorna uma lista de trocas possíveis onde:
+/// - O usuário oferece um livro que outro usuário quer
+/// - O outro usuário oferece um livro que o usuário quer
+///
+/// As trocas vêm ordenadas pelo campo `score`, que combina a prioridade dada por
+/// cada parte ao livro que receberia, a reputação do parceiro (trocas concluídas)
+/// e a distância entre os dois, quando ambos informaram a localização.
+///
+/// O usuário é identificado automaticamente através do token JWT.
+#[utoipa::path(
+    get,
+    path = "/api/v1/trades/possible",
+    tag = "trades",
+    responses(
+        (status = 200, description = "Lista de trocas possíveis encontradas", body = [PossibleTrade]),
+        (status = 401, description = "Não autorizado - Token inválido ou ausente", body = ErrorResponse),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse),
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn get_possible_trades(
+    State(state): State<AppState>,
+    AuthUser { id: user_id, .. }: AuthUser,
+) -> Result<Json<Vec<PossibleTrade>>, AppError> {
+    let trades = state.trade_service.find_possible_trades(user_id).await?;
+
+    Ok(Json(trades))
 }
 
-impl TradeHandler {
-    pub fn new(trade_service: Arc<dyn TradeService>) -> Self {
-        Self { trade_service }
-    }
+/// Propor uma troca entre dois exemplares
+///
+/// O usuário autenticado oferece um dos seus exemplares em troca de um exemplar
+/// de outro usuário. Os dois exemplares ficam reservados enquanto a proposta
+/// estiver pendente ou aceita, e não podem ser prometidos em outra troca.
+#[utoipa::path(
+    post,
+    path = "/api/v1/trades",
+    tag = "trades",
+    request_body = ProposeTradeRequest,
+    responses(
+        (status = 201, description = "Proposta de troca enviada", body = TradeResponse),
+        (status = 400, description = "Exemplar fora da sua lista (COPY_NOT_OFFERED), indisponível (COPY_NOT_AVAILABLE) ou troca com você mesmo (TRADE_WITH_SELF)", body = ErrorResponse),
+        (status = 409, description = "Exemplar já prometido em outra troca (COPY_RESERVED)", body = ErrorResponse),
+        (status = 401, description = "Não autorizado - Token inválido ou ausente", body = ErrorResponse),
+        (status = 404, description = "Exemplar não encontrado", body = ErrorResponse),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse),
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn propose_trade(
+    State(state): State<AppState>,
+    AuthUser { id: user_id, .. }: AuthUser,
+    JsonBody(request): JsonBody<ProposeTradeRequest>,
+) -> Result<(StatusCode, Json<TradeResponse>), AppError> {
+    let trade = state.trade_service.propose_trade(user_id, &request).await?;
 
-    /// Busca possíveis trocas para o usuário autenticado
-    pub async fn get_possible_trades(
-        &self,
-        Extension(user_id): Extension<Uuid>,
-    ) -> Result<impl IntoResponse, AppError> {
-        let trades = self.trade_service.find_possible_trades(user_id).await?;
-        
-        Ok((StatusCode::OK, Json(trades)))
-    }
+    Ok((StatusCode::CREATED, trade_response(messages::TRADE_PROPOSED, trade)))
 }
 
-/// Função standalone para uso com axum routes
-/// Busca possíveis trocas para o usuário autenticado
-pub async fn get_possible_trades(
-    Extension(user_id): Extension<Uuid>,
-    Extension(trade_service): Extension<Arc<dyn TradeService>>,
-) -> Result<impl IntoResponse, AppError> {
-    let trades = trade_service.find_possible_trades(user_id).await?;
-    
-    Ok((StatusCode::OK, Json(trades)))
-} 
\ No newline at end of file
+/// Listar as trocas propostas ou recebidas pelo usuário autenticado
+#[utoipa::path(
+    get,
+    path = "/api/v1/trades",
+    tag = "trades",
+    responses(
+        (status = 200, description = "Lista de trocas do usuário", body = TradesResponse),
+        (status = 401, description = "Não autorizado - Token inválido ou ausente", body = ErrorResponse),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse),
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn get_user_trades(
+    State(state): State<AppState>,
+    AuthUser { id: user_id, .. }: AuthUser,
+) -> Result<Json<TradesResponse>, AppError> {
+    let trades = state.trade_service.find_user_trades(user_id).await?;
+
+    Ok(Json(ApiResponse::new(messages::TRADES_FOUND, trades)))
+}
+
+/// Aceitar uma proposta de troca recebida
+#[utoipa::path(
+    post,
+    path = "/api/v1/trades/{trade_id}/accept",
+    tag = "trades",
+    params(
+        ("trade_id", description = "ID da troca")
+    ),
+    responses(
+        (status = 200, description = "Troca aceita", body = TradeResponse),
+        (status = 403, description = "O usuário não pode executar esta operação", body = ErrorResponse),
+        (status = 409, description = "A situação da troca não permite a operação (TRADE_STATUS_CONFLICT)", body = ErrorResponse),
+        (status = 401, description = "Não autorizado - Token inválido ou ausente", body = ErrorResponse),
+        (status = 404, description = "Troca não encontrada", body = ErrorResponse),
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn accept_trade(
+    State(state): State<AppState>,
+    AuthUser { id: user_id, .. }: AuthUser,
+    Path(trade_id): Path<Uuid>,
+) -> Result<Json<TradeResponse>, AppError> {
+    let trade = state.trade_service.accept_trade(trade_id, user_id).await?;
+
+    Ok(trade_response(messages::TRADE_ACCEPTED, trade))
+}
+
+/// Recusar uma proposta de troca recebida, liberando os exemplares
+#[utoipa::path(
+    post,
+    path = "/api/v1/trades/{trade_id}/reject",
+    tag = "trades",
+    params(
+        ("trade_id", description = "ID da troca")
+    ),
+    responses(
+        (status = 200, description = "Troca recusada", body = TradeResponse),
+        (status = 403, description = "O usuário não pode executar esta operação", body = ErrorResponse),
+        (status = 409, description = "A situação da troca não permite a operação (TRADE_STATUS_CONFLICT)", body = ErrorResponse),
+        (status = 401, description = "Não autorizado - Token inválido ou ausente", body = ErrorResponse),
+        (status = 404, description = "Troca não encontrada", body = ErrorResponse),
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn reject_trade(
+    State(state): State<AppState>,
+    AuthUser { id: user_id, .. }: AuthUser,
+    Path(trade_id): Path<Uuid>,
+) -> Result<Json<TradeResponse>, AppError> {
+    let trade = state.trade_service.reject_trade(trade_id, user_id).await?;
+
+    Ok(trade_response(messages::TRADE_REJECTED, trade))
+}
+
+/// Cancelar uma troca em andamento, liberando os exemplares
+#[utoipa::path(
+    post,
+    path = "/api/v1/trades/{trade_id}/cancel",
+    tag = "trades",
+    params(
+        ("trade_id", description = "ID da troca")
+    ),
+    responses(
+        (status = 200, description = "Troca cancelada", body = TradeResponse),
+        (status = 403, description = "O usuário não pode executar esta operação", body = ErrorResponse),
+        (status = 409, description = "A situação da troca não permite a operação (TRADE_STATUS_CONFLICT)", body = ErrorResponse),
+        (status = 401, description = "Não autorizado - Token inválido ou ausente", body = ErrorResponse),
+        (status = 404, description = "Troca não encontrada", body = ErrorResponse),
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn cancel_trade(
+    State(state): State<AppState>,
+    AuthUser { id: user_id, .. }: AuthUser,
+    Path(trade_id): Path<Uuid>,
+) -> Result<Json<TradeResponse>, AppError> {
+    let trade = state.trade_service.cancel_trade(trade_id, user_id).await?;
+
+    Ok(trade_response(messages::TRADE_CANCELLED, trade))
+}
+
+/// Concluir uma troca aceita
+///
+/// Os exemplares entregues são retirados da lista de possuídos dos seus donos e
+/// cada livro recebido sai da lista de desejados de quem o recebeu e entra na
+/// sua lista de possuídos.
+#[utoipa::path(
+    post,
+    path = "/api/v1/trades/{trade_id}/complete",
+    tag = "trades",
+    params(
+        ("trade_id", description = "ID da troca")
+    ),
+    responses(
+        (status = 200, description = "Troca concluída", body = TradeResponse),
+        (status = 409, description = "A situação da troca não permite a operação (TRADE_STATUS_CONFLICT)", body = ErrorResponse),
+        (status = 401, description = "Não autorizado - Token inválido ou ausente", body = ErrorResponse),
+        (status = 404, description = "Troca não encontrada", body = ErrorResponse),
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn complete_trade(
+    State(state): State<AppState>,
+    AuthUser { id: user_id, .. }: AuthUser,
+    Path(trade_id): Path<Uuid>,
+) -> Result<Json<TradeResponse>, AppError> {
+    let trade = state.trade_service.complete_trade(trade_id, user_id).await?;
+
+    Ok(trade_response(messages::TRADE_COMPLETED, trade))
+}
+
+fn trade_response(message: Message, trade: Trade) -> Json<TradeResponse> {
+    Json(ApiResponse::new(message, trade))
+}
@@ -0,0 +1,153 @@
+use axum::{extract::State, Json};
+
+use crate::error::AppError;
+#[allow(unused_imports)]
+use crate::error::ErrorResponse;
+use crate::extractors::{AuthUser, JsonBody};
+use crate::i18n::messages;
+use crate::models::response::{
+    AccessTokenResponse, ApiResponse, MessageResponse, RecoveryCodesResponse, TwoFactorSetupResponse,
+};
+use crate::models::two_factor::{DisableTwoFactorDto, EnableTwoFactorDto, VerifyTwoFactorDto};
+use crate::state::AppState;
+
+/// Inicia a ativação da verificação em duas etapas
+///
+/// Gera um segredo TOTP e a URI `otpauth://` a ser exibida como QR code no
+/// aplicativo autenticador. A verificação só passa a valer depois de
+/// confirmada em `/api/v1/auth/2fa/enable`; repetir esta chamada antes disso
+/// substitui o segredo.
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/2fa/setup",
+    responses(
+        (status = 200, description = "Segredo gerado", body = TwoFactorSetupResponse),
+        (status = 401, description = "Não autorizado (UNAUTHORIZED)", body = ErrorResponse),
+        (status = 409, description = "A verificação já está ativa (TWO_FACTOR_ALREADY_ENABLED)", body = ErrorResponse),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse)
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "auth"
+)]
+pub async fn setup(
+    State(state): State<AppState>,
+    AuthUser { id: user_id, .. }: AuthUser,
+) -> Result<Json<TwoFactorSetupResponse>, AppError> {
+    let setup = state.two_factor_service.setup(&user_id).await?;
+
+    Ok(Json(ApiResponse::new(messages::TWO_FACTOR_SETUP_STARTED, setup)))
+}
+
+/// Ativa a verificação em duas etapas
+///
+/// Confirma o segredo com um código do aplicativo autenticador e retorna 10
+/// códigos de recuperação. Eles são exibidos apenas nesta resposta e cada um
+/// pode substituir o código do autenticador uma única vez.
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/2fa/enable",
+    request_body(
+        content = EnableTwoFactorDto,
+        example = json!({
+            "code": "123456"
+        })
+    ),
+    responses(
+        (status = 200, description = "Verificação ativada", body = RecoveryCodesResponse),
+        (status = 400, description = "Ativação não iniciada (TWO_FACTOR_NOT_STARTED) ou código inválido (TWO_FACTOR_CODE_INVALID)", body = ErrorResponse),
+        (status = 401, description = "Não autorizado (UNAUTHORIZED)", body = ErrorResponse),
+        (status = 409, description = "A verificação já está ativa (TWO_FACTOR_ALREADY_ENABLED)", body = ErrorResponse),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse)
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "auth"
+)]
+pub async fn enable(
+    State(state): State<AppState>,
+    AuthUser { id: user_id, .. }: AuthUser,
+    JsonBody(dto): JsonBody<EnableTwoFactorDto>,
+) -> Result<Json<RecoveryCodesResponse>, AppError> {
+    let recovery_codes = state.two_factor_service.enable(&user_id, &dto.code).await?;
+
+    Ok(Json(ApiResponse::new(messages::TWO_FACTOR_ENABLED, recovery_codes)))
+}
+
+/// Desativa a verificação em duas etapas
+///
+/// Exige a senha atual ou, para contas que entram apenas pelo Google ou
+/// GitHub, um código do autenticador ou de recuperação. Códigos inválidos
+/// contam para o mesmo bloqueio do login. O segredo e os códigos de
+/// recuperação são removidos.
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/2fa/disable",
+    request_body(
+        content = DisableTwoFactorDto,
+        example = json!({
+            "password": "senha123"
+        })
+    ),
+    responses(
+        (status = 200, description = "Verificação desativada", body = MessageResponse),
+        (status = 400, description = "A verificação não está ativa (TWO_FACTOR_NOT_ENABLED) ou nenhuma comprovação foi informada (VALIDATION_FAILED)", body = ErrorResponse),
+        (status = 401, description = "Não autorizado (UNAUTHORIZED)", body = ErrorResponse),
+        (status = 403, description = "Senha ou código incorreto (FORBIDDEN)", body = ErrorResponse),
+        (status = 429, description = "Muitos códigos inválidos (TOO_MANY_REQUESTS)", body = ErrorResponse),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse)
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "auth"
+)]
+pub async fn disable(
+    State(state): State<AppState>,
+    AuthUser { id: user_id, .. }: AuthUser,
+    JsonBody(dto): JsonBody<DisableTwoFactorDto>,
+) -> Result<Json<MessageResponse>, AppError> {
+    state.two_factor_service.disable(&user_id, &dto).await?;
+
+    Ok(Json(MessageResponse::new(messages::TWO_FACTOR_DISABLED)))
+}
+
+/// Conclui o login com a verificação em duas etapas
+///
+/// Recebe o desafio retornado pelo login (válido por 5 minutos) e um código
+/// do aplicativo autenticador ou um código de recuperação. Cada código do
+/// autenticador é aceito uma única vez. Após 5 códigos inválidos, a
+/// verificação da conta fica bloqueada por 15 minutos.
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/2fa/verify",
+    request_body(
+        content = VerifyTwoFactorDto,
+        example = json!({
+            "challenge_token": "eyJhbGciOiJIUzI1NiJ9...",
+            "code": "123456"
+        })
+    ),
+    responses(
+        (status = 200, description = "Login realizado com sucesso", body = AccessTokenResponse),
+        (status = 401, description = "Desafio inválido ou expirado, ou código inválido (UNAUTHORIZED)", body = ErrorResponse),
+        (status = 403, description = "Conta suspensa (FORBIDDEN)", body = ErrorResponse),
+        (status = 429, description = "Muitas tentativas a partir do mesmo IP ou verificação bloqueada após vários códigos inválidos (RATE_LIMITED)", body = ErrorResponse,
+            headers(("Retry-After" = u64, description = "Segundos até uma nova tentativa ser aceita"))),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse)
+    ),
+    tag = "auth"
+)]
+pub async fn verify(
+    State(state): State<AppState>,
+    JsonBody(dto): JsonBody<VerifyTwoFactorDto>,
+) -> Result<Json<AccessTokenResponse>, AppError> {
+    let token = state
+        .two_factor_service
+        .verify_login(&dto.challenge_token, &dto.code)
+        .await?;
+
+    Ok(Json(ApiResponse::new(messages::LOGIN_SUCCEEDED, token)))
+}
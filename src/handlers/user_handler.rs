@@ -0,0 +1,62 @@
+use axum::{extract::State, Json};
+
+use crate::error::AppError;
+#[allow(unused_imports)]
+use crate::error::ErrorResponse;
+use crate::extractors::{AuthUser, ValidatedJson};
+use crate::i18n::messages;
+use crate::models::response::{ApiResponse, MessageResponse, UserLocationResponse};
+use crate::models::user::UserLocation;
+use crate::state::AppState;
+
+/// Definir a localização do usuário
+///
+/// A localização é opcional e serve apenas para priorizar sugestões de troca
+/// com usuários próximos.
+#[utoipa::path(
+    put,
+    path = "/api/v1/users/me/location",
+    tag = "users",
+    request_body = UserLocation,
+    responses(
+        (status = 200, description = "Localização atualizada com sucesso", body = UserLocationResponse),
+        (status = 400, description = "Coordenadas inválidas", body = ErrorResponse),
+        (status = 401, description = "Não autorizado", body = ErrorResponse),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse),
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn update_location(
+    State(state): State<AppState>,
+    AuthUser { id: user_id, .. }: AuthUser,
+    ValidatedJson(location): ValidatedJson<UserLocation>,
+) -> Result<Json<UserLocationResponse>, AppError> {
+    let location = state.user_service.update_location(&user_id, location).await?;
+
+    Ok(Json(ApiResponse::new(messages::LOCATION_UPDATED, location)))
+}
+
+/// Remover a localização do usuário
+#[utoipa::path(
+    delete,
+    path = "/api/v1/users/me/location",
+    tag = "users",
+    responses(
+        (status = 200, description = "Localização removida com sucesso", body = MessageResponse),
+        (status = 401, description = "Não autorizado", body = ErrorResponse),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse),
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn clear_location(
+    State(state): State<AppState>,
+    AuthUser { id: user_id, .. }: AuthUser,
+) -> Result<Json<MessageResponse>, AppError> {
+    state.user_service.clear_location(&user_id).await?;
+
+    Ok(Json(MessageResponse::new(messages::LOCATION_REMOVED)))
+}
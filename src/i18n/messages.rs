@@ -0,0 +1,414 @@
+use super::Message;
+
+// Catálogo de mensagens da API. Cada mensagem tem um código estável e o texto
+// em cada idioma suportado; os códigos das mensagens de validação são os mesmos
+// usados nas regras do validator e enviados no campo `code` dos erros de campo.
+
+macro_rules! catalog {
+    ($($name:ident => $code:literal, $pt_br:literal, $en:literal;)*) => {
+        $(pub const $name: Message = Message { code: $code, pt_br: $pt_br, en: $en };)*
+
+        /// Todas as mensagens do catálogo
+        pub const ALL: &[Message] = &[$($name),*];
+    };
+}
+
+/// Busca uma mensagem pelo código
+pub fn find(code: &str) -> Option<&'static Message> {
+    ALL.iter().find(|message| message.code == code)
+}
+
+catalog! {
+    // Respostas de sucesso
+    USER_REGISTERED => "user_registered",
+        "Usuário registrado com sucesso",
+        "User registered successfully";
+    LOGIN_SUCCEEDED => "login_succeeded",
+        "Login realizado com sucesso",
+        "Logged in successfully";
+    TWO_FACTOR_REQUIRED => "two_factor_required",
+        "Informe o código do aplicativo autenticador para concluir o login",
+        "Enter the code from your authenticator app to finish signing in";
+    TWO_FACTOR_SETUP_STARTED => "two_factor_setup_started",
+        "Leia o QR code no aplicativo autenticador e confirme com um código",
+        "Scan the QR code with your authenticator app and confirm with a code";
+    TWO_FACTOR_ENABLED => "two_factor_enabled",
+        "Verificação em duas etapas ativada. Guarde os códigos de recuperação em um lugar seguro",
+        "Two-factor authentication enabled. Keep the recovery codes somewhere safe";
+    TWO_FACTOR_DISABLED => "two_factor_disabled",
+        "Verificação em duas etapas desativada",
+        "Two-factor authentication disabled";
+    USER_BOOKS_FOUND => "user_books_found",
+        "Livros do usuário recuperados com sucesso",
+        "User books retrieved successfully";
+    OFFERED_COPIES_FOUND => "offered_copies_found",
+        "Exemplares oferecidos recuperados com sucesso",
+        "Offered copies retrieved successfully";
+    BOOKS_FOUND => "books_found",
+        "Livros encontrados com sucesso",
+        "Books found successfully";
+    BOOK_FOUND => "book_found",
+        "Livro encontrado com sucesso",
+        "Book found successfully";
+    BOOKS_PROCESSED => "books_processed",
+        "Livros processados",
+        "Books processed";
+    OFFERED_BOOK_ADDED => "offered_book_added",
+        "Livro adicionado à lista de possuídos com sucesso",
+        "Book added to your offered list successfully";
+    OFFERED_COPY_UPDATED => "offered_copy_updated",
+        "Exemplar atualizado com sucesso",
+        "Copy updated successfully";
+    OFFERED_BOOK_REMOVED => "offered_book_removed",
+        "Livro removido da lista de possuídos com sucesso",
+        "Book removed from your offered list successfully";
+    OFFERED_COPY_REMOVED => "offered_copy_removed",
+        "Exemplar removido da lista de possuídos com sucesso",
+        "Copy removed from your offered list successfully";
+    WANTED_BOOK_ADDED => "wanted_book_added",
+        "Livro adicionado à lista de desejados com sucesso",
+        "Book added to your wanted list successfully";
+    WANTED_PRIORITY_UPDATED => "wanted_priority_updated",
+        "Prioridade atualizada com sucesso",
+        "Priority updated successfully";
+    WANTED_BOOK_REMOVED => "wanted_book_removed",
+        "Livro removido da lista de desejados com sucesso",
+        "Book removed from your wanted list successfully";
+    WANTED_BOOK_ACQUIRED => "wanted_book_acquired",
+        "Livro movido para a lista de possuídos com sucesso",
+        "Book moved to your offered list successfully";
+    PHOTO_ADDED => "photo_added",
+        "Foto adicionada com sucesso",
+        "Photo added successfully";
+    PHOTO_REMOVED => "photo_removed",
+        "Foto removida com sucesso",
+        "Photo removed successfully";
+    SHELF_IMPORTED => "shelf_imported",
+        "Importação concluída",
+        "Import finished";
+    TRADE_PROPOSED => "trade_proposed",
+        "Proposta de troca enviada com sucesso",
+        "Trade proposal sent successfully";
+    TRADES_FOUND => "trades_found",
+        "Trocas recuperadas com sucesso",
+        "Trades retrieved successfully";
+    TRADE_ACCEPTED => "trade_accepted",
+        "Troca aceita com sucesso",
+        "Trade accepted successfully";
+    TRADE_REJECTED => "trade_rejected",
+        "Troca recusada com sucesso",
+        "Trade rejected successfully";
+    TRADE_CANCELLED => "trade_cancelled",
+        "Troca cancelada com sucesso",
+        "Trade cancelled successfully";
+    TRADE_COMPLETED => "trade_completed",
+        "Troca concluída com sucesso",
+        "Trade completed successfully";
+    LOCATION_UPDATED => "location_updated",
+        "Localização atualizada com sucesso",
+        "Location updated successfully";
+    LOCATION_REMOVED => "location_removed",
+        "Localização removida com sucesso",
+        "Location removed successfully";
+
+    // Erros gerais
+    INVALID_CREDENTIALS => "invalid_credentials",
+        "Credenciais inválidas",
+        "Invalid credentials";
+    INTERNAL_ERROR => "internal_error",
+        "Ocorreu um erro interno. Tente novamente mais tarde",
+        "An internal error occurred. Please try again later";
+    RECORD_NOT_FOUND => "record_not_found",
+        "Registro não encontrado",
+        "Record not found";
+    FIELD_INVALID => "field_invalid",
+        "Valor inválido para {}",
+        "Invalid value for {}";
+    UPSTREAM_REQUEST_FAILED => "upstream_request_failed",
+        "Erro na requisição HTTP: {}",
+        "HTTP request failed: {}";
+    UPSTREAM_STATUS => "upstream_status",
+        "Erro na requisição: Status {}",
+        "Request failed: Status {}";
+    UPSTREAM_RESPONSE_INVALID => "upstream_response_invalid",
+        "Erro ao processar resposta: {}",
+        "Failed to process response: {}";
+    TOO_MANY_REQUESTS => "too_many_requests",
+        "Muitas requisições. Tente novamente em {} segundos",
+        "Too many requests. Try again in {} seconds";
+
+    // Autenticação e usuários
+    TOKEN_MISSING => "token_missing",
+        "Token de autenticação ausente",
+        "Missing authentication token";
+    TOKEN_FORMAT_INVALID => "token_format_invalid",
+        "Formato de token inválido. Use Bearer <token>",
+        "Invalid token format. Use Bearer <token>";
+    TOKEN_INVALID => "token_invalid",
+        "Token inválido: {}",
+        "Invalid token: {}";
+    TOKEN_KEY_UNKNOWN => "token_key_unknown",
+        "Token assinado com uma chave desconhecida",
+        "Token signed with an unknown key";
+    TOKEN_USER_INVALID => "token_user_invalid",
+        "ID de usuário inválido no token",
+        "Invalid user ID in token";
+    USER_NOT_FOUND => "user_not_found",
+        "Usuário não encontrado",
+        "User not found";
+    ACCOUNT_NOT_FOUND => "account_not_found",
+        "A conta deste token não existe mais",
+        "The account for this token no longer exists";
+    ACCOUNT_BANNED => "account_banned",
+        "Esta conta foi suspensa",
+        "This account has been suspended";
+    USER_ID_NOT_FOUND => "user_id_not_found",
+        "Usuário com ID {} não encontrado",
+        "User with ID {} not found";
+    EMAIL_ALREADY_IN_USE => "email_already_in_use",
+        "Email já está em uso",
+        "Email is already in use";
+    LOGIN_LOCKED => "login_locked",
+        "Login bloqueado temporariamente após várias tentativas sem sucesso. Tente novamente em {} segundos",
+        "Login temporarily locked after too many failed attempts. Try again in {} seconds";
+    TWO_FACTOR_ALREADY_ENABLED => "two_factor_already_enabled",
+        "A verificação em duas etapas já está ativada",
+        "Two-factor authentication is already enabled";
+    TWO_FACTOR_NOT_STARTED => "two_factor_not_started",
+        "Inicie a ativação da verificação em duas etapas antes de confirmá-la",
+        "Start the two-factor setup before confirming it";
+    TWO_FACTOR_NOT_ENABLED => "two_factor_not_enabled",
+        "A verificação em duas etapas não está ativada",
+        "Two-factor authentication is not enabled";
+    TWO_FACTOR_CODE_INVALID => "two_factor_code_invalid",
+        "Código de verificação inválido",
+        "Invalid verification code";
+    TWO_FACTOR_LOCKED => "two_factor_locked",
+        "Muitos códigos inválidos. Tente novamente em {} segundos",
+        "Too many invalid codes. Try again in {} seconds";
+    TWO_FACTOR_PROOF_REQUIRED => "two_factor_proof_required",
+        "Informe a senha atual ou um código de verificação",
+        "Provide the current password or a verification code";
+    PASSWORD_INCORRECT => "password_incorrect",
+        "Senha incorreta",
+        "Incorrect password";
+    OAUTH_PROVIDER_UNKNOWN => "oauth_provider_unknown",
+        "Login com {} não está disponível",
+        "Sign-in with {} is not available";
+    OAUTH_CALLBACK_INVALID => "oauth_callback_invalid",
+        "Retorno do provedor sem os parâmetros code e state",
+        "Provider callback is missing the code and state parameters";
+    OAUTH_STATE_INVALID => "oauth_state_invalid",
+        "Login expirado ou inválido. Inicie o login novamente",
+        "Sign-in expired or invalid. Please start again";
+    OAUTH_AUTHORIZATION_DENIED => "oauth_authorization_denied",
+        "O provedor não autorizou o login: {}",
+        "The provider did not authorize the sign-in: {}";
+    OAUTH_ID_TOKEN_INVALID => "oauth_id_token_invalid",
+        "id_token do provedor inválido: {}",
+        "Invalid id_token from provider: {}";
+    OAUTH_EMAIL_NOT_VERIFIED => "oauth_email_not_verified",
+        "O provedor não confirmou o email da conta. Confirme o email no provedor e tente novamente",
+        "The provider has not verified the account email. Verify it with the provider and try again";
+
+    // Validação de campos
+    NAME_REQUIRED => "name_required",
+        "O nome não pode estar vazio",
+        "Name cannot be empty";
+    NAME_TOO_LONG => "name_too_long",
+        "O nome deve ter menos de 255 caracteres",
+        "Name must be shorter than 255 characters";
+    EMAIL_REQUIRED => "email_required",
+        "O email não pode estar vazio",
+        "Email cannot be empty";
+    EMAIL_TOO_LONG => "email_too_long",
+        "O email deve ter menos de 255 caracteres",
+        "Email must be shorter than 255 characters";
+    EMAIL_INVALID => "email_invalid",
+        "Formato de email inválido",
+        "Invalid email format";
+    PASSWORD_REQUIRED => "password_required",
+        "A senha não pode estar vazia",
+        "Password cannot be empty";
+    PASSWORD_TOO_SHORT => "password_too_short",
+        "A senha deve ter pelo menos 6 caracteres",
+        "Password must have at least 6 characters";
+    LATITUDE_OUT_OF_RANGE => "latitude_out_of_range",
+        "A latitude deve estar entre -90 e 90",
+        "Latitude must be between -90 and 90";
+    LONGITUDE_OUT_OF_RANGE => "longitude_out_of_range",
+        "A longitude deve estar entre -180 e 180",
+        "Longitude must be between -180 and 180";
+    LANGUAGE_LENGTH => "language_length",
+        "O idioma deve ter entre 2 e 10 caracteres",
+        "Language must have between 2 and 10 characters";
+    NOTES_TOO_LONG => "notes_too_long",
+        "As observações devem ter no máximo 1000 caracteres",
+        "Notes must have at most 1000 characters";
+    SEARCH_QUERY_EMPTY => "search_query_empty",
+        "A consulta não pode estar vazia",
+        "The search query cannot be empty";
+
+    // Corpo das requisições
+    BODY_MALFORMED => "body_malformed",
+        "O corpo da requisição não é um JSON válido: {}",
+        "The request body is not valid JSON: {}";
+    BODY_INVALID => "body_invalid",
+        "O corpo da requisição não tem o formato esperado: {}",
+        "The request body does not have the expected shape: {}";
+    BODY_CONTENT_TYPE => "body_content_type",
+        "Envie o corpo da requisição em JSON, com o header Content-Type: application/json",
+        "Send the request body as JSON, with the Content-Type: application/json header";
+    BODY_TOO_LARGE => "body_too_large",
+        "O corpo da requisição passa do tamanho máximo permitido",
+        "The request body exceeds the maximum allowed size";
+    BODY_UNREADABLE => "body_unreadable",
+        "Não foi possível ler o corpo da requisição",
+        "Could not read the request body";
+
+    // Livros e listas
+    BOOK_NOT_FOUND => "book_not_found",
+        "Livro não encontrado",
+        "Book not found";
+    BOOK_ID_NOT_FOUND => "book_id_not_found",
+        "Livro com ID {} não encontrado",
+        "Book with ID {} not found";
+    PUBLISHED_DATE_INVALID => "published_date_invalid",
+        "A data '{}' deve estar no formato AAAA-MM-DD",
+        "The date '{}' must be in the YYYY-MM-DD format";
+    PRIORITY_OUT_OF_RANGE => "priority_out_of_range",
+        "A prioridade deve estar entre {} e {}",
+        "Priority must be between {} and {}";
+    QUANTITY_OUT_OF_RANGE => "quantity_out_of_range",
+        "A quantidade deve estar entre 1 e {}",
+        "Quantity must be between 1 and {}";
+    BATCH_SIZE_OUT_OF_RANGE => "batch_size_out_of_range",
+        "Informe entre 1 e {} livros por requisição",
+        "Send between 1 and {} books per request";
+    BOOK_ALREADY_WANTED => "book_already_wanted",
+        "Este livro já está na sua lista de desejados",
+        "This book is already on your wanted list";
+    BOOK_ALREADY_OFFERED => "book_already_offered",
+        "Este livro já está na sua lista de possuídos",
+        "This book is already on your offered list";
+    BOOK_NOT_WANTED => "book_not_wanted",
+        "Este livro não está na sua lista de desejados",
+        "This book is not on your wanted list";
+    BOOK_NOT_OFFERED => "book_not_offered",
+        "Este livro não está na sua lista de possuídos",
+        "This book is not on your offered list";
+    COPY_NOT_OFFERED => "copy_not_offered",
+        "Este exemplar não está na sua lista de possuídos",
+        "This copy is not on your offered list";
+    COPY_NOT_FOUND => "copy_not_found",
+        "Exemplar não encontrado",
+        "Copy not found";
+    BOOK_COPIES_RESERVED => "book_copies_reserved",
+        "Este livro tem exemplares reservados em trocas em andamento",
+        "This book has copies reserved in ongoing trades";
+    BOOKS_COPIES_RESERVED => "books_copies_reserved",
+        "Um dos livros tem exemplares reservados em trocas em andamento",
+        "One of the books has copies reserved in ongoing trades";
+    COPY_RESERVED => "copy_reserved",
+        "Este exemplar está reservado em uma troca em andamento",
+        "This copy is reserved in an ongoing trade";
+
+    // Fotos
+    PHOTO_EMPTY => "photo_empty",
+        "A foto não pode estar vazia",
+        "The photo cannot be empty";
+    PHOTO_TOO_LARGE => "photo_too_large",
+        "A foto deve ter no máximo {} bytes",
+        "The photo must have at most {} bytes";
+    PHOTO_TYPE_UNSUPPORTED => "photo_type_unsupported",
+        "Tipo de foto não suportado: {}. Use JPEG, PNG ou WebP",
+        "Unsupported photo type: {}. Use JPEG, PNG or WebP";
+    PHOTO_CONTENT_MISMATCH => "photo_content_mismatch",
+        "O conteúdo da foto não corresponde ao tipo informado",
+        "The photo content does not match the given type";
+    PHOTO_LIMIT_REACHED => "photo_limit_reached",
+        "Cada exemplar pode ter no máximo {} fotos",
+        "Each copy can have at most {} photos";
+    PHOTO_NOT_FOUND => "photo_not_found",
+        "Foto não encontrada",
+        "Photo not found";
+    PHOTO_KEY_INVALID => "photo_key_invalid",
+        "Chave de foto inválida: {}",
+        "Invalid photo key: {}";
+
+    // Trocas
+    TRADE_NOT_FOUND => "trade_not_found",
+        "Troca não encontrada",
+        "Trade not found";
+    TRADE_STATUS_CONFLICT => "trade_status_conflict",
+        "A situação atual da troca não permite esta operação",
+        "The current trade status does not allow this operation";
+    TRADE_WITH_SELF => "trade_with_self",
+        "Não é possível propor uma troca com você mesmo",
+        "You cannot propose a trade to yourself";
+    COPY_NOT_AVAILABLE => "copy_not_available",
+        "Este exemplar não está disponível para troca",
+        "This copy is not available for trade";
+    COPY_ALREADY_PROMISED => "copy_already_promised",
+        "Este exemplar já está prometido em outra troca",
+        "This copy is already promised in another trade";
+    TRADE_ACCEPT_FORBIDDEN => "trade_accept_forbidden",
+        "Apenas quem recebeu a proposta pode aceitá-la",
+        "Only the recipient of the proposal can accept it";
+    TRADE_REJECT_FORBIDDEN => "trade_reject_forbidden",
+        "Apenas quem recebeu a proposta pode recusá-la",
+        "Only the recipient of the proposal can reject it";
+    TRADE_CANCEL_FORBIDDEN => "trade_cancel_forbidden",
+        "Apenas quem fez a proposta pode cancelá-la",
+        "Only the proposer can cancel the proposal";
+
+    // Importação e exportação da estante
+    SHELF_FORMAT_UNKNOWN => "shelf_format_unknown",
+        "Formato desconhecido: {}. Use csv, goodreads ou json",
+        "Unknown format: {}. Use csv, goodreads or json";
+    SHELF_LIST_UNKNOWN => "shelf_list_unknown",
+        "Lista desconhecida: {}. Use offered ou wanted",
+        "Unknown list: {}. Use offered or wanted";
+    SHELF_EMPTY => "shelf_empty",
+        "O arquivo não contém livros",
+        "The file contains no books";
+    SHELF_TOO_LARGE => "shelf_too_large",
+        "O arquivo deve ter no máximo {} livros",
+        "The file must have at most {} books";
+    SHELF_JSON_INVALID => "shelf_json_invalid",
+        "O arquivo JSON deve conter uma lista de livros: {}",
+        "The JSON file must contain a list of books: {}";
+    SHELF_CSV_COLUMNS_MISSING => "shelf_csv_columns_missing",
+        "O CSV deve ter ao menos uma das colunas google_id, isbn ou title",
+        "The CSV must have at least one of the google_id, isbn or title columns";
+    SHELF_GOODREADS_TITLE_MISSING => "shelf_goodreads_title_missing",
+        "A exportação do Goodreads deve ter a coluna Title",
+        "The Goodreads export must have the Title column";
+    SHELF_CSV_UNCLOSED_QUOTE => "shelf_csv_unclosed_quote",
+        "CSV inválido: campo entre aspas não foi fechado",
+        "Invalid CSV: a quoted field was not closed";
+    SHELF_ROW_INVALID => "shelf_row_invalid",
+        "Linha inválida: {}",
+        "Invalid row: {}";
+    SHELF_PRIORITY_INVALID => "shelf_priority_invalid",
+        "Prioridade inválida: {}",
+        "Invalid priority: {}";
+    SHELF_CONDITION_INVALID => "shelf_condition_invalid",
+        "Estado de conservação inválido: {}",
+        "Invalid condition: {}";
+    SHELF_ENTRY_UNIDENTIFIED => "shelf_entry_unidentified",
+        "Informe google_id, isbn ou title",
+        "Provide google_id, isbn or title";
+    SHELF_BOOK_NOT_ON_GOOGLE => "shelf_book_not_on_google",
+        "Livro não encontrado no Google Books",
+        "Book not found on Google Books";
+    SHELF_GOOGLE_ID_INVALID => "shelf_google_id_invalid",
+        "google_id inválido: use apenas letras, números, _ e -, com até 64 caracteres",
+        "Invalid google_id: use only letters, digits, _ and -, up to 64 characters";
+    SHELF_BOOK_IS_WANTED => "shelf_book_is_wanted",
+        "Este livro está na sua lista de desejados",
+        "This book is on your wanted list";
+    SHELF_BOOK_IS_OFFERED => "shelf_book_is_offered",
+        "Este livro está na sua lista de possuídos",
+        "This book is on your offered list";
+}
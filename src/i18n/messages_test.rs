@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use validator::Validate;
+
+    use crate::error::AppError;
+    use crate::i18n::{current_locale, messages, with_locale, Locale};
+    use crate::models::user::CreateUserDto;
+
+    #[test]
+    fn test_negotiate_locale() {
+        assert_eq!(Locale::negotiate("en"), Locale::En);
+        assert_eq!(Locale::negotiate("en-US,en;q=0.9"), Locale::En);
+        assert_eq!(Locale::negotiate("pt-BR,pt;q=0.9,en;q=0.8"), Locale::PtBr);
+        assert_eq!(Locale::negotiate("fr-FR,en;q=0.5,pt;q=0.7"), Locale::PtBr);
+        assert_eq!(Locale::negotiate("pt;q=0.2, EN-gb;q=0.8"), Locale::En);
+    }
+
+    #[test]
+    fn test_negotiate_falls_back_to_pt_br() {
+        assert_eq!(Locale::negotiate(""), Locale::PtBr);
+        assert_eq!(Locale::negotiate("*"), Locale::PtBr);
+        assert_eq!(Locale::negotiate("fr, de;q=0.9"), Locale::PtBr);
+        // Idioma recusado explicitamente com q=0
+        assert_eq!(Locale::negotiate("en;q=0"), Locale::PtBr);
+    }
+
+    #[test]
+    fn test_catalog_codes_are_unique_and_translated() {
+        let mut codes = HashSet::new();
+
+        for message in messages::ALL {
+            assert!(codes.insert(message.code), "Código repetido no catálogo: {}", message.code);
+            assert!(!message.en.is_empty(), "Mensagem sem tradução: {}", message.code);
+            assert_eq!(
+                message.pt_br.matches("{}").count(),
+                message.en.matches("{}").count(),
+                "Argumentos diferentes entre os idiomas: {}",
+                message.code
+            );
+        }
+    }
+
+    #[test]
+    fn test_format_fills_arguments_in_order() {
+        assert_eq!(
+            messages::PRIORITY_OUT_OF_RANGE.format(&[&1, &5]),
+            "A prioridade deve estar entre 1 e 5"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_messages_follow_the_request_locale() {
+        assert_eq!(current_locale(), Locale::PtBr);
+        assert_eq!(messages::BOOK_NOT_FOUND.text(), "Livro não encontrado");
+
+        let text = with_locale(Locale::En, async { messages::QUANTITY_OUT_OF_RANGE.format(&[&20]) }).await;
+        assert_eq!(text, "Quantity must be between 1 and 20");
+    }
+
+    #[tokio::test]
+    async fn test_validation_errors_are_translated() {
+        let dto = CreateUserDto {
+            name: "Teste".to_string(),
+            email: "teste@example.com".to_string(),
+            password: "123".to_string(),
+        };
+        let errors = dto.validate().unwrap_err();
+
+        let error = with_locale(Locale::En, async { AppError::from(errors) }).await;
+
+        match error {
+            AppError::InvalidFields(fields) => {
+                assert_eq!(fields[0].code, "password_too_short");
+                assert_eq!(fields[0].message, "Password must have at least 6 characters");
+            }
+            other => panic!("Erro inesperado: {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use std::fmt::{Display, Write};
+use std::future::Future;
+
+pub mod messages;
+
+#[cfg(test)]
+mod messages_test;
+
+/// Idiomas em que a API responde
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    #[default]
+    PtBr,
+    En,
+}
+
+impl Locale {
+    /// Tag do idioma, usada no header Content-Language
+    pub fn tag(&self) -> &'static str {
+        match self {
+            Locale::PtBr => "pt-BR",
+            Locale::En => "en",
+        }
+    }
+
+    /// Escolhe o idioma a partir do header Accept-Language
+    ///
+    /// Considera os pesos (`q`) informados e ignora idiomas não suportados; sem
+    /// nenhum idioma suportado, a resposta é em pt-BR.
+    pub fn negotiate(accept_language: &str) -> Locale {
+        let mut best: Option<(f32, Locale)> = None;
+
+        for entry in accept_language.split(',') {
+            let mut parts = entry.split(';');
+            let Some(locale) = parts.next().and_then(Locale::from_tag) else {
+                continue;
+            };
+            let quality = parts
+                .find_map(|part| part.trim().strip_prefix("q="))
+                .and_then(|q| q.parse::<f32>().ok())
+                .unwrap_or(1.0);
+
+            if quality > 0.0 && best.is_none_or(|(best_quality, _)| quality > best_quality) {
+                best = Some((quality, locale));
+            }
+        }
+
+        best.map(|(_, locale)| locale).unwrap_or_default()
+    }
+
+    // Apenas o idioma principal da tag importa: "pt", "pt-PT" e "pt-BR" são pt-BR
+    fn from_tag(tag: &str) -> Option<Locale> {
+        let language = tag.trim().split(['-', '_']).next().unwrap_or_default();
+        if language.eq_ignore_ascii_case("pt") {
+            Some(Locale::PtBr)
+        } else if language.eq_ignore_ascii_case("en") {
+            Some(Locale::En)
+        } else {
+            None
+        }
+    }
+}
+
+tokio::task_local! {
+    static LOCALE: Locale;
+}
+
+/// Idioma da requisição em andamento; fora de uma requisição, pt-BR
+pub fn current_locale() -> Locale {
+    LOCALE.try_with(|locale| *locale).unwrap_or_default()
+}
+
+/// Executa o futuro com o idioma informado
+///
+/// Usado pelo middleware e por tarefas criadas durante a requisição, que não
+/// herdam o idioma de quem as criou.
+pub async fn with_locale<F: Future>(locale: Locale, future: F) -> F::Output {
+    LOCALE.scope(locale, future).await
+}
+
+/// Mensagem do catálogo, com o texto em cada idioma suportado
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Message {
+    /// Código estável da mensagem
+    pub code: &'static str,
+    pub pt_br: &'static str,
+    pub en: &'static str,
+}
+
+impl Message {
+    pub fn text_in(&self, locale: Locale) -> &'static str {
+        match locale {
+            Locale::PtBr => self.pt_br,
+            Locale::En => self.en,
+        }
+    }
+
+    /// Texto no idioma da requisição em andamento
+    pub fn text(&self) -> String {
+        self.text_in(current_locale()).to_string()
+    }
+
+    /// Texto no idioma da requisição, com cada `{}` substituído por um argumento
+    pub fn format(&self, args: &[&dyn Display]) -> String {
+        let mut parts = self.text_in(current_locale()).split("{}");
+        let mut output = parts.next().unwrap_or_default().to_string();
+        let mut args = args.iter();
+
+        for part in parts {
+            if let Some(arg) = args.next() {
+                let _ = write!(output, "{}", arg);
+            }
+            output.push_str(part);
+        }
+
+        output
+    }
+}
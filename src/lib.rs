@@ -1,10 +1,23 @@
 pub mod app;
 pub mod config;
+#[cfg(test)]
+mod config_test;
 pub mod docs;
 pub mod error;
+#[cfg(test)]
+mod error_test;
+pub mod extractors;
+#[cfg(test)]
+mod extractors_test;
 pub mod handlers;
+pub mod i18n;
 pub mod middleware;
 pub mod models;
 pub mod repositories;
 pub mod routes;
-pub mod services; 
\ No newline at end of file
+pub mod server;
+pub mod services;
+pub mod state;
+pub mod telemetry;
+#[cfg(test)]
+mod telemetry_test;
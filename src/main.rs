@@ -1,41 +1,32 @@
-use troca_livros_api::app;
+use std::process::ExitCode;
+
 use troca_livros_api::config::Config;
-use std::net::{SocketAddr, TcpListener};
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use troca_livros_api::server;
+use troca_livros_api::telemetry;
 
 #[tokio::main]
-#[allow(dead_code)]
-async fn main() {
+async fn main() -> ExitCode {
     // Carregar variáveis de ambiente
     dotenv::dotenv().ok();
 
-    // Configurar logging
-    tracing_subscriber::registry()
-        .with(tracing_subscriber::EnvFilter::new(
-            std::env::var("RUST_LOG").unwrap_or_else(|_| "info".into()),
-        ))
-        .with(tracing_subscriber::fmt::layer())
-        .init();
-
-    // Carregar configuração
-    let config = Config::from_env().expect("Falha ao carregar configuração");
+    // Carregar configuração; o logging ainda não existe, então o erro vai para o stderr
+    let config = match Config::from_env() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Falha ao carregar configuração: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
 
-    let app = app::create_app(&config.database_url).await;
-
-    // Configura o listener na porta especificada
-    let listener = TcpListener::bind(SocketAddr::from(([0, 0, 0, 0], config.port)))
-        .expect("Falha ao vincular à porta");
-
-    // Iniciar servidor
-    tracing::info!("Servidor iniciado em {}", listener.local_addr().unwrap());
-    tracing::info!(
-        "Swagger: {}",
-        format!("http://localhost:{}/docs", config.port)
-    );
+    // Configurar logging
+    telemetry::init_tracing(&config);
 
-    axum::Server::from_tcp(listener)
-        .unwrap()
-        .serve(app.into_make_service())
-        .await
-        .unwrap();
+    // Iniciar servidor até receber SIGTERM ou SIGINT
+    match server::run(config).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            tracing::error!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
 }
@@ -0,0 +1,31 @@
+use axum::{
+    extract::Request,
+    http::{
+        header::{ACCEPT_LANGUAGE, CONTENT_LANGUAGE},
+        HeaderValue,
+    },
+    middleware::Next,
+    response::Response,
+};
+
+use crate::i18n::{with_locale, Locale};
+
+/// Define o idioma das mensagens de cada requisição
+///
+/// O idioma é negociado pelo header Accept-Language, com pt-BR como padrão, e
+/// informado no header Content-Language da resposta.
+pub async fn locale_middleware(request: Request, next: Next) -> Response {
+    let locale = request
+        .headers()
+        .get(ACCEPT_LANGUAGE)
+        .and_then(|value| value.to_str().ok())
+        .map(Locale::negotiate)
+        .unwrap_or_default();
+
+    let mut response = with_locale(locale, next.run(request)).await;
+    response
+        .headers_mut()
+        .insert(CONTENT_LANGUAGE, HeaderValue::from_static(locale.tag()));
+
+    response
+}
@@ -0,0 +1,29 @@
+use std::time::Instant;
+
+use axum::{
+    extract::{MatchedPath, Request},
+    middleware::Next,
+    response::Response,
+};
+
+use crate::services::metrics::metrics;
+
+/// Mede a duração de cada requisição, com a rota registrada como rótulo
+///
+/// Deve ser aplicado com `route_layer`, depois de todas as rotas, para que o
+/// padrão da rota já esteja disponível na requisição.
+pub async fn metrics_middleware(request: Request, next: Next) -> Response {
+    let started = Instant::now();
+    let method = request.method().to_string();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+
+    let response = next.run(request).await;
+
+    metrics().observe_http_request(&method, &route, response.status().as_u16(), started.elapsed());
+
+    response
+}
@@ -1 +1,9 @@
 pub mod auth_middleware;
+pub mod locale;
+pub mod metrics;
+pub mod rate_limit;
+#[cfg(test)]
+mod rate_limit_test;
+pub mod request_id;
+pub mod request_trace;
+pub mod security;
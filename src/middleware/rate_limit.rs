@@ -0,0 +1,101 @@
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::HeaderMap,
+    middleware::Next,
+    response::Response,
+};
+use uuid::Uuid;
+
+use crate::{
+    error::AppError,
+    services::rate_limit_service::{RateLimit, RateLimitService},
+    state::AppState,
+};
+
+/// Header com a cadeia de endereços adicionada pelos proxies reversos
+const X_FORWARDED_FOR: &str = "x-forwarded-for";
+
+/// Limite de requisições aplicado a um grupo de rotas
+#[derive(Clone)]
+pub struct RouteRateLimit {
+    /// Prefixo das chaves, para que cada grupo de rotas tenha os seus contadores
+    pub scope: &'static str,
+    pub limit: RateLimit,
+    pub rate_limit_service: Arc<dyn RateLimitService>,
+    /// Proxies cujo `X-Forwarded-For` é aceito para identificar o cliente
+    pub trusted_proxies: Arc<[IpAddr]>,
+}
+
+impl RouteRateLimit {
+    pub fn new(scope: &'static str, limit: RateLimit, state: &AppState) -> Self {
+        Self {
+            scope,
+            limit,
+            rate_limit_service: state.rate_limit_service.clone(),
+            trusted_proxies: state.config.trusted_proxies.clone().into(),
+        }
+    }
+}
+
+/// Limita as requisições de cada cliente às rotas da camada
+///
+/// Em rotas protegidas (camada aplicada dentro do `protect_routes`) o cliente é
+/// o usuário autenticado; nas demais, o IP de origem (ver [`client_ip`]).
+pub async fn rate_limit_middleware(
+    State(route): State<RouteRateLimit>,
+    request: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    let client = match request.extensions().get::<Uuid>() {
+        Some(user_id) => format!("user:{}", user_id),
+        None => request
+            .extensions()
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(address)| {
+                format!("ip:{}", client_ip(address.ip(), request.headers(), &route.trusted_proxies))
+            })
+            .unwrap_or_else(|| "ip:unknown".to_string()),
+    };
+
+    route
+        .rate_limit_service
+        .hit(&format!("{}:{}", route.scope, client), route.limit)
+        .await?;
+
+    Ok(next.run(request).await)
+}
+
+/// IP do cliente de uma conexão vinda de `peer`
+///
+/// Só quando `peer` é um proxy confiável o `X-Forwarded-For` é lido, da direita
+/// para a esquerda: o cliente é o primeiro endereço que não é de um proxy
+/// confiável. Entradas inválidas encerram a leitura, já que tudo à esquerda
+/// delas pode ter sido escrito pelo próprio cliente.
+pub fn client_ip(peer: IpAddr, headers: &HeaderMap, trusted_proxies: &[IpAddr]) -> IpAddr {
+    let mut client = peer;
+    if !trusted_proxies.contains(&client) {
+        return client;
+    }
+
+    // Headers repetidos equivalem a uma única lista, na ordem em que chegaram
+    let hops: Vec<&str> = headers
+        .get_all(X_FORWARDED_FOR)
+        .iter()
+        .flat_map(|value| value.to_str().unwrap_or_default().split(','))
+        .collect();
+
+    for hop in hops.into_iter().rev() {
+        let Ok(address) = hop.trim().parse::<IpAddr>() else {
+            break;
+        };
+        client = address;
+        if !trusted_proxies.contains(&client) {
+            break;
+        }
+    }
+
+    client
+}
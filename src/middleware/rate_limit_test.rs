@@ -0,0 +1,71 @@
+#[cfg(test)]
+mod tests {
+    use std::net::IpAddr;
+
+    use axum::http::{HeaderMap, HeaderValue};
+
+    use crate::middleware::rate_limit::client_ip;
+
+    const PROXY: &str = "10.0.0.2";
+    const CLIENT: &str = "203.0.113.7";
+
+    fn ip(address: &str) -> IpAddr {
+        address.parse().unwrap()
+    }
+
+    fn forwarded_for(values: &[&'static str]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for value in values {
+            headers.append("x-forwarded-for", HeaderValue::from_static(value));
+        }
+        headers
+    }
+
+    #[test]
+    fn test_header_is_ignored_without_trusted_proxies() {
+        let headers = forwarded_for(&["198.51.100.1"]);
+
+        assert_eq!(client_ip(ip(PROXY), &headers, &[]), ip(PROXY));
+    }
+
+    #[test]
+    fn test_header_is_ignored_from_untrusted_peer() {
+        let headers = forwarded_for(&["198.51.100.1"]);
+
+        assert_eq!(client_ip(ip(CLIENT), &headers, &[ip(PROXY)]), ip(CLIENT));
+    }
+
+    #[test]
+    fn test_trusted_proxy_forwards_client_address() {
+        let headers = forwarded_for(&[CLIENT]);
+
+        assert_eq!(client_ip(ip(PROXY), &headers, &[ip(PROXY)]), ip(CLIENT));
+    }
+
+    #[test]
+    fn test_addresses_written_by_the_client_are_ignored() {
+        // O cliente tenta se passar por outro endereço; o proxy acrescenta o real
+        let headers = forwarded_for(&["198.51.100.1, 10.0.0.3", CLIENT]);
+
+        assert_eq!(client_ip(ip(PROXY), &headers, &[ip(PROXY), ip("10.0.0.3")]), ip(CLIENT));
+    }
+
+    #[test]
+    fn test_chain_of_trusted_proxies_is_skipped() {
+        let headers = forwarded_for(&["198.51.100.1, 203.0.113.7, 10.0.0.3"]);
+
+        assert_eq!(client_ip(ip(PROXY), &headers, &[ip(PROXY), ip("10.0.0.3")]), ip(CLIENT));
+    }
+
+    #[test]
+    fn test_invalid_entry_stops_at_last_trusted_hop() {
+        let headers = forwarded_for(&["198.51.100.1, lixo, 10.0.0.3"]);
+
+        assert_eq!(client_ip(ip(PROXY), &headers, &[ip(PROXY), ip("10.0.0.3")]), ip("10.0.0.3"));
+    }
+
+    #[test]
+    fn test_missing_header_falls_back_to_the_proxy() {
+        assert_eq!(client_ip(ip(PROXY), &HeaderMap::new(), &[ip(PROXY)]), ip(PROXY));
+    }
+}
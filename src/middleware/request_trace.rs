@@ -0,0 +1,45 @@
+use std::time::Duration;
+
+use axum::{
+    extract::MatchedPath,
+    http::{Request, Response},
+};
+use tracing::{field::Empty, Span};
+
+use crate::middleware::request_id::current_request_id;
+
+/// Span de cada requisição, usado pelo `TraceLayer`
+///
+/// Começa com o método, o padrão da rota e o identificador da requisição; o
+/// usuário é gravado pelo `auth_middleware`, e o status e a duração, ao final.
+pub fn request_span<B>(request: &Request<B>) -> Span {
+    // Requisições sem rota (404) ficam com o caminho recebido
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str())
+        .unwrap_or_else(|| request.uri().path());
+
+    tracing::info_span!(
+        "http_request",
+        method = %request.method(),
+        route,
+        request_id = current_request_id().as_deref().unwrap_or("-"),
+        user_id = Empty,
+        status = Empty,
+        latency_ms = Empty,
+    )
+}
+
+/// Registra o fim da requisição, com o status e a duração em milissegundos
+pub fn record_response<B>(response: &Response<B>, latency: Duration, span: &Span) {
+    let status = response.status();
+    span.record("status", status.as_u16());
+    span.record("latency_ms", latency.as_millis() as u64);
+
+    if status.is_server_error() {
+        tracing::error!("requisição concluída com erro");
+    } else {
+        tracing::info!("requisição concluída");
+    }
+}
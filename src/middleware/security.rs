@@ -0,0 +1,89 @@
+use axum::{
+    extract::{Request, State},
+    http::{
+        header::{
+            CONTENT_LANGUAGE, CONTENT_SECURITY_POLICY, REFERRER_POLICY, RETRY_AFTER, STRICT_TRANSPORT_SECURITY,
+            X_CONTENT_TYPE_OPTIONS, X_FRAME_OPTIONS,
+        },
+        HeaderValue,
+    },
+    middleware::Next,
+    response::Response,
+};
+use tower_http::cors::{AllowOrigin, CorsLayer};
+
+use crate::config::{Config, CorsConfig};
+use crate::middleware::request_id::REQUEST_ID_HEADER;
+
+/// Caminho do Swagger UI, a única página HTML servida pela API
+pub const DOCS_PATH: &str = "/docs";
+
+/// A API só devolve JSON: nada pode ser carregado nem embutido a partir das respostas
+const API_CSP: &str = "default-src 'none'; frame-ancestors 'none'";
+
+/// O Swagger UI carrega os próprios scripts e aplica estilos inline
+const DOCS_CSP: &str =
+    "default-src 'self'; img-src 'self' data:; style-src 'self' 'unsafe-inline'; frame-ancestors 'none'";
+
+/// Monta o CORS a partir da configuração
+pub fn cors_layer(config: &CorsConfig) -> CorsLayer {
+    let allow_origin = match &config.allowed_origins {
+        Some(origins) => AllowOrigin::list(origins.iter().cloned()),
+        None => AllowOrigin::any(),
+    };
+
+    CorsLayer::new()
+        .allow_origin(allow_origin)
+        .allow_methods(config.allowed_methods.clone())
+        .allow_headers(config.allowed_headers.clone())
+        .allow_credentials(config.allow_credentials)
+        .max_age(config.max_age)
+        .expose_headers([REQUEST_ID_HEADER, CONTENT_LANGUAGE, RETRY_AFTER])
+}
+
+/// Headers de segurança incluídos em todas as respostas
+#[derive(Clone)]
+pub struct SecurityHeaders {
+    hsts: Option<HeaderValue>,
+}
+
+impl SecurityHeaders {
+    pub fn from_config(config: &Config) -> Self {
+        let hsts = (config.hsts_max_age_secs > 0).then(|| {
+            HeaderValue::from_str(&format!("max-age={}; includeSubDomains", config.hsts_max_age_secs))
+                .expect("HSTS inválido")
+        });
+
+        Self { hsts }
+    }
+}
+
+/// Aplica os headers de segurança, sem sobrescrever os definidos pelas rotas
+pub async fn security_headers_middleware(
+    State(headers): State<SecurityHeaders>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let csp = if request.uri().path().starts_with(DOCS_PATH) { DOCS_CSP } else { API_CSP };
+
+    let mut response = next.run(request).await;
+    let response_headers = response.headers_mut();
+
+    if let Some(hsts) = headers.hsts {
+        response_headers.entry(STRICT_TRANSPORT_SECURITY).or_insert(hsts);
+    }
+    response_headers
+        .entry(X_CONTENT_TYPE_OPTIONS)
+        .or_insert(HeaderValue::from_static("nosniff"));
+    response_headers
+        .entry(X_FRAME_OPTIONS)
+        .or_insert(HeaderValue::from_static("DENY"));
+    response_headers
+        .entry(REFERRER_POLICY)
+        .or_insert(HeaderValue::from_static("no-referrer"));
+    response_headers
+        .entry(CONTENT_SECURITY_POLICY)
+        .or_insert(HeaderValue::from_static(csp));
+
+    response
+}
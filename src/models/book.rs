@@ -1,6 +1,15 @@
+use std::str::FromStr;
+
+use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use uuid::Uuid;
+use validator::Validate;
+
+use crate::error::AppError;
+use crate::i18n::messages;
+use crate::models::shelf::ShelfList;
+use crate::models::user::UserResponse;
 
 #[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
 pub struct GoogleBookDto {
@@ -14,17 +23,228 @@ pub struct GoogleBookDto {
     pub page_count: Option<i32>,
 }
 
-#[derive(Debug, Serialize, ToSchema, Deserialize)]
+#[derive(Debug, Serialize, ToSchema, Deserialize, Validate)]
 pub struct BookSearchRequest {
+    #[validate(length(min = 1, code = "search_query_empty"))]
     pub query: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, ToSchema)]
+/// Estado de conservação de um exemplar oferecido
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BookCondition {
+    New,
+    LikeNew,
+    #[default]
+    Good,
+    Fair,
+    Poor,
+}
+
+impl BookCondition {
+    /// Valor armazenado na coluna `books_offered.condition`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BookCondition::New => "new",
+            BookCondition::LikeNew => "like_new",
+            BookCondition::Good => "good",
+            BookCondition::Fair => "fair",
+            BookCondition::Poor => "poor",
+        }
+    }
+}
+
+impl FromStr for BookCondition {
+    type Err = AppError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "new" => Ok(BookCondition::New),
+            "like_new" => Ok(BookCondition::LikeNew),
+            "good" => Ok(BookCondition::Good),
+            "fair" => Ok(BookCondition::Fair),
+            "poor" => Ok(BookCondition::Poor),
+            _ => Err(AppError::DatabaseError(format!(
+                "Estado de conservação desconhecido: {}",
+                value
+            ))),
+        }
+    }
+}
+
+/// Atributos de um exemplar oferecido, informados pelo dono
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema, Validate)]
+pub struct OfferedCopyAttributes {
+    /// Estado de conservação do exemplar
+    #[serde(default)]
+    pub condition: BookCondition,
+    /// Idioma do exemplar (por exemplo, "pt-BR" ou "en")
+    #[validate(length(min = 2, max = 10, code = "language_length"))]
+    pub language: Option<String>,
+    /// Observações pessoais sobre o exemplar
+    #[validate(length(max = 1000, code = "notes_too_long"))]
+    pub notes: Option<String>,
+    /// Indica se o exemplar está disponível para troca
+    #[serde(default = "default_available")]
+    pub available: bool,
+}
+
+fn default_available() -> bool {
+    true
+}
+
+impl Default for OfferedCopyAttributes {
+    fn default() -> Self {
+        Self {
+            condition: BookCondition::default(),
+            language: None,
+            notes: None,
+            available: default_available(),
+        }
+    }
+}
+
+impl OfferedCopyAttributes {
+    /// Valida todos os campos dos atributos
+    ///
+    /// Retorna erro se algum campo não estiver de acordo com as regras de validação
+    pub fn validate_all(&self) -> Result<(), AppError> {
+        self.validate().map_err(AppError::from)
+    }
+}
+
+/// Exemplar de um livro oferecido por um usuário
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct BookOffered {
+    /// Identificador do exemplar
+    #[schema(value_type = String, format = "uuid")]
+    pub id: Uuid,
     #[schema(value_type = String, format = "uuid")]
     pub book_id: Uuid,
     #[schema(value_type = String, format = "uuid")]
     pub user_id: Uuid,
+    #[serde(flatten)]
+    pub attributes: OfferedCopyAttributes,
+}
+
+/// Foto enviada pelo dono de um exemplar oferecido
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BookPhoto {
+    #[schema(value_type = String, format = "uuid")]
+    pub id: Uuid,
+    /// Caminho para download da foto
+    pub url: String,
+    pub content_type: String,
+    pub size_bytes: i32,
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: NaiveDateTime,
+}
+
+impl BookPhoto {
+    /// Caminho da API que serve o conteúdo de uma foto
+    pub fn url_for(id: &Uuid) -> String {
+        format!("/api/v1/photos/{}", id)
+    }
+}
+
+/// Registro de uma foto como armazenado no banco de dados
+#[derive(Debug, Clone)]
+pub struct BookPhotoRecord {
+    pub id: Uuid,
+    pub copy_id: Uuid,
+    pub user_id: Uuid,
+    pub storage_key: String,
+    pub content_type: String,
+    pub size_bytes: i32,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<BookPhotoRecord> for BookPhoto {
+    fn from(record: BookPhotoRecord) -> Self {
+        Self {
+            url: BookPhoto::url_for(&record.id),
+            id: record.id,
+            content_type: record.content_type,
+            size_bytes: record.size_bytes,
+            created_at: record.created_at,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct CreateBookPhotoDto {
+    pub copy_id: Uuid,
+    pub user_id: Uuid,
+    pub storage_key: String,
+    pub content_type: String,
+    pub size_bytes: i32,
+}
+
+/// Detalhes de um exemplar oferecido exibidos nas listagens
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct OfferedCopy {
+    /// Identificador do exemplar
+    #[schema(value_type = String, format = "uuid")]
+    pub id: Uuid,
+    #[serde(flatten)]
+    pub attributes: OfferedCopyAttributes,
+    pub photos: Vec<BookPhoto>,
+}
+
+impl OfferedCopy {
+    /// Monta os detalhes de um exemplar a partir do registro e das suas fotos
+    pub fn from_offered(offered: BookOffered, photos: Vec<BookPhoto>) -> Self {
+        Self {
+            id: offered.id,
+            attributes: offered.attributes,
+            photos,
+        }
+    }
+}
+
+/// Exemplar de um livro oferecido por outro usuário
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BookOffer {
+    pub owner: UserResponse,
+    pub copy: OfferedCopy,
+}
+
+/// Livro cadastrado, com quantos o oferecem e desejam e a lista do usuário que o consulta
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BookDetails {
+    #[schema(value_type = String, format = "uuid")]
+    pub id: Uuid,
+    pub book: GoogleBookDto,
+    /// Exemplares cadastrados, de todos os usuários
+    pub offered_count: i64,
+    /// Usuários que desejam o livro
+    pub wanted_count: i64,
+    /// Lista em que o usuário tem o livro, se tiver
+    pub user_list: Option<ShelfList>,
+    /// Última vez em que os dados foram obtidos do Google Books
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub metadata_fetched_at: Option<NaiveDateTime>,
+    /// Última tentativa de obter os dados, mesmo que tenha falhado
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub metadata_checked_at: Option<NaiveDateTime>,
+}
+
+/// Menor prioridade de um livro desejado
+pub const MIN_WANTED_PRIORITY: i16 = 1;
+/// Maior prioridade de um livro desejado
+pub const MAX_WANTED_PRIORITY: i16 = 5;
+/// Prioridade atribuída quando o usuário não informa uma
+pub const DEFAULT_WANTED_PRIORITY: i16 = 3;
+
+/// Verifica se a prioridade está entre os limites aceitos
+pub fn validate_wanted_priority(priority: i16) -> Result<(), AppError> {
+    if (MIN_WANTED_PRIORITY..=MAX_WANTED_PRIORITY).contains(&priority) {
+        Ok(())
+    } else {
+        Err(AppError::ValidationError(
+            messages::PRIORITY_OUT_OF_RANGE.format(&[&MIN_WANTED_PRIORITY, &MAX_WANTED_PRIORITY]),
+        ))
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -33,17 +253,107 @@ pub struct BookWanted {
     pub book_id: Uuid,
     #[schema(value_type = String, format = "uuid")]
     pub user_id: Uuid,
+    /// Quanto o usuário deseja o livro, de 1 (pouco) a 5 (muito)
+    pub priority: i16,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateBookOfferedDto {
     pub book_id: Uuid,
     pub user_id: Uuid,
+    pub attributes: OfferedCopyAttributes,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateBookWantedDto {
     pub book_id: Uuid,
     pub user_id: Uuid,
+    pub priority: i16,
+}
+
+
+/// Número máximo de livros em uma operação em lote
+pub const MAX_BATCH_SIZE: usize = 50;
+
+/// Livro a ser adicionado à lista de possuídos em uma operação em lote
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct OfferedBookItem {
+    pub google_id: String,
+    /// Número de exemplares idênticos a adicionar (padrão 1)
+    #[serde(default = "default_batch_quantity")]
+    pub quantity: i32,
+    #[serde(flatten)]
+    pub attributes: OfferedCopyAttributes,
+}
+
+fn default_batch_quantity() -> i32 {
+    1
+}
+
+/// Livro a ser adicionado à lista de desejados em uma operação em lote
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WantedBookItem {
+    pub google_id: String,
+    /// Prioridade do livro, de 1 a 5; 3 por padrão
+    #[serde(default = "default_batch_priority")]
+    pub priority: i16,
+}
+
+fn default_batch_priority() -> i16 {
+    DEFAULT_WANTED_PRIORITY
 }
 
+/// Resultado de um item de uma operação em lote
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchItemStatus {
+    Added,
+    Removed,
+    Failed,
+}
+
+/// Relatório de um item de uma operação em lote, na mesma ordem da requisição
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BatchItemResult {
+    /// google_id (inclusão) ou ID do livro (remoção) informado no item
+    pub id: String,
+    pub status: BatchItemStatus,
+    /// ID interno do livro, quando identificado
+    #[schema(value_type = Option<String>, format = "uuid")]
+    pub book_id: Option<Uuid>,
+    /// Exemplares criados (apenas inclusões na lista de possuídos)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[schema(value_type = Vec<String>)]
+    pub copy_ids: Vec<Uuid>,
+    /// Motivo da falha do item
+    pub message: Option<String>,
+}
+
+impl BatchItemResult {
+    pub fn failed(id: impl Into<String>, book_id: Option<Uuid>, message: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            status: BatchItemStatus::Failed,
+            book_id,
+            copy_ids: vec![],
+            message: Some(message.into()),
+        }
+    }
+
+    pub fn succeeded(id: impl Into<String>, status: BatchItemStatus, book_id: Uuid, copy_ids: Vec<Uuid>) -> Self {
+        Self {
+            id: id.into(),
+            status,
+            book_id: Some(book_id),
+            copy_ids,
+            message: None,
+        }
+    }
+}
+
+/// Livros a serem removidos de uma lista em uma operação em lote
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BatchRemoveRequest {
+    #[schema(value_type = Vec<String>)]
+    pub book_ids: Vec<Uuid>,
+}
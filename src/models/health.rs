@@ -0,0 +1,27 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// Resultado de uma verificação de dependência
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Ok,
+    Unavailable,
+}
+
+/// Estado de prontidão da API e de cada dependência verificada
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct ReadinessReport {
+    /// `ok` apenas quando todas as verificações passaram
+    pub status: CheckStatus,
+    /// Resultado por dependência (`database` e, se configurado, `google_books`)
+    pub checks: BTreeMap<String, CheckStatus>,
+}
+
+impl ReadinessReport {
+    pub fn is_ready(&self) -> bool {
+        self.status == CheckStatus::Ok
+    }
+}
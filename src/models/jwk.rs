@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Chave pública no formato JWK (RFC 7517), usada para verificar os tokens
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Jwk {
+    /// Tipo da chave: "RSA" ou "OKP" (Ed25519)
+    pub kty: String,
+    /// Identificador da chave, o mesmo do campo `kid` no cabeçalho do token
+    pub kid: String,
+    /// Algoritmo de assinatura: "RS256" ou "EdDSA"
+    pub alg: String,
+    #[serde(rename = "use")]
+    pub key_use: String,
+    /// Módulo da chave RSA, em base64url
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub n: Option<String>,
+    /// Expoente da chave RSA, em base64url
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub e: Option<String>,
+    /// Curva da chave OKP ("Ed25519")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub crv: Option<String>,
+    /// Chave pública Ed25519, em base64url
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub x: Option<String>,
+}
+
+/// Conjunto de chaves publicado em /.well-known/jwks.json
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct JwkSet {
+    pub keys: Vec<Jwk>,
+}
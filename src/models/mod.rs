@@ -1,6 +1,14 @@
 pub mod book;
+pub mod health;
+pub mod jwk;
+pub mod oauth;
+pub mod response;
 pub mod user;
 pub mod trade;
+pub mod shelf;
+pub mod two_factor;
 
 #[cfg(test)]
 mod user_test;
+#[cfg(test)]
+mod trade_test;
@@ -0,0 +1,25 @@
+use sqlx::FromRow;
+
+/// Conta do usuário em um provedor externo, obtida ao concluir o login
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExternalIdentity {
+    /// Nome do provedor ("google" ou "github")
+    pub provider: String,
+    /// Identificador estável da conta no provedor
+    pub subject: String,
+    pub email: String,
+    /// Se o provedor confirmou que o email pertence à conta
+    pub email_verified: bool,
+    pub name: Option<String>,
+}
+
+/// Autorização iniciada e ainda não concluída
+#[derive(Debug, Clone, PartialEq, FromRow)]
+pub struct OAuthLoginState {
+    pub state: String,
+    pub provider: String,
+    /// Verificador PKCE, enviado apenas na troca do código pelo token
+    pub code_verifier: String,
+    /// Valor que o provedor OpenID Connect deve devolver no id_token
+    pub nonce: String,
+}
@@ -0,0 +1,72 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::i18n::Message;
+use crate::models::book::{BatchItemResult, BookDetails, BookOffer, BookOffered, BookPhoto, BookWanted, GoogleBookDto};
+use crate::models::shelf::ImportReport;
+use crate::models::trade::Trade;
+use crate::models::two_factor::{RecoveryCodes, TwoFactorSetup};
+use crate::models::user::{TokenResponse, TwoFactorChallenge, UserLocation, UserResponse};
+use crate::services::book_service::UserBooks;
+
+/// Envelope das respostas de sucesso, com a mensagem no idioma da requisição
+///
+/// Cada alias dá nome, na documentação, ao envelope de um tipo de dado.
+#[derive(Debug, Serialize, ToSchema)]
+#[aliases(
+    RegisteredUserResponse = ApiResponse<UserResponse>,
+    AccessTokenResponse = ApiResponse<TokenResponse>,
+    TwoFactorChallengeResponse = ApiResponse<TwoFactorChallenge>,
+    TwoFactorSetupResponse = ApiResponse<TwoFactorSetup>,
+    RecoveryCodesResponse = ApiResponse<RecoveryCodes>,
+    GoogleBooksResponse = ApiResponse<Vec<GoogleBookDto>>,
+    UserBooksResponse = ApiResponse<UserBooks>,
+    BookDetailsResponse = ApiResponse<BookDetails>,
+    BookOffersResponse = ApiResponse<Vec<BookOffer>>,
+    BookOfferedResponse = ApiResponse<BookOffered>,
+    BookOfferedCopiesResponse = ApiResponse<Vec<BookOffered>>,
+    BookPhotoResponse = ApiResponse<BookPhoto>,
+    BookWantedResponse = ApiResponse<BookWanted>,
+    BatchResultsResponse = ApiResponse<Vec<BatchItemResult>>,
+    ImportReportResponse = ApiResponse<ImportReport>,
+    TradeResponse = ApiResponse<Trade>,
+    TradesResponse = ApiResponse<Vec<Trade>>,
+    UserLocationResponse = ApiResponse<UserLocation>
+)]
+pub struct ApiResponse<T> {
+    /// Sempre `success`
+    #[schema(example = "success")]
+    pub status: String,
+    pub message: String,
+    pub data: T,
+}
+
+impl<T> ApiResponse<T> {
+    pub fn new(message: Message, data: T) -> Self {
+        Self {
+            status: SUCCESS.to_string(),
+            message: message.text(),
+            data,
+        }
+    }
+}
+
+/// Resposta de sucesso sem dados
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MessageResponse {
+    /// Sempre `success`
+    #[schema(example = "success")]
+    pub status: String,
+    pub message: String,
+}
+
+impl MessageResponse {
+    pub fn new(message: Message) -> Self {
+        Self {
+            status: SUCCESS.to_string(),
+            message: message.text(),
+        }
+    }
+}
+
+const SUCCESS: &str = "success";
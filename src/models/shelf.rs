@@ -0,0 +1,154 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::i18n::messages;
+
+/// Formato de arquivo aceito na importação e gerado na exportação da estante
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ShelfFormat {
+    /// CSV próprio da aplicação
+    Csv,
+    /// CSV exportado pela biblioteca do Goodreads
+    Goodreads,
+    Json,
+}
+
+impl ShelfFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ShelfFormat::Csv | ShelfFormat::Goodreads => "text/csv; charset=utf-8",
+            ShelfFormat::Json => "application/json",
+        }
+    }
+
+    pub fn file_extension(&self) -> &'static str {
+        match self {
+            ShelfFormat::Csv | ShelfFormat::Goodreads => "csv",
+            ShelfFormat::Json => "json",
+        }
+    }
+}
+
+impl FromStr for ShelfFormat {
+    type Err = AppError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "csv" => Ok(ShelfFormat::Csv),
+            "goodreads" => Ok(ShelfFormat::Goodreads),
+            "json" => Ok(ShelfFormat::Json),
+            _ => Err(AppError::ValidationError(messages::SHELF_FORMAT_UNKNOWN.format(&[&value]))),
+        }
+    }
+}
+
+/// Lista da estante do usuário
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ShelfList {
+    /// Livros possuídos
+    #[default]
+    Offered,
+    /// Livros desejados
+    Wanted,
+}
+
+impl ShelfList {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ShelfList::Offered => "offered",
+            ShelfList::Wanted => "wanted",
+        }
+    }
+}
+
+impl FromStr for ShelfList {
+    type Err = AppError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "offered" => Ok(ShelfList::Offered),
+            "wanted" => Ok(ShelfList::Wanted),
+            _ => Err(AppError::ValidationError(messages::SHELF_LIST_UNKNOWN.format(&[&value]))),
+        }
+    }
+}
+
+/// Linha de uma estante importada ou exportada
+///
+/// Na importação, o livro é identificado pelo `google_id`, pelo `isbn` ou pelo
+/// par `title`/`author`, nessa ordem.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ShelfEntry {
+    /// Lista de destino; quando ausente, usa a lista informada na requisição
+    pub list: Option<ShelfList>,
+    pub google_id: Option<String>,
+    pub isbn: Option<String>,
+    pub title: Option<String>,
+    pub author: Option<String>,
+    /// Estado de conservação do exemplar (apenas livros possuídos)
+    pub condition: Option<String>,
+    /// Idioma do exemplar (apenas livros possuídos)
+    pub language: Option<String>,
+    /// Observações sobre o exemplar (apenas livros possuídos)
+    pub notes: Option<String>,
+    /// Prioridade de 1 a 5 (apenas livros desejados)
+    pub priority: Option<i16>,
+}
+
+/// Resultado da importação de uma linha
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportStatus {
+    /// O livro foi adicionado à lista
+    Added,
+    /// O livro já estava na lista e foi ignorado
+    AlreadyPresent,
+    /// Não foi possível identificar o livro ou a linha é inválida
+    Unresolved,
+    /// O livro está na outra lista do usuário
+    Conflict,
+}
+
+/// Relatório de uma linha importada
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ImportRowResult {
+    /// Número da linha no arquivo (a partir de 1, sem contar o cabeçalho)
+    pub row: usize,
+    pub list: ShelfList,
+    pub status: ImportStatus,
+    /// ID interno do livro, quando identificado
+    #[schema(value_type = Option<String>, format = "uuid")]
+    pub book_id: Option<Uuid>,
+    pub google_id: Option<String>,
+    pub title: Option<String>,
+    /// Motivo de a linha não ter sido adicionada
+    pub message: Option<String>,
+}
+
+/// Relatório completo de uma importação
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct ImportReport {
+    pub added: usize,
+    pub already_present: usize,
+    pub unresolved: usize,
+    pub conflict: usize,
+    pub rows: Vec<ImportRowResult>,
+}
+
+impl ImportReport {
+    pub fn push(&mut self, result: ImportRowResult) {
+        match result.status {
+            ImportStatus::Added => self.added += 1,
+            ImportStatus::AlreadyPresent => self.already_present += 1,
+            ImportStatus::Unresolved => self.unresolved += 1,
+            ImportStatus::Conflict => self.conflict += 1,
+        }
+        self.rows.push(result);
+    }
+}
@@ -1,8 +1,12 @@
+use std::str::FromStr;
+
+use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use uuid::Uuid;
 
-use crate::models::book::GoogleBookDto;
+use crate::error::AppError;
+use crate::models::book::{GoogleBookDto, OfferedCopy, MAX_WANTED_PRIORITY, MIN_WANTED_PRIORITY};
 use crate::models::user::UserResponse;
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -14,6 +18,64 @@ pub struct PossibleTrade {
     #[schema(value_type = String, format = "uuid")]
     pub wanted_book_id: Uuid,
     pub trade_partner: UserResponse,
+    /// Exemplar do usuário que seria entregue na troca
+    pub offered_copy: OfferedCopy,
+    /// Exemplar do parceiro que seria recebido na troca
+    pub wanted_copy: OfferedCopy,
+    /// Relevância da sugestão, de 0 a 100; as trocas são ordenadas por ela
+    pub score: f64,
+}
+
+// Pesos de cada fator na pontuação de uma sugestão de troca (somam 100)
+const MY_PRIORITY_WEIGHT: f64 = 35.0;
+const PARTNER_PRIORITY_WEIGHT: f64 = 35.0;
+const REPUTATION_WEIGHT: f64 = 15.0;
+const DISTANCE_WEIGHT: f64 = 15.0;
+
+// Trocas concluídas necessárias para metade da pontuação de reputação
+const REPUTATION_HALF_TRADES: f64 = 5.0;
+// Distância, em km, que vale metade da pontuação de proximidade
+const DISTANCE_HALF_KM: f64 = 50.0;
+
+/// Fatores usados para pontuar uma sugestão de troca
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TradeScoreFactors {
+    /// Prioridade que o usuário deu ao livro que receberia
+    pub my_priority: i16,
+    /// Prioridade que o parceiro deu ao livro que receberia
+    pub partner_priority: i16,
+    /// Quantidade de trocas concluídas pelo parceiro
+    pub partner_completed_trades: i64,
+    /// Distância até o parceiro, quando os dois informaram a localização
+    pub distance_km: Option<f64>,
+}
+
+impl TradeScoreFactors {
+    /// Combina os fatores em uma pontuação de 0 a 100
+    ///
+    /// Sem localização de uma das partes, a proximidade conta pela metade, de
+    /// modo que a falta do dado não favoreça nem penalize a sugestão.
+    pub fn score(&self) -> f64 {
+        let priority = |p: i16| {
+            let p = p.clamp(MIN_WANTED_PRIORITY, MAX_WANTED_PRIORITY);
+            f64::from(p - MIN_WANTED_PRIORITY) / f64::from(MAX_WANTED_PRIORITY - MIN_WANTED_PRIORITY)
+        };
+
+        let trades = self.partner_completed_trades.max(0) as f64;
+        let reputation = trades / (trades + REPUTATION_HALF_TRADES);
+
+        let proximity = match self.distance_km {
+            Some(km) => DISTANCE_HALF_KM / (DISTANCE_HALF_KM + km.max(0.0)),
+            None => 0.5,
+        };
+
+        let score = MY_PRIORITY_WEIGHT * priority(self.my_priority)
+            + PARTNER_PRIORITY_WEIGHT * priority(self.partner_priority)
+            + REPUTATION_WEIGHT * reputation
+            + DISTANCE_WEIGHT * proximity;
+
+        (score * 100.0).round() / 100.0
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -27,4 +89,105 @@ pub struct TradeMatch {
     #[schema(value_type = String, format = "uuid")]
     pub partner_wants: Uuid,  // ID do livro que o parceiro quer
     pub partner: UserResponse,
-} 
\ No newline at end of file
+}
+
+/// Situação de uma proposta de troca
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TradeStatus {
+    /// Aguardando a resposta do parceiro
+    Pending,
+    /// Aceita pelo parceiro, aguardando a entrega dos exemplares
+    Accepted,
+    Rejected,
+    Cancelled,
+    Completed,
+}
+
+impl TradeStatus {
+    /// Valor armazenado na coluna `trades.status`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TradeStatus::Pending => "pending",
+            TradeStatus::Accepted => "accepted",
+            TradeStatus::Rejected => "rejected",
+            TradeStatus::Cancelled => "cancelled",
+            TradeStatus::Completed => "completed",
+        }
+    }
+
+    /// Indica se a troca ainda mantém os exemplares reservados
+    pub fn is_open(&self) -> bool {
+        matches!(self, TradeStatus::Pending | TradeStatus::Accepted)
+    }
+}
+
+impl FromStr for TradeStatus {
+    type Err = AppError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "pending" => Ok(TradeStatus::Pending),
+            "accepted" => Ok(TradeStatus::Accepted),
+            "rejected" => Ok(TradeStatus::Rejected),
+            "cancelled" => Ok(TradeStatus::Cancelled),
+            "completed" => Ok(TradeStatus::Completed),
+            _ => Err(AppError::DatabaseError(format!(
+                "Situação de troca desconhecida: {}",
+                value
+            ))),
+        }
+    }
+}
+
+/// Proposta de troca entre dois exemplares específicos
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Trade {
+    #[schema(value_type = String, format = "uuid")]
+    pub id: Uuid,
+    /// Usuário que fez a proposta
+    #[schema(value_type = String, format = "uuid")]
+    pub proposer_id: Uuid,
+    /// Usuário que recebeu a proposta
+    #[schema(value_type = String, format = "uuid")]
+    pub partner_id: Uuid,
+    /// Livro entregue pelo autor da proposta
+    #[schema(value_type = String, format = "uuid")]
+    pub offered_book_id: Uuid,
+    /// Exemplar entregue pelo autor da proposta (vazio após a conclusão da troca)
+    #[schema(value_type = Option<String>, format = "uuid")]
+    pub offered_copy_id: Option<Uuid>,
+    /// Livro entregue pelo parceiro
+    #[schema(value_type = String, format = "uuid")]
+    pub wanted_book_id: Uuid,
+    /// Exemplar entregue pelo parceiro (vazio após a conclusão da troca)
+    #[schema(value_type = Option<String>, format = "uuid")]
+    pub wanted_copy_id: Option<Uuid>,
+    pub status: TradeStatus,
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: NaiveDateTime,
+    #[schema(value_type = String, format = DateTime)]
+    pub updated_at: NaiveDateTime,
+}
+
+/// Dados para propor uma troca de um exemplar próprio por um exemplar de outro usuário
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ProposeTradeRequest {
+    /// Exemplar do usuário autenticado que será entregue
+    #[schema(value_type = String, format = "uuid")]
+    pub offered_copy_id: Uuid,
+    /// Exemplar do parceiro que será recebido
+    #[schema(value_type = String, format = "uuid")]
+    pub wanted_copy_id: Uuid,
+}
+
+/// Dados para registrar uma proposta de troca
+#[derive(Debug, Clone)]
+pub struct CreateTradeDto {
+    pub proposer_id: Uuid,
+    pub partner_id: Uuid,
+    pub offered_book_id: Uuid,
+    pub offered_copy_id: Uuid,
+    pub wanted_book_id: Uuid,
+    pub wanted_copy_id: Uuid,
+}
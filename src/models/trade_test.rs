@@ -0,0 +1,70 @@
+#[cfg(test)]
+mod tests {
+    use crate::models::trade::TradeScoreFactors;
+    use crate::models::user::UserLocation;
+
+    fn factors(my_priority: i16, partner_priority: i16) -> TradeScoreFactors {
+        TradeScoreFactors {
+            my_priority,
+            partner_priority,
+            partner_completed_trades: 0,
+            distance_km: None,
+        }
+    }
+
+    #[test]
+    fn test_score_bounds() {
+        let worst = TradeScoreFactors {
+            distance_km: Some(f64::INFINITY),
+            ..factors(1, 1)
+        };
+        let best = TradeScoreFactors {
+            partner_completed_trades: i64::MAX,
+            distance_km: Some(0.0),
+            ..factors(5, 5)
+        };
+
+        assert_eq!(worst.score(), 0.0);
+        assert!((99.0..=100.0).contains(&best.score()), "Pontuação máxima inesperada: {}", best.score());
+    }
+
+    #[test]
+    fn test_score_increases_with_priorities() {
+        assert!(factors(5, 3).score() > factors(3, 3).score());
+        assert!(factors(3, 5).score() > factors(3, 3).score());
+        // As duas prioridades têm o mesmo peso
+        assert_eq!(factors(5, 1).score(), factors(1, 5).score());
+    }
+
+    #[test]
+    fn test_score_rewards_reputation_and_proximity() {
+        let base = factors(3, 3);
+        let experienced = TradeScoreFactors {
+            partner_completed_trades: 10,
+            ..base
+        };
+        let near = TradeScoreFactors {
+            distance_km: Some(5.0),
+            ..base
+        };
+        let far = TradeScoreFactors {
+            distance_km: Some(500.0),
+            ..base
+        };
+
+        assert!(experienced.score() > base.score());
+        assert!(near.score() > base.score(), "Parceiro próximo deve valer mais que localização desconhecida");
+        assert!(far.score() < base.score(), "Parceiro distante deve valer menos que localização desconhecida");
+    }
+
+    #[test]
+    fn test_distance_km() {
+        let sao_paulo = UserLocation { latitude: -23.5505, longitude: -46.6333 };
+        let rio = UserLocation { latitude: -22.9068, longitude: -43.1729 };
+
+        let distance = sao_paulo.distance_km(&rio);
+
+        assert!((355.0..365.0).contains(&distance), "Distância inesperada: {}", distance);
+        assert_eq!(sao_paulo.distance_km(&sao_paulo), 0.0);
+    }
+}
@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Segredo TOTP gerado no início da ativação
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TwoFactorSetup {
+    /// Segredo em base32, para digitar no aplicativo autenticador
+    pub secret: String,
+    /// URI `otpauth://` a ser exibida como QR code
+    pub provisioning_uri: String,
+}
+
+/// Códigos de recuperação, exibidos uma única vez
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RecoveryCodes {
+    /// Cada código pode substituir o código do autenticador uma vez
+    pub recovery_codes: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct EnableTwoFactorDto {
+    /// Código de 6 dígitos exibido pelo aplicativo autenticador
+    pub code: String,
+}
+
+/// Comprovação para desativar a verificação: a senha ou um código
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct DisableTwoFactorDto {
+    /// Senha atual do usuário
+    pub password: Option<String>,
+    /// Código do aplicativo autenticador ou um código de recuperação, para
+    /// contas que entram apenas pelo Google ou GitHub
+    pub code: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct VerifyTwoFactorDto {
+    /// Token de desafio recebido no login
+    pub challenge_token: String,
+    /// Código do aplicativo autenticador ou um código de recuperação
+    pub code: String,
+}
+
+/// Segredo TOTP gravado para o usuário
+#[derive(Debug, Clone, PartialEq)]
+pub struct TwoFactorSecret {
+    /// Segredo em base32
+    pub secret: String,
+    /// Se a ativação já foi confirmada com um código
+    pub enabled: bool,
+}
@@ -6,6 +6,8 @@ use uuid::Uuid;
 use validator::Validate;
 use validator::{validate_email, ValidationError};
 
+use crate::i18n::messages;
+
 #[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
 pub struct User {
     pub id: Uuid,
@@ -15,23 +17,26 @@ pub struct User {
     pub hash_password: String,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
+    /// Momento da suspensão da conta; `None` para contas ativas
+    #[serde(skip_serializing, default)]
+    pub banned_at: Option<NaiveDateTime>,
 }
 
 #[derive(Debug, Deserialize, ToSchema, Validate)]
 pub struct CreateUserDto {
     /// Nome do usuário (máximo 255 caracteres)
-    #[validate(length(min = 1, message = "O nome não pode estar vazio"))]
-    #[validate(length(max = 255, message = "O nome deve ter menos de 255 caracteres"))]
+    #[validate(length(min = 1, code = "name_required"))]
+    #[validate(length(max = 255, code = "name_too_long"))]
     pub name: String,
 
     /// Email do usuário (deve ser único e em formato válido)
-    #[validate(length(min = 1, message = "O email não pode estar vazio"))]
-    #[validate(length(max = 255, message = "O email deve ter menos de 255 caracteres"))]
+    #[validate(length(min = 1, code = "email_required"))]
+    #[validate(length(max = 255, code = "email_too_long"))]
     #[validate(custom = "validate_email_format")]
     pub email: String,
 
     /// Senha do usuário (mínimo 6 caracteres)
-    #[validate(length(min = 6, message = "A senha deve ter pelo menos 6 caracteres"))]
+    #[validate(length(min = 6, code = "password_too_short"))]
     pub password: String,
 }
 
@@ -40,13 +45,7 @@ impl CreateUserDto {
     ///
     /// Retorna erro se algum campo não estiver de acordo com as regras de validação
     pub fn validate_all(&self) -> Result<(), crate::error::AppError> {
-        match self.validate() {
-            Ok(_) => Ok(()),
-            Err(e) => Err(crate::error::AppError::ValidationError(format!(
-                "Erro de validação: {}",
-                e
-            ))),
-        }
+        self.validate().map_err(crate::error::AppError::from)
     }
 }
 
@@ -55,22 +54,20 @@ fn validate_email_format(email: &str) -> Result<(), ValidationError> {
     if validate_email(email) {
         Ok(())
     } else {
-        let mut error = ValidationError::new("email");
-        error.message = Some("Formato de email inválido".into());
-        Err(error)
+        Err(ValidationError::new(messages::EMAIL_INVALID.code))
     }
 }
 
 #[derive(Debug, Deserialize, ToSchema, Validate)]
 pub struct LoginUserDto {
     /// Email do usuário
-    #[validate(length(min = 1, message = "O email não pode estar vazio"))]
-    #[validate(length(max = 255, message = "O email deve ter menos de 255 caracteres"))]
+    #[validate(length(min = 1, code = "email_required"))]
+    #[validate(length(max = 255, code = "email_too_long"))]
     #[validate(custom = "validate_email_format")]
     pub email: String,
 
     /// Senha do usuário
-    #[validate(length(min = 1, message = "A senha não pode estar vazia"))]
+    #[validate(length(min = 1, code = "password_required"))]
     pub password: String,
 }
 
@@ -79,13 +76,7 @@ impl LoginUserDto {
     ///
     /// Retorna erro se algum campo não estiver de acordo com as regras de validação
     pub fn validate_all(&self) -> Result<(), crate::error::AppError> {
-        match self.validate() {
-            Ok(_) => Ok(()),
-            Err(e) => Err(crate::error::AppError::ValidationError(format!(
-                "Erro de validação: {}",
-                e
-            ))),
-        }
+        self.validate().map_err(crate::error::AppError::from)
     }
 }
 
@@ -116,6 +107,54 @@ pub struct TokenResponse {
     pub user: UserResponse,
 }
 
+/// Desafio emitido no login quando a conta tem verificação em duas etapas
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct TwoFactorChallenge {
+    /// Token a ser enviado junto com o código do autenticador
+    pub challenge_token: String,
+    /// Validade do desafio, em segundos
+    pub expires_in: i64,
+}
+
+/// Resultado do login: o token de acesso ou o desafio do segundo fator
+#[derive(Debug, Clone)]
+pub enum LoginResponse {
+    Authenticated(TokenResponse),
+    TwoFactorRequired(TwoFactorChallenge),
+}
+
+/// Localização do usuário, usada para sugerir trocas com pessoas próximas
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, ToSchema, Validate)]
+pub struct UserLocation {
+    /// Latitude em graus decimais
+    #[validate(range(min = -90.0, max = 90.0, code = "latitude_out_of_range"))]
+    pub latitude: f64,
+    /// Longitude em graus decimais
+    #[validate(range(min = -180.0, max = 180.0, code = "longitude_out_of_range"))]
+    pub longitude: f64,
+}
+
+impl UserLocation {
+    /// Valida todos os campos da localização
+    pub fn validate_all(&self) -> Result<(), crate::error::AppError> {
+        self.validate().map_err(crate::error::AppError::from)
+    }
+
+    /// Distância aproximada em quilômetros até outra localização (fórmula de haversine)
+    pub fn distance_km(&self, other: &UserLocation) -> f64 {
+        const EARTH_RADIUS_KM: f64 = 6371.0;
+
+        let d_lat = (other.latitude - self.latitude).to_radians();
+        let d_lon = (other.longitude - self.longitude).to_radians();
+        let a = (d_lat / 2.0).sin().powi(2)
+            + self.latitude.to_radians().cos()
+                * other.latitude.to_radians().cos()
+                * (d_lon / 2.0).sin().powi(2);
+
+        2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+    }
+}
+
 impl From<User> for UserResponse {
     fn from(user: User) -> Self {
         Self {
@@ -1,5 +1,6 @@
 #[cfg(test)]
 mod tests {
+    use crate::i18n::messages;
     use crate::models::user::CreateUserDto;
     use validator::Validate;
 
@@ -37,10 +38,9 @@ mod tests {
             "Erro deveria estar no campo 'name'"
         );
         assert!(
-            error.field_errors().get("name").unwrap()[0]
-                .message
-                .as_ref()
+            messages::find(&error.field_errors().get("name").unwrap()[0].code)
                 .unwrap()
+                .pt_br
                 .contains("não pode estar vazio"),
             "Mensagem de erro deveria indicar que o nome não pode estar vazio"
         );
@@ -69,10 +69,9 @@ mod tests {
             "Erro deveria estar no campo 'name'"
         );
         assert!(
-            error.field_errors().get("name").unwrap()[0]
-                .message
-                .as_ref()
+            messages::find(&error.field_errors().get("name").unwrap()[0].code)
                 .unwrap()
+                .pt_br
                 .contains("menos de 255"),
             "Mensagem de erro deveria indicar que o nome deve ter menos de 255 caracteres"
         );
@@ -101,10 +100,9 @@ mod tests {
             "Erro deveria estar no campo 'email'"
         );
         assert!(
-            error.field_errors().get("email").unwrap()[0]
-                .message
-                .as_ref()
+            messages::find(&error.field_errors().get("email").unwrap()[0].code)
                 .unwrap()
+                .pt_br
                 .contains("menos de 255"),
             "Mensagem de erro deveria indicar que o email deve ter menos de 255 caracteres"
         );
@@ -130,10 +128,9 @@ mod tests {
             "Erro deveria estar no campo 'email'"
         );
         assert!(
-            error.field_errors().get("email").unwrap()[0]
-                .message
-                .as_ref()
+            messages::find(&error.field_errors().get("email").unwrap()[0].code)
                 .unwrap()
+                .pt_br
                 .contains("não pode estar vazio"),
             "Mensagem de erro deveria indicar que o email não pode estar vazio"
         );
@@ -159,10 +156,9 @@ mod tests {
             "Erro deveria estar no campo 'email'"
         );
         assert!(
-            error.field_errors().get("email").unwrap()[0]
-                .message
-                .as_ref()
+            messages::find(&error.field_errors().get("email").unwrap()[0].code)
                 .unwrap()
+                .pt_br
                 .contains("Formato de email inválido"),
             "Mensagem de erro deveria indicar que o formato do email é inválido"
         );
@@ -188,10 +184,9 @@ mod tests {
             "Erro deveria estar no campo 'password'"
         );
         assert!(
-            error.field_errors().get("password").unwrap()[0]
-                .message
-                .as_ref()
+            messages::find(&error.field_errors().get("password").unwrap()[0].code)
                 .unwrap()
+                .pt_br
                 .contains("pelo menos 6 caracteres"),
             "Mensagem de erro deveria indicar que a senha deve ter pelo menos 6 caracteres"
         );
@@ -224,13 +219,15 @@ mod tests {
 
         let error = result.unwrap_err();
         match error {
-            crate::error::AppError::ValidationError(msg) => {
-                assert!(
-                    msg.contains("Erro de validação"),
-                    "Mensagem de erro deveria começar com 'Erro de validação'"
+            crate::error::AppError::InvalidFields(fields) => {
+                let names: Vec<&str> = fields.iter().map(|field| field.field.as_str()).collect();
+                assert_eq!(
+                    names,
+                    vec!["email", "name", "password"],
+                    "Cada campo inválido deveria ter o seu erro, em ordem alfabética"
                 );
             }
-            _ => panic!("Tipo de erro inesperado, esperava ValidationError"),
+            _ => panic!("Tipo de erro inesperado, esperava InvalidFields"),
         }
     }
 }
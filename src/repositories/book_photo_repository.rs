@@ -0,0 +1,151 @@
+use async_trait::async_trait;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::i18n::messages;
+use crate::repositories::db_error::{constraint_violation, ConstraintKind};
+use crate::models::book::{BookPhotoRecord, CreateBookPhotoDto};
+
+#[async_trait]
+pub trait BookPhotoRepository: Send + Sync + 'static {
+    async fn create(&self, photo: &CreateBookPhotoDto) -> Result<BookPhotoRecord, AppError>;
+    async fn find_by_id(&self, id: &Uuid) -> Result<Option<BookPhotoRecord>, AppError>;
+    async fn find_by_copy_id(&self, copy_id: &Uuid) -> Result<Vec<BookPhotoRecord>, AppError>;
+    async fn find_by_user_id(&self, user_id: &Uuid) -> Result<Vec<BookPhotoRecord>, AppError>;
+    async fn find_by_copy_ids(&self, copy_ids: &[Uuid]) -> Result<Vec<BookPhotoRecord>, AppError>;
+    async fn delete(&self, id: &Uuid) -> Result<bool, AppError>;
+}
+
+pub struct PgBookPhotoRepository {
+    pool: PgPool,
+}
+
+impl PgBookPhotoRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl BookPhotoRepository for PgBookPhotoRepository {
+    #[tracing::instrument(name = "book_photo_repository.create", level = "debug", skip_all)]
+    async fn create(&self, photo: &CreateBookPhotoDto) -> Result<BookPhotoRecord, AppError> {
+        let result = sqlx::query_as!(
+            BookPhotoRecord,
+            r#"
+            INSERT INTO books_offered_photos (copy_id, user_id, storage_key, content_type, size_bytes)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id, copy_id, user_id, storage_key, content_type, size_bytes, created_at
+            "#,
+            photo.copy_id,
+            photo.user_id,
+            photo.storage_key,
+            photo.content_type,
+            photo.size_bytes
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| match constraint_violation(&e) {
+            Some((ConstraintKind::ForeignKey, _)) => AppError::NotFoundError(messages::COPY_NOT_FOUND.text()),
+            _ => AppError::DatabaseError(e.to_string()),
+        })?;
+
+        Ok(result)
+    }
+
+    #[tracing::instrument(name = "book_photo_repository.find_by_id", level = "debug", skip_all)]
+    async fn find_by_id(&self, id: &Uuid) -> Result<Option<BookPhotoRecord>, AppError> {
+        let result = sqlx::query_as!(
+            BookPhotoRecord,
+            r#"
+            SELECT id, copy_id, user_id, storage_key, content_type, size_bytes, created_at
+            FROM books_offered_photos
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(result)
+    }
+
+    #[tracing::instrument(name = "book_photo_repository.find_by_copy_id", level = "debug", skip_all)]
+    async fn find_by_copy_id(&self, copy_id: &Uuid) -> Result<Vec<BookPhotoRecord>, AppError> {
+        let result = sqlx::query_as!(
+            BookPhotoRecord,
+            r#"
+            SELECT id, copy_id, user_id, storage_key, content_type, size_bytes, created_at
+            FROM books_offered_photos
+            WHERE copy_id = $1
+            ORDER BY created_at
+            "#,
+            copy_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(result)
+    }
+
+    #[tracing::instrument(name = "book_photo_repository.find_by_user_id", level = "debug", skip_all)]
+    async fn find_by_user_id(&self, user_id: &Uuid) -> Result<Vec<BookPhotoRecord>, AppError> {
+        let result = sqlx::query_as!(
+            BookPhotoRecord,
+            r#"
+            SELECT id, copy_id, user_id, storage_key, content_type, size_bytes, created_at
+            FROM books_offered_photos
+            WHERE user_id = $1
+            ORDER BY created_at
+            "#,
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(result)
+    }
+
+    #[tracing::instrument(name = "book_photo_repository.find_by_copy_ids", level = "debug", skip_all)]
+    async fn find_by_copy_ids(&self, copy_ids: &[Uuid]) -> Result<Vec<BookPhotoRecord>, AppError> {
+        if copy_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let result = sqlx::query_as!(
+            BookPhotoRecord,
+            r#"
+            SELECT id, copy_id, user_id, storage_key, content_type, size_bytes, created_at
+            FROM books_offered_photos
+            WHERE copy_id = ANY($1)
+            ORDER BY created_at
+            "#,
+            copy_ids
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(result)
+    }
+
+    #[tracing::instrument(name = "book_photo_repository.delete", level = "debug", skip_all)]
+    async fn delete(&self, id: &Uuid) -> Result<bool, AppError> {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM books_offered_photos
+            WHERE id = $1
+            "#,
+            id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
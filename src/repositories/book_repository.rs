@@ -1,4 +1,5 @@
-use crate::models::book::GoogleBookDto;
+use crate::models::book::{BookDetails, GoogleBookDto};
+use crate::models::shelf::ShelfList;
 use chrono::NaiveDate;
 use sqlx::PgPool;
 use async_trait::async_trait;
@@ -6,6 +7,7 @@ use serde::{Serialize, Deserialize};
 use utoipa::ToSchema;
 use uuid::Uuid;
 use crate::error::AppError;
+use crate::i18n::messages;
 
 // Estender GoogleBookDto para incluir o id do banco de dados
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -14,12 +16,59 @@ pub struct BookWithId {
     pub book: GoogleBookDto,
 }
 
+/// Tamanho máximo dos textos do livro gravados no cadastro
+const MAX_TEXT_LENGTH: usize = 250;
+
+/// Converte a data de publicação, que deve estar no formato AAAA-MM-DD
+pub(crate) fn parse_published_date(published_date: &Option<String>) -> Result<Option<NaiveDate>, AppError> {
+    published_date
+        .as_deref()
+        .map(|date_str| {
+            NaiveDate::parse_from_str(date_str, "%Y-%m-%d").map_err(|_| {
+                AppError::ValidationError(messages::PUBLISHED_DATE_INVALID.format(&[&date_str]))
+            })
+        })
+        .transpose()
+}
+
+/// Corta o texto no tamanho máximo gravado, sem dividir um caractere
+pub(crate) fn truncate_column(value: &str) -> &str {
+    let mut end = value.len().min(MAX_TEXT_LENGTH);
+    while !value.is_char_boundary(end) {
+        end -= 1;
+    }
+    &value[..end]
+}
+
 #[async_trait]
 pub trait BookRepository: Send + Sync + 'static {
+    /// Cadastra o livro e retorna o seu ID; se já existir um livro com o mesmo
+    /// google_id, retorna o ID dele em vez de criar um cadastro duplicado
     async fn create(&self, book: &GoogleBookDto) -> Result<Uuid, AppError>;
     async fn find_by_google_id(&self, google_id: &str) -> Result<Option<BookWithId>, AppError>;
+    /// Busca os livros já cadastrados entre os google_ids informados
+    async fn find_by_google_ids(&self, google_ids: &[String]) -> Result<Vec<BookWithId>, AppError>;
     async fn find_by_id(&self, id: &str) -> Result<Option<GoogleBookDto>, AppError>;
     async fn find_by_ids(&self, ids: &[String]) -> Result<Vec<BookWithId>, AppError>;
+    /// Busca o livro com as contagens de exemplares e de interessados e a lista
+    /// em que o usuário o tem
+    async fn find_details(&self, id: &Uuid, user_id: &Uuid) -> Result<Option<BookDetails>, AppError>;
+    /// Grava os dados obtidos do Google Books; campos ausentes ou vazios, e uma
+    /// data de publicação em outro formato, mantêm o valor atual
+    async fn update_metadata(&self, id: &Uuid, book: &GoogleBookDto) -> Result<(), AppError>;
+    /// Registra uma consulta ao Google Books que não trouxe dados
+    async fn mark_metadata_checked(&self, id: &Uuid) -> Result<(), AppError>;
+}
+
+/// Lista do usuário a partir das duas listas em que o livro pode estar
+pub(crate) fn user_list(offered: bool, wanted: bool) -> Option<ShelfList> {
+    if offered {
+        Some(ShelfList::Offered)
+    } else if wanted {
+        Some(ShelfList::Wanted)
+    } else {
+        None
+    }
 }
 
 pub struct PgBookRepository {
@@ -34,6 +83,7 @@ impl PgBookRepository {
 
 #[async_trait]
 impl BookRepository for PgBookRepository {
+    #[tracing::instrument(name = "book_repository.find_by_google_id", level = "debug", skip_all)]
     async fn find_by_google_id(&self, google_id: &str) -> Result<Option<BookWithId>, AppError> {
         let result = sqlx::query!(
             r#"
@@ -71,6 +121,52 @@ impl BookRepository for PgBookRepository {
         }))
     }
 
+    #[tracing::instrument(name = "book_repository.find_by_google_ids", level = "debug", skip_all)]
+    async fn find_by_google_ids(&self, google_ids: &[String]) -> Result<Vec<BookWithId>, AppError> {
+        if google_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let result = sqlx::query!(
+            r#"
+            SELECT 
+                id,
+                google_id,
+                title,
+                author,
+                publisher,
+                published_date,
+                description,
+                image_url,
+                page_count
+            FROM books 
+            WHERE google_id = ANY($1)
+            "#,
+            google_ids
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(result
+            .into_iter()
+            .map(|r| BookWithId {
+                id: r.id,
+                book: GoogleBookDto {
+                    google_id: r.google_id.unwrap_or_default(),
+                    title: r.title,
+                    authors: Some(r.author),
+                    publisher: r.publisher,
+                    published_date: r.published_date.map(|d| d.to_string()),
+                    description: Some(r.description),
+                    image_url: Some(r.image_url),
+                    page_count: r.page_count,
+                }
+            })
+            .collect())
+    }
+
+    #[tracing::instrument(name = "book_repository.find_by_id", level = "debug", skip_all)]
     async fn find_by_id(&self, id: &str) -> Result<Option<GoogleBookDto>, AppError> {
         // Tenta converter a string em UUID
         let book_id = match Uuid::parse_str(id) {
@@ -113,22 +209,9 @@ impl BookRepository for PgBookRepository {
         }
     }
 
+    #[tracing::instrument(name = "book_repository.create", level = "debug", skip_all)]
     async fn create(&self, book: &GoogleBookDto) -> Result<Uuid, AppError> {
-        // Tenta converter a data de publicação para o formato NaiveDate
-        let published_date = match &book.published_date {
-            Some(date_str) => {
-                match NaiveDate::parse_from_str(date_str, "%Y-%m-%d") {
-                    Ok(date) => Some(date),
-                    Err(_) => {
-                        return Err(AppError::ValidationError(format!(
-                            "A data '{}' deve estar no formato AAAA-MM-DD",
-                            date_str
-                        )));
-                    }
-                }
-            }
-            None => None,
-        };
+        let published_date = parse_published_date(&book.published_date)?;
 
         let result = sqlx::query!(
             r#"
@@ -140,14 +223,16 @@ impl BookRepository for PgBookRepository {
                 publisher, 
                 published_date, 
                 page_count, 
-                google_id
+                google_id,
+                metadata_fetched_at
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, CURRENT_TIMESTAMP)
+            ON CONFLICT (google_id) DO UPDATE SET updated_at = CURRENT_TIMESTAMP
             RETURNING id
             "#,
-            &book.title[..book.title.len().min(250)],
-            &book.authors.clone().unwrap_or_default()[..book.authors.clone().unwrap_or_default().len().min(250)],
-            &book.description.clone().unwrap_or_default()[..book.description.clone().unwrap_or_default().len().min(250)],
+            truncate_column(&book.title),
+            truncate_column(book.authors.as_deref().unwrap_or_default()),
+            truncate_column(book.description.as_deref().unwrap_or_default()),
             book.image_url.clone().unwrap_or_default(),
             book.publisher,
             published_date,
@@ -161,6 +246,7 @@ impl BookRepository for PgBookRepository {
         Ok(result.id)
     }
 
+    #[tracing::instrument(name = "book_repository.find_by_ids", level = "debug", skip_all)]
     async fn find_by_ids(&self, ids: &[String]) -> Result<Vec<BookWithId>, AppError> {
         // Validar e converter as strings para UUIDs
         let mut valid_uuids = Vec::with_capacity(ids.len());
@@ -219,4 +305,107 @@ impl BookRepository for PgBookRepository {
 
         Ok(books)
     }
+
+    #[tracing::instrument(name = "book_repository.find_details", level = "debug", skip_all)]
+    async fn find_details(&self, id: &Uuid, user_id: &Uuid) -> Result<Option<BookDetails>, AppError> {
+        let result = sqlx::query!(
+            r#"
+            SELECT 
+                b.id,
+                b.google_id,
+                b.title,
+                b.author,
+                b.publisher,
+                b.published_date,
+                b.description,
+                b.image_url,
+                b.page_count,
+                b.metadata_fetched_at,
+                b.metadata_checked_at,
+                (SELECT COUNT(*) FROM books_offered o WHERE o.book_id = b.id) AS "offered_count!",
+                (SELECT COUNT(*) FROM books_wanted w WHERE w.book_id = b.id) AS "wanted_count!",
+                EXISTS (
+                    SELECT 1 FROM books_offered o WHERE o.book_id = b.id AND o.user_id = $2
+                ) AS "offered_by_user!",
+                EXISTS (
+                    SELECT 1 FROM books_wanted w WHERE w.book_id = b.id AND w.user_id = $2
+                ) AS "wanted_by_user!"
+            FROM books b
+            WHERE b.id = $1
+            "#,
+            id,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(result.map(|r| BookDetails {
+            id: r.id,
+            book: GoogleBookDto {
+                google_id: r.google_id.unwrap_or_default(),
+                title: r.title,
+                authors: Some(r.author),
+                publisher: r.publisher,
+                published_date: r.published_date.map(|d| d.to_string()),
+                description: Some(r.description),
+                image_url: Some(r.image_url),
+                page_count: r.page_count,
+            },
+            offered_count: r.offered_count,
+            wanted_count: r.wanted_count,
+            user_list: user_list(r.offered_by_user, r.wanted_by_user),
+            metadata_fetched_at: r.metadata_fetched_at,
+            metadata_checked_at: r.metadata_checked_at,
+        }))
+    }
+
+    #[tracing::instrument(name = "book_repository.update_metadata", level = "debug", skip_all)]
+    async fn update_metadata(&self, id: &Uuid, book: &GoogleBookDto) -> Result<(), AppError> {
+        let published_date = parse_published_date(&book.published_date).ok().flatten();
+
+        sqlx::query!(
+            r#"
+            UPDATE books
+            SET 
+                title = COALESCE(NULLIF($2, ''), title),
+                author = COALESCE(NULLIF($3, ''), author),
+                description = COALESCE(NULLIF($4, ''), description),
+                image_url = COALESCE(NULLIF($5, ''), image_url),
+                publisher = COALESCE(NULLIF($6, ''), publisher),
+                published_date = COALESCE($7, published_date),
+                page_count = COALESCE($8, page_count),
+                metadata_fetched_at = CURRENT_TIMESTAMP,
+                metadata_checked_at = CURRENT_TIMESTAMP,
+                updated_at = CURRENT_TIMESTAMP
+            WHERE id = $1
+            "#,
+            id,
+            truncate_column(&book.title),
+            truncate_column(book.authors.as_deref().unwrap_or_default()),
+            truncate_column(book.description.as_deref().unwrap_or_default()),
+            book.image_url.clone().unwrap_or_default(),
+            book.publisher,
+            published_date,
+            book.page_count
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "book_repository.mark_metadata_checked", level = "debug", skip_all)]
+    async fn mark_metadata_checked(&self, id: &Uuid) -> Result<(), AppError> {
+        sqlx::query!(
+            "UPDATE books SET metadata_checked_at = CURRENT_TIMESTAMP WHERE id = $1",
+            id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
 }
@@ -1,15 +1,11 @@
 use crate::error::AppError;
+use crate::i18n::messages;
 use crate::repositories::book_repository::BookRepository;
 use crate::repositories::book_repository_test::create_test_book;
 use crate::repositories::book_repository_test::setup_test_repository;
-use crate::repositories::test_helpers::get_test_mutex;
 
 #[tokio::test]
 async fn test_create_book() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-
     let book_repository = setup_test_repository().await;
 
     // Cria um livro para o teste usando a factory
@@ -23,7 +19,7 @@ async fn test_create_book() {
 
     // Verifica se o ID retornado não é vazio
     assert!(
-        book_id.to_string().len() > 0,
+        !book_id.to_string().is_empty(),
         "O ID do livro não deve ser vazio"
     );
 
@@ -57,10 +53,6 @@ async fn test_create_book() {
 
 #[tokio::test]
 async fn test_create_book_with_invalid_date() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-
     let book_repository = setup_test_repository().await;
 
     // Cria um livro para o teste usando a factory, com uma data inválida
@@ -71,9 +63,30 @@ async fn test_create_book_with_invalid_date() {
 
     assert!(result.is_err(), "Deveria falhar ao criar livro com data inválida");
 
-    // Verifica se é um erro de validação
+    // Verifica se é um erro de validação, com a mensagem do catálogo
     match result {
-        Err(AppError::ValidationError(_)) => (),
+        Err(AppError::ValidationError(message)) => {
+            assert_eq!(message, messages::PUBLISHED_DATE_INVALID.format(&[&"10/05/2022"]));
+        }
         _ => panic!("Esperava um erro de validação"),
     }
 }
+
+#[tokio::test]
+async fn test_create_book_with_existing_google_id() {
+    let book_repository = setup_test_repository().await;
+    let book = create_test_book("abc789", true);
+
+    // Duas inclusões simultâneas do mesmo livro resultam em um único cadastro
+    let (first, second) = tokio::join!(book_repository.create(&book), book_repository.create(&book));
+    let first = first.expect("Falha ao criar livro");
+    let second = second.expect("Falha ao criar livro");
+
+    assert_eq!(first, second, "O mesmo google_id deve resultar no mesmo livro");
+
+    let found = book_repository
+        .find_by_google_ids(std::slice::from_ref(&book.google_id))
+        .await
+        .expect("Falha ao buscar livros pelo google_id");
+    assert_eq!(found.len(), 1);
+}
@@ -1,14 +1,9 @@
 use crate::repositories::book_repository::BookRepository;
 use crate::repositories::book_repository_test::create_test_book;
 use crate::repositories::book_repository_test::setup_test_repository;
-use crate::repositories::test_helpers::get_test_mutex;
 
 #[tokio::test]
 async fn test_find_book_by_google_id() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-
     let book_repository = setup_test_repository().await;
     // Cria um livro para o teste usando a factory
     let book = create_test_book("test123", true);
@@ -57,10 +52,6 @@ async fn test_find_book_by_google_id() {
 
 #[tokio::test]
 async fn test_find_book_by_google_id_not_found() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-
     let book_repository = setup_test_repository().await;
 
     // Testa buscar um livro que não existe
@@ -1,14 +1,9 @@
 use crate::repositories::book_repository::BookRepository;
 use crate::repositories::book_repository_test::create_test_book;
 use crate::repositories::book_repository_test::setup_test_repository;
-use crate::repositories::test_helpers::get_test_mutex;
 
 #[tokio::test]
 async fn test_find_by_google_id() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-
     let book_repository = setup_test_repository().await;
 
     // Cria um livro para o teste usando a factory
@@ -22,7 +17,7 @@ async fn test_find_by_google_id() {
 
     // Verifica se o ID retornado não é vazio
     assert!(
-        book_id.to_string().len() > 0,
+        !book_id.to_string().is_empty(),
         "O ID do livro não deve ser vazio"
     );
 
@@ -56,10 +51,6 @@ async fn test_find_by_google_id() {
 
 #[tokio::test]
 async fn test_find_by_google_id_not_found() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-
     let book_repository = setup_test_repository().await;
 
     // Busca um livro com um google_id que não existe
@@ -74,3 +65,24 @@ async fn test_find_by_google_id_not_found() {
         "Não deveria encontrar um livro com google_id inexistente"
     );
 }
+
+#[tokio::test]
+async fn test_find_by_google_ids() {
+    let book_repository = setup_test_repository().await;
+
+    let first_id = book_repository.create(&create_test_book("batch_google_id_1", true)).await.unwrap();
+    let second_id = book_repository.create(&create_test_book("batch_google_id_2", true)).await.unwrap();
+
+    let ids = vec![
+        "batch_google_id_1".to_string(),
+        "batch_google_id_2".to_string(),
+        "batch_google_id_inexistente".to_string(),
+    ];
+    let mut found = book_repository.find_by_google_ids(&ids).await.unwrap();
+    found.sort_by(|a, b| a.book.google_id.cmp(&b.book.google_id));
+
+    assert_eq!(found.len(), 2);
+    assert_eq!(found[0].id, first_id);
+    assert_eq!(found[1].id, second_id);
+    assert!(book_repository.find_by_google_ids(&[]).await.unwrap().is_empty());
+}
@@ -1,14 +1,9 @@
 use crate::repositories::book_repository::BookRepository;
 use crate::repositories::book_repository_test::create_test_book;
 use crate::repositories::book_repository_test::setup_test_repository;
-use crate::repositories::test_helpers::get_test_mutex;
 
 #[tokio::test]
 async fn test_find_by_id() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-
     let book_repository = setup_test_repository().await;
 
     // Cria um livro para o teste usando a factory
@@ -22,7 +17,7 @@ async fn test_find_by_id() {
 
     // Verifica se o ID retornado não é vazio
     assert!(
-        book_id.to_string().len() > 0,
+        !book_id.to_string().is_empty(),
         "O ID do livro não deve ser vazio"
     );
 
@@ -53,10 +48,6 @@ async fn test_find_by_id() {
 
 #[tokio::test]
 async fn test_find_by_id_not_found() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-
     let book_repository = setup_test_repository().await;
 
     // Busca um livro com um ID que não existe
@@ -1,15 +1,10 @@
 use crate::repositories::book_repository::BookRepository;
 use crate::repositories::book_repository_test::create_test_book;
 use crate::repositories::book_repository_test::setup_test_repository;
-use crate::repositories::test_helpers::get_test_mutex;
 use uuid::Uuid;
 
 #[tokio::test]
 async fn test_find_by_ids_multiple_books() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-
     let book_repository = setup_test_repository().await;
 
     // Cria múltiplos livros para o teste
@@ -66,10 +61,6 @@ async fn test_find_by_ids_multiple_books() {
 
 #[tokio::test]
 async fn test_find_by_ids_empty_list() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-
     let book_repository = setup_test_repository().await;
 
     // Busca com lista vazia
@@ -89,10 +80,6 @@ async fn test_find_by_ids_empty_list() {
 
 #[tokio::test]
 async fn test_find_by_ids_invalid_ids() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-
     let book_repository = setup_test_repository().await;
 
     // Busca com IDs inválidos
@@ -116,10 +103,6 @@ async fn test_find_by_ids_invalid_ids() {
 
 #[tokio::test]
 async fn test_find_by_ids_mixed_valid_invalid() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-
     let book_repository = setup_test_repository().await;
 
     // Cria um livro para o teste
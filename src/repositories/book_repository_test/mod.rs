@@ -14,7 +14,6 @@ async fn setup_test_repository() -> PgBookRepository {
     let pool = get_test_db_pool().await;
 
     // Limpa o banco de dados para garantir o isolamento dos testes
-    crate::repositories::test_helpers::clean_database(&pool).await;
 
     // Criamos o DatabasePool com o pool real
     PgBookRepository::new(pool)
@@ -2,15 +2,52 @@ use async_trait::async_trait;
 use sqlx::PgPool;
 use uuid::Uuid;
 
-use crate::error::AppError;
-use crate::models::book::{BookOffered, CreateBookOfferedDto};
+use crate::error::{AppError, ErrorCode};
+use crate::i18n::{messages, Message};
+use crate::models::book::{BookCondition, BookOffered, CreateBookOfferedDto, OfferedCopyAttributes};
+use crate::models::user::UserResponse;
+use crate::repositories::books_wanted_repository::remove_wanted;
+use crate::repositories::db_error::{constraint_violation, ConstraintKind};
+use crate::repositories::unit_of_work::UnitOfWork;
 
 #[async_trait]
 pub trait BooksOfferedRepository: Send + Sync + 'static {
     async fn create(&self, book_offered: &CreateBookOfferedDto) -> Result<BookOffered, AppError>;
+    /// Cria `quantity` exemplares idênticos do mesmo livro em uma única operação
+    async fn create_copies(
+        &self,
+        book_offered: &CreateBookOfferedDto,
+        quantity: i32,
+    ) -> Result<Vec<BookOffered>, AppError>;
+    /// Cria os exemplares de vários livros em uma única transação, na ordem informada
+    async fn create_many(&self, books_offered: &[CreateBookOfferedDto]) -> Result<Vec<BookOffered>, AppError>;
+    /// Cria o exemplar de um livro desejado, retirando-o da lista de desejados na
+    /// mesma transação; retorna None se o livro não estava entre os desejados
+    async fn create_from_wanted(&self, book_offered: &CreateBookOfferedDto) -> Result<Option<BookOffered>, AppError>;
+    /// Busca um exemplar (o mais antigo) do livro oferecido pelo usuário
     async fn find(&self, book_id: &Uuid, user_id: &Uuid) -> Result<Option<BookOffered>, AppError>;
+    async fn find_by_id(&self, id: &Uuid) -> Result<Option<BookOffered>, AppError>;
+    /// Lista todos os exemplares de um livro oferecidos pelo usuário
+    async fn find_copies(&self, book_id: &Uuid, user_id: &Uuid) -> Result<Vec<BookOffered>, AppError>;
+    async fn update(
+        &self,
+        id: &Uuid,
+        user_id: &Uuid,
+        attributes: &OfferedCopyAttributes,
+    ) -> Result<Option<BookOffered>, AppError>;
+    /// Remove todos os exemplares de um livro oferecidos pelo usuário
     async fn delete(&self, book_id: &Uuid, user_id: &Uuid) -> Result<bool, AppError>;
+    async fn delete_copy(&self, id: &Uuid, user_id: &Uuid) -> Result<bool, AppError>;
+    /// Remove todos os exemplares dos livros informados; retorna quantos foram removidos
+    async fn delete_many(&self, book_ids: &[Uuid], user_id: &Uuid) -> Result<u64, AppError>;
+    /// Indica se algum dos exemplares está reservado em uma troca em andamento
+    async fn any_reserved(&self, ids: &[Uuid]) -> Result<bool, AppError>;
     async fn find_by_user_id(&self, user_id: &Uuid) -> Result<Vec<Uuid>, AppError>;
+    async fn find_all_by_user_id(&self, user_id: &Uuid) -> Result<Vec<BookOffered>, AppError>;
+    async fn find_owners_by_book_id(
+        &self,
+        book_id: &Uuid,
+    ) -> Result<Vec<(UserResponse, BookOffered)>, AppError>;
 }
 
 pub struct PgBooksOfferedRepository {
@@ -23,69 +60,266 @@ impl PgBooksOfferedRepository {
     }
 }
 
+// Exemplares reservados em trocas em andamento não podem ser removidos
+fn map_delete_error(e: sqlx::Error, message: Message) -> AppError {
+    match constraint_violation(&e) {
+        Some((ConstraintKind::ForeignKey, Some("trade_copy_reservations_copy_id_fkey"))) => {
+            AppError::Conflict(ErrorCode::CopyReserved, message.text())
+        }
+        _ => AppError::DatabaseError(e.to_string()),
+    }
+}
+
+// Traduz as violações de chave estrangeira na inclusão de exemplares
+fn map_create_error(e: sqlx::Error, book_offered: &CreateBookOfferedDto) -> AppError {
+    match constraint_violation(&e) {
+        Some((ConstraintKind::ForeignKey, Some("books_offered_book_id_fkey"))) => AppError::ValidationError(
+            messages::BOOK_ID_NOT_FOUND.format(&[&book_offered.book_id]),
+        ),
+        Some((ConstraintKind::ForeignKey, Some("books_offered_user_id_fkey"))) => AppError::ValidationError(
+            messages::USER_ID_NOT_FOUND.format(&[&book_offered.user_id]),
+        ),
+        _ => AppError::DatabaseError(e.to_string()),
+    }
+}
+
+/// Insere `quantity` exemplares idênticos dentro de uma unidade de trabalho
+///
+/// O livro não pode estar na lista de desejados do usuário; a verificação é
+/// feita sob o bloqueio do par usuário/livro, o mesmo usado na inclusão nos
+/// desejados, para que duas requisições simultâneas não coloquem o livro nas
+/// duas listas.
+pub(crate) async fn insert_copies(
+    uow: &mut UnitOfWork,
+    book_offered: &CreateBookOfferedDto,
+    quantity: i32,
+) -> Result<Vec<BookOffered>, AppError> {
+    uow.lock_user_book(&book_offered.user_id, &book_offered.book_id).await?;
+
+    let wanted = sqlx::query!(
+        r#"
+        SELECT EXISTS (
+            SELECT 1 FROM books_wanted WHERE book_id = $1 AND user_id = $2
+        ) as "wanted!"
+        "#,
+        book_offered.book_id,
+        book_offered.user_id
+    )
+    .fetch_one(uow.conn())
+    .await
+    .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    if wanted.wanted {
+        return Err(AppError::Conflict(
+            ErrorCode::BookAlreadyWanted,
+            messages::BOOK_ALREADY_WANTED.text(),
+        ));
+    }
+
+    let attributes = &book_offered.attributes;
+    let result = sqlx::query!(
+        r#"
+        INSERT INTO books_offered (book_id, user_id, condition, language, notes, available)
+        SELECT $1, $2, $3, $4, $5, $6
+        FROM generate_series(1, $7::int)
+        RETURNING id, book_id, user_id, condition, language, notes, available
+        "#,
+        book_offered.book_id,
+        book_offered.user_id,
+        attributes.condition.as_str(),
+        attributes.language,
+        attributes.notes,
+        attributes.available,
+        quantity
+    )
+    .fetch_all(uow.conn())
+    .await
+    .map_err(|e| map_create_error(e, book_offered))?;
+
+    result
+        .into_iter()
+        .map(|r| to_book_offered(r.id, r.book_id, r.user_id, &r.condition, r.language, r.notes, r.available))
+        .collect()
+}
+
+// Monta um BookOffered a partir das colunas da tabela books_offered
+fn to_book_offered(
+    id: Uuid,
+    book_id: Uuid,
+    user_id: Uuid,
+    condition: &str,
+    language: Option<String>,
+    notes: Option<String>,
+    available: bool,
+) -> Result<BookOffered, AppError> {
+    Ok(BookOffered {
+        id,
+        book_id,
+        user_id,
+        attributes: OfferedCopyAttributes {
+            condition: condition.parse::<BookCondition>()?,
+            language,
+            notes,
+            available,
+        },
+    })
+}
+
 #[async_trait]
 impl BooksOfferedRepository for PgBooksOfferedRepository {
+    #[tracing::instrument(name = "books_offered_repository.create", level = "debug", skip_all)]
     async fn create(&self, book_offered: &CreateBookOfferedDto) -> Result<BookOffered, AppError> {
-        // Inserir na tabela books_offered
+        let mut copies = self.create_copies(book_offered, 1).await?;
+
+        copies
+            .pop()
+            .ok_or_else(|| AppError::DatabaseError("Nenhum exemplar foi criado".to_string()))
+    }
+
+    #[tracing::instrument(name = "books_offered_repository.create_copies", level = "debug", skip_all)]
+    async fn create_copies(
+        &self,
+        book_offered: &CreateBookOfferedDto,
+        quantity: i32,
+    ) -> Result<Vec<BookOffered>, AppError> {
+        let mut uow = UnitOfWork::begin(&self.pool).await?;
+        let copies = insert_copies(&mut uow, book_offered, quantity).await?;
+        uow.commit().await?;
+
+        Ok(copies)
+    }
+
+    #[tracing::instrument(name = "books_offered_repository.create_many", level = "debug", skip_all)]
+    async fn create_many(&self, books_offered: &[CreateBookOfferedDto]) -> Result<Vec<BookOffered>, AppError> {
+        let mut uow = UnitOfWork::begin(&self.pool).await?;
+
+        let mut copies = Vec::with_capacity(books_offered.len());
+        for book_offered in books_offered {
+            copies.extend(insert_copies(&mut uow, book_offered, 1).await?);
+        }
+
+        uow.commit().await?;
+
+        Ok(copies)
+    }
+
+    #[tracing::instrument(name = "books_offered_repository.create_from_wanted", level = "debug", skip_all)]
+    async fn create_from_wanted(&self, book_offered: &CreateBookOfferedDto) -> Result<Option<BookOffered>, AppError> {
+        let mut uow = UnitOfWork::begin(&self.pool).await?;
+
+        // Sem a entrada nos desejados não há o que mover; a transação é descartada
+        if !remove_wanted(&mut uow, &book_offered.book_id, &book_offered.user_id).await? {
+            return Ok(None);
+        }
+
+        let copy = insert_copies(&mut uow, book_offered, 1).await?.pop();
+        uow.commit().await?;
+
+        Ok(copy)
+    }
+
+    #[tracing::instrument(name = "books_offered_repository.find", level = "debug", skip_all)]
+    async fn find(&self, book_id: &Uuid, user_id: &Uuid) -> Result<Option<BookOffered>, AppError> {
         let result = sqlx::query!(
             r#"
-            INSERT INTO books_offered (book_id, user_id)
-            VALUES ($1, $2)
-            RETURNING book_id, user_id
+            SELECT id, book_id, user_id, condition, language, notes, available
+            FROM books_offered
+            WHERE book_id = $1 AND user_id = $2
+            ORDER BY created_at
+            LIMIT 1
             "#,
-            book_offered.book_id,
-            book_offered.user_id
+            book_id,
+            user_id
         )
-        .fetch_one(&self.pool)
+        .fetch_optional(&self.pool)
         .await
-        .map_err(|e| {
-            if e.to_string().contains("duplicate key") {
-                AppError::ValidationError("Este livro já está na sua lista de possuídos".to_string())
-            } else if e.to_string().contains("foreign key constraint") {
-                if e.to_string().contains("books_offered_book_id_fkey") {
-                    AppError::ValidationError(format!(
-                        "Livro com ID {} não encontrado",
-                        book_offered.book_id
-                    ))
-                } else if e.to_string().contains("books_offered_user_id_fkey") {
-                    AppError::ValidationError(format!(
-                        "Usuário com ID {} não encontrado",
-                        book_offered.user_id
-                    ))
-                } else {
-                    AppError::DatabaseError(e.to_string())
-                }
-            } else {
-                AppError::DatabaseError(e.to_string())
-            }
-        })?;
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
 
-        Ok(BookOffered {
-            book_id: result.book_id,
-            user_id: result.user_id,
-        })
+        result
+            .map(|r| {
+                to_book_offered(r.id, r.book_id, r.user_id, &r.condition, r.language, r.notes, r.available)
+            })
+            .transpose()
     }
 
-    async fn find(&self, book_id: &Uuid, user_id: &Uuid) -> Result<Option<BookOffered>, AppError> {
+    #[tracing::instrument(name = "books_offered_repository.find_by_id", level = "debug", skip_all)]
+    async fn find_by_id(&self, id: &Uuid) -> Result<Option<BookOffered>, AppError> {
         let result = sqlx::query!(
             r#"
-            SELECT book_id, user_id
+            SELECT id, book_id, user_id, condition, language, notes, available
+            FROM books_offered
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        result
+            .map(|r| {
+                to_book_offered(r.id, r.book_id, r.user_id, &r.condition, r.language, r.notes, r.available)
+            })
+            .transpose()
+    }
+
+    #[tracing::instrument(name = "books_offered_repository.find_copies", level = "debug", skip_all)]
+    async fn find_copies(&self, book_id: &Uuid, user_id: &Uuid) -> Result<Vec<BookOffered>, AppError> {
+        let result = sqlx::query!(
+            r#"
+            SELECT id, book_id, user_id, condition, language, notes, available
             FROM books_offered
             WHERE book_id = $1 AND user_id = $2
+            ORDER BY created_at
             "#,
             book_id,
             user_id
         )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        result
+            .into_iter()
+            .map(|r| {
+                to_book_offered(r.id, r.book_id, r.user_id, &r.condition, r.language, r.notes, r.available)
+            })
+            .collect()
+    }
+
+    #[tracing::instrument(name = "books_offered_repository.update", level = "debug", skip_all)]
+    async fn update(
+        &self,
+        id: &Uuid,
+        user_id: &Uuid,
+        attributes: &OfferedCopyAttributes,
+    ) -> Result<Option<BookOffered>, AppError> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE books_offered
+            SET condition = $3, language = $4, notes = $5, available = $6, updated_at = CURRENT_TIMESTAMP
+            WHERE id = $1 AND user_id = $2
+            RETURNING id, book_id, user_id, condition, language, notes, available
+            "#,
+            id,
+            user_id,
+            attributes.condition.as_str(),
+            attributes.language,
+            attributes.notes,
+            attributes.available
+        )
         .fetch_optional(&self.pool)
         .await
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
 
-        Ok(result.map(|r| BookOffered {
-            book_id: r.book_id,
-            user_id: r.user_id,
-        }))
+        result
+            .map(|r| {
+                to_book_offered(r.id, r.book_id, r.user_id, &r.condition, r.language, r.notes, r.available)
+            })
+            .transpose()
     }
 
+    #[tracing::instrument(name = "books_offered_repository.delete", level = "debug", skip_all)]
     async fn delete(&self, book_id: &Uuid, user_id: &Uuid) -> Result<bool, AppError> {
         let result = sqlx::query!(
             r#"
@@ -97,16 +331,72 @@ impl BooksOfferedRepository for PgBooksOfferedRepository {
         )
         .execute(&self.pool)
         .await
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        .map_err(|e| {
+            map_delete_error(e, messages::BOOK_COPIES_RESERVED)
+        })?;
 
         // Retorna true se algo foi excluído, false caso contrário
         Ok(result.rows_affected() > 0)
     }
 
+    #[tracing::instrument(name = "books_offered_repository.delete_many", level = "debug", skip_all)]
+    async fn delete_many(&self, book_ids: &[Uuid], user_id: &Uuid) -> Result<u64, AppError> {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM books_offered
+            WHERE book_id = ANY($1) AND user_id = $2
+            "#,
+            book_ids,
+            user_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            map_delete_error(e, messages::BOOKS_COPIES_RESERVED)
+        })?;
+
+        Ok(result.rows_affected())
+    }
+
+    #[tracing::instrument(name = "books_offered_repository.delete_copy", level = "debug", skip_all)]
+    async fn delete_copy(&self, id: &Uuid, user_id: &Uuid) -> Result<bool, AppError> {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM books_offered
+            WHERE id = $1 AND user_id = $2
+            "#,
+            id,
+            user_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| map_delete_error(e, messages::COPY_RESERVED))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[tracing::instrument(name = "books_offered_repository.any_reserved", level = "debug", skip_all)]
+    async fn any_reserved(&self, ids: &[Uuid]) -> Result<bool, AppError> {
+        let result = sqlx::query!(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM trade_copy_reservations WHERE copy_id = ANY($1)
+            ) as "reserved!"
+            "#,
+            ids
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(result.reserved)
+    }
+
+    #[tracing::instrument(name = "books_offered_repository.find_by_user_id", level = "debug", skip_all)]
     async fn find_by_user_id(&self, user_id: &Uuid) -> Result<Vec<Uuid>, AppError> {
         let result = sqlx::query!(
             r#"
-            SELECT book_id
+            SELECT DISTINCT book_id
             FROM books_offered
             WHERE user_id = $1
             "#,
@@ -118,4 +408,73 @@ impl BooksOfferedRepository for PgBooksOfferedRepository {
 
         Ok(result.into_iter().map(|r| r.book_id).collect())
     }
-} 
\ No newline at end of file
+
+    #[tracing::instrument(name = "books_offered_repository.find_all_by_user_id", level = "debug", skip_all)]
+    async fn find_all_by_user_id(&self, user_id: &Uuid) -> Result<Vec<BookOffered>, AppError> {
+        let result = sqlx::query!(
+            r#"
+            SELECT id, book_id, user_id, condition, language, notes, available
+            FROM books_offered
+            WHERE user_id = $1
+            ORDER BY created_at
+            "#,
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        result
+            .into_iter()
+            .map(|r| {
+                to_book_offered(r.id, r.book_id, r.user_id, &r.condition, r.language, r.notes, r.available)
+            })
+            .collect()
+    }
+
+    #[tracing::instrument(name = "books_offered_repository.find_owners_by_book_id", level = "debug", skip_all)]
+    async fn find_owners_by_book_id(
+        &self,
+        book_id: &Uuid,
+    ) -> Result<Vec<(UserResponse, BookOffered)>, AppError> {
+        // Lista os exemplares do livro com seus donos, com os disponíveis primeiro
+        let result = sqlx::query!(
+            r#"
+            SELECT
+                o.id, o.book_id, o.user_id, o.condition, o.language, o.notes, o.available,
+                u.name, u.email, u.created_at, u.updated_at
+            FROM books_offered o
+            INNER JOIN users u ON u.id = o.user_id
+            WHERE o.book_id = $1
+            ORDER BY o.available DESC, o.created_at
+            "#,
+            book_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        result
+            .into_iter()
+            .map(|r| {
+                let owner = UserResponse {
+                    id: r.user_id,
+                    name: r.name,
+                    email: r.email,
+                    created_at: r.created_at,
+                    updated_at: r.updated_at,
+                };
+                let offered = to_book_offered(
+                    r.id,
+                    r.book_id,
+                    r.user_id,
+                    &r.condition,
+                    r.language,
+                    r.notes,
+                    r.available,
+                )?;
+                Ok((owner, offered))
+            })
+            .collect()
+    }
+}
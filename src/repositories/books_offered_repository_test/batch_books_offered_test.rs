@@ -0,0 +1,84 @@
+use crate::models::book::{BookCondition, CreateBookOfferedDto, OfferedCopyAttributes};
+use crate::repositories::book_repository::BookRepository;
+use crate::repositories::books_offered_repository::BooksOfferedRepository;
+use crate::repositories::books_offered_repository_test::{
+    create_test_book, create_test_user, setup_book_repository, setup_test_repository, setup_user_repository,
+};
+use crate::repositories::user_repository::UserRepository;
+use uuid::Uuid;
+
+#[tokio::test]
+async fn test_create_many_keeps_order() {
+    let book_repository = setup_book_repository().await;
+    let user_repository = setup_user_repository().await;
+    let books_offered_repository = setup_test_repository().await;
+
+    let user = user_repository.create(&create_test_user(), "senha_hash".to_string()).await.unwrap();
+    let first_book = book_repository.create(&create_test_book("batch_1")).await.unwrap();
+    let second_book = book_repository.create(&create_test_book("batch_2")).await.unwrap();
+
+    let dtos = vec![
+        CreateBookOfferedDto { book_id: first_book, user_id: user.id, attributes: OfferedCopyAttributes::default() },
+        CreateBookOfferedDto {
+            book_id: second_book,
+            user_id: user.id,
+            attributes: OfferedCopyAttributes { condition: BookCondition::Poor, ..OfferedCopyAttributes::default() },
+        },
+        CreateBookOfferedDto { book_id: first_book, user_id: user.id, attributes: OfferedCopyAttributes::default() },
+    ];
+
+    let copies = books_offered_repository.create_many(&dtos).await.unwrap();
+
+    assert_eq!(copies.len(), 3);
+    assert_eq!(copies[0].book_id, first_book);
+    assert_eq!(copies[1].book_id, second_book);
+    assert_eq!(copies[1].attributes.condition, BookCondition::Poor);
+    assert_eq!(copies[2].book_id, first_book);
+    assert_eq!(books_offered_repository.find_all_by_user_id(&user.id).await.unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn test_create_many_is_atomic() {
+    let book_repository = setup_book_repository().await;
+    let user_repository = setup_user_repository().await;
+    let books_offered_repository = setup_test_repository().await;
+
+    let user = user_repository.create(&create_test_user(), "senha_hash".to_string()).await.unwrap();
+    let book_id = book_repository.create(&create_test_book("batch_atomic")).await.unwrap();
+
+    // O segundo item referencia um livro inexistente e desfaz o primeiro
+    let dtos = vec![
+        CreateBookOfferedDto { book_id, user_id: user.id, attributes: OfferedCopyAttributes::default() },
+        CreateBookOfferedDto { book_id: Uuid::new_v4(), user_id: user.id, attributes: OfferedCopyAttributes::default() },
+    ];
+
+    let result = books_offered_repository.create_many(&dtos).await;
+
+    assert!(result.is_err());
+    assert!(books_offered_repository.find_all_by_user_id(&user.id).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_delete_many() {
+    let book_repository = setup_book_repository().await;
+    let user_repository = setup_user_repository().await;
+    let books_offered_repository = setup_test_repository().await;
+
+    let user = user_repository.create(&create_test_user(), "senha_hash".to_string()).await.unwrap();
+    let first_book = book_repository.create(&create_test_book("delete_batch_1")).await.unwrap();
+    let second_book = book_repository.create(&create_test_book("delete_batch_2")).await.unwrap();
+    let kept_book = book_repository.create(&create_test_book("delete_batch_3")).await.unwrap();
+
+    let dto = |book_id| CreateBookOfferedDto { book_id, user_id: user.id, attributes: OfferedCopyAttributes::default() };
+    books_offered_repository
+        .create_many(&[dto(first_book), dto(first_book), dto(second_book), dto(kept_book)])
+        .await
+        .unwrap();
+
+    let removed = books_offered_repository.delete_many(&[first_book, second_book], &user.id).await.unwrap();
+
+    assert_eq!(removed, 3);
+    let remaining = books_offered_repository.find_all_by_user_id(&user.id).await.unwrap();
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].book_id, kept_book);
+}
@@ -1,19 +1,17 @@
-use crate::models::book::CreateBookOfferedDto;
+use crate::error::{AppError, ErrorCode};
+use crate::models::book::{BookCondition, CreateBookOfferedDto, CreateBookWantedDto, OfferedCopyAttributes};
 use crate::repositories::book_repository::BookRepository;
 use crate::repositories::books_offered_repository::BooksOfferedRepository;
 use crate::repositories::books_offered_repository_test::{
     create_test_book, create_test_user, setup_book_repository, setup_test_repository, setup_user_repository,
 };
-use crate::repositories::test_helpers::get_test_mutex;
+use crate::repositories::books_wanted_repository::{BooksWantedRepository, PgBooksWantedRepository};
+use crate::repositories::test_helpers::get_test_db_pool;
 use crate::repositories::user_repository::UserRepository;
 use uuid::Uuid;
 
 #[tokio::test]
 async fn test_create_books_offered() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-
     // Setup dos repositórios
     let book_repository = setup_book_repository().await;
     let user_repository = setup_user_repository().await;
@@ -31,6 +29,7 @@ async fn test_create_books_offered() {
     let book_offered = CreateBookOfferedDto {
         book_id,
         user_id: user.id,
+        attributes: OfferedCopyAttributes::default(),
     };
 
     let result = books_offered_repository.create(&book_offered).await;
@@ -44,10 +43,6 @@ async fn test_create_books_offered() {
 
 #[tokio::test]
 async fn test_create_books_offered_with_invalid_book_id() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-
     // Setup dos repositórios
     let user_repository = setup_user_repository().await;
     let books_offered_repository = setup_test_repository().await;
@@ -60,6 +55,7 @@ async fn test_create_books_offered_with_invalid_book_id() {
     let book_offered = CreateBookOfferedDto {
         book_id: Uuid::new_v4(),
         user_id: user.id,
+        attributes: OfferedCopyAttributes::default(),
     };
 
     let result = books_offered_repository.create(&book_offered).await;
@@ -74,10 +70,6 @@ async fn test_create_books_offered_with_invalid_book_id() {
 
 #[tokio::test]
 async fn test_create_books_offered_with_invalid_user_id() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-
     // Setup dos repositórios
     let book_repository = setup_book_repository().await;
     let books_offered_repository = setup_test_repository().await;
@@ -90,6 +82,7 @@ async fn test_create_books_offered_with_invalid_user_id() {
     let book_offered = CreateBookOfferedDto {
         book_id,
         user_id: Uuid::new_v4(),
+        attributes: OfferedCopyAttributes::default(),
     };
 
     let result = books_offered_repository.create(&book_offered).await;
@@ -103,11 +96,7 @@ async fn test_create_books_offered_with_invalid_user_id() {
 }
 
 #[tokio::test]
-async fn test_create_books_offered_duplicate() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-
+async fn test_create_books_offered_second_copy() {
     // Setup dos repositórios
     let book_repository = setup_book_repository().await;
     let user_repository = setup_user_repository().await;
@@ -121,23 +110,110 @@ async fn test_create_books_offered_duplicate() {
     let book = create_test_book("duplicate_test");
     let book_id = book_repository.create(&book).await.unwrap();
 
-    // Cria um book_offered
     let book_offered = CreateBookOfferedDto {
         book_id,
         user_id: user.id,
+        attributes: OfferedCopyAttributes::default(),
     };
 
-    // Primeira inserção deve ter sucesso
-    let first_result = books_offered_repository.create(&book_offered).await;
-    assert!(first_result.is_ok(), "A primeira inserção deveria ter sucesso");
+    // Inserir o mesmo livro duas vezes cria dois exemplares distintos
+    let first = books_offered_repository.create(&book_offered).await.unwrap();
+    let second = books_offered_repository.create(&book_offered).await.unwrap();
+    assert_ne!(first.id, second.id, "Cada exemplar deveria ter o seu próprio ID");
 
-    // Segunda inserção deve falhar com erro de duplicação
-    let second_result = books_offered_repository.create(&book_offered).await;
-    assert!(second_result.is_err(), "A segunda inserção deveria falhar");
-    
-    let error = second_result.unwrap_err();
-    assert!(
-        format!("{:?}", error).contains("já está na sua lista"), 
-        "Erro deveria indicar que o livro já está na lista: {:?}", error
+    let copies = books_offered_repository.find_copies(&book_id, &user.id).await.unwrap();
+    assert_eq!(copies.len(), 2);
+}
+
+#[tokio::test]
+async fn test_create_copies() {
+    // Setup dos repositórios
+    let book_repository = setup_book_repository().await;
+    let user_repository = setup_user_repository().await;
+    let books_offered_repository = setup_test_repository().await;
+
+    let user = create_test_user();
+    let user = user_repository.create(&user, "senha_hash".to_string()).await.unwrap();
+
+    let book = create_test_book("copies_test");
+    let book_id = book_repository.create(&book).await.unwrap();
+
+    let attributes = OfferedCopyAttributes {
+        condition: BookCondition::Good,
+        language: Some("pt-BR".to_string()),
+        notes: None,
+        available: true,
+    };
+    let book_offered = CreateBookOfferedDto {
+        book_id,
+        user_id: user.id,
+        attributes: attributes.clone(),
+    };
+
+    let result = books_offered_repository.create_copies(&book_offered, 3).await;
+
+    assert!(result.is_ok(), "Falha ao criar exemplares: {:?}", result.err());
+    let copies = result.unwrap();
+    assert_eq!(copies.len(), 3);
+    assert!(copies.iter().all(|copy| copy.book_id == book_id && copy.attributes == attributes));
+
+    let mut ids: Vec<Uuid> = copies.iter().map(|copy| copy.id).collect();
+    ids.sort();
+    ids.dedup();
+    assert_eq!(ids.len(), 3, "Os exemplares deveriam ter IDs distintos");
+
+    // O livro aparece uma única vez na lista de livros do usuário
+    let book_ids = books_offered_repository.find_by_user_id(&user.id).await.unwrap();
+    assert_eq!(book_ids, vec![book_id]);
+}
+
+#[tokio::test]
+async fn test_create_books_offered_when_wanted() {
+    let book_repository = setup_book_repository().await;
+    let user_repository = setup_user_repository().await;
+    let books_offered_repository = setup_test_repository().await;
+    let books_wanted_repository = PgBooksWantedRepository::new(get_test_db_pool().await);
+
+    let user = user_repository.create(&create_test_user(), "senha_hash".to_string()).await.unwrap();
+    let book_id = book_repository.create(&create_test_book("offered_wanted")).await.unwrap();
+    books_wanted_repository
+        .create(&CreateBookWantedDto { book_id, user_id: user.id, priority: 3 })
+        .await
+        .unwrap();
+
+    let dto = CreateBookOfferedDto { book_id, user_id: user.id, attributes: OfferedCopyAttributes::default() };
+    let result = books_offered_repository.create_copies(&dto, 2).await;
+
+    match result {
+        Err(AppError::Conflict(code, message)) => {
+            assert_eq!(code, ErrorCode::BookAlreadyWanted);
+            assert_eq!(message, "Este livro já está na sua lista de desejados");
+        }
+        other => panic!("Resultado inesperado: {:?}", other),
+    }
+    assert!(books_offered_repository.find_copies(&book_id, &user.id).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_concurrent_offered_and_wanted_keep_lists_exclusive() {
+    let book_repository = setup_book_repository().await;
+    let user_repository = setup_user_repository().await;
+    let books_offered_repository = setup_test_repository().await;
+    let books_wanted_repository = PgBooksWantedRepository::new(get_test_db_pool().await);
+
+    let user = user_repository.create(&create_test_user(), "senha_hash".to_string()).await.unwrap();
+    let book_id = book_repository.create(&create_test_book("offered_race")).await.unwrap();
+
+    // As duas inclusões disputam o mesmo par usuário/livro; só uma pode vencer
+    let offered_dto = CreateBookOfferedDto { book_id, user_id: user.id, attributes: OfferedCopyAttributes::default() };
+    let wanted_dto = CreateBookWantedDto { book_id, user_id: user.id, priority: 3 };
+    let (offered, wanted) = tokio::join!(
+        books_offered_repository.create(&offered_dto),
+        books_wanted_repository.create(&wanted_dto)
     );
-} 
\ No newline at end of file
+
+    assert!(offered.is_ok() != wanted.is_ok(), "Apenas uma das inclusões deve ser gravada");
+    let in_offered = !books_offered_repository.find_copies(&book_id, &user.id).await.unwrap().is_empty();
+    let in_wanted = books_wanted_repository.find(&book_id, &user.id).await.unwrap().is_some();
+    assert!(in_offered != in_wanted, "O livro não pode estar nas duas listas");
+}
@@ -0,0 +1,56 @@
+use crate::models::book::{BookCondition, CreateBookOfferedDto, CreateBookWantedDto, OfferedCopyAttributes};
+use crate::repositories::book_repository::BookRepository;
+use crate::repositories::books_offered_repository::BooksOfferedRepository;
+use crate::repositories::books_offered_repository_test::{
+    create_test_book, create_test_user, setup_book_repository, setup_test_repository, setup_user_repository,
+};
+use crate::repositories::books_wanted_repository::{BooksWantedRepository, PgBooksWantedRepository};
+use crate::repositories::test_helpers::get_test_db_pool;
+use crate::repositories::user_repository::UserRepository;
+
+#[tokio::test]
+async fn test_create_from_wanted_moves_book() {
+    let book_repository = setup_book_repository().await;
+    let user_repository = setup_user_repository().await;
+    let books_offered_repository = setup_test_repository().await;
+    let books_wanted_repository = PgBooksWantedRepository::new(get_test_db_pool().await);
+
+    let user = user_repository.create(&create_test_user(), "senha_hash".to_string()).await.unwrap();
+    let book_id = book_repository.create(&create_test_book("acquired_1")).await.unwrap();
+    books_wanted_repository
+        .create(&CreateBookWantedDto { book_id, user_id: user.id, priority: 3 })
+        .await
+        .unwrap();
+
+    let dto = CreateBookOfferedDto {
+        book_id,
+        user_id: user.id,
+        attributes: OfferedCopyAttributes { condition: BookCondition::Good, ..OfferedCopyAttributes::default() },
+    };
+
+    let copy = books_offered_repository.create_from_wanted(&dto).await.unwrap().unwrap();
+
+    assert_eq!(copy.book_id, book_id);
+    assert_eq!(copy.user_id, user.id);
+    assert_eq!(copy.attributes.condition, BookCondition::Good);
+    assert!(books_wanted_repository.find(&book_id, &user.id).await.unwrap().is_none());
+    assert_eq!(books_offered_repository.find_copies(&book_id, &user.id).await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_create_from_wanted_without_wanted_entry() {
+    let book_repository = setup_book_repository().await;
+    let user_repository = setup_user_repository().await;
+    let books_offered_repository = setup_test_repository().await;
+
+    let user = user_repository.create(&create_test_user(), "senha_hash".to_string()).await.unwrap();
+    let book_id = book_repository.create(&create_test_book("acquired_2")).await.unwrap();
+
+    let dto = CreateBookOfferedDto { book_id, user_id: user.id, attributes: OfferedCopyAttributes::default() };
+
+    let result = books_offered_repository.create_from_wanted(&dto).await.unwrap();
+
+    // Nada é criado quando o livro não estava entre os desejados
+    assert!(result.is_none());
+    assert!(books_offered_repository.find_copies(&book_id, &user.id).await.unwrap().is_empty());
+}
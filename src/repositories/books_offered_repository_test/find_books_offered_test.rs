@@ -1,19 +1,14 @@
-use crate::models::book::CreateBookOfferedDto;
+use crate::models::book::{CreateBookOfferedDto, OfferedCopyAttributes};
 use crate::repositories::book_repository::BookRepository;
 use crate::repositories::books_offered_repository::BooksOfferedRepository;
 use crate::repositories::books_offered_repository_test::{
     create_test_book, create_test_user, setup_book_repository, setup_test_repository, setup_user_repository,
 };
-use crate::repositories::test_helpers::get_test_mutex;
 use crate::repositories::user_repository::UserRepository;
 use uuid::Uuid;
 
 #[tokio::test]
 async fn test_find_books_offered() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-
     // Setup dos repositórios
     let book_repository = setup_book_repository().await;
     let user_repository = setup_user_repository().await;
@@ -31,6 +26,7 @@ async fn test_find_books_offered() {
     let book_offered = CreateBookOfferedDto {
         book_id,
         user_id: user.id,
+        attributes: OfferedCopyAttributes::default(),
     };
 
     books_offered_repository.create(&book_offered).await.unwrap();
@@ -50,10 +46,6 @@ async fn test_find_books_offered() {
 
 #[tokio::test]
 async fn test_find_books_offered_nonexistent_book() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-
     // Setup dos repositórios
     let user_repository = setup_user_repository().await;
     let books_offered_repository = setup_test_repository().await;
@@ -72,10 +64,6 @@ async fn test_find_books_offered_nonexistent_book() {
 
 #[tokio::test]
 async fn test_find_books_offered_nonexistent_user() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-
     // Setup dos repositórios
     let book_repository = setup_book_repository().await;
     let books_offered_repository = setup_test_repository().await;
@@ -94,10 +82,6 @@ async fn test_find_books_offered_nonexistent_user() {
 
 #[tokio::test]
 async fn test_find_books_offered_multiple_entries() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-
     // Setup dos repositórios
     let book_repository = setup_book_repository().await;
     let user_repository = setup_user_repository().await;
@@ -121,16 +105,19 @@ async fn test_find_books_offered_multiple_entries() {
     let book_offered1 = CreateBookOfferedDto {
         book_id: book1_id,
         user_id: user1.id,
+        attributes: OfferedCopyAttributes::default(),
     };
     
     let book_offered2 = CreateBookOfferedDto {
         book_id: book2_id,
         user_id: user1.id,
+        attributes: OfferedCopyAttributes::default(),
     };
     
     let book_offered3 = CreateBookOfferedDto {
         book_id: book1_id,
         user_id: user2.id,
+        attributes: OfferedCopyAttributes::default(),
     };
 
     // Insere as relações
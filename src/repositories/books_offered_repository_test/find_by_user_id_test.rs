@@ -1,7 +1,7 @@
 use std::sync::Arc;
 use uuid::Uuid;
 
-use crate::models::book::CreateBookOfferedDto;
+use crate::models::book::{CreateBookOfferedDto, OfferedCopyAttributes};
 use crate::repositories::book_repository::BookRepository;
 use crate::repositories::books_offered_repository::BooksOfferedRepository;
 use crate::repositories::user_repository::UserRepository;
@@ -9,13 +9,9 @@ use crate::repositories::books_offered_repository_test::{
     create_test_book, create_test_user, setup_book_repository, setup_test_repository,
     setup_user_repository,
 };
-use crate::repositories::test_helpers::get_test_mutex;
 
 #[sqlx::test]
 async fn should_find_books_offered_by_user_id() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
     // Arrange
     let user_repository = setup_user_repository().await;
     let book_repository = setup_book_repository().await;
@@ -39,12 +35,14 @@ async fn should_find_books_offered_by_user_id() {
     let book_offered1 = CreateBookOfferedDto {
         book_id: book_id1,
         user_id: user.id,
+        attributes: OfferedCopyAttributes::default(),
     };
     books_offered_repository.create(&book_offered1).await.unwrap();
 
     let book_offered2 = CreateBookOfferedDto {
         book_id: book_id2,
         user_id: user.id,
+        attributes: OfferedCopyAttributes::default(),
     };
     books_offered_repository.create(&book_offered2).await.unwrap();
 
@@ -60,10 +58,6 @@ async fn should_find_books_offered_by_user_id() {
 
 #[sqlx::test]
 async fn should_return_empty_vector_when_user_has_no_books() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-
     // Arrange
     let user_repository = setup_user_repository().await;
     let books_offered_repository = Arc::new(setup_test_repository().await);
@@ -81,10 +75,6 @@ async fn should_return_empty_vector_when_user_has_no_books() {
 
 #[sqlx::test]
 async fn should_return_only_books_from_specific_user() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-
     // Arrange
     let user_repository = setup_user_repository().await;
     let book_repository = setup_book_repository().await;
@@ -108,6 +98,7 @@ async fn should_return_only_books_from_specific_user() {
     let book_offered1 = CreateBookOfferedDto {
         book_id: book_id1,
         user_id: user1.id,
+        attributes: OfferedCopyAttributes::default(),
     };
     books_offered_repository.create(&book_offered1).await.unwrap();
 
@@ -115,6 +106,7 @@ async fn should_return_only_books_from_specific_user() {
     let book_offered2 = CreateBookOfferedDto {
         book_id: book_id2,
         user_id: user2.id,
+        attributes: OfferedCopyAttributes::default(),
     };
     books_offered_repository.create(&book_offered2).await.unwrap();
 
@@ -134,10 +126,6 @@ async fn should_return_only_books_from_specific_user() {
 
 #[sqlx::test]
 async fn should_return_empty_vector_for_non_existent_user() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-
     // Arrange
     let books_offered_repository = Arc::new(setup_test_repository().await);
     let non_existent_user_id = Uuid::new_v4();
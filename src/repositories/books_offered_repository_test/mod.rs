@@ -2,33 +2,33 @@ pub mod create_books_offered_test;
 pub mod find_books_offered_test;
 pub mod delete_books_offered_test;
 pub mod find_by_user_id_test;
+pub mod update_books_offered_test;
+pub mod batch_books_offered_test;
+pub mod create_from_wanted_test;
 
 use crate::models::book::GoogleBookDto;
 use crate::models::user::CreateUserDto;
 use crate::repositories::book_repository::{BookRepository, PgBookRepository};
 use crate::repositories::books_offered_repository::{BooksOfferedRepository, PgBooksOfferedRepository};
-use crate::repositories::test_helpers::{get_test_db_pool, clean_database};
+use crate::repositories::test_helpers::get_test_db_pool;
 use crate::repositories::user_repository::{UserRepository, PgUserRepository};
 use uuid::Uuid;
 
 // Cria uma função de setup de repositório para o PgBookRepository
 pub async fn setup_book_repository() -> impl BookRepository {
     let pool = get_test_db_pool().await;
-    clean_database(&pool).await;
     PgBookRepository::new(pool)
 }
 
 // Cria uma função de setup de repositório para o PgUserRepository
 pub async fn setup_user_repository() -> impl UserRepository {
     let pool = get_test_db_pool().await;
-    clean_database(&pool).await;
     PgUserRepository::new(pool)
 }
 
 // Função para criar o repositório de books_offered
 pub async fn setup_test_repository() -> impl BooksOfferedRepository {
     let pool = get_test_db_pool().await;
-    clean_database(&pool).await;
     PgBooksOfferedRepository::new(pool)
 }
 
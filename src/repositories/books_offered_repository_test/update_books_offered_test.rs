@@ -0,0 +1,89 @@
+use crate::models::book::{BookCondition, CreateBookOfferedDto, OfferedCopyAttributes};
+use crate::repositories::book_repository::BookRepository;
+use crate::repositories::books_offered_repository::BooksOfferedRepository;
+use crate::repositories::books_offered_repository_test::{
+    create_test_book, create_test_user, setup_book_repository, setup_test_repository, setup_user_repository,
+};
+use crate::repositories::user_repository::UserRepository;
+use uuid::Uuid;
+
+#[tokio::test]
+async fn test_update_books_offered() {
+    // Setup dos repositórios
+    let book_repository = setup_book_repository().await;
+    let user_repository = setup_user_repository().await;
+    let books_offered_repository = setup_test_repository().await;
+
+    // Cria um usuário e um livro para o teste
+    let user = create_test_user();
+    let user = user_repository.create(&user, "senha_hash".to_string()).await.unwrap();
+
+    let book = create_test_book("update_test123");
+    let book_id = book_repository.create(&book).await.unwrap();
+
+    let copy = books_offered_repository
+        .create(&CreateBookOfferedDto {
+            book_id,
+            user_id: user.id,
+            attributes: OfferedCopyAttributes::default(),
+        })
+        .await
+        .unwrap();
+
+    // Atualiza os detalhes do exemplar
+    let attributes = OfferedCopyAttributes {
+        condition: BookCondition::Fair,
+        language: Some("pt-BR".to_string()),
+        notes: Some("Capa levemente gasta".to_string()),
+        available: false,
+    };
+
+    let result = books_offered_repository.update(&copy.id, &user.id, &attributes).await;
+
+    assert!(result.is_ok(), "Falha ao atualizar book_offered: {:?}", result.err());
+    let updated = result.unwrap().expect("Book offered não encontrado");
+    assert_eq!(updated.attributes, attributes);
+
+    // A alteração deve estar persistida
+    let found = books_offered_repository.find_by_id(&copy.id).await.unwrap().unwrap();
+    assert_eq!(found.attributes, attributes);
+}
+
+#[tokio::test]
+async fn test_update_books_offered_nonexistent() {
+    let books_offered_repository = setup_test_repository().await;
+
+    let result = books_offered_repository
+        .update(&Uuid::new_v4(), &Uuid::new_v4(), &OfferedCopyAttributes::default())
+        .await;
+
+    assert!(result.is_ok());
+    assert!(result.unwrap().is_none(), "Não deveria atualizar um exemplar inexistente");
+}
+
+#[tokio::test]
+async fn test_update_books_offered_of_another_user() {
+    let book_repository = setup_book_repository().await;
+    let user_repository = setup_user_repository().await;
+    let books_offered_repository = setup_test_repository().await;
+
+    let user = user_repository.create(&create_test_user(), "senha_hash".to_string()).await.unwrap();
+    let book_id = book_repository.create(&create_test_book("update_other_user")).await.unwrap();
+
+    let copy = books_offered_repository
+        .create(&CreateBookOfferedDto {
+            book_id,
+            user_id: user.id,
+            attributes: OfferedCopyAttributes::default(),
+        })
+        .await
+        .unwrap();
+
+    // Outro usuário não pode alterar o exemplar
+    let result = books_offered_repository
+        .update(&copy.id, &Uuid::new_v4(), &OfferedCopyAttributes::default())
+        .await
+        .unwrap();
+
+    assert!(result.is_none());
+}
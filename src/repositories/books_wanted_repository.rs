@@ -2,15 +2,119 @@ use async_trait::async_trait;
 use sqlx::PgPool;
 use uuid::Uuid;
 
-use crate::error::AppError;
+use crate::error::{AppError, ErrorCode};
+use crate::i18n::messages;
 use crate::models::book::{BookWanted, CreateBookWantedDto};
+use crate::repositories::db_error::{constraint_violation, ConstraintKind};
+use crate::repositories::unit_of_work::UnitOfWork;
 
 #[async_trait]
 pub trait BooksWantedRepository: Send + Sync + 'static {
     async fn create(&self, book_wanted: &CreateBookWantedDto) -> Result<BookWanted, AppError>;
+    /// Adiciona vários livros em uma única transação, na ordem informada
+    async fn create_many(&self, books_wanted: &[CreateBookWantedDto]) -> Result<Vec<BookWanted>, AppError>;
     async fn find(&self, book_id: &Uuid, user_id: &Uuid) -> Result<Option<BookWanted>, AppError>;
+    async fn update_priority(
+        &self,
+        book_id: &Uuid,
+        user_id: &Uuid,
+        priority: i16,
+    ) -> Result<Option<BookWanted>, AppError>;
     async fn delete(&self, book_id: &Uuid, user_id: &Uuid) -> Result<bool, AppError>;
+    /// Remove vários livros da lista; retorna quantos foram removidos
+    async fn delete_many(&self, book_ids: &[Uuid], user_id: &Uuid) -> Result<u64, AppError>;
     async fn find_by_user_id(&self, user_id: &Uuid) -> Result<Vec<Uuid>, AppError>;
+    async fn find_all_by_user_id(&self, user_id: &Uuid) -> Result<Vec<BookWanted>, AppError>;
+}
+
+// Traduz as violações de unicidade e de chave estrangeira na inclusão
+fn map_create_error(e: sqlx::Error, book_wanted: &CreateBookWantedDto) -> AppError {
+    match constraint_violation(&e) {
+        Some((ConstraintKind::Unique, _)) => AppError::Conflict(
+            ErrorCode::BookAlreadyWanted,
+            messages::BOOK_ALREADY_WANTED.text(),
+        ),
+        Some((ConstraintKind::ForeignKey, Some("books_wanted_book_id_fkey"))) => AppError::ValidationError(
+            messages::BOOK_ID_NOT_FOUND.format(&[&book_wanted.book_id]),
+        ),
+        Some((ConstraintKind::ForeignKey, Some("books_wanted_user_id_fkey"))) => AppError::ValidationError(
+            messages::USER_ID_NOT_FOUND.format(&[&book_wanted.user_id]),
+        ),
+        _ => AppError::DatabaseError(e.to_string()),
+    }
+}
+
+/// Adiciona um livro à lista de desejados dentro de uma unidade de trabalho
+///
+/// O livro não pode estar na lista de possuídos do usuário; a verificação é
+/// feita sob o bloqueio do par usuário/livro, o mesmo usado na inclusão de
+/// exemplares, para que duas requisições simultâneas não coloquem o livro nas
+/// duas listas.
+pub(crate) async fn insert_wanted(
+    uow: &mut UnitOfWork,
+    book_wanted: &CreateBookWantedDto,
+) -> Result<BookWanted, AppError> {
+    uow.lock_user_book(&book_wanted.user_id, &book_wanted.book_id).await?;
+
+    let offered = sqlx::query!(
+        r#"
+        SELECT EXISTS (
+            SELECT 1 FROM books_offered WHERE book_id = $1 AND user_id = $2
+        ) as "offered!"
+        "#,
+        book_wanted.book_id,
+        book_wanted.user_id
+    )
+    .fetch_one(uow.conn())
+    .await
+    .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    if offered.offered {
+        return Err(AppError::Conflict(
+            ErrorCode::BookAlreadyOffered,
+            messages::BOOK_ALREADY_OFFERED.text(),
+        ));
+    }
+
+    let r = sqlx::query!(
+        r#"
+        INSERT INTO books_wanted (book_id, user_id, priority)
+        VALUES ($1, $2, $3)
+        RETURNING book_id, user_id, priority
+        "#,
+        book_wanted.book_id,
+        book_wanted.user_id,
+        book_wanted.priority
+    )
+    .fetch_one(uow.conn())
+    .await
+    .map_err(|e| map_create_error(e, book_wanted))?;
+
+    Ok(BookWanted {
+        book_id: r.book_id,
+        user_id: r.user_id,
+        priority: r.priority,
+    })
+}
+
+/// Retira um livro da lista de desejados dentro de uma unidade de trabalho;
+/// retorna false se o livro não estava na lista
+pub(crate) async fn remove_wanted(uow: &mut UnitOfWork, book_id: &Uuid, user_id: &Uuid) -> Result<bool, AppError> {
+    uow.lock_user_book(user_id, book_id).await?;
+
+    let result = sqlx::query!(
+        r#"
+        DELETE FROM books_wanted
+        WHERE book_id = $1 AND user_id = $2
+        "#,
+        book_id,
+        user_id
+    )
+    .execute(uow.conn())
+    .await
+    .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    Ok(result.rows_affected() > 0)
 }
 
 pub struct PgBooksWantedRepository {
@@ -25,56 +129,68 @@ impl PgBooksWantedRepository {
 
 #[async_trait]
 impl BooksWantedRepository for PgBooksWantedRepository {
+    #[tracing::instrument(name = "books_wanted_repository.create", level = "debug", skip_all)]
     async fn create(&self, book_wanted: &CreateBookWantedDto) -> Result<BookWanted, AppError> {
-        // Inserir na tabela books_wanted
+        let mut uow = UnitOfWork::begin(&self.pool).await?;
+        let created = insert_wanted(&mut uow, book_wanted).await?;
+        uow.commit().await?;
+
+        Ok(created)
+    }
+
+    #[tracing::instrument(name = "books_wanted_repository.create_many", level = "debug", skip_all)]
+    async fn create_many(&self, books_wanted: &[CreateBookWantedDto]) -> Result<Vec<BookWanted>, AppError> {
+        let mut uow = UnitOfWork::begin(&self.pool).await?;
+
+        let mut created = Vec::with_capacity(books_wanted.len());
+        for book_wanted in books_wanted {
+            created.push(insert_wanted(&mut uow, book_wanted).await?);
+        }
+
+        uow.commit().await?;
+
+        Ok(created)
+    }
+
+    #[tracing::instrument(name = "books_wanted_repository.find", level = "debug", skip_all)]
+    async fn find(&self, book_id: &Uuid, user_id: &Uuid) -> Result<Option<BookWanted>, AppError> {
         let result = sqlx::query!(
             r#"
-            INSERT INTO books_wanted (book_id, user_id)
-            VALUES ($1, $2)
-            RETURNING book_id, user_id
+            SELECT book_id, user_id, priority
+            FROM books_wanted
+            WHERE book_id = $1 AND user_id = $2
             "#,
-            book_wanted.book_id,
-            book_wanted.user_id
+            book_id,
+            user_id
         )
-        .fetch_one(&self.pool)
+        .fetch_optional(&self.pool)
         .await
-        .map_err(|e| {
-            if e.to_string().contains("duplicate key") {
-                AppError::ValidationError("Este livro já está na sua lista de desejados".to_string())
-            } else if e.to_string().contains("foreign key constraint") {
-                if e.to_string().contains("books_wanted_book_id_fkey") {
-                    AppError::ValidationError(format!(
-                        "Livro com ID {} não encontrado",
-                        book_wanted.book_id
-                    ))
-                } else if e.to_string().contains("books_wanted_user_id_fkey") {
-                    AppError::ValidationError(format!(
-                        "Usuário com ID {} não encontrado",
-                        book_wanted.user_id
-                    ))
-                } else {
-                    AppError::DatabaseError(e.to_string())
-                }
-            } else {
-                AppError::DatabaseError(e.to_string())
-            }
-        })?;
-
-        Ok(BookWanted {
-            book_id: result.book_id,
-            user_id: result.user_id,
-        })
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(result.map(|r| BookWanted {
+            book_id: r.book_id,
+            user_id: r.user_id,
+            priority: r.priority,
+        }))
     }
 
-    async fn find(&self, book_id: &Uuid, user_id: &Uuid) -> Result<Option<BookWanted>, AppError> {
+    #[tracing::instrument(name = "books_wanted_repository.update_priority", level = "debug", skip_all)]
+    async fn update_priority(
+        &self,
+        book_id: &Uuid,
+        user_id: &Uuid,
+        priority: i16,
+    ) -> Result<Option<BookWanted>, AppError> {
         let result = sqlx::query!(
             r#"
-            SELECT book_id, user_id
-            FROM books_wanted
+            UPDATE books_wanted
+            SET priority = $3, updated_at = CURRENT_TIMESTAMP
             WHERE book_id = $1 AND user_id = $2
+            RETURNING book_id, user_id, priority
             "#,
             book_id,
-            user_id
+            user_id,
+            priority
         )
         .fetch_optional(&self.pool)
         .await
@@ -83,9 +199,11 @@ impl BooksWantedRepository for PgBooksWantedRepository {
         Ok(result.map(|r| BookWanted {
             book_id: r.book_id,
             user_id: r.user_id,
+            priority: r.priority,
         }))
     }
 
+    #[tracing::instrument(name = "books_wanted_repository.delete", level = "debug", skip_all)]
     async fn delete(&self, book_id: &Uuid, user_id: &Uuid) -> Result<bool, AppError> {
         let result = sqlx::query!(
             r#"
@@ -103,6 +221,24 @@ impl BooksWantedRepository for PgBooksWantedRepository {
         Ok(result.rows_affected() > 0)
     }
 
+    #[tracing::instrument(name = "books_wanted_repository.delete_many", level = "debug", skip_all)]
+    async fn delete_many(&self, book_ids: &[Uuid], user_id: &Uuid) -> Result<u64, AppError> {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM books_wanted
+            WHERE book_id = ANY($1) AND user_id = $2
+            "#,
+            book_ids,
+            user_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(result.rows_affected())
+    }
+
+    #[tracing::instrument(name = "books_wanted_repository.find_by_user_id", level = "debug", skip_all)]
     async fn find_by_user_id(&self, user_id: &Uuid) -> Result<Vec<Uuid>, AppError> {
         let result = sqlx::query!(
             r#"
@@ -118,4 +254,29 @@ impl BooksWantedRepository for PgBooksWantedRepository {
 
         Ok(result.into_iter().map(|r| r.book_id).collect())
     }
-} 
\ No newline at end of file
+
+    #[tracing::instrument(name = "books_wanted_repository.find_all_by_user_id", level = "debug", skip_all)]
+    async fn find_all_by_user_id(&self, user_id: &Uuid) -> Result<Vec<BookWanted>, AppError> {
+        let result = sqlx::query!(
+            r#"
+            SELECT book_id, user_id, priority
+            FROM books_wanted
+            WHERE user_id = $1
+            ORDER BY priority DESC, created_at
+            "#,
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(result
+            .into_iter()
+            .map(|r| BookWanted {
+                book_id: r.book_id,
+                user_id: r.user_id,
+                priority: r.priority,
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,90 @@
+use uuid::Uuid;
+
+use crate::models::book::CreateBookWantedDto;
+use crate::repositories::book_repository::BookRepository;
+use crate::repositories::books_wanted_repository::BooksWantedRepository;
+use crate::repositories::books_wanted_repository_test::{
+    create_test_book, create_test_user, setup_book_repository, setup_test_repository, setup_user_repository,
+};
+use crate::repositories::user_repository::UserRepository;
+
+#[sqlx::test]
+async fn should_create_many_books_wanted() {
+    // Arrange
+    let user_repository = setup_user_repository().await;
+    let book_repository = setup_book_repository().await;
+    let books_wanted_repository = setup_test_repository().await;
+
+    let user = user_repository.create(&create_test_user(), "hashed_password".to_string()).await.unwrap();
+    let book_id1 = book_repository.create(&create_test_book("batch_id_1")).await.unwrap();
+    let book_id2 = book_repository.create(&create_test_book("batch_id_2")).await.unwrap();
+
+    // Act
+    let created = books_wanted_repository
+        .create_many(&[
+            CreateBookWantedDto { book_id: book_id1, user_id: user.id, priority: 1 },
+            CreateBookWantedDto { book_id: book_id2, user_id: user.id, priority: 4 },
+        ])
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(created.len(), 2);
+    assert_eq!(created[0].book_id, book_id1);
+    assert_eq!(created[1].priority, 4);
+    assert_eq!(books_wanted_repository.find_by_user_id(&user.id).await.unwrap().len(), 2);
+}
+
+#[sqlx::test]
+async fn should_rollback_create_many_on_duplicate() {
+    // Arrange
+    let user_repository = setup_user_repository().await;
+    let book_repository = setup_book_repository().await;
+    let books_wanted_repository = setup_test_repository().await;
+
+    let user = user_repository.create(&create_test_user(), "hashed_password".to_string()).await.unwrap();
+    let book_id1 = book_repository.create(&create_test_book("batch_id_1")).await.unwrap();
+    let book_id2 = book_repository.create(&create_test_book("batch_id_2")).await.unwrap();
+
+    // Act
+    let result = books_wanted_repository
+        .create_many(&[
+            CreateBookWantedDto { book_id: book_id1, user_id: user.id, priority: 3 },
+            CreateBookWantedDto { book_id: book_id2, user_id: user.id, priority: 3 },
+            CreateBookWantedDto { book_id: book_id1, user_id: user.id, priority: 3 },
+        ])
+        .await;
+
+    // Assert
+    assert!(result.is_err());
+    assert!(books_wanted_repository.find_by_user_id(&user.id).await.unwrap().is_empty());
+}
+
+#[sqlx::test]
+async fn should_delete_many_books_wanted() {
+    // Arrange
+    let user_repository = setup_user_repository().await;
+    let book_repository = setup_book_repository().await;
+    let books_wanted_repository = setup_test_repository().await;
+
+    let user = user_repository.create(&create_test_user(), "hashed_password".to_string()).await.unwrap();
+    let book_id1 = book_repository.create(&create_test_book("batch_id_1")).await.unwrap();
+    let book_id2 = book_repository.create(&create_test_book("batch_id_2")).await.unwrap();
+    books_wanted_repository
+        .create_many(&[
+            CreateBookWantedDto { book_id: book_id1, user_id: user.id, priority: 3 },
+            CreateBookWantedDto { book_id: book_id2, user_id: user.id, priority: 3 },
+        ])
+        .await
+        .unwrap();
+
+    // Act
+    let removed = books_wanted_repository
+        .delete_many(&[book_id1, Uuid::new_v4()], &user.id)
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(removed, 1);
+    assert_eq!(books_wanted_repository.find_by_user_id(&user.id).await.unwrap(), vec![book_id2]);
+}
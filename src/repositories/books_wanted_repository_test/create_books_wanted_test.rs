@@ -1,19 +1,17 @@
-use crate::models::book::CreateBookWantedDto;
+use crate::error::{AppError, ErrorCode};
+use crate::models::book::{CreateBookOfferedDto, CreateBookWantedDto, OfferedCopyAttributes, DEFAULT_WANTED_PRIORITY};
 use crate::repositories::book_repository::BookRepository;
 use crate::repositories::books_wanted_repository::BooksWantedRepository;
 use crate::repositories::books_wanted_repository_test::{
     create_test_book, create_test_user, setup_book_repository, setup_test_repository, setup_user_repository,
 };
-use crate::repositories::test_helpers::get_test_mutex;
+use crate::repositories::books_offered_repository::{BooksOfferedRepository, PgBooksOfferedRepository};
+use crate::repositories::test_helpers::get_test_db_pool;
 use crate::repositories::user_repository::UserRepository;
 use uuid::Uuid;
 
 #[tokio::test]
 async fn test_create_books_wanted() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-
     // Setup dos repositórios
     let book_repository = setup_book_repository().await;
     let user_repository = setup_user_repository().await;
@@ -31,6 +29,7 @@ async fn test_create_books_wanted() {
     let book_wanted = CreateBookWantedDto {
         book_id,
         user_id: user.id,
+        priority: DEFAULT_WANTED_PRIORITY,
     };
 
     let result = books_wanted_repository.create(&book_wanted).await;
@@ -44,10 +43,6 @@ async fn test_create_books_wanted() {
 
 #[tokio::test]
 async fn test_create_books_wanted_with_invalid_book_id() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-
     // Setup dos repositórios
     let user_repository = setup_user_repository().await;
     let books_wanted_repository = setup_test_repository().await;
@@ -60,6 +55,7 @@ async fn test_create_books_wanted_with_invalid_book_id() {
     let book_wanted = CreateBookWantedDto {
         book_id: Uuid::new_v4(),
         user_id: user.id,
+        priority: DEFAULT_WANTED_PRIORITY,
     };
 
     let result = books_wanted_repository.create(&book_wanted).await;
@@ -74,10 +70,6 @@ async fn test_create_books_wanted_with_invalid_book_id() {
 
 #[tokio::test]
 async fn test_create_books_wanted_with_invalid_user_id() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-
     // Setup dos repositórios
     let book_repository = setup_book_repository().await;
     let books_wanted_repository = setup_test_repository().await;
@@ -90,6 +82,7 @@ async fn test_create_books_wanted_with_invalid_user_id() {
     let book_wanted = CreateBookWantedDto {
         book_id,
         user_id: Uuid::new_v4(),
+        priority: DEFAULT_WANTED_PRIORITY,
     };
 
     let result = books_wanted_repository.create(&book_wanted).await;
@@ -104,10 +97,6 @@ async fn test_create_books_wanted_with_invalid_user_id() {
 
 #[tokio::test]
 async fn test_create_books_wanted_duplicate() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-
     // Setup dos repositórios
     let book_repository = setup_book_repository().await;
     let user_repository = setup_user_repository().await;
@@ -125,6 +114,7 @@ async fn test_create_books_wanted_duplicate() {
     let book_wanted = CreateBookWantedDto {
         book_id,
         user_id: user.id,
+        priority: DEFAULT_WANTED_PRIORITY,
     };
 
     // Primeira inserção deve ter sucesso
@@ -140,4 +130,30 @@ async fn test_create_books_wanted_duplicate() {
         format!("{:?}", error).contains("já está na sua lista"), 
         "Erro deveria indicar que o livro já está na lista: {:?}", error
     );
-} 
\ No newline at end of file
+} 
+#[tokio::test]
+async fn test_create_books_wanted_when_offered() {
+    let book_repository = setup_book_repository().await;
+    let user_repository = setup_user_repository().await;
+    let books_wanted_repository = setup_test_repository().await;
+    let books_offered_repository = PgBooksOfferedRepository::new(get_test_db_pool().await);
+
+    let user = user_repository.create(&create_test_user(), "senha_hash".to_string()).await.unwrap();
+    let book_id = book_repository.create(&create_test_book("wanted_offered")).await.unwrap();
+    books_offered_repository
+        .create(&CreateBookOfferedDto { book_id, user_id: user.id, attributes: OfferedCopyAttributes::default() })
+        .await
+        .unwrap();
+
+    let result = books_wanted_repository
+        .create(&CreateBookWantedDto { book_id, user_id: user.id, priority: DEFAULT_WANTED_PRIORITY })
+        .await;
+
+    match result {
+        Err(AppError::Conflict(code, message)) => {
+            assert_eq!(code, ErrorCode::BookAlreadyOffered);
+            assert_eq!(message, "Este livro já está na sua lista de possuídos");
+        }
+        other => panic!("Resultado inesperado: {:?}", other),
+    }
+}
@@ -1,7 +1,7 @@
 use std::sync::Arc;
 use uuid::Uuid;
 
-use crate::models::book::CreateBookWantedDto;
+use crate::models::book::{CreateBookWantedDto, DEFAULT_WANTED_PRIORITY};
 use crate::repositories::book_repository::BookRepository;
 use crate::repositories::books_wanted_repository::BooksWantedRepository;
 use crate::repositories::user_repository::UserRepository;
@@ -9,13 +9,9 @@ use crate::repositories::books_wanted_repository_test::{
     create_test_book, create_test_user, setup_book_repository, setup_test_repository,
     setup_user_repository,
 };
-use crate::repositories::test_helpers::get_test_mutex;
 
 #[sqlx::test]
 async fn should_find_books_wanted_by_user_id() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
     // Arrange
     let user_repository = setup_user_repository().await;
     let book_repository = setup_book_repository().await;
@@ -39,12 +35,14 @@ async fn should_find_books_wanted_by_user_id() {
     let book_wanted1 = CreateBookWantedDto {
         book_id: book_id1,
         user_id: user.id,
+        priority: DEFAULT_WANTED_PRIORITY,
     };
     books_wanted_repository.create(&book_wanted1).await.unwrap();
 
     let book_wanted2 = CreateBookWantedDto {
         book_id: book_id2,
         user_id: user.id,
+        priority: DEFAULT_WANTED_PRIORITY,
     };
     books_wanted_repository.create(&book_wanted2).await.unwrap();
 
@@ -60,10 +58,6 @@ async fn should_find_books_wanted_by_user_id() {
 
 #[sqlx::test]
 async fn should_return_empty_vector_when_user_has_no_books() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-
     // Arrange
     let user_repository = setup_user_repository().await;
     let books_wanted_repository = Arc::new(setup_test_repository().await);
@@ -81,10 +75,6 @@ async fn should_return_empty_vector_when_user_has_no_books() {
 
 #[sqlx::test]
 async fn should_return_only_books_from_specific_user() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-
     // Arrange
     let user_repository = setup_user_repository().await;
     let book_repository = setup_book_repository().await;
@@ -108,6 +98,7 @@ async fn should_return_only_books_from_specific_user() {
     let book_wanted1 = CreateBookWantedDto {
         book_id: book_id1,
         user_id: user1.id,
+        priority: DEFAULT_WANTED_PRIORITY,
     };
     books_wanted_repository.create(&book_wanted1).await.unwrap();
 
@@ -115,6 +106,7 @@ async fn should_return_only_books_from_specific_user() {
     let book_wanted2 = CreateBookWantedDto {
         book_id: book_id2,
         user_id: user2.id,
+        priority: DEFAULT_WANTED_PRIORITY,
     };
     books_wanted_repository.create(&book_wanted2).await.unwrap();
 
@@ -134,10 +126,6 @@ async fn should_return_only_books_from_specific_user() {
 
 #[sqlx::test]
 async fn should_return_empty_vector_for_non_existent_user() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-
     // Arrange
     let books_wanted_repository = Arc::new(setup_test_repository().await);
     let non_existent_user_id = Uuid::new_v4();
@@ -147,4 +135,36 @@ async fn should_return_empty_vector_for_non_existent_user() {
 
     // Assert
     assert_eq!(result.len(), 0);
-}
\ No newline at end of file
+}
+#[sqlx::test]
+async fn should_find_all_books_wanted_with_priority_ordered() {
+    // Arrange
+    let user_repository = setup_user_repository().await;
+    let book_repository = setup_book_repository().await;
+    let books_wanted_repository = Arc::new(setup_test_repository().await);
+
+    let test_user = create_test_user();
+    let user = user_repository.create(&test_user, "hashed_password".to_string()).await.unwrap();
+
+    let book_id1 = book_repository.create(&create_test_book("test_id_1")).await.unwrap();
+    let book_id2 = book_repository.create(&create_test_book("test_id_2")).await.unwrap();
+
+    books_wanted_repository
+        .create(&CreateBookWantedDto { book_id: book_id1, user_id: user.id, priority: 2 })
+        .await
+        .unwrap();
+    books_wanted_repository
+        .create(&CreateBookWantedDto { book_id: book_id2, user_id: user.id, priority: 5 })
+        .await
+        .unwrap();
+
+    // Act
+    let result = books_wanted_repository.find_all_by_user_id(&user.id).await.unwrap();
+
+    // Assert
+    assert_eq!(result.len(), 2);
+    assert_eq!(result[0].book_id, book_id2);
+    assert_eq!(result[0].priority, 5);
+    assert_eq!(result[1].book_id, book_id1);
+    assert_eq!(result[1].priority, 2);
+}
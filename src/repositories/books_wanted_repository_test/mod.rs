@@ -2,33 +2,32 @@ pub mod create_books_wanted_test;
 pub mod find_books_wanted_test;
 pub mod delete_books_wanted_test;
 pub mod find_by_user_id_test;
+pub mod update_priority_test;
+pub mod batch_books_wanted_test;
 
 use crate::models::book::GoogleBookDto;
 use crate::models::user::CreateUserDto;
 use crate::repositories::book_repository::{BookRepository, PgBookRepository};
 use crate::repositories::books_wanted_repository::{BooksWantedRepository, PgBooksWantedRepository};
-use crate::repositories::test_helpers::{get_test_db_pool, clean_database};
+use crate::repositories::test_helpers::get_test_db_pool;
 use crate::repositories::user_repository::{UserRepository, PgUserRepository};
 use uuid::Uuid;
 
 // Cria uma função de setup de repositório para o PgBookRepository
 pub async fn setup_book_repository() -> impl BookRepository {
     let pool = get_test_db_pool().await;
-    clean_database(&pool).await;
     PgBookRepository::new(pool)
 }
 
 // Cria uma função de setup de repositório para o PgUserRepository
 pub async fn setup_user_repository() -> impl UserRepository {
     let pool = get_test_db_pool().await;
-    clean_database(&pool).await;
     PgUserRepository::new(pool)
 }
 
 // Função para criar o repositório de books_wanted
 pub async fn setup_test_repository() -> impl BooksWantedRepository {
     let pool = get_test_db_pool().await;
-    clean_database(&pool).await;
     PgBooksWantedRepository::new(pool)
 }
 
@@ -0,0 +1,50 @@
+use crate::models::book::{CreateBookWantedDto, DEFAULT_WANTED_PRIORITY};
+use crate::repositories::book_repository::BookRepository;
+use crate::repositories::books_wanted_repository::BooksWantedRepository;
+use crate::repositories::books_wanted_repository_test::{
+    create_test_book, create_test_user, setup_book_repository, setup_test_repository, setup_user_repository,
+};
+use crate::repositories::user_repository::UserRepository;
+use uuid::Uuid;
+
+#[tokio::test]
+async fn test_update_priority() {
+    // Setup dos repositórios
+    let book_repository = setup_book_repository().await;
+    let user_repository = setup_user_repository().await;
+    let books_wanted_repository = setup_test_repository().await;
+
+    let user = user_repository.create(&create_test_user(), "senha_hash".to_string()).await.unwrap();
+    let book_id = book_repository.create(&create_test_book("priority_test")).await.unwrap();
+
+    let created = books_wanted_repository
+        .create(&CreateBookWantedDto {
+            book_id,
+            user_id: user.id,
+            priority: DEFAULT_WANTED_PRIORITY,
+        })
+        .await
+        .unwrap();
+    assert_eq!(created.priority, DEFAULT_WANTED_PRIORITY);
+
+    let result = books_wanted_repository.update_priority(&book_id, &user.id, 5).await;
+
+    assert!(result.is_ok(), "Falha ao atualizar a prioridade: {:?}", result.err());
+    assert_eq!(result.unwrap().expect("Livro desejado não encontrado").priority, 5);
+
+    // A alteração deve estar persistida
+    let found = books_wanted_repository.find(&book_id, &user.id).await.unwrap().unwrap();
+    assert_eq!(found.priority, 5);
+}
+
+#[tokio::test]
+async fn test_update_priority_nonexistent() {
+    let books_wanted_repository = setup_test_repository().await;
+
+    let result = books_wanted_repository
+        .update_priority(&Uuid::new_v4(), &Uuid::new_v4(), 4)
+        .await;
+
+    assert!(result.is_ok());
+    assert!(result.unwrap().is_none(), "Não deveria atualizar um livro que não está na lista");
+}
@@ -0,0 +1,396 @@
+use crate::error::{AppError, ErrorCode};
+use crate::models::book::{
+    BookCondition, CreateBookOfferedDto, CreateBookWantedDto, CreateBookPhotoDto, GoogleBookDto,
+    OfferedCopyAttributes,
+};
+use crate::models::shelf::ShelfList;
+use crate::models::trade::CreateTradeDto;
+use crate::repositories::contract_test::Backend;
+use uuid::Uuid;
+
+contract_tests!(
+    finds_books_by_google_id_and_id,
+    counts_offers_and_wants_in_book_details,
+    updates_book_metadata,
+    keeps_metadata_missing_from_refresh,
+    marks_metadata_checked,
+    rejects_wanting_an_offered_book,
+    rejects_offering_a_wanted_book,
+    rejects_references_to_missing_rows,
+    moves_wanted_book_to_offered,
+    creates_copies_atomically,
+    lists_owners_with_available_copies_first,
+    orders_wanted_books_by_priority,
+    keeps_reserved_copies,
+    deletes_photos_with_the_copy,
+);
+
+async fn finds_books_by_google_id_and_id(backend: Backend) {
+    // Arrange
+    let book_id = backend.create_book("Dom Casmurro").await;
+
+    // Act
+    let by_id = backend.book.find_by_id(&book_id.to_string()).await.unwrap().unwrap();
+    let by_google_id = backend.book.find_by_google_id(&by_id.google_id).await.unwrap().unwrap();
+    let recreated = backend.book.create(&by_id).await.unwrap();
+
+    // Assert - O cadastro repetido devolve o mesmo livro
+    assert_eq!(by_google_id.id, book_id);
+    assert_eq!(by_id.title, "Dom Casmurro");
+    assert_eq!(by_id.published_date.as_deref(), Some("2020-01-01"));
+    assert_eq!(recreated, book_id);
+    assert!(backend.book.find_by_id("nao-e-um-uuid").await.unwrap().is_none());
+}
+
+async fn counts_offers_and_wants_in_book_details(backend: Backend) {
+    // Arrange - Dois exemplares oferecidos e um interessado
+    let ana = backend.create_user("Ana").await;
+    let bruno = backend.create_user("Bruno").await;
+    let carla = backend.create_user("Carla").await;
+    let book_id = backend.create_book("Dom Casmurro").await;
+    backend.offer(book_id, ana.id).await;
+    backend.offer(book_id, bruno.id).await;
+    backend.want(book_id, carla.id, 3).await;
+
+    // Act
+    let for_ana = backend.book.find_details(&book_id, &ana.id).await.unwrap().unwrap();
+    let for_carla = backend.book.find_details(&book_id, &carla.id).await.unwrap().unwrap();
+    let missing = backend.book.find_details(&Uuid::new_v4(), &ana.id).await.unwrap();
+
+    // Assert
+    assert_eq!(for_ana.id, book_id);
+    assert_eq!(for_ana.book.title, "Dom Casmurro");
+    assert_eq!(for_ana.offered_count, 2);
+    assert_eq!(for_ana.wanted_count, 1);
+    assert_eq!(for_ana.user_list, Some(ShelfList::Offered));
+    assert_eq!(for_carla.user_list, Some(ShelfList::Wanted));
+    assert!(for_ana.metadata_fetched_at.is_some());
+    assert!(missing.is_none());
+}
+
+async fn updates_book_metadata(backend: Backend) {
+    // Arrange
+    let user = backend.create_user("Ana").await;
+    let book_id = backend.create_book("Dom Casmurro").await;
+    let before = backend.book.find_details(&book_id, &user.id).await.unwrap().unwrap();
+    let refreshed = GoogleBookDto {
+        title: "Dom Casmurro (Edição Revista)".to_string(),
+        // Data só com o ano, como o Google Books às vezes devolve
+        published_date: Some("1899".to_string()),
+        page_count: Some(256),
+        ..before.book.clone()
+    };
+
+    // Act
+    backend.book.update_metadata(&book_id, &refreshed).await.unwrap();
+    let after = backend.book.find_details(&book_id, &user.id).await.unwrap().unwrap();
+
+    // Assert - A data que não pôde ser lida mantém a anterior
+    assert_eq!(after.book.title, "Dom Casmurro (Edição Revista)");
+    assert_eq!(after.book.page_count, Some(256));
+    assert_eq!(after.book.published_date.as_deref(), Some("2020-01-01"));
+    assert!(after.metadata_fetched_at >= before.metadata_fetched_at);
+    assert_eq!(after.user_list, None);
+}
+
+async fn keeps_metadata_missing_from_refresh(backend: Backend) {
+    // Arrange
+    let user = backend.create_user("Ana").await;
+    let book_id = backend.create_book("Dom Casmurro").await;
+    let before = backend.book.find_details(&book_id, &user.id).await.unwrap().unwrap();
+    let refreshed = GoogleBookDto {
+        google_id: before.book.google_id.clone(),
+        title: "Dom Casmurro".to_string(),
+        authors: None,
+        publisher: None,
+        published_date: None,
+        description: Some(String::new()),
+        image_url: None,
+        page_count: None,
+    };
+
+    // Act
+    backend.book.update_metadata(&book_id, &refreshed).await.unwrap();
+    let after = backend.book.find_details(&book_id, &user.id).await.unwrap().unwrap();
+
+    // Assert - Os campos que o Google Books deixou de enviar continuam os mesmos
+    assert_eq!(after.book.authors, before.book.authors);
+    assert_eq!(after.book.publisher, before.book.publisher);
+    assert_eq!(after.book.published_date, before.book.published_date);
+    assert_eq!(after.book.description, before.book.description);
+    assert_eq!(after.book.image_url, before.book.image_url);
+    assert_eq!(after.book.page_count, before.book.page_count);
+}
+
+async fn marks_metadata_checked(backend: Backend) {
+    // Arrange
+    let user = backend.create_user("Ana").await;
+    let book_id = backend.create_book("Dom Casmurro").await;
+    let before = backend.book.find_details(&book_id, &user.id).await.unwrap().unwrap();
+
+    // Act
+    backend.book.mark_metadata_checked(&book_id).await.unwrap();
+    let after = backend.book.find_details(&book_id, &user.id).await.unwrap().unwrap();
+
+    // Assert - Só a tentativa é registrada; os dados continuam os mesmos
+    assert!(before.metadata_checked_at.is_none());
+    assert!(after.metadata_checked_at.is_some());
+    assert_eq!(after.metadata_fetched_at, before.metadata_fetched_at);
+    assert_eq!(after.book.title, before.book.title);
+}
+
+async fn rejects_wanting_an_offered_book(backend: Backend) {
+    // Arrange
+    let user = backend.create_user("Ana").await;
+    let book_id = backend.create_book("Dom Casmurro").await;
+    backend.offer(book_id, user.id).await;
+
+    // Act
+    let result = backend
+        .books_wanted
+        .create(&CreateBookWantedDto {
+            book_id,
+            user_id: user.id,
+            priority: 3,
+        })
+        .await;
+
+    // Assert
+    assert!(matches!(result, Err(AppError::Conflict(ErrorCode::BookAlreadyOffered, _))));
+}
+
+async fn rejects_offering_a_wanted_book(backend: Backend) {
+    // Arrange
+    let user = backend.create_user("Ana").await;
+    let book_id = backend.create_book("Dom Casmurro").await;
+    backend.want(book_id, user.id, 3).await;
+
+    // Act
+    let result = backend
+        .books_offered
+        .create(&CreateBookOfferedDto {
+            book_id,
+            user_id: user.id,
+            attributes: OfferedCopyAttributes::default(),
+        })
+        .await;
+
+    // Assert
+    assert!(matches!(result, Err(AppError::Conflict(ErrorCode::BookAlreadyWanted, _))));
+}
+
+async fn rejects_references_to_missing_rows(backend: Backend) {
+    // Arrange
+    let user = backend.create_user("Ana").await;
+    let book_id = backend.create_book("Dom Casmurro").await;
+
+    // Act
+    let missing_book = backend
+        .books_wanted
+        .create(&CreateBookWantedDto {
+            book_id: Uuid::new_v4(),
+            user_id: user.id,
+            priority: 3,
+        })
+        .await;
+    let missing_user = backend
+        .books_offered
+        .create(&CreateBookOfferedDto {
+            book_id,
+            user_id: Uuid::new_v4(),
+            attributes: OfferedCopyAttributes::default(),
+        })
+        .await;
+
+    // Assert
+    assert!(matches!(missing_book, Err(AppError::ValidationError(_))));
+    assert!(matches!(missing_user, Err(AppError::ValidationError(_))));
+}
+
+async fn moves_wanted_book_to_offered(backend: Backend) {
+    // Arrange
+    let user = backend.create_user("Ana").await;
+    let wanted_id = backend.create_book("Dom Casmurro").await;
+    let other_id = backend.create_book("Iracema").await;
+    backend.want(wanted_id, user.id, 3).await;
+    let dto = |book_id| CreateBookOfferedDto {
+        book_id,
+        user_id: user.id,
+        attributes: OfferedCopyAttributes::default(),
+    };
+
+    // Act
+    let moved = backend.books_offered.create_from_wanted(&dto(wanted_id)).await.unwrap();
+    let not_wanted = backend.books_offered.create_from_wanted(&dto(other_id)).await.unwrap();
+
+    // Assert
+    assert_eq!(moved.map(|copy| copy.book_id), Some(wanted_id));
+    assert!(not_wanted.is_none());
+    assert!(backend.books_wanted.find(&wanted_id, &user.id).await.unwrap().is_none());
+    assert!(backend.books_offered.find(&other_id, &user.id).await.unwrap().is_none());
+}
+
+async fn creates_copies_atomically(backend: Backend) {
+    // Arrange - O segundo item conflita com um livro desejado
+    let user = backend.create_user("Ana").await;
+    let first_id = backend.create_book("Dom Casmurro").await;
+    let wanted_id = backend.create_book("Iracema").await;
+    backend.want(wanted_id, user.id, 3).await;
+    let items: Vec<CreateBookOfferedDto> = [first_id, wanted_id]
+        .into_iter()
+        .map(|book_id| CreateBookOfferedDto {
+            book_id,
+            user_id: user.id,
+            attributes: OfferedCopyAttributes::default(),
+        })
+        .collect();
+
+    // Act
+    let result = backend.books_offered.create_many(&items).await;
+    let copies = backend
+        .books_offered
+        .create_copies(&items[0], 3)
+        .await
+        .unwrap();
+
+    // Assert - Nada do lote falho foi gravado; as cópias seguem a ordem de inclusão
+    assert!(result.is_err());
+    assert_eq!(copies.len(), 3);
+    let stored = backend.books_offered.find_copies(&first_id, &user.id).await.unwrap();
+    assert_eq!(
+        stored.iter().map(|copy| copy.id).collect::<Vec<_>>(),
+        copies.iter().map(|copy| copy.id).collect::<Vec<_>>()
+    );
+    assert_eq!(backend.books_offered.find_by_user_id(&user.id).await.unwrap(), vec![first_id]);
+}
+
+async fn lists_owners_with_available_copies_first(backend: Backend) {
+    // Arrange
+    let lending = backend.create_user("Ana").await;
+    let available = backend.create_user("Bruno").await;
+    let book_id = backend.create_book("Dom Casmurro").await;
+    let unavailable_copy = backend.offer(book_id, lending.id).await;
+    backend.offer(book_id, available.id).await;
+    let attributes = OfferedCopyAttributes {
+        condition: BookCondition::Fair,
+        available: false,
+        ..OfferedCopyAttributes::default()
+    };
+    let updated = backend
+        .books_offered
+        .update(&unavailable_copy.id, &lending.id, &attributes)
+        .await
+        .unwrap();
+    let not_owner = backend
+        .books_offered
+        .update(&unavailable_copy.id, &available.id, &attributes)
+        .await
+        .unwrap();
+
+    // Act
+    let owners = backend.books_offered.find_owners_by_book_id(&book_id).await.unwrap();
+
+    // Assert
+    assert_eq!(updated.map(|copy| copy.attributes), Some(attributes));
+    assert!(not_owner.is_none());
+    let owner_ids: Vec<_> = owners.iter().map(|(owner, _)| owner.id).collect();
+    assert_eq!(owner_ids, vec![available.id, lending.id]);
+}
+
+async fn orders_wanted_books_by_priority(backend: Backend) {
+    // Arrange
+    let user = backend.create_user("Ana").await;
+    let low_id = backend.create_book("Dom Casmurro").await;
+    let high_id = backend.create_book("Iracema").await;
+    backend.want(low_id, user.id, 2).await;
+    backend.want(high_id, user.id, 4).await;
+
+    // Act
+    let duplicated = backend
+        .books_wanted
+        .create(&CreateBookWantedDto {
+            book_id: low_id,
+            user_id: user.id,
+            priority: 5,
+        })
+        .await;
+    let raised = backend.books_wanted.update_priority(&low_id, &user.id, 5).await.unwrap();
+    let wanted = backend.books_wanted.find_all_by_user_id(&user.id).await.unwrap();
+
+    // Assert
+    assert!(matches!(duplicated, Err(AppError::Conflict(ErrorCode::BookAlreadyWanted, _))));
+    assert_eq!(raised.map(|book| book.priority), Some(5));
+    let book_ids: Vec<_> = wanted.iter().map(|book| book.book_id).collect();
+    assert_eq!(book_ids, vec![low_id, high_id]);
+    assert_eq!(backend.books_wanted.delete_many(&[low_id, high_id], &user.id).await.unwrap(), 2);
+    assert!(!backend.books_wanted.delete(&low_id, &user.id).await.unwrap());
+}
+
+async fn keeps_reserved_copies(backend: Backend) {
+    // Arrange - Uma proposta reserva os dois exemplares
+    let ana = backend.create_user("Ana").await;
+    let bruno = backend.create_user("Bruno").await;
+    let offered_id = backend.create_book("Dom Casmurro").await;
+    let wanted_id = backend.create_book("Iracema").await;
+    let offered_copy = backend.offer(offered_id, ana.id).await;
+    let wanted_copy = backend.offer(wanted_id, bruno.id).await;
+    backend
+        .trade
+        .create(&CreateTradeDto {
+            proposer_id: ana.id,
+            partner_id: bruno.id,
+            offered_book_id: offered_id,
+            offered_copy_id: offered_copy.id,
+            wanted_book_id: wanted_id,
+            wanted_copy_id: wanted_copy.id,
+        })
+        .await
+        .unwrap();
+
+    // Act
+    let delete_copy = backend.books_offered.delete_copy(&offered_copy.id, &ana.id).await;
+    let delete_book = backend.books_offered.delete(&wanted_id, &bruno.id).await;
+
+    // Assert
+    assert!(matches!(delete_copy, Err(AppError::Conflict(ErrorCode::CopyReserved, _))));
+    assert!(matches!(delete_book, Err(AppError::Conflict(ErrorCode::CopyReserved, _))));
+    assert!(backend.books_offered.any_reserved(&[offered_copy.id]).await.unwrap());
+    assert!(backend.books_offered.find_by_id(&offered_copy.id).await.unwrap().is_some());
+}
+
+async fn deletes_photos_with_the_copy(backend: Backend) {
+    // Arrange
+    let user = backend.create_user("Ana").await;
+    let book_id = backend.create_book("Dom Casmurro").await;
+    let copy = backend.offer(book_id, user.id).await;
+    let photo = backend
+        .book_photo
+        .create(&CreateBookPhotoDto {
+            copy_id: copy.id,
+            user_id: user.id,
+            storage_key: "fotos/capa.jpg".to_string(),
+            content_type: "image/jpeg".to_string(),
+            size_bytes: 1024,
+        })
+        .await
+        .unwrap();
+
+    // Act
+    let deleted = backend.books_offered.delete_copy(&copy.id, &user.id).await.unwrap();
+
+    // Assert
+    assert!(deleted);
+    assert!(backend.book_photo.find_by_id(&photo.id).await.unwrap().is_none());
+    assert!(backend.book_photo.find_by_copy_ids(&[copy.id]).await.unwrap().is_empty());
+    let orphan = backend
+        .book_photo
+        .create(&CreateBookPhotoDto {
+            copy_id: copy.id,
+            user_id: user.id,
+            storage_key: "fotos/outra.jpg".to_string(),
+            content_type: "image/jpeg".to_string(),
+            size_bytes: 1024,
+        })
+        .await;
+    assert!(matches!(orphan, Err(AppError::NotFoundError(_))));
+}
@@ -0,0 +1,146 @@
+// Testes de contrato: cada cenário roda contra o Postgres e contra o banco em
+// memória, garantindo que as duas implementações dos repositórios se comportem
+// da mesma forma
+
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::models::book::{
+    BookOffered, CreateBookOfferedDto, CreateBookWantedDto, GoogleBookDto, OfferedCopyAttributes,
+};
+use crate::models::user::{CreateUserDto, User};
+use crate::repositories::book_photo_repository::{BookPhotoRepository, PgBookPhotoRepository};
+use crate::repositories::book_repository::{BookRepository, PgBookRepository};
+use crate::repositories::books_offered_repository::{BooksOfferedRepository, PgBooksOfferedRepository};
+use crate::repositories::books_wanted_repository::{BooksWantedRepository, PgBooksWantedRepository};
+use crate::repositories::in_memory::{
+    InMemoryBookPhotoRepository, InMemoryBookRepository, InMemoryBooksOfferedRepository,
+    InMemoryBooksWantedRepository, InMemoryDatabase, InMemoryTradeRepository, InMemoryUserRepository,
+};
+use crate::repositories::test_helpers::get_test_db_pool;
+use crate::repositories::trade_repository::{PgTradeRepository, TradeRepository};
+use crate::repositories::user_repository::{PgUserRepository, UserRepository};
+
+// Gera, para cada cenário, um teste no Postgres (em um schema isolado) e outro
+// no banco em memória
+macro_rules! contract_tests {
+    ($($scenario:ident),* $(,)?) => {
+        mod postgres {
+            use crate::repositories::contract_test::Backend;
+
+            $(
+                #[tokio::test]
+                async fn $scenario() {
+                    super::$scenario(Backend::postgres().await).await;
+                }
+            )*
+        }
+
+        mod in_memory {
+            use crate::repositories::contract_test::Backend;
+
+            $(
+                #[tokio::test]
+                async fn $scenario() {
+                    super::$scenario(Backend::in_memory()).await;
+                }
+            )*
+        }
+    };
+}
+
+pub mod books_contract_test;
+pub mod trade_contract_test;
+pub mod user_contract_test;
+
+/// Repositórios de uma das implementações sob teste
+pub struct Backend {
+    pub user: Arc<dyn UserRepository>,
+    pub book: Arc<dyn BookRepository>,
+    pub books_offered: Arc<dyn BooksOfferedRepository>,
+    pub books_wanted: Arc<dyn BooksWantedRepository>,
+    pub book_photo: Arc<dyn BookPhotoRepository>,
+    pub trade: Arc<dyn TradeRepository>,
+}
+
+impl Backend {
+    // Repositórios no schema isolado do teste
+    pub async fn postgres() -> Self {
+        let pool = get_test_db_pool().await;
+
+        Self {
+            user: Arc::new(PgUserRepository::new(pool.clone())),
+            book: Arc::new(PgBookRepository::new(pool.clone())),
+            books_offered: Arc::new(PgBooksOfferedRepository::new(pool.clone())),
+            books_wanted: Arc::new(PgBooksWantedRepository::new(pool.clone())),
+            book_photo: Arc::new(PgBookPhotoRepository::new(pool.clone())),
+            trade: Arc::new(PgTradeRepository::new(pool)),
+        }
+    }
+
+    // Repositórios sobre um banco em memória novo
+    pub fn in_memory() -> Self {
+        let db = InMemoryDatabase::new();
+
+        Self {
+            user: Arc::new(InMemoryUserRepository::new(db.clone())),
+            book: Arc::new(InMemoryBookRepository::new(db.clone())),
+            books_offered: Arc::new(InMemoryBooksOfferedRepository::new(db.clone())),
+            books_wanted: Arc::new(InMemoryBooksWantedRepository::new(db.clone())),
+            book_photo: Arc::new(InMemoryBookPhotoRepository::new(db.clone())),
+            trade: Arc::new(InMemoryTradeRepository::new(db)),
+        }
+    }
+
+    // Cria um usuário com email único
+    pub async fn create_user(&self, name: &str) -> User {
+        let user = CreateUserDto {
+            name: name.to_string(),
+            email: format!("{}_{}@example.com", name.to_lowercase(), Uuid::new_v4()),
+            password: "password".to_string(),
+        };
+
+        self.user.create(&user, "senha_hash".to_string()).await.unwrap()
+    }
+
+    // Cadastra um livro com o título informado
+    pub async fn create_book(&self, title: &str) -> Uuid {
+        let book = GoogleBookDto {
+            google_id: format!("contrato-{}", Uuid::new_v4()),
+            title: title.to_string(),
+            authors: Some("Autor Teste".to_string()),
+            publisher: Some("Editora Teste".to_string()),
+            published_date: Some("2020-01-01".to_string()),
+            description: Some("Descrição de teste".to_string()),
+            image_url: Some("http://example.com/image.jpg".to_string()),
+            page_count: Some(100),
+        };
+
+        self.book.create(&book).await.unwrap()
+    }
+
+    // Adiciona um exemplar do livro à lista de possuídos do usuário
+    pub async fn offer(&self, book_id: Uuid, user_id: Uuid) -> BookOffered {
+        self.books_offered
+            .create(&CreateBookOfferedDto {
+                book_id,
+                user_id,
+                attributes: OfferedCopyAttributes::default(),
+            })
+            .await
+            .unwrap()
+    }
+
+    // Adiciona o livro à lista de desejados do usuário
+    pub async fn want(&self, book_id: Uuid, user_id: Uuid, priority: i16) {
+        self.books_wanted
+            .create(&CreateBookWantedDto {
+                book_id,
+                user_id,
+                priority,
+            })
+            .await
+            .unwrap();
+    }
+}
@@ -0,0 +1,212 @@
+use crate::error::{AppError, ErrorCode};
+use crate::models::book::BookOffered;
+use crate::models::trade::{CreateTradeDto, TradeStatus};
+use crate::models::user::User;
+use crate::repositories::contract_test::Backend;
+use uuid::Uuid;
+
+contract_tests!(
+    finds_possible_trades_with_oldest_copies,
+    skips_reserved_and_unavailable_copies,
+    orders_possible_trades_by_score,
+    rejects_promising_a_copy_twice,
+    completes_an_accepted_trade,
+    only_changes_status_from_the_expected_one,
+);
+
+// Ana oferece "Dom Casmurro" e quer "Iracema"; Bruno oferece "Iracema" e quer "Dom Casmurro"
+struct Scenario {
+    ana: User,
+    bruno: User,
+    offered_id: Uuid,
+    wanted_id: Uuid,
+    ana_copy: BookOffered,
+    bruno_copy: BookOffered,
+}
+
+async fn setup_matching_users(backend: &Backend) -> Scenario {
+    let ana = backend.create_user("Ana").await;
+    let bruno = backend.create_user("Bruno").await;
+    let offered_id = backend.create_book("Dom Casmurro").await;
+    let wanted_id = backend.create_book("Iracema").await;
+
+    let ana_copy = backend.offer(offered_id, ana.id).await;
+    let bruno_copy = backend.offer(wanted_id, bruno.id).await;
+    backend.want(wanted_id, ana.id, 4).await;
+    backend.want(offered_id, bruno.id, 2).await;
+
+    Scenario {
+        ana,
+        bruno,
+        offered_id,
+        wanted_id,
+        ana_copy,
+        bruno_copy,
+    }
+}
+
+fn propose(scenario: &Scenario) -> CreateTradeDto {
+    CreateTradeDto {
+        proposer_id: scenario.ana.id,
+        partner_id: scenario.bruno.id,
+        offered_book_id: scenario.offered_id,
+        offered_copy_id: scenario.ana_copy.id,
+        wanted_book_id: scenario.wanted_id,
+        wanted_copy_id: scenario.bruno_copy.id,
+    }
+}
+
+async fn finds_possible_trades_with_oldest_copies(backend: Backend) {
+    // Arrange - Exemplares extras, mais novos, não devem ser sugeridos
+    let scenario = setup_matching_users(&backend).await;
+    backend.offer(scenario.offered_id, scenario.ana.id).await;
+    backend.offer(scenario.wanted_id, scenario.bruno.id).await;
+
+    // Act
+    let trades = backend.trade.find_possible_trades(scenario.ana.id).await.unwrap();
+    let partner_trades = backend.trade.find_possible_trades(scenario.bruno.id).await.unwrap();
+
+    // Assert
+    assert_eq!(trades.len(), 1);
+    let trade = &trades[0];
+    assert_eq!(trade.trade_partner.id, scenario.bruno.id);
+    assert_eq!(trade.offered_book_id, scenario.offered_id);
+    assert_eq!(trade.wanted_book_id, scenario.wanted_id);
+    assert_eq!(trade.offered_book.title, "Dom Casmurro");
+    assert_eq!(trade.wanted_book.title, "Iracema");
+    assert_eq!(trade.offered_copy.id, scenario.ana_copy.id);
+    assert_eq!(trade.wanted_copy.id, scenario.bruno_copy.id);
+    assert_eq!(partner_trades.len(), 1);
+    assert_eq!(partner_trades[0].trade_partner.id, scenario.ana.id);
+    assert_eq!(partner_trades[0].score, trade.score);
+}
+
+async fn skips_reserved_and_unavailable_copies(backend: Backend) {
+    // Arrange
+    let scenario = setup_matching_users(&backend).await;
+    backend.trade.create(&propose(&scenario)).await.unwrap();
+
+    // Act - Os únicos exemplares estão prometidos na proposta
+    let trades = backend.trade.find_possible_trades(scenario.ana.id).await.unwrap();
+
+    // Assert
+    assert!(trades.is_empty());
+
+    // Arrange - Recusada a proposta, a sugestão volta a aparecer
+    let trade_id = backend.trade.find_by_user_id(&scenario.ana.id).await.unwrap()[0].id;
+    backend
+        .trade
+        .update_status(&trade_id, TradeStatus::Pending, TradeStatus::Rejected)
+        .await
+        .unwrap();
+    assert_eq!(backend.trade.find_possible_trades(scenario.ana.id).await.unwrap().len(), 1);
+
+    // Act - Exemplar marcado como indisponível
+    let mut attributes = scenario.bruno_copy.attributes.clone();
+    attributes.available = false;
+    backend
+        .books_offered
+        .update(&scenario.bruno_copy.id, &scenario.bruno.id, &attributes)
+        .await
+        .unwrap();
+
+    // Assert
+    assert!(backend.trade.find_possible_trades(scenario.ana.id).await.unwrap().is_empty());
+}
+
+async fn orders_possible_trades_by_score(backend: Backend) {
+    // Arrange - Carla também troca com Ana, mas dá prioridade máxima ao livro dela
+    let scenario = setup_matching_users(&backend).await;
+    let carla = backend.create_user("Carla").await;
+    let carla_book = backend.create_book("Memórias Póstumas").await;
+    backend.offer(carla_book, carla.id).await;
+    backend.want(carla_book, scenario.ana.id, 4).await;
+    backend.want(scenario.offered_id, carla.id, 5).await;
+
+    // Act
+    let trades = backend.trade.find_possible_trades(scenario.ana.id).await.unwrap();
+
+    // Assert
+    let partners: Vec<_> = trades.iter().map(|trade| trade.trade_partner.id).collect();
+    assert_eq!(partners, vec![carla.id, scenario.bruno.id]);
+    assert!(trades[0].score > trades[1].score);
+}
+
+async fn rejects_promising_a_copy_twice(backend: Backend) {
+    // Arrange
+    let scenario = setup_matching_users(&backend).await;
+    backend.trade.create(&propose(&scenario)).await.unwrap();
+
+    // Act
+    let again = backend.trade.create(&propose(&scenario)).await;
+    let missing = backend
+        .trade
+        .create(&CreateTradeDto {
+            wanted_copy_id: Uuid::new_v4(),
+            offered_copy_id: backend.offer(scenario.offered_id, scenario.ana.id).await.id,
+            ..propose(&scenario)
+        })
+        .await;
+
+    // Assert - A proposta falha sem deixar nada gravado
+    assert!(matches!(again, Err(AppError::Conflict(ErrorCode::CopyReserved, _))));
+    assert!(missing.is_err());
+    assert_eq!(backend.trade.find_by_user_id(&scenario.ana.id).await.unwrap().len(), 1);
+}
+
+async fn completes_an_accepted_trade(backend: Backend) {
+    // Arrange
+    let scenario = setup_matching_users(&backend).await;
+    let trade = backend.trade.create(&propose(&scenario)).await.unwrap();
+
+    // Act - Trocas pendentes não podem ser concluídas
+    let pending = backend.trade.complete(&trade.id).await.unwrap();
+    backend
+        .trade
+        .update_status(&trade.id, TradeStatus::Pending, TradeStatus::Accepted)
+        .await
+        .unwrap();
+    let completed = backend.trade.complete(&trade.id).await.unwrap().unwrap();
+
+    // Assert
+    assert!(pending.is_none());
+    assert_eq!(completed.status, TradeStatus::Completed);
+    assert_eq!(completed.offered_copy_id, None);
+    assert_eq!(completed.wanted_copy_id, None);
+
+    // Cada parte possui o livro recebido e não o deseja mais
+    let ana_books = backend.books_offered.find_by_user_id(&scenario.ana.id).await.unwrap();
+    let bruno_books = backend.books_offered.find_by_user_id(&scenario.bruno.id).await.unwrap();
+    assert_eq!(ana_books, vec![scenario.wanted_id]);
+    assert_eq!(bruno_books, vec![scenario.offered_id]);
+    assert!(backend.books_wanted.find(&scenario.wanted_id, &scenario.ana.id).await.unwrap().is_none());
+    assert!(backend.books_offered.find_by_id(&scenario.ana_copy.id).await.unwrap().is_none());
+    assert!(!backend.books_offered.any_reserved(&[scenario.bruno_copy.id]).await.unwrap());
+    assert!(backend.trade.complete(&trade.id).await.unwrap().is_none());
+}
+
+async fn only_changes_status_from_the_expected_one(backend: Backend) {
+    // Arrange
+    let scenario = setup_matching_users(&backend).await;
+    let trade = backend.trade.create(&propose(&scenario)).await.unwrap();
+
+    // Act
+    let wrong_from = backend
+        .trade
+        .update_status(&trade.id, TradeStatus::Accepted, TradeStatus::Cancelled)
+        .await
+        .unwrap();
+    let accepted = backend
+        .trade
+        .update_status(&trade.id, TradeStatus::Pending, TradeStatus::Accepted)
+        .await
+        .unwrap();
+
+    // Assert - Trocas aceitas mantêm a reserva
+    assert!(wrong_from.is_none());
+    assert_eq!(accepted.map(|trade| trade.status), Some(TradeStatus::Accepted));
+    assert!(backend.books_offered.any_reserved(&[scenario.ana_copy.id]).await.unwrap());
+    let found = backend.trade.find_by_id(&trade.id).await.unwrap().unwrap();
+    assert_eq!(found.status, TradeStatus::Accepted);
+    assert_eq!(backend.trade.find_by_user_id(&scenario.bruno.id).await.unwrap().len(), 1);
+}
@@ -0,0 +1,79 @@
+use crate::error::{AppError, ErrorCode};
+use crate::models::user::{CreateUserDto, UserLocation};
+use crate::repositories::contract_test::Backend;
+use uuid::Uuid;
+
+contract_tests!(
+    creates_and_finds_users,
+    rejects_duplicated_email,
+    updates_location_and_password,
+    bans_and_reactivates_users,
+);
+
+async fn creates_and_finds_users(backend: Backend) {
+    // Arrange & Act
+    let user = backend.create_user("Ana").await;
+
+    // Assert
+    let by_email = backend.user.find_by_email(&user.email).await.unwrap().unwrap();
+    let by_id = backend.user.find_by_id(&user.id).await.unwrap().unwrap();
+
+    assert_eq!(by_email.id, user.id);
+    assert_eq!(by_id.email, user.email);
+    assert_eq!(by_id.hash_password, "senha_hash");
+    assert!(backend.user.find_by_id(&Uuid::new_v4()).await.unwrap().is_none());
+    assert!(!backend.user.two_factor_enabled(&user.id).await.unwrap());
+}
+
+async fn rejects_duplicated_email(backend: Backend) {
+    // Arrange
+    let user = backend.create_user("Ana").await;
+    let duplicated = CreateUserDto {
+        name: "Outra Ana".to_string(),
+        email: user.email.clone(),
+        password: "password".to_string(),
+    };
+
+    // Act
+    let result = backend.user.create(&duplicated, "senha_hash".to_string()).await;
+
+    // Assert
+    assert!(matches!(result, Err(AppError::Conflict(ErrorCode::EmailAlreadyInUse, _))));
+}
+
+async fn updates_location_and_password(backend: Backend) {
+    // Arrange
+    let user = backend.create_user("Ana").await;
+    let location = UserLocation {
+        latitude: -23.55,
+        longitude: -46.63,
+    };
+
+    // Act & Assert
+    assert!(backend.user.update_location(&user.id, Some(location)).await.unwrap());
+    assert!(backend.user.update_password(&user.id, "novo_hash".to_string()).await.unwrap());
+    assert!(!backend.user.update_password(&Uuid::new_v4(), "novo_hash".to_string()).await.unwrap());
+
+    let updated = backend.user.find_by_id(&user.id).await.unwrap().unwrap();
+    assert_eq!(updated.hash_password, "novo_hash");
+}
+
+async fn bans_and_reactivates_users(backend: Backend) {
+    // Arrange
+    let user = backend.create_user("Ana").await;
+    assert!(user.banned_at.is_none());
+
+    // Act & Assert
+    assert!(backend.user.set_banned(&user.id, true).await.unwrap());
+    let banned = backend.user.find_by_id(&user.id).await.unwrap().unwrap();
+    assert!(banned.banned_at.is_some());
+
+    // Suspender de novo mantém a data da primeira suspensão
+    assert!(backend.user.set_banned(&user.id, true).await.unwrap());
+    let banned_again = backend.user.find_by_id(&user.id).await.unwrap().unwrap();
+    assert_eq!(banned_again.banned_at, banned.banned_at);
+
+    assert!(backend.user.set_banned(&user.id, false).await.unwrap());
+    assert!(backend.user.find_by_id(&user.id).await.unwrap().unwrap().banned_at.is_none());
+    assert!(!backend.user.set_banned(&Uuid::new_v4(), true).await.unwrap());
+}
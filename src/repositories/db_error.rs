@@ -0,0 +1,33 @@
+use sqlx::error::DatabaseError;
+
+/// SQLSTATE de violação de chave estrangeira
+const FOREIGN_KEY_VIOLATION: &str = "23503";
+/// SQLSTATE de violação de unicidade
+const UNIQUE_VIOLATION: &str = "23505";
+/// SQLSTATE de violação de restrição CHECK
+const CHECK_VIOLATION: &str = "23514";
+
+/// Tipo de restrição do banco violada por um comando
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConstraintKind {
+    Unique,
+    ForeignKey,
+    Check,
+}
+
+/// Identifica a restrição violada a partir do código SQLSTATE do erro
+///
+/// Retorna o tipo da restrição e, quando o banco informa, o seu nome. Erros que
+/// não são violações de restrição retornam None.
+pub fn constraint_violation(error: &sqlx::Error) -> Option<(ConstraintKind, Option<&str>)> {
+    let database_error: &dyn DatabaseError = error.as_database_error()?;
+
+    let kind = match database_error.code()?.as_ref() {
+        UNIQUE_VIOLATION => ConstraintKind::Unique,
+        FOREIGN_KEY_VIOLATION => ConstraintKind::ForeignKey,
+        CHECK_VIOLATION => ConstraintKind::Check,
+        _ => return None,
+    };
+
+    Some((kind, database_error.constraint()))
+}
@@ -0,0 +1,77 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::i18n::messages;
+use crate::models::book::{BookPhotoRecord, CreateBookPhotoDto};
+use crate::repositories::book_photo_repository::BookPhotoRepository;
+use crate::repositories::in_memory::{now, InMemoryDatabase};
+
+pub struct InMemoryBookPhotoRepository {
+    db: InMemoryDatabase,
+}
+
+impl InMemoryBookPhotoRepository {
+    pub fn new(db: InMemoryDatabase) -> Self {
+        Self { db }
+    }
+
+    fn filter(&self, selected: impl Fn(&BookPhotoRecord) -> bool) -> Result<Vec<BookPhotoRecord>, AppError> {
+        self.db.read(|tables| {
+            tables
+                .photos
+                .iter()
+                .filter(|photo| selected(photo))
+                .cloned()
+                .collect()
+        })
+    }
+}
+
+#[async_trait]
+impl BookPhotoRepository for InMemoryBookPhotoRepository {
+    async fn create(&self, photo: &CreateBookPhotoDto) -> Result<BookPhotoRecord, AppError> {
+        self.db.write(|tables| {
+            if tables.copy(&photo.copy_id).is_none() {
+                return Err(AppError::NotFoundError(messages::COPY_NOT_FOUND.text()));
+            }
+
+            let record = BookPhotoRecord {
+                id: Uuid::new_v4(),
+                copy_id: photo.copy_id,
+                user_id: photo.user_id,
+                storage_key: photo.storage_key.clone(),
+                content_type: photo.content_type.clone(),
+                size_bytes: photo.size_bytes,
+                created_at: now(),
+            };
+            tables.photos.push(record.clone());
+
+            Ok(record)
+        })
+    }
+
+    async fn find_by_id(&self, id: &Uuid) -> Result<Option<BookPhotoRecord>, AppError> {
+        Ok(self.filter(|photo| photo.id == *id)?.pop())
+    }
+
+    async fn find_by_copy_id(&self, copy_id: &Uuid) -> Result<Vec<BookPhotoRecord>, AppError> {
+        self.filter(|photo| photo.copy_id == *copy_id)
+    }
+
+    async fn find_by_user_id(&self, user_id: &Uuid) -> Result<Vec<BookPhotoRecord>, AppError> {
+        self.filter(|photo| photo.user_id == *user_id)
+    }
+
+    async fn find_by_copy_ids(&self, copy_ids: &[Uuid]) -> Result<Vec<BookPhotoRecord>, AppError> {
+        self.filter(|photo| copy_ids.contains(&photo.copy_id))
+    }
+
+    async fn delete(&self, id: &Uuid) -> Result<bool, AppError> {
+        self.db.write(|tables| {
+            let before = tables.photos.len();
+            tables.photos.retain(|photo| photo.id != *id);
+            Ok(tables.photos.len() < before)
+        })
+    }
+}
@@ -0,0 +1,156 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::models::book::{BookDetails, GoogleBookDto};
+use crate::repositories::book_repository::{
+    parse_published_date, truncate_column, user_list, BookRepository, BookWithId,
+};
+use crate::repositories::in_memory::{now, BookRow, InMemoryDatabase};
+
+pub struct InMemoryBookRepository {
+    db: InMemoryDatabase,
+}
+
+impl InMemoryBookRepository {
+    pub fn new(db: InMemoryDatabase) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl BookRepository for InMemoryBookRepository {
+    async fn create(&self, book: &GoogleBookDto) -> Result<Uuid, AppError> {
+        let published_date = parse_published_date(&book.published_date)?;
+
+        self.db.write(|tables| {
+            // Mesmo google_id: o cadastro existente é reaproveitado
+            if let Some(existing) = tables
+                .books
+                .iter()
+                .find(|row| row.google_id.as_deref() == Some(book.google_id.as_str()))
+            {
+                return Ok(existing.id);
+            }
+
+            let id = Uuid::new_v4();
+            tables.books.push(BookRow {
+                id,
+                google_id: Some(book.google_id.clone()),
+                title: truncate_column(&book.title).to_string(),
+                author: truncate_column(book.authors.as_deref().unwrap_or_default()).to_string(),
+                publisher: book.publisher.clone(),
+                published_date,
+                description: truncate_column(book.description.as_deref().unwrap_or_default()).to_string(),
+                image_url: book.image_url.clone().unwrap_or_default(),
+                page_count: book.page_count,
+                metadata_fetched_at: Some(now()),
+                metadata_checked_at: None,
+            });
+
+            Ok(id)
+        })
+    }
+
+    async fn find_by_google_id(&self, google_id: &str) -> Result<Option<BookWithId>, AppError> {
+        self.db.read(|tables| {
+            tables
+                .books
+                .iter()
+                .find(|row| row.google_id.as_deref() == Some(google_id))
+                .map(BookRow::with_id)
+        })
+    }
+
+    async fn find_by_google_ids(&self, google_ids: &[String]) -> Result<Vec<BookWithId>, AppError> {
+        self.db.read(|tables| {
+            tables
+                .books
+                .iter()
+                .filter(|row| row.google_id.as_ref().is_some_and(|id| google_ids.contains(id)))
+                .map(BookRow::with_id)
+                .collect()
+        })
+    }
+
+    async fn find_by_id(&self, id: &str) -> Result<Option<GoogleBookDto>, AppError> {
+        // IDs inválidos não correspondem a nenhum livro
+        let Ok(book_id) = Uuid::parse_str(id) else {
+            return Ok(None);
+        };
+
+        self.db.read(|tables| tables.book(&book_id).map(BookRow::to_dto))
+    }
+
+    async fn find_by_ids(&self, ids: &[String]) -> Result<Vec<BookWithId>, AppError> {
+        let book_ids: Vec<Uuid> = ids.iter().filter_map(|id| Uuid::parse_str(id).ok()).collect();
+
+        self.db.read(|tables| {
+            tables
+                .books
+                .iter()
+                .filter(|row| book_ids.contains(&row.id))
+                .map(BookRow::with_id)
+                .collect()
+        })
+    }
+
+    async fn find_details(&self, id: &Uuid, user_id: &Uuid) -> Result<Option<BookDetails>, AppError> {
+        self.db.read(|tables| {
+            tables.book(id).map(|row| BookDetails {
+                id: row.id,
+                book: row.to_dto(),
+                offered_count: tables.books_offered.iter().filter(|copy| copy.book_id == *id).count() as i64,
+                wanted_count: tables.books_wanted.iter().filter(|wanted| wanted.book_id == *id).count() as i64,
+                user_list: user_list(tables.is_offered(id, user_id), tables.wanted(id, user_id).is_some()),
+                metadata_fetched_at: row.metadata_fetched_at,
+                metadata_checked_at: row.metadata_checked_at,
+            })
+        })
+    }
+
+    async fn update_metadata(&self, id: &Uuid, book: &GoogleBookDto) -> Result<(), AppError> {
+        let published_date = parse_published_date(&book.published_date).ok().flatten();
+
+        self.db.write(|tables| {
+            if let Some(row) = tables.books.iter_mut().find(|row| row.id == *id) {
+                if let Some(title) = present(Some(&book.title)) {
+                    row.title = truncate_column(title).to_string();
+                }
+                if let Some(author) = present(book.authors.as_deref()) {
+                    row.author = truncate_column(author).to_string();
+                }
+                if let Some(description) = present(book.description.as_deref()) {
+                    row.description = truncate_column(description).to_string();
+                }
+                if let Some(image_url) = present(book.image_url.as_deref()) {
+                    row.image_url = image_url.to_string();
+                }
+                if let Some(publisher) = present(book.publisher.as_deref()) {
+                    row.publisher = Some(publisher.to_string());
+                }
+                row.published_date = published_date.or(row.published_date);
+                row.page_count = book.page_count.or(row.page_count);
+                row.metadata_fetched_at = Some(now());
+                row.metadata_checked_at = Some(now());
+            }
+
+            Ok(())
+        })
+    }
+
+    async fn mark_metadata_checked(&self, id: &Uuid) -> Result<(), AppError> {
+        self.db.write(|tables| {
+            if let Some(row) = tables.books.iter_mut().find(|row| row.id == *id) {
+                row.metadata_checked_at = Some(now());
+            }
+
+            Ok(())
+        })
+    }
+}
+
+// Como o NULLIF do Postgres: texto vazio conta como ausente
+fn present(value: Option<&str>) -> Option<&str> {
+    value.filter(|v| !v.is_empty())
+}
@@ -0,0 +1,174 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::i18n::messages;
+use crate::models::book::{BookOffered, CreateBookOfferedDto, OfferedCopyAttributes};
+use crate::models::user::UserResponse;
+use crate::repositories::books_offered_repository::BooksOfferedRepository;
+use crate::repositories::in_memory::InMemoryDatabase;
+
+pub struct InMemoryBooksOfferedRepository {
+    db: InMemoryDatabase,
+}
+
+impl InMemoryBooksOfferedRepository {
+    pub fn new(db: InMemoryDatabase) -> Self {
+        Self { db }
+    }
+
+    fn filter(&self, selected: impl Fn(&BookOffered) -> bool) -> Result<Vec<BookOffered>, AppError> {
+        self.db.read(|tables| {
+            tables
+                .books_offered
+                .iter()
+                .filter(|copy| selected(copy))
+                .cloned()
+                .collect()
+        })
+    }
+}
+
+#[async_trait]
+impl BooksOfferedRepository for InMemoryBooksOfferedRepository {
+    async fn create(&self, book_offered: &CreateBookOfferedDto) -> Result<BookOffered, AppError> {
+        let mut copies = self.create_copies(book_offered, 1).await?;
+
+        copies
+            .pop()
+            .ok_or_else(|| AppError::DatabaseError("Nenhum exemplar foi criado".to_string()))
+    }
+
+    async fn create_copies(
+        &self,
+        book_offered: &CreateBookOfferedDto,
+        quantity: i32,
+    ) -> Result<Vec<BookOffered>, AppError> {
+        self.db
+            .write(|tables| tables.insert_copies(book_offered, quantity))
+    }
+
+    async fn create_many(&self, books_offered: &[CreateBookOfferedDto]) -> Result<Vec<BookOffered>, AppError> {
+        self.db.write(|tables| {
+            let mut copies = Vec::with_capacity(books_offered.len());
+            for book_offered in books_offered {
+                copies.extend(tables.insert_copies(book_offered, 1)?);
+            }
+            Ok(copies)
+        })
+    }
+
+    async fn create_from_wanted(&self, book_offered: &CreateBookOfferedDto) -> Result<Option<BookOffered>, AppError> {
+        self.db.write(|tables| {
+            if !tables.remove_wanted(&book_offered.book_id, &book_offered.user_id) {
+                return Ok(None);
+            }
+            Ok(tables.insert_copies(book_offered, 1)?.pop())
+        })
+    }
+
+    async fn find(&self, book_id: &Uuid, user_id: &Uuid) -> Result<Option<BookOffered>, AppError> {
+        Ok(self.find_copies(book_id, user_id).await?.into_iter().next())
+    }
+
+    async fn find_by_id(&self, id: &Uuid) -> Result<Option<BookOffered>, AppError> {
+        self.db.read(|tables| tables.copy(id).cloned())
+    }
+
+    async fn find_copies(&self, book_id: &Uuid, user_id: &Uuid) -> Result<Vec<BookOffered>, AppError> {
+        self.filter(|copy| copy.book_id == *book_id && copy.user_id == *user_id)
+    }
+
+    async fn update(
+        &self,
+        id: &Uuid,
+        user_id: &Uuid,
+        attributes: &OfferedCopyAttributes,
+    ) -> Result<Option<BookOffered>, AppError> {
+        self.db.write(|tables| {
+            let copy = tables
+                .books_offered
+                .iter_mut()
+                .find(|copy| copy.id == *id && copy.user_id == *user_id);
+
+            Ok(copy.map(|copy| {
+                copy.attributes = attributes.clone();
+                copy.clone()
+            }))
+        })
+    }
+
+    async fn delete(&self, book_id: &Uuid, user_id: &Uuid) -> Result<bool, AppError> {
+        let deleted = self.db.write(|tables| {
+            tables.delete_copies(
+                |copy| copy.book_id == *book_id && copy.user_id == *user_id,
+                messages::BOOK_COPIES_RESERVED,
+            )
+        })?;
+
+        Ok(deleted > 0)
+    }
+
+    async fn delete_copy(&self, id: &Uuid, user_id: &Uuid) -> Result<bool, AppError> {
+        let deleted = self.db.write(|tables| {
+            tables.delete_copies(
+                |copy| copy.id == *id && copy.user_id == *user_id,
+                messages::COPY_RESERVED,
+            )
+        })?;
+
+        Ok(deleted > 0)
+    }
+
+    async fn delete_many(&self, book_ids: &[Uuid], user_id: &Uuid) -> Result<u64, AppError> {
+        self.db.write(|tables| {
+            tables.delete_copies(
+                |copy| book_ids.contains(&copy.book_id) && copy.user_id == *user_id,
+                messages::BOOKS_COPIES_RESERVED,
+            )
+        })
+    }
+
+    async fn any_reserved(&self, ids: &[Uuid]) -> Result<bool, AppError> {
+        self.db
+            .read(|tables| ids.iter().any(|id| tables.reservations.contains_key(id)))
+    }
+
+    async fn find_by_user_id(&self, user_id: &Uuid) -> Result<Vec<Uuid>, AppError> {
+        let mut book_ids: Vec<Uuid> = self
+            .filter(|copy| copy.user_id == *user_id)?
+            .into_iter()
+            .map(|copy| copy.book_id)
+            .collect();
+        book_ids.sort();
+        book_ids.dedup();
+
+        Ok(book_ids)
+    }
+
+    async fn find_all_by_user_id(&self, user_id: &Uuid) -> Result<Vec<BookOffered>, AppError> {
+        self.filter(|copy| copy.user_id == *user_id)
+    }
+
+    async fn find_owners_by_book_id(
+        &self,
+        book_id: &Uuid,
+    ) -> Result<Vec<(UserResponse, BookOffered)>, AppError> {
+        let mut owners: Vec<(UserResponse, BookOffered)> = self.db.read(|tables| {
+            tables
+                .books_offered
+                .iter()
+                .filter(|copy| copy.book_id == *book_id)
+                .filter_map(|copy| {
+                    let owner = tables.user(&copy.user_id)?;
+                    Some((UserResponse::from(owner.user.clone()), copy.clone()))
+                })
+                .collect()
+        })?;
+
+        // Disponíveis primeiro; a ordenação estável mantém a ordem de inclusão
+        owners.sort_by_key(|(_, copy)| !copy.attributes.available);
+
+        Ok(owners)
+    }
+}
@@ -0,0 +1,144 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::error::{AppError, ErrorCode};
+use crate::i18n::messages;
+use crate::models::book::{BookWanted, CreateBookWantedDto};
+use crate::repositories::books_wanted_repository::BooksWantedRepository;
+use crate::repositories::in_memory::{InMemoryDatabase, Tables, WantedRow};
+
+pub struct InMemoryBooksWantedRepository {
+    db: InMemoryDatabase,
+}
+
+impl InMemoryBooksWantedRepository {
+    pub fn new(db: InMemoryDatabase) -> Self {
+        Self { db }
+    }
+}
+
+fn to_book_wanted(row: &WantedRow) -> BookWanted {
+    BookWanted {
+        book_id: row.book_id,
+        user_id: row.user_id,
+        priority: row.priority,
+    }
+}
+
+/// Equivalente em memória do `insert_wanted` do Postgres
+fn insert_wanted(tables: &mut Tables, book_wanted: &CreateBookWantedDto) -> Result<BookWanted, AppError> {
+    if tables.is_offered(&book_wanted.book_id, &book_wanted.user_id) {
+        return Err(AppError::Conflict(
+            ErrorCode::BookAlreadyOffered,
+            messages::BOOK_ALREADY_OFFERED.text(),
+        ));
+    }
+    if tables.wanted(&book_wanted.book_id, &book_wanted.user_id).is_some() {
+        return Err(AppError::Conflict(
+            ErrorCode::BookAlreadyWanted,
+            messages::BOOK_ALREADY_WANTED.text(),
+        ));
+    }
+    if tables.book(&book_wanted.book_id).is_none() {
+        return Err(AppError::ValidationError(
+            messages::BOOK_ID_NOT_FOUND.format(&[&book_wanted.book_id]),
+        ));
+    }
+    if tables.user(&book_wanted.user_id).is_none() {
+        return Err(AppError::ValidationError(
+            messages::USER_ID_NOT_FOUND.format(&[&book_wanted.user_id]),
+        ));
+    }
+
+    let row = WantedRow {
+        book_id: book_wanted.book_id,
+        user_id: book_wanted.user_id,
+        priority: book_wanted.priority,
+    };
+    let created = to_book_wanted(&row);
+    tables.books_wanted.push(row);
+
+    Ok(created)
+}
+
+#[async_trait]
+impl BooksWantedRepository for InMemoryBooksWantedRepository {
+    async fn create(&self, book_wanted: &CreateBookWantedDto) -> Result<BookWanted, AppError> {
+        self.db.write(|tables| insert_wanted(tables, book_wanted))
+    }
+
+    async fn create_many(&self, books_wanted: &[CreateBookWantedDto]) -> Result<Vec<BookWanted>, AppError> {
+        self.db.write(|tables| {
+            books_wanted
+                .iter()
+                .map(|book_wanted| insert_wanted(tables, book_wanted))
+                .collect()
+        })
+    }
+
+    async fn find(&self, book_id: &Uuid, user_id: &Uuid) -> Result<Option<BookWanted>, AppError> {
+        self.db
+            .read(|tables| tables.wanted(book_id, user_id).map(to_book_wanted))
+    }
+
+    async fn update_priority(
+        &self,
+        book_id: &Uuid,
+        user_id: &Uuid,
+        priority: i16,
+    ) -> Result<Option<BookWanted>, AppError> {
+        self.db.write(|tables| {
+            let row = tables
+                .books_wanted
+                .iter_mut()
+                .find(|row| row.book_id == *book_id && row.user_id == *user_id);
+
+            Ok(row.map(|row| {
+                row.priority = priority;
+                to_book_wanted(row)
+            }))
+        })
+    }
+
+    async fn delete(&self, book_id: &Uuid, user_id: &Uuid) -> Result<bool, AppError> {
+        self.db
+            .write(|tables| Ok(tables.remove_wanted(book_id, user_id)))
+    }
+
+    async fn delete_many(&self, book_ids: &[Uuid], user_id: &Uuid) -> Result<u64, AppError> {
+        self.db.write(|tables| {
+            let before = tables.books_wanted.len();
+            tables
+                .books_wanted
+                .retain(|row| !(book_ids.contains(&row.book_id) && row.user_id == *user_id));
+            Ok((before - tables.books_wanted.len()) as u64)
+        })
+    }
+
+    async fn find_by_user_id(&self, user_id: &Uuid) -> Result<Vec<Uuid>, AppError> {
+        self.db.read(|tables| {
+            tables
+                .books_wanted
+                .iter()
+                .filter(|row| row.user_id == *user_id)
+                .map(|row| row.book_id)
+                .collect()
+        })
+    }
+
+    async fn find_all_by_user_id(&self, user_id: &Uuid) -> Result<Vec<BookWanted>, AppError> {
+        let mut rows: Vec<BookWanted> = self.db.read(|tables| {
+            tables
+                .books_wanted
+                .iter()
+                .filter(|row| row.user_id == *user_id)
+                .map(to_book_wanted)
+                .collect()
+        })?;
+
+        // Maior prioridade primeiro; empates mantêm a ordem de inclusão
+        rows.sort_by_key(|row| std::cmp::Reverse(row.priority));
+
+        Ok(rows)
+    }
+}
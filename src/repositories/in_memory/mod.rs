@@ -0,0 +1,293 @@
+mod book_photo_repository;
+mod book_repository;
+mod books_offered_repository;
+mod books_wanted_repository;
+mod oauth_repository;
+mod trade_repository;
+mod two_factor_repository;
+mod user_repository;
+
+pub use book_photo_repository::InMemoryBookPhotoRepository;
+pub use book_repository::InMemoryBookRepository;
+pub use books_offered_repository::InMemoryBooksOfferedRepository;
+pub use books_wanted_repository::InMemoryBooksWantedRepository;
+pub use oauth_repository::InMemoryOAuthRepository;
+pub use trade_repository::InMemoryTradeRepository;
+pub use two_factor_repository::InMemoryTwoFactorRepository;
+pub use user_repository::InMemoryUserRepository;
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Instant;
+
+use chrono::{NaiveDate, NaiveDateTime, Utc};
+use uuid::Uuid;
+
+use crate::error::{AppError, ErrorCode};
+use crate::i18n::{messages, Message};
+use crate::models::book::{BookOffered, BookPhotoRecord, CreateBookOfferedDto, GoogleBookDto};
+use crate::models::oauth::OAuthLoginState;
+use crate::models::trade::Trade;
+use crate::models::user::{User, UserLocation};
+use crate::repositories::book_repository::BookWithId;
+
+/// Banco de dados em memória compartilhado pelos repositórios `InMemory*`
+///
+/// Reproduz as restrições e as transações do Postgres usadas pelos
+/// repositórios, para que os testes rodem sem banco e em paralelo. Cada
+/// instância é um banco independente; os repositórios criados com clones da
+/// mesma instância enxergam os mesmos dados.
+#[derive(Clone, Default)]
+pub struct InMemoryDatabase {
+    tables: Arc<Mutex<Tables>>,
+}
+
+impl InMemoryDatabase {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, Tables>, AppError> {
+        self.tables
+            .lock()
+            .map_err(|e| AppError::InternalServerError(format!("Falha ao acessar o banco em memória: {}", e)))
+    }
+
+    /// Executa uma consulta sobre as tabelas
+    fn read<T>(&self, f: impl FnOnce(&Tables) -> T) -> Result<T, AppError> {
+        Ok(f(&*self.lock()?))
+    }
+
+    /// Executa as alterações como uma transação: se `f` falhar, nada é gravado
+    fn write<T>(&self, f: impl FnOnce(&mut Tables) -> Result<T, AppError>) -> Result<T, AppError> {
+        let mut tables = self.lock()?;
+        let mut draft = tables.clone();
+        let result = f(&mut draft)?;
+        *tables = draft;
+
+        Ok(result)
+    }
+}
+
+fn now() -> NaiveDateTime {
+    Utc::now().naive_utc()
+}
+
+#[derive(Clone)]
+struct UserRow {
+    user: User,
+    location: Option<UserLocation>,
+}
+
+#[derive(Clone)]
+struct BookRow {
+    id: Uuid,
+    google_id: Option<String>,
+    title: String,
+    author: String,
+    publisher: Option<String>,
+    published_date: Option<NaiveDate>,
+    description: String,
+    image_url: String,
+    page_count: Option<i32>,
+    metadata_fetched_at: Option<NaiveDateTime>,
+    metadata_checked_at: Option<NaiveDateTime>,
+}
+
+impl BookRow {
+    /// Livro como lido das colunas da tabela books
+    fn to_dto(&self) -> GoogleBookDto {
+        GoogleBookDto {
+            google_id: self.google_id.clone().unwrap_or_default(),
+            title: self.title.clone(),
+            authors: Some(self.author.clone()),
+            publisher: self.publisher.clone(),
+            published_date: self.published_date.map(|d| d.to_string()),
+            description: Some(self.description.clone()),
+            image_url: Some(self.image_url.clone()),
+            page_count: self.page_count,
+        }
+    }
+
+    fn with_id(&self) -> BookWithId {
+        BookWithId {
+            id: self.id,
+            book: self.to_dto(),
+        }
+    }
+}
+
+#[derive(Clone)]
+struct WantedRow {
+    book_id: Uuid,
+    user_id: Uuid,
+    priority: i16,
+}
+
+#[derive(Clone)]
+struct TwoFactorRow {
+    secret: String,
+    enabled: bool,
+    last_used_step: Option<i64>,
+}
+
+#[derive(Clone)]
+struct RecoveryCodeRow {
+    user_id: Uuid,
+    code_hash: String,
+    used: bool,
+}
+
+#[derive(Clone)]
+struct IdentityRow {
+    provider: String,
+    subject: String,
+    user_id: Uuid,
+}
+
+/// Tabelas do banco em memória; a ordem dos vetores é a ordem de inclusão,
+/// usada no lugar das colunas `created_at`
+#[derive(Clone, Default)]
+struct Tables {
+    users: Vec<UserRow>,
+    books: Vec<BookRow>,
+    books_offered: Vec<BookOffered>,
+    books_wanted: Vec<WantedRow>,
+    photos: Vec<BookPhotoRecord>,
+    trades: Vec<Trade>,
+    /// Exemplar reservado e a troca em andamento que o reservou
+    reservations: HashMap<Uuid, Uuid>,
+    two_factor: HashMap<Uuid, TwoFactorRow>,
+    recovery_codes: Vec<RecoveryCodeRow>,
+    identities: Vec<IdentityRow>,
+    login_states: HashMap<String, (OAuthLoginState, Instant)>,
+}
+
+impl Tables {
+    fn user(&self, user_id: &Uuid) -> Option<&UserRow> {
+        self.users.iter().find(|row| row.user.id == *user_id)
+    }
+
+    fn book(&self, book_id: &Uuid) -> Option<&BookRow> {
+        self.books.iter().find(|row| row.id == *book_id)
+    }
+
+    fn copy(&self, copy_id: &Uuid) -> Option<&BookOffered> {
+        self.books_offered.iter().find(|copy| copy.id == *copy_id)
+    }
+
+    fn is_offered(&self, book_id: &Uuid, user_id: &Uuid) -> bool {
+        self.books_offered
+            .iter()
+            .any(|copy| copy.book_id == *book_id && copy.user_id == *user_id)
+    }
+
+    fn wanted(&self, book_id: &Uuid, user_id: &Uuid) -> Option<&WantedRow> {
+        self.books_wanted
+            .iter()
+            .find(|row| row.book_id == *book_id && row.user_id == *user_id)
+    }
+
+    fn create_user(&mut self, name: &str, email: &str, hash_password: String) -> Result<User, AppError> {
+        if self.users.iter().any(|row| row.user.email == email) {
+            return Err(AppError::Conflict(
+                ErrorCode::EmailAlreadyInUse,
+                messages::EMAIL_ALREADY_IN_USE.text(),
+            ));
+        }
+
+        let created_at = now();
+        let user = User {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            email: email.to_string(),
+            hash_password,
+            created_at,
+            updated_at: created_at,
+            banned_at: None,
+        };
+        self.users.push(UserRow {
+            user: user.clone(),
+            location: None,
+        });
+
+        Ok(user)
+    }
+
+    /// Equivalente em memória do `insert_copies` do Postgres
+    fn insert_copies(
+        &mut self,
+        book_offered: &CreateBookOfferedDto,
+        quantity: i32,
+    ) -> Result<Vec<BookOffered>, AppError> {
+        if self.wanted(&book_offered.book_id, &book_offered.user_id).is_some() {
+            return Err(AppError::Conflict(
+                ErrorCode::BookAlreadyWanted,
+                messages::BOOK_ALREADY_WANTED.text(),
+            ));
+        }
+
+        if quantity < 1 {
+            return Ok(Vec::new());
+        }
+        if self.book(&book_offered.book_id).is_none() {
+            return Err(AppError::ValidationError(
+                messages::BOOK_ID_NOT_FOUND.format(&[&book_offered.book_id]),
+            ));
+        }
+        if self.user(&book_offered.user_id).is_none() {
+            return Err(AppError::ValidationError(
+                messages::USER_ID_NOT_FOUND.format(&[&book_offered.user_id]),
+            ));
+        }
+
+        let copies: Vec<BookOffered> = (0..quantity)
+            .map(|_| BookOffered {
+                id: Uuid::new_v4(),
+                book_id: book_offered.book_id,
+                user_id: book_offered.user_id,
+                attributes: book_offered.attributes.clone(),
+            })
+            .collect();
+        self.books_offered.extend(copies.iter().cloned());
+
+        Ok(copies)
+    }
+
+    /// Equivalente em memória do `remove_wanted` do Postgres
+    fn remove_wanted(&mut self, book_id: &Uuid, user_id: &Uuid) -> bool {
+        let before = self.books_wanted.len();
+        self.books_wanted
+            .retain(|row| !(row.book_id == *book_id && row.user_id == *user_id));
+
+        self.books_wanted.len() < before
+    }
+
+    /// Remove os exemplares selecionados, com as fotos deles, e desvincula as
+    /// trocas que os referenciam; exemplares reservados impedem a remoção
+    fn delete_copies(&mut self, selected: impl Fn(&BookOffered) -> bool, reserved_message: Message) -> Result<u64, AppError> {
+        let ids: Vec<Uuid> = self
+            .books_offered
+            .iter()
+            .filter(|copy| selected(copy))
+            .map(|copy| copy.id)
+            .collect();
+
+        if ids.iter().any(|id| self.reservations.contains_key(id)) {
+            return Err(AppError::Conflict(ErrorCode::CopyReserved, reserved_message.text()));
+        }
+
+        self.books_offered.retain(|copy| !ids.contains(&copy.id));
+        self.photos.retain(|photo| !ids.contains(&photo.copy_id));
+        for trade in &mut self.trades {
+            if trade.offered_copy_id.is_some_and(|id| ids.contains(&id)) {
+                trade.offered_copy_id = None;
+            }
+            if trade.wanted_copy_id.is_some_and(|id| ids.contains(&id)) {
+                trade.wanted_copy_id = None;
+            }
+        }
+
+        Ok(ids.len() as u64)
+    }
+}
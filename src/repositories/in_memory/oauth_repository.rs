@@ -0,0 +1,113 @@
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::models::oauth::{ExternalIdentity, OAuthLoginState};
+use crate::models::user::User;
+use crate::repositories::in_memory::{IdentityRow, InMemoryDatabase};
+use crate::repositories::oauth_repository::OAuthRepository;
+
+// Idade a partir da qual autorizações abandonadas são descartadas
+const ABANDONED_LOGIN_STATE_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+pub struct InMemoryOAuthRepository {
+    db: InMemoryDatabase,
+}
+
+impl InMemoryOAuthRepository {
+    pub fn new(db: InMemoryDatabase) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl OAuthRepository for InMemoryOAuthRepository {
+    async fn save_login_state(&self, login_state: &OAuthLoginState) -> Result<(), AppError> {
+        self.db.write(|tables| {
+            tables
+                .login_states
+                .retain(|_, (_, created_at)| created_at.elapsed() < ABANDONED_LOGIN_STATE_AGE);
+            tables
+                .login_states
+                .insert(login_state.state.clone(), (login_state.clone(), Instant::now()));
+            Ok(())
+        })
+    }
+
+    async fn take_login_state(&self, state: &str, max_age: Duration) -> Result<Option<OAuthLoginState>, AppError> {
+        self.db.write(|tables| {
+            let taken = tables.login_states.remove(state);
+            Ok(taken
+                .filter(|(_, created_at)| created_at.elapsed() < max_age)
+                .map(|(login_state, _)| login_state))
+        })
+    }
+
+    async fn find_user_by_identity(&self, provider: &str, subject: &str) -> Result<Option<User>, AppError> {
+        self.db.read(|tables| {
+            let identity = tables
+                .identities
+                .iter()
+                .find(|identity| identity.provider == provider && identity.subject == subject)?;
+            tables.user(&identity.user_id).map(|row| row.user.clone())
+        })
+    }
+
+    async fn find_user_by_email(&self, email: &str) -> Result<Option<User>, AppError> {
+        let email = email.to_lowercase();
+        self.db.read(|tables| {
+            tables
+                .users
+                .iter()
+                .find(|row| row.user.email.to_lowercase() == email)
+                .map(|row| row.user.clone())
+        })
+    }
+
+    async fn link_identity(&self, user_id: &Uuid, identity: &ExternalIdentity) -> Result<(), AppError> {
+        self.db.write(|tables| {
+            let linked = tables
+                .identities
+                .iter()
+                .any(|row| row.provider == identity.provider && row.subject == identity.subject);
+            if !linked {
+                tables.identities.push(IdentityRow {
+                    provider: identity.provider.clone(),
+                    subject: identity.subject.clone(),
+                    user_id: *user_id,
+                });
+            }
+            Ok(())
+        })
+    }
+
+    async fn create_user_with_identity(
+        &self,
+        name: &str,
+        hash_password: String,
+        identity: &ExternalIdentity,
+    ) -> Result<User, AppError> {
+        self.db.write(|tables| {
+            if tables
+                .identities
+                .iter()
+                .any(|row| row.provider == identity.provider && row.subject == identity.subject)
+            {
+                return Err(AppError::DatabaseError(format!(
+                    "A identidade {}:{} já está vinculada",
+                    identity.provider, identity.subject
+                )));
+            }
+
+            let user = tables.create_user(name, &identity.email, hash_password)?;
+            tables.identities.push(IdentityRow {
+                provider: identity.provider.clone(),
+                subject: identity.subject.clone(),
+                user_id: user.id,
+            });
+            Ok(user)
+        })
+    }
+}
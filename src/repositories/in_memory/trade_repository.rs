@@ -0,0 +1,281 @@
+use std::collections::HashSet;
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::error::{AppError, ErrorCode};
+use crate::i18n::messages;
+use crate::models::book::{BookOffered, BookPhoto, CreateBookOfferedDto, OfferedCopy, OfferedCopyAttributes};
+use crate::models::trade::{CreateTradeDto, PossibleTrade, Trade, TradeScoreFactors, TradeStatus};
+use crate::models::user::UserResponse;
+use crate::repositories::in_memory::{now, InMemoryDatabase, Tables};
+use crate::repositories::trade_repository::TradeRepository;
+
+pub struct InMemoryTradeRepository {
+    db: InMemoryDatabase,
+}
+
+impl InMemoryTradeRepository {
+    pub fn new(db: InMemoryDatabase) -> Self {
+        Self { db }
+    }
+}
+
+impl Tables {
+    fn trade_mut(&mut self, id: &Uuid) -> Option<&mut Trade> {
+        self.trades.iter_mut().find(|trade| trade.id == *id)
+    }
+
+    // Exemplar que pode entrar em uma sugestão: disponível e não prometido
+    fn is_tradable(&self, copy: &BookOffered) -> bool {
+        copy.attributes.available && !self.reservations.contains_key(&copy.id)
+    }
+
+    fn completed_trades(&self, user_id: &Uuid) -> i64 {
+        self.trades
+            .iter()
+            .filter(|trade| trade.status == TradeStatus::Completed)
+            .filter(|trade| trade.proposer_id == *user_id || trade.partner_id == *user_id)
+            .count() as i64
+    }
+
+    fn offered_copy(&self, copy: &BookOffered) -> OfferedCopy {
+        OfferedCopy {
+            id: copy.id,
+            attributes: OfferedCopyAttributes {
+                available: true,
+                ..copy.attributes.clone()
+            },
+            photos: self
+                .photos
+                .iter()
+                .filter(|photo| photo.copy_id == copy.id)
+                .cloned()
+                .map(BookPhoto::from)
+                .collect(),
+        }
+    }
+
+    fn release_reservations(&mut self, trade_id: &Uuid) {
+        self.reservations.retain(|_, reserved_by| reserved_by != trade_id);
+    }
+
+    // Equivalente em memória do `move_received_book` do Postgres
+    fn move_received_book(
+        &mut self,
+        book_id: Uuid,
+        receiver_id: Uuid,
+        delivered_copy_id: Option<Uuid>,
+    ) -> Result<(), AppError> {
+        let attributes = match delivered_copy_id.and_then(|id| self.copy(&id)) {
+            Some(copy) => OfferedCopyAttributes {
+                condition: copy.attributes.condition,
+                language: copy.attributes.language.clone(),
+                ..OfferedCopyAttributes::default()
+            },
+            None => OfferedCopyAttributes::default(),
+        };
+
+        self.remove_wanted(&book_id, &receiver_id);
+        self.insert_copies(
+            &CreateBookOfferedDto {
+                book_id,
+                user_id: receiver_id,
+                attributes,
+            },
+            1,
+        )?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TradeRepository for InMemoryTradeRepository {
+    async fn find_possible_trades(&self, user_id: Uuid) -> Result<Vec<PossibleTrade>, AppError> {
+        let mut trades = self.db.read(|tables| {
+            let Some(me) = tables.user(&user_id) else {
+                return Vec::new();
+            };
+
+            let mut found = Vec::new();
+            for partner_wants in tables.books_wanted.iter().filter(|row| row.user_id != user_id) {
+                // Exemplar mais antigo do usuário entre os que o parceiro quer
+                let Some(my_copy) = tables.books_offered.iter().find(|copy| {
+                    copy.user_id == user_id && copy.book_id == partner_wants.book_id && tables.is_tradable(copy)
+                }) else {
+                    continue;
+                };
+                let (Some(partner), Some(offered_book)) =
+                    (tables.user(&partner_wants.user_id), tables.book(&my_copy.book_id))
+                else {
+                    continue;
+                };
+
+                // Exemplar mais antigo do parceiro de cada livro que o usuário quer
+                let mut seen_books = HashSet::new();
+                for partner_copy in tables.books_offered.iter().filter(|copy| {
+                    copy.user_id == partner.user.id && tables.is_tradable(copy)
+                }) {
+                    if !seen_books.insert(partner_copy.book_id) {
+                        continue;
+                    }
+                    let (Some(my_wants), Some(wanted_book)) = (
+                        tables.wanted(&partner_copy.book_id, &user_id),
+                        tables.book(&partner_copy.book_id),
+                    ) else {
+                        continue;
+                    };
+
+                    let factors = TradeScoreFactors {
+                        my_priority: my_wants.priority,
+                        partner_priority: partner_wants.priority,
+                        partner_completed_trades: tables.completed_trades(&partner.user.id),
+                        distance_km: me
+                            .location
+                            .zip(partner.location)
+                            .map(|(mine, theirs)| mine.distance_km(&theirs)),
+                    };
+
+                    let key = (
+                        partner.user.name.clone(),
+                        partner.user.id,
+                        offered_book.title.clone(),
+                        offered_book.id,
+                        wanted_book.title.clone(),
+                        wanted_book.id,
+                    );
+                    found.push((
+                        key,
+                        PossibleTrade {
+                            offered_book: offered_book.to_dto(),
+                            offered_book_id: offered_book.id,
+                            wanted_book: wanted_book.to_dto(),
+                            wanted_book_id: wanted_book.id,
+                            trade_partner: UserResponse::from(partner.user.clone()),
+                            offered_copy: tables.offered_copy(my_copy),
+                            wanted_copy: tables.offered_copy(partner_copy),
+                            score: factors.score(),
+                        },
+                    ));
+                }
+            }
+
+            // Mesma ordem da consulta do Postgres
+            found.sort_by(|(a, _), (b, _)| a.cmp(b));
+            found.into_iter().map(|(_, trade)| trade).collect::<Vec<_>>()
+        })?;
+
+        // As sugestões mais relevantes primeiro; empates mantêm a ordem da consulta
+        trades.sort_by(|a, b| b.score.total_cmp(&a.score));
+
+        Ok(trades)
+    }
+
+    async fn create(&self, trade: &CreateTradeDto) -> Result<Trade, AppError> {
+        self.db.write(|tables| {
+            let created_at = now();
+            let created = Trade {
+                id: Uuid::new_v4(),
+                proposer_id: trade.proposer_id,
+                partner_id: trade.partner_id,
+                offered_book_id: trade.offered_book_id,
+                offered_copy_id: Some(trade.offered_copy_id),
+                wanted_book_id: trade.wanted_book_id,
+                wanted_copy_id: Some(trade.wanted_copy_id),
+                status: TradeStatus::Pending,
+                created_at,
+                updated_at: created_at,
+            };
+
+            for copy_id in [trade.offered_copy_id, trade.wanted_copy_id] {
+                if tables.reservations.contains_key(&copy_id) {
+                    return Err(AppError::Conflict(
+                        ErrorCode::CopyReserved,
+                        messages::COPY_ALREADY_PROMISED.text(),
+                    ));
+                }
+                if tables.copy(&copy_id).is_none() {
+                    return Err(AppError::NotFoundError(messages::COPY_NOT_FOUND.text()));
+                }
+                tables.reservations.insert(copy_id, created.id);
+            }
+            tables.trades.push(created.clone());
+
+            Ok(created)
+        })
+    }
+
+    async fn find_by_id(&self, id: &Uuid) -> Result<Option<Trade>, AppError> {
+        self.db
+            .read(|tables| tables.trades.iter().find(|trade| trade.id == *id).cloned())
+    }
+
+    async fn find_by_user_id(&self, user_id: &Uuid) -> Result<Vec<Trade>, AppError> {
+        self.db.read(|tables| {
+            tables
+                .trades
+                .iter()
+                .rev()
+                .filter(|trade| trade.proposer_id == *user_id || trade.partner_id == *user_id)
+                .cloned()
+                .collect()
+        })
+    }
+
+    async fn update_status(
+        &self,
+        id: &Uuid,
+        from: TradeStatus,
+        to: TradeStatus,
+    ) -> Result<Option<Trade>, AppError> {
+        self.db.write(|tables| {
+            let Some(trade) = tables.trade_mut(id).filter(|trade| trade.status == from) else {
+                return Ok(None);
+            };
+            trade.status = to;
+            trade.updated_at = now();
+            let updated = trade.clone();
+
+            if !to.is_open() {
+                tables.release_reservations(id);
+            }
+
+            Ok(Some(updated))
+        })
+    }
+
+    async fn complete(&self, id: &Uuid) -> Result<Option<Trade>, AppError> {
+        self.db.write(|tables| {
+            let Some(trade) = tables
+                .trades
+                .iter()
+                .find(|trade| trade.id == *id && trade.status == TradeStatus::Accepted)
+                .cloned()
+            else {
+                return Ok(None);
+            };
+
+            tables.release_reservations(id);
+
+            // Cada parte passa a possuir o livro recebido, que sai da sua lista de desejados
+            tables.move_received_book(trade.wanted_book_id, trade.proposer_id, trade.wanted_copy_id)?;
+            tables.move_received_book(trade.offered_book_id, trade.partner_id, trade.offered_copy_id)?;
+
+            // Os exemplares entregues deixam a lista de possuídos dos donos
+            let delivered: Vec<Uuid> = [trade.offered_copy_id, trade.wanted_copy_id]
+                .into_iter()
+                .flatten()
+                .collect();
+            tables.delete_copies(|copy| delivered.contains(&copy.id), messages::COPY_RESERVED)?;
+
+            let Some(completed) = tables.trade_mut(id) else {
+                return Ok(None);
+            };
+            completed.status = TradeStatus::Completed;
+            completed.updated_at = now();
+
+            Ok(Some(completed.clone()))
+        })
+    }
+}
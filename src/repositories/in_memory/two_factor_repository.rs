@@ -0,0 +1,99 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::models::two_factor::TwoFactorSecret;
+use crate::repositories::in_memory::{InMemoryDatabase, RecoveryCodeRow, TwoFactorRow};
+use crate::repositories::two_factor_repository::TwoFactorRepository;
+
+pub struct InMemoryTwoFactorRepository {
+    db: InMemoryDatabase,
+}
+
+impl InMemoryTwoFactorRepository {
+    pub fn new(db: InMemoryDatabase) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl TwoFactorRepository for InMemoryTwoFactorRepository {
+    async fn save_pending_secret(&self, user_id: &Uuid, secret: &str) -> Result<bool, AppError> {
+        self.db.write(|tables| {
+            if tables.two_factor.get(user_id).is_some_and(|row| row.enabled) {
+                return Ok(false);
+            }
+
+            tables.two_factor.insert(
+                *user_id,
+                TwoFactorRow {
+                    secret: secret.to_string(),
+                    enabled: false,
+                    last_used_step: None,
+                },
+            );
+            Ok(true)
+        })
+    }
+
+    async fn find(&self, user_id: &Uuid) -> Result<Option<TwoFactorSecret>, AppError> {
+        self.db.read(|tables| {
+            tables.two_factor.get(user_id).map(|row| TwoFactorSecret {
+                secret: row.secret.clone(),
+                enabled: row.enabled,
+            })
+        })
+    }
+
+    async fn enable(&self, user_id: &Uuid, used_step: i64, recovery_code_hashes: &[String]) -> Result<(), AppError> {
+        self.db.write(|tables| {
+            if let Some(row) = tables.two_factor.get_mut(user_id) {
+                row.enabled = true;
+                row.last_used_step = Some(used_step);
+            }
+
+            tables.recovery_codes.retain(|code| code.user_id != *user_id);
+            tables
+                .recovery_codes
+                .extend(recovery_code_hashes.iter().map(|code_hash| RecoveryCodeRow {
+                    user_id: *user_id,
+                    code_hash: code_hash.clone(),
+                    used: false,
+                }));
+            Ok(())
+        })
+    }
+
+    async fn use_step(&self, user_id: &Uuid, step: i64) -> Result<bool, AppError> {
+        self.db.write(|tables| {
+            let Some(row) = tables.two_factor.get_mut(user_id) else {
+                return Ok(false);
+            };
+            if row.last_used_step.is_some_and(|last| last >= step) {
+                return Ok(false);
+            }
+
+            row.last_used_step = Some(step);
+            Ok(true)
+        })
+    }
+
+    async fn use_recovery_code(&self, user_id: &Uuid, code_hash: &str) -> Result<bool, AppError> {
+        self.db.write(|tables| {
+            let code = tables
+                .recovery_codes
+                .iter_mut()
+                .find(|code| code.user_id == *user_id && code.code_hash == code_hash && !code.used);
+
+            Ok(code.map(|code| code.used = true).is_some())
+        })
+    }
+
+    async fn delete(&self, user_id: &Uuid) -> Result<(), AppError> {
+        self.db.write(|tables| {
+            tables.recovery_codes.retain(|code| code.user_id != *user_id);
+            tables.two_factor.remove(user_id);
+            Ok(())
+        })
+    }
+}
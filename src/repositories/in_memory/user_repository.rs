@@ -0,0 +1,78 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::models::user::{CreateUserDto, User, UserLocation};
+use crate::repositories::in_memory::{now, InMemoryDatabase};
+use crate::repositories::user_repository::UserRepository;
+
+pub struct InMemoryUserRepository {
+    db: InMemoryDatabase,
+}
+
+impl InMemoryUserRepository {
+    pub fn new(db: InMemoryDatabase) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl UserRepository for InMemoryUserRepository {
+    async fn create(&self, user: &CreateUserDto, hash_password: String) -> Result<User, AppError> {
+        self.db
+            .write(|tables| tables.create_user(&user.name, &user.email, hash_password))
+    }
+
+    async fn find_by_email(&self, email: &str) -> Result<Option<User>, AppError> {
+        self.db.read(|tables| {
+            tables
+                .users
+                .iter()
+                .find(|row| row.user.email == email)
+                .map(|row| row.user.clone())
+        })
+    }
+
+    async fn find_by_id(&self, user_id: &Uuid) -> Result<Option<User>, AppError> {
+        self.db
+            .read(|tables| tables.user(user_id).map(|row| row.user.clone()))
+    }
+
+    async fn update_location(&self, user_id: &Uuid, location: Option<UserLocation>) -> Result<bool, AppError> {
+        self.db.write(|tables| {
+            let Some(row) = tables.users.iter_mut().find(|row| row.user.id == *user_id) else {
+                return Ok(false);
+            };
+            row.location = location;
+            row.user.updated_at = now();
+            Ok(true)
+        })
+    }
+
+    async fn update_password(&self, user_id: &Uuid, hash_password: String) -> Result<bool, AppError> {
+        self.db.write(|tables| {
+            let Some(row) = tables.users.iter_mut().find(|row| row.user.id == *user_id) else {
+                return Ok(false);
+            };
+            row.user.hash_password = hash_password;
+            row.user.updated_at = now();
+            Ok(true)
+        })
+    }
+
+    async fn two_factor_enabled(&self, user_id: &Uuid) -> Result<bool, AppError> {
+        self.db
+            .read(|tables| tables.two_factor.get(user_id).is_some_and(|row| row.enabled))
+    }
+
+    async fn set_banned(&self, user_id: &Uuid, banned: bool) -> Result<bool, AppError> {
+        self.db.write(|tables| {
+            let Some(row) = tables.users.iter_mut().find(|row| row.user.id == *user_id) else {
+                return Ok(false);
+            };
+            row.user.banned_at = if banned { row.user.banned_at.or(Some(now())) } else { None };
+            row.user.updated_at = now();
+            Ok(true)
+        })
+    }
+}
@@ -1,8 +1,15 @@
+pub mod book_photo_repository;
 pub mod book_repository;
 pub mod user_repository;
 pub mod books_offered_repository;
 pub mod books_wanted_repository;
 pub mod trade_repository;
+pub mod rate_limit_repository;
+pub mod oauth_repository;
+pub mod two_factor_repository;
+pub mod db_error;
+pub mod unit_of_work;
+pub mod in_memory;
 #[cfg(test)]
 pub mod user_repository_test;
 
@@ -16,27 +23,36 @@ pub mod books_offered_repository_test;
 pub mod books_wanted_repository_test;
 #[cfg(test)]
 pub mod trade_repository_test;
+
+#[cfg(test)]
+pub mod rate_limit_repository_test;
+
+#[cfg(test)]
+pub mod oauth_repository_test;
+
+#[cfg(test)]
+pub mod two_factor_repository_test;
+#[cfg(test)]
+pub mod contract_test;
+#[cfg(test)]
+pub mod test_database;
 #[cfg(test)]
 pub mod test_helpers {
     use dotenv::dotenv;
     use sqlx::PgPool;
+    use std::cell::RefCell;
     use std::env;
-    use std::sync::Arc;
-    use tokio::sync::Mutex;
 
-    // Mutex para garantir que apenas um teste por vez acesse o banco
-    static TEST_MUTEX: tokio::sync::OnceCell<Arc<Mutex<()>>> = tokio::sync::OnceCell::const_new();
+    use crate::repositories::test_database::TestDatabase;
 
-    // Retorna um mutex para garantir a execução sequencial dos testes
-    pub async fn get_test_mutex() -> Arc<Mutex<()>> {
-        TEST_MUTEX
-            .get_or_init(|| async { Arc::new(Mutex::new(())) })
-            .await
-            .clone()
+    thread_local! {
+        // Banco do teste em execução: cada teste roda na própria thread, e o
+        // schema é removido quando ela termina
+        static TEST_DATABASE: RefCell<Option<TestDatabase>> = const { RefCell::new(None) };
     }
 
-    // Configura e retorna um pool de conexão com o banco de dados de teste
-    pub async fn get_test_db_pool() -> PgPool {
+    // URL do banco de teste, montada a partir das variáveis de ambiente
+    fn test_database_url() -> String {
         // Carrega variáveis de ambiente
         dotenv().ok();
 
@@ -48,49 +64,26 @@ pub mod test_helpers {
         let db_port =
             env::var("POSTGRES_TEST_PORT").expect("POSTGRES_TEST_PORT deve estar definido");
 
-        // Constrói a string de conexão para o banco de dados de teste
-        let connection_string = format!(
+        format!(
             "postgres://{}:{}@localhost:{}/{}",
             db_user, db_password, db_port, db_name
-        );
-
-        // Conecta ao banco de dados de teste
-        let pool = PgPool::connect(&connection_string)
-            .await
-            .expect("Falha ao conectar ao banco de teste");
-
-        clean_database(&pool).await;
-
-        pool
+        )
     }
 
-    // Limpa todas as tabelas do banco de teste para garantir um estado inicial conhecido
-    pub async fn clean_database(pool: &PgPool) {
-        // Limpa todas as tabelas que possam afetar o teste
-        sqlx::query("TRUNCATE TABLE users CASCADE")
-            .execute(pool)
-            .await
-            .expect("Falha ao limpar a tabela users");
-
-        // Limpa outras tabelas relacionadas
-        sqlx::query("TRUNCATE TABLE books_wanted CASCADE")
-            .execute(pool)
-            .await
-            .expect("Falha ao limpar a tabela books_wanted");
-
-        sqlx::query("TRUNCATE TABLE books_offered CASCADE")
-            .execute(pool)
-            .await
-            .expect("Falha ao limpar a tabela books_offered");
+    // Retorna o pool do schema isolado do teste atual, criando-o na primeira
+    // chamada; as chamadas seguintes no mesmo teste enxergam os mesmos dados
+    pub async fn get_test_db_pool() -> PgPool {
+        let current = TEST_DATABASE.with(|database| {
+            database.borrow().as_ref().map(|database| database.pool().clone())
+        });
+        if let Some(pool) = current {
+            return pool;
+        }
 
-        sqlx::query("TRUNCATE TABLE trades CASCADE")
-            .execute(pool)
-            .await
-            .expect("Falha ao limpar a tabela trades");
+        let database = TestDatabase::create(&test_database_url()).await;
+        let pool = database.pool().clone();
+        TEST_DATABASE.with(|current| *current.borrow_mut() = Some(database));
 
-        sqlx::query("TRUNCATE TABLE books CASCADE")
-            .execute(pool)
-            .await
-            .expect("Falha ao limpar a tabela books");
+        pool
     }
 }
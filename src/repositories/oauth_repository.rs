@@ -0,0 +1,174 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::{AppError, ErrorCode};
+use crate::i18n::messages;
+use crate::models::oauth::{ExternalIdentity, OAuthLoginState};
+use crate::models::user::User;
+use crate::repositories::db_error::{constraint_violation, ConstraintKind};
+
+#[async_trait]
+pub trait OAuthRepository: Send + Sync + 'static {
+    async fn save_login_state(&self, login_state: &OAuthLoginState) -> Result<(), AppError>;
+    /// Remove e retorna a autorização, se ela existir e tiver no máximo `max_age`
+    async fn take_login_state(&self, state: &str, max_age: Duration) -> Result<Option<OAuthLoginState>, AppError>;
+    /// Usuário vinculado à conta do provedor
+    async fn find_user_by_identity(&self, provider: &str, subject: &str) -> Result<Option<User>, AppError>;
+    /// Usuário com o email, sem diferenciar maiúsculas
+    async fn find_user_by_email(&self, email: &str) -> Result<Option<User>, AppError>;
+    async fn link_identity(&self, user_id: &Uuid, identity: &ExternalIdentity) -> Result<(), AppError>;
+    /// Cria o usuário e o vínculo com a conta do provedor na mesma transação
+    async fn create_user_with_identity(
+        &self,
+        name: &str,
+        hash_password: String,
+        identity: &ExternalIdentity,
+    ) -> Result<User, AppError>;
+}
+
+pub struct PgOAuthRepository {
+    pool: PgPool,
+}
+
+impl PgOAuthRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl OAuthRepository for PgOAuthRepository {
+    #[tracing::instrument(name = "oauth_repository.save_login_state", level = "debug", skip_all)]
+    async fn save_login_state(&self, login_state: &OAuthLoginState) -> Result<(), AppError> {
+        // Autorizações abandonadas são descartadas junto com as novas
+        sqlx::query("DELETE FROM oauth_login_states WHERE created_at < now() - interval '1 day'")
+            .execute(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+
+        sqlx::query(
+            "INSERT INTO oauth_login_states (state, provider, code_verifier, nonce) VALUES ($1, $2, $3, $4)",
+        )
+        .bind(&login_state.state)
+        .bind(&login_state.provider)
+        .bind(&login_state.code_verifier)
+        .bind(&login_state.nonce)
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "oauth_repository.take_login_state", level = "debug", skip_all)]
+    async fn take_login_state(&self, state: &str, max_age: Duration) -> Result<Option<OAuthLoginState>, AppError> {
+        // O DELETE garante que cada state seja usado uma única vez, mesmo com
+        // retornos simultâneos; um state expirado também é removido
+        let login_state = sqlx::query_as::<_, OAuthLoginState>(
+            r#"
+            WITH taken AS (
+                DELETE FROM oauth_login_states WHERE state = $1 RETURNING *
+            )
+            SELECT state, provider, code_verifier, nonce FROM taken
+            WHERE created_at > now() - make_interval(secs => $2)
+            "#,
+        )
+        .bind(state)
+        .bind(max_age.as_secs_f64())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(login_state)
+    }
+
+    #[tracing::instrument(name = "oauth_repository.find_user_by_identity", level = "debug", skip_all)]
+    async fn find_user_by_identity(&self, provider: &str, subject: &str) -> Result<Option<User>, AppError> {
+        let user = sqlx::query_as::<_, User>(
+            r#"
+            SELECT u.* FROM users u
+            JOIN user_identities i ON i.user_id = u.id
+            WHERE i.provider = $1 AND i.subject = $2
+            "#,
+        )
+        .bind(provider)
+        .bind(subject)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(user)
+    }
+
+    #[tracing::instrument(name = "oauth_repository.find_user_by_email", level = "debug", skip_all)]
+    async fn find_user_by_email(&self, email: &str) -> Result<Option<User>, AppError> {
+        let user = sqlx::query_as::<_, User>("SELECT * FROM users WHERE lower(email) = lower($1)")
+            .bind(email)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+
+        Ok(user)
+    }
+
+    #[tracing::instrument(name = "oauth_repository.link_identity", level = "debug", skip_all)]
+    async fn link_identity(&self, user_id: &Uuid, identity: &ExternalIdentity) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            INSERT INTO user_identities (provider, subject, user_id, email)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (provider, subject) DO NOTHING
+            "#,
+        )
+        .bind(&identity.provider)
+        .bind(&identity.subject)
+        .bind(user_id)
+        .bind(&identity.email)
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "oauth_repository.create_user_with_identity", level = "debug", skip_all)]
+    async fn create_user_with_identity(
+        &self,
+        name: &str,
+        hash_password: String,
+        identity: &ExternalIdentity,
+    ) -> Result<User, AppError> {
+        let mut tx = self.pool.begin().await.map_err(AppError::from)?;
+
+        let user = sqlx::query_as::<_, User>(
+            "INSERT INTO users (name, email, hash_password) VALUES ($1, $2, $3) RETURNING *",
+        )
+        .bind(name)
+        .bind(&identity.email)
+        .bind(&hash_password)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| match constraint_violation(&e) {
+            Some((ConstraintKind::Unique, _)) => {
+                AppError::Conflict(ErrorCode::EmailAlreadyInUse, messages::EMAIL_ALREADY_IN_USE.text())
+            }
+            _ => AppError::DatabaseError(e.to_string()),
+        })?;
+
+        sqlx::query("INSERT INTO user_identities (provider, subject, user_id, email) VALUES ($1, $2, $3, $4)")
+            .bind(&identity.provider)
+            .bind(&identity.subject)
+            .bind(user.id)
+            .bind(&identity.email)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::from)?;
+
+        tx.commit().await.map_err(AppError::from)?;
+
+        Ok(user)
+    }
+}
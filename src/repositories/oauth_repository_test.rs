@@ -0,0 +1,127 @@
+use std::time::Duration;
+
+use crate::error::AppError;
+use crate::models::oauth::{ExternalIdentity, OAuthLoginState};
+use crate::models::user::CreateUserDto;
+use crate::repositories::{
+    oauth_repository::{OAuthRepository, PgOAuthRepository},
+    test_helpers::get_test_db_pool,
+    user_repository::{PgUserRepository, UserRepository},
+};
+
+fn login_state(state: &str) -> OAuthLoginState {
+    OAuthLoginState {
+        state: state.to_string(),
+        provider: "google".to_string(),
+        code_verifier: "verificador".to_string(),
+        nonce: "nonce".to_string(),
+    }
+}
+
+fn identity(subject: &str, email: &str) -> ExternalIdentity {
+    ExternalIdentity {
+        provider: "google".to_string(),
+        subject: subject.to_string(),
+        email: email.to_string(),
+        email_verified: true,
+        name: Some("Usuária Externa".to_string()),
+    }
+}
+
+#[tokio::test]
+async fn test_login_state_is_taken_only_once() {
+    let repository = PgOAuthRepository::new(get_test_db_pool().await);
+    repository.save_login_state(&login_state("state-1")).await.unwrap();
+
+    let taken = repository
+        .take_login_state("state-1", Duration::from_secs(600))
+        .await
+        .unwrap();
+    assert_eq!(taken, Some(login_state("state-1")));
+
+    let again = repository
+        .take_login_state("state-1", Duration::from_secs(600))
+        .await
+        .unwrap();
+    assert!(again.is_none());
+
+    assert!(repository
+        .take_login_state("inexistente", Duration::from_secs(600))
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn test_expired_login_state_is_rejected_and_removed() {
+    let pool = get_test_db_pool().await;
+    let repository = PgOAuthRepository::new(pool.clone());
+    repository.save_login_state(&login_state("state-antigo")).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let taken = repository
+        .take_login_state("state-antigo", Duration::from_millis(10))
+        .await
+        .unwrap();
+    assert!(taken.is_none());
+
+    let (remaining,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM oauth_login_states")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(remaining, 0);
+}
+
+#[tokio::test]
+async fn test_link_identity_to_existing_user() {
+    let pool = get_test_db_pool().await;
+    let repository = PgOAuthRepository::new(pool.clone());
+    let user = PgUserRepository::new(pool)
+        .create(
+            &CreateUserDto {
+                name: "Usuária".to_string(),
+                email: "Usuaria@Example.com".to_string(),
+                password: "senha123".to_string(),
+            },
+            "hash".to_string(),
+        )
+        .await
+        .unwrap();
+
+    // O email é comparado sem diferenciar maiúsculas
+    let found = repository.find_user_by_email("usuaria@example.com").await.unwrap().unwrap();
+    assert_eq!(found.id, user.id);
+
+    let identity = identity("google-123", "usuaria@example.com");
+    assert!(repository.find_user_by_identity("google", "google-123").await.unwrap().is_none());
+
+    repository.link_identity(&user.id, &identity).await.unwrap();
+    // Vincular de novo a mesma conta não é um erro
+    repository.link_identity(&user.id, &identity).await.unwrap();
+
+    let linked = repository.find_user_by_identity("google", "google-123").await.unwrap().unwrap();
+    assert_eq!(linked.id, user.id);
+    assert!(repository.find_user_by_identity("github", "google-123").await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_create_user_with_identity() {
+    let repository = PgOAuthRepository::new(get_test_db_pool().await);
+
+    let user = repository
+        .create_user_with_identity("Nova Usuária", "hash".to_string(), &identity("google-456", "nova@example.com"))
+        .await
+        .unwrap();
+    assert_eq!(user.name, "Nova Usuária");
+    assert_eq!(user.email, "nova@example.com");
+
+    let linked = repository.find_user_by_identity("google", "google-456").await.unwrap().unwrap();
+    assert_eq!(linked.id, user.id);
+
+    // Email já cadastrado: nem o usuário nem o vínculo são criados
+    let result = repository
+        .create_user_with_identity("Outra", "hash".to_string(), &identity("google-789", "nova@example.com"))
+        .await;
+    assert!(matches!(result, Err(AppError::Conflict(_, _))));
+    assert!(repository.find_user_by_identity("google", "google-789").await.unwrap().is_none());
+}
@@ -0,0 +1,210 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+use crate::config::{Config, RateLimitBackend};
+use crate::error::AppError;
+
+/// Quantidade de chaves a partir da qual as janelas expiradas são descartadas
+const PRUNE_THRESHOLD: usize = 10_000;
+
+// Tentativas e fim da janela de cada chave
+type Windows = HashMap<String, (u32, Instant)>;
+
+/// Estado da janela de contagem de uma chave
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimitWindow {
+    /// Tentativas registradas na janela atual
+    pub hits: u32,
+    /// Tempo até a janela expirar
+    pub resets_in: Duration,
+}
+
+#[async_trait]
+pub trait RateLimitRepository: Send + Sync + 'static {
+    /// Registra uma tentativa, abrindo uma nova janela se a anterior expirou
+    async fn increment(&self, key: &str, window: Duration) -> Result<RateLimitWindow, AppError>;
+    /// Janela ainda válida da chave, se houver
+    async fn find(&self, key: &str) -> Result<Option<RateLimitWindow>, AppError>;
+    /// Desfaz uma tentativa da janela ainda válida da chave, se houver
+    async fn decrement(&self, key: &str) -> Result<(), AppError>;
+    async fn delete(&self, key: &str) -> Result<(), AppError>;
+}
+
+/// Contadores mantidos na memória do processo
+///
+/// Cada instância da API tem os seus próprios contadores; com mais de uma
+/// instância, use o `PgRateLimitRepository`.
+#[derive(Default)]
+pub struct InMemoryRateLimitRepository {
+    windows: Mutex<Windows>,
+}
+
+impl InMemoryRateLimitRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, Windows>, AppError> {
+        self.windows
+            .lock()
+            .map_err(|e| AppError::InternalServerError(format!("Falha ao acessar os contadores: {}", e)))
+    }
+}
+
+#[async_trait]
+impl RateLimitRepository for InMemoryRateLimitRepository {
+    async fn increment(&self, key: &str, window: Duration) -> Result<RateLimitWindow, AppError> {
+        let mut windows = self.lock()?;
+        let now = Instant::now();
+
+        if windows.len() >= PRUNE_THRESHOLD {
+            windows.retain(|_, (_, ends_at)| *ends_at > now);
+        }
+
+        let entry = windows.entry(key.to_string()).or_insert((0, now + window));
+        if entry.1 <= now {
+            *entry = (0, now + window);
+        }
+        entry.0 += 1;
+
+        Ok(RateLimitWindow {
+            hits: entry.0,
+            resets_in: entry.1 - now,
+        })
+    }
+
+    async fn find(&self, key: &str) -> Result<Option<RateLimitWindow>, AppError> {
+        let windows = self.lock()?;
+        let now = Instant::now();
+
+        Ok(windows
+            .get(key)
+            .filter(|(_, ends_at)| *ends_at > now)
+            .map(|(hits, ends_at)| RateLimitWindow {
+                hits: *hits,
+                resets_in: *ends_at - now,
+            }))
+    }
+
+    async fn decrement(&self, key: &str) -> Result<(), AppError> {
+        let mut windows = self.lock()?;
+        let now = Instant::now();
+
+        if let Some((hits, ends_at)) = windows.get_mut(key) {
+            if *ends_at > now {
+                *hits = hits.saturating_sub(1);
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), AppError> {
+        self.lock()?.remove(key);
+        Ok(())
+    }
+}
+
+/// Contadores na tabela rate_limits, compartilhados entre instâncias da API
+pub struct PgRateLimitRepository {
+    pool: PgPool,
+}
+
+impl PgRateLimitRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+// Converte a linha (tentativas, segundos restantes) retornada pelas consultas
+fn to_window((hits, resets_in): (i32, f64)) -> RateLimitWindow {
+    RateLimitWindow {
+        hits: hits.max(0) as u32,
+        resets_in: Duration::from_secs_f64(resets_in.max(0.0)),
+    }
+}
+
+#[async_trait]
+impl RateLimitRepository for PgRateLimitRepository {
+    #[tracing::instrument(name = "rate_limit_repository.increment", level = "debug", skip_all)]
+    async fn increment(&self, key: &str, window: Duration) -> Result<RateLimitWindow, AppError> {
+        // O upsert é atômico: requisições simultâneas com a mesma chave não
+        // perdem tentativas
+        let row = sqlx::query_as::<_, (i32, f64)>(
+            r#"
+            INSERT INTO rate_limits (key, hits, window_ends_at)
+            VALUES ($1, 1, now() + make_interval(secs => $2))
+            ON CONFLICT (key) DO UPDATE SET
+                hits = CASE
+                    WHEN rate_limits.window_ends_at <= now() THEN 1
+                    ELSE rate_limits.hits + 1
+                END,
+                window_ends_at = CASE
+                    WHEN rate_limits.window_ends_at <= now() THEN EXCLUDED.window_ends_at
+                    ELSE rate_limits.window_ends_at
+                END
+            RETURNING hits, EXTRACT(EPOCH FROM window_ends_at - now())::float8
+            "#,
+        )
+        .bind(key)
+        .bind(window.as_secs_f64())
+        .fetch_one(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(to_window(row))
+    }
+
+    #[tracing::instrument(name = "rate_limit_repository.find", level = "debug", skip_all)]
+    async fn find(&self, key: &str) -> Result<Option<RateLimitWindow>, AppError> {
+        let row = sqlx::query_as::<_, (i32, f64)>(
+            r#"
+            SELECT hits, EXTRACT(EPOCH FROM window_ends_at - now())::float8
+            FROM rate_limits
+            WHERE key = $1 AND window_ends_at > now()
+            "#,
+        )
+        .bind(key)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(row.map(to_window))
+    }
+
+    #[tracing::instrument(name = "rate_limit_repository.decrement", level = "debug", skip_all)]
+    async fn decrement(&self, key: &str) -> Result<(), AppError> {
+        sqlx::query(
+            "UPDATE rate_limits SET hits = GREATEST(hits - 1, 0) WHERE key = $1 AND window_ends_at > now()",
+        )
+        .bind(key)
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "rate_limit_repository.delete", level = "debug", skip_all)]
+    async fn delete(&self, key: &str) -> Result<(), AppError> {
+        sqlx::query("DELETE FROM rate_limits WHERE key = $1")
+            .bind(key)
+            .execute(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+
+        Ok(())
+    }
+}
+
+/// Factory para criar o repositório de contadores conforme o backend configurado
+pub fn create_rate_limit_repository(config: &Config, pool: &PgPool) -> Arc<dyn RateLimitRepository> {
+    match config.rate_limit_backend {
+        RateLimitBackend::Memory => Arc::new(InMemoryRateLimitRepository::new()),
+        RateLimitBackend::Postgres => Arc::new(PgRateLimitRepository::new(pool.clone())),
+    }
+}
@@ -0,0 +1,108 @@
+use std::time::Duration;
+
+use crate::repositories::{
+    rate_limit_repository::{InMemoryRateLimitRepository, PgRateLimitRepository, RateLimitRepository},
+    test_helpers::get_test_db_pool,
+};
+
+async fn assert_counts_within_window(repository: &dyn RateLimitRepository) {
+    let window = Duration::from_secs(60);
+
+    assert_eq!(repository.increment("login:a@test.com", window).await.unwrap().hits, 1);
+    let second = repository.increment("login:a@test.com", window).await.unwrap();
+    assert_eq!(second.hits, 2);
+    assert!(second.resets_in <= window);
+    assert!(second.resets_in > Duration::from_secs(55));
+
+    // Chaves diferentes têm contadores independentes
+    assert_eq!(repository.increment("login:b@test.com", window).await.unwrap().hits, 1);
+
+    let found = repository.find("login:a@test.com").await.unwrap().unwrap();
+    assert_eq!(found.hits, 2);
+
+    repository.delete("login:a@test.com").await.unwrap();
+    assert!(repository.find("login:a@test.com").await.unwrap().is_none());
+    assert_eq!(repository.increment("login:a@test.com", window).await.unwrap().hits, 1);
+}
+
+async fn assert_restarts_expired_window(repository: &dyn RateLimitRepository) {
+    let short = Duration::from_millis(200);
+
+    repository.increment("ip:127.0.0.1", short).await.unwrap();
+    repository.increment("ip:127.0.0.1", short).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(300)).await;
+
+    assert!(repository.find("ip:127.0.0.1").await.unwrap().is_none());
+    let window = repository.increment("ip:127.0.0.1", Duration::from_secs(60)).await.unwrap();
+    assert_eq!(window.hits, 1);
+    assert!(window.resets_in > Duration::from_secs(55));
+}
+
+async fn assert_decrement_returns_attempt(repository: &dyn RateLimitRepository) {
+    let window = Duration::from_secs(60);
+
+    repository.increment("failed_login:c@test.com", window).await.unwrap();
+    repository.increment("failed_login:c@test.com", window).await.unwrap();
+    repository.decrement("failed_login:c@test.com").await.unwrap();
+    assert_eq!(repository.find("failed_login:c@test.com").await.unwrap().unwrap().hits, 1);
+
+    // Nunca fica negativo, e chaves sem janela continuam sem janela
+    repository.decrement("failed_login:c@test.com").await.unwrap();
+    repository.decrement("failed_login:c@test.com").await.unwrap();
+    assert_eq!(repository.find("failed_login:c@test.com").await.unwrap().unwrap().hits, 0);
+    repository.decrement("failed_login:d@test.com").await.unwrap();
+    assert!(repository.find("failed_login:d@test.com").await.unwrap().is_none());
+
+    repository.delete("failed_login:c@test.com").await.unwrap();
+}
+
+#[tokio::test]
+async fn test_in_memory_counts_within_window() {
+    assert_counts_within_window(&InMemoryRateLimitRepository::new()).await;
+}
+
+#[tokio::test]
+async fn test_in_memory_restarts_expired_window() {
+    assert_restarts_expired_window(&InMemoryRateLimitRepository::new()).await;
+}
+
+#[tokio::test]
+async fn test_in_memory_decrement_returns_attempt() {
+    assert_decrement_returns_attempt(&InMemoryRateLimitRepository::new()).await;
+}
+
+#[tokio::test]
+async fn test_pg_counts_within_window() {
+    let repository = PgRateLimitRepository::new(get_test_db_pool().await);
+    assert_counts_within_window(&repository).await;
+}
+
+#[tokio::test]
+async fn test_pg_restarts_expired_window() {
+    let repository = PgRateLimitRepository::new(get_test_db_pool().await);
+    assert_restarts_expired_window(&repository).await;
+}
+
+#[tokio::test]
+async fn test_pg_decrement_returns_attempt() {
+    let repository = PgRateLimitRepository::new(get_test_db_pool().await);
+    assert_decrement_returns_attempt(&repository).await;
+}
+
+#[tokio::test]
+async fn test_pg_concurrent_increments_are_not_lost() {
+    let pool = get_test_db_pool().await;
+    let mut handles = Vec::new();
+    for _ in 0..10 {
+        let repository = PgRateLimitRepository::new(pool.clone());
+        handles.push(tokio::spawn(async move {
+            repository.increment("register:10.0.0.1", Duration::from_secs(60)).await.unwrap()
+        }));
+    }
+    for handle in handles {
+        handle.await.unwrap();
+    }
+
+    let repository = PgRateLimitRepository::new(pool);
+    assert_eq!(repository.find("register:10.0.0.1").await.unwrap().unwrap().hits, 10);
+}
@@ -0,0 +1,150 @@
+// Banco de dados isolado para os testes que usam o Postgres
+//
+// Este arquivo também é incluído pelos testes de integração (tests/common), por
+// isso depende apenas do sqlx, do uuid e do tokio, e não dos módulos do crate.
+
+use sqlx::postgres::{PgConnection, PgPoolOptions};
+use sqlx::{Connection, Executor, PgPool};
+use uuid::Uuid;
+
+/// Estrutura completa do banco, a mesma aplicada pelo contêiner do Postgres
+const SCHEMA_SQL: &str = include_str!("../../postgres/setup.sql");
+
+/// Schema exclusivo de um teste, criado a partir do `postgres/setup.sql`
+///
+/// O pool devolvido por `pool()` enxerga apenas as tabelas do schema, de modo
+/// que os testes podem rodar em paralelo sem interferir uns nos outros. O
+/// schema é removido quando a instância é descartada.
+pub struct TestDatabase {
+    database_url: String,
+    schema: String,
+    pool: PgPool,
+}
+
+impl TestDatabase {
+    /// Cria um schema novo no banco de `database_url` e aplica a estrutura nele
+    pub async fn create(database_url: &str) -> Self {
+        let schema = format!("test_{}", Uuid::new_v4().simple());
+
+        let mut admin = PgConnection::connect(database_url)
+            .await
+            .expect("Falha ao conectar ao banco de teste");
+        admin
+            .execute(format!("CREATE SCHEMA \"{}\"", schema).as_str())
+            .await
+            .expect("Falha ao criar o schema de teste");
+        admin.close().await.ok();
+
+        // O schema do teste vem antes do public, onde ficam as extensões
+        let search_path = format!("SET search_path TO \"{}\", public", schema);
+        let pool = PgPoolOptions::new()
+            .max_connections(5)
+            .after_connect(move |conn, _| {
+                let search_path = search_path.clone();
+                Box::pin(async move {
+                    conn.execute(search_path.as_str()).await?;
+                    Ok(())
+                })
+            })
+            .connect(database_url)
+            .await
+            .expect("Falha ao conectar ao banco de teste");
+
+        let database = Self {
+            database_url: database_url.to_string(),
+            schema,
+            pool,
+        };
+
+        database
+            .pool
+            .execute(SCHEMA_SQL)
+            .await
+            .expect("Falha ao aplicar a estrutura do banco no schema de teste");
+
+        database
+    }
+
+    pub fn pool(&self) -> &PgPool {
+        &self.pool
+    }
+
+    pub fn schema(&self) -> &str {
+        &self.schema
+    }
+
+    /// Cadastra um usuário com email único e devolve o id
+    pub async fn create_user(&self, name: &str) -> Uuid {
+        let email = format!("{}_{}@example.com", name.to_lowercase().replace(' ', "_"), Uuid::new_v4());
+
+        sqlx::query_scalar("INSERT INTO users (name, email, hash_password) VALUES ($1, $2, 'senha_hash') RETURNING id")
+            .bind(name)
+            .bind(email)
+            .fetch_one(&self.pool)
+            .await
+            .expect("Falha ao cadastrar usuário de teste")
+    }
+
+    /// Cadastra um livro com o título informado e devolve o id
+    pub async fn create_book(&self, title: &str) -> Uuid {
+        sqlx::query_scalar(
+            r#"
+            INSERT INTO books (google_id, title, author, description, image_url)
+            VALUES ($1, $2, 'Autor Teste', 'Descrição de teste', 'http://example.com/image.jpg')
+            RETURNING id
+            "#,
+        )
+        .bind(format!("teste-{}", Uuid::new_v4()))
+        .bind(title)
+        .fetch_one(&self.pool)
+        .await
+        .expect("Falha ao cadastrar livro de teste")
+    }
+
+    /// Adiciona um exemplar do livro à lista de possuídos do usuário e devolve o id do exemplar
+    pub async fn add_offered(&self, user_id: Uuid, book_id: Uuid) -> Uuid {
+        sqlx::query_scalar("INSERT INTO books_offered (book_id, user_id) VALUES ($1, $2) RETURNING id")
+            .bind(book_id)
+            .bind(user_id)
+            .fetch_one(&self.pool)
+            .await
+            .expect("Falha ao adicionar livro possuído de teste")
+    }
+
+    /// Adiciona o livro à lista de desejados do usuário
+    pub async fn add_wanted(&self, user_id: Uuid, book_id: Uuid, priority: i16) {
+        sqlx::query("INSERT INTO books_wanted (book_id, user_id, priority) VALUES ($1, $2, $3)")
+            .bind(book_id)
+            .bind(user_id)
+            .bind(priority)
+            .execute(&self.pool)
+            .await
+            .expect("Falha ao adicionar livro desejado de teste");
+    }
+}
+
+impl Drop for TestDatabase {
+    fn drop(&mut self) {
+        let database_url = self.database_url.clone();
+        let drop_schema = format!("DROP SCHEMA IF EXISTS \"{}\" CASCADE", self.schema);
+
+        // O Drop não pode aguardar; a remoção roda em outra thread, com um
+        // runtime próprio, para funcionar dentro ou fora de um runtime do tokio
+        let removal = std::thread::spawn(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .expect("Falha ao criar o runtime de limpeza");
+
+            runtime.block_on(async move {
+                let mut conn = PgConnection::connect(&database_url).await?;
+                conn.execute(drop_schema.as_str()).await?;
+                conn.close().await
+            })
+        });
+
+        if let Ok(Err(e)) = removal.join() {
+            eprintln!("Falha ao remover o schema de teste {}: {}", self.schema, e);
+        }
+    }
+}
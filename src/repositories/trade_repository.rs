@@ -1,29 +1,94 @@
+use std::collections::HashMap;
+
 use async_trait::async_trait;
 use sqlx::PgPool;
 use uuid::Uuid;
 
-use crate::error::AppError;
-use crate::models::trade::PossibleTrade;
-use crate::models::book::GoogleBookDto;
-use crate::models::user::UserResponse;
+use chrono::NaiveDateTime;
+
+use crate::error::{AppError, ErrorCode};
+use crate::i18n::messages;
+use crate::models::trade::{CreateTradeDto, PossibleTrade, Trade, TradeScoreFactors, TradeStatus};
+use crate::models::book::{
+    BookCondition, BookPhoto, CreateBookOfferedDto, GoogleBookDto, OfferedCopy, OfferedCopyAttributes,
+};
+use crate::models::user::{UserLocation, UserResponse};
+use crate::repositories::book_photo_repository::{BookPhotoRepository, PgBookPhotoRepository};
+use crate::repositories::books_offered_repository::insert_copies;
+use crate::repositories::books_wanted_repository::remove_wanted;
+use crate::repositories::db_error::{constraint_violation, ConstraintKind};
+use crate::repositories::unit_of_work::UnitOfWork;
 
 #[async_trait]
 pub trait TradeRepository: Send + Sync + 'static {
     async fn find_possible_trades(&self, user_id: Uuid) -> Result<Vec<PossibleTrade>, AppError>;
+    /// Registra uma proposta de troca e reserva os dois exemplares envolvidos
+    async fn create(&self, trade: &CreateTradeDto) -> Result<Trade, AppError>;
+    async fn find_by_id(&self, id: &Uuid) -> Result<Option<Trade>, AppError>;
+    /// Lista as trocas em que o usuário é o autor da proposta ou o parceiro
+    async fn find_by_user_id(&self, user_id: &Uuid) -> Result<Vec<Trade>, AppError>;
+    /// Altera a situação da troca se ela ainda estiver em `from`; trocas encerradas
+    /// liberam a reserva dos exemplares
+    async fn update_status(
+        &self,
+        id: &Uuid,
+        from: TradeStatus,
+        to: TradeStatus,
+    ) -> Result<Option<Trade>, AppError>;
+    /// Conclui uma troca aceita, retirando os exemplares trocados das listas dos donos
+    async fn complete(&self, id: &Uuid) -> Result<Option<Trade>, AppError>;
+}
+
+// Linha da tabela trades como retornada pelas consultas
+struct TradeRecord {
+    id: Uuid,
+    proposer_id: Uuid,
+    partner_id: Uuid,
+    offered_book_id: Uuid,
+    offered_copy_id: Option<Uuid>,
+    wanted_book_id: Uuid,
+    wanted_copy_id: Option<Uuid>,
+    status: String,
+    created_at: NaiveDateTime,
+    updated_at: NaiveDateTime,
+}
+
+impl TryFrom<TradeRecord> for Trade {
+    type Error = AppError;
+
+    fn try_from(record: TradeRecord) -> Result<Self, Self::Error> {
+        Ok(Trade {
+            id: record.id,
+            proposer_id: record.proposer_id,
+            partner_id: record.partner_id,
+            offered_book_id: record.offered_book_id,
+            offered_copy_id: record.offered_copy_id,
+            wanted_book_id: record.wanted_book_id,
+            wanted_copy_id: record.wanted_copy_id,
+            status: record.status.parse::<TradeStatus>()?,
+            created_at: record.created_at,
+            updated_at: record.updated_at,
+        })
+    }
 }
 
 pub struct PgTradeRepository {
     pool: PgPool,
+    photo_repository: PgBookPhotoRepository,
 }
 
 impl PgTradeRepository {
     pub fn new(pool: PgPool) -> Self {
-        Self { pool }
+        Self {
+            photo_repository: PgBookPhotoRepository::new(pool.clone()),
+            pool,
+        }
     }
 }
 
 #[async_trait]
 impl TradeRepository for PgTradeRepository {
+    #[tracing::instrument(name = "trade_repository.find_possible_trades", level = "debug", skip_all)]
     async fn find_possible_trades(&self, user_id: Uuid) -> Result<Vec<PossibleTrade>, AppError> {
         // Query complexa que encontra trocas possíveis:
         // 1. Pega livros que o usuário oferece
@@ -31,7 +96,7 @@ impl TradeRepository for PgTradeRepository {
         // 3. Verifica se o usuário atual quer algum livro que esses outros usuários oferecem
         let result = sqlx::query!(
             r#"
-            SELECT DISTINCT
+            SELECT DISTINCT ON (partner.name, partner.id, offered_book.title, offered_book.id, wanted_book.title, wanted_book.id)
                 -- Livro que o usuário oferece
                 offered_book.id as offered_book_id,
                 offered_book.title as offered_book_title,
@@ -42,6 +107,10 @@ impl TradeRepository for PgTradeRepository {
                 offered_book.image_url as offered_book_image_url,
                 offered_book.page_count as offered_book_page_count,
                 offered_book.google_id as offered_book_google_id,
+                my_offers.id as offered_copy_id,
+                my_offers.condition as offered_copy_condition,
+                my_offers.language as offered_copy_language,
+                my_offers.notes as offered_copy_notes,
                 
                 -- Livro que o usuário quer (oferecido pelo parceiro)
                 wanted_book.id as wanted_book_id,
@@ -53,13 +122,29 @@ impl TradeRepository for PgTradeRepository {
                 wanted_book.image_url as wanted_book_image_url,
                 wanted_book.page_count as wanted_book_page_count,
                 wanted_book.google_id as wanted_book_google_id,
+                partner_offers.id as wanted_copy_id,
+                partner_offers.condition as wanted_copy_condition,
+                partner_offers.language as wanted_copy_language,
+                partner_offers.notes as wanted_copy_notes,
                 
                 -- Parceiro de troca
                 partner.id as partner_id,
                 partner.name as partner_name,
                 partner.email as partner_email,
                 partner.created_at as partner_created_at,
-                partner.updated_at as partner_updated_at
+                partner.updated_at as partner_updated_at,
+
+                -- Fatores de pontuação da sugestão
+                my_wants.priority as my_priority,
+                partner_wants.priority as partner_priority,
+                partner.latitude as partner_latitude,
+                partner.longitude as partner_longitude,
+                me.latitude as my_latitude,
+                me.longitude as my_longitude,
+                (
+                    SELECT COUNT(*) FROM trades t
+                    WHERE t.status = 'completed' AND (t.user_id = partner.id OR t.partner_id = partner.id)
+                ) as "partner_completed_trades!"
             FROM 
                 -- Livros que o usuário oferece
                 books_offered my_offers
@@ -75,12 +160,20 @@ impl TradeRepository for PgTradeRepository {
                 
                 -- O usuário atual quer esses livros
                 INNER JOIN books_wanted my_wants ON my_wants.book_id = wanted_book.id
+                INNER JOIN users me ON me.id = my_offers.user_id
             WHERE 
                 my_offers.user_id = $1
                 AND my_wants.user_id = $1
                 AND partner.id != $1
+                AND my_offers.available
+                AND partner_offers.available
+                -- Exemplares já prometidos em outra troca não entram nas sugestões
+                AND NOT EXISTS (SELECT 1 FROM trade_copy_reservations r WHERE r.copy_id = my_offers.id)
+                AND NOT EXISTS (SELECT 1 FROM trade_copy_reservations r WHERE r.copy_id = partner_offers.id)
             ORDER BY 
-                partner.name, offered_book.title, wanted_book.title
+                -- Para cada par de livros, sugere os exemplares cadastrados há mais tempo
+                partner.name, partner.id, offered_book.title, offered_book.id, wanted_book.title, wanted_book.id,
+                my_offers.created_at, partner_offers.created_at
             "#,
             user_id
         )
@@ -88,9 +181,33 @@ impl TradeRepository for PgTradeRepository {
         .await
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
 
-        let trades = result
-            .into_iter()
-            .map(|row| PossibleTrade {
+        // Buscar as fotos dos exemplares envolvidos em uma única consulta
+        let mut copy_ids = Vec::with_capacity(result.len() * 2);
+        for row in &result {
+            copy_ids.push(row.offered_copy_id);
+            copy_ids.push(row.wanted_copy_id);
+        }
+        let mut photos: HashMap<Uuid, Vec<BookPhoto>> = HashMap::new();
+        for record in self.photo_repository.find_by_copy_ids(&copy_ids).await? {
+            photos.entry(record.copy_id).or_default().push(BookPhoto::from(record));
+        }
+        let photos_of = |copy_id: Uuid| photos.get(&copy_id).cloned().unwrap_or_default();
+
+        let mut trades = Vec::with_capacity(result.len());
+        for row in result {
+            let location = |latitude: Option<f64>, longitude: Option<f64>| {
+                Some(UserLocation { latitude: latitude?, longitude: longitude? })
+            };
+            let my_location = location(row.my_latitude, row.my_longitude);
+            let partner_location = location(row.partner_latitude, row.partner_longitude);
+            let factors = TradeScoreFactors {
+                my_priority: row.my_priority,
+                partner_priority: row.partner_priority,
+                partner_completed_trades: row.partner_completed_trades,
+                distance_km: my_location.zip(partner_location).map(|(me, partner)| me.distance_km(&partner)),
+            };
+
+            trades.push(PossibleTrade {
                 offered_book_id: row.offered_book_id,
                 offered_book: GoogleBookDto {
                     google_id: row.offered_book_google_id.unwrap_or_default(),
@@ -120,9 +237,285 @@ impl TradeRepository for PgTradeRepository {
                     created_at: row.partner_created_at,
                     updated_at: row.partner_updated_at,
                 },
-            })
-            .collect();
+                offered_copy: OfferedCopy {
+                    id: row.offered_copy_id,
+                    attributes: OfferedCopyAttributes {
+                        condition: row.offered_copy_condition.parse::<BookCondition>()?,
+                        language: row.offered_copy_language,
+                        notes: row.offered_copy_notes,
+                        available: true,
+                    },
+                    photos: photos_of(row.offered_copy_id),
+                },
+                wanted_copy: OfferedCopy {
+                    id: row.wanted_copy_id,
+                    attributes: OfferedCopyAttributes {
+                        condition: row.wanted_copy_condition.parse::<BookCondition>()?,
+                        language: row.wanted_copy_language,
+                        notes: row.wanted_copy_notes,
+                        available: true,
+                    },
+                    photos: photos_of(row.wanted_copy_id),
+                },
+                score: factors.score(),
+            });
+        }
+
+        // As sugestões mais relevantes primeiro; empates mantêm a ordem da consulta
+        trades.sort_by(|a, b| b.score.total_cmp(&a.score));
 
         Ok(trades)
     }
-} 
\ No newline at end of file
+
+    #[tracing::instrument(name = "trade_repository.create", level = "debug", skip_all)]
+    async fn create(&self, trade: &CreateTradeDto) -> Result<Trade, AppError> {
+        let mut uow = UnitOfWork::begin(&self.pool).await?;
+
+        let record = sqlx::query_as!(
+            TradeRecord,
+            r#"
+            INSERT INTO trades (user_id, partner_id, book_offered_id, offered_copy_id, book_wanted_id, wanted_copy_id, status)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING
+                id, user_id as proposer_id, partner_id as "partner_id!",
+                book_offered_id as offered_book_id, offered_copy_id,
+                book_wanted_id as wanted_book_id, wanted_copy_id,
+                status, created_at, updated_at
+            "#,
+            trade.proposer_id,
+            trade.partner_id,
+            trade.offered_book_id,
+            trade.offered_copy_id,
+            trade.wanted_book_id,
+            trade.wanted_copy_id,
+            TradeStatus::Pending.as_str()
+        )
+        .fetch_one(uow.conn())
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        // A chave primária da reserva impede que um exemplar seja prometido duas vezes
+        sqlx::query!(
+            r#"
+            INSERT INTO trade_copy_reservations (copy_id, trade_id)
+            VALUES ($1, $3), ($2, $3)
+            "#,
+            trade.offered_copy_id,
+            trade.wanted_copy_id,
+            record.id
+        )
+        .execute(uow.conn())
+        .await
+        .map_err(|e| match constraint_violation(&e) {
+            Some((ConstraintKind::Unique, _)) => AppError::Conflict(
+                ErrorCode::CopyReserved,
+                messages::COPY_ALREADY_PROMISED.text(),
+            ),
+            Some((ConstraintKind::ForeignKey, _)) => AppError::NotFoundError(messages::COPY_NOT_FOUND.text()),
+            _ => AppError::DatabaseError(e.to_string()),
+        })?;
+
+        uow.commit().await?;
+
+        Trade::try_from(record)
+    }
+
+    #[tracing::instrument(name = "trade_repository.find_by_id", level = "debug", skip_all)]
+    async fn find_by_id(&self, id: &Uuid) -> Result<Option<Trade>, AppError> {
+        let record = sqlx::query_as!(
+            TradeRecord,
+            r#"
+            SELECT
+                id, user_id as proposer_id, partner_id as "partner_id!",
+                book_offered_id as offered_book_id, offered_copy_id,
+                book_wanted_id as wanted_book_id, wanted_copy_id,
+                status, created_at, updated_at
+            FROM trades
+            WHERE id = $1 AND partner_id IS NOT NULL
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        record.map(Trade::try_from).transpose()
+    }
+
+    #[tracing::instrument(name = "trade_repository.find_by_user_id", level = "debug", skip_all)]
+    async fn find_by_user_id(&self, user_id: &Uuid) -> Result<Vec<Trade>, AppError> {
+        let records = sqlx::query_as!(
+            TradeRecord,
+            r#"
+            SELECT
+                id, user_id as proposer_id, partner_id as "partner_id!",
+                book_offered_id as offered_book_id, offered_copy_id,
+                book_wanted_id as wanted_book_id, wanted_copy_id,
+                status, created_at, updated_at
+            FROM trades
+            WHERE (user_id = $1 OR partner_id = $1) AND partner_id IS NOT NULL
+            ORDER BY created_at DESC
+            "#,
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        records.into_iter().map(Trade::try_from).collect()
+    }
+
+    #[tracing::instrument(name = "trade_repository.update_status", level = "debug", skip_all)]
+    async fn update_status(
+        &self,
+        id: &Uuid,
+        from: TradeStatus,
+        to: TradeStatus,
+    ) -> Result<Option<Trade>, AppError> {
+        let mut uow = UnitOfWork::begin(&self.pool).await?;
+
+        // A condição sobre a situação atual evita transições concorrentes
+        let record = sqlx::query_as!(
+            TradeRecord,
+            r#"
+            UPDATE trades
+            SET status = $3, updated_at = CURRENT_TIMESTAMP
+            WHERE id = $1 AND status = $2
+            RETURNING
+                id, user_id as proposer_id, partner_id as "partner_id!",
+                book_offered_id as offered_book_id, offered_copy_id,
+                book_wanted_id as wanted_book_id, wanted_copy_id,
+                status, created_at, updated_at
+            "#,
+            id,
+            from.as_str(),
+            to.as_str()
+        )
+        .fetch_optional(uow.conn())
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let Some(record) = record else {
+            return Ok(None);
+        };
+
+        if !to.is_open() {
+            sqlx::query!("DELETE FROM trade_copy_reservations WHERE trade_id = $1", id)
+                .execute(uow.conn())
+                .await
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        uow.commit().await?;
+
+        Trade::try_from(record).map(Some)
+    }
+
+    #[tracing::instrument(name = "trade_repository.complete", level = "debug", skip_all)]
+    async fn complete(&self, id: &Uuid) -> Result<Option<Trade>, AppError> {
+        let mut uow = UnitOfWork::begin(&self.pool).await?;
+
+        // Trava a troca para que só uma conclusão aconteça
+        let trade = sqlx::query!(
+            r#"
+            SELECT user_id, partner_id, book_offered_id, offered_copy_id, book_wanted_id, wanted_copy_id
+            FROM trades
+            WHERE id = $1 AND status = $2
+            FOR UPDATE
+            "#,
+            id,
+            TradeStatus::Accepted.as_str()
+        )
+        .fetch_optional(uow.conn())
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let Some(trade) = trade else {
+            return Ok(None);
+        };
+
+        sqlx::query!("DELETE FROM trade_copy_reservations WHERE trade_id = $1", id)
+            .execute(uow.conn())
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        // Cada parte passa a possuir o livro recebido, que sai da sua lista de desejados
+        move_received_book(&mut uow, trade.book_wanted_id, trade.user_id, trade.wanted_copy_id).await?;
+        if let Some(partner_id) = trade.partner_id {
+            move_received_book(&mut uow, trade.book_offered_id, partner_id, trade.offered_copy_id).await?;
+        }
+
+        // Os exemplares entregues deixam a lista de possuídos dos donos
+        let copy_ids: Vec<Uuid> = [trade.offered_copy_id, trade.wanted_copy_id]
+            .into_iter()
+            .flatten()
+            .collect();
+        sqlx::query!("DELETE FROM books_offered WHERE id = ANY($1)", &copy_ids[..])
+            .execute(uow.conn())
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let record = sqlx::query_as!(
+            TradeRecord,
+            r#"
+            UPDATE trades
+            SET status = $2, updated_at = CURRENT_TIMESTAMP
+            WHERE id = $1
+            RETURNING
+                id, user_id as proposer_id, partner_id as "partner_id!",
+                book_offered_id as offered_book_id, offered_copy_id,
+                book_wanted_id as wanted_book_id, wanted_copy_id,
+                status, created_at, updated_at
+            "#,
+            id,
+            TradeStatus::Completed.as_str()
+        )
+        .fetch_one(uow.conn())
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        uow.commit().await?;
+
+        Trade::try_from(record).map(Some)
+    }
+}
+
+// Adiciona o livro recebido em uma troca à lista de possuídos de quem o recebeu,
+// mantendo o estado e o idioma do exemplar entregue, e o retira dos desejados
+async fn move_received_book(
+    uow: &mut UnitOfWork,
+    book_id: Uuid,
+    receiver_id: Uuid,
+    delivered_copy_id: Option<Uuid>,
+) -> Result<(), AppError> {
+    let delivered = sqlx::query!(
+        "SELECT condition, language FROM books_offered WHERE id = $1",
+        delivered_copy_id
+    )
+    .fetch_optional(uow.conn())
+    .await
+    .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    let attributes = match delivered {
+        Some(copy) => OfferedCopyAttributes {
+            condition: copy.condition.parse::<BookCondition>()?,
+            language: copy.language,
+            ..OfferedCopyAttributes::default()
+        },
+        None => OfferedCopyAttributes::default(),
+    };
+
+    remove_wanted(uow, &book_id, &receiver_id).await?;
+    insert_copies(
+        uow,
+        &CreateBookOfferedDto {
+            book_id,
+            user_id: receiver_id,
+            attributes,
+        },
+        1,
+    )
+    .await?;
+
+    Ok(())
+}
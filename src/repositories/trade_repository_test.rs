@@ -1,6 +1,8 @@
 use crate::{
+    error::{AppError, ErrorCode},
+    models::trade::{CreateTradeDto, TradeStatus},
     repositories::{
-        test_helpers::{get_test_db_pool, get_test_mutex},
+        test_helpers::get_test_db_pool,
         trade_repository::{PgTradeRepository, TradeRepository},
     },
 };
@@ -113,9 +115,6 @@ async fn setup_test_data(pool: &PgPool) -> (Uuid, Uuid, Uuid, Uuid) {
 
 #[tokio::test]
 async fn test_find_possible_trades_success() {
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-
     let trade_repository = setup_test_repository().await;
     let pool = get_test_db_pool().await;
 
@@ -135,9 +134,6 @@ async fn test_find_possible_trades_success() {
 
 #[tokio::test]
 async fn test_find_possible_trades_no_matches() {
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-
     let trade_repository = setup_test_repository().await;
     let pool = get_test_db_pool().await;
 
@@ -162,9 +158,6 @@ async fn test_find_possible_trades_no_matches() {
 
 #[tokio::test]
 async fn test_find_possible_trades_with_multiple_matches() {
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-
     let trade_repository = setup_test_repository().await;
     let pool = get_test_db_pool().await;
 
@@ -233,9 +226,6 @@ async fn test_find_possible_trades_with_multiple_matches() {
 
 #[tokio::test]
 async fn test_find_possible_trades_excludes_same_user() {
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-
     let trade_repository = setup_test_repository().await;
     let pool = get_test_db_pool().await;
 
@@ -270,4 +260,226 @@ async fn test_find_possible_trades_excludes_same_user() {
     assert!(result.is_ok(), "Deve executar busca sem erros");
     let trades = result.unwrap();
     assert_eq!(trades.len(), 0, "Não deve encontrar troca do usuário consigo mesmo");
-} 
\ No newline at end of file
+}
+
+// Busca o ID do exemplar de um livro oferecido pelo usuário
+async fn find_copy_id(pool: &PgPool, book_id: Uuid, user_id: Uuid) -> Uuid {
+    sqlx::query_scalar!(
+        "SELECT id FROM books_offered WHERE book_id = $1 AND user_id = $2",
+        book_id,
+        user_id
+    )
+    .fetch_one(pool)
+    .await
+    .unwrap()
+}
+
+async fn create_test_trade(pool: &PgPool) -> CreateTradeDto {
+    let (user1_id, user2_id, book1_id, book2_id) = setup_test_data(pool).await;
+
+    CreateTradeDto {
+        proposer_id: user1_id,
+        partner_id: user2_id,
+        offered_book_id: book1_id,
+        offered_copy_id: find_copy_id(pool, book1_id, user1_id).await,
+        wanted_book_id: book2_id,
+        wanted_copy_id: find_copy_id(pool, book2_id, user2_id).await,
+    }
+}
+
+#[tokio::test]
+async fn test_create_trade_reserves_copies() {
+    let trade_repository = setup_test_repository().await;
+    let pool = get_test_db_pool().await;
+
+    let dto = create_test_trade(&pool).await;
+
+    let result = trade_repository.create(&dto).await;
+
+    assert!(result.is_ok(), "Deve criar a proposta: {:?}", result.err());
+    let trade = result.unwrap();
+    assert_eq!(trade.status, TradeStatus::Pending);
+    assert_eq!(trade.offered_copy_id, Some(dto.offered_copy_id));
+    assert_eq!(trade.wanted_copy_id, Some(dto.wanted_copy_id));
+
+    // Os exemplares reservados deixam de aparecer como trocas possíveis
+    let possible = trade_repository.find_possible_trades(dto.proposer_id).await.unwrap();
+    assert!(possible.is_empty(), "Exemplares reservados não devem aparecer em novas trocas");
+
+    // E não podem ser prometidos em outra proposta
+    let second = trade_repository.create(&dto).await;
+    match second {
+        Err(AppError::Conflict(code, msg)) => {
+            assert_eq!(code, ErrorCode::CopyReserved);
+            assert_eq!(msg, "Este exemplar já está prometido em outra troca");
+        }
+        other => panic!("Resultado inesperado: {:?}", other),
+    }
+
+    let trades = trade_repository.find_by_user_id(&dto.partner_id).await.unwrap();
+    assert_eq!(trades.len(), 1, "A proposta recusada não deve ter sido gravada");
+}
+
+#[tokio::test]
+async fn test_update_status_releases_reservation() {
+    let trade_repository = setup_test_repository().await;
+    let pool = get_test_db_pool().await;
+
+    let dto = create_test_trade(&pool).await;
+    let trade = trade_repository.create(&dto).await.unwrap();
+
+    // A transição só acontece a partir da situação esperada
+    let stale = trade_repository
+        .update_status(&trade.id, TradeStatus::Accepted, TradeStatus::Cancelled)
+        .await
+        .unwrap();
+    assert!(stale.is_none(), "Não deve alterar uma troca em outra situação");
+
+    let rejected = trade_repository
+        .update_status(&trade.id, TradeStatus::Pending, TradeStatus::Rejected)
+        .await
+        .unwrap()
+        .expect("Deve recusar a troca pendente");
+    assert_eq!(rejected.status, TradeStatus::Rejected);
+
+    // Com a reserva liberada, os exemplares podem ser propostos novamente
+    let again = trade_repository.create(&dto).await;
+    assert!(again.is_ok(), "Deve permitir nova proposta: {:?}", again.err());
+}
+
+#[tokio::test]
+async fn test_complete_trade_removes_copies() {
+    let trade_repository = setup_test_repository().await;
+    let pool = get_test_db_pool().await;
+
+    let dto = create_test_trade(&pool).await;
+    let trade = trade_repository.create(&dto).await.unwrap();
+
+    // Uma troca pendente não pode ser concluída
+    assert!(trade_repository.complete(&trade.id).await.unwrap().is_none());
+
+    trade_repository
+        .update_status(&trade.id, TradeStatus::Pending, TradeStatus::Accepted)
+        .await
+        .unwrap()
+        .expect("Deve aceitar a troca");
+
+    let completed = trade_repository
+        .complete(&trade.id)
+        .await
+        .unwrap()
+        .expect("Deve concluir a troca aceita");
+    assert_eq!(completed.status, TradeStatus::Completed);
+
+    // Os exemplares entregues saem das listas dos donos
+    let remaining = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!" FROM books_offered WHERE id = ANY($1)"#,
+        &[dto.offered_copy_id, dto.wanted_copy_id][..]
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(remaining, 0);
+}
+
+#[tokio::test]
+async fn test_complete_trade_moves_received_books() {
+    let trade_repository = setup_test_repository().await;
+    let pool = get_test_db_pool().await;
+
+    let dto = create_test_trade(&pool).await;
+    let trade = trade_repository.create(&dto).await.unwrap();
+    trade_repository
+        .update_status(&trade.id, TradeStatus::Pending, TradeStatus::Accepted)
+        .await
+        .unwrap()
+        .expect("Deve aceitar a troca");
+
+    trade_repository
+        .complete(&trade.id)
+        .await
+        .unwrap()
+        .expect("Deve concluir a troca aceita");
+
+    // Cada parte passa a possuir o livro que recebeu e deixa de desejá-lo
+    for (book_id, receiver_id) in [
+        (dto.wanted_book_id, dto.proposer_id),
+        (dto.offered_book_id, dto.partner_id),
+    ] {
+        let copies = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count!" FROM books_offered WHERE book_id = $1 AND user_id = $2"#,
+            book_id,
+            receiver_id
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(copies, 1, "O livro recebido deve entrar na lista de possuídos");
+
+        let wanted = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count!" FROM books_wanted WHERE book_id = $1 AND user_id = $2"#,
+            book_id,
+            receiver_id
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(wanted, 0, "O livro recebido deve sair da lista de desejados");
+    }
+}
+
+#[tokio::test]
+async fn test_find_possible_trades_ordered_by_score() {
+    let trade_repository = setup_test_repository().await;
+    let pool = get_test_db_pool().await;
+
+    let user1_id = Uuid::new_v4();
+    let user2_id = Uuid::new_v4();
+    let user3_id = Uuid::new_v4();
+
+    for (id, name, email) in [
+        (user1_id, "User 1", "user1@test.com"),
+        (user2_id, "User 2", "user2@test.com"),
+        (user3_id, "User 3", "user3@test.com"),
+    ] {
+        sqlx::query!(
+            "INSERT INTO users (id, name, email, hash_password) VALUES ($1, $2, $3, $4)",
+            id, name, email, "hash"
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+
+    let book1_id = Uuid::new_v4();
+    let book2_id = Uuid::new_v4();
+    let book3_id = Uuid::new_v4();
+
+    for (id, title) in [(book1_id, "Livro 1"), (book2_id, "Livro 2"), (book3_id, "Livro 3")] {
+        sqlx::query!(
+            "INSERT INTO books (id, title, author, description, image_url) VALUES ($1, $2, $3, $4, $5)",
+            id, title, "Autor", "Descrição", "http://example.com/book.jpg"
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+
+    // User1 oferece Book1; deseja pouco o Book2 e muito o Book3
+    sqlx::query!("INSERT INTO books_offered (book_id, user_id) VALUES ($1, $2)", book1_id, user1_id).execute(&pool).await.unwrap();
+    sqlx::query!("INSERT INTO books_wanted (book_id, user_id, priority) VALUES ($1, $2, 1)", book2_id, user1_id).execute(&pool).await.unwrap();
+    sqlx::query!("INSERT INTO books_wanted (book_id, user_id, priority) VALUES ($1, $2, 5)", book3_id, user1_id).execute(&pool).await.unwrap();
+
+    // User2 e User3 querem o Book1 com a mesma prioridade
+    sqlx::query!("INSERT INTO books_offered (book_id, user_id) VALUES ($1, $2)", book2_id, user2_id).execute(&pool).await.unwrap();
+    sqlx::query!("INSERT INTO books_wanted (book_id, user_id) VALUES ($1, $2)", book1_id, user2_id).execute(&pool).await.unwrap();
+    sqlx::query!("INSERT INTO books_offered (book_id, user_id) VALUES ($1, $2)", book3_id, user3_id).execute(&pool).await.unwrap();
+    sqlx::query!("INSERT INTO books_wanted (book_id, user_id) VALUES ($1, $2)", book1_id, user3_id).execute(&pool).await.unwrap();
+
+    let trades = trade_repository.find_possible_trades(user1_id).await.unwrap();
+
+    assert_eq!(trades.len(), 2, "Deve encontrar duas trocas possíveis");
+    assert_eq!(trades[0].trade_partner.name, "User 3", "O livro mais desejado deve vir primeiro");
+    assert_eq!(trades[1].trade_partner.name, "User 2");
+    assert!(trades[0].score > trades[1].score);
+}
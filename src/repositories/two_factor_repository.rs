@@ -0,0 +1,157 @@
+use async_trait::async_trait;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::models::two_factor::TwoFactorSecret;
+
+#[async_trait]
+pub trait TwoFactorRepository: Send + Sync + 'static {
+    /// Grava um novo segredo pendente de confirmação, substituindo outro
+    /// pendente; retorna false se a verificação já estiver ativa
+    async fn save_pending_secret(&self, user_id: &Uuid, secret: &str) -> Result<bool, AppError>;
+    async fn find(&self, user_id: &Uuid) -> Result<Option<TwoFactorSecret>, AppError>;
+    /// Ativa a verificação e grava os hashes dos códigos de recuperação
+    async fn enable(&self, user_id: &Uuid, used_step: i64, recovery_code_hashes: &[String]) -> Result<(), AppError>;
+    /// Registra o uso do intervalo; retorna false se ele ou um posterior já foi usado
+    async fn use_step(&self, user_id: &Uuid, step: i64) -> Result<bool, AppError>;
+    /// Marca o código de recuperação como usado; retorna false se ele não existe ou já foi usado
+    async fn use_recovery_code(&self, user_id: &Uuid, code_hash: &str) -> Result<bool, AppError>;
+    /// Remove o segredo e os códigos de recuperação
+    async fn delete(&self, user_id: &Uuid) -> Result<(), AppError>;
+}
+
+pub struct PgTwoFactorRepository {
+    pool: PgPool,
+}
+
+impl PgTwoFactorRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl TwoFactorRepository for PgTwoFactorRepository {
+    #[tracing::instrument(name = "two_factor_repository.save_pending_secret", level = "debug", skip_all)]
+    async fn save_pending_secret(&self, user_id: &Uuid, secret: &str) -> Result<bool, AppError> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO user_two_factor (user_id, secret) VALUES ($1, $2)
+            ON CONFLICT (user_id) DO UPDATE SET
+                secret = EXCLUDED.secret,
+                last_used_step = NULL,
+                created_at = CURRENT_TIMESTAMP
+            WHERE user_two_factor.enabled_at IS NULL
+            "#,
+        )
+        .bind(user_id)
+        .bind(secret)
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[tracing::instrument(name = "two_factor_repository.find", level = "debug", skip_all)]
+    async fn find(&self, user_id: &Uuid) -> Result<Option<TwoFactorSecret>, AppError> {
+        let row = sqlx::query_as::<_, (String, bool)>(
+            "SELECT secret, enabled_at IS NOT NULL FROM user_two_factor WHERE user_id = $1",
+        )
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(row.map(|(secret, enabled)| TwoFactorSecret { secret, enabled }))
+    }
+
+    #[tracing::instrument(name = "two_factor_repository.enable", level = "debug", skip_all)]
+    async fn enable(&self, user_id: &Uuid, used_step: i64, recovery_code_hashes: &[String]) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await.map_err(AppError::from)?;
+
+        sqlx::query(
+            "UPDATE user_two_factor SET enabled_at = CURRENT_TIMESTAMP, last_used_step = $2 WHERE user_id = $1",
+        )
+        .bind(user_id)
+        .bind(used_step)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+
+        sqlx::query("DELETE FROM user_recovery_codes WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::from)?;
+
+        sqlx::query("INSERT INTO user_recovery_codes (user_id, code_hash) SELECT $1, UNNEST($2::varchar[])")
+            .bind(user_id)
+            .bind(recovery_code_hashes)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::from)?;
+
+        tx.commit().await.map_err(AppError::from)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "two_factor_repository.use_step", level = "debug", skip_all)]
+    async fn use_step(&self, user_id: &Uuid, step: i64) -> Result<bool, AppError> {
+        // A condição no UPDATE impede que duas requisições simultâneas usem o
+        // mesmo código
+        let result = sqlx::query(
+            r#"
+            UPDATE user_two_factor SET last_used_step = $2
+            WHERE user_id = $1 AND (last_used_step IS NULL OR last_used_step < $2)
+            "#,
+        )
+        .bind(user_id)
+        .bind(step)
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[tracing::instrument(name = "two_factor_repository.use_recovery_code", level = "debug", skip_all)]
+    async fn use_recovery_code(&self, user_id: &Uuid, code_hash: &str) -> Result<bool, AppError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE user_recovery_codes SET used_at = CURRENT_TIMESTAMP
+            WHERE user_id = $1 AND code_hash = $2 AND used_at IS NULL
+            "#,
+        )
+        .bind(user_id)
+        .bind(code_hash)
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[tracing::instrument(name = "two_factor_repository.delete", level = "debug", skip_all)]
+    async fn delete(&self, user_id: &Uuid) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await.map_err(AppError::from)?;
+
+        sqlx::query("DELETE FROM user_recovery_codes WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::from)?;
+
+        sqlx::query("DELETE FROM user_two_factor WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::from)?;
+
+        tx.commit().await.map_err(AppError::from)?;
+
+        Ok(())
+    }
+}
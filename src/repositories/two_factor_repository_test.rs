@@ -0,0 +1,104 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::two_factor::TwoFactorSecret;
+use crate::models::user::CreateUserDto;
+use crate::repositories::{
+    test_helpers::get_test_db_pool,
+    two_factor_repository::{PgTwoFactorRepository, TwoFactorRepository},
+    user_repository::{PgUserRepository, UserRepository},
+};
+
+async fn create_user(pool: &PgPool) -> Uuid {
+    PgUserRepository::new(pool.clone())
+        .create(
+            &CreateUserDto {
+                name: "Usuária".to_string(),
+                email: "usuaria@example.com".to_string(),
+                password: "senha123".to_string(),
+            },
+            "hash".to_string(),
+        )
+        .await
+        .unwrap()
+        .id
+}
+
+fn hashes(codes: &[&str]) -> Vec<String> {
+    codes.iter().map(|code| code.to_string()).collect()
+}
+
+#[tokio::test]
+async fn test_pending_secret_is_replaced_until_enabled() {
+    let pool = get_test_db_pool().await;
+    let user_id = create_user(&pool).await;
+    let repository = PgTwoFactorRepository::new(pool.clone());
+    let user_repository = PgUserRepository::new(pool);
+
+    assert!(repository.find(&user_id).await.unwrap().is_none());
+    assert!(repository.save_pending_secret(&user_id, "SEGREDO1").await.unwrap());
+    assert!(repository.save_pending_secret(&user_id, "SEGREDO2").await.unwrap());
+    assert_eq!(
+        repository.find(&user_id).await.unwrap(),
+        Some(TwoFactorSecret {
+            secret: "SEGREDO2".to_string(),
+            enabled: false,
+        })
+    );
+    assert!(!user_repository.two_factor_enabled(&user_id).await.unwrap());
+
+    repository.enable(&user_id, 100, &hashes(&["a", "b"])).await.unwrap();
+
+    // Depois de ativo, o segredo só muda desativando antes
+    assert!(!repository.save_pending_secret(&user_id, "SEGREDO3").await.unwrap());
+    assert_eq!(
+        repository.find(&user_id).await.unwrap(),
+        Some(TwoFactorSecret {
+            secret: "SEGREDO2".to_string(),
+            enabled: true,
+        })
+    );
+    assert!(user_repository.two_factor_enabled(&user_id).await.unwrap());
+}
+
+#[tokio::test]
+async fn test_each_step_is_used_only_once() {
+    let pool = get_test_db_pool().await;
+    let user_id = create_user(&pool).await;
+    let repository = PgTwoFactorRepository::new(pool);
+
+    repository.save_pending_secret(&user_id, "SEGREDO").await.unwrap();
+    repository.enable(&user_id, 100, &[]).await.unwrap();
+
+    // O código usado na ativação não serve para o login
+    assert!(!repository.use_step(&user_id, 100).await.unwrap());
+    assert!(repository.use_step(&user_id, 101).await.unwrap());
+    assert!(!repository.use_step(&user_id, 101).await.unwrap());
+    assert!(!repository.use_step(&user_id, 99).await.unwrap());
+}
+
+#[tokio::test]
+async fn test_recovery_codes_are_single_use_and_removed_on_delete() {
+    let pool = get_test_db_pool().await;
+    let user_id = create_user(&pool).await;
+    let repository = PgTwoFactorRepository::new(pool.clone());
+
+    repository.save_pending_secret(&user_id, "SEGREDO").await.unwrap();
+    repository.enable(&user_id, 1, &hashes(&["hash-a", "hash-b"])).await.unwrap();
+
+    assert!(repository.use_recovery_code(&user_id, "hash-a").await.unwrap());
+    assert!(!repository.use_recovery_code(&user_id, "hash-a").await.unwrap());
+    assert!(!repository.use_recovery_code(&user_id, "hash-c").await.unwrap());
+    assert!(!repository.use_recovery_code(&Uuid::new_v4(), "hash-b").await.unwrap());
+
+    repository.delete(&user_id).await.unwrap();
+
+    assert!(repository.find(&user_id).await.unwrap().is_none());
+    assert!(!repository.use_recovery_code(&user_id, "hash-b").await.unwrap());
+    let remaining: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM user_recovery_codes WHERE user_id = $1")
+        .bind(user_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(remaining, 0);
+}
@@ -0,0 +1,55 @@
+use sqlx::{PgConnection, PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::error::AppError;
+
+/// Transação compartilhada pelos repositórios
+///
+/// Os comandos executados em `conn()` só são gravados em `commit()`; se a
+/// unidade for descartada antes disso, a transação é desfeita.
+pub struct UnitOfWork {
+    tx: Transaction<'static, Postgres>,
+}
+
+impl UnitOfWork {
+    /// Abre uma transação no pool
+    pub async fn begin(pool: &PgPool) -> Result<Self, AppError> {
+        let tx = pool
+            .begin()
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(Self { tx })
+    }
+
+    /// Conexão da transação, usada para executar os comandos
+    pub fn conn(&mut self) -> &mut PgConnection {
+        &mut self.tx
+    }
+
+    /// Serializa as alterações das listas de um usuário para um mesmo livro
+    ///
+    /// O bloqueio dura até o fim da transação e garante que verificações como
+    /// "o livro não está na outra lista" não sejam invalidadas por uma
+    /// requisição concorrente.
+    pub async fn lock_user_book(&mut self, user_id: &Uuid, book_id: &Uuid) -> Result<(), AppError> {
+        sqlx::query!(
+            "SELECT pg_advisory_xact_lock(hashtextextended($1::uuid::text || $2::uuid::text, 0))",
+            user_id,
+            book_id
+        )
+        .fetch_one(self.conn())
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Confirma a transação
+    pub async fn commit(self) -> Result<(), AppError> {
+        self.tx
+            .commit()
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+}
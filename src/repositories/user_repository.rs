@@ -1,13 +1,26 @@
 use async_trait::async_trait;
 use sqlx::PgPool;
 
-use crate::error::AppError;
-use crate::models::user::{CreateUserDto, User};
+use crate::error::{AppError, ErrorCode};
+use crate::i18n::messages;
+use crate::repositories::db_error::{constraint_violation, ConstraintKind};
+use uuid::Uuid;
+
+use crate::models::user::{CreateUserDto, User, UserLocation};
 
 #[async_trait]
 pub trait UserRepository: Send + Sync + 'static {
     async fn create(&self, user: &CreateUserDto, hash_password: String) -> Result<User, AppError>;
     async fn find_by_email(&self, email: &str) -> Result<Option<User>, AppError>;
+    async fn find_by_id(&self, user_id: &Uuid) -> Result<Option<User>, AppError>;
+    /// Define ou remove (`None`) a localização do usuário
+    async fn update_location(&self, user_id: &Uuid, location: Option<UserLocation>) -> Result<bool, AppError>;
+    /// Substitui o hash da senha do usuário
+    async fn update_password(&self, user_id: &Uuid, hash_password: String) -> Result<bool, AppError>;
+    /// Se o login do usuário exige a verificação em duas etapas
+    async fn two_factor_enabled(&self, user_id: &Uuid) -> Result<bool, AppError>;
+    /// Suspende (`true`) ou reativa (`false`) a conta do usuário
+    async fn set_banned(&self, user_id: &Uuid, banned: bool) -> Result<bool, AppError>;
 }
 
 pub struct PgUserRepository {
@@ -22,6 +35,7 @@ impl PgUserRepository {
 
 #[async_trait]
 impl UserRepository for PgUserRepository {
+    #[tracing::instrument(name = "user_repository.create", level = "debug", skip_all)]
     async fn create(&self, user: &CreateUserDto, hash_password: String) -> Result<User, AppError> {
         let result = sqlx::query_as::<_, User>(
             "INSERT INTO users (name, email, hash_password) VALUES ($1, $2, $3) RETURNING *",
@@ -31,17 +45,17 @@ impl UserRepository for PgUserRepository {
         .bind(&hash_password)
         .fetch_one(&self.pool)
         .await
-        .map_err(|e| {
-            if e.to_string().contains("duplicate key") {
-                AppError::ValidationError("Email já está em uso".to_string())
-            } else {
-                AppError::DatabaseError(e.to_string())
+        .map_err(|e| match constraint_violation(&e) {
+            Some((ConstraintKind::Unique, _)) => {
+                AppError::Conflict(ErrorCode::EmailAlreadyInUse, messages::EMAIL_ALREADY_IN_USE.text())
             }
+            _ => AppError::DatabaseError(e.to_string()),
         })?;
 
         Ok(result)
     }
 
+    #[tracing::instrument(name = "user_repository.find_by_email", level = "debug", skip_all)]
     async fn find_by_email(&self, email: &str) -> Result<Option<User>, AppError> {
         let result = sqlx::query_as::<_, User>("SELECT * FROM users WHERE email = $1")
             .bind(email)
@@ -51,4 +65,76 @@ impl UserRepository for PgUserRepository {
 
         Ok(result)
     }
+
+    #[tracing::instrument(name = "user_repository.find_by_id", level = "debug", skip_all)]
+    async fn find_by_id(&self, user_id: &Uuid) -> Result<Option<User>, AppError> {
+        let result = sqlx::query_as::<_, User>("SELECT * FROM users WHERE id = $1")
+            .bind(user_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+
+        Ok(result)
+    }
+
+    #[tracing::instrument(name = "user_repository.update_location", level = "debug", skip_all)]
+    async fn update_location(&self, user_id: &Uuid, location: Option<UserLocation>) -> Result<bool, AppError> {
+        let result = sqlx::query(
+            "UPDATE users SET latitude = $2, longitude = $3, updated_at = CURRENT_TIMESTAMP WHERE id = $1",
+        )
+        .bind(user_id)
+        .bind(location.map(|l| l.latitude))
+        .bind(location.map(|l| l.longitude))
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[tracing::instrument(name = "user_repository.update_password", level = "debug", skip_all)]
+    async fn update_password(&self, user_id: &Uuid, hash_password: String) -> Result<bool, AppError> {
+        let result = sqlx::query(
+            "UPDATE users SET hash_password = $2, updated_at = CURRENT_TIMESTAMP WHERE id = $1",
+        )
+        .bind(user_id)
+        .bind(&hash_password)
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[tracing::instrument(name = "user_repository.two_factor_enabled", level = "debug", skip_all)]
+    async fn two_factor_enabled(&self, user_id: &Uuid) -> Result<bool, AppError> {
+        let (enabled,) = sqlx::query_as::<_, (bool,)>(
+            "SELECT EXISTS (SELECT 1 FROM user_two_factor WHERE user_id = $1 AND enabled_at IS NOT NULL)",
+        )
+        .bind(user_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(enabled)
+    }
+
+    #[tracing::instrument(name = "user_repository.set_banned", level = "debug", skip_all)]
+    async fn set_banned(&self, user_id: &Uuid, banned: bool) -> Result<bool, AppError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE users
+            SET banned_at = CASE WHEN $2 THEN COALESCE(banned_at, CURRENT_TIMESTAMP) END,
+                updated_at = CURRENT_TIMESTAMP
+            WHERE id = $1
+            "#,
+        )
+        .bind(user_id)
+        .bind(banned)
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(result.rows_affected() > 0)
+    }
 }
@@ -1,8 +1,8 @@
 use crate::{
-    error::AppError,
+    error::{AppError, ErrorCode},
     models::user::CreateUserDto,
     repositories::{
-        test_helpers::{get_test_db_pool, get_test_mutex},
+        test_helpers::get_test_db_pool,
         user_repository::{PgUserRepository, UserRepository},
     },
 };
@@ -18,9 +18,6 @@ async fn setup_test_repository() -> PgUserRepository {
 
 #[tokio::test]
 async fn test_create_user() {
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-
     let user_repository = setup_test_repository().await;
 
     let user = CreateUserDto {
@@ -45,9 +42,6 @@ async fn test_create_user() {
 
 #[tokio::test]
 async fn test_find_by_email() {
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-
     let user_repository = setup_test_repository().await;
 
     // Cria um usuário para teste
@@ -88,9 +82,6 @@ async fn test_find_by_email() {
 
 #[tokio::test]
 async fn test_email_exists() {
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-
     let user_repository = setup_test_repository().await;
 
     // Cria um usuário para teste
@@ -125,9 +116,6 @@ async fn test_email_exists() {
 
 #[tokio::test]
 async fn test_duplicate_email() {
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-
     let user_repository = setup_test_repository().await;
 
     // Cria um primeiro usuário
@@ -156,7 +144,42 @@ async fn test_duplicate_email() {
 
     // Verifica se é o tipo de erro esperado
     match result {
-        Err(AppError::ValidationError(_)) => (),
-        _ => panic!("Esperava erro de validação para email duplicado"),
+        Err(AppError::Conflict(ErrorCode::EmailAlreadyInUse, _)) => (),
+        _ => panic!("Esperava conflito para email duplicado"),
     }
 }
+
+#[tokio::test]
+async fn test_update_password() {
+    let user_repository = setup_test_repository().await;
+
+    let user = CreateUserDto {
+        name: "Rehash Test".to_string(),
+        email: "rehash@example.com".to_string(),
+        password: "password".to_string(),
+    };
+    let created_user = user_repository
+        .create(&user, "hash_antigo".to_string())
+        .await
+        .expect("Falha ao criar usuário");
+
+    let updated = user_repository
+        .update_password(&created_user.id, "hash_novo".to_string())
+        .await
+        .expect("Falha ao atualizar a senha");
+    assert!(updated);
+
+    let found_user = user_repository
+        .find_by_email("rehash@example.com")
+        .await
+        .expect("Falha ao buscar usuário pelo email")
+        .unwrap();
+    assert_eq!(found_user.hash_password, "hash_novo");
+
+    // Usuário inexistente
+    let updated = user_repository
+        .update_password(&Uuid::new_v4(), "hash_novo".to_string())
+        .await
+        .expect("Falha ao atualizar a senha");
+    assert!(!updated);
+}
@@ -0,0 +1,184 @@
+use std::convert::Infallible;
+
+use axum::{
+    extract::Request,
+    handler::Handler,
+    response::IntoResponse,
+    routing::{on, MethodFilter, MethodRouter, Route},
+    Router,
+};
+use tower::{Layer, Service};
+use utoipa::openapi::path::{PathItem, PathItemType, Paths};
+
+use crate::state::AppState;
+
+/// Cria uma [`ApiRoute`] a partir de um handler anotado com `#[utoipa::path]`
+///
+/// Recebe o módulo e o nome do handler, por exemplo
+/// `api_route!(trade_handler, accept_trade)`.
+#[macro_export]
+macro_rules! api_route {
+    ($module:ident, $handler:ident) => {
+        ::paste::paste! {
+            $crate::routes::api_router::ApiRoute::new::<$module::[<__path_ $handler>], _, _>($module::$handler)
+        }
+    };
+}
+
+/// Rota cujo método e caminho vêm da anotação `#[utoipa::path]` do handler
+pub struct ApiRoute {
+    path: String,
+    item: PathItem,
+    method_router: MethodRouter<AppState>,
+}
+
+impl ApiRoute {
+    pub fn new<P, H, T>(handler: H) -> Self
+    where
+        P: utoipa::Path,
+        H: Handler<T, AppState>,
+        T: 'static,
+    {
+        let path = P::path();
+        let item = P::path_item(None);
+
+        let mut operations = item.operations.keys();
+        let method = match (operations.next(), operations.next()) {
+            (Some(method), None) => method_filter(method),
+            _ => panic!("a anotação de {} deve declarar exatamente um método", path),
+        };
+
+        Self {
+            path,
+            item,
+            method_router: on(method, handler),
+        }
+    }
+
+    /// Aplica uma camada somente a esta rota
+    pub fn layer<L>(mut self, layer: L) -> Self
+    where
+        L: Layer<Route> + Clone + Send + 'static,
+        L::Service: Service<Request> + Clone + Send + 'static,
+        <L::Service as Service<Request>>::Response: IntoResponse + 'static,
+        <L::Service as Service<Request>>::Error: Into<Infallible> + 'static,
+        <L::Service as Service<Request>>::Future: Send + 'static,
+    {
+        self.method_router = self.method_router.layer(layer);
+        self
+    }
+}
+
+/// Router que registra as rotas junto com a sua documentação
+///
+/// O router do Axum e os caminhos da especificação OpenAPI são montados a partir
+/// das mesmas [`ApiRoute`], então não há rota sem documentação nem o contrário.
+/// Os caminhos do router são relativos a `prefix`, onde ele será aninhado.
+pub struct ApiRouter {
+    prefix: &'static str,
+    router: Router<AppState>,
+    paths: Paths,
+}
+
+impl ApiRouter {
+    pub fn new(prefix: &'static str) -> Self {
+        Self {
+            prefix,
+            router: Router::new(),
+            paths: Paths::new(),
+        }
+    }
+
+    pub fn route(mut self, route: ApiRoute) -> Self {
+        let relative = route.path.strip_prefix(self.prefix).unwrap_or_else(|| {
+            panic!("o caminho {} não está sob o prefixo {}", route.path, self.prefix)
+        });
+        self.router = self.router.route(&axum_path(relative), route.method_router);
+        self.add_path(route.path, route.item);
+        self
+    }
+
+    /// Junta as rotas de outro router com o mesmo prefixo
+    pub fn merge(mut self, other: ApiRouter) -> Self {
+        assert_eq!(self.prefix, other.prefix, "só é possível juntar routers com o mesmo prefixo");
+        self.router = self.router.merge(other.router);
+        self.merge_paths(other.paths);
+        self
+    }
+
+    /// Aninha um router sob o prefixo dele
+    pub fn nest(mut self, other: ApiRouter) -> Self {
+        let prefix = other
+            .prefix
+            .strip_prefix(self.prefix)
+            .unwrap_or_else(|| panic!("o prefixo {} não está sob {}", other.prefix, self.prefix));
+        self.router = self.router.nest(prefix, other.router);
+        self.merge_paths(other.paths);
+        self
+    }
+
+    /// Aplica uma camada a todas as rotas registradas até aqui
+    pub fn layer<L>(mut self, layer: L) -> Self
+    where
+        L: Layer<Route> + Clone + Send + 'static,
+        L::Service: Service<Request> + Clone + Send + 'static,
+        <L::Service as Service<Request>>::Response: IntoResponse + 'static,
+        <L::Service as Service<Request>>::Error: Into<Infallible> + 'static,
+        <L::Service as Service<Request>>::Future: Send + 'static,
+    {
+        self.router = self.router.layer(layer);
+        self
+    }
+
+    /// Caminhos documentados, com o prefixo
+    pub fn paths(&self) -> &Paths {
+        &self.paths
+    }
+
+    /// Separa o router do Axum dos caminhos documentados
+    pub fn into_parts(self) -> (Router<AppState>, Paths) {
+        (self.router, self.paths)
+    }
+
+    fn merge_paths(&mut self, paths: Paths) {
+        for (path, item) in paths.paths {
+            self.add_path(path, item);
+        }
+    }
+
+    // Métodos diferentes do mesmo caminho ficam no mesmo item da especificação
+    fn add_path(&mut self, path: String, item: PathItem) {
+        match self.paths.paths.get_mut(&path) {
+            Some(existing) => existing.operations.extend(item.operations),
+            None => {
+                self.paths.paths.insert(path, item);
+            }
+        }
+    }
+}
+
+fn method_filter(method: &PathItemType) -> MethodFilter {
+    match method {
+        PathItemType::Get => MethodFilter::GET,
+        PathItemType::Post => MethodFilter::POST,
+        PathItemType::Put => MethodFilter::PUT,
+        PathItemType::Delete => MethodFilter::DELETE,
+        PathItemType::Options => MethodFilter::OPTIONS,
+        PathItemType::Head => MethodFilter::HEAD,
+        PathItemType::Patch => MethodFilter::PATCH,
+        PathItemType::Trace => MethodFilter::TRACE,
+        PathItemType::Connect => MethodFilter::CONNECT,
+    }
+}
+
+/// Converte os parâmetros do OpenAPI (`{trade_id}`) para a sintaxe do Axum (`:trade_id`)
+pub fn axum_path(openapi_path: &str) -> String {
+    openapi_path
+        .split('/')
+        .map(|segment| match segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+            Some(param) => format!(":{}", param),
+            None => segment.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
@@ -1,37 +1,40 @@
-use std::sync::Arc;
+use axum::middleware::from_fn_with_state;
 
-use axum::{routing::post, Router};
-use sqlx::PgPool;
+use crate::api_route;
+use crate::handlers::{auth_handler, jwks_handler, oauth_handler};
+use crate::middleware::rate_limit::{rate_limit_middleware, RouteRateLimit};
+use crate::routes::{api_router::ApiRouter, API_V1};
+use crate::services::rate_limit_service::{LOGIN_LIMIT, REGISTER_LIMIT};
+use crate::state::AppState;
 
-use crate::config::Config;
-use crate::handlers::auth_handler::AuthHandler;
-use crate::repositories::user_repository::PgUserRepository;
-use crate::services::auth_service::AuthServiceImpl;
-use crate::services::password_service::create_password_service;
+pub fn auth_routes(state: &AppState) -> ApiRouter {
+    let register_limit = RouteRateLimit::new("register", REGISTER_LIMIT, state);
+    let login_limit = RouteRateLimit::new("login", LOGIN_LIMIT, state);
+    let oauth_limit = RouteRateLimit::new("oauth", LOGIN_LIMIT, state);
 
-pub fn auth_routes(pool: Arc<PgPool>) -> Router {
-    let config = Config::from_env().expect("Falha ao carregar configuração");
-
-    let user_repository = Arc::new(PgUserRepository::new(pool.as_ref().clone()));
-    let password_service = create_password_service();
-
-    let auth_service = Arc::new(AuthServiceImpl::new(
-        user_repository,
-        password_service,
-        config,
-    ));
-
-    let auth_handler = Arc::new(AuthHandler::new(auth_service));
-
-    let handler_clone = auth_handler.clone();
-
-    Router::new()
+    ApiRouter::new(API_V1)
         .route(
-            "/api/auth/register",
-            post(move |body| async move { handler_clone.register(body).await }),
+            api_route!(auth_handler, register)
+                .layer(from_fn_with_state(register_limit, rate_limit_middleware)),
         )
         .route(
-            "/api/auth/login",
-            post(move |body| async move { auth_handler.login(body).await }),
+            api_route!(auth_handler, login)
+                .layer(from_fn_with_state(login_limit, rate_limit_middleware)),
         )
+        // Login com Google ou GitHub: o usuário é redirecionado ao provedor e
+        // volta para o callback, que responde como o login com senha
+        .route(
+            api_route!(oauth_handler, authorize)
+                .layer(from_fn_with_state(oauth_limit.clone(), rate_limit_middleware)),
+        )
+        .route(
+            api_route!(oauth_handler, callback)
+                .layer(from_fn_with_state(oauth_limit, rate_limit_middleware)),
+        )
+}
+
+/// Rotas fora do versionamento, cujo caminho é fixado por padrão
+pub fn well_known_routes() -> ApiRouter {
+    // Chaves públicas para que outros serviços verifiquem os tokens
+    ApiRouter::new("").route(api_route!(jwks_handler, jwks))
 }
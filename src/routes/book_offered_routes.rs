@@ -1,65 +1,31 @@
-use std::sync::Arc;
-
-use axum::{
-    routing::{delete, post},
-    Router,
-};
-use sqlx::PgPool;
+use axum::extract::DefaultBodyLimit;
 
 use crate::{
-    handlers::book_offered_handler::BookOfferedHandler,
-    repositories::{
-        book_repository::PgBookRepository, 
-        books_offered_repository::PgBooksOfferedRepository,
-        books_wanted_repository::PgBooksWantedRepository
-    },
-    routes::protect_routes,
-    services::{
-        book_offered_service::BookOfferedServiceImpl,
-        google_book_service::GoogleBookServiceImpl,
-        http_service::HttpServiceImpl,
-    },
+    api_route,
+    handlers::{book_offered_handler, book_photo_handler},
+    routes::{api_router::ApiRouter, protect_routes, API_V1},
+    state::AppState,
 };
 
-pub fn book_offered_routes(pool: Arc<PgPool>) -> Router {
-    // Repositórios
-    let book_repository = Arc::new(PgBookRepository::new(pool.as_ref().clone()));
-    let books_offered_repository = Arc::new(PgBooksOfferedRepository::new(pool.as_ref().clone()));
-    let books_wanted_repository = Arc::new(PgBooksWantedRepository::new(pool.as_ref().clone()));
-    
-    // Serviço HTTP
-    let http_service = Arc::new(HttpServiceImpl::new());
-    
-    // Serviço do Google Books
-    let google_book_service = Arc::new(GoogleBookServiceImpl::new(http_service));
-    
-    // Serviço de Livros Possuídos
-    let book_offered_service = Arc::new(BookOfferedServiceImpl::new(
-        book_repository,
-        books_offered_repository,
-        books_wanted_repository,
-        google_book_service,
-    ));
-
-    // Handler
-    let book_offered_handler = Arc::new(BookOfferedHandler::new(book_offered_service));
-    let handler_clone = book_offered_handler.clone();
-    let handler_clone2 = book_offered_handler.clone();
+pub fn book_offered_routes(state: &AppState) -> ApiRouter {
+    let photo_max_bytes = state.config.photo_max_bytes;
 
     // Configurar rotas protegidas
     protect_routes(
-        Router::new()
+        ApiRouter::new(API_V1)
+            .route(api_route!(book_offered_handler, add_book_to_offered))
+            .route(api_route!(book_offered_handler, add_books_to_offered))
+            .route(api_route!(book_offered_handler, remove_books_from_offered))
+            .route(api_route!(book_offered_handler, remove_book_from_offered))
+            .route(api_route!(book_offered_handler, update_book_offered))
+            .route(api_route!(book_offered_handler, remove_copy_from_offered))
             .route(
-                "/api/books/offered",
-                post(move |user_id, body| async move {
-                    handler_clone.add_book_to_offered(user_id, body).await
-                }),
+                api_route!(book_photo_handler, add_photo)
+                    // O limite padrão do Axum (2 MB) é menor que o tamanho máximo configurado
+                    .layer(DefaultBodyLimit::max(photo_max_bytes + 1)),
             )
-            .route(
-                "/api/books/offered/:book_id",
-                delete(move |user_id, path| async move {
-                    handler_clone2.remove_book_from_offered(user_id, path).await
-                }),
-            ),
+            .route(api_route!(book_photo_handler, get_photo))
+            .route(api_route!(book_photo_handler, remove_photo)),
+        state,
     )
-} 
\ No newline at end of file
+}
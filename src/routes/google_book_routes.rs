@@ -1,26 +1,25 @@
-use std::sync::Arc;
-
-use axum::{routing::post, Router};
+use axum::middleware::from_fn_with_state;
 
 use crate::{
-    handlers::google_book_handler::GoogleBookHandler, routes::protect_routes,
-    services::google_book_service::GoogleBookServiceImpl, services::http_service::HttpServiceImpl,
+    api_route,
+    handlers::google_book_handler,
+    middleware::rate_limit::{rate_limit_middleware, RouteRateLimit},
+    routes::{api_router::ApiRouter, protect_routes, API_V1},
+    services::rate_limit_service::BOOK_SEARCH_LIMIT,
+    state::AppState,
 };
 
-pub fn google_book_routes() -> Router {
-    // Serviço HTTP
-    let http_service = Arc::new(HttpServiceImpl::new());
-
-    // Serviço do Google Books
-    let book_service = Arc::new(GoogleBookServiceImpl::new(http_service));
-
-    // Handler
-    let book_handler = Arc::new(GoogleBookHandler::new(book_service));
-    let handler_clone = book_handler.clone();
+pub fn google_book_routes(state: &AppState) -> ApiRouter {
+    // Limite de buscas por usuário, já que cada busca consulta a API do Google
+    let search_limit = RouteRateLimit::new("book_search", BOOK_SEARCH_LIMIT, state);
 
-    // Configurar rota protegida
-    protect_routes(Router::new().route(
-        "/api/books/search",
-        post(move |body| async move { handler_clone.search_books(body).await }),
-    ))
+    // Configurar rota protegida; o limite fica dentro da autenticação para
+    // conhecer o usuário
+    protect_routes(
+        ApiRouter::new(API_V1).route(
+            api_route!(google_book_handler, search_books)
+                .layer(from_fn_with_state(search_limit, rate_limit_middleware)),
+        ),
+        state,
+    )
 }
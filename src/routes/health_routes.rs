@@ -0,0 +1,9 @@
+use crate::{api_route, handlers::health_handler, routes::api_router::ApiRouter};
+
+pub fn health_routes() -> ApiRouter {
+    // Rotas públicas para o orquestrador e o Prometheus
+    ApiRouter::new("")
+        .route(api_route!(health_handler, live))
+        .route(api_route!(health_handler, ready))
+        .route(api_route!(health_handler, metrics))
+}
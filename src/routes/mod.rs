@@ -1,18 +1,67 @@
+pub mod api_router;
 pub mod auth_routes;
 pub mod book_offered_routes;
 pub mod book_routes;
 pub mod book_wanted_routes;
 pub mod google_book_routes;
+pub mod health_routes;
+pub mod shelf_routes;
 pub mod trade_routes;
+pub mod two_factor_routes;
+pub mod user_routes;
 
-use axum::{middleware::from_fn, Router};
+use axum::middleware::from_fn_with_state;
 
 use crate::middleware::auth_middleware::auth_middleware;
+use crate::state::AppState;
+
+use self::{
+    api_router::ApiRouter,
+    auth_routes::{auth_routes, well_known_routes}, book_offered_routes::book_offered_routes, book_routes::book_routes,
+    book_wanted_routes::book_wanted_routes, google_book_routes::google_book_routes,
+    health_routes::health_routes, shelf_routes::shelf_routes, trade_routes::trade_routes, two_factor_routes::two_factor_routes,
+    user_routes::user_routes,
+};
+
+/// Prefixo da primeira versão da API
+///
+/// Uma versão nova ganha o próprio prefixo e o próprio conjunto de rotas, montado
+/// ao lado deste, para que os clientes da v1 continuem funcionando.
+pub const API_V1: &str = "/api/v1";
+
+/// Todas as rotas documentadas do aplicativo
+///
+/// A API fica sob o prefixo da versão; health, métricas e JWKS ficam fora dele.
+pub fn api_routes(state: &AppState) -> ApiRouter {
+    ApiRouter::new("")
+        .nest(v1_routes(state))
+        .merge(health_routes())
+        .merge(well_known_routes())
+}
+
+/// Rotas da versão 1, aninhadas em `API_V1`
+pub fn v1_routes(state: &AppState) -> ApiRouter {
+    // Definir rotas públicas (sem autenticação)
+    let public_routes = auth_routes(state);
+
+    // Definir rotas protegidas (com autenticação)
+    let protected_routes = ApiRouter::new(API_V1)
+        .merge(google_book_routes(state))
+        .merge(two_factor_routes(state))
+        .merge(book_offered_routes(state))
+        .merge(book_wanted_routes(state))
+        .merge(book_routes(state))
+        .merge(shelf_routes(state))
+        .merge(trade_routes(state))
+        .merge(user_routes(state));
+
+    public_routes.merge(protected_routes)
+}
 
 /// Função auxiliar para aplicar o middleware de autenticação a qualquer rota
 ///
 /// Esta função facilita a proteção de rotas, mantendo consistência na aplicação
 /// do middleware de autenticação em todo o projeto.
-pub fn protect_routes(router: Router) -> Router {
-    router.layer(from_fn(auth_middleware))
+pub fn protect_routes(router: ApiRouter, state: &AppState) -> ApiRouter {
+    router.layer(from_fn_with_state(state.clone(), auth_middleware))
 }
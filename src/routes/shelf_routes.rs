@@ -0,0 +1,25 @@
+use axum::extract::DefaultBodyLimit;
+
+use crate::{
+    api_route,
+    handlers::shelf_handler,
+    routes::{api_router::ApiRouter, protect_routes, API_V1},
+    state::AppState,
+};
+
+/// Tamanho máximo do arquivo da estante importado (2 MB)
+const SHELF_IMPORT_MAX_BYTES: usize = 2 * 1024 * 1024;
+
+pub fn shelf_routes(state: &AppState) -> ApiRouter {
+    // Configurar rotas protegidas
+    protect_routes(
+        ApiRouter::new(API_V1)
+            .route(
+                api_route!(shelf_handler, import_shelf)
+                    // O arquivo não é JSON e pode passar do limite dos corpos JSON
+                    .layer(DefaultBodyLimit::max(SHELF_IMPORT_MAX_BYTES)),
+            )
+            .route(api_route!(shelf_handler, export_shelf)),
+        state,
+    )
+}
@@ -1,33 +1,21 @@
-use std::sync::Arc;
-use axum::{routing::get, Router};
-use sqlx::PgPool;
-
 use crate::{
-    handlers::trade_handler::TradeHandler,
-    repositories::trade_repository::PgTradeRepository,
-    routes::protect_routes,
-    services::trade_service::TradeServiceImpl,
+    api_route,
+    handlers::trade_handler,
+    routes::{api_router::ApiRouter, protect_routes, API_V1},
+    state::AppState,
 };
 
-pub fn trade_routes(pool: Arc<PgPool>) -> Router {
-    // Repositório
-    let trade_repository = Arc::new(PgTradeRepository::new(pool.as_ref().clone()));
-    
-    // Serviço
-    let trade_service = Arc::new(TradeServiceImpl::new(trade_repository));
-    
-    // Handler
-    let trade_handler = Arc::new(TradeHandler::new(trade_service));
-    let handler_clone = trade_handler.clone();
-
+pub fn trade_routes(state: &AppState) -> ApiRouter {
     // Configurar rotas protegidas
     protect_routes(
-        Router::new()
-            .route(
-                "/api/trades/possible",
-                get(move |user_id| async move {
-                    handler_clone.get_possible_trades(user_id).await
-                }),
-            ),
+        ApiRouter::new(API_V1)
+            .route(api_route!(trade_handler, get_possible_trades))
+            .route(api_route!(trade_handler, get_user_trades))
+            .route(api_route!(trade_handler, propose_trade))
+            .route(api_route!(trade_handler, accept_trade))
+            .route(api_route!(trade_handler, reject_trade))
+            .route(api_route!(trade_handler, cancel_trade))
+            .route(api_route!(trade_handler, complete_trade)),
+        state,
     )
-} 
\ No newline at end of file
+}
@@ -0,0 +1,31 @@
+use axum::middleware::from_fn_with_state;
+
+use crate::{
+    api_route,
+    handlers::two_factor_handler,
+    middleware::rate_limit::{rate_limit_middleware, RouteRateLimit},
+    routes::{api_router::ApiRouter, protect_routes, API_V1},
+    services::rate_limit_service::LOGIN_LIMIT,
+    state::AppState,
+};
+
+pub fn two_factor_routes(state: &AppState) -> ApiRouter {
+    let verify_limit = RouteRateLimit::new("two_factor", LOGIN_LIMIT, state);
+
+    // Ativação e desativação exigem o usuário autenticado
+    let protected = protect_routes(
+        ApiRouter::new(API_V1)
+            .route(api_route!(two_factor_handler, setup))
+            .route(api_route!(two_factor_handler, enable))
+            .route(api_route!(two_factor_handler, disable)),
+        state,
+    );
+
+    // Segunda etapa do login: o desafio substitui o token de acesso
+    ApiRouter::new(API_V1)
+        .route(
+            api_route!(two_factor_handler, verify)
+                .layer(from_fn_with_state(verify_limit, rate_limit_middleware)),
+        )
+        .merge(protected)
+}
@@ -0,0 +1,16 @@
+use crate::{
+    api_route,
+    handlers::user_handler,
+    routes::{api_router::ApiRouter, protect_routes, API_V1},
+    state::AppState,
+};
+
+pub fn user_routes(state: &AppState) -> ApiRouter {
+    // Configurar rotas protegidas
+    protect_routes(
+        ApiRouter::new(API_V1)
+            .route(api_route!(user_handler, update_location))
+            .route(api_route!(user_handler, clear_location)),
+        state,
+    )
+}
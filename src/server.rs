@@ -0,0 +1,136 @@
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::Router;
+use thiserror::Error;
+use tokio::{net::TcpListener, sync::Notify};
+
+use crate::{
+    app::{create_app_with_state, create_database_pool},
+    config::Config,
+    state::AppState,
+};
+
+/// Tempo máximo para devolver as conexões do pool no desligamento
+const POOL_CLOSE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Falhas que impedem a API de iniciar ou encerram o servidor
+#[derive(Error, Debug)]
+pub enum StartupError {
+    #[error("Falha ao carregar as chaves do JWT: {0}")]
+    JwtKeys(String),
+
+    #[error("Parâmetros do Argon2 inválidos: {0}")]
+    PasswordHashing(String),
+
+    #[error("Falha ao conectar ao banco de dados: {0}")]
+    Database(#[from] sqlx::Error),
+
+    #[error("Falha ao vincular à porta {port}: {source}")]
+    Bind { port: u16, source: std::io::Error },
+
+    #[error("Falha no servidor HTTP: {0}")]
+    Server(#[source] std::io::Error),
+}
+
+/// Conecta ao banco, sobe o servidor e o desliga ao receber SIGTERM ou SIGINT
+///
+/// No desligamento, as requisições em andamento têm até `shutdown_timeout`
+/// para terminar; depois disso o pool de conexões é fechado, esperando no
+/// máximo `POOL_CLOSE_TIMEOUT` pelas conexões ainda em uso.
+pub async fn run(config: Config) -> Result<(), StartupError> {
+    let pool = create_database_pool(&config.database_url, &config.database).await?;
+
+    let port = config.port;
+    let shutdown_timeout = config.shutdown_timeout;
+    let app = create_app_with_state(AppState::from_config(config, pool.clone())?);
+
+    let address = SocketAddr::from(([0, 0, 0, 0], port));
+    let listener = TcpListener::bind(address)
+        .await
+        .map_err(|source| StartupError::Bind { port, source })?;
+
+    tracing::info!("Servidor iniciado em {}", address);
+    tracing::info!("Swagger: http://localhost:{}/docs", port);
+
+    let result = serve(listener, app, shutdown_signal(), shutdown_timeout).await;
+
+    // Requisições abandonadas pelo timeout ainda podem segurar conexões
+    if tokio::time::timeout(POOL_CLOSE_TIMEOUT, pool.close()).await.is_err() {
+        tracing::warn!("Tempo esgotado ao fechar o pool de conexões com o banco");
+    }
+    tracing::info!("Servidor encerrado");
+
+    result
+}
+
+/// Atende as requisições até `shutdown` completar
+///
+/// A partir daí o servidor para de aceitar conexões e espera as requisições em
+/// andamento por até `drain_timeout`. Esgotado o prazo, a função retorna sem
+/// esperá-las: as tarefas das conexões continuam rodando até o runtime ser
+/// encerrado junto com o processo.
+pub async fn serve(
+    listener: TcpListener,
+    app: Router,
+    shutdown: impl Future<Output = ()> + Send + 'static,
+    drain_timeout: Duration,
+) -> Result<(), StartupError> {
+    let draining = Arc::new(Notify::new());
+    let signal = {
+        let draining = draining.clone();
+        async move {
+            shutdown.await;
+            tracing::info!("Desligando: aguardando as requisições em andamento");
+            draining.notify_one();
+        }
+    };
+
+    let server = axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
+        .with_graceful_shutdown(signal);
+
+    tokio::select! {
+        result = server => result.map_err(StartupError::Server)?,
+        _ = async {
+            draining.notified().await;
+            tokio::time::sleep(drain_timeout).await;
+        } => {
+            tracing::warn!("Tempo de desligamento esgotado; requisições em andamento foram abandonadas");
+        }
+    }
+
+    Ok(())
+}
+
+/// Completa quando o processo recebe SIGINT (Ctrl+C) ou, no Unix, SIGTERM
+pub async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::error!("Falha ao escutar o SIGINT: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                tracing::error!("Falha ao escutar o SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+}
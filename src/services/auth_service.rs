@@ -1,67 +1,86 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use chrono::{Duration, Utc};
-use jsonwebtoken::{encode, EncodingKey, Header};
-use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::config::Config;
 use crate::error::AppError;
-use crate::models::user::{CreateUserDto, LoginUserDto, TokenResponse, UserResponse};
+use crate::i18n::messages;
+use crate::models::user::{
+    CreateUserDto, LoginResponse, LoginUserDto, TokenResponse, TwoFactorChallenge, User, UserResponse,
+};
 use crate::repositories::user_repository::UserRepository;
 use crate::services::password_service::PasswordService;
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct TokenClaims {
-    pub sub: String,
-    pub iat: usize,
-    pub exp: usize,
-}
+use crate::services::token_service::{
+    create_token_service, TokenService, CHALLENGE_EXPIRES_IN_SECONDS,
+};
 
 #[async_trait]
 pub trait AuthService: Send + Sync + 'static {
     async fn register(&self, user_dto: CreateUserDto) -> Result<UserResponse, AppError>;
-    async fn login(&self, login_dto: LoginUserDto) -> Result<TokenResponse, AppError>;
+    /// Verifica email e senha; contas com verificação em duas etapas recebem
+    /// um desafio em vez do token de acesso
+    async fn login(&self, login_dto: LoginUserDto) -> Result<LoginResponse, AppError>;
+}
+
+/// Conclui um login já autenticado: emite o token de acesso ou, se a conta
+/// exigir o segundo fator, o desafio a ser trocado por ele
+pub async fn complete_login(
+    user: User,
+    user_repository: &dyn UserRepository,
+    token_service: &dyn TokenService,
+) -> Result<LoginResponse, AppError> {
+    // Contas suspensas não recebem token, mesmo com as credenciais corretas
+    if user.banned_at.is_some() {
+        return Err(AppError::Forbidden(messages::ACCOUNT_BANNED.text()));
+    }
+
+    if user_repository.two_factor_enabled(&user.id).await? {
+        return Ok(LoginResponse::TwoFactorRequired(TwoFactorChallenge {
+            challenge_token: token_service.issue_challenge(&user.id)?,
+            expires_in: CHALLENGE_EXPIRES_IN_SECONDS,
+        }));
+    }
+
+    Ok(LoginResponse::Authenticated(TokenResponse {
+        access_token: token_service.issue(&user.id)?,
+        token_type: "Bearer".to_string(),
+        user: UserResponse::from(user),
+    }))
 }
 
 pub struct AuthServiceImpl {
     user_repository: Arc<dyn UserRepository>,
     password_service: Arc<dyn PasswordService>,
-    config: Config,
+    token_service: Arc<dyn TokenService>,
 }
 
 impl AuthServiceImpl {
+    /// Cria o serviço com as chaves de token carregadas da configuração
     pub fn new(
         user_repository: Arc<dyn UserRepository>,
         password_service: Arc<dyn PasswordService>,
         config: Config,
+    ) -> Result<Self, String> {
+        Ok(Self::with_token_service(user_repository, password_service, create_token_service(&config)?))
+    }
+
+    pub fn with_token_service(
+        user_repository: Arc<dyn UserRepository>,
+        password_service: Arc<dyn PasswordService>,
+        token_service: Arc<dyn TokenService>,
     ) -> Self {
         Self {
             user_repository,
             password_service,
-            config,
+            token_service,
         }
     }
 
-    fn generate_token(&self, user_id: &Uuid) -> Result<String, AppError> {
-        let now = Utc::now();
-        let iat = now.timestamp() as usize;
-        let exp = (now + Duration::hours(self.config.jwt_expires_in.parse::<i64>().unwrap()))
-            .timestamp() as usize;
-
-        let claims = TokenClaims {
-            sub: user_id.to_string(),
-            iat,
-            exp,
-        };
-
-        encode(
-            &Header::default(),
-            &claims,
-            &EncodingKey::from_secret(self.config.jwt_secret.as_bytes()),
-        )
-        .map_err(|e| AppError::InternalServerError(format!("Erro ao gerar token: {}", e)))
+    async fn rehash_password(&self, user_id: &Uuid, password: &str) -> Result<(), AppError> {
+        let hash_password = self.password_service.hash_password(password).await?;
+        self.user_repository.update_password(user_id, hash_password).await?;
+        Ok(())
     }
 }
 
@@ -72,7 +91,7 @@ impl AuthService for AuthServiceImpl {
         user_dto.validate_all()?;
 
         // Hash da senha usando o adapter
-        let hash_password = self.password_service.hash_password(&user_dto.password)?;
+        let hash_password = self.password_service.hash_password(&user_dto.password).await?;
 
         // Criar usuário
         let user = self
@@ -83,7 +102,7 @@ impl AuthService for AuthServiceImpl {
         Ok(UserResponse::from(user))
     }
 
-    async fn login(&self, login_dto: LoginUserDto) -> Result<TokenResponse, AppError> {
+    async fn login(&self, login_dto: LoginUserDto) -> Result<LoginResponse, AppError> {
         // Validar entrada usando as validações do DTO
         login_dto.validate_all()?;
 
@@ -92,23 +111,25 @@ impl AuthService for AuthServiceImpl {
             .user_repository
             .find_by_email(&login_dto.email)
             .await?
-            .ok_or_else(|| AppError::AuthError("Credenciais inválidas".to_string()))?;
+            .ok_or(AppError::InvalidCredentials)?;
 
         // Verificar senha usando o adapter
         let is_valid = self
             .password_service
-            .verify_password(&login_dto.password, &user.hash_password)?;
+            .verify_password(&login_dto.password, &user.hash_password)
+            .await?;
         if !is_valid {
-            return Err(AppError::AuthError("Credenciais inválidas".to_string()));
+            return Err(AppError::InvalidCredentials);
         }
 
-        // Gerar token
-        let token = self.generate_token(&user.id)?;
+        // Com a senha em mãos, hashes gerados com parâmetros antigos são
+        // refeitos; uma falha aqui não impede o login
+        if self.password_service.needs_rehash(&user.hash_password) {
+            if let Err(e) = self.rehash_password(&user.id, &login_dto.password).await {
+                tracing::warn!("Falha ao atualizar o hash da senha do usuário {}: {}", user.id, e);
+            }
+        }
 
-        Ok(TokenResponse {
-            access_token: token,
-            token_type: "Bearer".to_string(),
-            user: UserResponse::from(user),
-        })
+        complete_login(user, self.user_repository.as_ref(), self.token_service.as_ref()).await
     }
 }
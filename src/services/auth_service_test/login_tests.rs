@@ -1,6 +1,8 @@
 use super::*;
-use crate::models::user::{LoginUserDto, User};
+use crate::models::user::{LoginResponse, LoginUserDto, User};
 use crate::services::auth_service::AuthServiceImpl;
+use crate::services::token_service::CHALLENGE_EXPIRES_IN_SECONDS;
+use axum::http::StatusCode;
 use mockall::predicate;
 use std::sync::Arc;
 
@@ -29,8 +31,10 @@ async fn success_with_valid_credentials() {
                 hash_password: "hash_password".to_string(),
                 created_at: test_timestamp,
                 updated_at: test_timestamp,
+                banned_at: None,
             }))
         });
+    mock_repo.expect_two_factor_enabled().returning(|_| Ok(false));
 
     // Criar mock do PasswordService configurado para retornar true (senha válida)
     let mock_password_service = create_mock_password_service("hash_dummy".to_string(), true);
@@ -40,7 +44,8 @@ async fn success_with_valid_credentials() {
         Arc::new(mock_repo),
         mock_password_service,
         create_test_config(),
-    );
+    )
+    .unwrap();
 
     // Act
     let login_dto = LoginUserDto {
@@ -53,11 +58,14 @@ async fn success_with_valid_credentials() {
     // Assert
     assert!(result.is_ok(), "O login deveria ter sido bem-sucedido");
 
-    if let Ok(token_response) = result {
-        assert_eq!(token_response.token_type, "Bearer");
-        assert!(!token_response.access_token.is_empty());
-        assert_eq!(token_response.user.name, "Teste");
-        assert_eq!(token_response.user.email, "teste@example.com");
+    match result {
+        Ok(LoginResponse::Authenticated(token_response)) => {
+            assert_eq!(token_response.token_type, "Bearer");
+            assert!(!token_response.access_token.is_empty());
+            assert_eq!(token_response.user.name, "Teste");
+            assert_eq!(token_response.user.email, "teste@example.com");
+        }
+        other => panic!("Esperava o token de acesso, obteve {:?}", other),
     }
 }
 
@@ -107,7 +115,8 @@ async fn fail_with_invalid_fields() {
             Arc::new(mock_repo),
             mock_password_service,
             create_test_config(),
-        );
+        )
+        .unwrap();
 
         let login_dto = LoginUserDto {
             email: test_case.user_email.to_string(),
@@ -138,7 +147,8 @@ async fn fail_with_invalid_fields() {
         Arc::new(mock_repo),
         mock_password_service,
         create_test_config(),
-    );
+    )
+    .unwrap();
 
     // Criar um email longo (acima do limite)
     let long_email = "A".repeat(256);
@@ -168,7 +178,8 @@ async fn fail_with_invalid_fields() {
         Arc::new(mock_repo),
         mock_password_service,
         create_test_config(),
-    );
+    )
+    .unwrap();
 
     let login_dto = LoginUserDto {
         email: "teste@example.com".to_string(),
@@ -211,7 +222,8 @@ async fn fail_with_invalid_email() {
         Arc::new(mock_repo),
         mock_password_service,
         create_test_config(),
-    );
+    )
+    .unwrap();
 
     // Act
     let login_dto = LoginUserDto {
@@ -254,6 +266,7 @@ async fn fail_with_invalid_password() {
                 hash_password: "hash_password".to_string(),
                 created_at: test_timestamp,
                 updated_at: test_timestamp,
+                banned_at: None,
             }))
         });
 
@@ -265,7 +278,8 @@ async fn fail_with_invalid_password() {
         Arc::new(mock_repo),
         mock_password_service,
         create_test_config(),
-    );
+    )
+    .unwrap();
 
     // Act
     let login_dto = LoginUserDto {
@@ -286,3 +300,183 @@ async fn fail_with_invalid_password() {
         "Erro ao fazer login com senha incorreta",
     );
 }
+
+/// Testa a atualização do hash da senha no login
+///
+/// Verifica se:
+/// 1. Um hash com parâmetros antigos é refeito com a senha informada
+/// 2. O novo hash é gravado para o usuário
+#[tokio::test]
+async fn rehash_outdated_password_on_login() {
+    // Arrange
+    let mut mock_repo = MockUserRepository::new();
+    let user_id = Uuid::new_v4();
+
+    mock_repo
+        .expect_find_by_email()
+        .returning(move |_| Ok(Some(User { id: user_id, ..create_test_user("Teste", "teste@example.com") })));
+
+    // O novo hash deve ser gravado para o usuário que fez login
+    mock_repo
+        .expect_update_password()
+        .with(predicate::eq(user_id), predicate::eq("novo_hash".to_string()))
+        .times(1)
+        .returning(|_, _| Ok(true));
+    mock_repo.expect_two_factor_enabled().returning(|_| Ok(false));
+
+    let mut mock_password_service = MockPasswordService::new();
+    mock_password_service.expect_verify_password().returning(|_, _| Ok(true));
+    mock_password_service
+        .expect_needs_rehash()
+        .with(predicate::eq("hashed_password"))
+        .returning(|_| true);
+    mock_password_service
+        .expect_hash_password()
+        .with(predicate::eq("senha123"))
+        .times(1)
+        .returning(|_| Ok("novo_hash".to_string()));
+
+    let auth_service = AuthServiceImpl::new(
+        Arc::new(mock_repo),
+        Arc::new(mock_password_service),
+        create_test_config(),
+    )
+    .unwrap();
+
+    // Act
+    let result = auth_service
+        .login(LoginUserDto {
+            email: "teste@example.com".to_string(),
+            password: "senha123".to_string(),
+        })
+        .await;
+
+    // Assert
+    assert!(result.is_ok(), "O login deveria ser bem-sucedido");
+}
+
+/// Testa que uma falha ao refazer o hash não impede o login
+#[tokio::test]
+async fn login_succeeds_when_rehash_fails() {
+    // Arrange
+    let mut mock_repo = MockUserRepository::new();
+    mock_repo
+        .expect_find_by_email()
+        .returning(|_| Ok(Some(create_test_user("Teste", "teste@example.com"))));
+    mock_repo
+        .expect_update_password()
+        .returning(|_, _| Err(AppError::DatabaseError("falha".to_string())));
+    mock_repo.expect_two_factor_enabled().returning(|_| Ok(false));
+
+    let mut mock_password_service = MockPasswordService::new();
+    mock_password_service.expect_verify_password().returning(|_, _| Ok(true));
+    mock_password_service.expect_needs_rehash().returning(|_| true);
+    mock_password_service
+        .expect_hash_password()
+        .returning(|_| Ok("novo_hash".to_string()));
+
+    let auth_service = AuthServiceImpl::new(
+        Arc::new(mock_repo),
+        Arc::new(mock_password_service),
+        create_test_config(),
+    )
+    .unwrap();
+
+    // Act
+    let result = auth_service
+        .login(LoginUserDto {
+            email: "teste@example.com".to_string(),
+            password: "senha123".to_string(),
+        })
+        .await;
+
+    // Assert
+    assert!(result.is_ok(), "Uma falha ao refazer o hash não deveria impedir o login");
+}
+
+/// Testa o login de um usuário com verificação em duas etapas ativa
+///
+/// Verifica se:
+/// 1. A senha correta não basta para receber o token de acesso
+/// 2. É retornado um desafio de curta duração para a segunda etapa
+#[tokio::test]
+async fn two_factor_required_returns_challenge() {
+    // Arrange
+    let mut mock_repo = MockUserRepository::new();
+    let user = create_test_user("Teste", "teste@example.com");
+    let user_id = user.id;
+
+    mock_repo
+        .expect_find_by_email()
+        .returning(move |_| Ok(Some(user.clone())));
+    mock_repo
+        .expect_two_factor_enabled()
+        .with(predicate::eq(user_id))
+        .times(1)
+        .returning(|_| Ok(true));
+
+    let auth_service = AuthServiceImpl::new(
+        Arc::new(mock_repo),
+        create_mock_password_service("hash_dummy".to_string(), true),
+        create_test_config(),
+    )
+    .unwrap();
+
+    // Act
+    let result = auth_service
+        .login(LoginUserDto {
+            email: "teste@example.com".to_string(),
+            password: "senha123".to_string(),
+        })
+        .await;
+
+    // Assert
+    match result {
+        Ok(LoginResponse::TwoFactorRequired(challenge)) => {
+            assert!(!challenge.challenge_token.is_empty());
+            assert_eq!(challenge.expires_in, CHALLENGE_EXPIRES_IN_SECONDS);
+        }
+        other => panic!("Esperava o desafio da verificação em duas etapas, obteve {:?}", other),
+    }
+}
+
+/// Testa o login de uma conta suspensa
+///
+/// Verifica se:
+/// 1. Com a senha correta, o login é recusado com 403 e nenhum token é emitido
+/// 2. Com a senha errada, a resposta continua sendo "Credenciais inválidas"
+#[tokio::test]
+async fn banned_account_is_refused_after_password_check() {
+    for (password_is_valid, expected_status) in [(true, StatusCode::FORBIDDEN), (false, StatusCode::UNAUTHORIZED)] {
+        // Arrange
+        let mut mock_repo = MockUserRepository::new();
+        let mut user = create_test_user("Teste", "teste@example.com");
+        user.banned_at = Some(create_test_timestamp());
+
+        mock_repo
+            .expect_find_by_email()
+            .returning(move |_| Ok(Some(user.clone())));
+        mock_repo.expect_two_factor_enabled().never();
+
+        let auth_service = AuthServiceImpl::new(
+            Arc::new(mock_repo),
+            create_mock_password_service("hash_dummy".to_string(), password_is_valid),
+            create_test_config(),
+        )
+        .unwrap();
+
+        // Act
+        let result = auth_service
+            .login(LoginUserDto {
+                email: "teste@example.com".to_string(),
+                password: "senha123".to_string(),
+            })
+            .await;
+
+        // Assert
+        match result {
+            Err(error) => assert_eq!(error.status(), expected_status),
+            Ok(response) => panic!("Esperava o login recusado, obteve {:?}", response),
+        }
+    }
+}
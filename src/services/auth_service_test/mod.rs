@@ -4,9 +4,9 @@ use mockall::mock;
 use std::sync::Arc;
 use uuid::Uuid;
 
-use crate::config::Config;
+use crate::config::{Config, CorsConfig, DatabaseConfig, LogFormat, RateLimitBackend};
 use crate::error::AppError;
-use crate::models::user::{CreateUserDto, User};
+use crate::models::user::{CreateUserDto, User, UserLocation};
 use crate::repositories::user_repository::UserRepository;
 use crate::services::auth_service::AuthService;
 use crate::services::password_service::PasswordService;
@@ -25,6 +25,11 @@ mock! {
     impl UserRepository for UserRepository {
         async fn create(&self, user: &CreateUserDto, hash_password: String) -> Result<User, AppError>;
         async fn find_by_email(&self, email: &str) -> Result<Option<User>, AppError>;
+        async fn find_by_id(&self, user_id: &Uuid) -> Result<Option<User>, AppError>;
+        async fn update_location(&self, user_id: &Uuid, location: Option<UserLocation>) -> Result<bool, AppError>;
+        async fn update_password(&self, user_id: &Uuid, hash_password: String) -> Result<bool, AppError>;
+        async fn two_factor_enabled(&self, user_id: &Uuid) -> Result<bool, AppError>;
+        async fn set_banned(&self, user_id: &Uuid, banned: bool) -> Result<bool, AppError>;
     }
 }
 
@@ -34,8 +39,9 @@ mock! {
 
     #[async_trait]
     impl PasswordService for PasswordService {
-        fn hash_password(&self, password: &str) -> Result<String, AppError>;
-        fn verify_password(&self, password: &str, hash: &str) -> Result<bool, AppError>;
+        async fn hash_password(&self, password: &str) -> Result<String, AppError>;
+        async fn verify_password(&self, password: &str, hash: &str) -> Result<bool, AppError>;
+        fn needs_rehash(&self, hash: &str) -> bool;
     }
 }
 
@@ -47,7 +53,30 @@ pub fn create_test_config() -> Config {
         database_url: "postgres://dummy".to_string(),
         jwt_secret: "test_secret".to_string(),
         jwt_expires_in: "1".to_string(),
+        jwt_issuer: "troca-livros-api".to_string(),
+        jwt_audience: "troca-livros".to_string(),
+        jwt_keys_dir: None,
+        jwt_active_kid: None,
         port: 8080,
+        photo_storage_dir: "uploads".to_string(),
+        photo_max_bytes: 5 * 1024 * 1024,
+        rate_limit_backend: RateLimitBackend::Memory,
+        trusted_proxies: vec![],
+        argon2_memory_kib: 19 * 1024,
+        argon2_iterations: 2,
+        argon2_parallelism: 1,
+        password_hash_concurrency: 2,
+        oauth_redirect_base_url: "http://localhost:8080".to_string(),
+        google_oauth: None,
+        github_oauth: None,
+        readiness_check_google_books: false,
+        log_format: LogFormat::Text,
+        cors: CorsConfig::default(),
+        hsts_max_age_secs: 0,
+        json_body_limit_bytes: 256 * 1024,
+        database: DatabaseConfig::default(),
+        shutdown_timeout: std::time::Duration::from_secs(1),
+        book_metadata_max_age: std::time::Duration::from_secs(7 * 24 * 60 * 60),
     }
 }
 
@@ -66,6 +95,7 @@ pub fn create_test_user(name: &str, email: &str) -> User {
         hash_password: "hashed_password".to_string(),
         created_at: create_test_timestamp(),
         updated_at: create_test_timestamp(),
+        banned_at: None,
     }
 }
 
@@ -95,6 +125,9 @@ pub fn create_mock_password_service(
         .expect_verify_password()
         .returning(move |_, _| Ok(verify_result));
 
+    // Hashes dos testes já usam os parâmetros atuais
+    mock_service.expect_needs_rehash().returning(|_| false);
+
     Arc::new(mock_service)
 }
 
@@ -127,6 +160,25 @@ pub fn assert_error_with_message(
                 expected_message
             );
         }
+        (AppError::InvalidFields(fields), "ValidationError") => {
+            assert!(
+                fields.iter().any(|field| field.message.contains(expected_message)),
+                "{}: Erros de campo inesperados: {:?}, esperava um que contivesse: '{}'",
+                context,
+                fields,
+                expected_message
+            );
+        }
+        (AppError::InvalidCredentials, "AuthError") => {
+            let msg = err.public_message();
+            assert!(
+                msg.contains(expected_message),
+                "{}: Mensagem de erro inesperada: '{}', esperava que contivesse: '{}'",
+                context,
+                msg,
+                expected_message
+            );
+        }
         (AppError::AuthError(msg), "AuthError") => {
             assert!(
                 msg.contains(expected_message),
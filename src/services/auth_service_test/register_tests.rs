@@ -35,7 +35,8 @@ async fn success_with_valid_data() {
         Arc::new(mock_repo),
         mock_password_service,
         create_test_config(),
-    );
+    )
+    .unwrap();
 
     let dto = create_user_dto("Teste", "teste@example.com", "senha123");
 
@@ -102,7 +103,8 @@ async fn fail_with_invalid_fields() {
             Arc::new(mock_repo),
             mock_password_service,
             create_test_config(),
-        );
+        )
+        .unwrap();
 
         let dto = create_user_dto(
             test_case.user_name,
@@ -145,7 +147,8 @@ async fn fail_with_short_password() {
         Arc::new(mock_repo),
         mock_password_service,
         create_test_config(),
-    );
+    )
+    .unwrap();
 
     let dto = create_user_dto("Teste", "teste@example.com", "12345"); // Menos de 6 caracteres
 
@@ -189,7 +192,8 @@ async fn fail_with_duplicate_email() {
         Arc::new(mock_repo),
         mock_password_service,
         create_test_config(),
-    );
+    )
+    .unwrap();
 
     let dto = create_user_dto("Teste", "email_existente@example.com", "senha123");
 
@@ -226,7 +230,8 @@ async fn fail_with_long_name() {
         Arc::new(mock_repo),
         mock_password_service,
         create_test_config(),
-    );
+    )
+    .unwrap();
 
     // Criar um nome com 256 caracteres (acima do limite)
     let long_name = "A".repeat(256);
@@ -276,7 +281,8 @@ async fn fail_with_database_error() {
         Arc::new(mock_repo),
         mock_password_service,
         create_test_config(),
-    );
+    )
+    .unwrap();
 
     let dto = create_user_dto("Teste", "teste@example.com", "senha123");
 
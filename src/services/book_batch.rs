@@ -0,0 +1,78 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::i18n::{current_locale, messages, with_locale};
+use crate::models::book::MAX_BATCH_SIZE;
+use crate::repositories::book_repository::BookRepository;
+use crate::services::google_book_service::GoogleBookService;
+
+/// Número máximo de consultas simultâneas ao Google Books em uma operação em lote
+pub const BATCH_LOOKUP_CONCURRENCY: usize = 4;
+
+/// Verifica se a quantidade de itens de uma operação em lote está dentro do limite
+pub fn validate_batch_size(len: usize) -> Result<(), AppError> {
+    if (1..=MAX_BATCH_SIZE).contains(&len) {
+        Ok(())
+    } else {
+        Err(AppError::ValidationError(messages::BATCH_SIZE_OUT_OF_RANGE.format(&[&MAX_BATCH_SIZE])))
+    }
+}
+
+/// Obtém o ID interno de cada google_id, cadastrando os livros que ainda não existem
+///
+/// Os livros já cadastrados são buscados em uma única consulta; os demais são
+/// buscados no Google Books em paralelo, com no máximo
+/// `BATCH_LOOKUP_CONCURRENCY` consultas simultâneas. Falhas de um livro ficam
+/// no seu item e não interrompem os outros.
+pub async fn resolve_google_ids(
+    book_repository: &Arc<dyn BookRepository>,
+    google_book_service: &Arc<dyn GoogleBookService>,
+    google_ids: &[String],
+) -> Result<HashMap<String, Result<Uuid, String>>, AppError> {
+    let mut unique_ids = google_ids.to_vec();
+    unique_ids.sort();
+    unique_ids.dedup();
+
+    let mut resolved: HashMap<String, Result<Uuid, String>> = book_repository
+        .find_by_google_ids(&unique_ids)
+        .await?
+        .into_iter()
+        .map(|book| (book.book.google_id, Ok(book.id)))
+        .collect();
+
+    let semaphore = Arc::new(Semaphore::new(BATCH_LOOKUP_CONCURRENCY));
+    let mut lookups = JoinSet::new();
+    // As tarefas não herdam o idioma da requisição, usado nas mensagens de erro
+    let locale = current_locale();
+
+    for google_id in unique_ids.into_iter().filter(|id| !resolved.contains_key(id)) {
+        let book_repository = book_repository.clone();
+        let google_book_service = google_book_service.clone();
+        let permit = semaphore
+            .clone()
+            .acquire_owned()
+            .await
+            .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+        lookups.spawn(with_locale(locale, async move {
+            let _permit = permit;
+            let result = match google_book_service.find_book_by_id(&google_id).await {
+                Ok(book) => book_repository.create(&book).await,
+                Err(e) => Err(e),
+            };
+            (google_id, result.map_err(|e| e.public_message()))
+        }));
+    }
+
+    while let Some(lookup) = lookups.join_next().await {
+        let (google_id, result) = lookup.map_err(|e| AppError::InternalServerError(e.to_string()))?;
+        resolved.insert(google_id, result);
+    }
+
+    Ok(resolved)
+}
@@ -1,19 +1,53 @@
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use async_trait::async_trait;
 use uuid::Uuid;
 
-use crate::error::AppError;
-use crate::models::book::{BookOffered, CreateBookOfferedDto};
+use crate::error::{AppError, ErrorCode};
+use crate::i18n::{messages, Message};
+use crate::models::book::{
+    BatchItemResult, BatchItemStatus, BookOffered, CreateBookOfferedDto, OfferedBookItem, OfferedCopyAttributes,
+};
 use crate::repositories::book_repository::BookRepository;
 use crate::repositories::books_offered_repository::BooksOfferedRepository;
 use crate::repositories::books_wanted_repository::BooksWantedRepository;
+use crate::services::book_batch::{resolve_google_ids, validate_batch_size};
+use crate::services::book_photo_service::BookPhotoService;
 use crate::services::google_book_service::GoogleBookService;
 
+/// Número máximo de exemplares adicionados em uma única requisição
+pub const MAX_COPIES_PER_REQUEST: i32 = 20;
+
 #[async_trait]
 pub trait BookOfferedService: Send + Sync + 'static {
-    async fn add_book_to_offered(&self, google_id: &str, user_id: &Uuid) -> Result<BookOffered, AppError>;
+    async fn add_book_to_offered(
+        &self,
+        google_id: &str,
+        user_id: &Uuid,
+        attributes: OfferedCopyAttributes,
+        quantity: i32,
+    ) -> Result<Vec<BookOffered>, AppError>;
+    async fn update_book_offered(
+        &self,
+        copy_id: &Uuid,
+        user_id: &Uuid,
+        attributes: OfferedCopyAttributes,
+    ) -> Result<BookOffered, AppError>;
     async fn remove_book_from_offered(&self, book_id: &Uuid, user_id: &Uuid) -> Result<bool, AppError>;
+    async fn remove_copy_from_offered(&self, copy_id: &Uuid, user_id: &Uuid) -> Result<bool, AppError>;
+    /// Adiciona vários livros de uma vez; o resultado de cada item segue a ordem da requisição
+    async fn add_books_to_offered(
+        &self,
+        user_id: &Uuid,
+        items: Vec<OfferedBookItem>,
+    ) -> Result<Vec<BatchItemResult>, AppError>;
+    /// Remove vários livros de uma vez; o resultado de cada item segue a ordem da requisição
+    async fn remove_books_from_offered(
+        &self,
+        user_id: &Uuid,
+        book_ids: Vec<Uuid>,
+    ) -> Result<Vec<BatchItemResult>, AppError>;
 }
 
 pub struct BookOfferedServiceImpl {
@@ -21,6 +55,7 @@ pub struct BookOfferedServiceImpl {
     books_offered_repository: Arc<dyn BooksOfferedRepository>,
     books_wanted_repository: Arc<dyn BooksWantedRepository>,
     google_book_service: Arc<dyn GoogleBookService>,
+    book_photo_service: Arc<dyn BookPhotoService>,
 }
 
 impl BookOfferedServiceImpl {
@@ -29,25 +64,65 @@ impl BookOfferedServiceImpl {
         books_offered_repository: Arc<dyn BooksOfferedRepository>,
         books_wanted_repository: Arc<dyn BooksWantedRepository>,
         google_book_service: Arc<dyn GoogleBookService>,
+        book_photo_service: Arc<dyn BookPhotoService>,
     ) -> Self {
         Self {
             book_repository,
             books_offered_repository,
             books_wanted_repository,
             google_book_service,
+            book_photo_service,
+        }
+    }
+
+    // Valida os dados de um item antes de qualquer consulta
+    fn validate_item(item: &OfferedBookItem) -> Result<(), String> {
+        item.attributes.validate_all().map_err(|e| e.public_message())?;
+
+        if !(1..=MAX_COPIES_PER_REQUEST).contains(&item.quantity) {
+            return Err(messages::QUANTITY_OUT_OF_RANGE.format(&[&MAX_COPIES_PER_REQUEST]));
         }
+
+        Ok(())
+    }
+
+    // Confere que nenhum exemplar está reservado e devolve as chaves das fotos deles,
+    // que só devem ser apagadas depois que a remoção dos exemplares for gravada; a
+    // reserva é verificada de novo pela chave estrangeira na remoção
+    async fn removable_photo_keys(&self, copies: &[BookOffered], reserved_message: Message) -> Result<Vec<String>, AppError> {
+        let copy_ids: Vec<Uuid> = copies.iter().map(|copy| copy.id).collect();
+        if self.books_offered_repository.any_reserved(&copy_ids).await? {
+            return Err(AppError::Conflict(ErrorCode::CopyReserved, reserved_message.text()));
+        }
+
+        self.book_photo_service.storage_keys(&copy_ids).await
     }
 }
 
 #[async_trait]
 impl BookOfferedService for BookOfferedServiceImpl {
-    async fn add_book_to_offered(&self, google_id: &str, user_id: &Uuid) -> Result<BookOffered, AppError> {
+    async fn add_book_to_offered(
+        &self,
+        google_id: &str,
+        user_id: &Uuid,
+        attributes: OfferedCopyAttributes,
+        quantity: i32,
+    ) -> Result<Vec<BookOffered>, AppError> {
+        // Validar os atributos do exemplar antes de qualquer consulta
+        attributes.validate_all()?;
+
+        if !(1..=MAX_COPIES_PER_REQUEST).contains(&quantity) {
+            return Err(AppError::ValidationError(
+                messages::QUANTITY_OUT_OF_RANGE.format(&[&MAX_COPIES_PER_REQUEST]),
+            ));
+        }
+
         // Variável para armazenar o UUID do banco de dados
         let book_uuid: Uuid;
-        
+
         // Verificar se o livro existe no banco de dados e obter seu ID interno
         let existing_book = self.book_repository.find_by_google_id(google_id).await?;
-        
+
         if let Some(book_with_id) = existing_book {
             // Se o livro já existe, usar o ID existente
             book_uuid = book_with_id.id;
@@ -55,43 +130,223 @@ impl BookOfferedService for BookOfferedServiceImpl {
             // Livro não existe, precisa ser criado
             // Buscar do Google Books API
             let book_dto = self.google_book_service.find_book_by_id(google_id).await?;
-            
-            // Criar o livro no banco de dados
+
+            // Criar o livro no banco de dados; se outra requisição o cadastrou
+            // nesse meio tempo, o ID do cadastro existente é retornado
             book_uuid = self.book_repository.create(&book_dto).await?;
         }
-        
-        // Verificar se o livro já está na lista de desejados do usuário
-        if let Some(_) = self.books_wanted_repository.find(&book_uuid, user_id).await? {
-            return Err(AppError::ValidationError("Este livro já está na sua lista de desejados".to_string()));
-        }
-        
-        // Verificar se o livro já está na lista de possuídos do usuário
-        if let Some(_) = self.books_offered_repository.find(&book_uuid, user_id).await? {
-            return Err(AppError::ValidationError("Este livro já está na sua lista de possuídos".to_string()));
+
+        // Verificar se o livro já está na lista de desejados do usuário; o
+        // repositório repete a verificação na transação que cria os exemplares
+        if self.books_wanted_repository.find(&book_uuid, user_id).await?.is_some() {
+            return Err(AppError::Conflict(
+                ErrorCode::BookAlreadyWanted,
+                messages::BOOK_ALREADY_WANTED.text(),
+            ));
         }
-        
+
         // Criar DTO para adicionar à lista de possuídos
         let create_dto = CreateBookOfferedDto {
             book_id: book_uuid,
             user_id: *user_id,
+            attributes,
         };
 
-        // Adicionar à lista de livros possuídos
-        let book_offered = self.books_offered_repository.create(&create_dto).await?;
+        // Adicionar os exemplares à lista de livros possuídos; o usuário pode
+        // oferecer várias cópias do mesmo livro
+        self.books_offered_repository.create_copies(&create_dto, quantity).await
+    }
 
-        Ok(book_offered)
+    async fn update_book_offered(
+        &self,
+        copy_id: &Uuid,
+        user_id: &Uuid,
+        attributes: OfferedCopyAttributes,
+    ) -> Result<BookOffered, AppError> {
+        attributes.validate_all()?;
+
+        self.books_offered_repository
+            .update(copy_id, user_id, &attributes)
+            .await?
+            .ok_or_else(|| {
+                AppError::Rejected(ErrorCode::CopyNotOffered, messages::COPY_NOT_OFFERED.text())
+            })
     }
 
     async fn remove_book_from_offered(&self, book_id: &Uuid, user_id: &Uuid) -> Result<bool, AppError> {
         // Verificar se o livro existe na lista de possuídos do usuário
-        let exists = self.books_offered_repository.find(book_id, user_id).await?;
-        if exists.is_none() {
-            return Err(AppError::ValidationError(
-                "Este livro não está na sua lista de possuídos".to_string(),
+        let copies = self.books_offered_repository.find_copies(book_id, user_id).await?;
+        if copies.is_empty() {
+            return Err(AppError::Rejected(
+                ErrorCode::BookNotOffered,
+                messages::BOOK_NOT_OFFERED.text(),
             ));
         }
 
-        // Remover da lista de livros possuídos
-        self.books_offered_repository.delete(book_id, user_id).await
+        let photo_keys = self
+            .removable_photo_keys(&copies, messages::BOOK_COPIES_RESERVED)
+            .await?;
+
+        // Remover todos os exemplares do livro da lista de possuídos
+        let deleted = self.books_offered_repository.delete(book_id, user_id).await?;
+        self.book_photo_service.delete_stored(&photo_keys).await;
+
+        Ok(deleted)
+    }
+
+    async fn remove_copy_from_offered(&self, copy_id: &Uuid, user_id: &Uuid) -> Result<bool, AppError> {
+        let copy = self
+            .books_offered_repository
+            .find_by_id(copy_id)
+            .await?
+            .filter(|copy| copy.user_id == *user_id)
+            .ok_or_else(|| {
+                AppError::Rejected(ErrorCode::CopyNotOffered, messages::COPY_NOT_OFFERED.text())
+            })?;
+
+        let photo_keys = self
+            .removable_photo_keys(&[copy], messages::COPY_RESERVED)
+            .await?;
+
+        let deleted = self.books_offered_repository.delete_copy(copy_id, user_id).await?;
+        self.book_photo_service.delete_stored(&photo_keys).await;
+
+        Ok(deleted)
+    }
+
+    async fn add_books_to_offered(
+        &self,
+        user_id: &Uuid,
+        items: Vec<OfferedBookItem>,
+    ) -> Result<Vec<BatchItemResult>, AppError> {
+        validate_batch_size(items.len())?;
+
+        let mut results: Vec<Option<BatchItemResult>> = items
+            .iter()
+            .map(|item| {
+                Self::validate_item(item)
+                    .err()
+                    .map(|message| BatchItemResult::failed(&item.google_id, None, message))
+            })
+            .collect();
+
+        let google_ids: Vec<String> = items
+            .iter()
+            .zip(&results)
+            .filter(|(_, result)| result.is_none())
+            .map(|(item, _)| item.google_id.clone())
+            .collect();
+        let resolved = resolve_google_ids(&self.book_repository, &self.google_book_service, &google_ids).await?;
+
+        // Uma consulta para toda a lista de desejados em vez de uma por item
+        let wanted: HashSet<Uuid> = self.books_wanted_repository.find_by_user_id(user_id).await?.into_iter().collect();
+
+        // Exemplares a criar e, para cada item, quantos deles pertencem ao item
+        let mut dtos = Vec::new();
+        let mut pending: Vec<(usize, usize)> = Vec::new();
+
+        for (index, item) in items.into_iter().enumerate() {
+            if results[index].is_some() {
+                continue;
+            }
+
+            let book_id = match resolved.get(&item.google_id) {
+                Some(Ok(book_id)) => *book_id,
+                Some(Err(message)) => {
+                    results[index] = Some(BatchItemResult::failed(&item.google_id, None, message.as_str()));
+                    continue;
+                }
+                None => {
+                    results[index] = Some(BatchItemResult::failed(&item.google_id, None, messages::BOOK_NOT_FOUND.text()));
+                    continue;
+                }
+            };
+
+            if wanted.contains(&book_id) {
+                results[index] = Some(BatchItemResult::failed(
+                    &item.google_id,
+                    Some(book_id),
+                    messages::BOOK_ALREADY_WANTED.text(),
+                ));
+                continue;
+            }
+
+            for _ in 0..item.quantity {
+                dtos.push(CreateBookOfferedDto {
+                    book_id,
+                    user_id: *user_id,
+                    attributes: item.attributes.clone(),
+                });
+            }
+            pending.push((index, item.quantity as usize));
+            results[index] = Some(BatchItemResult::succeeded(item.google_id, BatchItemStatus::Added, book_id, vec![]));
+        }
+
+        // Todos os exemplares são criados na mesma transação
+        if !dtos.is_empty() {
+            let mut copies = self.books_offered_repository.create_many(&dtos).await?.into_iter();
+
+            for (index, quantity) in pending {
+                if let Some(result) = results[index].as_mut() {
+                    result.copy_ids = copies.by_ref().take(quantity).map(|copy| copy.id).collect();
+                }
+            }
+        }
+
+        Ok(results.into_iter().flatten().collect())
     }
-} 
\ No newline at end of file
+
+    async fn remove_books_from_offered(
+        &self,
+        user_id: &Uuid,
+        book_ids: Vec<Uuid>,
+    ) -> Result<Vec<BatchItemResult>, AppError> {
+        validate_batch_size(book_ids.len())?;
+
+        // Exemplares do usuário agrupados por livro, obtidos em uma única consulta
+        let mut copies_by_book: HashMap<Uuid, Vec<BookOffered>> = HashMap::new();
+        for copy in self.books_offered_repository.find_all_by_user_id(user_id).await? {
+            copies_by_book.entry(copy.book_id).or_default().push(copy);
+        }
+
+        let mut results = Vec::with_capacity(book_ids.len());
+        let mut removable = Vec::new();
+        let mut photo_keys = Vec::new();
+
+        for book_id in book_ids {
+            let id = book_id.to_string();
+
+            // Cada livro só é processado uma vez, mesmo se repetido na requisição
+            let Some(copies) = copies_by_book.remove(&book_id) else {
+                results.push(BatchItemResult::failed(
+                    id,
+                    Some(book_id),
+                    messages::BOOK_NOT_OFFERED.text(),
+                ));
+                continue;
+            };
+
+            match self
+                .removable_photo_keys(&copies, messages::BOOK_COPIES_RESERVED)
+                .await
+            {
+                Ok(keys) => {
+                    photo_keys.extend(keys);
+                    removable.push(book_id);
+                    results.push(BatchItemResult::succeeded(id, BatchItemStatus::Removed, book_id, vec![]));
+                }
+                Err(AppError::Conflict(_, message)) => {
+                    results.push(BatchItemResult::failed(id, Some(book_id), message));
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        if !removable.is_empty() {
+            self.books_offered_repository.delete_many(&removable, user_id).await?;
+            self.book_photo_service.delete_stored(&photo_keys).await;
+        }
+
+        Ok(results)
+    }
+}
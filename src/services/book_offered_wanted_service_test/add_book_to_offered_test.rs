@@ -3,11 +3,11 @@ use std::sync::Arc;
 use mockall::predicate::*;
 use uuid::Uuid;
 
-use crate::error::AppError;
-use crate::models::book::{BookOffered, BookWanted, CreateBookOfferedDto, GoogleBookDto};
+use crate::error::{AppError, ErrorCode};
+use crate::models::book::{BookOffered, BookWanted, CreateBookOfferedDto, GoogleBookDto, OfferedCopyAttributes, DEFAULT_WANTED_PRIORITY};
 use crate::repositories::book_repository::BookWithId;
-use crate::services::book_offered_service::{BookOfferedService, BookOfferedServiceImpl};
-use crate::services::book_offered_wanted_service_test::{MockBookRepository, MockBooksOfferedRepository, MockBooksWantedRepository, MockGoogleBookService};
+use crate::services::book_offered_service::{BookOfferedService, BookOfferedServiceImpl, MAX_COPIES_PER_REQUEST};
+use crate::services::book_offered_wanted_service_test::{MockBookRepository, MockBooksOfferedRepository, MockBooksWantedRepository, MockGoogleBookService, MockBookPhotoService};
 
 #[tokio::test]
 async fn test_add_book_to_offered_when_book_exists() {
@@ -49,30 +49,28 @@ async fn test_add_book_to_offered_when_book_exists() {
         .times(1)
         .returning(|_, _| Ok(None));
 
-    // Configurar o mock do books_offered_repository para retornar None para find
-    // (indicando que o livro ainda não está na lista de possuídos)
+    // Configurar o mock do books_offered_repository para criar um exemplar
     books_offered_repo
-        .expect_find()
-        .with(eq(book_id), eq(user_id))
+        .expect_create_copies()
+        .with(
+            function(move |dto: &CreateBookOfferedDto| {
+                dto.book_id == book_id && dto.user_id == user_id
+            }),
+            eq(1),
+        )
         .times(1)
-        .returning(|_, _| Ok(None));
-
-    // Configurar o mock do books_offered_repository para retornar um BookOffered para create
-    books_offered_repo
-        .expect_create()
-        .with(function(move |dto: &CreateBookOfferedDto| {
-            dto.book_id == book_id && dto.user_id == user_id
-        }))
-        .times(1)
-        .returning(move |dto| {
-            Ok(BookOffered {
+        .returning(move |dto, _| {
+            Ok(vec![BookOffered {
+                id: Uuid::new_v4(),
                 book_id: dto.book_id,
                 user_id: dto.user_id,
-            })
+                attributes: OfferedCopyAttributes::default(),
+            }])
         });
 
     // Google Book Service não será usado neste teste, pois o livro já existe
     let google_book_service = MockGoogleBookService::new();
+    let book_photo_service = MockBookPhotoService::new();
 
     // Act
     let service = BookOfferedServiceImpl::new(
@@ -80,15 +78,17 @@ async fn test_add_book_to_offered_when_book_exists() {
         Arc::new(books_offered_repo),
         Arc::new(books_wanted_repo),
         Arc::new(google_book_service),
+        Arc::new(book_photo_service),
     );
 
-    let result = service.add_book_to_offered(google_id, &user_id).await;
+    let result = service.add_book_to_offered(google_id, &user_id, OfferedCopyAttributes::default(), 1).await;
 
     // Assert
     assert!(result.is_ok());
-    let book_offered = result.unwrap();
-    assert_eq!(book_offered.book_id, book_id);
-    assert_eq!(book_offered.user_id, user_id);
+    let copies = result.unwrap();
+    assert_eq!(copies.len(), 1);
+    assert_eq!(copies[0].book_id, book_id);
+    assert_eq!(copies[0].user_id, user_id);
 }
 
 #[tokio::test]
@@ -139,26 +139,23 @@ async fn test_add_book_to_offered_when_book_does_not_exist() {
         .times(1)
         .returning(|_, _| Ok(None));
 
-    // Configurar o mock do books_offered_repository para retornar None para find
-    // (indicando que o livro ainda não está na lista de possuídos)
+    // Configurar o mock do books_offered_repository para criar um exemplar
     books_offered_repo
-        .expect_find()
-        .with(eq(book_id), eq(user_id))
-        .times(1)
-        .returning(|_, _| Ok(None));
-
-    // Configurar o mock do books_offered_repository para retornar um BookOffered para create
-    books_offered_repo
-        .expect_create()
-        .with(function(move |dto: &CreateBookOfferedDto| {
-            dto.book_id == book_id && dto.user_id == user_id
-        }))
+        .expect_create_copies()
+        .with(
+            function(move |dto: &CreateBookOfferedDto| {
+                dto.book_id == book_id && dto.user_id == user_id
+            }),
+            eq(1),
+        )
         .times(1)
-        .returning(move |dto| {
-            Ok(BookOffered {
+        .returning(move |dto, _| {
+            Ok(vec![BookOffered {
+                id: Uuid::new_v4(),
                 book_id: dto.book_id,
                 user_id: dto.user_id,
-            })
+                attributes: OfferedCopyAttributes::default(),
+            }])
         });
 
     // Act
@@ -167,19 +164,21 @@ async fn test_add_book_to_offered_when_book_does_not_exist() {
         Arc::new(books_offered_repo),
         Arc::new(books_wanted_repo),
         Arc::new(google_book_service),
+        Arc::new(MockBookPhotoService::new()),
     );
 
-    let result = service.add_book_to_offered(google_id, &user_id).await;
+    let result = service.add_book_to_offered(google_id, &user_id, OfferedCopyAttributes::default(), 1).await;
 
     // Assert
     assert!(result.is_ok());
-    let book_offered = result.unwrap();
-    assert_eq!(book_offered.book_id, book_id);
-    assert_eq!(book_offered.user_id, user_id);
+    let copies = result.unwrap();
+    assert_eq!(copies.len(), 1);
+    assert_eq!(copies[0].book_id, book_id);
+    assert_eq!(copies[0].user_id, user_id);
 }
 
 #[tokio::test]
-async fn test_add_book_to_offered_when_book_already_offered() {
+async fn test_add_book_to_offered_when_book_already_offered_adds_copies() {
     // Arrange
     let mut book_repo = MockBookRepository::new();
     let mut books_offered_repo = MockBooksOfferedRepository::new();
@@ -218,21 +217,25 @@ async fn test_add_book_to_offered_when_book_already_offered() {
         .times(1)
         .returning(|_, _| Ok(None));
 
-    // Configurar o mock do books_offered_repository para retornar Some para find
-    // (indicando que o livro já está na lista de possuídos)
+    // O usuário já possui o livro, mas pode cadastrar novos exemplares
     books_offered_repo
-        .expect_find()
-        .with(eq(book_id), eq(user_id))
+        .expect_create_copies()
+        .with(always(), eq(2))
         .times(1)
-        .returning(move |book_id, user_id| {
-            Ok(Some(BookOffered {
-                book_id: *book_id,
-                user_id: *user_id,
-            }))
+        .returning(move |dto, quantity| {
+            Ok((0..quantity)
+                .map(|_| BookOffered {
+                    id: Uuid::new_v4(),
+                    book_id: dto.book_id,
+                    user_id: dto.user_id,
+                    attributes: dto.attributes.clone(),
+                })
+                .collect())
         });
 
     // Google Book Service não será usado neste teste
     let google_book_service = MockGoogleBookService::new();
+    let book_photo_service = MockBookPhotoService::new();
 
     // Act
     let service = BookOfferedServiceImpl::new(
@@ -240,17 +243,43 @@ async fn test_add_book_to_offered_when_book_already_offered() {
         Arc::new(books_offered_repo),
         Arc::new(books_wanted_repo),
         Arc::new(google_book_service),
+        Arc::new(book_photo_service),
     );
 
-    let result = service.add_book_to_offered(google_id, &user_id).await;
+    let result = service.add_book_to_offered(google_id, &user_id, OfferedCopyAttributes::default(), 2).await;
 
     // Assert
-    assert!(result.is_err());
-    match result {
-        Err(AppError::ValidationError(msg)) => {
-            assert_eq!(msg, "Este livro já está na sua lista de possuídos");
+    let copies = result.expect("Deve adicionar novos exemplares");
+    assert_eq!(copies.len(), 2);
+    assert_ne!(copies[0].id, copies[1].id, "Cada exemplar deve ter o seu próprio ID");
+    assert!(copies.iter().all(|copy| copy.book_id == book_id));
+}
+
+#[tokio::test]
+async fn test_add_book_to_offered_with_invalid_quantity() {
+    // Arrange
+    let service = BookOfferedServiceImpl::new(
+        Arc::new(MockBookRepository::new()),
+        Arc::new(MockBooksOfferedRepository::new()),
+        Arc::new(MockBooksWantedRepository::new()),
+        Arc::new(MockGoogleBookService::new()),
+        Arc::new(MockBookPhotoService::new()),
+    );
+    let user_id = Uuid::new_v4();
+
+    for quantity in [0, MAX_COPIES_PER_REQUEST + 1] {
+        // Act
+        let result = service
+            .add_book_to_offered("any123", &user_id, OfferedCopyAttributes::default(), quantity)
+            .await;
+
+        // Assert
+        match result {
+            Err(AppError::ValidationError(msg)) => {
+                assert_eq!(msg, format!("A quantidade deve estar entre 1 e {}", MAX_COPIES_PER_REQUEST));
+            }
+            _ => panic!("Erro inesperado"),
         }
-        _ => panic!("Erro inesperado"),
     }
 }
 
@@ -296,11 +325,13 @@ async fn test_add_book_to_offered_when_book_already_wanted() {
             Ok(Some(BookWanted {
                 book_id: *book_id,
                 user_id: *user_id,
+                priority: DEFAULT_WANTED_PRIORITY,
             }))
         });
 
     // Google Book Service não será usado neste teste
     let google_book_service = MockGoogleBookService::new();
+    let book_photo_service = MockBookPhotoService::new();
 
     // Act
     let service = BookOfferedServiceImpl::new(
@@ -308,14 +339,16 @@ async fn test_add_book_to_offered_when_book_already_wanted() {
         Arc::new(books_offered_repo),
         Arc::new(books_wanted_repo),
         Arc::new(google_book_service),
+        Arc::new(book_photo_service),
     );
 
-    let result = service.add_book_to_offered(google_id, &user_id).await;
+    let result = service.add_book_to_offered(google_id, &user_id, OfferedCopyAttributes::default(), 1).await;
 
     // Assert
     assert!(result.is_err());
     match result {
-        Err(AppError::ValidationError(msg)) => {
+        Err(AppError::Conflict(code, msg)) => {
+            assert_eq!(code, ErrorCode::BookAlreadyWanted);
             assert_eq!(msg, "Este livro já está na sua lista de desejados");
         }
         _ => panic!("Erro inesperado"),
@@ -3,8 +3,8 @@ use std::sync::Arc;
 use mockall::predicate::*;
 use uuid::Uuid;
 
-use crate::error::AppError;
-use crate::models::book::{BookWanted, BookOffered, CreateBookWantedDto, GoogleBookDto};
+use crate::error::{AppError, ErrorCode};
+use crate::models::book::{BookWanted, BookOffered, CreateBookWantedDto, GoogleBookDto, OfferedCopyAttributes, DEFAULT_WANTED_PRIORITY};
 use crate::repositories::book_repository::BookWithId;
 use crate::services::book_wanted_service::{BookWantedService, BookWantedServiceImpl};
 use crate::services::book_offered_wanted_service_test::{MockBookRepository, MockBooksWantedRepository, MockBooksOfferedRepository, MockGoogleBookService};
@@ -68,6 +68,7 @@ async fn test_add_book_to_wanted_when_book_exists() {
             Ok(BookWanted {
                 book_id: dto.book_id,
                 user_id: dto.user_id,
+                priority: DEFAULT_WANTED_PRIORITY,
             })
         });
 
@@ -82,7 +83,7 @@ async fn test_add_book_to_wanted_when_book_exists() {
         Arc::new(google_book_service),
     );
 
-    let result = service.add_book_to_wanted(google_id, &user_id).await;
+    let result = service.add_book_to_wanted(google_id, &user_id, DEFAULT_WANTED_PRIORITY).await;
 
     // Assert
     assert!(result.is_ok());
@@ -158,6 +159,7 @@ async fn test_add_book_to_wanted_when_book_does_not_exist() {
             Ok(BookWanted {
                 book_id: dto.book_id,
                 user_id: dto.user_id,
+                priority: DEFAULT_WANTED_PRIORITY,
             })
         });
 
@@ -169,7 +171,7 @@ async fn test_add_book_to_wanted_when_book_does_not_exist() {
         Arc::new(google_book_service),
     );
 
-    let result = service.add_book_to_wanted(google_id, &user_id).await;
+    let result = service.add_book_to_wanted(google_id, &user_id, DEFAULT_WANTED_PRIORITY).await;
 
     // Assert
     assert!(result.is_ok());
@@ -228,6 +230,7 @@ async fn test_add_book_to_wanted_when_book_already_wanted() {
             Ok(Some(BookWanted {
                 book_id: *book_id,
                 user_id: *user_id,
+                priority: DEFAULT_WANTED_PRIORITY,
             }))
         });
 
@@ -242,12 +245,13 @@ async fn test_add_book_to_wanted_when_book_already_wanted() {
         Arc::new(google_book_service),
     );
 
-    let result = service.add_book_to_wanted(google_id, &user_id).await;
+    let result = service.add_book_to_wanted(google_id, &user_id, DEFAULT_WANTED_PRIORITY).await;
 
     // Assert
     assert!(result.is_err());
     match result {
-        Err(AppError::ValidationError(msg)) => {
+        Err(AppError::Conflict(code, msg)) => {
+            assert_eq!(code, ErrorCode::BookAlreadyWanted);
             assert_eq!(msg, "Este livro já está na sua lista de desejados");
         }
         _ => panic!("Erro inesperado"),
@@ -294,8 +298,10 @@ async fn test_add_book_to_wanted_when_book_already_offered() {
         .times(1)
         .returning(move |book_id, user_id| {
             Ok(Some(BookOffered {
+                id: Uuid::new_v4(),
                 book_id: *book_id,
                 user_id: *user_id,
+                attributes: OfferedCopyAttributes::default(),
             }))
         });
 
@@ -319,12 +325,13 @@ async fn test_add_book_to_wanted_when_book_already_offered() {
         Arc::new(google_book_service),
     );
 
-    let result = service.add_book_to_wanted(google_id, &user_id).await;
+    let result = service.add_book_to_wanted(google_id, &user_id, DEFAULT_WANTED_PRIORITY).await;
 
     // Assert
     assert!(result.is_err());
     match result {
-        Err(AppError::ValidationError(msg)) => {
+        Err(AppError::Conflict(code, msg)) => {
+            assert_eq!(code, ErrorCode::BookAlreadyOffered);
             assert_eq!(msg, "Este livro já está na sua lista de possuídos");
         }
         _ => panic!("Erro inesperado"),
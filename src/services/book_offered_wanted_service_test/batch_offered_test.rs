@@ -0,0 +1,292 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use mockall::predicate::*;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::models::book::{
+    BatchItemStatus, BookOffered, CreateBookOfferedDto, GoogleBookDto, OfferedBookItem, OfferedCopyAttributes,
+    MAX_BATCH_SIZE,
+};
+use crate::services::book_offered_service::{BookOfferedService, BookOfferedServiceImpl};
+use crate::services::book_offered_wanted_service_test::{
+    create_test_book_with_id, MockBookPhotoService, MockBookRepository, MockBooksOfferedRepository,
+    MockBooksWantedRepository, MockGoogleBookService,
+};
+
+fn item(google_id: &str, quantity: i32) -> OfferedBookItem {
+    OfferedBookItem {
+        google_id: google_id.to_string(),
+        quantity,
+        attributes: OfferedCopyAttributes::default(),
+    }
+}
+
+fn copy_of(dto: &CreateBookOfferedDto) -> BookOffered {
+    BookOffered {
+        id: Uuid::new_v4(),
+        book_id: dto.book_id,
+        user_id: dto.user_id,
+        attributes: dto.attributes.clone(),
+    }
+}
+
+fn create_service(
+    book_repo: MockBookRepository,
+    books_offered_repo: MockBooksOfferedRepository,
+    books_wanted_repo: MockBooksWantedRepository,
+    google_book_service: MockGoogleBookService,
+    book_photo_service: MockBookPhotoService,
+) -> BookOfferedServiceImpl {
+    BookOfferedServiceImpl::new(
+        Arc::new(book_repo),
+        Arc::new(books_offered_repo),
+        Arc::new(books_wanted_repo),
+        Arc::new(google_book_service),
+        Arc::new(book_photo_service),
+    )
+}
+
+#[tokio::test]
+async fn test_add_books_to_offered_reports_each_item() {
+    // Arrange
+    let user_id = Uuid::new_v4();
+    let existing_id = Uuid::new_v4();
+    let new_id = Uuid::new_v4();
+    let wanted_id = Uuid::new_v4();
+
+    // Livros já cadastrados são buscados em uma única consulta
+    let mut book_repo = MockBookRepository::new();
+    book_repo
+        .expect_find_by_google_ids()
+        .times(1)
+        .returning(move |ids| {
+            assert_eq!(ids.len(), 4, "Os google_ids devem ser consultados sem repetição");
+            Ok(vec![
+                create_test_book_with_id(existing_id, "existing"),
+                create_test_book_with_id(wanted_id, "wanted"),
+            ])
+        });
+    book_repo
+        .expect_create()
+        .withf(|book: &GoogleBookDto| book.google_id == "new")
+        .times(1)
+        .returning(move |_| Ok(new_id));
+
+    // Apenas os livros ainda não cadastrados vão ao Google Books, uma vez cada
+    let lookups = Arc::new(AtomicUsize::new(0));
+    let lookups_clone = lookups.clone();
+    let google_book_service = MockGoogleBookService::new().with_find_book_by_id(move |id| {
+        lookups_clone.fetch_add(1, Ordering::SeqCst);
+        match id {
+            "new" => Ok(create_test_book_with_id(Uuid::nil(), "new").book),
+            _ => Err(AppError::NotFoundError("Livro não encontrado".to_string())),
+        }
+    });
+
+    let mut books_wanted_repo = MockBooksWantedRepository::new();
+    books_wanted_repo
+        .expect_find_by_user_id()
+        .with(eq(user_id))
+        .times(1)
+        .returning(move |_| Ok(vec![wanted_id]));
+
+    // Todos os exemplares são criados em uma única chamada
+    let mut books_offered_repo = MockBooksOfferedRepository::new();
+    books_offered_repo
+        .expect_create_many()
+        .withf(move |dtos: &[CreateBookOfferedDto]| {
+            dtos.len() == 4
+                && dtos[..2].iter().all(|dto| dto.book_id == existing_id)
+                && dtos[2].book_id == new_id
+                && dtos[3].book_id == existing_id
+        })
+        .times(1)
+        .returning(|dtos| Ok(dtos.iter().map(copy_of).collect()));
+
+    let service = create_service(
+        book_repo,
+        books_offered_repo,
+        books_wanted_repo,
+        google_book_service,
+        MockBookPhotoService::new(),
+    );
+
+    // Act
+    let results = service
+        .add_books_to_offered(
+            &user_id,
+            vec![
+                item("existing", 2),
+                item("new", 1),
+                item("missing", 1),
+                item("wanted", 1),
+                item("invalid", 0),
+                item("existing", 1),
+            ],
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(lookups.load(Ordering::SeqCst), 2);
+    assert_eq!(results.len(), 6);
+
+    assert_eq!(results[0].status, BatchItemStatus::Added);
+    assert_eq!(results[0].book_id, Some(existing_id));
+    assert_eq!(results[0].copy_ids.len(), 2);
+
+    assert_eq!(results[1].status, BatchItemStatus::Added);
+    assert_eq!(results[1].book_id, Some(new_id));
+    assert_eq!(results[1].copy_ids.len(), 1);
+
+    assert_eq!(results[2].status, BatchItemStatus::Failed);
+    assert!(results[2].message.as_ref().unwrap().contains("não encontrado"));
+
+    assert_eq!(results[3].status, BatchItemStatus::Failed);
+    assert_eq!(results[3].book_id, Some(wanted_id));
+    assert!(results[3].message.as_ref().unwrap().contains("desejados"));
+
+    assert_eq!(results[4].status, BatchItemStatus::Failed);
+    assert!(results[4].message.as_ref().unwrap().contains("quantidade"));
+
+    assert_eq!(results[5].status, BatchItemStatus::Added);
+    assert_eq!(results[5].copy_ids.len(), 1);
+    assert!(!results[0].copy_ids.contains(&results[5].copy_ids[0]));
+}
+
+#[tokio::test]
+async fn test_add_books_to_offered_validates_batch_size() {
+    let service = create_service(
+        MockBookRepository::new(),
+        MockBooksOfferedRepository::new(),
+        MockBooksWantedRepository::new(),
+        MockGoogleBookService::new(),
+        MockBookPhotoService::new(),
+    );
+    let user_id = Uuid::new_v4();
+
+    let empty = service.add_books_to_offered(&user_id, vec![]).await;
+    let oversized = service
+        .add_books_to_offered(&user_id, vec![item("x", 1); MAX_BATCH_SIZE + 1])
+        .await;
+
+    assert!(matches!(empty, Err(AppError::ValidationError(_))));
+    assert!(matches!(oversized, Err(AppError::ValidationError(_))));
+}
+
+#[tokio::test]
+async fn test_add_books_to_offered_fails_whole_batch_on_database_error() {
+    // Arrange
+    let user_id = Uuid::new_v4();
+    let book_id = Uuid::new_v4();
+
+    let mut book_repo = MockBookRepository::new();
+    book_repo
+        .expect_find_by_google_ids()
+        .returning(move |_| Ok(vec![create_test_book_with_id(book_id, "existing")]));
+
+    let mut books_wanted_repo = MockBooksWantedRepository::new();
+    books_wanted_repo.expect_find_by_user_id().returning(|_| Ok(vec![]));
+
+    let mut books_offered_repo = MockBooksOfferedRepository::new();
+    books_offered_repo
+        .expect_create_many()
+        .times(1)
+        .returning(|_| Err(AppError::DatabaseError("Falha".to_string())));
+
+    let service = create_service(
+        book_repo,
+        books_offered_repo,
+        books_wanted_repo,
+        MockGoogleBookService::new(),
+        MockBookPhotoService::new(),
+    );
+
+    // Act
+    let result = service.add_books_to_offered(&user_id, vec![item("existing", 1)]).await;
+
+    // Assert
+    assert!(matches!(result, Err(AppError::DatabaseError(_))));
+}
+
+#[tokio::test]
+async fn test_remove_books_from_offered() {
+    // Arrange
+    let user_id = Uuid::new_v4();
+    let removable_id = Uuid::new_v4();
+    let reserved_id = Uuid::new_v4();
+    let missing_id = Uuid::new_v4();
+
+    let removable_copy = BookOffered {
+        id: Uuid::new_v4(),
+        book_id: removable_id,
+        user_id,
+        attributes: OfferedCopyAttributes::default(),
+    };
+    let reserved_copy = BookOffered {
+        id: Uuid::new_v4(),
+        book_id: reserved_id,
+        user_id,
+        attributes: OfferedCopyAttributes::default(),
+    };
+    let removable_copy_id = removable_copy.id;
+    let reserved_copy_id = reserved_copy.id;
+
+    let mut books_offered_repo = MockBooksOfferedRepository::new();
+    books_offered_repo
+        .expect_find_all_by_user_id()
+        .with(eq(user_id))
+        .times(1)
+        .returning(move |_| Ok(vec![removable_copy.clone(), reserved_copy.clone()]));
+    books_offered_repo
+        .expect_any_reserved()
+        .returning(move |ids| Ok(ids.contains(&reserved_copy_id)));
+    books_offered_repo
+        .expect_delete_many()
+        .withf(move |ids: &[Uuid], user: &Uuid| ids == [removable_id] && *user == user_id)
+        .times(1)
+        .returning(|ids, _| Ok(ids.len() as u64));
+
+    // Apenas as fotos dos exemplares removidos são apagadas
+    let mut book_photo_service = MockBookPhotoService::new();
+    book_photo_service
+        .expect_storage_keys()
+        .withf(move |ids: &[Uuid]| ids == [removable_copy_id])
+        .times(1)
+        .returning(|_| Ok(vec!["foto.jpg".to_string()]));
+    book_photo_service
+        .expect_delete_stored()
+        .withf(|keys: &[String]| keys == ["foto.jpg"])
+        .times(1)
+        .returning(|_| ());
+
+    let service = create_service(
+        MockBookRepository::new(),
+        books_offered_repo,
+        MockBooksWantedRepository::new(),
+        MockGoogleBookService::new(),
+        book_photo_service,
+    );
+
+    // Act
+    let results = service
+        .remove_books_from_offered(&user_id, vec![removable_id, reserved_id, missing_id, removable_id])
+        .await
+        .unwrap();
+
+    // Assert
+    let statuses: Vec<BatchItemStatus> = results.iter().map(|r| r.status).collect();
+    assert_eq!(
+        statuses,
+        vec![
+            BatchItemStatus::Removed,
+            BatchItemStatus::Failed,
+            BatchItemStatus::Failed,
+            BatchItemStatus::Failed
+        ]
+    );
+    assert!(results[1].message.as_ref().unwrap().contains("reservados"));
+    assert!(results[2].message.as_ref().unwrap().contains("não está"));
+}
@@ -0,0 +1,184 @@
+use std::sync::Arc;
+
+use mockall::predicate::*;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::models::book::{BatchItemStatus, BookWanted, CreateBookWantedDto, WantedBookItem};
+use crate::services::book_offered_wanted_service_test::{
+    create_test_book_with_id, MockBookRepository, MockBooksOfferedRepository, MockBooksWantedRepository,
+    MockGoogleBookService,
+};
+use crate::services::book_wanted_service::{BookWantedService, BookWantedServiceImpl};
+
+fn item(google_id: &str, priority: i16) -> WantedBookItem {
+    WantedBookItem {
+        google_id: google_id.to_string(),
+        priority,
+    }
+}
+
+fn create_service(
+    book_repo: MockBookRepository,
+    books_wanted_repo: MockBooksWantedRepository,
+    books_offered_repo: MockBooksOfferedRepository,
+) -> BookWantedServiceImpl {
+    BookWantedServiceImpl::new(
+        Arc::new(book_repo),
+        Arc::new(books_wanted_repo),
+        Arc::new(books_offered_repo),
+        Arc::new(MockGoogleBookService::new()),
+    )
+}
+
+#[tokio::test]
+async fn test_add_books_to_wanted_reports_each_item() {
+    // Arrange
+    let user_id = Uuid::new_v4();
+    let new_id = Uuid::new_v4();
+    let already_wanted_id = Uuid::new_v4();
+    let offered_id = Uuid::new_v4();
+
+    let mut book_repo = MockBookRepository::new();
+    book_repo.expect_find_by_google_ids().times(1).returning(move |_| {
+        Ok(vec![
+            create_test_book_with_id(new_id, "new"),
+            create_test_book_with_id(already_wanted_id, "already_wanted"),
+            create_test_book_with_id(offered_id, "offered"),
+        ])
+    });
+
+    let mut books_offered_repo = MockBooksOfferedRepository::new();
+    books_offered_repo
+        .expect_find_by_user_id()
+        .with(eq(user_id))
+        .times(1)
+        .returning(move |_| Ok(vec![offered_id]));
+
+    let mut books_wanted_repo = MockBooksWantedRepository::new();
+    books_wanted_repo
+        .expect_find_by_user_id()
+        .with(eq(user_id))
+        .times(1)
+        .returning(move |_| Ok(vec![already_wanted_id]));
+    books_wanted_repo
+        .expect_create_many()
+        .withf(move |dtos: &[CreateBookWantedDto]| {
+            dtos.len() == 1 && dtos[0].book_id == new_id && dtos[0].priority == 5
+        })
+        .times(1)
+        .returning(|dtos| {
+            Ok(dtos
+                .iter()
+                .map(|dto| BookWanted { book_id: dto.book_id, user_id: dto.user_id, priority: dto.priority })
+                .collect())
+        });
+
+    let service = create_service(book_repo, books_wanted_repo, books_offered_repo);
+
+    // Act
+    let results = service
+        .add_books_to_wanted(
+            &user_id,
+            vec![
+                item("new", 5),
+                item("already_wanted", 3),
+                item("offered", 3),
+                item("new", 2),
+                item("priority", 9),
+            ],
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    let statuses: Vec<BatchItemStatus> = results.iter().map(|r| r.status).collect();
+    assert_eq!(
+        statuses,
+        vec![
+            BatchItemStatus::Added,
+            BatchItemStatus::Failed,
+            BatchItemStatus::Failed,
+            BatchItemStatus::Failed,
+            BatchItemStatus::Failed
+        ]
+    );
+    assert_eq!(results[0].book_id, Some(new_id));
+    assert!(results[1].message.as_ref().unwrap().contains("desejados"));
+    assert!(results[2].message.as_ref().unwrap().contains("possuídos"));
+    assert!(results[3].message.as_ref().unwrap().contains("desejados"));
+    assert!(results[4].message.as_ref().unwrap().contains("prioridade"));
+}
+
+#[tokio::test]
+async fn test_add_books_to_wanted_without_valid_items_does_not_write() {
+    // Arrange
+    let mut book_repo = MockBookRepository::new();
+    book_repo.expect_find_by_google_ids().returning(|_| Ok(vec![]));
+
+    let mut books_offered_repo = MockBooksOfferedRepository::new();
+    books_offered_repo.expect_find_by_user_id().returning(|_| Ok(vec![]));
+
+    let mut books_wanted_repo = MockBooksWantedRepository::new();
+    books_wanted_repo.expect_find_by_user_id().returning(|_| Ok(vec![]));
+    books_wanted_repo.expect_create_many().never();
+
+    let service = create_service(book_repo, books_wanted_repo, books_offered_repo);
+
+    // Act
+    let results = service
+        .add_books_to_wanted(&Uuid::new_v4(), vec![item("missing", 3)])
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].status, BatchItemStatus::Failed);
+}
+
+#[tokio::test]
+async fn test_remove_books_from_wanted() {
+    // Arrange
+    let user_id = Uuid::new_v4();
+    let wanted_id = Uuid::new_v4();
+    let missing_id = Uuid::new_v4();
+
+    let mut books_wanted_repo = MockBooksWantedRepository::new();
+    books_wanted_repo
+        .expect_find_by_user_id()
+        .with(eq(user_id))
+        .times(1)
+        .returning(move |_| Ok(vec![wanted_id]));
+    books_wanted_repo
+        .expect_delete_many()
+        .withf(move |ids: &[Uuid], user: &Uuid| ids == [wanted_id] && *user == user_id)
+        .times(1)
+        .returning(|ids, _| Ok(ids.len() as u64));
+
+    let service = create_service(MockBookRepository::new(), books_wanted_repo, MockBooksOfferedRepository::new());
+
+    // Act
+    let results = service
+        .remove_books_from_wanted(&user_id, vec![wanted_id, missing_id, wanted_id])
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(results[0].status, BatchItemStatus::Removed);
+    assert_eq!(results[1].status, BatchItemStatus::Failed);
+    assert_eq!(results[2].status, BatchItemStatus::Failed);
+    assert_eq!(results[1].id, missing_id.to_string());
+}
+
+#[tokio::test]
+async fn test_remove_books_from_wanted_validates_batch_size() {
+    let service = create_service(
+        MockBookRepository::new(),
+        MockBooksWantedRepository::new(),
+        MockBooksOfferedRepository::new(),
+    );
+
+    let result = service.remove_books_from_wanted(&Uuid::new_v4(), vec![]).await;
+
+    assert!(matches!(result, Err(AppError::ValidationError(_))));
+}
@@ -0,0 +1,101 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::error::{AppError, ErrorCode};
+use crate::models::book::{BookCondition, BookOffered, OfferedCopyAttributes};
+use crate::services::book_wanted_service::{BookWantedService, BookWantedServiceImpl};
+use crate::services::book_offered_wanted_service_test::{MockBookRepository, MockBooksWantedRepository, MockBooksOfferedRepository, MockGoogleBookService};
+
+fn setup_service(books_offered_repo: MockBooksOfferedRepository) -> BookWantedServiceImpl {
+    BookWantedServiceImpl::new(
+        Arc::new(MockBookRepository::new()),
+        Arc::new(MockBooksWantedRepository::new()),
+        Arc::new(books_offered_repo),
+        Arc::new(MockGoogleBookService::new()),
+    )
+}
+
+#[tokio::test]
+async fn test_mark_as_acquired() {
+    // Arrange
+    let mut books_offered_repo = MockBooksOfferedRepository::new();
+
+    let book_id = Uuid::new_v4();
+    let user_id = Uuid::new_v4();
+
+    // A retirada dos desejados e a criação do exemplar são feitas pelo repositório
+    books_offered_repo
+        .expect_create_from_wanted()
+        .withf(move |dto| {
+            dto.book_id == book_id && dto.user_id == user_id && dto.attributes.condition == BookCondition::Good
+        })
+        .times(1)
+        .returning(|dto| {
+            Ok(Some(BookOffered {
+                id: Uuid::new_v4(),
+                book_id: dto.book_id,
+                user_id: dto.user_id,
+                attributes: dto.attributes.clone(),
+            }))
+        });
+
+    let service = setup_service(books_offered_repo);
+    let attributes = OfferedCopyAttributes { condition: BookCondition::Good, ..OfferedCopyAttributes::default() };
+
+    // Act
+    let result = service.mark_as_acquired(&book_id, &user_id, attributes).await;
+
+    // Assert
+    let copy = result.unwrap();
+    assert_eq!(copy.book_id, book_id);
+    assert_eq!(copy.user_id, user_id);
+    assert_eq!(copy.attributes.condition, BookCondition::Good);
+}
+
+#[tokio::test]
+async fn test_mark_as_acquired_when_not_wanted() {
+    // Arrange
+    let mut books_offered_repo = MockBooksOfferedRepository::new();
+
+    books_offered_repo
+        .expect_create_from_wanted()
+        .times(1)
+        .returning(|_| Ok(None));
+
+    let service = setup_service(books_offered_repo);
+
+    // Act
+    let result = service
+        .mark_as_acquired(&Uuid::new_v4(), &Uuid::new_v4(), OfferedCopyAttributes::default())
+        .await;
+
+    // Assert
+    match result {
+        Err(AppError::Rejected(ErrorCode::BookNotWanted, message)) => {
+            assert_eq!(message, "Este livro não está na sua lista de desejados");
+        }
+        _ => panic!("Esperava BOOK_NOT_WANTED"),
+    }
+}
+
+#[tokio::test]
+async fn test_mark_as_acquired_with_invalid_attributes() {
+    // Arrange: o repositório não deve ser chamado
+    let books_offered_repo = MockBooksOfferedRepository::new();
+    let service = setup_service(books_offered_repo);
+
+    let attributes = OfferedCopyAttributes {
+        notes: Some("a".repeat(2000)),
+        ..OfferedCopyAttributes::default()
+    };
+
+    // Act
+    let result = service.mark_as_acquired(&Uuid::new_v4(), &Uuid::new_v4(), attributes).await;
+
+    // Assert
+    match result {
+        Err(AppError::InvalidFields(fields)) => assert_eq!(fields[0].field, "notes"),
+        other => panic!("Resultado inesperado: {:?}", other),
+    }
+}
@@ -2,8 +2,12 @@ pub mod add_book_to_offered_test;
 pub mod remove_book_from_offered_test;
 pub mod add_book_to_wanted_test;
 pub mod remove_book_from_wanted_test;
+pub mod update_wanted_priority_test;
+pub mod batch_offered_test;
+pub mod batch_wanted_test;
+pub mod mark_as_acquired_test;
 
 pub use crate::services::test_mocks::{
     MockBookRepository, MockBooksOfferedRepository, MockBooksWantedRepository, 
-    MockGoogleBookService, create_test_book_with_id
+    MockGoogleBookService, MockBookPhotoService, create_test_book_with_id
 };
\ No newline at end of file
@@ -3,10 +3,10 @@ use std::sync::Arc;
 use mockall::predicate::*;
 use uuid::Uuid;
 
-use crate::error::AppError;
-use crate::models::book::BookOffered;
+use crate::error::{AppError, ErrorCode};
+use crate::models::book::{BookOffered, OfferedCopyAttributes};
 use crate::services::book_offered_service::{BookOfferedService, BookOfferedServiceImpl};
-use crate::services::book_offered_wanted_service_test::{MockBookRepository, MockBooksOfferedRepository, MockBooksWantedRepository, MockGoogleBookService};
+use crate::services::book_offered_wanted_service_test::{MockBookRepository, MockBooksOfferedRepository, MockBooksWantedRepository, MockGoogleBookService, MockBookPhotoService};
 
 #[tokio::test]
 async fn test_remove_book_from_offered() {
@@ -15,22 +15,44 @@ async fn test_remove_book_from_offered() {
     let mut books_offered_repo = MockBooksOfferedRepository::new();
     let books_wanted_repo = MockBooksWantedRepository::new();
     let google_book_service = MockGoogleBookService::new();
+    let mut book_photo_service = MockBookPhotoService::new();
 
     let book_id = Uuid::new_v4();
     let user_id = Uuid::new_v4();
+    let copy_id = Uuid::new_v4();
 
-    // Configurar o mock do books_offered_repository para retornar Some para find
+    // Os arquivos das fotos do exemplar só são apagados depois da remoção
+    book_photo_service
+        .expect_storage_keys()
+        .withf(move |ids: &[Uuid]| ids == [copy_id])
+        .times(1)
+        .returning(|_| Ok(vec!["foto.jpg".to_string()]));
+    book_photo_service
+        .expect_delete_stored()
+        .withf(|keys: &[String]| keys == ["foto.jpg"])
+        .times(1)
+        .returning(|_| ());
+
+    // Configurar o mock do books_offered_repository para retornar o exemplar do livro
     books_offered_repo
-        .expect_find()
+        .expect_find_copies()
         .with(eq(book_id), eq(user_id))
         .times(1)
         .returning(move |book_id, user_id| {
-            Ok(Some(BookOffered {
+            Ok(vec![BookOffered {
+                id: copy_id,
                 book_id: *book_id,
                 user_id: *user_id,
-            }))
+                attributes: OfferedCopyAttributes::default(),
+            }])
         });
 
+    // O exemplar não está reservado em nenhuma troca
+    books_offered_repo
+        .expect_any_reserved()
+        .times(1)
+        .returning(|_| Ok(false));
+
     // Configurar o mock do books_offered_repository para retornar true para delete
     books_offered_repo
         .expect_delete()
@@ -44,13 +66,14 @@ async fn test_remove_book_from_offered() {
         Arc::new(books_offered_repo),
         Arc::new(books_wanted_repo),
         Arc::new(google_book_service),
+        Arc::new(book_photo_service),
     );
 
     let result = service.remove_book_from_offered(&book_id, &user_id).await;
 
     // Assert
     assert!(result.is_ok());
-    assert_eq!(result.unwrap(), true);
+    assert!(result.unwrap());
 }
 
 #[tokio::test]
@@ -60,16 +83,17 @@ async fn test_remove_book_from_offered_when_not_offered() {
     let mut books_offered_repo = MockBooksOfferedRepository::new();
     let books_wanted_repo = MockBooksWantedRepository::new();
     let google_book_service = MockGoogleBookService::new();
+    let book_photo_service = MockBookPhotoService::new();
 
     let book_id = Uuid::new_v4();
     let user_id = Uuid::new_v4();
 
-    // Configurar o mock do books_offered_repository para retornar None para find
+    // Configurar o mock do books_offered_repository para não retornar exemplares
     books_offered_repo
-        .expect_find()
+        .expect_find_copies()
         .with(eq(book_id), eq(user_id))
         .times(1)
-        .returning(|_, _| Ok(None));
+        .returning(|_, _| Ok(vec![]));
 
     // Act
     let service = BookOfferedServiceImpl::new(
@@ -77,6 +101,7 @@ async fn test_remove_book_from_offered_when_not_offered() {
         Arc::new(books_offered_repo),
         Arc::new(books_wanted_repo),
         Arc::new(google_book_service),
+        Arc::new(book_photo_service),
     );
 
     let result = service.remove_book_from_offered(&book_id, &user_id).await;
@@ -84,9 +109,115 @@ async fn test_remove_book_from_offered_when_not_offered() {
     // Assert
     assert!(result.is_err());
     match result {
-        Err(AppError::ValidationError(msg)) => {
+        Err(AppError::Rejected(ErrorCode::BookNotOffered, msg)) => {
             assert_eq!(msg, "Este livro não está na sua lista de possuídos");
         }
         _ => panic!("Erro inesperado"),
     }
-} 
\ No newline at end of file
+}
+
+#[tokio::test]
+async fn test_remove_book_from_offered_when_copy_reserved() {
+    // Arrange
+    let mut books_offered_repo = MockBooksOfferedRepository::new();
+
+    let book_id = Uuid::new_v4();
+    let user_id = Uuid::new_v4();
+
+    books_offered_repo
+        .expect_find_copies()
+        .times(1)
+        .returning(move |book_id, user_id| {
+            Ok(vec![BookOffered {
+                id: Uuid::new_v4(),
+                book_id: *book_id,
+                user_id: *user_id,
+                attributes: OfferedCopyAttributes::default(),
+            }])
+        });
+
+    // O exemplar está prometido em uma troca em andamento
+    books_offered_repo
+        .expect_any_reserved()
+        .times(1)
+        .returning(|_| Ok(true));
+
+    // Nem as fotos nem o exemplar podem ser removidos
+    books_offered_repo.expect_delete().times(0);
+
+    // Act
+    let service = BookOfferedServiceImpl::new(
+        Arc::new(MockBookRepository::new()),
+        Arc::new(books_offered_repo),
+        Arc::new(MockBooksWantedRepository::new()),
+        Arc::new(MockGoogleBookService::new()),
+        Arc::new(MockBookPhotoService::new()),
+    );
+
+    let result = service.remove_book_from_offered(&book_id, &user_id).await;
+
+    // Assert
+    match result {
+        Err(AppError::Conflict(code, msg)) => {
+            assert_eq!(code, ErrorCode::CopyReserved);
+            assert_eq!(msg, "Este livro tem exemplares reservados em trocas em andamento");
+        }
+        _ => panic!("Erro inesperado"),
+    }
+}
+
+#[tokio::test]
+async fn test_remove_book_from_offered_keeps_photos_when_reserved_meanwhile() {
+    // Arrange - A reserva acontece entre a verificação e a remoção
+    let mut books_offered_repo = MockBooksOfferedRepository::new();
+    let mut book_photo_service = MockBookPhotoService::new();
+
+    let book_id = Uuid::new_v4();
+    let user_id = Uuid::new_v4();
+
+    books_offered_repo
+        .expect_find_copies()
+        .times(1)
+        .returning(move |book_id, user_id| {
+            Ok(vec![BookOffered {
+                id: Uuid::new_v4(),
+                book_id: *book_id,
+                user_id: *user_id,
+                attributes: OfferedCopyAttributes::default(),
+            }])
+        });
+    books_offered_repo
+        .expect_any_reserved()
+        .times(1)
+        .returning(|_| Ok(false));
+    books_offered_repo
+        .expect_delete()
+        .times(1)
+        .returning(|_, _| {
+            Err(AppError::Conflict(
+                ErrorCode::CopyReserved,
+                "Este livro tem exemplares reservados em trocas em andamento".to_string(),
+            ))
+        });
+
+    // Os arquivos das fotos não podem ser apagados
+    book_photo_service
+        .expect_storage_keys()
+        .times(1)
+        .returning(|_| Ok(vec!["foto.jpg".to_string()]));
+    book_photo_service.expect_delete_stored().times(0);
+
+    // Act
+    let service = BookOfferedServiceImpl::new(
+        Arc::new(MockBookRepository::new()),
+        Arc::new(books_offered_repo),
+        Arc::new(MockBooksWantedRepository::new()),
+        Arc::new(MockGoogleBookService::new()),
+        Arc::new(book_photo_service),
+    );
+
+    let result = service.remove_book_from_offered(&book_id, &user_id).await;
+
+    // Assert
+    assert!(matches!(result, Err(AppError::Conflict(ErrorCode::CopyReserved, _))));
+}
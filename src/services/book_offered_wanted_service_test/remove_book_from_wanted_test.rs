@@ -3,8 +3,8 @@ use std::sync::Arc;
 use mockall::predicate::*;
 use uuid::Uuid;
 
-use crate::error::AppError;
-use crate::models::book::BookWanted;
+use crate::error::{AppError, ErrorCode};
+use crate::models::book::{BookWanted, DEFAULT_WANTED_PRIORITY};
 use crate::services::book_wanted_service::{BookWantedService, BookWantedServiceImpl};
 use crate::services::book_offered_wanted_service_test::{MockBookRepository, MockBooksWantedRepository, MockBooksOfferedRepository, MockGoogleBookService};
 
@@ -28,6 +28,7 @@ async fn test_remove_book_from_wanted() {
             Ok(Some(BookWanted {
                 book_id: *book_id,
                 user_id: *user_id,
+                priority: DEFAULT_WANTED_PRIORITY,
             }))
         });
 
@@ -50,7 +51,7 @@ async fn test_remove_book_from_wanted() {
 
     // Assert
     assert!(result.is_ok());
-    assert_eq!(result.unwrap(), true);
+    assert!(result.unwrap());
 }
 
 #[tokio::test]
@@ -84,7 +85,7 @@ async fn test_remove_book_from_wanted_when_not_wanted() {
     // Assert
     assert!(result.is_err());
     match result {
-        Err(AppError::ValidationError(msg)) => {
+        Err(AppError::Rejected(ErrorCode::BookNotWanted, msg)) => {
             assert_eq!(msg, "Este livro não está na sua lista de desejados");
         }
         _ => panic!("Erro inesperado"),
@@ -0,0 +1,212 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::error::{AppError, ErrorCode};
+use crate::i18n::messages;
+use crate::models::book::{BookPhoto, CreateBookPhotoDto};
+use crate::repositories::book_photo_repository::BookPhotoRepository;
+use crate::repositories::books_offered_repository::BooksOfferedRepository;
+use crate::services::photo_storage::PhotoStorage;
+
+/// Número máximo de fotos por exemplar oferecido
+pub const MAX_PHOTOS_PER_COPY: usize = 5;
+
+/// Tipos de imagem aceitos, com a extensão usada no armazenamento
+const ALLOWED_PHOTO_TYPES: [(&str, &str); 3] = [
+    ("image/jpeg", "jpg"),
+    ("image/png", "png"),
+    ("image/webp", "webp"),
+];
+
+/// Conteúdo de uma foto pronto para ser devolvido ao cliente
+#[derive(Debug)]
+pub struct PhotoContent {
+    pub content_type: String,
+    pub content: Vec<u8>,
+}
+
+#[async_trait]
+pub trait BookPhotoService: Send + Sync + 'static {
+    async fn add_photo(
+        &self,
+        copy_id: &Uuid,
+        user_id: &Uuid,
+        content_type: &str,
+        content: Vec<u8>,
+    ) -> Result<BookPhoto, AppError>;
+    async fn remove_photo(&self, photo_id: &Uuid, user_id: &Uuid) -> Result<bool, AppError>;
+    async fn get_photo(&self, photo_id: &Uuid) -> Result<PhotoContent, AppError>;
+    /// Chaves no armazenamento das fotos dos exemplares
+    async fn storage_keys(&self, copy_ids: &[Uuid]) -> Result<Vec<String>, AppError>;
+    /// Apaga os arquivos de fotos cujos registros já saíram do banco
+    ///
+    /// Os registros somem junto com o exemplar, na mesma transação; os arquivos
+    /// só são apagados depois, para que uma remoção que falhe não leve as fotos.
+    async fn delete_stored(&self, storage_keys: &[String]);
+}
+
+pub struct BookPhotoServiceImpl {
+    books_offered_repository: Arc<dyn BooksOfferedRepository>,
+    book_photo_repository: Arc<dyn BookPhotoRepository>,
+    photo_storage: Arc<dyn PhotoStorage>,
+    max_photo_bytes: usize,
+}
+
+impl BookPhotoServiceImpl {
+    pub fn new(
+        books_offered_repository: Arc<dyn BooksOfferedRepository>,
+        book_photo_repository: Arc<dyn BookPhotoRepository>,
+        photo_storage: Arc<dyn PhotoStorage>,
+        max_photo_bytes: usize,
+    ) -> Self {
+        Self {
+            books_offered_repository,
+            book_photo_repository,
+            photo_storage,
+            max_photo_bytes,
+        }
+    }
+
+    // Valida tamanho e tipo da foto, retornando a extensão usada no armazenamento
+    fn validate_photo(&self, content_type: &str, content: &[u8]) -> Result<&'static str, AppError> {
+        if content.is_empty() {
+            return Err(AppError::ValidationError(messages::PHOTO_EMPTY.text()));
+        }
+
+        if content.len() > self.max_photo_bytes {
+            return Err(AppError::ValidationError(messages::PHOTO_TOO_LARGE.format(&[&self.max_photo_bytes])));
+        }
+
+        let extension = ALLOWED_PHOTO_TYPES
+            .iter()
+            .find(|(allowed, _)| *allowed == content_type)
+            .map(|(_, extension)| *extension)
+            .ok_or_else(|| {
+                AppError::ValidationError(messages::PHOTO_TYPE_UNSUPPORTED.format(&[&content_type]))
+            })?;
+
+        // O conteúdo precisa corresponder ao tipo declarado
+        if !matches_signature(content_type, content) {
+            return Err(AppError::ValidationError(
+                messages::PHOTO_CONTENT_MISMATCH.text(),
+            ));
+        }
+
+        Ok(extension)
+    }
+}
+
+// Confere os bytes iniciais do arquivo com a assinatura do formato declarado
+fn matches_signature(content_type: &str, content: &[u8]) -> bool {
+    match content_type {
+        "image/jpeg" => content.starts_with(&[0xFF, 0xD8, 0xFF]),
+        "image/png" => content.starts_with(&[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A]),
+        "image/webp" => content.len() >= 12 && &content[0..4] == b"RIFF" && &content[8..12] == b"WEBP",
+        _ => false,
+    }
+}
+
+#[async_trait]
+impl BookPhotoService for BookPhotoServiceImpl {
+    async fn add_photo(
+        &self,
+        copy_id: &Uuid,
+        user_id: &Uuid,
+        content_type: &str,
+        content: Vec<u8>,
+    ) -> Result<BookPhoto, AppError> {
+        let extension = self.validate_photo(content_type, &content)?;
+
+        // Verificar se o exemplar pertence ao usuário
+        let is_owner = self
+            .books_offered_repository
+            .find_by_id(copy_id)
+            .await?
+            .is_some_and(|copy| copy.user_id == *user_id);
+        if !is_owner {
+            return Err(AppError::Rejected(
+                ErrorCode::CopyNotOffered,
+                messages::COPY_NOT_OFFERED.text(),
+            ));
+        }
+
+        let existing = self.book_photo_repository.find_by_copy_id(copy_id).await?;
+        if existing.len() >= MAX_PHOTOS_PER_COPY {
+            return Err(AppError::Rejected(
+                ErrorCode::PhotoLimitReached,
+                messages::PHOTO_LIMIT_REACHED.format(&[&MAX_PHOTOS_PER_COPY]),
+            ));
+        }
+
+        // Gravar o conteúdo antes de registrar os metadados
+        let storage_key = format!("{}.{}", Uuid::new_v4(), extension);
+        self.photo_storage.save(&storage_key, &content).await?;
+
+        let create_dto = CreateBookPhotoDto {
+            copy_id: *copy_id,
+            user_id: *user_id,
+            storage_key: storage_key.clone(),
+            content_type: content_type.to_string(),
+            size_bytes: content.len() as i32,
+        };
+
+        match self.book_photo_repository.create(&create_dto).await {
+            Ok(record) => Ok(BookPhoto::from(record)),
+            Err(e) => {
+                // Não deixar arquivos órfãos quando o registro falha
+                let _ = self.photo_storage.delete(&storage_key).await;
+                Err(e)
+            }
+        }
+    }
+
+    async fn remove_photo(&self, photo_id: &Uuid, user_id: &Uuid) -> Result<bool, AppError> {
+        let photo = self
+            .book_photo_repository
+            .find_by_id(photo_id)
+            .await?
+            .filter(|photo| photo.user_id == *user_id)
+            .ok_or_else(|| AppError::NotFoundError(messages::PHOTO_NOT_FOUND.text()))?;
+
+        let deleted = self.book_photo_repository.delete(&photo.id).await?;
+        self.photo_storage.delete(&photo.storage_key).await?;
+
+        Ok(deleted)
+    }
+
+    async fn get_photo(&self, photo_id: &Uuid) -> Result<PhotoContent, AppError> {
+        let photo = self
+            .book_photo_repository
+            .find_by_id(photo_id)
+            .await?
+            .ok_or_else(|| AppError::NotFoundError(messages::PHOTO_NOT_FOUND.text()))?;
+
+        let content = self.photo_storage.load(&photo.storage_key).await?;
+
+        Ok(PhotoContent {
+            content_type: photo.content_type,
+            content,
+        })
+    }
+
+    async fn storage_keys(&self, copy_ids: &[Uuid]) -> Result<Vec<String>, AppError> {
+        if copy_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let photos = self.book_photo_repository.find_by_copy_ids(copy_ids).await?;
+
+        Ok(photos.into_iter().map(|photo| photo.storage_key).collect())
+    }
+
+    async fn delete_stored(&self, storage_keys: &[String]) {
+        // A remoção já foi gravada; um arquivo que sobre não afeta o usuário
+        for storage_key in storage_keys {
+            if let Err(e) = self.photo_storage.delete(storage_key).await {
+                tracing::warn!("Falha ao apagar o arquivo da foto {}: {}", storage_key, e);
+            }
+        }
+    }
+}
@@ -0,0 +1,265 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use mockall::predicate::*;
+use uuid::Uuid;
+
+use crate::error::{AppError, ErrorCode};
+use crate::models::book::{BookOffered, BookPhotoRecord, CreateBookPhotoDto, OfferedCopyAttributes};
+use crate::services::book_photo_service::{BookPhotoService, BookPhotoServiceImpl, MAX_PHOTOS_PER_COPY};
+use crate::services::photo_storage::{LocalPhotoStorage, PhotoStorage};
+use crate::services::test_mocks::{MockBookPhotoRepository, MockBooksOfferedRepository, MockPhotoStorage};
+
+const MAX_BYTES: usize = 64;
+const OWNER_ID: Uuid = Uuid::from_u128(0x5eed);
+
+// Conteúdo mínimo com a assinatura de um arquivo PNG
+fn png_content() -> Vec<u8> {
+    vec![0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, 0x00, 0x00]
+}
+
+fn create_photo_record(copy_id: Uuid, user_id: Uuid, storage_key: &str) -> BookPhotoRecord {
+    BookPhotoRecord {
+        id: Uuid::new_v4(),
+        copy_id,
+        user_id,
+        storage_key: storage_key.to_string(),
+        content_type: "image/png".to_string(),
+        size_bytes: 10,
+        created_at: Utc::now().naive_utc(),
+    }
+}
+
+fn offered_repo_with_copy() -> MockBooksOfferedRepository {
+    let mut books_offered_repo = MockBooksOfferedRepository::new();
+    books_offered_repo.expect_find_by_id().returning(|copy_id| {
+        Ok(Some(BookOffered {
+            id: *copy_id,
+            book_id: Uuid::new_v4(),
+            user_id: OWNER_ID,
+            attributes: OfferedCopyAttributes::default(),
+        }))
+    });
+    books_offered_repo
+}
+
+fn create_service(
+    books_offered_repo: MockBooksOfferedRepository,
+    photo_repo: MockBookPhotoRepository,
+    storage: MockPhotoStorage,
+) -> BookPhotoServiceImpl {
+    BookPhotoServiceImpl::new(
+        Arc::new(books_offered_repo),
+        Arc::new(photo_repo),
+        Arc::new(storage),
+        MAX_BYTES,
+    )
+}
+
+fn expect_validation_error(result: Result<impl std::fmt::Debug, AppError>, expected: &str) {
+    match result {
+        Err(AppError::ValidationError(msg)) => assert!(msg.contains(expected), "Mensagem inesperada: {}", msg),
+        other => panic!("Esperado ValidationError, obtido {:?}", other),
+    }
+}
+
+fn expect_rejected(result: Result<impl std::fmt::Debug, AppError>, code: ErrorCode, expected: &str) {
+    match result {
+        Err(AppError::Rejected(actual, msg)) if actual == code => {
+            assert!(msg.contains(expected), "Mensagem inesperada: {}", msg)
+        }
+        other => panic!("Esperado {:?}, obtido {:?}", code, other),
+    }
+}
+
+#[tokio::test]
+async fn test_add_photo_success() {
+    let copy_id = Uuid::new_v4();
+    let user_id = OWNER_ID;
+
+    let mut photo_repo = MockBookPhotoRepository::new();
+    photo_repo
+        .expect_find_by_copy_id()
+        .with(eq(copy_id))
+        .times(1)
+        .returning(|_| Ok(vec![]));
+    photo_repo
+        .expect_create()
+        .withf(move |dto: &CreateBookPhotoDto| {
+            dto.copy_id == copy_id && dto.content_type == "image/png" && dto.storage_key.ends_with(".png")
+        })
+        .times(1)
+        .returning(|dto| Ok(create_photo_record(dto.copy_id, dto.user_id, &dto.storage_key)));
+
+    let mut storage = MockPhotoStorage::new();
+    storage.expect_save().times(1).returning(|_, _| Ok(()));
+
+    let service = create_service(offered_repo_with_copy(), photo_repo, storage);
+
+    let result = service.add_photo(&copy_id, &user_id, "image/png", png_content()).await;
+
+    let photo = result.expect("A foto deveria ser adicionada");
+    assert_eq!(photo.content_type, "image/png");
+    assert_eq!(photo.url, format!("/api/v1/photos/{}", photo.id));
+}
+
+#[tokio::test]
+async fn test_add_photo_rejects_invalid_content() {
+    let copy_id = Uuid::new_v4();
+    let user_id = OWNER_ID;
+
+    // Nenhum repositório deve ser consultado quando a foto é inválida
+    let service = create_service(
+        MockBooksOfferedRepository::new(),
+        MockBookPhotoRepository::new(),
+        MockPhotoStorage::new(),
+    );
+
+    let empty = service.add_photo(&copy_id, &user_id, "image/png", vec![]).await;
+    expect_validation_error(empty, "vazia");
+
+    let too_large = service
+        .add_photo(&copy_id, &user_id, "image/png", vec![0x89; MAX_BYTES + 1])
+        .await;
+    expect_validation_error(too_large, "no máximo");
+
+    let unsupported = service.add_photo(&copy_id, &user_id, "image/gif", png_content()).await;
+    expect_validation_error(unsupported, "não suportado");
+
+    let mismatched = service.add_photo(&copy_id, &user_id, "image/jpeg", png_content()).await;
+    expect_validation_error(mismatched, "não corresponde");
+}
+
+#[tokio::test]
+async fn test_add_photo_when_copy_not_offered() {
+    let copy_id = Uuid::new_v4();
+    let user_id = OWNER_ID;
+
+    let mut books_offered_repo = MockBooksOfferedRepository::new();
+    books_offered_repo.expect_find_by_id().times(1).returning(|_| Ok(None));
+
+    let service = create_service(books_offered_repo, MockBookPhotoRepository::new(), MockPhotoStorage::new());
+
+    let result = service.add_photo(&copy_id, &user_id, "image/png", png_content()).await;
+
+    expect_rejected(result, ErrorCode::CopyNotOffered, "Este exemplar não está na sua lista de possuídos");
+}
+
+#[tokio::test]
+async fn test_add_photo_when_limit_reached() {
+    let copy_id = Uuid::new_v4();
+    let user_id = OWNER_ID;
+
+    let mut photo_repo = MockBookPhotoRepository::new();
+    photo_repo.expect_find_by_copy_id().times(1).returning(move |_| {
+        Ok((0..MAX_PHOTOS_PER_COPY)
+            .map(|i| create_photo_record(copy_id, user_id, &format!("{}.png", i)))
+            .collect())
+    });
+
+    let service = create_service(offered_repo_with_copy(), photo_repo, MockPhotoStorage::new());
+
+    let result = service.add_photo(&copy_id, &user_id, "image/png", png_content()).await;
+
+    expect_rejected(result, ErrorCode::PhotoLimitReached, "no máximo 5 fotos");
+}
+
+#[tokio::test]
+async fn test_add_photo_removes_file_when_insert_fails() {
+    let copy_id = Uuid::new_v4();
+    let user_id = OWNER_ID;
+
+    let mut photo_repo = MockBookPhotoRepository::new();
+    photo_repo.expect_find_by_copy_id().returning(|_| Ok(vec![]));
+    photo_repo
+        .expect_create()
+        .times(1)
+        .returning(|_| Err(AppError::DatabaseError("Erro simulado".to_string())));
+
+    let mut storage = MockPhotoStorage::new();
+    storage.expect_save().times(1).returning(|_, _| Ok(()));
+    storage
+        .expect_delete()
+        .withf(|key: &str| key.ends_with(".png"))
+        .times(1)
+        .returning(|_| Ok(()));
+
+    let service = create_service(offered_repo_with_copy(), photo_repo, storage);
+
+    let result = service.add_photo(&copy_id, &user_id, "image/png", png_content()).await;
+
+    assert!(matches!(result, Err(AppError::DatabaseError(_))));
+}
+
+#[tokio::test]
+async fn test_delete_stored_continues_after_failure() {
+    let mut storage = MockPhotoStorage::new();
+    storage
+        .expect_delete()
+        .with(eq("a.png"))
+        .times(1)
+        .returning(|_| Err(AppError::InternalServerError("Erro simulado".to_string())));
+    storage.expect_delete().with(eq("b.png")).times(1).returning(|_| Ok(()));
+
+    let service = create_service(MockBooksOfferedRepository::new(), MockBookPhotoRepository::new(), storage);
+
+    // Os registros já foram removidos, então a falha de um arquivo não interrompe os demais
+    service.delete_stored(&["a.png".to_string(), "b.png".to_string()]).await;
+}
+
+#[tokio::test]
+async fn test_remove_photo_of_another_user() {
+    let photo_id = Uuid::new_v4();
+    let owner_id = Uuid::new_v4();
+    let other_user_id = Uuid::new_v4();
+
+    let mut photo_repo = MockBookPhotoRepository::new();
+    photo_repo
+        .expect_find_by_id()
+        .with(eq(photo_id))
+        .times(1)
+        .returning(move |_| Ok(Some(create_photo_record(Uuid::new_v4(), owner_id, "foto.png"))));
+
+    // A foto não pode ser apagada por quem não é o dono
+    let service = create_service(MockBooksOfferedRepository::new(), photo_repo, MockPhotoStorage::new());
+
+    let result = service.remove_photo(&photo_id, &other_user_id).await;
+
+    assert!(matches!(result, Err(AppError::NotFoundError(_))));
+}
+
+#[tokio::test]
+async fn test_local_photo_storage_roundtrip() {
+    let root = std::env::temp_dir().join(format!("troca-livros-fotos-{}", Uuid::new_v4()));
+    let storage = LocalPhotoStorage::new(&root);
+
+    storage.save("foto.png", &png_content()).await.unwrap();
+    assert_eq!(storage.load("foto.png").await.unwrap(), png_content());
+
+    storage.delete("foto.png").await.unwrap();
+    assert!(matches!(storage.load("foto.png").await, Err(AppError::NotFoundError(_))));
+
+    // Remover uma chave inexistente não é erro
+    assert!(storage.delete("foto.png").await.is_ok());
+
+    // Chaves que escapam do diretório raiz são recusadas
+    assert!(matches!(
+        storage.save("../fora.png", &png_content()).await,
+        Err(AppError::ValidationError(_))
+    ));
+
+    let _ = tokio::fs::remove_dir_all(&root).await;
+}
+
+#[tokio::test]
+async fn test_add_photo_to_copy_of_another_user() {
+    let copy_id = Uuid::new_v4();
+    let other_user_id = Uuid::new_v4();
+
+    // O exemplar existe, mas pertence a outro usuário
+    let service = create_service(offered_repo_with_copy(), MockBookPhotoRepository::new(), MockPhotoStorage::new());
+
+    let result = service.add_photo(&copy_id, &other_user_id, "image/png", png_content()).await;
+
+    expect_rejected(result, ErrorCode::CopyNotOffered, "Este exemplar não está na sua lista de possuídos");
+}
@@ -1,30 +1,55 @@
-use std::sync::Arc;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use async_trait::async_trait;
+use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use uuid::Uuid;
 
 use crate::error::AppError;
+use crate::i18n::messages;
+use crate::models::book::{BookDetails, BookOffer, BookPhoto, GoogleBookDto, OfferedCopy};
+use crate::repositories::book_photo_repository::BookPhotoRepository;
 use crate::repositories::book_repository::{BookRepository, BookWithId};
 use crate::repositories::books_offered_repository::BooksOfferedRepository;
 use crate::repositories::books_wanted_repository::BooksWantedRepository;
+use crate::services::google_book_service::GoogleBookService;
+
+/// Livro oferecido pelo usuário, com os detalhes de cada exemplar
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct OfferedBookWithId {
+    #[schema(value_type = String, format = "uuid")]
+    pub id: Uuid,
+    pub book: GoogleBookDto,
+    pub copies: Vec<OfferedCopy>,
+}
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct UserBooks {
-    pub offered_books: Vec<BookWithId>,
+    pub offered_books: Vec<OfferedBookWithId>,
     pub wanted_books: Vec<BookWithId>,
 }
 
 #[async_trait]
 pub trait BookService: Send + Sync + 'static {
     async fn get_user_books(&self, user_id: &Uuid) -> Result<UserBooks, AppError>;
+    async fn get_book_offers(&self, book_id: &Uuid) -> Result<Vec<BookOffer>, AppError>;
+    /// Livro cadastrado, sem consultar o Google Books; dados antigos são
+    /// atualizados em segundo plano
+    async fn get_book_details(&self, book_id: &Uuid, user_id: &Uuid) -> Result<BookDetails, AppError>;
 }
 
 pub struct BookServiceImpl {
     book_repository: Arc<dyn BookRepository>,
     books_offered_repository: Arc<dyn BooksOfferedRepository>,
     books_wanted_repository: Arc<dyn BooksWantedRepository>,
+    book_photo_repository: Arc<dyn BookPhotoRepository>,
+    google_book_service: Arc<dyn GoogleBookService>,
+    metadata_max_age: Duration,
+    /// Livros com atualização em andamento, para não repetir a consulta
+    refreshing: Arc<Mutex<HashSet<Uuid>>>,
 }
 
 impl BookServiceImpl {
@@ -32,45 +57,172 @@ impl BookServiceImpl {
         book_repository: Arc<dyn BookRepository>,
         books_offered_repository: Arc<dyn BooksOfferedRepository>,
         books_wanted_repository: Arc<dyn BooksWantedRepository>,
+        book_photo_repository: Arc<dyn BookPhotoRepository>,
+        google_book_service: Arc<dyn GoogleBookService>,
+        metadata_max_age: Duration,
     ) -> Self {
         Self {
             book_repository,
             books_offered_repository,
             books_wanted_repository,
+            book_photo_repository,
+            google_book_service,
+            metadata_max_age,
+            refreshing: Arc::new(Mutex::new(HashSet::new())),
+        }
+    }
+
+    fn is_stale(&self, details: &BookDetails) -> bool {
+        // Uma tentativa que falhou também adia a próxima consulta
+        let Some(checked_at) = details.metadata_fetched_at.max(details.metadata_checked_at) else {
+            return true;
+        };
+        let age = Utc::now().naive_utc() - checked_at;
+
+        age.to_std().is_ok_and(|age| age >= self.metadata_max_age)
+    }
+
+    /// Busca os dados do livro no Google Books sem esperar a resposta
+    fn spawn_refresh(&self, book_id: Uuid, google_id: String) {
+        if !self.refreshing.lock().unwrap_or_else(|e| e.into_inner()).insert(book_id) {
+            return;
         }
+
+        let book_repository = self.book_repository.clone();
+        let google_book_service = self.google_book_service.clone();
+        let guard = RefreshGuard {
+            refreshing: self.refreshing.clone(),
+            book_id,
+        };
+
+        tokio::spawn(async move {
+            let _guard = guard;
+
+            let result = match google_book_service.find_book_by_id(&google_id).await {
+                Ok(book) => book_repository.update_metadata(&book_id, &book).await,
+                Err(e) => {
+                    tracing::warn!("Falha ao consultar os dados do livro {}: {}", book_id, e);
+                    book_repository.mark_metadata_checked(&book_id).await
+                }
+            };
+            if let Err(e) = result {
+                tracing::warn!("Falha ao atualizar os dados do livro {}: {}", book_id, e);
+            }
+        });
+    }
+}
+
+// Libera o livro para uma nova atualização quando a tarefa termina, mesmo em pânico
+struct RefreshGuard {
+    refreshing: Arc<Mutex<HashSet<Uuid>>>,
+    book_id: Uuid,
+}
+
+impl Drop for RefreshGuard {
+    fn drop(&mut self) {
+        self.refreshing.lock().unwrap_or_else(|e| e.into_inner()).remove(&self.book_id);
     }
 }
 
 #[async_trait]
 impl BookService for BookServiceImpl {
     async fn get_user_books(&self, user_id: &Uuid) -> Result<UserBooks, AppError> {
-        // Obter IDs de livros possuídos pelo usuário
-        let offered_book_ids = self.books_offered_repository.find_by_user_id(user_id).await?;
-        
+        // Obter os exemplares possuídos pelo usuário
+        let offered_copies = self.books_offered_repository.find_all_by_user_id(user_id).await?;
+
         // Obter IDs de livros desejados pelo usuário
         let wanted_book_ids = self.books_wanted_repository.find_by_user_id(user_id).await?;
-        
+
         // Converter IDs para strings para usar no find_by_ids
-        let offered_ids: Vec<String> = offered_book_ids.iter().map(|id| id.to_string()).collect();
+        let mut offered_ids: Vec<String> = offered_copies.iter().map(|c| c.book_id.to_string()).collect();
+        offered_ids.sort();
+        offered_ids.dedup();
         let wanted_ids: Vec<String> = wanted_book_ids.iter().map(|id| id.to_string()).collect();
-        
+
         // Buscar detalhes dos livros possuídos
-        let offered_books = if !offered_ids.is_empty() {
+        let offered_details = if !offered_ids.is_empty() {
             self.book_repository.find_by_ids(&offered_ids).await?
         } else {
             Vec::new()
         };
-        
+
         // Buscar detalhes dos livros desejados
         let wanted_books = if !wanted_ids.is_empty() {
             self.book_repository.find_by_ids(&wanted_ids).await?
         } else {
             Vec::new()
         };
-        
+
+        // Agrupar as fotos do usuário por exemplar
+        let mut photos: HashMap<Uuid, Vec<BookPhoto>> = HashMap::new();
+        if !offered_copies.is_empty() {
+            for record in self.book_photo_repository.find_by_user_id(user_id).await? {
+                photos.entry(record.copy_id).or_default().push(BookPhoto::from(record));
+            }
+        }
+
+        // Agrupar os exemplares por livro, mantendo a ordem de cadastro
+        let mut copies: HashMap<Uuid, Vec<OfferedCopy>> = HashMap::new();
+        for offered in offered_copies {
+            let copy_photos = photos.remove(&offered.id).unwrap_or_default();
+            copies
+                .entry(offered.book_id)
+                .or_default()
+                .push(OfferedCopy::from_offered(offered, copy_photos));
+        }
+
+        // Combinar cada livro possuído com os seus exemplares
+        let offered_books = offered_details
+            .into_iter()
+            .filter_map(|book| {
+                let copies = copies.remove(&book.id)?;
+                Some(OfferedBookWithId {
+                    id: book.id,
+                    book: book.book,
+                    copies,
+                })
+            })
+            .collect();
+
         Ok(UserBooks {
             offered_books,
             wanted_books,
         })
     }
-} 
\ No newline at end of file
+
+    async fn get_book_offers(&self, book_id: &Uuid) -> Result<Vec<BookOffer>, AppError> {
+        let owners = self.books_offered_repository.find_owners_by_book_id(book_id).await?;
+
+        let copy_ids: Vec<Uuid> = owners.iter().map(|(_, offered)| offered.id).collect();
+        let mut photos: HashMap<Uuid, Vec<BookPhoto>> = HashMap::new();
+        for record in self.book_photo_repository.find_by_copy_ids(&copy_ids).await? {
+            photos.entry(record.copy_id).or_default().push(BookPhoto::from(record));
+        }
+
+        Ok(owners
+            .into_iter()
+            .map(|(owner, offered)| {
+                let copy_photos = photos.remove(&offered.id).unwrap_or_default();
+                BookOffer {
+                    owner,
+                    copy: OfferedCopy::from_offered(offered, copy_photos),
+                }
+            })
+            .collect())
+    }
+
+    async fn get_book_details(&self, book_id: &Uuid, user_id: &Uuid) -> Result<BookDetails, AppError> {
+        let details = self
+            .book_repository
+            .find_details(book_id, user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFoundError(messages::BOOK_NOT_FOUND.text()))?;
+
+        // Livros sem google_id não têm de onde ser atualizados
+        if !details.book.google_id.is_empty() && self.is_stale(&details) {
+            self.spawn_refresh(details.id, details.book.google_id.clone());
+        }
+
+        Ok(details)
+    }
+}
@@ -0,0 +1,250 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::models::book::{BookDetails, GoogleBookDto};
+use crate::models::shelf::ShelfList;
+use crate::services::book_service::{BookService, BookServiceImpl};
+use crate::services::test_mocks::{
+    create_test_book_with_id, MockBookPhotoRepository, MockBookRepository, MockBooksOfferedRepository,
+    MockBooksWantedRepository, MockGoogleBookService,
+};
+
+const MAX_AGE: Duration = Duration::from_secs(60 * 60);
+
+fn create_details(book_id: Uuid, fetched_seconds_ago: Option<i64>) -> BookDetails {
+    BookDetails {
+        id: book_id,
+        book: create_test_book_with_id(book_id, "google_id_1").book,
+        offered_count: 3,
+        wanted_count: 2,
+        user_list: Some(ShelfList::Wanted),
+        metadata_fetched_at: fetched_seconds_ago
+            .map(|seconds| Utc::now().naive_utc() - chrono::Duration::seconds(seconds)),
+        metadata_checked_at: None,
+    }
+}
+
+fn create_service(book_repo: MockBookRepository, google_book_service: MockGoogleBookService) -> BookServiceImpl {
+    BookServiceImpl::new(
+        Arc::new(book_repo),
+        Arc::new(MockBooksOfferedRepository::new()),
+        Arc::new(MockBooksWantedRepository::new()),
+        Arc::new(MockBookPhotoRepository::new()),
+        Arc::new(google_book_service),
+        MAX_AGE,
+    )
+}
+
+#[tokio::test]
+async fn test_get_book_details_not_found() {
+    // Arrange
+    let mut mock_book_repo = MockBookRepository::new();
+    mock_book_repo.expect_find_details().times(1).returning(|_, _| Ok(None));
+    mock_book_repo.expect_update_metadata().times(0);
+    let service = create_service(mock_book_repo, MockGoogleBookService::new());
+
+    // Act
+    let result = service.get_book_details(&Uuid::new_v4(), &Uuid::new_v4()).await;
+
+    // Assert
+    assert!(matches!(result, Err(AppError::NotFoundError(_))));
+}
+
+#[tokio::test]
+async fn test_get_book_details_fresh_metadata_is_not_refreshed() {
+    // Arrange
+    let book_id = Uuid::new_v4();
+    let user_id = Uuid::new_v4();
+    let mut mock_book_repo = MockBookRepository::new();
+    mock_book_repo
+        .expect_find_details()
+        .with(mockall::predicate::eq(book_id), mockall::predicate::eq(user_id))
+        .times(1)
+        .returning(move |_, _| Ok(Some(create_details(book_id, Some(60)))));
+    mock_book_repo.expect_update_metadata().times(0);
+    let google_book_service = MockGoogleBookService::new()
+        .with_find_book_by_id(|_| panic!("dados recentes não devem ser consultados"));
+    let service = create_service(mock_book_repo, google_book_service);
+
+    // Act
+    let details = service.get_book_details(&book_id, &user_id).await.unwrap();
+    tokio::task::yield_now().await;
+
+    // Assert
+    assert_eq!(details.id, book_id);
+    assert_eq!(details.offered_count, 3);
+    assert_eq!(details.wanted_count, 2);
+    assert_eq!(details.user_list, Some(ShelfList::Wanted));
+}
+
+#[tokio::test]
+async fn test_get_book_details_stale_metadata_is_refreshed_once() {
+    // Arrange
+    let book_id = Uuid::new_v4();
+    let (sender, mut receiver) = mpsc::unbounded_channel();
+    let mut mock_book_repo = MockBookRepository::new();
+    mock_book_repo
+        .expect_find_details()
+        .times(2)
+        .returning(move |_, _| Ok(Some(create_details(book_id, Some(2 * 60 * 60)))));
+    mock_book_repo
+        .expect_update_metadata()
+        .with(mockall::predicate::eq(book_id), mockall::predicate::always())
+        .times(1)
+        .returning(move |_, book: &GoogleBookDto| {
+            sender.send(book.title.clone()).unwrap();
+            Ok(())
+        });
+    let google_book_service = MockGoogleBookService::new().with_find_book_by_id(|google_id| {
+        assert_eq!(google_id, "google_id_1");
+        let mut book = create_test_book_with_id(Uuid::new_v4(), google_id).book;
+        book.title = String::from("Título Atualizado");
+        Ok(book)
+    });
+    let service = create_service(mock_book_repo, google_book_service);
+
+    // Act - A segunda consulta chega com a atualização ainda pendente
+    let first = service.get_book_details(&book_id, &Uuid::new_v4()).await.unwrap();
+    let second = service.get_book_details(&book_id, &Uuid::new_v4()).await.unwrap();
+
+    // Assert - As respostas não esperam o Google Books
+    assert_eq!(first.book.title, "Livro de Teste");
+    assert_eq!(second.book.title, "Livro de Teste");
+
+    let title = tokio::time::timeout(Duration::from_secs(5), receiver.recv())
+        .await
+        .expect("a atualização não foi executada");
+    assert_eq!(title.as_deref(), Some("Título Atualizado"));
+}
+
+#[tokio::test]
+async fn test_get_book_details_never_fetched_is_refreshed() {
+    // Arrange
+    let book_id = Uuid::new_v4();
+    let (sender, mut receiver) = mpsc::unbounded_channel();
+    let mut mock_book_repo = MockBookRepository::new();
+    mock_book_repo
+        .expect_find_details()
+        .times(1)
+        .returning(move |_, _| Ok(Some(create_details(book_id, None))));
+    mock_book_repo.expect_update_metadata().times(1).returning(move |id, _| {
+        sender.send(*id).unwrap();
+        Ok(())
+    });
+    let google_book_service = MockGoogleBookService::new()
+        .with_find_book_by_id(|google_id| Ok(create_test_book_with_id(Uuid::new_v4(), google_id).book));
+    let service = create_service(mock_book_repo, google_book_service);
+
+    // Act
+    let details = service.get_book_details(&book_id, &Uuid::new_v4()).await.unwrap();
+
+    // Assert
+    assert!(details.metadata_fetched_at.is_none());
+    let refreshed = tokio::time::timeout(Duration::from_secs(5), receiver.recv())
+        .await
+        .expect("a atualização não foi executada");
+    assert_eq!(refreshed, Some(book_id));
+}
+
+#[tokio::test]
+async fn test_get_book_details_failed_refresh_is_recorded() {
+    // Arrange
+    let book_id = Uuid::new_v4();
+    let (sender, mut receiver) = mpsc::unbounded_channel();
+    let mut mock_book_repo = MockBookRepository::new();
+    mock_book_repo
+        .expect_find_details()
+        .times(1)
+        .returning(move |_, _| Ok(Some(create_details(book_id, Some(2 * 60 * 60)))));
+    mock_book_repo.expect_update_metadata().times(0);
+    mock_book_repo
+        .expect_mark_metadata_checked()
+        .with(mockall::predicate::eq(book_id))
+        .times(1)
+        .returning(move |id| {
+            sender.send(*id).unwrap();
+            Ok(())
+        });
+    let google_book_service = MockGoogleBookService::new()
+        .with_find_book_by_id(|_| Err(AppError::BadGateway("Google Books indisponível".to_string())));
+    let service = create_service(mock_book_repo, google_book_service);
+
+    // Act
+    service.get_book_details(&book_id, &Uuid::new_v4()).await.unwrap();
+
+    // Assert - A tentativa fica registrada para adiar a próxima
+    let checked = tokio::time::timeout(Duration::from_secs(5), receiver.recv())
+        .await
+        .expect("a tentativa não foi registrada");
+    assert_eq!(checked, Some(book_id));
+}
+
+#[tokio::test]
+async fn test_get_book_details_recently_checked_is_not_refreshed() {
+    // Arrange
+    let book_id = Uuid::new_v4();
+    let mut mock_book_repo = MockBookRepository::new();
+    mock_book_repo.expect_find_details().times(1).returning(move |_, _| {
+        let mut details = create_details(book_id, Some(2 * 60 * 60));
+        details.metadata_checked_at = Some(Utc::now().naive_utc() - chrono::Duration::seconds(60));
+        Ok(Some(details))
+    });
+    mock_book_repo.expect_update_metadata().times(0);
+    mock_book_repo.expect_mark_metadata_checked().times(0);
+    let google_book_service = MockGoogleBookService::new()
+        .with_find_book_by_id(|_| panic!("uma tentativa recente adia a próxima consulta"));
+    let service = create_service(mock_book_repo, google_book_service);
+
+    // Act
+    service.get_book_details(&book_id, &Uuid::new_v4()).await.unwrap();
+    tokio::task::yield_now().await;
+}
+
+#[tokio::test]
+async fn test_get_book_details_refresh_is_retried_after_panic() {
+    // Arrange
+    let book_id = Uuid::new_v4();
+    let (sender, mut receiver) = mpsc::unbounded_channel();
+    let calls = Arc::new(AtomicUsize::new(0));
+    let mut mock_book_repo = MockBookRepository::new();
+    mock_book_repo
+        .expect_find_details()
+        .returning(move |_, _| Ok(Some(create_details(book_id, None))));
+    mock_book_repo.expect_update_metadata().returning(move |id, _| {
+        sender.send(*id).unwrap();
+        Ok(())
+    });
+    let google_book_service = MockGoogleBookService::new().with_find_book_by_id({
+        let calls = calls.clone();
+        move |google_id| {
+            if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                panic!("falha inesperada na primeira atualização");
+            }
+            Ok(create_test_book_with_id(Uuid::new_v4(), google_id).book)
+        }
+    });
+    let service = create_service(mock_book_repo, google_book_service);
+
+    // Act - Consulta até a atualização seguinte ao pânico ser executada
+    let refreshed = tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            service.get_book_details(&book_id, &Uuid::new_v4()).await.unwrap();
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            if let Ok(id) = receiver.try_recv() {
+                return id;
+            }
+        }
+    })
+    .await
+    .expect("o livro continuou marcado como em atualização");
+
+    // Assert
+    assert_eq!(refreshed, book_id);
+    assert!(calls.load(Ordering::SeqCst) >= 2);
+}
@@ -1,12 +1,24 @@
 use std::sync::Arc;
+use std::time::Duration;
 use uuid::Uuid;
 
 use crate::error::AppError;
+use crate::models::book::{BookOffered, OfferedCopyAttributes};
 use crate::services::book_service::{BookService, BookServiceImpl};
 use crate::services::test_mocks::{
-    create_test_book_with_id, MockBookRepository, MockBooksOfferedRepository, MockBooksWantedRepository,
+    create_test_book_with_id, MockBookPhotoRepository, MockBookRepository, MockBooksOfferedRepository,
+    MockBooksWantedRepository, MockGoogleBookService,
 };
 
+fn create_offered_copy(book_id: Uuid, user_id: Uuid) -> BookOffered {
+    BookOffered {
+        id: Uuid::new_v4(),
+        book_id,
+        user_id,
+        attributes: OfferedCopyAttributes::default(),
+    }
+}
+
 #[tokio::test]
 async fn test_get_user_books_success() {
     // Arrange - Configurar os mocks e dados de teste
@@ -21,10 +33,15 @@ async fn test_get_user_books_success() {
     // Configurar o mock do BooksOfferedRepository
     let mut mock_books_offered_repo = MockBooksOfferedRepository::new();
     mock_books_offered_repo
-        .expect_find_by_user_id()
+        .expect_find_all_by_user_id()
         .with(mockall::predicate::eq(user_id))
         .times(1)
-        .returning(move |_| Ok(vec![offered_book_id1, offered_book_id2]));
+        .returning(move |_| {
+            Ok(vec![
+                create_offered_copy(offered_book_id1, user_id),
+                create_offered_copy(offered_book_id2, user_id),
+            ])
+        });
     
     // Configurar o mock do BooksWantedRepository
     let mut mock_books_wanted_repo = MockBooksWantedRepository::new();
@@ -65,11 +82,22 @@ async fn test_get_user_books_success() {
         .times(1)
         .returning(move |_| Ok(wanted_books.clone()));
     
+    // Configurar o mock do BookPhotoRepository para o usuário sem fotos
+    let mut mock_book_photo_repo = MockBookPhotoRepository::new();
+    mock_book_photo_repo
+        .expect_find_by_user_id()
+        .with(mockall::predicate::eq(user_id))
+        .times(1)
+        .returning(|_| Ok(vec![]));
+
     // Criar o serviço com os mocks
     let book_service = BookServiceImpl::new(
         Arc::new(mock_book_repo),
         Arc::new(mock_books_offered_repo),
         Arc::new(mock_books_wanted_repo),
+        Arc::new(mock_book_photo_repo),
+        Arc::new(MockGoogleBookService::new()),
+        Duration::from_secs(60),
     );
     
     // Act - Chamar a função a ser testada
@@ -111,7 +139,7 @@ async fn test_get_user_books_empty() {
     // Configurar o mock do BooksOfferedRepository para retornar lista vazia
     let mut mock_books_offered_repo = MockBooksOfferedRepository::new();
     mock_books_offered_repo
-        .expect_find_by_user_id()
+        .expect_find_all_by_user_id()
         .with(mockall::predicate::eq(user_id))
         .times(1)
         .returning(|_| Ok(vec![]));
@@ -129,11 +157,16 @@ async fn test_get_user_books_empty() {
     // Não precisamos configurar expectativas para find_by_ids porque
     // não deve ser chamado quando as listas estão vazias
     
+    let mock_book_photo_repo = MockBookPhotoRepository::new();
+
     // Criar o serviço com os mocks
     let book_service = BookServiceImpl::new(
         Arc::new(mock_book_repo),
         Arc::new(mock_books_offered_repo),
         Arc::new(mock_books_wanted_repo),
+        Arc::new(mock_book_photo_repo),
+        Arc::new(MockGoogleBookService::new()),
+        Duration::from_secs(60),
     );
     
     // Act - Chamar a função a ser testada
@@ -158,10 +191,10 @@ async fn test_get_user_books_only_offered() {
     // Configurar o mock do BooksOfferedRepository
     let mut mock_books_offered_repo = MockBooksOfferedRepository::new();
     mock_books_offered_repo
-        .expect_find_by_user_id()
+        .expect_find_all_by_user_id()
         .with(mockall::predicate::eq(user_id))
         .times(1)
-        .returning(move |_| Ok(vec![offered_book_id]));
+        .returning(move |_| Ok(vec![create_offered_copy(offered_book_id, user_id)]));
     
     // Configurar o mock do BooksWantedRepository para retornar lista vazia
     let mut mock_books_wanted_repo = MockBooksWantedRepository::new();
@@ -186,11 +219,22 @@ async fn test_get_user_books_only_offered() {
         .times(1)
         .returning(move |_| Ok(vec![offered_book.clone()]));
     
+    // Configurar o mock do BookPhotoRepository para o usuário sem fotos
+    let mut mock_book_photo_repo = MockBookPhotoRepository::new();
+    mock_book_photo_repo
+        .expect_find_by_user_id()
+        .with(mockall::predicate::eq(user_id))
+        .times(1)
+        .returning(|_| Ok(vec![]));
+
     // Criar o serviço com os mocks
     let book_service = BookServiceImpl::new(
         Arc::new(mock_book_repo),
         Arc::new(mock_books_offered_repo),
         Arc::new(mock_books_wanted_repo),
+        Arc::new(mock_book_photo_repo),
+        Arc::new(MockGoogleBookService::new()),
+        Duration::from_secs(60),
     );
     
     // Act - Chamar a função a ser testada
@@ -216,7 +260,7 @@ async fn test_get_user_books_only_wanted() {
     // Configurar o mock do BooksOfferedRepository para retornar lista vazia
     let mut mock_books_offered_repo = MockBooksOfferedRepository::new();
     mock_books_offered_repo
-        .expect_find_by_user_id()
+        .expect_find_all_by_user_id()
         .with(mockall::predicate::eq(user_id))
         .times(1)
         .returning(|_| Ok(vec![]));
@@ -244,11 +288,16 @@ async fn test_get_user_books_only_wanted() {
         .times(1)
         .returning(move |_| Ok(vec![wanted_book.clone()]));
     
+    let mock_book_photo_repo = MockBookPhotoRepository::new();
+
     // Criar o serviço com os mocks
     let book_service = BookServiceImpl::new(
         Arc::new(mock_book_repo),
         Arc::new(mock_books_offered_repo),
         Arc::new(mock_books_wanted_repo),
+        Arc::new(mock_book_photo_repo),
+        Arc::new(MockGoogleBookService::new()),
+        Duration::from_secs(60),
     );
     
     // Act - Chamar a função a ser testada
@@ -273,7 +322,7 @@ async fn test_get_user_books_error_in_offered_repository() {
     // Configurar o mock do BooksOfferedRepository para retornar erro
     let mut mock_books_offered_repo = MockBooksOfferedRepository::new();
     mock_books_offered_repo
-        .expect_find_by_user_id()
+        .expect_find_all_by_user_id()
         .with(mockall::predicate::eq(user_id))
         .times(1)
         .returning(|_| Err(AppError::DatabaseError("Erro de banco de dados simulado".to_string())));
@@ -282,11 +331,16 @@ async fn test_get_user_books_error_in_offered_repository() {
     let mock_books_wanted_repo = MockBooksWantedRepository::new();
     let mock_book_repo = MockBookRepository::new();
     
+    let mock_book_photo_repo = MockBookPhotoRepository::new();
+
     // Criar o serviço com os mocks
     let book_service = BookServiceImpl::new(
         Arc::new(mock_book_repo),
         Arc::new(mock_books_offered_repo),
         Arc::new(mock_books_wanted_repo),
+        Arc::new(mock_book_photo_repo),
+        Arc::new(MockGoogleBookService::new()),
+        Duration::from_secs(60),
     );
     
     // Act - Chamar a função a ser testada
@@ -310,7 +364,7 @@ async fn test_get_user_books_error_in_wanted_repository() {
     // Configurar o mock do BooksOfferedRepository
     let mut mock_books_offered_repo = MockBooksOfferedRepository::new();
     mock_books_offered_repo
-        .expect_find_by_user_id()
+        .expect_find_all_by_user_id()
         .with(mockall::predicate::eq(user_id))
         .times(1)
         .returning(|_| Ok(vec![]));
@@ -326,11 +380,16 @@ async fn test_get_user_books_error_in_wanted_repository() {
     // Configurar mock que não deve ser chamado
     let mock_book_repo = MockBookRepository::new();
     
+    let mock_book_photo_repo = MockBookPhotoRepository::new();
+
     // Criar o serviço com os mocks
     let book_service = BookServiceImpl::new(
         Arc::new(mock_book_repo),
         Arc::new(mock_books_offered_repo),
         Arc::new(mock_books_wanted_repo),
+        Arc::new(mock_book_photo_repo),
+        Arc::new(MockGoogleBookService::new()),
+        Duration::from_secs(60),
     );
     
     // Act - Chamar a função a ser testada
@@ -355,10 +414,10 @@ async fn test_get_user_books_error_in_book_repository() {
     // Configurar o mock do BooksOfferedRepository
     let mut mock_books_offered_repo = MockBooksOfferedRepository::new();
     mock_books_offered_repo
-        .expect_find_by_user_id()
+        .expect_find_all_by_user_id()
         .with(mockall::predicate::eq(user_id))
         .times(1)
-        .returning(move |_| Ok(vec![book_id]));
+        .returning(move |_| Ok(vec![create_offered_copy(book_id, user_id)]));
     
     // Configurar o mock do BooksWantedRepository
     let mut mock_books_wanted_repo = MockBooksWantedRepository::new();
@@ -375,11 +434,16 @@ async fn test_get_user_books_error_in_book_repository() {
         .times(1)
         .returning(|_| Err(AppError::DatabaseError("Erro de banco de dados simulado".to_string())));
     
+    let mock_book_photo_repo = MockBookPhotoRepository::new();
+
     // Criar o serviço com os mocks
     let book_service = BookServiceImpl::new(
         Arc::new(mock_book_repo),
         Arc::new(mock_books_offered_repo),
         Arc::new(mock_books_wanted_repo),
+        Arc::new(mock_book_photo_repo),
+        Arc::new(MockGoogleBookService::new()),
+        Duration::from_secs(60),
     );
     
     // Act - Chamar a função a ser testada
@@ -1 +1,2 @@
+pub mod get_book_details_test;
 pub mod get_user_books_test;
\ No newline at end of file
@@ -1,19 +1,50 @@
+use std::collections::HashSet;
 use std::sync::Arc;
 
 use async_trait::async_trait;
 use uuid::Uuid;
 
-use crate::error::AppError;
-use crate::models::book::{BookWanted, CreateBookWantedDto};
+use crate::error::{AppError, ErrorCode};
+use crate::i18n::messages;
+use crate::models::book::{
+    validate_wanted_priority, BatchItemResult, BatchItemStatus, BookOffered, BookWanted, CreateBookOfferedDto,
+    CreateBookWantedDto, OfferedCopyAttributes, WantedBookItem,
+};
 use crate::repositories::book_repository::BookRepository;
 use crate::repositories::books_wanted_repository::BooksWantedRepository;
 use crate::repositories::books_offered_repository::BooksOfferedRepository;
+use crate::services::book_batch::{resolve_google_ids, validate_batch_size};
 use crate::services::google_book_service::GoogleBookService;
 
 #[async_trait]
 pub trait BookWantedService: Send + Sync + 'static {
-    async fn add_book_to_wanted(&self, google_id: &str, user_id: &Uuid) -> Result<BookWanted, AppError>;
+    async fn add_book_to_wanted(
+        &self,
+        google_id: &str,
+        user_id: &Uuid,
+        priority: i16,
+    ) -> Result<BookWanted, AppError>;
+    async fn update_priority(&self, book_id: &Uuid, user_id: &Uuid, priority: i16) -> Result<BookWanted, AppError>;
     async fn remove_book_from_wanted(&self, book_id: &Uuid, user_id: &Uuid) -> Result<bool, AppError>;
+    /// Move um livro desejado que o usuário conseguiu para a lista de possuídos
+    async fn mark_as_acquired(
+        &self,
+        book_id: &Uuid,
+        user_id: &Uuid,
+        attributes: OfferedCopyAttributes,
+    ) -> Result<BookOffered, AppError>;
+    /// Adiciona vários livros de uma vez; o resultado de cada item segue a ordem da requisição
+    async fn add_books_to_wanted(
+        &self,
+        user_id: &Uuid,
+        items: Vec<WantedBookItem>,
+    ) -> Result<Vec<BatchItemResult>, AppError>;
+    /// Remove vários livros de uma vez; o resultado de cada item segue a ordem da requisição
+    async fn remove_books_from_wanted(
+        &self,
+        user_id: &Uuid,
+        book_ids: Vec<Uuid>,
+    ) -> Result<Vec<BatchItemResult>, AppError>;
 }
 
 pub struct BookWantedServiceImpl {
@@ -41,7 +72,14 @@ impl BookWantedServiceImpl {
 
 #[async_trait]
 impl BookWantedService for BookWantedServiceImpl {
-    async fn add_book_to_wanted(&self, google_id: &str, user_id: &Uuid) -> Result<BookWanted, AppError> {
+    async fn add_book_to_wanted(
+        &self,
+        google_id: &str,
+        user_id: &Uuid,
+        priority: i16,
+    ) -> Result<BookWanted, AppError> {
+        validate_wanted_priority(priority)?;
+
         // Variável para armazenar o UUID do banco de dados
         let book_uuid: Uuid;
         
@@ -61,19 +99,26 @@ impl BookWantedService for BookWantedServiceImpl {
         }
         
         // Verificar se o livro já está na lista de desejados do usuário
-        if let Some(_) = self.books_wanted_repository.find(&book_uuid, user_id).await? {
-            return Err(AppError::ValidationError("Este livro já está na sua lista de desejados".to_string()));
+        if self.books_wanted_repository.find(&book_uuid, user_id).await?.is_some() {
+            return Err(AppError::Conflict(
+                ErrorCode::BookAlreadyWanted,
+                messages::BOOK_ALREADY_WANTED.text(),
+            ));
         }
         
         // Verificar se o livro já está na lista de possuídos do usuário
-        if let Some(_) = self.books_offered_repository.find(&book_uuid, user_id).await? {
-            return Err(AppError::ValidationError("Este livro já está na sua lista de possuídos".to_string()));
+        if self.books_offered_repository.find(&book_uuid, user_id).await?.is_some() {
+            return Err(AppError::Conflict(
+                ErrorCode::BookAlreadyOffered,
+                messages::BOOK_ALREADY_OFFERED.text(),
+            ));
         }
         
         // Criar DTO para adicionar à lista de desejados
         let create_dto = CreateBookWantedDto {
             book_id: book_uuid,
             user_id: *user_id,
+            priority,
         };
 
         // Adicionar à lista de livros desejados
@@ -82,16 +127,166 @@ impl BookWantedService for BookWantedServiceImpl {
         Ok(book_wanted)
     }
 
+    async fn update_priority(&self, book_id: &Uuid, user_id: &Uuid, priority: i16) -> Result<BookWanted, AppError> {
+        validate_wanted_priority(priority)?;
+
+        self.books_wanted_repository
+            .update_priority(book_id, user_id, priority)
+            .await?
+            .ok_or_else(|| {
+                AppError::Rejected(ErrorCode::BookNotWanted, messages::BOOK_NOT_WANTED.text())
+            })
+    }
+
     async fn remove_book_from_wanted(&self, book_id: &Uuid, user_id: &Uuid) -> Result<bool, AppError> {
         // Verificar se o livro existe na lista de desejados do usuário
         let exists = self.books_wanted_repository.find(book_id, user_id).await?;
         if exists.is_none() {
-            return Err(AppError::ValidationError(
-                "Este livro não está na sua lista de desejados".to_string(),
+            return Err(AppError::Rejected(
+                ErrorCode::BookNotWanted,
+                messages::BOOK_NOT_WANTED.text(),
             ));
         }
 
         // Remover da lista de livros desejados
         self.books_wanted_repository.delete(book_id, user_id).await
     }
-} 
\ No newline at end of file
+
+    async fn mark_as_acquired(
+        &self,
+        book_id: &Uuid,
+        user_id: &Uuid,
+        attributes: OfferedCopyAttributes,
+    ) -> Result<BookOffered, AppError> {
+        attributes.validate_all()?;
+
+        let create_dto = CreateBookOfferedDto {
+            book_id: *book_id,
+            user_id: *user_id,
+            attributes,
+        };
+
+        // A retirada dos desejados e a criação do exemplar acontecem na mesma transação
+        self.books_offered_repository
+            .create_from_wanted(&create_dto)
+            .await?
+            .ok_or_else(|| {
+                AppError::Rejected(ErrorCode::BookNotWanted, messages::BOOK_NOT_WANTED.text())
+            })
+    }
+
+    async fn add_books_to_wanted(
+        &self,
+        user_id: &Uuid,
+        items: Vec<WantedBookItem>,
+    ) -> Result<Vec<BatchItemResult>, AppError> {
+        validate_batch_size(items.len())?;
+
+        let mut results: Vec<Option<BatchItemResult>> = items
+            .iter()
+            .map(|item| {
+                validate_wanted_priority(item.priority)
+                    .err()
+                    .map(|e| BatchItemResult::failed(&item.google_id, None, e.to_string()))
+            })
+            .collect();
+
+        let google_ids: Vec<String> = items
+            .iter()
+            .zip(&results)
+            .filter(|(_, result)| result.is_none())
+            .map(|(item, _)| item.google_id.clone())
+            .collect();
+        let resolved = resolve_google_ids(&self.book_repository, &self.google_book_service, &google_ids).await?;
+
+        // Uma consulta por lista em vez de uma por item
+        let mut wanted: HashSet<Uuid> = self.books_wanted_repository.find_by_user_id(user_id).await?.into_iter().collect();
+        let offered: HashSet<Uuid> = self.books_offered_repository.find_by_user_id(user_id).await?.into_iter().collect();
+
+        let mut dtos = Vec::new();
+        for (index, item) in items.into_iter().enumerate() {
+            if results[index].is_some() {
+                continue;
+            }
+
+            let book_id = match resolved.get(&item.google_id) {
+                Some(Ok(book_id)) => *book_id,
+                Some(Err(message)) => {
+                    results[index] = Some(BatchItemResult::failed(&item.google_id, None, message.as_str()));
+                    continue;
+                }
+                None => {
+                    results[index] = Some(BatchItemResult::failed(&item.google_id, None, messages::BOOK_NOT_FOUND.text()));
+                    continue;
+                }
+            };
+
+            // Livros repetidos na requisição também caem aqui, pois já foram marcados
+            if !wanted.insert(book_id) {
+                results[index] = Some(BatchItemResult::failed(
+                    &item.google_id,
+                    Some(book_id),
+                    messages::BOOK_ALREADY_WANTED.text(),
+                ));
+                continue;
+            }
+
+            if offered.contains(&book_id) {
+                results[index] = Some(BatchItemResult::failed(
+                    &item.google_id,
+                    Some(book_id),
+                    messages::BOOK_ALREADY_OFFERED.text(),
+                ));
+                continue;
+            }
+
+            dtos.push(CreateBookWantedDto {
+                book_id,
+                user_id: *user_id,
+                priority: item.priority,
+            });
+            results[index] = Some(BatchItemResult::succeeded(item.google_id, BatchItemStatus::Added, book_id, vec![]));
+        }
+
+        // Todos os livros são adicionados na mesma transação
+        if !dtos.is_empty() {
+            self.books_wanted_repository.create_many(&dtos).await?;
+        }
+
+        Ok(results.into_iter().flatten().collect())
+    }
+
+    async fn remove_books_from_wanted(
+        &self,
+        user_id: &Uuid,
+        book_ids: Vec<Uuid>,
+    ) -> Result<Vec<BatchItemResult>, AppError> {
+        validate_batch_size(book_ids.len())?;
+
+        let mut wanted: HashSet<Uuid> = self.books_wanted_repository.find_by_user_id(user_id).await?.into_iter().collect();
+
+        let mut removable = Vec::new();
+        let results = book_ids
+            .into_iter()
+            .map(|book_id| {
+                // Cada livro só pode ser removido uma vez, mesmo se repetido na requisição
+                if wanted.remove(&book_id) {
+                    removable.push(book_id);
+                    BatchItemResult::succeeded(book_id.to_string(), BatchItemStatus::Removed, book_id, vec![])
+                } else {
+                    BatchItemResult::failed(
+                        book_id.to_string(),
+                        Some(book_id),
+                        messages::BOOK_NOT_WANTED.text(),
+                    )
+                }
+            })
+            .collect();
+
+        if !removable.is_empty() {
+            self.books_wanted_repository.delete_many(&removable, user_id).await?;
+        }
+
+        Ok(results)
+    }
+}
@@ -1,8 +1,11 @@
 use crate::error::AppError;
+use crate::i18n::messages;
 use crate::models::book::GoogleBookDto;
 use crate::services::http_service::HttpService;
+use crate::services::metrics::metrics;
 use serde_json::Value;
 use std::sync::Arc;
+use std::time::Instant;
 
 pub trait GoogleBookService: Send + Sync {
     fn search_books<'a>(
@@ -29,6 +32,14 @@ impl GoogleBookServiceImpl {
         Self { http_service }
     }
 
+    #[tracing::instrument(name = "google_books", skip(self), err(Display, level = "warn"))]
+    async fn fetch(&self, url: &str) -> Result<Value, AppError> {
+        let started = Instant::now();
+        let result = self.http_service.get(url).await;
+        metrics().observe_upstream_call("google_books", started, &result);
+        result
+    }
+
     // Função auxiliar para converter JSON em GoogleBookDto
     fn convert_to_google_book_dto(&self, item: &Value) -> GoogleBookDto {
         let google_id = item["id"].as_str().unwrap_or_default().to_string();
@@ -54,10 +65,7 @@ impl GoogleBookServiceImpl {
 
         let publisher = volume_info["publisher"].as_str().map(|s| s.to_string());
 
-        let published_date = match volume_info["publishedDate"].as_str() {
-            Some(date_str) => Some(date_str.to_string()),
-            None => None,
-        };
+        let published_date = volume_info["publishedDate"].as_str().map(|s| s.to_string());
 
         let description = volume_info["description"].as_str().map(|s| s.to_string());
         let page_count = volume_info["pageCount"].as_i64().map(|n| n as i32);
@@ -92,10 +100,10 @@ impl GoogleBookService for GoogleBookServiceImpl {
     > {
         Box::pin(async move {
             let mut url = String::from("https://www.googleapis.com/books/v1/volumes?q=");
-            url.push_str(&query);
+            url.push_str(query);
             url.push_str("&fields=items(id,volumeInfo(title,authors,publisher,publishedDate,description,pageCount,imageLinks/thumbnail))");
 
-            let data = self.http_service.get(&url).await?;
+            let data = self.fetch(&url).await?;
 
             let items = match data.get("items") {
                 Some(items) => items,
@@ -122,14 +130,20 @@ impl GoogleBookService for GoogleBookServiceImpl {
         Box<dyn std::future::Future<Output = Result<GoogleBookDto, AppError>> + Send + 'a>,
     > {
         Box::pin(async move {
+            // O id vai no caminho da URL; fora do formato do Google ele mudaria a requisição
+            if !is_valid_google_id(google_id) {
+                let message = messages::BOOK_ID_NOT_FOUND.format(&[&google_id]);
+                return Err(AppError::NotFoundError(message));
+            }
+
             let mut url = String::from("https://www.googleapis.com/books/v1/volumes/");
-            url.push_str(&google_id);
+            url.push_str(google_id);
             url.push_str("?fields=id,volumeInfo(title,authors,publisher,publishedDate,description,pageCount,imageLinks/thumbnail)");
 
-            let data = match self.http_service.get(&url).await {
+            let data = match self.fetch(&url).await {
                 Ok(data) => data,
                 Err(AppError::NotFoundError(_)) => {
-                    let message = format!("Livro com ID {} não encontrado", google_id);
+                    let message = messages::BOOK_ID_NOT_FOUND.format(&[&google_id]);
                     return Err(AppError::NotFoundError(message));
                 }
                 Err(e) => return Err(e),
@@ -141,3 +155,9 @@ impl GoogleBookService for GoogleBookServiceImpl {
         })
     }
 }
+
+/// Se o id segue o formato dos volumes do Google Books (letras, números, `_` e `-`)
+pub fn is_valid_google_id(google_id: &str) -> bool {
+    (1..=64).contains(&google_id.len())
+        && google_id.bytes().all(|byte| byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'-')
+}
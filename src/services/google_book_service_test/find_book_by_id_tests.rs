@@ -183,6 +183,27 @@ mod tests {
         }
     }
 
+    // Ids fora do formato do Google não chegam a ser enviados
+    #[tokio::test]
+    async fn test_find_book_by_id_rejects_ids_that_change_the_url() {
+        // Arrange
+        // O mock responderia com um livro se a requisição fosse feita
+        let mock_http_service = Arc::new(MockHttpService::new_success(json!({ "id": "outro" })));
+        let service = GoogleBookServiceImpl::new(mock_http_service);
+
+        for book_id in ["x?q=harry", "../volumes?q=harry", "id#", "a/b", &"a".repeat(65)] {
+            // Act
+            let result = service.find_book_by_id(book_id).await;
+
+            // Assert
+            assert!(
+                matches!(result, Err(AppError::NotFoundError(_))),
+                "O id {} deveria ser recusado",
+                book_id
+            );
+        }
+    }
+
     // Teste usando mock para simular resposta vazia ou inválida
     #[tokio::test]
     async fn test_find_book_by_id_empty_response() {
@@ -0,0 +1,112 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+use crate::config::Config;
+use crate::error::AppError;
+use crate::models::health::{CheckStatus, ReadinessReport};
+use crate::services::http_service::{HttpService, HttpServiceImpl};
+use crate::services::metrics::PoolStats;
+
+/// Tempo máximo de cada verificação, para que uma dependência travada não
+/// segure a resposta até o timeout do orquestrador
+pub const CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Consulta mínima à API do Google Books, sem resultados
+const GOOGLE_BOOKS_CHECK_URL: &str =
+    "https://www.googleapis.com/books/v1/volumes?q=isbn:0&maxResults=1&fields=totalItems";
+
+/// Verificações de prontidão usadas pelo orquestrador
+#[async_trait]
+pub trait HealthService: Send + Sync + 'static {
+    /// Verifica o banco e, se configurado, a API do Google Books
+    async fn readiness(&self) -> ReadinessReport;
+
+    /// Uso atual do pool de conexões
+    fn pool_stats(&self) -> PoolStats;
+}
+
+pub struct HealthServiceImpl {
+    pool: PgPool,
+    /// Sem cliente, a API do Google Books não é verificada
+    google_books: Option<Arc<dyn HttpService>>,
+}
+
+impl HealthServiceImpl {
+    pub fn new(pool: PgPool, google_books: Option<Arc<dyn HttpService>>) -> Self {
+        Self { pool, google_books }
+    }
+
+    async fn check_database(&self) -> CheckStatus {
+        let query = sqlx::query("SELECT 1").execute(&self.pool);
+        match tokio::time::timeout(CHECK_TIMEOUT, query).await {
+            Ok(Ok(_)) => CheckStatus::Ok,
+            Ok(Err(e)) => {
+                tracing::warn!("Banco de dados indisponível: {}", e);
+                CheckStatus::Unavailable
+            }
+            Err(_) => {
+                tracing::warn!("Banco de dados não respondeu em {:?}", CHECK_TIMEOUT);
+                CheckStatus::Unavailable
+            }
+        }
+    }
+
+    async fn check_google_books(http_service: &dyn HttpService) -> CheckStatus {
+        match tokio::time::timeout(CHECK_TIMEOUT, http_service.get(GOOGLE_BOOKS_CHECK_URL)).await {
+            // Um 404 também mostra que a API está respondendo
+            Ok(Ok(_)) | Ok(Err(AppError::NotFoundError(_))) => CheckStatus::Ok,
+            Ok(Err(e)) => {
+                tracing::warn!("API do Google Books indisponível: {}", e);
+                CheckStatus::Unavailable
+            }
+            Err(_) => {
+                tracing::warn!("API do Google Books não respondeu em {:?}", CHECK_TIMEOUT);
+                CheckStatus::Unavailable
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl HealthService for HealthServiceImpl {
+    async fn readiness(&self) -> ReadinessReport {
+        let mut checks = BTreeMap::new();
+        checks.insert("database".to_string(), self.check_database().await);
+
+        if let Some(http_service) = &self.google_books {
+            checks.insert(
+                "google_books".to_string(),
+                Self::check_google_books(http_service.as_ref()).await,
+            );
+        }
+
+        let status = if checks.values().all(|status| *status == CheckStatus::Ok) {
+            CheckStatus::Ok
+        } else {
+            CheckStatus::Unavailable
+        };
+
+        ReadinessReport { status, checks }
+    }
+
+    fn pool_stats(&self) -> PoolStats {
+        PoolStats {
+            size: self.pool.size(),
+            idle: self.pool.num_idle() as u32,
+            max: self.pool.options().get_max_connections(),
+        }
+    }
+}
+
+/// Factory para criar o HealthService conforme a configuração
+pub fn create_health_service(config: &Config, pool: Arc<PgPool>) -> Arc<dyn HealthService> {
+    let google_books = config
+        .readiness_check_google_books
+        .then(|| Arc::new(HttpServiceImpl::new()) as Arc<dyn HttpService>);
+
+    Arc::new(HealthServiceImpl::new(pool.as_ref().clone(), google_books))
+}
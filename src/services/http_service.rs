@@ -1,5 +1,7 @@
 use crate::error::AppError;
-use reqwest::Client;
+use crate::i18n::messages;
+use crate::middleware::request_id::{current_request_id, REQUEST_ID_HEADER};
+use reqwest::{Client, StatusCode};
 use serde_json::Value;
 
 pub trait HttpService: Send + Sync {
@@ -21,6 +23,12 @@ impl HttpServiceImpl {
     }
 }
 
+impl Default for HttpServiceImpl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl HttpService for HttpServiceImpl {
     fn get<'a>(
         &'a self,
@@ -28,18 +36,28 @@ impl HttpService for HttpServiceImpl {
     ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<Value, AppError>> + Send + 'a>>
     {
         Box::pin(async move {
-            let response = self.client.get(url).send().await.map_err(|e| {
-                AppError::InternalServerError(format!("Erro na requisição HTTP: {}", e))
+            // O identificador da requisição acompanha as chamadas a outros serviços
+            let mut request = self.client.get(url);
+            if let Some(request_id) = current_request_id() {
+                request = request.header(REQUEST_ID_HEADER.as_str(), request_id);
+            }
+
+            let response = request.send().await.map_err(|e| {
+                AppError::BadGateway(messages::UPSTREAM_REQUEST_FAILED.format(&[&e]))
             })?;
 
             let status = response.status();
-            if !status.is_success() {
-                let message = format!("Erro na requisição: Status {}", status);
+            if status == StatusCode::NOT_FOUND {
+                let message = messages::UPSTREAM_STATUS.format(&[&status]);
                 return Err(AppError::NotFoundError(message));
             }
+            if !status.is_success() {
+                let message = messages::UPSTREAM_STATUS.format(&[&status]);
+                return Err(AppError::BadGateway(message));
+            }
 
             let data: Value = response.json().await.map_err(|e| {
-                AppError::InternalServerError(format!("Erro ao processar resposta: {}", e))
+                AppError::BadGateway(messages::UPSTREAM_RESPONSE_INVALID.format(&[&e]))
             })?;
 
             Ok(data)
@@ -0,0 +1,84 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::error::AppError;
+use crate::i18n::messages;
+use crate::models::user::{CreateUserDto, LoginResponse, LoginUserDto, UserResponse};
+use crate::services::auth_service::AuthService;
+use crate::services::rate_limit_service::{RateLimitService, FAILED_LOGIN_LIMIT, REGISTER_EMAIL_LIMIT};
+
+/// AuthService que bloqueia temporariamente o login de uma conta após
+/// várias tentativas com a senha errada e limita os cadastros por email
+///
+/// Cada tentativa de login é reservada antes da verificação da senha, de modo
+/// que requisições simultâneas não escapem do limite, mas só continua contando
+/// quando a resposta é `InvalidCredentials`: requisições inválidas ou que falham
+/// por outro motivo devolvem a tentativa, e um login bem-sucedido zera a
+/// contagem. Emails sem conta também contam, já que recebem a mesma resposta da
+/// senha errada e o bloqueio não pode revelar quais emails estão cadastrados.
+/// Enquanto a conta está bloqueada a senha nem chega a ser verificada, então
+/// tentativas repetidas não consomem o custo do Argon2.
+pub struct ThrottledAuthService {
+    auth_service: Arc<dyn AuthService>,
+    rate_limit_service: Arc<dyn RateLimitService>,
+}
+
+impl ThrottledAuthService {
+    pub fn new(auth_service: Arc<dyn AuthService>, rate_limit_service: Arc<dyn RateLimitService>) -> Self {
+        Self {
+            auth_service,
+            rate_limit_service,
+        }
+    }
+
+    fn normalize_email(email: &str) -> String {
+        email.trim().to_lowercase()
+    }
+
+    fn failed_login_key(email: &str) -> String {
+        format!("failed_login:{}", Self::normalize_email(email))
+    }
+
+    fn register_key(email: &str) -> String {
+        format!("register:email:{}", Self::normalize_email(email))
+    }
+}
+
+#[async_trait]
+impl AuthService for ThrottledAuthService {
+    async fn register(&self, user_dto: CreateUserDto) -> Result<UserResponse, AppError> {
+        // O limite por IP fica na rota; este vale para o email, de qualquer origem
+        self.rate_limit_service
+            .hit(&Self::register_key(&user_dto.email), REGISTER_EMAIL_LIMIT)
+            .await?;
+
+        self.auth_service.register(user_dto).await
+    }
+
+    async fn login(&self, login_dto: LoginUserDto) -> Result<LoginResponse, AppError> {
+        let key = Self::failed_login_key(&login_dto.email);
+
+        self.rate_limit_service
+            .hit(&key, FAILED_LOGIN_LIMIT)
+            .await
+            .map_err(|e| match e {
+                AppError::TooManyRequests(_, retry_after) => {
+                    AppError::TooManyRequests(messages::LOGIN_LOCKED.format(&[&retry_after]), retry_after)
+                }
+                other => other,
+            })?;
+
+        match self.auth_service.login(login_dto).await {
+            Ok(response) => {
+                self.rate_limit_service.reset(&key).await?;
+                Ok(response)
+            }
+            Err(AppError::InvalidCredentials) => Err(AppError::InvalidCredentials),
+            Err(e) => {
+                self.rate_limit_service.release(&key).await?;
+                Err(e)
+            }
+        }
+    }
+}
@@ -0,0 +1,211 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+
+use crate::error::AppError;
+
+/// Limites dos buckets dos histogramas de duração, em segundos
+pub const DURATION_BUCKETS: [f64; 11] = [0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+static METRICS: Lazy<Metrics> = Lazy::new(Metrics::new);
+
+/// Métricas do processo, compartilhadas por middleware e serviços
+pub fn metrics() -> &'static Metrics {
+    &METRICS
+}
+
+/// Uso do pool de conexões no momento da coleta
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolStats {
+    pub size: u32,
+    pub idle: u32,
+    pub max: u32,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Histogram {
+    // Contagem não acumulada por bucket; o último é o +Inf
+    buckets: [u64; DURATION_BUCKETS.len() + 1],
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn observe(&mut self, seconds: f64) {
+        let index = DURATION_BUCKETS
+            .iter()
+            .position(|&bound| seconds <= bound)
+            .unwrap_or(DURATION_BUCKETS.len());
+        self.buckets[index] += 1;
+        self.sum += seconds;
+        self.count += 1;
+    }
+}
+
+// Séries de uma métrica, indexadas pelos valores dos rótulos
+struct Family<T> {
+    name: &'static str,
+    help: &'static str,
+    labels: &'static [&'static str],
+    series: Mutex<BTreeMap<Vec<String>, T>>,
+}
+
+impl<T: Default> Family<T> {
+    fn new(name: &'static str, help: &'static str, labels: &'static [&'static str]) -> Self {
+        Self {
+            name,
+            help,
+            labels,
+            series: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    fn update(&self, values: &[&str], f: impl FnOnce(&mut T)) {
+        let key = values.iter().map(|value| value.to_string()).collect();
+        let mut series = self.series.lock().unwrap_or_else(|e| e.into_inner());
+        f(series.entry(key).or_default());
+    }
+
+    fn label_pairs(&self, values: &[String], extra: Option<(&str, &str)>) -> String {
+        let pairs: Vec<String> = self
+            .labels
+            .iter()
+            .zip(values)
+            .map(|(name, value)| (*name, value.as_str()))
+            .chain(extra)
+            .map(|(name, value)| format!("{}=\"{}\"", name, escape_label(value)))
+            .collect();
+
+        if pairs.is_empty() {
+            String::new()
+        } else {
+            format!("{{{}}}", pairs.join(","))
+        }
+    }
+}
+
+impl Family<u64> {
+    fn render(&self, out: &mut String) {
+        write_header(out, self.name, self.help, "counter");
+        let series = self.series.lock().unwrap_or_else(|e| e.into_inner());
+        for (values, count) in series.iter() {
+            let _ = writeln!(out, "{}{} {}", self.name, self.label_pairs(values, None), count);
+        }
+    }
+}
+
+impl Family<Histogram> {
+    fn render(&self, out: &mut String) {
+        write_header(out, self.name, self.help, "histogram");
+        let series = self.series.lock().unwrap_or_else(|e| e.into_inner());
+        for (values, histogram) in series.iter() {
+            let mut cumulative = 0;
+            for (i, count) in histogram.buckets.iter().enumerate() {
+                cumulative += count;
+                let bound = DURATION_BUCKETS.get(i).map_or("+Inf".to_string(), |b| b.to_string());
+                let labels = self.label_pairs(values, Some(("le", &bound)));
+                let _ = writeln!(out, "{}_bucket{} {}", self.name, labels, cumulative);
+            }
+            let labels = self.label_pairs(values, None);
+            let _ = writeln!(out, "{}_sum{} {}", self.name, labels, histogram.sum);
+            let _ = writeln!(out, "{}_count{} {}", self.name, labels, histogram.count);
+        }
+    }
+}
+
+fn write_header(out: &mut String, name: &str, help: &str, kind: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+}
+
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+/// Métricas no formato de texto do Prometheus
+///
+/// Os rótulos de rota usam o padrão registrado (`/api/v1/trades/:trade_id/accept`), e
+/// não o caminho recebido, para que a quantidade de séries não cresça com os IDs.
+pub struct Metrics {
+    http_requests: Family<Histogram>,
+    upstream_requests: Family<u64>,
+    upstream_durations: Family<Histogram>,
+    trade_matcher: Family<Histogram>,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Self {
+            http_requests: Family::new(
+                "http_request_duration_seconds",
+                "Duração das requisições HTTP por rota",
+                &["method", "route", "status"],
+            ),
+            upstream_requests: Family::new(
+                "upstream_requests_total",
+                "Chamadas a serviços externos por resultado",
+                &["service", "outcome"],
+            ),
+            upstream_durations: Family::new(
+                "upstream_request_duration_seconds",
+                "Duração das chamadas a serviços externos",
+                &["service"],
+            ),
+            trade_matcher: Family::new(
+                "trade_matcher_duration_seconds",
+                "Duração da busca de trocas possíveis",
+                &["operation"],
+            ),
+        }
+    }
+
+    pub fn observe_http_request(&self, method: &str, route: &str, status: u16, duration: Duration) {
+        self.http_requests
+            .update(&[method, route, &status.to_string()], |h| h.observe(duration.as_secs_f64()));
+    }
+
+    /// Registra uma chamada a um serviço externo; apenas falhas de comunicação
+    /// ou respostas inválidas (BadGateway) contam como erro
+    pub fn observe_upstream_call<T>(&self, service: &str, started: Instant, result: &Result<T, AppError>) {
+        let outcome = match result {
+            Err(AppError::BadGateway(_)) => "error",
+            _ => "success",
+        };
+        self.upstream_requests.update(&[service, outcome], |count| *count += 1);
+        self.upstream_durations
+            .update(&[service], |h| h.observe(started.elapsed().as_secs_f64()));
+    }
+
+    pub fn observe_trade_matcher(&self, operation: &str, duration: Duration) {
+        self.trade_matcher
+            .update(&[operation], |h| h.observe(duration.as_secs_f64()));
+    }
+
+    /// Gera o texto exposto em `/metrics`
+    pub fn render(&self, pool: Option<PoolStats>) -> String {
+        let mut out = String::new();
+        self.http_requests.render(&mut out);
+        self.upstream_requests.render(&mut out);
+        self.upstream_durations.render(&mut out);
+        self.trade_matcher.render(&mut out);
+
+        if let Some(pool) = pool {
+            write_header(&mut out, "db_pool_connections", "Conexões abertas no pool por estado", "gauge");
+            let _ = writeln!(out, "db_pool_connections{{state=\"idle\"}} {}", pool.idle);
+            let _ = writeln!(out, "db_pool_connections{{state=\"in_use\"}} {}", pool.size.saturating_sub(pool.idle));
+            write_header(&mut out, "db_pool_max_connections", "Limite de conexões do pool", "gauge");
+            let _ = writeln!(out, "db_pool_max_connections {}", pool.max);
+        }
+
+        out
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
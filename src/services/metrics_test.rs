@@ -0,0 +1,70 @@
+use std::time::{Duration, Instant};
+
+use crate::error::AppError;
+use crate::services::metrics::{Metrics, PoolStats};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_http_histogram_is_cumulative_per_route() {
+        let metrics = Metrics::new();
+        metrics.observe_http_request("GET", "/api/trades/:id", 200, Duration::from_millis(3));
+        metrics.observe_http_request("GET", "/api/trades/:id", 200, Duration::from_millis(300));
+        metrics.observe_http_request("GET", "/api/trades/:id", 404, Duration::from_secs(20));
+
+        let text = metrics.render(None);
+
+        assert!(text.contains("# TYPE http_request_duration_seconds histogram"));
+        let series = r#"method="GET",route="/api/trades/:id",status="200""#;
+        assert!(text.contains(&format!("http_request_duration_seconds_bucket{{{},le=\"0.005\"}} 1", series)));
+        assert!(text.contains(&format!("http_request_duration_seconds_bucket{{{},le=\"0.25\"}} 1", series)));
+        assert!(text.contains(&format!("http_request_duration_seconds_bucket{{{},le=\"0.5\"}} 2", series)));
+        assert!(text.contains(&format!("http_request_duration_seconds_bucket{{{},le=\"+Inf\"}} 2", series)));
+        assert!(text.contains(&format!("http_request_duration_seconds_count{{{}}} 2", series)));
+
+        // Acima do maior bucket, a observação só aparece em +Inf
+        let not_found = r#"method="GET",route="/api/trades/:id",status="404""#;
+        assert!(text.contains(&format!("http_request_duration_seconds_bucket{{{},le=\"10\"}} 0", not_found)));
+        assert!(text.contains(&format!("http_request_duration_seconds_bucket{{{},le=\"+Inf\"}} 1", not_found)));
+    }
+
+    #[test]
+    fn test_upstream_errors_are_only_gateway_failures() {
+        let metrics = Metrics::new();
+        let started = Instant::now();
+        metrics.observe_upstream_call::<()>("google_books", started, &Ok(()));
+        metrics.observe_upstream_call::<()>("google_books", started, &Err(AppError::NotFoundError("x".to_string())));
+        metrics.observe_upstream_call::<()>("google_books", started, &Err(AppError::BadGateway("x".to_string())));
+
+        let text = metrics.render(None);
+
+        assert!(text.contains(r#"upstream_requests_total{service="google_books",outcome="success"} 2"#));
+        assert!(text.contains(r#"upstream_requests_total{service="google_books",outcome="error"} 1"#));
+        assert!(text.contains(r#"upstream_request_duration_seconds_count{service="google_books"} 3"#));
+    }
+
+    #[test]
+    fn test_trade_matcher_and_pool_gauges() {
+        let metrics = Metrics::new();
+        metrics.observe_trade_matcher("find_possible_trades", Duration::from_millis(20));
+
+        let text = metrics.render(Some(PoolStats { size: 4, idle: 1, max: 10 }));
+
+        assert!(text.contains(r#"trade_matcher_duration_seconds_count{operation="find_possible_trades"} 1"#));
+        assert!(text.contains(r#"db_pool_connections{state="idle"} 1"#));
+        assert!(text.contains(r#"db_pool_connections{state="in_use"} 3"#));
+        assert!(text.contains("db_pool_max_connections 10"));
+    }
+
+    #[test]
+    fn test_label_values_are_escaped() {
+        let metrics = Metrics::new();
+        metrics.observe_trade_matcher("a\"b\\c", Duration::from_millis(1));
+
+        assert!(metrics
+            .render(None)
+            .contains(r#"trade_matcher_duration_seconds_count{operation="a\"b\\c"} 1"#));
+    }
+}
@@ -1,11 +1,25 @@
 pub mod auth_service;
+pub mod book_batch;
 pub mod book_offered_service;
+pub mod book_photo_service;
 pub mod book_wanted_service;
 pub mod book_service;
 pub mod google_book_service;
+pub mod health_service;
 pub mod http_service;
+pub mod login_throttle;
+pub mod metrics;
+pub mod oauth_service;
 pub mod password_service;
+pub mod photo_storage;
+pub mod rate_limit_service;
+pub mod shelf_format;
+pub mod shelf_service;
+pub mod token_service;
+pub mod totp;
 pub mod trade_service;
+pub mod two_factor_service;
+pub mod user_service;
 
 #[cfg(test)]
 pub mod auth_service_test;
@@ -13,20 +27,50 @@ pub mod auth_service_test;
 #[cfg(test)]
 pub mod book_offered_wanted_service_test;
 
+#[cfg(test)]
+pub mod book_photo_service_test;
+
 #[cfg(test)]
 pub mod book_service_test;
 
 #[cfg(test)]
 pub mod google_book_service_test;
 
+#[cfg(test)]
+pub mod health_service_test;
+
 #[cfg(test)]
 pub mod http_service_test;
 
+#[cfg(test)]
+pub mod metrics_test;
+
+#[cfg(test)]
+pub mod oauth_service_test;
+
 #[cfg(test)]
 pub mod password_service_test;
 
+#[cfg(test)]
+pub mod rate_limit_service_test;
+
+#[cfg(test)]
+pub mod shelf_format_test;
+
+#[cfg(test)]
+pub mod shelf_service_test;
+
+#[cfg(test)]
+pub mod token_service_test;
+
+#[cfg(test)]
+pub mod totp_test;
+
 #[cfg(test)]
 pub mod trade_service_test;
 
+#[cfg(test)]
+pub mod two_factor_service_test;
+
 #[cfg(test)]
 pub mod test_mocks;
@@ -0,0 +1,456 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use jsonwebtoken::{decode, DecodingKey, Validation};
+use reqwest::{Client, Url};
+use ring::digest::{digest, SHA256};
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+use crate::config::{Config, OAuthClientConfig};
+use crate::error::AppError;
+use crate::i18n::messages;
+use crate::models::oauth::{ExternalIdentity, OAuthLoginState};
+use crate::models::user::{LoginResponse, User};
+use crate::repositories::oauth_repository::OAuthRepository;
+use crate::repositories::user_repository::UserRepository;
+use crate::services::auth_service::complete_login;
+use crate::services::metrics::metrics;
+use crate::services::password_service::PasswordService;
+use crate::services::token_service::TokenService;
+
+/// Tempo que o usuário tem para concluir o login no provedor
+pub const LOGIN_STATE_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// Como a conta do usuário é obtida depois da troca do código
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProviderProfile {
+    /// OpenID Connect: a conta vem do id_token, emitido por `issuer`
+    OpenIdConnect { issuer: String },
+    /// GitHub, que não emite id_token: a conta vem da API REST
+    GitHub { api_url: String },
+}
+
+/// Provedor de login externo
+#[derive(Debug, Clone)]
+pub struct OAuthProvider {
+    /// Nome usado nas rotas e nos vínculos ("google" ou "github")
+    pub name: String,
+    pub client_id: String,
+    pub client_secret: String,
+    pub authorization_url: String,
+    pub token_url: String,
+    pub scope: String,
+    /// URL de retorno registrada no provedor
+    pub redirect_uri: String,
+    pub profile: ProviderProfile,
+}
+
+impl OAuthProvider {
+    pub fn google(client: &OAuthClientConfig, redirect_base_url: &str) -> Self {
+        Self {
+            name: "google".to_string(),
+            client_id: client.client_id.clone(),
+            client_secret: client.client_secret.clone(),
+            authorization_url: "https://accounts.google.com/o/oauth2/v2/auth".to_string(),
+            token_url: "https://oauth2.googleapis.com/token".to_string(),
+            scope: "openid email profile".to_string(),
+            redirect_uri: callback_url(redirect_base_url, "google"),
+            profile: ProviderProfile::OpenIdConnect {
+                issuer: "https://accounts.google.com".to_string(),
+            },
+        }
+    }
+
+    pub fn github(client: &OAuthClientConfig, redirect_base_url: &str) -> Self {
+        Self {
+            name: "github".to_string(),
+            client_id: client.client_id.clone(),
+            client_secret: client.client_secret.clone(),
+            authorization_url: "https://github.com/login/oauth/authorize".to_string(),
+            token_url: "https://github.com/login/oauth/access_token".to_string(),
+            scope: "read:user user:email".to_string(),
+            redirect_uri: callback_url(redirect_base_url, "github"),
+            profile: ProviderProfile::GitHub {
+                api_url: "https://api.github.com".to_string(),
+            },
+        }
+    }
+}
+
+fn callback_url(redirect_base_url: &str, provider: &str) -> String {
+    format!("{}/api/v1/auth/oauth/{}/callback", redirect_base_url, provider)
+}
+
+/// Login com provedores externos (OAuth 2.0 / OpenID Connect)
+#[async_trait]
+pub trait OAuthService: Send + Sync + 'static {
+    /// Inicia o login: guarda state, nonce e verificador PKCE e retorna a URL
+    /// de autorização do provedor
+    async fn authorization_url(&self, provider: &str) -> Result<String, AppError>;
+
+    /// Conclui o login com o código recebido no retorno do provedor
+    async fn login(&self, provider: &str, code: &str, state: &str) -> Result<LoginResponse, AppError>;
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenEndpointResponse {
+    access_token: Option<String>,
+    id_token: Option<String>,
+    error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct IdTokenClaims {
+    sub: String,
+    email: Option<String>,
+    #[serde(default)]
+    email_verified: bool,
+    name: Option<String>,
+    nonce: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubUser {
+    id: u64,
+    login: String,
+    name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubEmail {
+    email: String,
+    primary: bool,
+    verified: bool,
+}
+
+/// Fluxo authorization code com PKCE (S256)
+///
+/// A conta do provedor é vinculada ao usuário com o mesmo email, desde que o
+/// provedor o tenha confirmado; sem usuário com o email, um novo é criado.
+pub struct OAuthServiceImpl {
+    providers: Vec<OAuthProvider>,
+    oauth_repository: Arc<dyn OAuthRepository>,
+    user_repository: Arc<dyn UserRepository>,
+    password_service: Arc<dyn PasswordService>,
+    token_service: Arc<dyn TokenService>,
+    client: Client,
+    rng: SystemRandom,
+}
+
+impl OAuthServiceImpl {
+    pub fn new(
+        providers: Vec<OAuthProvider>,
+        oauth_repository: Arc<dyn OAuthRepository>,
+        user_repository: Arc<dyn UserRepository>,
+        password_service: Arc<dyn PasswordService>,
+        token_service: Arc<dyn TokenService>,
+    ) -> Self {
+        let client = Client::builder()
+            .user_agent("troca-livros-api")
+            .timeout(Duration::from_secs(10))
+            .build()
+            .expect("Falha ao criar o cliente HTTP");
+
+        Self {
+            providers,
+            oauth_repository,
+            user_repository,
+            password_service,
+            token_service,
+            client,
+            rng: SystemRandom::new(),
+        }
+    }
+
+    fn find_provider(&self, name: &str) -> Result<&OAuthProvider, AppError> {
+        self.providers
+            .iter()
+            .find(|provider| provider.name == name)
+            .ok_or_else(|| AppError::NotFoundError(messages::OAUTH_PROVIDER_UNKNOWN.format(&[&name])))
+    }
+
+    // 32 bytes aleatórios em base64url, usados no state, no nonce e no verificador PKCE
+    fn random_token(&self) -> Result<String, AppError> {
+        let mut bytes = [0u8; 32];
+        self.rng
+            .fill(&mut bytes)
+            .map_err(|_| AppError::InternalServerError("Falha ao gerar valor aleatório".to_string()))?;
+        Ok(URL_SAFE_NO_PAD.encode(bytes))
+    }
+
+    async fn exchange_code(
+        &self,
+        provider: &OAuthProvider,
+        code: &str,
+        code_verifier: &str,
+    ) -> Result<TokenEndpointResponse, AppError> {
+        let started = Instant::now();
+        let result = self.request_token(provider, code, code_verifier).await;
+        metrics().observe_upstream_call(&format!("oauth_{}", provider.name), started, &result);
+        result
+    }
+
+    async fn request_token(
+        &self,
+        provider: &OAuthProvider,
+        code: &str,
+        code_verifier: &str,
+    ) -> Result<TokenEndpointResponse, AppError> {
+        let response = self
+            .client
+            .post(&provider.token_url)
+            .header(reqwest::header::ACCEPT, "application/json")
+            .form(&[
+                ("grant_type", "authorization_code"),
+                ("code", code),
+                ("redirect_uri", provider.redirect_uri.as_str()),
+                ("client_id", provider.client_id.as_str()),
+                ("client_secret", provider.client_secret.as_str()),
+                ("code_verifier", code_verifier),
+            ])
+            .send()
+            .await
+            .map_err(|e| AppError::BadGateway(messages::UPSTREAM_REQUEST_FAILED.format(&[&e])))?;
+
+        let status = response.status();
+        let body = response.json::<TokenEndpointResponse>().await;
+
+        // Código inválido, expirado ou verificador PKCE incorreto; o GitHub
+        // responde esses erros com status 200
+        if let Ok(TokenEndpointResponse { error: Some(error), .. }) = &body {
+            return Err(AppError::AuthError(messages::OAUTH_AUTHORIZATION_DENIED.format(&[error])));
+        }
+        if !status.is_success() {
+            return Err(AppError::BadGateway(messages::UPSTREAM_STATUS.format(&[&status])));
+        }
+
+        body.map_err(|e| AppError::BadGateway(messages::UPSTREAM_RESPONSE_INVALID.format(&[&e])))
+    }
+
+    // O id_token chega pela conexão TLS direta com o endpoint de token, que
+    // já autentica o emissor (OpenID Connect Core, seção 3.1.3.7); por isso a
+    // assinatura não é verificada, mas emissor, público, validade e nonce são
+    fn identity_from_id_token(
+        &self,
+        provider: &OAuthProvider,
+        issuer: &str,
+        id_token: &str,
+        nonce: &str,
+    ) -> Result<ExternalIdentity, AppError> {
+        let mut validation = Validation::default();
+        validation.insecure_disable_signature_validation();
+        // O Google também emite tokens com o emissor sem o esquema
+        validation.set_issuer(&[issuer, issuer.trim_start_matches("https://")]);
+        validation.set_audience(&[&provider.client_id]);
+        validation.set_required_spec_claims(&["exp", "sub", "iss", "aud"]);
+
+        let claims = decode::<IdTokenClaims>(id_token, &DecodingKey::from_secret(&[]), &validation)
+            .map_err(|e| AppError::AuthError(messages::OAUTH_ID_TOKEN_INVALID.format(&[&e])))?
+            .claims;
+
+        if claims.nonce.as_deref() != Some(nonce) {
+            return Err(AppError::AuthError(messages::OAUTH_ID_TOKEN_INVALID.format(&[&"nonce"])));
+        }
+
+        let email = claims
+            .email
+            .ok_or_else(|| AppError::Forbidden(messages::OAUTH_EMAIL_NOT_VERIFIED.text()))?;
+
+        Ok(ExternalIdentity {
+            provider: provider.name.clone(),
+            subject: claims.sub,
+            email,
+            email_verified: claims.email_verified,
+            name: claims.name,
+        })
+    }
+
+    async fn github_get<T: DeserializeOwned>(&self, url: String, access_token: &str) -> Result<T, AppError> {
+        let started = Instant::now();
+        let result = self.github_request(url, access_token).await;
+        metrics().observe_upstream_call("oauth_github", started, &result);
+        result
+    }
+
+    async fn github_request<T: DeserializeOwned>(&self, url: String, access_token: &str) -> Result<T, AppError> {
+        let response = self
+            .client
+            .get(url)
+            .bearer_auth(access_token)
+            .header(reqwest::header::ACCEPT, "application/vnd.github+json")
+            .send()
+            .await
+            .map_err(|e| AppError::BadGateway(messages::UPSTREAM_REQUEST_FAILED.format(&[&e])))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(AppError::BadGateway(messages::UPSTREAM_STATUS.format(&[&status])));
+        }
+
+        response
+            .json::<T>()
+            .await
+            .map_err(|e| AppError::BadGateway(messages::UPSTREAM_RESPONSE_INVALID.format(&[&e])))
+    }
+
+    async fn identity_from_github(
+        &self,
+        provider: &OAuthProvider,
+        api_url: &str,
+        access_token: &str,
+    ) -> Result<ExternalIdentity, AppError> {
+        let user: GitHubUser = self.github_get(format!("{}/user", api_url), access_token).await?;
+        let emails: Vec<GitHubEmail> = self.github_get(format!("{}/user/emails", api_url), access_token).await?;
+
+        // Email principal confirmado; sem ele, o primeiro confirmado
+        let email = emails
+            .iter()
+            .find(|email| email.primary && email.verified)
+            .or_else(|| emails.iter().find(|email| email.verified))
+            .ok_or_else(|| AppError::Forbidden(messages::OAUTH_EMAIL_NOT_VERIFIED.text()))?;
+
+        Ok(ExternalIdentity {
+            provider: provider.name.clone(),
+            subject: user.id.to_string(),
+            email: email.email.clone(),
+            email_verified: email.verified,
+            name: user.name.or(Some(user.login)),
+        })
+    }
+
+    // Usuário vinculado à conta; sem vínculo, o usuário com o mesmo email ou um novo
+    async fn find_or_create_user(&self, identity: &ExternalIdentity) -> Result<User, AppError> {
+        if let Some(user) = self
+            .oauth_repository
+            .find_user_by_identity(&identity.provider, &identity.subject)
+            .await?
+        {
+            return Ok(user);
+        }
+
+        // Sem a confirmação do provedor, qualquer um poderia assumir a conta
+        // de outra pessoa cadastrando o email dela no provedor
+        if !identity.email_verified {
+            return Err(AppError::Forbidden(messages::OAUTH_EMAIL_NOT_VERIFIED.text()));
+        }
+
+        if let Some(user) = self.oauth_repository.find_user_by_email(&identity.email).await? {
+            self.oauth_repository.link_identity(&user.id, identity).await?;
+            return Ok(user);
+        }
+
+        // O usuário entra apenas pelo provedor; a senha aleatória só preenche o hash
+        let hash_password = self.password_service.hash_password(&self.random_token()?).await?;
+        self.oauth_repository
+            .create_user_with_identity(&display_name(identity), hash_password, identity)
+            .await
+    }
+}
+
+// Nome informado pelo provedor ou, sem ele, a parte local do email
+fn display_name(identity: &ExternalIdentity) -> String {
+    let name = identity
+        .name
+        .as_deref()
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| identity.email.split('@').next().unwrap_or_default());
+
+    name.chars().take(255).collect()
+}
+
+#[async_trait]
+impl OAuthService for OAuthServiceImpl {
+    async fn authorization_url(&self, provider: &str) -> Result<String, AppError> {
+        let provider = self.find_provider(provider)?;
+
+        let login_state = OAuthLoginState {
+            state: self.random_token()?,
+            provider: provider.name.clone(),
+            code_verifier: self.random_token()?,
+            nonce: self.random_token()?,
+        };
+        self.oauth_repository.save_login_state(&login_state).await?;
+
+        let code_challenge = URL_SAFE_NO_PAD.encode(digest(&SHA256, login_state.code_verifier.as_bytes()));
+        let mut params = vec![
+            ("response_type", "code"),
+            ("client_id", provider.client_id.as_str()),
+            ("redirect_uri", provider.redirect_uri.as_str()),
+            ("scope", provider.scope.as_str()),
+            ("state", login_state.state.as_str()),
+            ("code_challenge", code_challenge.as_str()),
+            ("code_challenge_method", "S256"),
+        ];
+        if let ProviderProfile::OpenIdConnect { .. } = provider.profile {
+            params.push(("nonce", login_state.nonce.as_str()));
+        }
+
+        let url = Url::parse_with_params(&provider.authorization_url, &params)
+            .map_err(|e| AppError::InternalServerError(format!("URL de autorização inválida: {}", e)))?;
+
+        Ok(url.to_string())
+    }
+
+    async fn login(&self, provider: &str, code: &str, state: &str) -> Result<LoginResponse, AppError> {
+        let provider = self.find_provider(provider)?;
+
+        // O state é consumido antes de qualquer outra verificação, para que
+        // não possa ser reutilizado
+        let login_state = self
+            .oauth_repository
+            .take_login_state(state, LOGIN_STATE_TTL)
+            .await?
+            .filter(|login_state| login_state.provider == provider.name)
+            .ok_or_else(|| AppError::AuthError(messages::OAUTH_STATE_INVALID.text()))?;
+
+        let tokens = self.exchange_code(provider, code, &login_state.code_verifier).await?;
+        let invalid_response =
+            || AppError::BadGateway(messages::UPSTREAM_RESPONSE_INVALID.format(&[&"token"]));
+
+        let identity = match &provider.profile {
+            ProviderProfile::OpenIdConnect { issuer } => {
+                let id_token = tokens.id_token.as_deref().ok_or_else(invalid_response)?;
+                self.identity_from_id_token(provider, issuer, id_token, &login_state.nonce)?
+            }
+            ProviderProfile::GitHub { api_url } => {
+                let access_token = tokens.access_token.as_deref().ok_or_else(invalid_response)?;
+                self.identity_from_github(provider, api_url, access_token).await?
+            }
+        };
+
+        let user = self.find_or_create_user(&identity).await?;
+
+        // Quem ativou a verificação em duas etapas também a faz aqui
+        complete_login(user, self.user_repository.as_ref(), self.token_service.as_ref()).await
+    }
+}
+
+/// Factory para criar o OAuthService com os provedores configurados
+pub fn create_oauth_service(
+    config: &Config,
+    oauth_repository: Arc<dyn OAuthRepository>,
+    user_repository: Arc<dyn UserRepository>,
+    password_service: Arc<dyn PasswordService>,
+    token_service: Arc<dyn TokenService>,
+) -> Arc<dyn OAuthService> {
+    let mut providers = Vec::new();
+    if let Some(client) = &config.google_oauth {
+        providers.push(OAuthProvider::google(client, &config.oauth_redirect_base_url));
+    }
+    if let Some(client) = &config.github_oauth {
+        providers.push(OAuthProvider::github(client, &config.oauth_redirect_base_url));
+    }
+
+    Arc::new(OAuthServiceImpl::new(
+        providers,
+        oauth_repository,
+        user_repository,
+        password_service,
+        token_service,
+    ))
+}
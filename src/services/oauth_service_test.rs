@@ -0,0 +1,465 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{Duration, Utc};
+use jsonwebtoken::{encode, EncodingKey, Header};
+use mockito::{Matcher, Server, ServerGuard};
+use reqwest::Url;
+use ring::digest::{digest, SHA256};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::config::OAuthClientConfig;
+use crate::error::AppError;
+use crate::models::oauth::OAuthLoginState;
+use crate::models::user::{LoginResponse, TokenResponse, User};
+use crate::services::auth_service_test::{create_mock_password_service, create_test_user, MockUserRepository};
+use crate::services::oauth_service::{OAuthProvider, OAuthService, OAuthServiceImpl, ProviderProfile};
+use crate::services::test_mocks::MockOAuthRepository;
+use crate::services::token_service::{JwtTokenService, TokenService, TokenSettings};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLIENT_ID: &str = "cliente-teste";
+    const VERIFIER: &str = "verificador-pkce";
+    const NONCE: &str = "nonce-teste";
+
+    fn client() -> OAuthClientConfig {
+        OAuthClientConfig {
+            client_id: CLIENT_ID.to_string(),
+            client_secret: "segredo-teste".to_string(),
+        }
+    }
+
+    // Provedores apontando para o servidor local que faz o papel do provedor
+    fn oidc_provider(server: &ServerGuard) -> OAuthProvider {
+        OAuthProvider {
+            authorization_url: format!("{}/authorize", server.url()),
+            token_url: format!("{}/token", server.url()),
+            profile: ProviderProfile::OpenIdConnect { issuer: server.url() },
+            ..OAuthProvider::google(&client(), "http://localhost:8080")
+        }
+    }
+
+    fn github_provider(server: &ServerGuard) -> OAuthProvider {
+        OAuthProvider {
+            authorization_url: format!("{}/login/oauth/authorize", server.url()),
+            token_url: format!("{}/login/oauth/access_token", server.url()),
+            profile: ProviderProfile::GitHub { api_url: server.url() },
+            ..OAuthProvider::github(&client(), "http://localhost:8080")
+        }
+    }
+
+    fn token_service() -> Arc<JwtTokenService> {
+        Arc::new(JwtTokenService::with_secret(
+            "segredo",
+            TokenSettings {
+                issuer: "troca-livros-api".to_string(),
+                audience: "troca-livros".to_string(),
+                expires_in: Duration::hours(1),
+            },
+        ))
+    }
+
+    fn create_service(providers: Vec<OAuthProvider>, repository: MockOAuthRepository) -> OAuthServiceImpl {
+        let mut user_repository = MockUserRepository::new();
+        user_repository.expect_two_factor_enabled().returning(|_| Ok(false));
+
+        create_service_with_users(providers, repository, user_repository)
+    }
+
+    fn create_service_with_users(
+        providers: Vec<OAuthProvider>,
+        repository: MockOAuthRepository,
+        user_repository: MockUserRepository,
+    ) -> OAuthServiceImpl {
+        OAuthServiceImpl::new(
+            providers,
+            Arc::new(repository),
+            Arc::new(user_repository),
+            create_mock_password_service("hash_aleatorio".to_string(), false),
+            token_service(),
+        )
+    }
+
+    fn authenticated(response: LoginResponse) -> TokenResponse {
+        match response {
+            LoginResponse::Authenticated(token) => token,
+            other => panic!("Esperava o token de acesso, obteve {:?}", other),
+        }
+    }
+
+    // Repositório com uma autorização pendente ("state-valido") do provedor
+    fn repository_with_state(provider: &str) -> MockOAuthRepository {
+        let provider = provider.to_string();
+        let mut repository = MockOAuthRepository::new();
+        repository.expect_take_login_state().returning(move |state, _| {
+            Ok((state == "state-valido").then(|| OAuthLoginState {
+                state: state.to_string(),
+                provider: provider.clone(),
+                code_verifier: VERIFIER.to_string(),
+                nonce: NONCE.to_string(),
+            }))
+        });
+        repository
+    }
+
+    fn id_token(issuer: &str, subject: &str, email: &str, email_verified: bool, nonce: &str) -> String {
+        let now = Utc::now().timestamp();
+        let claims = json!({
+            "iss": issuer,
+            "aud": CLIENT_ID,
+            "sub": subject,
+            "email": email,
+            "email_verified": email_verified,
+            "name": "Usuária Google",
+            "nonce": nonce,
+            "iat": now,
+            "exp": now + 3600,
+        });
+        encode(&Header::default(), &claims, &EncodingKey::from_secret(b"provedor")).unwrap()
+    }
+
+    // Endpoint de token que só aceita o código e o verificador PKCE esperados
+    async fn mock_token_endpoint(server: &mut ServerGuard, path: &str, body: serde_json::Value) -> mockito::Mock {
+        server
+            .mock("POST", path)
+            .match_body(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("grant_type".to_string(), "authorization_code".to_string()),
+                Matcher::UrlEncoded("code".to_string(), "codigo".to_string()),
+                Matcher::UrlEncoded("code_verifier".to_string(), VERIFIER.to_string()),
+                Matcher::UrlEncoded("client_id".to_string(), CLIENT_ID.to_string()),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(body.to_string())
+            .create_async()
+            .await
+    }
+
+    fn assert_token_for(token: &str, user_id: &Uuid) {
+        let claims = token_service().verify(token).unwrap();
+        assert_eq!(claims.sub, user_id.to_string());
+    }
+
+    #[tokio::test]
+    async fn test_authorization_url_uses_pkce_and_stores_state() {
+        let server = Server::new_async().await;
+        let saved = Arc::new(Mutex::new(Vec::new()));
+        let saved_clone = saved.clone();
+
+        let mut repository = MockOAuthRepository::new();
+        repository.expect_save_login_state().returning(move |login_state| {
+            saved_clone.lock().unwrap().push(login_state.clone());
+            Ok(())
+        });
+        let service = create_service(vec![oidc_provider(&server), github_provider(&server)], repository);
+
+        let url = Url::parse(&service.authorization_url("google").await.unwrap()).unwrap();
+        let params: HashMap<String, String> = url.query_pairs().into_owned().collect();
+        let login_state = saved.lock().unwrap()[0].clone();
+
+        assert!(url.as_str().starts_with(&format!("{}/authorize?", server.url())));
+        assert_eq!(login_state.provider, "google");
+        assert_eq!(params["response_type"], "code");
+        assert_eq!(params["client_id"], CLIENT_ID);
+        assert_eq!(params["redirect_uri"], "http://localhost:8080/api/v1/auth/oauth/google/callback");
+        assert_eq!(params["state"], login_state.state);
+        assert_eq!(params["nonce"], login_state.nonce);
+        assert_eq!(params["code_challenge_method"], "S256");
+        assert_eq!(
+            params["code_challenge"],
+            URL_SAFE_NO_PAD.encode(digest(&SHA256, login_state.code_verifier.as_bytes()))
+        );
+        // O verificador nunca sai na URL
+        assert!(!url.as_str().contains(&login_state.code_verifier));
+
+        // O GitHub não usa nonce; cada autorização tem os seus próprios valores
+        let url = Url::parse(&service.authorization_url("github").await.unwrap()).unwrap();
+        let params: HashMap<String, String> = url.query_pairs().into_owned().collect();
+        assert!(!params.contains_key("nonce"));
+        assert_ne!(params["state"], login_state.state);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_provider() {
+        let server = Server::new_async().await;
+        let service = create_service(vec![oidc_provider(&server)], MockOAuthRepository::new());
+
+        assert!(matches!(
+            service.authorization_url("github").await,
+            Err(AppError::NotFoundError(_))
+        ));
+        assert!(matches!(
+            service.login("facebook", "codigo", "state-valido").await,
+            Err(AppError::NotFoundError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_oidc_login_links_existing_user_by_verified_email() {
+        let mut server = Server::new_async().await;
+        let token = id_token(&server.url(), "google-123", "usuaria@example.com", true, NONCE);
+        let token_endpoint = mock_token_endpoint(&mut server, "/token", json!({ "access_token": "at", "id_token": token })).await;
+
+        let user = create_test_user("Usuária", "usuaria@example.com");
+        let user_id = user.id;
+        let mut repository = repository_with_state("google");
+        repository.expect_find_user_by_identity().returning(|_, _| Ok(None));
+        repository
+            .expect_find_user_by_email()
+            .withf(|email| email == "usuaria@example.com")
+            .returning(move |_| Ok(Some(user.clone())));
+        repository
+            .expect_link_identity()
+            .withf(move |id, identity| {
+                *id == user_id && identity.provider == "google" && identity.subject == "google-123"
+            })
+            .times(1)
+            .returning(|_, _| Ok(()));
+        repository.expect_create_user_with_identity().times(0);
+
+        let service = create_service(vec![oidc_provider(&server)], repository);
+        let response = authenticated(service.login("google", "codigo", "state-valido").await.unwrap());
+
+        token_endpoint.assert_async().await;
+        assert_eq!(response.user.id, user_id);
+        assert_eq!(response.token_type, "Bearer");
+        assert_token_for(&response.access_token, &user_id);
+    }
+
+    #[tokio::test]
+    async fn test_oidc_login_creates_user_for_new_email() {
+        let mut server = Server::new_async().await;
+        let token = id_token(&server.url(), "google-456", "nova@example.com", true, NONCE);
+        mock_token_endpoint(&mut server, "/token", json!({ "id_token": token })).await;
+
+        let mut repository = repository_with_state("google");
+        repository.expect_find_user_by_identity().returning(|_, _| Ok(None));
+        repository.expect_find_user_by_email().returning(|_| Ok(None));
+        repository
+            .expect_create_user_with_identity()
+            .withf(|name, hash, identity| {
+                name == "Usuária Google" && hash == "hash_aleatorio" && identity.email == "nova@example.com"
+            })
+            .times(1)
+            .returning(|name, _, identity| Ok(create_test_user(name, &identity.email)));
+
+        let service = create_service(vec![oidc_provider(&server)], repository);
+        let response = authenticated(service.login("google", "codigo", "state-valido").await.unwrap());
+
+        assert_eq!(response.user.email, "nova@example.com");
+    }
+
+    #[tokio::test]
+    async fn test_login_with_linked_identity() {
+        let mut server = Server::new_async().await;
+        // O email não confirmado não importa quando a conta já está vinculada
+        let token = id_token(&server.url(), "google-123", "usuaria@example.com", false, NONCE);
+        mock_token_endpoint(&mut server, "/token", json!({ "id_token": token })).await;
+
+        let user = create_test_user("Usuária", "usuaria@example.com");
+        let user_id = user.id;
+        let mut repository = repository_with_state("google");
+        repository
+            .expect_find_user_by_identity()
+            .withf(|provider, subject| provider == "google" && subject == "google-123")
+            .returning(move |_, _| Ok(Some(user.clone())));
+        repository.expect_find_user_by_email().times(0);
+        repository.expect_link_identity().times(0);
+
+        let service = create_service(vec![oidc_provider(&server)], repository);
+        let response = authenticated(service.login("google", "codigo", "state-valido").await.unwrap());
+
+        assert_eq!(response.user.id, user_id);
+    }
+
+    #[tokio::test]
+    async fn test_login_with_two_factor_returns_challenge() {
+        let mut server = Server::new_async().await;
+        let token = id_token(&server.url(), "google-123", "usuaria@example.com", true, NONCE);
+        mock_token_endpoint(&mut server, "/token", json!({ "id_token": token })).await;
+
+        let user = create_test_user("Usuária", "usuaria@example.com");
+        let user_id = user.id;
+        let mut repository = repository_with_state("google");
+        repository
+            .expect_find_user_by_identity()
+            .returning(move |_, _| Ok(Some(user.clone())));
+        let mut user_repository = MockUserRepository::new();
+        user_repository
+            .expect_two_factor_enabled()
+            .withf(move |id| *id == user_id)
+            .returning(|_| Ok(true));
+
+        let service = create_service_with_users(vec![oidc_provider(&server)], repository, user_repository);
+        match service.login("google", "codigo", "state-valido").await.unwrap() {
+            LoginResponse::TwoFactorRequired(challenge) => {
+                let claims = token_service().verify_challenge(&challenge.challenge_token).unwrap();
+                assert_eq!(claims.sub, user_id.to_string());
+            }
+            other => panic!("Esperava o desafio da verificação em duas etapas, obteve {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unverified_email_is_not_linked() {
+        let mut server = Server::new_async().await;
+        let token = id_token(&server.url(), "google-999", "usuaria@example.com", false, NONCE);
+        mock_token_endpoint(&mut server, "/token", json!({ "id_token": token })).await;
+
+        let mut repository = repository_with_state("google");
+        repository.expect_find_user_by_identity().returning(|_, _| Ok(None));
+        repository.expect_find_user_by_email().times(0);
+        repository.expect_link_identity().times(0);
+        repository.expect_create_user_with_identity().times(0);
+
+        let service = create_service(vec![oidc_provider(&server)], repository);
+        let result = service.login("google", "codigo", "state-valido").await;
+
+        assert!(matches!(result, Err(AppError::Forbidden(_))));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_state_is_rejected_before_exchanging_the_code() {
+        let mut server = Server::new_async().await;
+        let token_endpoint = server.mock("POST", "/token").expect(0).create_async().await;
+
+        // State desconhecido, já usado ou expirado
+        let service = create_service(vec![oidc_provider(&server)], repository_with_state("google"));
+        let result = service.login("google", "codigo", "state-reutilizado").await;
+        assert!(matches!(result, Err(AppError::AuthError(_))));
+
+        // State emitido para outro provedor
+        let service = create_service(
+            vec![oidc_provider(&server), github_provider(&server)],
+            repository_with_state("github"),
+        );
+        let result = service.login("google", "codigo", "state-valido").await;
+        assert!(matches!(result, Err(AppError::AuthError(_))));
+
+        token_endpoint.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_invalid_id_token_is_rejected() {
+        // Nonce de outra autorização e emissor diferente do provedor
+        let cases = [
+            ("nonce", None, "outro-nonce"),
+            ("emissor", Some("https://outro.example.com"), NONCE),
+        ];
+
+        for (case, issuer, nonce) in cases {
+            let mut server = Server::new_async().await;
+            let token = id_token(issuer.unwrap_or(&server.url()), "google-1", "a@example.com", true, nonce);
+            mock_token_endpoint(&mut server, "/token", json!({ "id_token": token })).await;
+
+            let mut repository = repository_with_state("google");
+            repository.expect_find_user_by_identity().times(0);
+
+            let service = create_service(vec![oidc_provider(&server)], repository);
+            let result = service.login("google", "codigo", "state-valido").await;
+            assert!(matches!(result, Err(AppError::AuthError(_))), "{}: {:?}", case, result);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rejected_code_is_an_auth_error() {
+        let mut server = Server::new_async().await;
+        server
+            .mock("POST", "/token")
+            .with_status(400)
+            .with_header("content-type", "application/json")
+            .with_body(json!({ "error": "invalid_grant" }).to_string())
+            .create_async()
+            .await;
+
+        let service = create_service(vec![oidc_provider(&server)], repository_with_state("google"));
+        match service.login("google", "codigo", "state-valido").await {
+            Err(AppError::AuthError(message)) => assert!(message.contains("invalid_grant")),
+            other => panic!("Esperava AuthError, obteve {:?}", other),
+        }
+    }
+
+    // Servidor do GitHub que troca o código e devolve o usuário 42 com os emails informados
+    async fn mock_github(server: &mut ServerGuard, emails: serde_json::Value) {
+        mock_token_endpoint(server, "/login/oauth/access_token", json!({ "access_token": "gho_teste" })).await;
+        server
+            .mock("GET", "/user")
+            .match_header("authorization", "Bearer gho_teste")
+            .with_header("content-type", "application/json")
+            .with_body(json!({ "id": 42, "login": "usuaria", "name": null }).to_string())
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/user/emails")
+            .match_header("authorization", "Bearer gho_teste")
+            .with_header("content-type", "application/json")
+            .with_body(emails.to_string())
+            .create_async()
+            .await;
+    }
+
+    // Repositório em que o email informado pertence ao usuário, que é vinculado ao GitHub
+    fn repository_linking(user: User, email: &'static str) -> MockOAuthRepository {
+        let mut repository = repository_with_state("github");
+        repository.expect_find_user_by_identity().returning(|_, _| Ok(None));
+        repository
+            .expect_find_user_by_email()
+            .withf(move |candidate| candidate == email)
+            .returning(move |_| Ok(Some(user.clone())));
+        repository
+            .expect_link_identity()
+            .withf(|_, identity| identity.provider == "github" && identity.subject == "42")
+            .times(1)
+            .returning(|_, _| Ok(()));
+        repository
+    }
+
+    #[tokio::test]
+    async fn test_github_login_uses_primary_verified_email() {
+        let mut server = Server::new_async().await;
+        mock_github(
+            &mut server,
+            json!([
+                { "email": "antigo@example.com", "primary": false, "verified": true },
+                { "email": "usuaria@example.com", "primary": true, "verified": true }
+            ]),
+        )
+        .await;
+
+        let user = create_test_user("Usuária", "usuaria@example.com");
+        let user_id = user.id;
+        let repository = repository_linking(user, "usuaria@example.com");
+
+        let service = create_service(vec![github_provider(&server)], repository);
+        let response = authenticated(service.login("github", "codigo", "state-valido").await.unwrap());
+
+        assert_eq!(response.user.id, user_id);
+    }
+
+    #[tokio::test]
+    async fn test_github_login_skips_unverified_primary_email() {
+        let mut server = Server::new_async().await;
+        mock_github(
+            &mut server,
+            json!([
+                { "email": "nao-confirmado@example.com", "primary": true, "verified": false },
+                { "email": "usuaria@example.com", "primary": false, "verified": true }
+            ]),
+        )
+        .await;
+
+        let user = create_test_user("Usuária", "usuaria@example.com");
+        let user_id = user.id;
+        let repository = repository_linking(user, "usuaria@example.com");
+
+        let service = create_service(vec![github_provider(&server)], repository);
+        let response = authenticated(service.login("github", "codigo", "state-valido").await.unwrap());
+
+        assert_eq!(response.user.id, user_id);
+    }
+}
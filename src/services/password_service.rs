@@ -3,9 +3,11 @@ use std::sync::Arc;
 
 use argon2::{
     password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
-    Argon2,
+    Algorithm, Argon2, Params, Version,
 };
+use tokio::sync::Semaphore;
 
+use crate::config::Config;
 use crate::error::AppError;
 
 /// Interface para serviços de gerenciamento de senha
@@ -15,46 +17,139 @@ use crate::error::AppError;
 #[async_trait]
 pub trait PasswordService: Send + Sync + 'static {
     /// Gera um hash para uma senha
-    fn hash_password(&self, password: &str) -> Result<String, AppError>;
+    async fn hash_password(&self, password: &str) -> Result<String, AppError>;
 
     /// Verifica se uma senha corresponde a um hash
-    fn verify_password(&self, password: &str, hash: &str) -> Result<bool, AppError>;
+    async fn verify_password(&self, password: &str, hash: &str) -> Result<bool, AppError>;
+
+    /// Indica se o hash foi gerado com um algoritmo ou parâmetros diferentes
+    /// dos atuais e deve ser refeito no próximo login
+    fn needs_rehash(&self, hash: &str) -> bool;
 }
 
-/// Implementação do PasswordService usando Argon2
-pub struct Argon2PasswordService;
+/// Implementação do PasswordService usando Argon2id
+///
+/// O cálculo do hash é feito no pool de threads bloqueantes do Tokio, para não
+/// ocupar as threads que atendem as requisições. Como cada cálculo aloca a
+/// memória configurada, o número de cálculos simultâneos é limitado.
+pub struct Argon2PasswordService {
+    params: Params,
+    permits: Arc<Semaphore>,
+}
 
 impl Argon2PasswordService {
+    /// Serviço com os parâmetros padrão do Argon2 e um cálculo por CPU
     pub fn new() -> Self {
-        Self {}
+        Self::with_params(Params::DEFAULT, default_concurrency())
+    }
+
+    pub fn with_params(params: Params, max_concurrency: usize) -> Self {
+        Self {
+            params,
+            permits: Arc::new(Semaphore::new(max_concurrency.max(1))),
+        }
+    }
+
+    fn argon2(&self) -> Argon2<'static> {
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, self.params.clone())
+    }
+
+    // Executa o cálculo no pool bloqueante, respeitando o limite de cálculos simultâneos
+    async fn run_blocking<T, F>(&self, task: F) -> Result<T, AppError>
+    where
+        T: Send + 'static,
+        F: FnOnce(Argon2<'static>) -> Result<T, AppError> + Send + 'static,
+    {
+        let _permit = self
+            .permits
+            .acquire()
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Erro ao aguardar o cálculo do hash: {}", e)))?;
+
+        let argon2 = self.argon2();
+        tokio::task::spawn_blocking(move || task(argon2))
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Erro ao calcular hash: {}", e)))?
     }
 }
 
+impl Default for Argon2PasswordService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn default_concurrency() -> usize {
+    std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1)
+}
+
 #[async_trait]
 impl PasswordService for Argon2PasswordService {
-    fn hash_password(&self, password: &str) -> Result<String, AppError> {
-        let salt = SaltString::generate(&mut OsRng);
-        let argon2 = Argon2::default();
+    async fn hash_password(&self, password: &str) -> Result<String, AppError> {
+        let password = password.to_string();
 
-        argon2
-            .hash_password(password.as_bytes(), &salt)
-            .map(|hash| hash.to_string())
-            .map_err(|e| AppError::InternalServerError(format!("Erro ao gerar hash: {}", e)))
+        self.run_blocking(move |argon2| {
+            let salt = SaltString::generate(&mut OsRng);
+
+            argon2
+                .hash_password(password.as_bytes(), &salt)
+                .map(|hash| hash.to_string())
+                .map_err(|e| AppError::InternalServerError(format!("Erro ao gerar hash: {}", e)))
+        })
+        .await
     }
 
-    fn verify_password(&self, password: &str, hash: &str) -> Result<bool, AppError> {
-        let parsed_hash = PasswordHash::new(hash)
+    async fn verify_password(&self, password: &str, hash: &str) -> Result<bool, AppError> {
+        // Hashes malformados são rejeitados antes de ocupar o pool bloqueante
+        PasswordHash::new(hash)
             .map_err(|e| AppError::InternalServerError(format!("Erro ao analisar hash: {}", e)))?;
 
-        Ok(Argon2::default()
-            .verify_password(password.as_bytes(), &parsed_hash)
-            .is_ok())
+        let password = password.to_string();
+        let hash = hash.to_string();
+
+        self.run_blocking(move |argon2| {
+            let parsed_hash = PasswordHash::new(&hash)
+                .map_err(|e| AppError::InternalServerError(format!("Erro ao analisar hash: {}", e)))?;
+
+            // A verificação usa os parâmetros gravados no próprio hash
+            Ok(argon2.verify_password(password.as_bytes(), &parsed_hash).is_ok())
+        })
+        .await
+    }
+
+    fn needs_rehash(&self, hash: &str) -> bool {
+        let Ok(parsed_hash) = PasswordHash::new(hash) else {
+            return false;
+        };
+
+        if parsed_hash.algorithm != Algorithm::Argon2id.ident()
+            || parsed_hash.version != Some(Version::V0x13.into())
+        {
+            return true;
+        }
+
+        match Params::try_from(&parsed_hash) {
+            Ok(params) => {
+                params.m_cost() != self.params.m_cost()
+                    || params.t_cost() != self.params.t_cost()
+                    || params.p_cost() != self.params.p_cost()
+            }
+            Err(_) => true,
+        }
     }
 }
 
 /// Factory para criar instâncias do PasswordService
 ///
-/// Permite flexibilidade para mudar a implementação no futuro
-pub fn create_password_service() -> Arc<dyn PasswordService> {
-    Arc::new(Argon2PasswordService::new())
+/// Os parâmetros do Argon2 e o limite de cálculos simultâneos vêm da configuração
+pub fn create_password_service(config: &Config) -> Result<Arc<dyn PasswordService>, String> {
+    let params = Params::new(
+        config.argon2_memory_kib,
+        config.argon2_iterations,
+        config.argon2_parallelism,
+        None,
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(Arc::new(Argon2PasswordService::with_params(params, config.password_hash_concurrency)))
 }
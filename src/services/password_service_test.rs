@@ -1,4 +1,7 @@
+use argon2::Params;
+
 use crate::error::AppError;
+use crate::services::auth_service_test::create_test_config;
 use crate::services::password_service::{
     create_password_service, Argon2PasswordService, PasswordService,
 };
@@ -7,14 +10,14 @@ use crate::services::password_service::{
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_hash_password_success() {
+    #[tokio::test]
+    async fn test_hash_password_success() {
         // Arrange
         let service = Argon2PasswordService::new();
         let password = "senha123";
 
         // Act
-        let result = service.hash_password(password);
+        let result = service.hash_password(password).await;
 
         // Assert
         assert!(
@@ -29,17 +32,17 @@ mod tests {
         );
     }
 
-    #[test]
-    fn test_verify_password_success() {
+    #[tokio::test]
+    async fn test_verify_password_success() {
         // Arrange
         let service = Argon2PasswordService::new();
         let password = "senha123";
 
         // Primeiro, gerar um hash da senha
-        let hash = service.hash_password(password).unwrap();
+        let hash = service.hash_password(password).await.unwrap();
 
         // Act
-        let result = service.verify_password(password, &hash);
+        let result = service.verify_password(password, &hash).await;
 
         // Assert
         assert!(result.is_ok(), "A verificação deveria ser bem-sucedida");
@@ -49,18 +52,18 @@ mod tests {
         );
     }
 
-    #[test]
-    fn test_verify_password_with_wrong_password() {
+    #[tokio::test]
+    async fn test_verify_password_with_wrong_password() {
         // Arrange
         let service = Argon2PasswordService::new();
         let correct_password = "senha123";
         let wrong_password = "senha456";
 
         // Primeiro, gerar um hash da senha correta
-        let hash = service.hash_password(correct_password).unwrap();
+        let hash = service.hash_password(correct_password).await.unwrap();
 
         // Act
-        let result = service.verify_password(wrong_password, &hash);
+        let result = service.verify_password(wrong_password, &hash).await;
 
         // Assert
         assert!(
@@ -73,15 +76,15 @@ mod tests {
         );
     }
 
-    #[test]
-    fn test_verify_password_with_invalid_hash() {
+    #[tokio::test]
+    async fn test_verify_password_with_invalid_hash() {
         // Arrange
         let service = Argon2PasswordService::new();
         let password = "senha123";
         let invalid_hash = "hash_invalido";
 
         // Act
-        let result = service.verify_password(password, invalid_hash);
+        let result = service.verify_password(password, invalid_hash).await;
 
         // Assert
         assert!(
@@ -100,27 +103,26 @@ mod tests {
         }
     }
 
-    #[test]
-    fn test_create_password_service() {
+    #[tokio::test]
+    async fn test_create_password_service() {
         // Act
-        let _service = create_password_service();
+        let service = create_password_service(&create_test_config()).unwrap();
 
         // Assert
-        // Verificamos se a função factory retorna corretamente um serviço
-        // A verificação é implícita, pois se não retornasse um PasswordService,
-        // o código não compilaria
-        assert!(true, "O serviço foi criado com sucesso");
+        // Verificamos se a função factory retorna um serviço funcional
+        let hash = service.hash_password("senha123").await;
+        assert!(hash.is_ok(), "O serviço foi criado com sucesso");
     }
 
-    #[test]
-    fn test_hashed_passwords_are_different() {
+    #[tokio::test]
+    async fn test_hashed_passwords_are_different() {
         // Arrange
         let service = Argon2PasswordService::new();
         let password = "senha123";
 
         // Act
-        let hash1 = service.hash_password(password).unwrap();
-        let hash2 = service.hash_password(password).unwrap();
+        let hash1 = service.hash_password(password).await.unwrap();
+        let hash2 = service.hash_password(password).await.unwrap();
 
         // Assert
         assert_ne!(
@@ -128,4 +130,49 @@ mod tests {
             "Hashes da mesma senha devem ser diferentes (salt diferente)"
         );
     }
+
+    #[tokio::test]
+    async fn test_needs_rehash_when_params_change() {
+        // Arrange
+        let old_service = Argon2PasswordService::with_params(Params::new(8 * 1024, 1, 1, None).unwrap(), 1);
+        let service = Argon2PasswordService::new();
+
+        let old_hash = old_service.hash_password("senha123").await.unwrap();
+        let current_hash = service.hash_password("senha123").await.unwrap();
+
+        // Assert
+        assert!(service.needs_rehash(&old_hash), "Hash com parâmetros antigos deveria ser refeito");
+        assert!(!service.needs_rehash(&current_hash), "Hash com os parâmetros atuais não precisa ser refeito");
+        assert!(!old_service.needs_rehash(&old_hash));
+
+        // Hashes antigos continuam válidos para a verificação
+        assert!(service.verify_password("senha123", &old_hash).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_needs_rehash_for_other_algorithms() {
+        let service = Argon2PasswordService::new();
+        let argon2i = argon2::Argon2::new(argon2::Algorithm::Argon2i, argon2::Version::V0x13, Params::DEFAULT);
+        let salt = argon2::password_hash::SaltString::generate(&mut argon2::password_hash::rand_core::OsRng);
+        let hash = argon2::PasswordHasher::hash_password(&argon2i, b"senha123", &salt).unwrap().to_string();
+
+        assert!(service.needs_rehash(&hash));
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_hashes_respect_the_limit() {
+        // Mesmo com um único cálculo por vez, todas as requisições são atendidas
+        let service = std::sync::Arc::new(Argon2PasswordService::with_params(Params::new(8 * 1024, 1, 1, None).unwrap(), 1));
+
+        let tasks: Vec<_> = (0..4)
+            .map(|i| {
+                let service = service.clone();
+                tokio::spawn(async move { service.hash_password(&format!("senha{}", i)).await })
+            })
+            .collect();
+
+        for task in tasks {
+            assert!(task.await.unwrap().is_ok());
+        }
+    }
 }
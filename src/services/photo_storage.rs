@@ -0,0 +1,95 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::config::Config;
+use crate::error::AppError;
+use crate::i18n::messages;
+
+/// Interface para armazenamento do conteúdo das fotos dos exemplares
+///
+/// O banco de dados guarda apenas os metadados e a chave de cada foto;
+/// os bytes ficam a cargo da implementação deste trait.
+#[async_trait]
+pub trait PhotoStorage: Send + Sync + 'static {
+    /// Grava o conteúdo de uma foto sob a chave informada
+    async fn save(&self, key: &str, content: &[u8]) -> Result<(), AppError>;
+
+    /// Lê o conteúdo de uma foto previamente gravada
+    async fn load(&self, key: &str) -> Result<Vec<u8>, AppError>;
+
+    /// Remove uma foto; remover uma chave inexistente não é erro
+    async fn delete(&self, key: &str) -> Result<(), AppError>;
+}
+
+/// Implementação do PhotoStorage que grava as fotos em um diretório local
+pub struct LocalPhotoStorage {
+    root: PathBuf,
+}
+
+impl LocalPhotoStorage {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    // Resolve o caminho de uma chave, recusando chaves que escapem do diretório raiz
+    fn path_for(&self, key: &str) -> Result<PathBuf, AppError> {
+        let is_safe = !key.is_empty()
+            && key
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+            && !key.starts_with('.');
+
+        if !is_safe {
+            return Err(AppError::ValidationError(messages::PHOTO_KEY_INVALID.format(&[&key])));
+        }
+
+        Ok(Path::new(&self.root).join(key))
+    }
+}
+
+#[async_trait]
+impl PhotoStorage for LocalPhotoStorage {
+    async fn save(&self, key: &str, content: &[u8]) -> Result<(), AppError> {
+        let path = self.path_for(key)?;
+
+        tokio::fs::create_dir_all(&self.root).await.map_err(|e| {
+            AppError::InternalServerError(format!("Erro ao criar diretório de fotos: {}", e))
+        })?;
+
+        tokio::fs::write(&path, content)
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Erro ao gravar foto: {}", e)))
+    }
+
+    async fn load(&self, key: &str) -> Result<Vec<u8>, AppError> {
+        let path = self.path_for(key)?;
+
+        tokio::fs::read(&path).await.map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                AppError::NotFoundError(messages::PHOTO_NOT_FOUND.text())
+            } else {
+                AppError::InternalServerError(format!("Erro ao ler foto: {}", e))
+            }
+        })
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), AppError> {
+        let path = self.path_for(key)?;
+
+        match tokio::fs::remove_file(&path).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(AppError::InternalServerError(format!(
+                "Erro ao remover foto: {}",
+                e
+            ))),
+        }
+    }
+}
+
+/// Factory para criar o armazenamento de fotos a partir da configuração
+pub fn create_photo_storage(config: &Config) -> Arc<dyn PhotoStorage> {
+    Arc::new(LocalPhotoStorage::new(&config.photo_storage_dir))
+}
@@ -0,0 +1,89 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use crate::error::AppError;
+use crate::i18n::messages;
+use crate::repositories::rate_limit_repository::{RateLimitRepository, RateLimitWindow};
+
+/// Quantidade máxima de tentativas em uma janela de tempo
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    pub max_attempts: u32,
+    pub window: Duration,
+}
+
+impl RateLimit {
+    pub const fn new(max_attempts: u32, window: Duration) -> Self {
+        Self { max_attempts, window }
+    }
+}
+
+/// Requisições de login por IP
+pub const LOGIN_LIMIT: RateLimit = RateLimit::new(30, Duration::from_secs(60));
+/// Cadastros por IP
+pub const REGISTER_LIMIT: RateLimit = RateLimit::new(30, Duration::from_secs(60 * 60));
+/// Cadastros por email
+pub const REGISTER_EMAIL_LIMIT: RateLimit = RateLimit::new(5, Duration::from_secs(60 * 60));
+/// Buscas no Google Books por usuário
+pub const BOOK_SEARCH_LIMIT: RateLimit = RateLimit::new(60, Duration::from_secs(60));
+/// Logins sem sucesso por conta antes do bloqueio temporário
+pub const FAILED_LOGIN_LIMIT: RateLimit = RateLimit::new(5, Duration::from_secs(15 * 60));
+/// Códigos de verificação em duas etapas inválidos por conta antes do bloqueio temporário
+pub const FAILED_TWO_FACTOR_LIMIT: RateLimit = RateLimit::new(5, Duration::from_secs(15 * 60));
+
+#[async_trait]
+pub trait RateLimitService: Send + Sync + 'static {
+    /// Registra uma tentativa; retorna `TooManyRequests` se ela ultrapassar o limite
+    async fn hit(&self, key: &str, limit: RateLimit) -> Result<(), AppError>;
+    /// Retorna `TooManyRequests` se o limite já foi atingido, sem registrar tentativa
+    async fn check(&self, key: &str, limit: RateLimit) -> Result<(), AppError>;
+    /// Devolve uma tentativa registrada por `hit` que acabou não contando
+    async fn release(&self, key: &str) -> Result<(), AppError>;
+    /// Zera as tentativas da chave
+    async fn reset(&self, key: &str) -> Result<(), AppError>;
+}
+
+pub struct RateLimitServiceImpl {
+    rate_limit_repository: Arc<dyn RateLimitRepository>,
+}
+
+impl RateLimitServiceImpl {
+    pub fn new(rate_limit_repository: Arc<dyn RateLimitRepository>) -> Self {
+        Self { rate_limit_repository }
+    }
+
+    // Erro com o tempo de espera arredondado para cima, em segundos
+    fn limited(window: RateLimitWindow) -> AppError {
+        let retry_after = window.resets_in.as_secs_f64().ceil().max(1.0) as u64;
+        AppError::TooManyRequests(messages::TOO_MANY_REQUESTS.format(&[&retry_after]), retry_after)
+    }
+}
+
+#[async_trait]
+impl RateLimitService for RateLimitServiceImpl {
+    async fn hit(&self, key: &str, limit: RateLimit) -> Result<(), AppError> {
+        let window = self.rate_limit_repository.increment(key, limit.window).await?;
+        if window.hits > limit.max_attempts {
+            return Err(Self::limited(window));
+        }
+
+        Ok(())
+    }
+
+    async fn check(&self, key: &str, limit: RateLimit) -> Result<(), AppError> {
+        match self.rate_limit_repository.find(key).await? {
+            Some(window) if window.hits >= limit.max_attempts => Err(Self::limited(window)),
+            _ => Ok(()),
+        }
+    }
+
+    async fn release(&self, key: &str) -> Result<(), AppError> {
+        self.rate_limit_repository.decrement(key).await
+    }
+
+    async fn reset(&self, key: &str) -> Result<(), AppError> {
+        self.rate_limit_repository.delete(key).await
+    }
+}
@@ -0,0 +1,235 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::models::user::{CreateUserDto, LoginResponse, LoginUserDto, TokenResponse, UserResponse};
+use crate::repositories::rate_limit_repository::InMemoryRateLimitRepository;
+use crate::services::auth_service::AuthService;
+use crate::services::login_throttle::ThrottledAuthService;
+use crate::services::rate_limit_service::{
+    RateLimit, RateLimitService, RateLimitServiceImpl, FAILED_LOGIN_LIMIT, REGISTER_EMAIL_LIMIT,
+};
+use crate::services::test_mocks::MockAuthService;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_service() -> Arc<dyn RateLimitService> {
+        Arc::new(RateLimitServiceImpl::new(Arc::new(InMemoryRateLimitRepository::new())))
+    }
+
+    fn login_dto(email: &str, password: &str) -> LoginUserDto {
+        LoginUserDto {
+            email: email.to_string(),
+            password: password.to_string(),
+        }
+    }
+
+    fn token_response() -> TokenResponse {
+        let now = chrono::DateTime::from_timestamp(61, 0).unwrap().naive_utc();
+        TokenResponse {
+            access_token: "token".to_string(),
+            token_type: "Bearer".to_string(),
+            user: UserResponse {
+                id: Uuid::new_v4(),
+                name: "Teste".to_string(),
+                email: "teste@example.com".to_string(),
+                created_at: now,
+                updated_at: now,
+            },
+        }
+    }
+
+    // AuthService que aceita apenas a senha "correta"
+    fn create_auth_service() -> Arc<dyn AuthService> {
+        let mut mock = MockAuthService::new();
+        mock.expect_login().returning(|dto| {
+            if dto.password == "correta" {
+                Ok(LoginResponse::Authenticated(token_response()))
+            } else {
+                Err(AppError::InvalidCredentials)
+            }
+        });
+        Arc::new(mock)
+    }
+
+    #[tokio::test]
+    async fn test_hit_allows_attempts_up_to_the_limit() {
+        let service = create_service();
+        let limit = RateLimit::new(3, Duration::from_secs(60));
+
+        for _ in 0..3 {
+            assert!(service.hit("login:ip:127.0.0.1", limit).await.is_ok());
+        }
+
+        match service.hit("login:ip:127.0.0.1", limit).await {
+            Err(AppError::TooManyRequests(_, retry_after)) => assert!((1..=60).contains(&retry_after)),
+            other => panic!("Esperava TooManyRequests, obteve {:?}", other),
+        }
+
+        // Outras chaves não são afetadas
+        assert!(service.hit("login:ip:10.0.0.1", limit).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_check_does_not_count_attempts() {
+        let service = create_service();
+        let limit = RateLimit::new(2, Duration::from_secs(60));
+
+        for _ in 0..5 {
+            assert!(service.check("key", limit).await.is_ok());
+        }
+
+        service.hit("key", limit).await.unwrap();
+        assert!(service.check("key", limit).await.is_ok());
+        service.hit("key", limit).await.unwrap();
+        assert!(matches!(service.check("key", limit).await, Err(AppError::TooManyRequests(_, _))));
+
+        service.reset("key").await.unwrap();
+        assert!(service.check("key", limit).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_login_is_locked_after_failed_attempts() {
+        let throttled = ThrottledAuthService::new(create_auth_service(), create_service());
+
+        for _ in 0..FAILED_LOGIN_LIMIT.max_attempts {
+            let result = throttled.login(login_dto("teste@example.com", "errada")).await;
+            assert!(matches!(result, Err(AppError::InvalidCredentials)));
+        }
+
+        // Bloqueada mesmo com a senha correta, e sem diferenciar maiúsculas no email
+        let result = throttled.login(login_dto("Teste@Example.com", "correta")).await;
+        match result {
+            Err(AppError::TooManyRequests(message, retry_after)) => {
+                assert!(message.contains("bloqueado"));
+                assert!(retry_after > 0);
+            }
+            other => panic!("Esperava o bloqueio do login, obteve {:?}", other),
+        }
+
+        // Outras contas continuam podendo entrar
+        assert!(throttled.login(login_dto("outra@example.com", "correta")).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_successful_login_resets_failed_attempts() {
+        let throttled = ThrottledAuthService::new(create_auth_service(), create_service());
+
+        for _ in 0..FAILED_LOGIN_LIMIT.max_attempts - 1 {
+            let _ = throttled.login(login_dto("teste@example.com", "errada")).await;
+        }
+        assert!(throttled.login(login_dto("teste@example.com", "correta")).await.is_ok());
+
+        for _ in 0..FAILED_LOGIN_LIMIT.max_attempts - 1 {
+            let result = throttled.login(login_dto("teste@example.com", "errada")).await;
+            assert!(matches!(result, Err(AppError::InvalidCredentials)));
+        }
+        assert!(throttled.login(login_dto("teste@example.com", "correta")).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_only_wrong_passwords_count_towards_the_lockout() {
+        // AuthService que recusa emails inválidos antes de verificar a senha
+        let mut mock = MockAuthService::new();
+        mock.expect_login().returning(|dto| {
+            if !dto.email.contains('@') {
+                Err(AppError::ValidationError("Email inválido".to_string()))
+            } else if dto.password == "suspensa" {
+                Err(AppError::Forbidden("Conta suspensa".to_string()))
+            } else {
+                Err(AppError::InvalidCredentials)
+            }
+        });
+        let throttled = ThrottledAuthService::new(Arc::new(mock), create_service());
+
+        // Requisições que não chegam a errar a senha não bloqueiam a conta
+        for _ in 0..FAILED_LOGIN_LIMIT.max_attempts * 2 {
+            let result = throttled.login(login_dto("teste@example.com", "suspensa")).await;
+            assert!(matches!(result, Err(AppError::Forbidden(_))));
+            let result = throttled.login(login_dto("teste", "errada")).await;
+            assert!(matches!(result, Err(AppError::ValidationError(_))));
+        }
+
+        // Senhas erradas continuam contando
+        for _ in 0..FAILED_LOGIN_LIMIT.max_attempts {
+            let result = throttled.login(login_dto("teste@example.com", "errada")).await;
+            assert!(matches!(result, Err(AppError::InvalidCredentials)));
+        }
+        let result = throttled.login(login_dto("teste@example.com", "suspensa")).await;
+        assert!(matches!(result, Err(AppError::TooManyRequests(_, _))));
+    }
+
+    // AuthService que demora a verificar a senha e conta as verificações
+    struct SlowAuthService {
+        verified: AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl AuthService for SlowAuthService {
+        async fn register(&self, _user_dto: CreateUserDto) -> Result<UserResponse, AppError> {
+            unimplemented!()
+        }
+
+        async fn login(&self, _login_dto: LoginUserDto) -> Result<LoginResponse, AppError> {
+            self.verified.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            Err(AppError::InvalidCredentials)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_logins_do_not_bypass_the_lockout() {
+        let auth_service = Arc::new(SlowAuthService {
+            verified: AtomicUsize::new(0),
+        });
+        let throttled = Arc::new(ThrottledAuthService::new(auth_service.clone(), create_service()));
+
+        // Todas as tentativas chegam antes que qualquer uma termine
+        let attempts: Vec<_> = (0..20)
+            .map(|_| {
+                let throttled = throttled.clone();
+                tokio::spawn(async move { throttled.login(login_dto("teste@example.com", "errada")).await })
+            })
+            .collect();
+
+        let mut locked = 0;
+        for attempt in attempts {
+            if let Err(AppError::TooManyRequests(_, _)) = attempt.await.unwrap() {
+                locked += 1;
+            }
+        }
+
+        let max_attempts = FAILED_LOGIN_LIMIT.max_attempts as usize;
+        assert_eq!(auth_service.verified.load(Ordering::SeqCst), max_attempts);
+        assert_eq!(locked, 20 - max_attempts);
+    }
+
+    #[tokio::test]
+    async fn test_register_is_limited_by_email() {
+        let mut mock = MockAuthService::new();
+        mock.expect_register()
+            .returning(|_| Err(AppError::ValidationError("Email já cadastrado".to_string())));
+        let throttled = ThrottledAuthService::new(Arc::new(mock), create_service());
+        let user = |email: &str| CreateUserDto {
+            name: "Teste".to_string(),
+            email: email.to_string(),
+            password: "Senha@123".to_string(),
+        };
+
+        for _ in 0..REGISTER_EMAIL_LIMIT.max_attempts {
+            let result = throttled.register(user("teste@example.com")).await;
+            assert!(matches!(result, Err(AppError::ValidationError(_))));
+        }
+
+        // O mesmo email, com outra grafia, atinge o limite; outros emails não
+        let result = throttled.register(user(" Teste@Example.com")).await;
+        assert!(matches!(result, Err(AppError::TooManyRequests(_, _))));
+        let result = throttled.register(user("outra@example.com")).await;
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+}
@@ -0,0 +1,283 @@
+use serde_json::Value;
+
+use crate::error::AppError;
+use crate::i18n::messages;
+use crate::models::shelf::{ShelfEntry, ShelfFormat, ShelfList};
+
+/// Colunas do CSV próprio da aplicação, na ordem em que são exportadas
+const CSV_COLUMNS: [&str; 9] = [
+    "list", "google_id", "isbn", "title", "author", "condition", "language", "notes", "priority",
+];
+
+/// Colunas exportadas no formato do Goodreads
+const GOODREADS_COLUMNS: [&str; 7] = [
+    "Title", "Author", "ISBN", "ISBN13", "Bookshelves", "Exclusive Shelf", "Private Notes",
+];
+
+/// Estante do Goodreads que corresponde à lista de desejados
+const GOODREADS_WANTED_SHELF: &str = "to-read";
+/// Estante do Goodreads usada para os livros possuídos na exportação
+const GOODREADS_OFFERED_SHELF: &str = "read";
+
+/// Linha interpretada de um arquivo; linhas inválidas trazem o motivo do erro
+pub type ParsedEntry = Result<ShelfEntry, String>;
+
+/// Descobre o formato de um arquivo quando ele não é informado na requisição
+///
+/// Conteúdo iniciado por `[` é JSON; um CSV com a coluna "Exclusive Shelf" é
+/// uma exportação do Goodreads.
+pub fn detect_format(content: &str) -> ShelfFormat {
+    let content = content.trim_start_matches('\u{feff}').trim_start();
+    if content.starts_with('[') {
+        return ShelfFormat::Json;
+    }
+
+    let header = content.lines().next().unwrap_or_default();
+    if header.contains("Exclusive Shelf") {
+        ShelfFormat::Goodreads
+    } else {
+        ShelfFormat::Csv
+    }
+}
+
+/// Interpreta o conteúdo de um arquivo de estante
+///
+/// Erros que impedem a leitura do arquivo inteiro (JSON malformado, cabeçalho
+/// sem as colunas necessárias) são retornados como `ValidationError`; erros de
+/// uma linha ficam no item correspondente.
+pub fn parse_entries(format: ShelfFormat, content: &str) -> Result<Vec<ParsedEntry>, AppError> {
+    let content = content.trim_start_matches('\u{feff}');
+    match format {
+        ShelfFormat::Csv => parse_csv(content),
+        ShelfFormat::Goodreads => parse_goodreads(content),
+        ShelfFormat::Json => parse_json(content),
+    }
+}
+
+/// Gera o conteúdo de um arquivo de estante
+pub fn write_entries(format: ShelfFormat, entries: &[ShelfEntry]) -> Result<String, AppError> {
+    match format {
+        ShelfFormat::Csv => Ok(write_csv_rows(
+            &CSV_COLUMNS,
+            entries.iter().map(|entry| {
+                vec![
+                    entry.list.unwrap_or_default().as_str().to_string(),
+                    entry.google_id.clone().unwrap_or_default(),
+                    entry.isbn.clone().unwrap_or_default(),
+                    entry.title.clone().unwrap_or_default(),
+                    entry.author.clone().unwrap_or_default(),
+                    entry.condition.clone().unwrap_or_default(),
+                    entry.language.clone().unwrap_or_default(),
+                    entry.notes.clone().unwrap_or_default(),
+                    entry.priority.map(|p| p.to_string()).unwrap_or_default(),
+                ]
+            }),
+        )),
+        ShelfFormat::Goodreads => Ok(write_csv_rows(
+            &GOODREADS_COLUMNS,
+            entries.iter().map(|entry| {
+                let isbn = entry.isbn.clone().unwrap_or_default();
+                let (isbn10, isbn13) = if isbn.len() == 13 { (String::new(), isbn) } else { (isbn, String::new()) };
+                let shelf = match entry.list.unwrap_or_default() {
+                    ShelfList::Wanted => GOODREADS_WANTED_SHELF,
+                    ShelfList::Offered => GOODREADS_OFFERED_SHELF,
+                };
+                vec![
+                    entry.title.clone().unwrap_or_default(),
+                    entry.author.clone().unwrap_or_default(),
+                    goodreads_isbn(&isbn10),
+                    goodreads_isbn(&isbn13),
+                    shelf.to_string(),
+                    shelf.to_string(),
+                    entry.notes.clone().unwrap_or_default(),
+                ]
+            }),
+        )),
+        ShelfFormat::Json => serde_json::to_string_pretty(entries)
+            .map_err(|e| AppError::InternalServerError(format!("Falha ao gerar o JSON: {}", e))),
+    }
+}
+
+fn parse_json(content: &str) -> Result<Vec<ParsedEntry>, AppError> {
+    let values: Vec<Value> = serde_json::from_str(content).map_err(|e| {
+        AppError::ValidationError(messages::SHELF_JSON_INVALID.format(&[&e]))
+    })?;
+
+    Ok(values
+        .into_iter()
+        .map(|value| serde_json::from_value::<ShelfEntry>(value).map_err(|e| messages::SHELF_ROW_INVALID.format(&[&e])))
+        .collect())
+}
+
+fn parse_csv(content: &str) -> Result<Vec<ParsedEntry>, AppError> {
+    let mut records = parse_csv_records(content)?.into_iter();
+    let header: Vec<String> = records
+        .next()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|column| column.trim().to_lowercase())
+        .collect();
+
+    if !header.iter().any(|c| c == "google_id" || c == "isbn" || c == "title") {
+        return Err(AppError::ValidationError(
+            messages::SHELF_CSV_COLUMNS_MISSING.text(),
+        ));
+    }
+
+    Ok(records
+        .map(|record| {
+            let field = |name: &str| column_value(&header, &record, name);
+
+            let list = field("list").map(|v| v.to_lowercase().parse::<ShelfList>()).transpose();
+            let priority = field("priority")
+                .map(|v| v.parse::<i16>().map_err(|_| messages::SHELF_PRIORITY_INVALID.format(&[&v])))
+                .transpose();
+
+            Ok(ShelfEntry {
+                list: list.map_err(|e| e.public_message())?,
+                google_id: field("google_id"),
+                isbn: field("isbn").map(|v| normalize_isbn(&v)),
+                title: field("title"),
+                author: field("author"),
+                condition: field("condition"),
+                language: field("language"),
+                notes: field("notes"),
+                priority: priority?,
+            })
+        })
+        .collect())
+}
+
+fn parse_goodreads(content: &str) -> Result<Vec<ParsedEntry>, AppError> {
+    let mut records = parse_csv_records(content)?.into_iter();
+    let header: Vec<String> = records
+        .next()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|column| column.trim().to_string())
+        .collect();
+
+    if !header.iter().any(|c| c == "Title") {
+        return Err(AppError::ValidationError(
+            messages::SHELF_GOODREADS_TITLE_MISSING.text(),
+        ));
+    }
+
+    Ok(records
+        .map(|record| {
+            let field = |name: &str| column_value(&header, &record, name);
+
+            // O Goodreads exporta o ISBN como fórmula de planilha: ="9780000000000"
+            let isbn_column = |name: &str| field(name).map(|v| normalize_isbn(&v)).filter(|v| !v.is_empty());
+            let isbn = isbn_column("ISBN13").or_else(|| isbn_column("ISBN"));
+
+            let list = match field("Exclusive Shelf").as_deref() {
+                Some(GOODREADS_WANTED_SHELF) => ShelfList::Wanted,
+                _ => ShelfList::Offered,
+            };
+
+            Ok(ShelfEntry {
+                list: Some(list),
+                isbn,
+                title: field("Title"),
+                author: field("Author"),
+                notes: field("Private Notes"),
+                ..ShelfEntry::default()
+            })
+        })
+        .collect())
+}
+
+// Valor de uma coluna da linha, ignorando campos vazios
+fn column_value(header: &[String], record: &[String], name: &str) -> Option<String> {
+    let index = header.iter().position(|column| column == name)?;
+    record
+        .get(index)
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+// Mantém apenas os dígitos (e o X final dos ISBN-10)
+fn normalize_isbn(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| c.is_ascii_digit() || *c == 'X' || *c == 'x')
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+fn goodreads_isbn(isbn: &str) -> String {
+    format!("=\"{}\"", isbn)
+}
+
+/// Lê os registros de um CSV (RFC 4180), aceitando campos entre aspas com
+/// vírgulas, aspas duplicadas e quebras de linha
+fn parse_csv_records(content: &str) -> Result<Vec<Vec<String>>, AppError> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    chars.next();
+                }
+                '"' => in_quotes = false,
+                _ => field.push(c),
+            }
+            continue;
+        }
+
+        match c {
+            '"' => in_quotes = true,
+            ',' => record.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' | '\r' => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            _ => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(AppError::ValidationError(
+            messages::SHELF_CSV_UNCLOSED_QUOTE.text(),
+        ));
+    }
+
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+
+    // Linhas em branco não são registros
+    records.retain(|r| !(r.len() == 1 && r[0].trim().is_empty()));
+
+    Ok(records)
+}
+
+fn write_csv_rows(header: &[&str], rows: impl Iterator<Item = Vec<String>>) -> String {
+    let mut output = header.join(",");
+    output.push_str("\r\n");
+
+    for row in rows {
+        let fields: Vec<String> = row.iter().map(|field| escape_csv_field(field)).collect();
+        output.push_str(&fields.join(","));
+        output.push_str("\r\n");
+    }
+
+    output
+}
+
+fn escape_csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
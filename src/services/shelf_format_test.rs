@@ -0,0 +1,147 @@
+use crate::error::AppError;
+use crate::models::shelf::{ShelfEntry, ShelfFormat, ShelfList};
+use crate::services::shelf_format::{detect_format, parse_entries, write_entries};
+
+fn offered_entry() -> ShelfEntry {
+    ShelfEntry {
+        list: Some(ShelfList::Offered),
+        google_id: Some("abc123".to_string()),
+        isbn: Some("9788535914849".to_string()),
+        title: Some("Dom Casmurro, edição \"especial\"".to_string()),
+        author: Some("Machado de Assis".to_string()),
+        condition: Some("good".to_string()),
+        language: Some("pt".to_string()),
+        notes: Some("Capa gasta\nsem anotações".to_string()),
+        priority: None,
+    }
+}
+
+fn wanted_entry() -> ShelfEntry {
+    ShelfEntry {
+        list: Some(ShelfList::Wanted),
+        title: Some("O Cortiço".to_string()),
+        author: Some("Aluísio Azevedo".to_string()),
+        priority: Some(5),
+        ..ShelfEntry::default()
+    }
+}
+
+fn parse_ok(format: ShelfFormat, content: &str) -> Vec<ShelfEntry> {
+    parse_entries(format, content)
+        .unwrap()
+        .into_iter()
+        .map(|entry| entry.unwrap())
+        .collect()
+}
+
+#[test]
+fn test_detect_format() {
+    assert_eq!(detect_format("  [{\"title\": \"x\"}]"), ShelfFormat::Json);
+    assert_eq!(
+        detect_format("Book Id,Title,Author,ISBN,ISBN13,Exclusive Shelf\n1,x,y,,,read"),
+        ShelfFormat::Goodreads
+    );
+    assert_eq!(detect_format("\u{feff}list,title\noffered,x"), ShelfFormat::Csv);
+}
+
+#[test]
+fn test_csv_round_trip() {
+    let entries = vec![offered_entry(), wanted_entry()];
+
+    let content = write_entries(ShelfFormat::Csv, &entries).unwrap();
+
+    assert_eq!(parse_ok(ShelfFormat::Csv, &content), entries);
+}
+
+#[test]
+fn test_json_round_trip() {
+    let entries = vec![offered_entry(), wanted_entry()];
+
+    let content = write_entries(ShelfFormat::Json, &entries).unwrap();
+
+    assert_eq!(parse_ok(ShelfFormat::Json, &content), entries);
+}
+
+#[test]
+fn test_csv_with_quoted_fields_and_missing_columns() {
+    let content = "Title,ISBN\r\n\"Memórias, Póstumas\",978-85-359-0277-1\r\n\r\n\"Com \"\"aspas\"\"\",\n";
+
+    let entries = parse_ok(ShelfFormat::Csv, content);
+
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].title.as_deref(), Some("Memórias, Póstumas"));
+    assert_eq!(entries[0].isbn.as_deref(), Some("9788535902771"));
+    assert_eq!(entries[0].list, None);
+    assert_eq!(entries[1].title.as_deref(), Some("Com \"aspas\""));
+    assert_eq!(entries[1].isbn, None);
+}
+
+#[test]
+fn test_csv_invalid_row_is_reported_without_failing_the_file() {
+    let content = "title,list,priority\nA,wanted,alta\nB,emprestado,\nC,wanted,2\n";
+
+    let entries = parse_entries(ShelfFormat::Csv, content).unwrap();
+
+    assert_eq!(entries.len(), 3);
+    assert!(entries[0].as_ref().unwrap_err().contains("Prioridade inválida"));
+    assert!(entries[1].as_ref().unwrap_err().contains("Lista desconhecida"));
+    assert_eq!(entries[2].as_ref().unwrap().priority, Some(2));
+}
+
+#[test]
+fn test_csv_without_identifying_columns_fails() {
+    let result = parse_entries(ShelfFormat::Csv, "author,notes\nAlguém,nada\n");
+
+    assert!(matches!(result, Err(AppError::ValidationError(_))));
+}
+
+#[test]
+fn test_csv_with_unclosed_quote_fails() {
+    let result = parse_entries(ShelfFormat::Csv, "title\n\"Sem fim\n");
+
+    assert!(matches!(result, Err(AppError::ValidationError(_))));
+}
+
+#[test]
+fn test_parse_goodreads_export() {
+    let content = "Book Id,Title,Author,ISBN,ISBN13,My Rating,Exclusive Shelf,Private Notes\n\
+                   1,Grande Sertão,Guimarães Rosa,\"=\"\"8520923255\"\"\",\"=\"\"9788520923252\"\"\",5,read,Autografado\n\
+                   2,Vidas Secas,Graciliano Ramos,\"=\"\"\"\"\",\"=\"\"\"\"\",0,to-read,\n";
+
+    let entries = parse_ok(ShelfFormat::Goodreads, content);
+
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].list, Some(ShelfList::Offered));
+    assert_eq!(entries[0].isbn.as_deref(), Some("9788520923252"));
+    assert_eq!(entries[0].notes.as_deref(), Some("Autografado"));
+    assert_eq!(entries[1].list, Some(ShelfList::Wanted));
+    assert_eq!(entries[1].isbn, None);
+    assert_eq!(entries[1].author.as_deref(), Some("Graciliano Ramos"));
+}
+
+#[test]
+fn test_goodreads_round_trip_keeps_list_and_isbn() {
+    let entries = vec![offered_entry(), wanted_entry()];
+
+    let content = write_entries(ShelfFormat::Goodreads, &entries).unwrap();
+    let parsed = parse_ok(ShelfFormat::Goodreads, &content);
+
+    assert_eq!(detect_format(&content), ShelfFormat::Goodreads);
+    assert_eq!(parsed[0].list, Some(ShelfList::Offered));
+    assert_eq!(parsed[0].isbn, offered_entry().isbn);
+    assert_eq!(parsed[0].title, offered_entry().title);
+    assert_eq!(parsed[1].list, Some(ShelfList::Wanted));
+    assert_eq!(parsed[1].title, wanted_entry().title);
+}
+
+#[test]
+fn test_json_invalid_content() {
+    assert!(matches!(
+        parse_entries(ShelfFormat::Json, "{\"title\": \"x\"}"),
+        Err(AppError::ValidationError(_))
+    ));
+
+    let entries = parse_entries(ShelfFormat::Json, "[{\"title\": \"x\"}, {\"priority\": \"alta\"}]").unwrap();
+    assert!(entries[0].is_ok());
+    assert!(entries[1].is_err());
+}
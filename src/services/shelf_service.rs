@@ -0,0 +1,375 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::i18n::messages;
+use crate::models::book::{
+    validate_wanted_priority, BookCondition, CreateBookOfferedDto, CreateBookWantedDto, GoogleBookDto,
+    OfferedCopyAttributes, DEFAULT_WANTED_PRIORITY,
+};
+use crate::models::shelf::{ImportReport, ImportRowResult, ImportStatus, ShelfEntry, ShelfFormat, ShelfList};
+use crate::repositories::book_repository::{BookRepository, BookWithId};
+use crate::repositories::books_offered_repository::BooksOfferedRepository;
+use crate::repositories::books_wanted_repository::BooksWantedRepository;
+use crate::services::google_book_service::{is_valid_google_id, GoogleBookService};
+use crate::services::shelf_format::{detect_format, parse_entries, write_entries};
+
+/// Número máximo de linhas aceitas em uma importação
+pub const MAX_IMPORT_ROWS: usize = 500;
+
+#[async_trait]
+pub trait ShelfService: Send + Sync + 'static {
+    /// Importa um arquivo de estante, adicionando cada linha à lista indicada
+    ///
+    /// Linhas sem lista vão para `default_list`. Quando `format` não é
+    /// informado, ele é deduzido do conteúdo.
+    async fn import_shelf(
+        &self,
+        user_id: &Uuid,
+        format: Option<ShelfFormat>,
+        default_list: ShelfList,
+        content: &str,
+    ) -> Result<ImportReport, AppError>;
+    /// Exporta os livros possuídos e desejados do usuário
+    async fn export_shelf(&self, user_id: &Uuid, format: ShelfFormat) -> Result<String, AppError>;
+}
+
+pub struct ShelfServiceImpl {
+    book_repository: Arc<dyn BookRepository>,
+    books_offered_repository: Arc<dyn BooksOfferedRepository>,
+    books_wanted_repository: Arc<dyn BooksWantedRepository>,
+    google_book_service: Arc<dyn GoogleBookService>,
+}
+
+// Linha pronta para ser adicionada a uma das listas
+enum ImportTarget {
+    Offered(OfferedCopyAttributes),
+    Wanted(i16),
+}
+
+impl ShelfServiceImpl {
+    pub fn new(
+        book_repository: Arc<dyn BookRepository>,
+        books_offered_repository: Arc<dyn BooksOfferedRepository>,
+        books_wanted_repository: Arc<dyn BooksWantedRepository>,
+        google_book_service: Arc<dyn GoogleBookService>,
+    ) -> Self {
+        Self {
+            book_repository,
+            books_offered_repository,
+            books_wanted_repository,
+            google_book_service,
+        }
+    }
+
+    // Converte os campos da linha nos dados da lista de destino
+    fn import_target(list: ShelfList, entry: &ShelfEntry) -> Result<ImportTarget, String> {
+        match list {
+            ShelfList::Offered => {
+                let condition = match entry.condition.as_deref() {
+                    Some(value) => value
+                        .parse::<BookCondition>()
+                        .map_err(|_| messages::SHELF_CONDITION_INVALID.format(&[&value]))?,
+                    None => BookCondition::default(),
+                };
+                let attributes = OfferedCopyAttributes {
+                    condition,
+                    language: entry.language.clone(),
+                    notes: entry.notes.clone(),
+                    ..OfferedCopyAttributes::default()
+                };
+                attributes.validate_all().map_err(|e| e.public_message())?;
+                Ok(ImportTarget::Offered(attributes))
+            }
+            ShelfList::Wanted => {
+                let priority = entry.priority.unwrap_or(DEFAULT_WANTED_PRIORITY);
+                validate_wanted_priority(priority).map_err(|e| e.public_message())?;
+                Ok(ImportTarget::Wanted(priority))
+            }
+        }
+    }
+
+    // Identifica o livro da linha no Google Books
+    async fn find_google_book(&self, entry: &ShelfEntry) -> Result<Option<GoogleBookDto>, AppError> {
+        if let Some(google_id) = &entry.google_id {
+            return match self.google_book_service.find_book_by_id(google_id).await {
+                Ok(book) => Ok(Some(book)),
+                Err(AppError::NotFoundError(_)) => Ok(None),
+                Err(e) => Err(e),
+            };
+        }
+
+        let query = if let Some(isbn) = &entry.isbn {
+            format!("isbn:{}", encode_query_term(isbn))
+        } else if let Some(title) = &entry.title {
+            match &entry.author {
+                Some(author) => format!("intitle:{}+inauthor:{}", encode_query_term(title), encode_query_term(author)),
+                None => format!("intitle:{}", encode_query_term(title)),
+            }
+        } else {
+            return Ok(None);
+        };
+
+        Ok(self.google_book_service.search_books(&query).await?.into_iter().next())
+    }
+
+    // Obtém o livro no banco, cadastrando-o se for a primeira vez que aparece
+    async fn resolve_book(&self, entry: &ShelfEntry) -> Result<Option<BookWithId>, AppError> {
+        // Livros já cadastrados dispensam a consulta ao Google Books
+        if let Some(google_id) = &entry.google_id {
+            if let Some(book) = self.book_repository.find_by_google_id(google_id).await? {
+                return Ok(Some(book));
+            }
+        }
+
+        let Some(google_book) = self.find_google_book(entry).await? else {
+            return Ok(None);
+        };
+
+        if let Some(book) = self.book_repository.find_by_google_id(&google_book.google_id).await? {
+            return Ok(Some(book));
+        }
+
+        let id = self.book_repository.create(&google_book).await?;
+        Ok(Some(BookWithId { id, book: google_book }))
+    }
+
+    // `offered_rows` conta as linhas de possuídos já vistas por livro no arquivo
+    async fn import_entry(
+        &self,
+        user_id: &Uuid,
+        target: ImportTarget,
+        book: &BookWithId,
+        offered_rows: &mut HashMap<Uuid, usize>,
+    ) -> Result<(ImportStatus, Option<String>), AppError> {
+        let copies = self.books_offered_repository.find_copies(&book.id, user_id).await?.len();
+        let in_offered = copies > 0;
+        let in_wanted = self.books_wanted_repository.find(&book.id, user_id).await?.is_some();
+
+        match target {
+            ImportTarget::Offered(attributes) => {
+                if in_wanted {
+                    return Ok((
+                        ImportStatus::Conflict,
+                        Some(messages::SHELF_BOOK_IS_WANTED.text()),
+                    ));
+                }
+                // Cada linha representa um exemplar: a n-ésima linha do livro só
+                // cria um exemplar se o usuário tiver menos de n, de modo que
+                // reimportar a mesma estante não duplica os exemplares
+                let row = offered_rows.entry(book.id).or_default();
+                *row += 1;
+                if copies >= *row {
+                    return Ok((ImportStatus::AlreadyPresent, None));
+                }
+
+                self.books_offered_repository
+                    .create(&CreateBookOfferedDto {
+                        book_id: book.id,
+                        user_id: *user_id,
+                        attributes,
+                    })
+                    .await?;
+            }
+            ImportTarget::Wanted(priority) => {
+                if in_offered {
+                    return Ok((
+                        ImportStatus::Conflict,
+                        Some(messages::SHELF_BOOK_IS_OFFERED.text()),
+                    ));
+                }
+                if in_wanted {
+                    return Ok((ImportStatus::AlreadyPresent, None));
+                }
+
+                self.books_wanted_repository
+                    .create(&CreateBookWantedDto {
+                        book_id: book.id,
+                        user_id: *user_id,
+                        priority,
+                    })
+                    .await?;
+            }
+        }
+
+        Ok((ImportStatus::Added, None))
+    }
+
+    async fn import_row(
+        &self,
+        user_id: &Uuid,
+        row: usize,
+        default_list: ShelfList,
+        entry: ShelfEntry,
+        offered_rows: &mut HashMap<Uuid, usize>,
+    ) -> ImportRowResult {
+        let list = entry.list.unwrap_or(default_list);
+        let mut result = ImportRowResult {
+            row,
+            list,
+            status: ImportStatus::Unresolved,
+            book_id: None,
+            google_id: entry.google_id.clone(),
+            title: entry.title.clone(),
+            message: None,
+        };
+
+        if entry.google_id.is_none() && entry.isbn.is_none() && entry.title.is_none() {
+            result.message = Some(messages::SHELF_ENTRY_UNIDENTIFIED.text());
+            return result;
+        }
+        if entry.google_id.as_deref().is_some_and(|id| !is_valid_google_id(id)) {
+            result.message = Some(messages::SHELF_GOOGLE_ID_INVALID.text());
+            return result;
+        }
+
+        let target = match Self::import_target(list, &entry) {
+            Ok(target) => target,
+            Err(message) => {
+                result.message = Some(message);
+                return result;
+            }
+        };
+
+        let book = match self.resolve_book(&entry).await {
+            Ok(Some(book)) => book,
+            Ok(None) => {
+                result.message = Some(messages::SHELF_BOOK_NOT_ON_GOOGLE.text());
+                return result;
+            }
+            Err(e) => {
+                result.message = Some(e.public_message());
+                return result;
+            }
+        };
+
+        result.book_id = Some(book.id);
+        result.google_id = Some(book.book.google_id.clone());
+        result.title = Some(book.book.title.clone());
+
+        match self.import_entry(user_id, target, &book, offered_rows).await {
+            Ok((status, message)) => {
+                result.status = status;
+                result.message = message;
+            }
+            Err(e) => result.message = Some(e.public_message()),
+        }
+
+        result
+    }
+}
+
+#[async_trait]
+impl ShelfService for ShelfServiceImpl {
+    async fn import_shelf(
+        &self,
+        user_id: &Uuid,
+        format: Option<ShelfFormat>,
+        default_list: ShelfList,
+        content: &str,
+    ) -> Result<ImportReport, AppError> {
+        let format = format.unwrap_or_else(|| detect_format(content));
+        let entries = parse_entries(format, content)?;
+
+        if entries.is_empty() {
+            return Err(AppError::ValidationError(messages::SHELF_EMPTY.text()));
+        }
+        if entries.len() > MAX_IMPORT_ROWS {
+            return Err(AppError::ValidationError(messages::SHELF_TOO_LARGE.format(&[&MAX_IMPORT_ROWS])));
+        }
+
+        let mut report = ImportReport::default();
+        let mut offered_rows = HashMap::new();
+        for (index, entry) in entries.into_iter().enumerate() {
+            let row = index + 1;
+            let result = match entry {
+                Ok(entry) => self.import_row(user_id, row, default_list, entry, &mut offered_rows).await,
+                Err(message) => ImportRowResult {
+                    row,
+                    list: default_list,
+                    status: ImportStatus::Unresolved,
+                    book_id: None,
+                    google_id: None,
+                    title: None,
+                    message: Some(message),
+                },
+            };
+            report.push(result);
+        }
+
+        Ok(report)
+    }
+
+    async fn export_shelf(&self, user_id: &Uuid, format: ShelfFormat) -> Result<String, AppError> {
+        let offered = self.books_offered_repository.find_all_by_user_id(user_id).await?;
+        let wanted = self.books_wanted_repository.find_all_by_user_id(user_id).await?;
+
+        let mut book_ids: Vec<String> = offered
+            .iter()
+            .map(|copy| copy.book_id)
+            .chain(wanted.iter().map(|book| book.book_id))
+            .map(|id| id.to_string())
+            .collect();
+        book_ids.sort();
+        book_ids.dedup();
+
+        let books: HashMap<Uuid, GoogleBookDto> = if book_ids.is_empty() {
+            HashMap::new()
+        } else {
+            self.book_repository
+                .find_by_ids(&book_ids)
+                .await?
+                .into_iter()
+                .map(|book| (book.id, book.book))
+                .collect()
+        };
+
+        let entry_for = |list: ShelfList, book_id: &Uuid| {
+            let book = books.get(book_id);
+            ShelfEntry {
+                list: Some(list),
+                google_id: book.map(|b| b.google_id.clone()).filter(|id| !id.is_empty()),
+                title: book.map(|b| b.title.clone()),
+                author: book.and_then(|b| b.authors.clone()),
+                ..ShelfEntry::default()
+            }
+        };
+
+        let mut entries: Vec<ShelfEntry> = offered
+            .iter()
+            .map(|copy| ShelfEntry {
+                condition: Some(copy.attributes.condition.as_str().to_string()),
+                language: copy.attributes.language.clone(),
+                notes: copy.attributes.notes.clone(),
+                ..entry_for(ShelfList::Offered, &copy.book_id)
+            })
+            .chain(wanted.iter().map(|book| ShelfEntry {
+                priority: Some(book.priority),
+                ..entry_for(ShelfList::Wanted, &book.book_id)
+            }))
+            .collect();
+
+        // Possuídos antes dos desejados, cada lista em ordem alfabética
+        entries.sort_by(|a, b| {
+            (a.list.map(|l| l.as_str()), &a.title).cmp(&(b.list.map(|l| l.as_str()), &b.title))
+        });
+
+        write_entries(format, &entries)
+    }
+}
+
+/// Codifica um termo de busca para ser usado na URL da API do Google Books
+pub fn encode_query_term(term: &str) -> String {
+    let mut encoded = String::with_capacity(term.len());
+    for byte in term.trim().bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => encoded.push(byte as char),
+            b' ' => encoded.push('+'),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
@@ -0,0 +1,428 @@
+use std::sync::Arc;
+
+use mockall::predicate::*;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::models::book::{
+    BookCondition, BookOffered, BookWanted, CreateBookOfferedDto, CreateBookWantedDto, GoogleBookDto,
+    OfferedCopyAttributes,
+};
+use crate::models::shelf::{ImportStatus, ShelfFormat, ShelfList};
+use crate::services::shelf_format::parse_entries;
+use crate::services::shelf_service::{encode_query_term, ShelfService, ShelfServiceImpl, MAX_IMPORT_ROWS};
+use crate::services::test_mocks::{
+    create_test_book_with_id, MockBookRepository, MockBooksOfferedRepository, MockBooksWantedRepository,
+    MockGoogleBookService,
+};
+
+const USER_ID: Uuid = Uuid::from_u128(0x5eed);
+
+fn google_book(google_id: &str, title: &str) -> GoogleBookDto {
+    GoogleBookDto {
+        google_id: google_id.to_string(),
+        title: title.to_string(),
+        authors: Some("Autor Teste".to_string()),
+        publisher: None,
+        published_date: None,
+        description: None,
+        image_url: None,
+        page_count: None,
+    }
+}
+
+fn create_copy(book_id: Uuid) -> BookOffered {
+    BookOffered {
+        id: Uuid::new_v4(),
+        book_id,
+        user_id: USER_ID,
+        attributes: OfferedCopyAttributes::default(),
+    }
+}
+
+fn create_service(
+    book_repo: MockBookRepository,
+    books_offered_repo: MockBooksOfferedRepository,
+    books_wanted_repo: MockBooksWantedRepository,
+    google_book_service: MockGoogleBookService,
+) -> ShelfServiceImpl {
+    ShelfServiceImpl::new(
+        Arc::new(book_repo),
+        Arc::new(books_offered_repo),
+        Arc::new(books_wanted_repo),
+        Arc::new(google_book_service),
+    )
+}
+
+// Repositório de livros em que o google_id informado já está cadastrado
+fn book_repo_with(book_id: Uuid, google_id: &'static str) -> MockBookRepository {
+    let mut book_repo = MockBookRepository::new();
+    book_repo
+        .expect_find_by_google_id()
+        .with(eq(google_id))
+        .returning(move |_| Ok(Some(create_test_book_with_id(book_id, google_id))));
+    book_repo
+}
+
+fn lists_with(
+    offered: Option<BookOffered>,
+    wanted_priority: Option<i16>,
+) -> (MockBooksOfferedRepository, MockBooksWantedRepository) {
+    let mut books_offered_repo = MockBooksOfferedRepository::new();
+    books_offered_repo
+        .expect_find_copies()
+        .returning(move |_, _| Ok(offered.clone().into_iter().collect()));
+
+    let mut books_wanted_repo = MockBooksWantedRepository::new();
+    books_wanted_repo
+        .expect_find()
+        .returning(move |book_id, user_id| {
+            Ok(wanted_priority.map(|priority| BookWanted { book_id: *book_id, user_id: *user_id, priority }))
+        });
+
+    (books_offered_repo, books_wanted_repo)
+}
+
+#[tokio::test]
+async fn test_import_adds_offered_copy_with_attributes() {
+    // Arrange
+    let book_id = Uuid::new_v4();
+    let (mut books_offered_repo, books_wanted_repo) = lists_with(None, None);
+    books_offered_repo
+        .expect_create()
+        .with(function(move |dto: &CreateBookOfferedDto| {
+            dto.book_id == book_id
+                && dto.user_id == USER_ID
+                && dto.attributes.condition == BookCondition::LikeNew
+                && dto.attributes.notes.as_deref() == Some("Autografado")
+        }))
+        .times(1)
+        .returning(|dto| Ok(create_copy(dto.book_id)));
+
+    let service = create_service(
+        book_repo_with(book_id, "abc"),
+        books_offered_repo,
+        books_wanted_repo,
+        MockGoogleBookService::new(),
+    );
+
+    // Act
+    let report = service
+        .import_shelf(
+            &USER_ID,
+            Some(ShelfFormat::Csv),
+            ShelfList::Offered,
+            "google_id,condition,notes\nabc,like_new,Autografado\n",
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(report.added, 1);
+    assert_eq!(report.rows[0].status, ImportStatus::Added);
+    assert_eq!(report.rows[0].book_id, Some(book_id));
+    assert_eq!(report.rows[0].list, ShelfList::Offered);
+}
+
+#[tokio::test]
+async fn test_import_is_idempotent_for_books_already_in_list() {
+    // Arrange
+    let book_id = Uuid::new_v4();
+    let (books_offered_repo, books_wanted_repo) = lists_with(None, Some(3));
+
+    let service = create_service(
+        book_repo_with(book_id, "abc"),
+        books_offered_repo,
+        books_wanted_repo,
+        MockGoogleBookService::new(),
+    );
+
+    // Act
+    let report = service
+        .import_shelf(&USER_ID, None, ShelfList::Wanted, "google_id\nabc\n")
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(report.already_present, 1);
+    assert_eq!(report.rows[0].status, ImportStatus::AlreadyPresent);
+}
+
+#[tokio::test]
+async fn test_import_adds_missing_copies_of_repeated_rows() {
+    // Arrange
+    let book_id = Uuid::new_v4();
+    let (mut books_offered_repo, books_wanted_repo) = lists_with(Some(create_copy(book_id)), None);
+    books_offered_repo
+        .expect_create()
+        .times(2)
+        .returning(|dto| Ok(create_copy(dto.book_id)));
+
+    let service = create_service(
+        book_repo_with(book_id, "abc"),
+        books_offered_repo,
+        books_wanted_repo,
+        MockGoogleBookService::new(),
+    );
+
+    // Act
+    let report = service
+        .import_shelf(&USER_ID, None, ShelfList::Offered, "google_id\nabc\nabc\nabc\n")
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(report.already_present, 1);
+    assert_eq!(report.added, 2);
+    assert_eq!(report.rows[0].status, ImportStatus::AlreadyPresent);
+}
+
+#[tokio::test]
+async fn test_import_searches_normalized_isbn() {
+    // Arrange
+    let google_book_service = MockGoogleBookService::new().with_search_books(|query| {
+        assert_eq!(query, "isbn:978851");
+        Ok(vec![])
+    });
+
+    let service = create_service(
+        MockBookRepository::new(),
+        MockBooksOfferedRepository::new(),
+        MockBooksWantedRepository::new(),
+        google_book_service,
+    );
+
+    // Act
+    let report = service
+        .import_shelf(&USER_ID, None, ShelfList::Wanted, "isbn\n978 85/1\n")
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(report.rows[0].status, ImportStatus::Unresolved);
+}
+
+#[tokio::test]
+async fn test_import_reports_conflict_with_other_list() {
+    // Arrange
+    let book_id = Uuid::new_v4();
+    let (books_offered_repo, books_wanted_repo) = lists_with(Some(create_copy(book_id)), None);
+
+    let service = create_service(
+        book_repo_with(book_id, "abc"),
+        books_offered_repo,
+        books_wanted_repo,
+        MockGoogleBookService::new(),
+    );
+
+    // Act
+    let report = service
+        .import_shelf(&USER_ID, None, ShelfList::Offered, "list,google_id\nwanted,abc\n")
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(report.conflict, 1);
+    assert_eq!(report.rows[0].list, ShelfList::Wanted);
+    assert_eq!(report.rows[0].status, ImportStatus::Conflict);
+    assert!(report.rows[0].message.is_some());
+}
+
+#[tokio::test]
+async fn test_import_resolves_by_title_and_author_and_registers_book() {
+    // Arrange
+    let book_id = Uuid::new_v4();
+    let mut book_repo = MockBookRepository::new();
+    book_repo
+        .expect_find_by_google_id()
+        .with(eq("found"))
+        .times(1)
+        .returning(|_| Ok(None));
+    book_repo
+        .expect_create()
+        .withf(|book: &GoogleBookDto| book.google_id == "found")
+        .times(1)
+        .returning(move |_| Ok(book_id));
+
+    let (books_offered_repo, mut books_wanted_repo) = lists_with(None, None);
+    books_wanted_repo
+        .expect_create()
+        .with(function(move |dto: &CreateBookWantedDto| dto.book_id == book_id && dto.priority == 5))
+        .times(1)
+        .returning(|dto| Ok(BookWanted { book_id: dto.book_id, user_id: dto.user_id, priority: dto.priority }));
+
+    let google_book_service = MockGoogleBookService::new().with_search_books(|query| {
+        assert_eq!(query, "intitle:O+Corti%C3%A7o+inauthor:Alu%C3%ADsio+Azevedo");
+        Ok(vec![google_book("found", "O Cortiço")])
+    });
+
+    let service = create_service(book_repo, books_offered_repo, books_wanted_repo, google_book_service);
+
+    // Act
+    let report = service
+        .import_shelf(
+            &USER_ID,
+            None,
+            ShelfList::Wanted,
+            "title,author,priority\nO Cortiço,Aluísio Azevedo,5\n",
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(report.added, 1);
+    assert_eq!(report.rows[0].google_id.as_deref(), Some("found"));
+    assert_eq!(report.rows[0].book_id, Some(book_id));
+}
+
+#[tokio::test]
+async fn test_import_reports_unresolved_rows() {
+    // Arrange
+    let google_book_service = MockGoogleBookService::new().with_search_books(|query| {
+        assert_eq!(query, "isbn:9780000000000");
+        Ok(vec![])
+    });
+
+    // Nenhum repositório deve ser consultado
+    let service = create_service(
+        MockBookRepository::new(),
+        MockBooksOfferedRepository::new(),
+        MockBooksWantedRepository::new(),
+        google_book_service,
+    );
+
+    let content = "isbn,title,condition,priority,list\n\
+                   9780000000000,,,,\n\
+                   ,Livro,rasgado,,\n\
+                   ,Livro,,9,wanted\n\
+                   ,,,,\n";
+
+    // Act
+    let report = service
+        .import_shelf(&USER_ID, None, ShelfList::Offered, content)
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(report.unresolved, 4);
+    assert_eq!(report.added, 0);
+    let messages: Vec<&str> = report.rows.iter().map(|r| r.message.as_deref().unwrap()).collect();
+    assert!(messages[0].contains("não encontrado"));
+    assert!(messages[1].contains("Estado de conservação"));
+    assert!(messages[2].contains("prioridade"), "{}", messages[2]);
+    assert!(messages[3].contains("google_id, isbn ou title"));
+    assert_eq!(report.rows.iter().map(|r| r.row).collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+}
+
+#[tokio::test]
+async fn test_import_rejects_google_ids_outside_google_format() {
+    // Arrange - O id iria no caminho da URL do Google Books
+    let google_book_service = MockGoogleBookService::new()
+        .with_find_book_by_id(|google_id| panic!("O Google Books não deveria ser consultado: {}", google_id));
+
+    // Nenhum repositório deve ser consultado
+    let service = create_service(
+        MockBookRepository::new(),
+        MockBooksOfferedRepository::new(),
+        MockBooksWantedRepository::new(),
+        google_book_service,
+    );
+
+    let content = "google_id\n\
+                   x?q=harry\n\
+                   ../volumes?q=harry\n\
+                   id#\n";
+
+    // Act
+    let report = service
+        .import_shelf(&USER_ID, Some(ShelfFormat::Csv), ShelfList::Offered, content)
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(report.unresolved, 3);
+    assert!(report
+        .rows
+        .iter()
+        .all(|r| r.status == ImportStatus::Unresolved && r.message.as_deref().unwrap().contains("google_id inválido")));
+}
+
+#[tokio::test]
+async fn test_import_rejects_empty_and_oversized_files() {
+    let service = create_service(
+        MockBookRepository::new(),
+        MockBooksOfferedRepository::new(),
+        MockBooksWantedRepository::new(),
+        MockGoogleBookService::new(),
+    );
+
+    let empty = service
+        .import_shelf(&USER_ID, None, ShelfList::Offered, "title\n")
+        .await;
+    assert!(matches!(empty, Err(AppError::ValidationError(_))));
+
+    let oversized = format!("title\n{}", "Livro\n".repeat(MAX_IMPORT_ROWS + 1));
+    let result = service
+        .import_shelf(&USER_ID, None, ShelfList::Offered, &oversized)
+        .await;
+    assert!(matches!(result, Err(AppError::ValidationError(_))));
+}
+
+#[tokio::test]
+async fn test_export_contains_copies_and_wanted_books() {
+    // Arrange
+    let offered_book_id = Uuid::new_v4();
+    let wanted_book_id = Uuid::new_v4();
+
+    let mut books_offered_repo = MockBooksOfferedRepository::new();
+    books_offered_repo
+        .expect_find_all_by_user_id()
+        .with(eq(USER_ID))
+        .returning(move |_| {
+            let mut copy = create_copy(offered_book_id);
+            copy.attributes.condition = BookCondition::Fair;
+            Ok(vec![create_copy(offered_book_id), copy])
+        });
+
+    let mut books_wanted_repo = MockBooksWantedRepository::new();
+    books_wanted_repo
+        .expect_find_all_by_user_id()
+        .with(eq(USER_ID))
+        .returning(move |_| Ok(vec![BookWanted { book_id: wanted_book_id, user_id: USER_ID, priority: 4 }]));
+
+    let mut book_repo = MockBookRepository::new();
+    book_repo
+        .expect_find_by_ids()
+        .times(1)
+        .returning(move |ids| {
+            assert_eq!(ids.len(), 2);
+            Ok(vec![
+                create_test_book_with_id(offered_book_id, "offered"),
+                create_test_book_with_id(wanted_book_id, "wanted"),
+            ])
+        });
+
+    let service = create_service(book_repo, books_offered_repo, books_wanted_repo, MockGoogleBookService::new());
+
+    // Act
+    let content = service.export_shelf(&USER_ID, ShelfFormat::Csv).await.unwrap();
+    let entries: Vec<_> = parse_entries(ShelfFormat::Csv, &content)
+        .unwrap()
+        .into_iter()
+        .map(|entry| entry.unwrap())
+        .collect();
+
+    // Assert
+    assert_eq!(entries.len(), 3);
+    assert!(entries[..2].iter().all(|e| e.list == Some(ShelfList::Offered) && e.google_id.as_deref() == Some("offered")));
+    assert!(entries.iter().any(|e| e.condition.as_deref() == Some("fair")));
+    assert_eq!(entries[2].list, Some(ShelfList::Wanted));
+    assert_eq!(entries[2].google_id.as_deref(), Some("wanted"));
+    assert_eq!(entries[2].priority, Some(4));
+}
+
+#[test]
+fn test_encode_query_term() {
+    assert_eq!(encode_query_term(" Dom Casmurro "), "Dom+Casmurro");
+    assert_eq!(encode_query_term("C&A/ção"), "C%26A%2F%C3%A7%C3%A3o");
+}
@@ -2,7 +2,14 @@ use mockall::{mock, predicate::*};
 use uuid::Uuid;
 
 use crate::error::AppError;
-use crate::models::book::{BookOffered, BookWanted, CreateBookOfferedDto, CreateBookWantedDto, GoogleBookDto};
+use crate::models::book::{
+    BookDetails, BookOffered, BookPhoto, BookPhotoRecord, BookWanted, CreateBookOfferedDto, CreateBookPhotoDto,
+    CreateBookWantedDto, GoogleBookDto, OfferedCopyAttributes,
+};
+use crate::models::oauth::{ExternalIdentity, OAuthLoginState};
+use crate::models::two_factor::TwoFactorSecret;
+use crate::models::user::{CreateUserDto, LoginResponse, LoginUserDto, User, UserResponse};
+use crate::services::book_photo_service::PhotoContent;
 use crate::repositories::book_repository::BookWithId;
 
 // Mock para o BookRepository
@@ -13,8 +20,12 @@ mock! {
     impl crate::repositories::book_repository::BookRepository for BookRepository {
         async fn create(&self, book: &GoogleBookDto) -> Result<Uuid, AppError>;
         async fn find_by_google_id(&self, google_id: &str) -> Result<Option<BookWithId>, AppError>;
+        async fn find_by_google_ids(&self, google_ids: &[String]) -> Result<Vec<BookWithId>, AppError>;
         async fn find_by_id(&self, id: &str) -> Result<Option<GoogleBookDto>, AppError>;
         async fn find_by_ids(&self, ids: &[String]) -> Result<Vec<BookWithId>, AppError>;
+        async fn find_details(&self, id: &Uuid, user_id: &Uuid) -> Result<Option<BookDetails>, AppError>;
+        async fn update_metadata(&self, id: &Uuid, book: &GoogleBookDto) -> Result<(), AppError>;
+        async fn mark_metadata_checked(&self, id: &Uuid) -> Result<(), AppError>;
     }
 }
 
@@ -25,9 +36,79 @@ mock! {
     #[async_trait::async_trait]
     impl crate::repositories::books_offered_repository::BooksOfferedRepository for BooksOfferedRepository {
         async fn create(&self, book_offered: &CreateBookOfferedDto) -> Result<BookOffered, AppError>;
+        async fn create_copies(
+            &self,
+            book_offered: &CreateBookOfferedDto,
+            quantity: i32,
+        ) -> Result<Vec<BookOffered>, AppError>;
+        async fn create_many(&self, books_offered: &[CreateBookOfferedDto]) -> Result<Vec<BookOffered>, AppError>;
+        async fn create_from_wanted(&self, book_offered: &CreateBookOfferedDto) -> Result<Option<BookOffered>, AppError>;
         async fn find(&self, book_id: &Uuid, user_id: &Uuid) -> Result<Option<BookOffered>, AppError>;
+        async fn find_by_id(&self, id: &Uuid) -> Result<Option<BookOffered>, AppError>;
+        async fn find_copies(&self, book_id: &Uuid, user_id: &Uuid) -> Result<Vec<BookOffered>, AppError>;
+        async fn update(
+            &self,
+            id: &Uuid,
+            user_id: &Uuid,
+            attributes: &OfferedCopyAttributes,
+        ) -> Result<Option<BookOffered>, AppError>;
         async fn delete(&self, book_id: &Uuid, user_id: &Uuid) -> Result<bool, AppError>;
+        async fn delete_copy(&self, id: &Uuid, user_id: &Uuid) -> Result<bool, AppError>;
+        async fn delete_many(&self, book_ids: &[Uuid], user_id: &Uuid) -> Result<u64, AppError>;
+        async fn any_reserved(&self, ids: &[Uuid]) -> Result<bool, AppError>;
         async fn find_by_user_id(&self, user_id: &Uuid) -> Result<Vec<Uuid>, AppError>;
+        async fn find_all_by_user_id(&self, user_id: &Uuid) -> Result<Vec<BookOffered>, AppError>;
+        async fn find_owners_by_book_id(
+            &self,
+            book_id: &Uuid,
+        ) -> Result<Vec<(UserResponse, BookOffered)>, AppError>;
+    }
+}
+
+// Mock para o BookPhotoRepository
+mock! {
+    pub BookPhotoRepository {}
+
+    #[async_trait::async_trait]
+    impl crate::repositories::book_photo_repository::BookPhotoRepository for BookPhotoRepository {
+        async fn create(&self, photo: &CreateBookPhotoDto) -> Result<BookPhotoRecord, AppError>;
+        async fn find_by_id(&self, id: &Uuid) -> Result<Option<BookPhotoRecord>, AppError>;
+        async fn find_by_copy_id(&self, copy_id: &Uuid) -> Result<Vec<BookPhotoRecord>, AppError>;
+        async fn find_by_user_id(&self, user_id: &Uuid) -> Result<Vec<BookPhotoRecord>, AppError>;
+        async fn find_by_copy_ids(&self, copy_ids: &[Uuid]) -> Result<Vec<BookPhotoRecord>, AppError>;
+        async fn delete(&self, id: &Uuid) -> Result<bool, AppError>;
+    }
+}
+
+// Mock para o PhotoStorage
+mock! {
+    pub PhotoStorage {}
+
+    #[async_trait::async_trait]
+    impl crate::services::photo_storage::PhotoStorage for PhotoStorage {
+        async fn save(&self, key: &str, content: &[u8]) -> Result<(), AppError>;
+        async fn load(&self, key: &str) -> Result<Vec<u8>, AppError>;
+        async fn delete(&self, key: &str) -> Result<(), AppError>;
+    }
+}
+
+// Mock para o BookPhotoService
+mock! {
+    pub BookPhotoService {}
+
+    #[async_trait::async_trait]
+    impl crate::services::book_photo_service::BookPhotoService for BookPhotoService {
+        async fn add_photo(
+            &self,
+            copy_id: &Uuid,
+            user_id: &Uuid,
+            content_type: &str,
+            content: Vec<u8>,
+        ) -> Result<BookPhoto, AppError>;
+        async fn remove_photo(&self, photo_id: &Uuid, user_id: &Uuid) -> Result<bool, AppError>;
+        async fn get_photo(&self, photo_id: &Uuid) -> Result<PhotoContent, AppError>;
+        async fn storage_keys(&self, copy_ids: &[Uuid]) -> Result<Vec<String>, AppError>;
+        async fn delete_stored(&self, storage_keys: &[String]);
     }
 }
 
@@ -38,15 +119,36 @@ mock! {
     #[async_trait::async_trait]
     impl crate::repositories::books_wanted_repository::BooksWantedRepository for BooksWantedRepository {
         async fn create(&self, book_wanted: &CreateBookWantedDto) -> Result<BookWanted, AppError>;
+        async fn create_many(&self, books_wanted: &[CreateBookWantedDto]) -> Result<Vec<BookWanted>, AppError>;
         async fn find(&self, book_id: &Uuid, user_id: &Uuid) -> Result<Option<BookWanted>, AppError>;
+        async fn update_priority(
+            &self,
+            book_id: &Uuid,
+            user_id: &Uuid,
+            priority: i16,
+        ) -> Result<Option<BookWanted>, AppError>;
         async fn delete(&self, book_id: &Uuid, user_id: &Uuid) -> Result<bool, AppError>;
+        async fn delete_many(&self, book_ids: &[Uuid], user_id: &Uuid) -> Result<u64, AppError>;
         async fn find_by_user_id(&self, user_id: &Uuid) -> Result<Vec<Uuid>, AppError>;
+        async fn find_all_by_user_id(&self, user_id: &Uuid) -> Result<Vec<BookWanted>, AppError>;
     }
 }
 
+// Assinatura da função usada para simular find_book_by_id
+type FindBookByIdFn = Box<dyn Fn(&str) -> Result<GoogleBookDto, AppError> + Send + Sync>;
+// Assinatura da função usada para simular search_books
+type SearchBooksFn = Box<dyn Fn(&str) -> Result<Vec<GoogleBookDto>, AppError> + Send + Sync>;
+
 // Mock para o GoogleBookService - versão simplificada
 pub struct MockGoogleBookService {
-    pub find_book_by_id_fn: Box<dyn Fn(&str) -> Result<GoogleBookDto, AppError> + Send + Sync>,
+    pub find_book_by_id_fn: FindBookByIdFn,
+    pub search_books_fn: SearchBooksFn,
+}
+
+impl Default for MockGoogleBookService {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl MockGoogleBookService {
@@ -54,6 +156,7 @@ impl MockGoogleBookService {
         // Por padrão, retorna um erro (será substituído nos testes)
         Self {
             find_book_by_id_fn: Box::new(|_| Err(AppError::NotFoundError("Livro não encontrado".to_string()))),
+            search_books_fn: Box::new(|_| Ok(vec![])),
         }
     }
 
@@ -64,15 +167,23 @@ impl MockGoogleBookService {
         self.find_book_by_id_fn = Box::new(f);
         self
     }
+
+    pub fn with_search_books<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&str) -> Result<Vec<GoogleBookDto>, AppError> + 'static + Send + Sync
+    {
+        self.search_books_fn = Box::new(f);
+        self
+    }
 }
 
 impl crate::services::google_book_service::GoogleBookService for MockGoogleBookService {
     fn search_books<'a>(
         &'a self,
-        _query: &'a str,
+        query: &'a str,
     ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<Vec<GoogleBookDto>, AppError>> + Send + 'a>> {
-        // Podemos implementar se for necessário nos testes
-        Box::pin(async { Ok(vec![]) })
+        let result = (self.search_books_fn)(query);
+        Box::pin(async move { result })
     }
 
     fn find_book_by_id<'a>(
@@ -84,6 +195,41 @@ impl crate::services::google_book_service::GoogleBookService for MockGoogleBookS
     }
 }
 
+// Mock para o AuthService
+mock! {
+    pub AuthService {}
+
+    #[async_trait::async_trait]
+    impl crate::services::auth_service::AuthService for AuthService {
+        async fn register(&self, user_dto: CreateUserDto) -> Result<UserResponse, AppError>;
+        async fn login(&self, login_dto: LoginUserDto) -> Result<LoginResponse, AppError>;
+    }
+}
+
+// Mock para o OAuthRepository
+mock! {
+    pub OAuthRepository {}
+
+    #[async_trait::async_trait]
+    impl crate::repositories::oauth_repository::OAuthRepository for OAuthRepository {
+        async fn save_login_state(&self, login_state: &OAuthLoginState) -> Result<(), AppError>;
+        async fn take_login_state(
+            &self,
+            state: &str,
+            max_age: std::time::Duration,
+        ) -> Result<Option<OAuthLoginState>, AppError>;
+        async fn find_user_by_identity(&self, provider: &str, subject: &str) -> Result<Option<User>, AppError>;
+        async fn find_user_by_email(&self, email: &str) -> Result<Option<User>, AppError>;
+        async fn link_identity(&self, user_id: &Uuid, identity: &ExternalIdentity) -> Result<(), AppError>;
+        async fn create_user_with_identity(
+            &self,
+            name: &str,
+            hash_password: String,
+            identity: &ExternalIdentity,
+        ) -> Result<User, AppError>;
+    }
+}
+
 // Função auxiliar para criar um livro com ID para testes
 pub fn create_test_book_with_id(id: Uuid, google_id: &str) -> BookWithId {
     BookWithId {
@@ -99,4 +245,18 @@ pub fn create_test_book_with_id(id: Uuid, google_id: &str) -> BookWithId {
             page_count: Some(300),
         }
     }
-} 
\ No newline at end of file
+} 
+// Mock para o TwoFactorRepository
+mock! {
+    pub TwoFactorRepository {}
+
+    #[async_trait::async_trait]
+    impl crate::repositories::two_factor_repository::TwoFactorRepository for TwoFactorRepository {
+        async fn save_pending_secret(&self, user_id: &Uuid, secret: &str) -> Result<bool, AppError>;
+        async fn find(&self, user_id: &Uuid) -> Result<Option<TwoFactorSecret>, AppError>;
+        async fn enable(&self, user_id: &Uuid, used_step: i64, recovery_code_hashes: &[String]) -> Result<(), AppError>;
+        async fn use_step(&self, user_id: &Uuid, step: i64) -> Result<bool, AppError>;
+        async fn use_recovery_code(&self, user_id: &Uuid, code_hash: &str) -> Result<bool, AppError>;
+        async fn delete(&self, user_id: &Uuid) -> Result<(), AppError>;
+    }
+}
@@ -0,0 +1,316 @@
+use std::fs;
+use std::sync::Arc;
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{Duration, Utc};
+use jsonwebtoken::{decode, decode_header, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use ring::signature::{Ed25519KeyPair, KeyPair, RsaKeyPair};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::config::Config;
+use crate::error::AppError;
+use crate::i18n::messages;
+use crate::models::jwk::{Jwk, JwkSet};
+
+/// Validade do token de desafio emitido quando a conta exige o segundo fator
+pub const CHALLENGE_EXPIRES_IN_SECONDS: i64 = 5 * 60;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenClaims {
+    pub sub: String,
+    pub iss: String,
+    pub aud: String,
+    /// Identificador único do token
+    pub jti: String,
+    pub iat: usize,
+    pub exp: usize,
+}
+
+/// Emissor, público e validade dos tokens
+#[derive(Debug, Clone)]
+pub struct TokenSettings {
+    pub issuer: String,
+    pub audience: String,
+    pub expires_in: Duration,
+}
+
+/// Emissão e verificação dos tokens de acesso
+pub trait TokenService: Send + Sync + 'static {
+    /// Gera um token para o usuário, assinado com a chave ativa
+    fn issue(&self, user_id: &Uuid) -> Result<String, AppError>;
+    /// Verifica a assinatura (pela chave indicada no `kid`), a validade e os
+    /// campos `iss`, `aud` e `jti` do token
+    fn verify(&self, token: &str) -> Result<TokenClaims, AppError>;
+    /// Gera o token de desafio da verificação em duas etapas, que não dá
+    /// acesso à API e só pode ser trocado pelo token de acesso
+    fn issue_challenge(&self, user_id: &Uuid) -> Result<String, AppError>;
+    /// Verifica um token de desafio
+    fn verify_challenge(&self, token: &str) -> Result<TokenClaims, AppError>;
+    /// Chaves públicas aceitas na verificação; vazio com segredo HS256
+    fn jwks(&self) -> JwkSet;
+}
+
+// Chave aceita na verificação dos tokens
+struct VerificationKey {
+    kid: Option<String>,
+    algorithm: Algorithm,
+    decoding_key: DecodingKey,
+    jwk: Option<Jwk>,
+}
+
+/// TokenService com jsonwebtoken
+///
+/// Com chaves assimétricas (RS256 ou EdDSA), todas as chaves carregadas são
+/// aceitas na verificação e apenas a ativa assina novos tokens. Para trocar de
+/// chave sem invalidar as sessões, adicione a nova chave, torne-a ativa e
+/// remova a antiga depois que os tokens assinados por ela expirarem.
+pub struct JwtTokenService {
+    signing_kid: Option<String>,
+    signing_algorithm: Algorithm,
+    encoding_key: EncodingKey,
+    keys: Vec<VerificationKey>,
+    settings: TokenSettings,
+}
+
+impl JwtTokenService {
+    /// Tokens HS256 assinados com um segredo compartilhado, sem `kid`
+    pub fn with_secret(secret: &str, settings: TokenSettings) -> Self {
+        Self {
+            signing_kid: None,
+            signing_algorithm: Algorithm::HS256,
+            encoding_key: EncodingKey::from_secret(secret.as_bytes()),
+            keys: vec![VerificationKey {
+                kid: None,
+                algorithm: Algorithm::HS256,
+                decoding_key: DecodingKey::from_secret(secret.as_bytes()),
+                jwk: None,
+            }],
+            settings,
+        }
+    }
+
+    /// Tokens assinados com chaves privadas RSA ou Ed25519 em PEM
+    ///
+    /// Cada chave é informada com o seu `kid`; `active_kid` indica a que assina
+    /// os novos tokens.
+    pub fn with_private_keys(
+        keys: &[(String, Vec<u8>)],
+        active_kid: &str,
+        settings: TokenSettings,
+    ) -> Result<Self, String> {
+        let mut signing = None;
+        let mut verification_keys = Vec::with_capacity(keys.len());
+
+        for (kid, pem) in keys {
+            let (encoding_key, verification_key) = load_private_key(kid, pem)?;
+            if kid == active_kid {
+                signing = Some((verification_key.algorithm, encoding_key));
+            }
+            verification_keys.push(verification_key);
+        }
+
+        let (signing_algorithm, encoding_key) =
+            signing.ok_or_else(|| format!("Chave ativa do JWT não encontrada: {}", active_kid))?;
+
+        Ok(Self {
+            signing_kid: Some(active_kid.to_string()),
+            signing_algorithm,
+            encoding_key,
+            keys: verification_keys,
+            settings,
+        })
+    }
+
+    /// Cria o serviço a partir da configuração
+    ///
+    /// Com `JWT_KEYS_DIR`, cada arquivo `<kid>.pem` do diretório é uma chave
+    /// privada; sem ele, os tokens são HS256 com o `JWT_SECRET`.
+    pub fn from_config(config: &Config) -> Result<Self, String> {
+        let hours = config
+            .jwt_expires_in
+            .trim()
+            .trim_end_matches('h')
+            .parse::<i64>()
+            .map_err(|_| format!("JWT_EXPIRES_IN inválido: {}", config.jwt_expires_in))?;
+
+        let settings = TokenSettings {
+            issuer: config.jwt_issuer.clone(),
+            audience: config.jwt_audience.clone(),
+            expires_in: Duration::hours(hours),
+        };
+
+        let Some(keys_dir) = &config.jwt_keys_dir else {
+            return Ok(Self::with_secret(&config.jwt_secret, settings));
+        };
+
+        let mut keys = Vec::new();
+        let entries = fs::read_dir(keys_dir).map_err(|e| format!("Falha ao ler {}: {}", keys_dir, e))?;
+        for entry in entries {
+            let path = entry.map_err(|e| e.to_string())?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("pem") {
+                continue;
+            }
+            let kid = path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .ok_or_else(|| format!("Nome de chave inválido: {}", path.display()))?
+                .to_string();
+            let pem = fs::read(&path).map_err(|e| format!("Falha ao ler {}: {}", path.display(), e))?;
+            keys.push((kid, pem));
+        }
+        keys.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let active_kid = match (&config.jwt_active_kid, keys.as_slice()) {
+            (Some(kid), _) => kid.clone(),
+            (None, [(kid, _)]) => kid.clone(),
+            (None, []) => return Err(format!("Nenhuma chave .pem em {}", keys_dir)),
+            (None, _) => return Err("JWT_ACTIVE_KID deve indicar a chave ativa".to_string()),
+        };
+
+        Self::with_private_keys(&keys, &active_kid, settings)
+    }
+
+    fn find_key(&self, kid: Option<&str>) -> Option<&VerificationKey> {
+        self.keys.iter().find(|key| key.kid.as_deref() == kid)
+    }
+
+    // Os desafios têm um público próprio, então não passam pela verificação
+    // dos tokens de acesso (e vice-versa)
+    fn challenge_audience(&self) -> String {
+        format!("{}:2fa", self.settings.audience)
+    }
+
+    fn sign(&self, user_id: &Uuid, audience: String, expires_in: Duration) -> Result<String, AppError> {
+        let now = Utc::now();
+        let claims = TokenClaims {
+            sub: user_id.to_string(),
+            iss: self.settings.issuer.clone(),
+            aud: audience,
+            jti: Uuid::new_v4().to_string(),
+            iat: now.timestamp() as usize,
+            exp: (now + expires_in).timestamp() as usize,
+        };
+
+        let mut header = Header::new(self.signing_algorithm);
+        header.kid = self.signing_kid.clone();
+
+        encode(&header, &claims, &self.encoding_key)
+            .map_err(|e| AppError::InternalServerError(format!("Erro ao gerar token: {}", e)))
+    }
+
+    fn decode_claims(&self, token: &str, audience: &str) -> Result<TokenClaims, AppError> {
+        let header =
+            decode_header(token).map_err(|e| AppError::AuthError(messages::TOKEN_INVALID.format(&[&e])))?;
+        let key = self
+            .find_key(header.kid.as_deref())
+            .ok_or_else(|| AppError::AuthError(messages::TOKEN_KEY_UNKNOWN.text()))?;
+
+        // O algoritmo vem da chave, nunca do cabeçalho do token
+        let mut validation = Validation::new(key.algorithm);
+        validation.set_issuer(&[&self.settings.issuer]);
+        validation.set_audience(&[audience]);
+        validation.set_required_spec_claims(&["exp", "sub", "iss", "aud"]);
+
+        let claims = decode::<TokenClaims>(token, &key.decoding_key, &validation)
+            .map_err(|e| AppError::AuthError(messages::TOKEN_INVALID.format(&[&e])))?
+            .claims;
+
+        if Uuid::parse_str(&claims.jti).is_err() {
+            return Err(AppError::AuthError(messages::TOKEN_INVALID.format(&[&"jti"])));
+        }
+
+        Ok(claims)
+    }
+}
+
+impl TokenService for JwtTokenService {
+    fn issue(&self, user_id: &Uuid) -> Result<String, AppError> {
+        self.sign(user_id, self.settings.audience.clone(), self.settings.expires_in)
+    }
+
+    fn verify(&self, token: &str) -> Result<TokenClaims, AppError> {
+        self.decode_claims(token, &self.settings.audience)
+    }
+
+    fn issue_challenge(&self, user_id: &Uuid) -> Result<String, AppError> {
+        self.sign(user_id, self.challenge_audience(), Duration::seconds(CHALLENGE_EXPIRES_IN_SECONDS))
+    }
+
+    fn verify_challenge(&self, token: &str) -> Result<TokenClaims, AppError> {
+        self.decode_claims(token, &self.challenge_audience())
+    }
+
+    fn jwks(&self) -> JwkSet {
+        JwkSet {
+            keys: self.keys.iter().filter_map(|key| key.jwk.clone()).collect(),
+        }
+    }
+}
+
+// Lê uma chave privada RSA (PKCS#1 ou PKCS#8) ou Ed25519 (PKCS#8) e extrai a
+// parte pública, usada na verificação e publicada no JWKS
+fn load_private_key(kid: &str, pem_bytes: &[u8]) -> Result<(EncodingKey, VerificationKey), String> {
+    let pem = pem::parse(pem_bytes).map_err(|e| format!("Chave {} inválida: {}", kid, e))?;
+
+    if pem.tag == "PRIVATE KEY" {
+        if let Ok(key_pair) = Ed25519KeyPair::from_pkcs8_maybe_unchecked(&pem.contents) {
+            let public_key = key_pair.public_key().as_ref();
+            return Ok((
+                EncodingKey::from_ed_der(&pem.contents),
+                VerificationKey {
+                    kid: Some(kid.to_string()),
+                    algorithm: Algorithm::EdDSA,
+                    decoding_key: DecodingKey::from_ed_der(public_key),
+                    jwk: Some(Jwk {
+                        kty: "OKP".to_string(),
+                        kid: kid.to_string(),
+                        alg: "EdDSA".to_string(),
+                        key_use: "sig".to_string(),
+                        n: None,
+                        e: None,
+                        crv: Some("Ed25519".to_string()),
+                        x: Some(URL_SAFE_NO_PAD.encode(public_key)),
+                    }),
+                },
+            ));
+        }
+    }
+
+    let key_pair = match pem.tag.as_str() {
+        "RSA PRIVATE KEY" => RsaKeyPair::from_der(&pem.contents),
+        "PRIVATE KEY" => RsaKeyPair::from_pkcs8(&pem.contents),
+        tag => return Err(format!("Chave {}: tipo não suportado ({})", kid, tag)),
+    }
+    .map_err(|e| format!("Chave {} inválida: {}", kid, e))?;
+
+    let public_key = key_pair.public_key();
+    let modulus = public_key.modulus();
+    let exponent = public_key.exponent();
+    let n = modulus.big_endian_without_leading_zero();
+    let e = exponent.big_endian_without_leading_zero();
+
+    Ok((
+        EncodingKey::from_rsa_pem(pem_bytes).map_err(|e| format!("Chave {} inválida: {}", kid, e))?,
+        VerificationKey {
+            kid: Some(kid.to_string()),
+            algorithm: Algorithm::RS256,
+            decoding_key: DecodingKey::from_rsa_raw_components(n, e),
+            jwk: Some(Jwk {
+                kty: "RSA".to_string(),
+                kid: kid.to_string(),
+                alg: "RS256".to_string(),
+                key_use: "sig".to_string(),
+                n: Some(URL_SAFE_NO_PAD.encode(n)),
+                e: Some(URL_SAFE_NO_PAD.encode(e)),
+                crv: None,
+                x: None,
+            }),
+        },
+    ))
+}
+
+/// Factory para criar o TokenService a partir da configuração
+pub fn create_token_service(config: &Config) -> Result<Arc<dyn TokenService>, String> {
+    Ok(Arc::new(JwtTokenService::from_config(config)?))
+}
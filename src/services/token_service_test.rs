@@ -0,0 +1,192 @@
+use chrono::Duration;
+use jsonwebtoken::{decode_header, encode, Algorithm, EncodingKey, Header};
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::services::token_service::{
+    JwtTokenService, TokenClaims, TokenService, TokenSettings, CHALLENGE_EXPIRES_IN_SECONDS,
+};
+
+const RSA_KEY: &[u8] = include_bytes!("../../tests/fixtures/jwt_rsa_test.pem");
+const ED25519_KEY: &[u8] = include_bytes!("../../tests/fixtures/jwt_ed25519_test.pem");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings() -> TokenSettings {
+        TokenSettings {
+            issuer: "troca-livros-api".to_string(),
+            audience: "troca-livros".to_string(),
+            expires_in: Duration::hours(1),
+        }
+    }
+
+    fn key_service(active_kid: &str) -> JwtTokenService {
+        JwtTokenService::with_private_keys(
+            &[
+                ("rsa-1".to_string(), RSA_KEY.to_vec()),
+                ("ed-1".to_string(), ED25519_KEY.to_vec()),
+            ],
+            active_kid,
+            settings(),
+        )
+        .expect("Falha ao carregar as chaves de teste")
+    }
+
+    fn assert_invalid_token(result: Result<TokenClaims, AppError>) {
+        match result {
+            Err(AppError::AuthError(_)) => {}
+            other => panic!("Esperava AuthError, obteve {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_hs256_round_trip() {
+        let service = JwtTokenService::with_secret("segredo", settings());
+        let user_id = Uuid::new_v4();
+
+        let token = service.issue(&user_id).unwrap();
+        let claims = service.verify(&token).unwrap();
+
+        assert_eq!(claims.sub, user_id.to_string());
+        assert_eq!(claims.iss, "troca-livros-api");
+        assert_eq!(claims.aud, "troca-livros");
+        assert!(Uuid::parse_str(&claims.jti).is_ok());
+        assert!(service.jwks().keys.is_empty());
+    }
+
+    #[test]
+    fn test_each_token_has_a_unique_jti() {
+        let service = JwtTokenService::with_secret("segredo", settings());
+        let user_id = Uuid::new_v4();
+
+        let first = service.verify(&service.issue(&user_id).unwrap()).unwrap();
+        let second = service.verify(&service.issue(&user_id).unwrap()).unwrap();
+
+        assert_ne!(first.jti, second.jti);
+    }
+
+    #[test]
+    fn test_rs256_and_eddsa_tokens_carry_the_kid() {
+        for (kid, algorithm) in [("rsa-1", Algorithm::RS256), ("ed-1", Algorithm::EdDSA)] {
+            let service = key_service(kid);
+            let token = service.issue(&Uuid::new_v4()).unwrap();
+
+            let header = decode_header(&token).unwrap();
+            assert_eq!(header.kid.as_deref(), Some(kid));
+            assert_eq!(header.alg, algorithm);
+            assert!(service.verify(&token).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_rotation_keeps_tokens_signed_with_previous_key() {
+        // Token emitido antes da troca da chave ativa
+        let token = key_service("rsa-1").issue(&Uuid::new_v4()).unwrap();
+
+        let rotated = key_service("ed-1");
+        assert!(rotated.verify(&token).is_ok());
+        assert_eq!(decode_header(&rotated.issue(&Uuid::new_v4()).unwrap()).unwrap().kid.as_deref(), Some("ed-1"));
+
+        // Depois que a chave antiga é removida, os tokens dela são recusados
+        let without_old_key = JwtTokenService::with_private_keys(
+            &[("ed-1".to_string(), ED25519_KEY.to_vec())],
+            "ed-1",
+            settings(),
+        )
+        .unwrap();
+        assert_invalid_token(without_old_key.verify(&token));
+    }
+
+    #[test]
+    fn test_jwks_publishes_public_keys() {
+        let jwks = key_service("rsa-1").jwks();
+
+        assert_eq!(jwks.keys.len(), 2);
+        let rsa = jwks.keys.iter().find(|key| key.kid == "rsa-1").unwrap();
+        assert_eq!((rsa.kty.as_str(), rsa.alg.as_str(), rsa.key_use.as_str()), ("RSA", "RS256", "sig"));
+        assert_eq!(rsa.e.as_deref(), Some("AQAB"));
+        assert!(rsa.n.as_ref().is_some_and(|n| n.len() > 300));
+
+        let ed = jwks.keys.iter().find(|key| key.kid == "ed-1").unwrap();
+        assert_eq!((ed.kty.as_str(), ed.crv.as_deref()), ("OKP", Some("Ed25519")));
+        assert_eq!(ed.x.as_ref().map(String::len), Some(43));
+
+        // Nenhuma parte privada da chave é publicada
+        let json = serde_json::to_string(&jwks).unwrap();
+        assert!(!json.contains("\"d\""));
+    }
+
+    #[test]
+    fn test_challenge_and_access_tokens_are_not_interchangeable() {
+        let service = key_service("ed-1");
+        let user_id = Uuid::new_v4();
+
+        let challenge = service.issue_challenge(&user_id).unwrap();
+        let claims = service.verify_challenge(&challenge).unwrap();
+        assert_eq!(claims.sub, user_id.to_string());
+        assert_eq!(claims.aud, "troca-livros:2fa");
+        assert!(claims.exp - claims.iat <= CHALLENGE_EXPIRES_IN_SECONDS as usize);
+
+        // O desafio não dá acesso à API e o token de acesso não substitui o desafio
+        assert_invalid_token(service.verify(&challenge));
+        assert_invalid_token(service.verify_challenge(&service.issue(&user_id).unwrap()));
+    }
+
+    #[test]
+    fn test_rejects_wrong_issuer_audience_and_expired_tokens() {
+        let service = JwtTokenService::with_secret("segredo", settings());
+        let now = chrono::Utc::now().timestamp() as usize;
+        let sign = |claims: &TokenClaims| {
+            encode(&Header::new(Algorithm::HS256), claims, &EncodingKey::from_secret(b"segredo")).unwrap()
+        };
+        let valid = TokenClaims {
+            sub: Uuid::new_v4().to_string(),
+            iss: "troca-livros-api".to_string(),
+            aud: "troca-livros".to_string(),
+            jti: Uuid::new_v4().to_string(),
+            iat: now,
+            exp: now + 3600,
+        };
+        assert!(service.verify(&sign(&valid)).is_ok());
+
+        assert_invalid_token(service.verify(&sign(&TokenClaims { iss: "outro".to_string(), ..valid.clone() })));
+        assert_invalid_token(service.verify(&sign(&TokenClaims { aud: "outro".to_string(), ..valid.clone() })));
+        assert_invalid_token(service.verify(&sign(&TokenClaims { jti: "123".to_string(), ..valid.clone() })));
+        assert_invalid_token(service.verify(&sign(&TokenClaims { exp: now - 3600, ..valid.clone() })));
+    }
+
+    #[test]
+    fn test_rejects_unknown_kid_and_algorithm_confusion() {
+        let service = key_service("rsa-1");
+
+        // Token HS256 "assinado" com a chave pública não pode passar por RS256
+        let mut header = Header::new(Algorithm::HS256);
+        header.kid = Some("rsa-1".to_string());
+        let claims = service.verify(&service.issue(&Uuid::new_v4()).unwrap()).unwrap();
+        let forged = encode(&header, &claims, &EncodingKey::from_secret(b"qualquer")).unwrap();
+        assert_invalid_token(service.verify(&forged));
+
+        header.kid = Some("desconhecida".to_string());
+        let unknown = encode(&header, &claims, &EncodingKey::from_secret(b"qualquer")).unwrap();
+        assert_invalid_token(service.verify(&unknown));
+    }
+
+    #[test]
+    fn test_invalid_key_configuration() {
+        let missing_active = JwtTokenService::with_private_keys(
+            &[("rsa-1".to_string(), RSA_KEY.to_vec())],
+            "outra",
+            settings(),
+        );
+        assert!(missing_active.is_err());
+
+        let invalid_pem = JwtTokenService::with_private_keys(
+            &[("rsa-1".to_string(), b"nao e uma chave".to_vec())],
+            "rsa-1",
+            settings(),
+        );
+        assert!(invalid_pem.is_err());
+    }
+}
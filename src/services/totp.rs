@@ -0,0 +1,114 @@
+use ring::constant_time::verify_slices_are_equal;
+use ring::hmac;
+
+/// Duração de cada código, em segundos
+pub const TOTP_STEP_SECONDS: u64 = 30;
+/// Quantidade de dígitos de cada código
+pub const TOTP_DIGITS: u32 = 6;
+/// Intervalos aceitos antes e depois do atual, para tolerar relógios adiantados ou atrasados
+const TOTP_SKEW_STEPS: u64 = 1;
+
+/// Alfabeto base32 da RFC 4648, usado pelos aplicativos autenticadores
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// Codifica em base32 sem o preenchimento `=`
+pub fn base32_encode(bytes: &[u8]) -> String {
+    let mut output = String::with_capacity(bytes.len().div_ceil(5) * 8);
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+
+    for &byte in bytes {
+        buffer = (buffer << 8) | u32::from(byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            output.push(BASE32_ALPHABET[((buffer >> bits) & 0x1f) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        output.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 0x1f) as usize] as char);
+    }
+
+    output
+}
+
+/// Decodifica base32, ignorando maiúsculas, espaços e o preenchimento
+pub fn base32_decode(encoded: &str) -> Option<Vec<u8>> {
+    let mut output = Vec::with_capacity(encoded.len() * 5 / 8);
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+
+    for c in encoded.chars().filter(|c| !c.is_whitespace() && *c != '=') {
+        let value = BASE32_ALPHABET
+            .iter()
+            .position(|&a| a as char == c.to_ascii_uppercase())?;
+        buffer = (buffer << 5) | value as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            output.push((buffer >> bits) as u8);
+        }
+    }
+
+    Some(output)
+}
+
+/// Intervalo de 30 segundos correspondente ao instante (segundos desde 1970)
+pub fn step_at(unix_seconds: u64) -> u64 {
+    unix_seconds / TOTP_STEP_SECONDS
+}
+
+/// Código do intervalo (HOTP da RFC 4226 com HMAC-SHA1, como na RFC 6238)
+pub fn code_at(secret: &[u8], step: u64) -> String {
+    let key = hmac::Key::new(hmac::HMAC_SHA1_FOR_LEGACY_USE_ONLY, secret);
+    let digest = hmac::sign(&key, &step.to_be_bytes());
+    let digest = digest.as_ref();
+
+    // Truncamento dinâmico
+    let offset = (digest[digest.len() - 1] & 0x0f) as usize;
+    let binary = u32::from_be_bytes([
+        digest[offset] & 0x7f,
+        digest[offset + 1],
+        digest[offset + 2],
+        digest[offset + 3],
+    ]);
+
+    format!("{:0width$}", binary % 10u32.pow(TOTP_DIGITS), width = TOTP_DIGITS as usize)
+}
+
+/// Procura o código nos intervalos próximos ao atual e retorna o intervalo
+/// correspondente, para que o mesmo código não seja aceito duas vezes
+pub fn verify_code(secret: &[u8], code: &str, current_step: u64) -> Option<u64> {
+    let code = code.trim();
+    if code.len() != TOTP_DIGITS as usize {
+        return None;
+    }
+
+    let first = current_step.saturating_sub(TOTP_SKEW_STEPS);
+    (first..=current_step + TOTP_SKEW_STEPS)
+        .find(|&step| verify_slices_are_equal(code_at(secret, step).as_bytes(), code.as_bytes()).is_ok())
+}
+
+/// URI `otpauth://` que os aplicativos autenticadores leem do QR code
+pub fn provisioning_uri(secret_base32: &str, account: &str, issuer: &str) -> String {
+    let label = format!("{}:{}", issuer, account);
+    format!(
+        "otpauth://totp/{}?secret={}&issuer={}&algorithm=SHA1&digits={}&period={}",
+        percent_encode(&label),
+        secret_base32,
+        percent_encode(issuer),
+        TOTP_DIGITS,
+        TOTP_STEP_SECONDS
+    )
+}
+
+// Codificação de URI em que apenas os caracteres não reservados ficam literais
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => (byte as char).to_string(),
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
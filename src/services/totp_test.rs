@@ -0,0 +1,73 @@
+use crate::services::totp::{base32_decode, base32_encode, code_at, provisioning_uri, step_at, verify_code};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Segredo dos vetores de teste SHA-1 da RFC 6238
+    const RFC_SECRET: &[u8] = b"12345678901234567890";
+
+    #[test]
+    fn test_base32_matches_rfc_4648() {
+        let cases = [
+            ("", ""),
+            ("f", "MY"),
+            ("fo", "MZXQ"),
+            ("foo", "MZXW6"),
+            ("foob", "MZXW6YQ"),
+            ("fooba", "MZXW6YTB"),
+            ("foobar", "MZXW6YTBOI"),
+        ];
+
+        for (decoded, encoded) in cases {
+            assert_eq!(base32_encode(decoded.as_bytes()), encoded);
+            assert_eq!(base32_decode(encoded).unwrap(), decoded.as_bytes());
+        }
+
+        // Minúsculas, espaços e preenchimento são aceitos na leitura
+        assert_eq!(base32_decode("mzxw 6ytb oi======").unwrap(), b"foobar");
+        assert!(base32_decode("MZXW1").is_none());
+    }
+
+    #[test]
+    fn test_codes_match_rfc_6238() {
+        // Últimos 6 dígitos dos códigos de 8 dígitos da RFC
+        let cases = [
+            (59, "287082"),
+            (1111111109, "081804"),
+            (1111111111, "050471"),
+            (1234567890, "005924"),
+            (2000000000, "279037"),
+        ];
+
+        for (time, code) in cases {
+            assert_eq!(code_at(RFC_SECRET, step_at(time)), code, "T = {}", time);
+        }
+    }
+
+    #[test]
+    fn test_verify_accepts_adjacent_steps_only() {
+        let step = step_at(1111111111);
+        let code = code_at(RFC_SECRET, step);
+
+        assert_eq!(verify_code(RFC_SECRET, &code, step), Some(step));
+        assert_eq!(verify_code(RFC_SECRET, &code, step + 1), Some(step));
+        assert_eq!(verify_code(RFC_SECRET, &code, step - 1), Some(step));
+        assert_eq!(verify_code(RFC_SECRET, &code, step + 2), None);
+
+        assert_eq!(verify_code(RFC_SECRET, &format!(" {} ", code), step), Some(step));
+        assert_eq!(verify_code(RFC_SECRET, "12345", step), None);
+        assert_eq!(verify_code(RFC_SECRET, "abcdef", step), None);
+    }
+
+    #[test]
+    fn test_provisioning_uri() {
+        let uri = provisioning_uri("GEZDGNBVGY3TQOJQ", "ana@example.com", "Troca Livros");
+
+        assert_eq!(
+            uri,
+            "otpauth://totp/Troca%20Livros%3Aana%40example.com?secret=GEZDGNBVGY3TQOJQ\
+             &issuer=Troca%20Livros&algorithm=SHA1&digits=6&period=30"
+        );
+    }
+}
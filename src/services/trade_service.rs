@@ -1,42 +1,214 @@
 use std::sync::Arc;
+use std::time::Instant;
 use uuid::Uuid;
 use async_trait::async_trait;
 
-use crate::error::AppError;
-use crate::models::trade::PossibleTrade;
+use crate::error::{AppError, ErrorCode};
+use crate::i18n::messages;
+use crate::models::trade::{CreateTradeDto, PossibleTrade, ProposeTradeRequest, Trade, TradeStatus};
+use crate::repositories::books_offered_repository::BooksOfferedRepository;
 use crate::repositories::trade_repository::TradeRepository;
+use crate::services::book_photo_service::BookPhotoService;
+use crate::services::metrics::metrics;
 
 #[async_trait]
 pub trait TradeService: Send + Sync {
     async fn find_possible_trades(&self, user_id: Uuid) -> Result<Vec<PossibleTrade>, AppError>;
+    async fn propose_trade(&self, user_id: Uuid, request: &ProposeTradeRequest) -> Result<Trade, AppError>;
+    async fn find_user_trades(&self, user_id: Uuid) -> Result<Vec<Trade>, AppError>;
+    async fn accept_trade(&self, trade_id: Uuid, user_id: Uuid) -> Result<Trade, AppError>;
+    async fn reject_trade(&self, trade_id: Uuid, user_id: Uuid) -> Result<Trade, AppError>;
+    async fn cancel_trade(&self, trade_id: Uuid, user_id: Uuid) -> Result<Trade, AppError>;
+    async fn complete_trade(&self, trade_id: Uuid, user_id: Uuid) -> Result<Trade, AppError>;
 }
 
 pub struct TradeServiceImpl {
     trade_repository: Arc<dyn TradeRepository>,
+    books_offered_repository: Arc<dyn BooksOfferedRepository>,
+    book_photo_service: Arc<dyn BookPhotoService>,
 }
 
 impl TradeServiceImpl {
-    pub fn new(trade_repository: Arc<dyn TradeRepository>) -> Self {
-        Self { trade_repository }
+    pub fn new(
+        trade_repository: Arc<dyn TradeRepository>,
+        books_offered_repository: Arc<dyn BooksOfferedRepository>,
+        book_photo_service: Arc<dyn BookPhotoService>,
+    ) -> Self {
+        Self {
+            trade_repository,
+            books_offered_repository,
+            book_photo_service,
+        }
+    }
+
+    // Busca uma troca da qual o usuário participa
+    async fn find_party_trade(&self, trade_id: Uuid, user_id: Uuid) -> Result<Trade, AppError> {
+        self.trade_repository
+            .find_by_id(&trade_id)
+            .await?
+            .filter(|trade| trade.proposer_id == user_id || trade.partner_id == user_id)
+            .ok_or_else(|| AppError::NotFoundError(messages::TRADE_NOT_FOUND.text()))
+    }
+
+    // Aplica a transição de situação, falhando se a troca já tiver mudado
+    async fn transition(
+        &self,
+        trade: &Trade,
+        from: TradeStatus,
+        to: TradeStatus,
+    ) -> Result<Trade, AppError> {
+        if trade.status != from {
+            return Err(invalid_status());
+        }
+
+        self.trade_repository
+            .update_status(&trade.id, from, to)
+            .await?
+            .ok_or_else(invalid_status)
     }
 }
 
+fn invalid_status() -> AppError {
+    AppError::Conflict(
+        ErrorCode::TradeStatusConflict,
+        messages::TRADE_STATUS_CONFLICT.text(),
+    )
+}
+
 #[async_trait]
 impl TradeService for TradeServiceImpl {
     /// Busca todas as trocas possíveis para um usuário
-    /// 
+    ///
     /// Retorna uma lista de trocas onde:
     /// - O usuário oferece um livro que outro usuário quer
     /// - O outro usuário oferece um livro que o usuário quer
-    /// 
+    ///
     /// # Arguments
-    /// 
+    ///
     /// * `user_id` - UUID do usuário para buscar trocas possíveis
-    /// 
+    ///
     /// # Returns
-    /// 
+    ///
     /// * `Result<Vec<PossibleTrade>, AppError>` - Lista de trocas possíveis ou erro
     async fn find_possible_trades(&self, user_id: Uuid) -> Result<Vec<PossibleTrade>, AppError> {
-        self.trade_repository.find_possible_trades(user_id).await
+        let started = Instant::now();
+        let result = self.trade_repository.find_possible_trades(user_id).await;
+        metrics().observe_trade_matcher("find_possible_trades", started.elapsed());
+        result
+    }
+
+    /// Propõe a troca de um exemplar do usuário por um exemplar de outro usuário
+    ///
+    /// Os dois exemplares ficam reservados enquanto a troca estiver em andamento,
+    /// de modo que não possam ser prometidos em outra proposta.
+    async fn propose_trade(&self, user_id: Uuid, request: &ProposeTradeRequest) -> Result<Trade, AppError> {
+        let offered = self
+            .books_offered_repository
+            .find_by_id(&request.offered_copy_id)
+            .await?
+            .filter(|copy| copy.user_id == user_id)
+            .ok_or_else(|| {
+                AppError::Rejected(ErrorCode::CopyNotOffered, messages::COPY_NOT_OFFERED.text())
+            })?;
+
+        let wanted = self
+            .books_offered_repository
+            .find_by_id(&request.wanted_copy_id)
+            .await?
+            .ok_or_else(|| AppError::NotFoundError(messages::COPY_NOT_FOUND.text()))?;
+
+        if wanted.user_id == user_id {
+            return Err(AppError::Rejected(
+                ErrorCode::TradeWithSelf,
+                messages::TRADE_WITH_SELF.text(),
+            ));
+        }
+
+        if !offered.attributes.available || !wanted.attributes.available {
+            return Err(AppError::Rejected(
+                ErrorCode::CopyNotAvailable,
+                messages::COPY_NOT_AVAILABLE.text(),
+            ));
+        }
+
+        let create_dto = CreateTradeDto {
+            proposer_id: user_id,
+            partner_id: wanted.user_id,
+            offered_book_id: offered.book_id,
+            offered_copy_id: offered.id,
+            wanted_book_id: wanted.book_id,
+            wanted_copy_id: wanted.id,
+        };
+
+        self.trade_repository.create(&create_dto).await
+    }
+
+    async fn find_user_trades(&self, user_id: Uuid) -> Result<Vec<Trade>, AppError> {
+        self.trade_repository.find_by_user_id(&user_id).await
+    }
+
+    async fn accept_trade(&self, trade_id: Uuid, user_id: Uuid) -> Result<Trade, AppError> {
+        let trade = self.find_party_trade(trade_id, user_id).await?;
+
+        if trade.partner_id != user_id {
+            return Err(AppError::Forbidden(messages::TRADE_ACCEPT_FORBIDDEN.text()));
+        }
+
+        self.transition(&trade, TradeStatus::Pending, TradeStatus::Accepted).await
+    }
+
+    async fn reject_trade(&self, trade_id: Uuid, user_id: Uuid) -> Result<Trade, AppError> {
+        let trade = self.find_party_trade(trade_id, user_id).await?;
+
+        if trade.partner_id != user_id {
+            return Err(AppError::Forbidden(messages::TRADE_REJECT_FORBIDDEN.text()));
+        }
+
+        self.transition(&trade, TradeStatus::Pending, TradeStatus::Rejected).await
+    }
+
+    /// Cancela uma troca em andamento
+    ///
+    /// Uma proposta pendente só pode ser cancelada por quem a fez; uma troca
+    /// aceita pode ser cancelada por qualquer uma das partes.
+    async fn cancel_trade(&self, trade_id: Uuid, user_id: Uuid) -> Result<Trade, AppError> {
+        let trade = self.find_party_trade(trade_id, user_id).await?;
+
+        if !trade.status.is_open() {
+            return Err(invalid_status());
+        }
+
+        if trade.status == TradeStatus::Pending && trade.proposer_id != user_id {
+            return Err(AppError::Forbidden(messages::TRADE_CANCEL_FORBIDDEN.text()));
+        }
+
+        self.transition(&trade, trade.status, TradeStatus::Cancelled).await
     }
-} 
\ No newline at end of file
+
+    /// Conclui uma troca aceita, retirando os exemplares entregues das listas dos donos
+    /// e movendo os livros recebidos dos desejados para os possuídos de cada parte
+    async fn complete_trade(&self, trade_id: Uuid, user_id: Uuid) -> Result<Trade, AppError> {
+        let trade = self.find_party_trade(trade_id, user_id).await?;
+
+        if trade.status != TradeStatus::Accepted {
+            return Err(invalid_status());
+        }
+
+        // As fotos dos exemplares entregues saem do banco com eles; os arquivos só
+        // são apagados depois que a conclusão for gravada
+        let copy_ids: Vec<Uuid> = [trade.offered_copy_id, trade.wanted_copy_id]
+            .into_iter()
+            .flatten()
+            .collect();
+        let photo_keys = self.book_photo_service.storage_keys(&copy_ids).await?;
+
+        let completed = self
+            .trade_repository
+            .complete(&trade.id)
+            .await?
+            .ok_or_else(invalid_status)?;
+        self.book_photo_service.delete_stored(&photo_keys).await;
+
+        Ok(completed)
+    }
+}
@@ -0,0 +1,282 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use ring::digest::{digest, SHA256};
+use ring::rand::{SecureRandom, SystemRandom};
+use uuid::Uuid;
+
+use crate::error::{AppError, ErrorCode};
+use crate::i18n::messages;
+use crate::models::two_factor::{DisableTwoFactorDto, RecoveryCodes, TwoFactorSetup};
+use crate::models::user::{TokenResponse, User, UserResponse};
+use crate::repositories::two_factor_repository::TwoFactorRepository;
+use crate::repositories::user_repository::UserRepository;
+use crate::services::password_service::PasswordService;
+use crate::services::rate_limit_service::{RateLimitService, FAILED_TWO_FACTOR_LIMIT};
+use crate::services::token_service::TokenService;
+use crate::services::totp;
+
+/// Nome exibido no aplicativo autenticador
+pub const TOTP_ISSUER: &str = "Troca Livros";
+/// Quantidade de códigos de recuperação gerados na ativação
+pub const RECOVERY_CODE_COUNT: usize = 10;
+/// Tamanho do segredo TOTP, em bytes (160 bits, como recomenda a RFC 4226)
+const SECRET_BYTES: usize = 20;
+
+/// Verificação em duas etapas com TOTP (RFC 6238)
+#[async_trait]
+pub trait TwoFactorService: Send + Sync + 'static {
+    /// Gera um novo segredo, que só passa a valer depois de confirmado
+    async fn setup(&self, user_id: &Uuid) -> Result<TwoFactorSetup, AppError>;
+
+    /// Confirma o segredo com um código do autenticador e ativa a verificação
+    async fn enable(&self, user_id: &Uuid, code: &str) -> Result<RecoveryCodes, AppError>;
+
+    /// Desativa a verificação, mediante a senha do usuário ou um código
+    async fn disable(&self, user_id: &Uuid, proof: &DisableTwoFactorDto) -> Result<(), AppError>;
+
+    /// Troca o desafio do login e um código (do autenticador ou de
+    /// recuperação) pelo token de acesso
+    async fn verify_login(&self, challenge_token: &str, code: &str) -> Result<TokenResponse, AppError>;
+}
+
+/// TwoFactorService com segredos e códigos de recuperação no banco
+///
+/// Os códigos de recuperação têm 80 bits aleatórios, então são guardados
+/// apenas como hash SHA-256. Após várias tentativas com códigos inválidos, a
+/// verificação da conta fica bloqueada temporariamente.
+pub struct TwoFactorServiceImpl {
+    two_factor_repository: Arc<dyn TwoFactorRepository>,
+    user_repository: Arc<dyn UserRepository>,
+    password_service: Arc<dyn PasswordService>,
+    token_service: Arc<dyn TokenService>,
+    rate_limit_service: Arc<dyn RateLimitService>,
+    rng: SystemRandom,
+}
+
+impl TwoFactorServiceImpl {
+    pub fn new(
+        two_factor_repository: Arc<dyn TwoFactorRepository>,
+        user_repository: Arc<dyn UserRepository>,
+        password_service: Arc<dyn PasswordService>,
+        token_service: Arc<dyn TokenService>,
+        rate_limit_service: Arc<dyn RateLimitService>,
+    ) -> Self {
+        Self {
+            two_factor_repository,
+            user_repository,
+            password_service,
+            token_service,
+            rate_limit_service,
+            rng: SystemRandom::new(),
+        }
+    }
+
+    fn random_bytes<const N: usize>(&self) -> Result<[u8; N], AppError> {
+        let mut bytes = [0u8; N];
+        self.rng
+            .fill(&mut bytes)
+            .map_err(|_| AppError::InternalServerError("Falha ao gerar valor aleatório".to_string()))?;
+        Ok(bytes)
+    }
+
+    // Códigos no formato xxxx-xxxx-xxxx-xxxx
+    fn generate_recovery_code(&self) -> Result<String, AppError> {
+        let encoded = totp::base32_encode(&self.random_bytes::<10>()?).to_lowercase();
+        let groups: Vec<&str> = encoded.as_bytes().chunks(4).filter_map(|c| std::str::from_utf8(c).ok()).collect();
+        Ok(groups.join("-"))
+    }
+
+    async fn find_user(&self, user_id: &Uuid) -> Result<User, AppError> {
+        self.user_repository
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFoundError(messages::USER_NOT_FOUND.text()))
+    }
+
+    // Aceita o código do autenticador (cada intervalo uma única vez) ou um
+    // código de recuperação ainda não usado
+    async fn accept_code(&self, user_id: &Uuid, secret: &str, code: &str) -> Result<bool, AppError> {
+        let secret = decode_secret(secret)?;
+        if let Some(step) = totp::verify_code(&secret, code, current_step()) {
+            return self.two_factor_repository.use_step(user_id, step as i64).await;
+        }
+
+        self.two_factor_repository
+            .use_recovery_code(user_id, &hash_recovery_code(code))
+            .await
+    }
+
+    // Conta a tentativa antes de conferir o código, para que requisições
+    // simultâneas não escapem do bloqueio; o código aceito zera a contagem
+    async fn accept_code_throttled(&self, user_id: &Uuid, secret: &str, code: &str) -> Result<bool, AppError> {
+        let key = format!("failed_two_factor:{}", user_id);
+
+        self.rate_limit_service
+            .hit(&key, FAILED_TWO_FACTOR_LIMIT)
+            .await
+            .map_err(|e| match e {
+                AppError::TooManyRequests(_, retry_after) => {
+                    AppError::TooManyRequests(messages::TWO_FACTOR_LOCKED.format(&[&retry_after]), retry_after)
+                }
+                other => other,
+            })?;
+
+        let accepted = self.accept_code(user_id, secret, code).await?;
+        if accepted {
+            self.rate_limit_service.reset(&key).await?;
+        }
+
+        Ok(accepted)
+    }
+}
+
+fn current_step() -> u64 {
+    totp::step_at(Utc::now().timestamp().max(0) as u64)
+}
+
+fn decode_secret(secret: &str) -> Result<Vec<u8>, AppError> {
+    totp::base32_decode(secret)
+        .ok_or_else(|| AppError::InternalServerError("Segredo TOTP inválido".to_string()))
+}
+
+/// Hash de um código de recuperação, sem diferenciar maiúsculas, hífens ou espaços
+pub fn hash_recovery_code(code: &str) -> String {
+    let normalized: String = code
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+
+    digest(&SHA256, normalized.as_bytes())
+        .as_ref()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+#[async_trait]
+impl TwoFactorService for TwoFactorServiceImpl {
+    async fn setup(&self, user_id: &Uuid) -> Result<TwoFactorSetup, AppError> {
+        let user = self.find_user(user_id).await?;
+        let secret = totp::base32_encode(&self.random_bytes::<SECRET_BYTES>()?);
+
+        if !self.two_factor_repository.save_pending_secret(user_id, &secret).await? {
+            return Err(AppError::Conflict(
+                ErrorCode::TwoFactorAlreadyEnabled,
+                messages::TWO_FACTOR_ALREADY_ENABLED.text(),
+            ));
+        }
+
+        Ok(TwoFactorSetup {
+            provisioning_uri: totp::provisioning_uri(&secret, &user.email, TOTP_ISSUER),
+            secret,
+        })
+    }
+
+    async fn enable(&self, user_id: &Uuid, code: &str) -> Result<RecoveryCodes, AppError> {
+        let two_factor = self
+            .two_factor_repository
+            .find(user_id)
+            .await?
+            .ok_or_else(|| {
+                AppError::Rejected(ErrorCode::TwoFactorNotStarted, messages::TWO_FACTOR_NOT_STARTED.text())
+            })?;
+
+        if two_factor.enabled {
+            return Err(AppError::Conflict(
+                ErrorCode::TwoFactorAlreadyEnabled,
+                messages::TWO_FACTOR_ALREADY_ENABLED.text(),
+            ));
+        }
+
+        let step = totp::verify_code(&decode_secret(&two_factor.secret)?, code, current_step())
+            .ok_or_else(|| {
+                AppError::Rejected(ErrorCode::TwoFactorCodeInvalid, messages::TWO_FACTOR_CODE_INVALID.text())
+            })?;
+
+        let recovery_codes = (0..RECOVERY_CODE_COUNT)
+            .map(|_| self.generate_recovery_code())
+            .collect::<Result<Vec<_>, _>>()?;
+        let hashes: Vec<String> = recovery_codes.iter().map(|code| hash_recovery_code(code)).collect();
+
+        self.two_factor_repository.enable(user_id, step as i64, &hashes).await?;
+
+        Ok(RecoveryCodes { recovery_codes })
+    }
+
+    async fn disable(&self, user_id: &Uuid, proof: &DisableTwoFactorDto) -> Result<(), AppError> {
+        match (&proof.password, &proof.code) {
+            (Some(password), _) => {
+                let user = self.find_user(user_id).await?;
+
+                if !self.password_service.verify_password(password, &user.hash_password).await? {
+                    return Err(AppError::Forbidden(messages::PASSWORD_INCORRECT.text()));
+                }
+
+                if self.two_factor_repository.find(user_id).await?.is_none() {
+                    return Err(AppError::Rejected(
+                        ErrorCode::TwoFactorNotEnabled,
+                        messages::TWO_FACTOR_NOT_ENABLED.text(),
+                    ));
+                }
+            }
+            // Contas criadas pelo login com Google ou GitHub não têm uma senha conhecida
+            (None, Some(code)) => {
+                let two_factor = self
+                    .two_factor_repository
+                    .find(user_id)
+                    .await?
+                    .filter(|two_factor| two_factor.enabled)
+                    .ok_or_else(|| {
+                        AppError::Rejected(ErrorCode::TwoFactorNotEnabled, messages::TWO_FACTOR_NOT_ENABLED.text())
+                    })?;
+
+                if !self.accept_code_throttled(user_id, &two_factor.secret, code).await? {
+                    return Err(AppError::Forbidden(messages::TWO_FACTOR_CODE_INVALID.text()));
+                }
+            }
+            (None, None) => {
+                return Err(AppError::ValidationError(messages::TWO_FACTOR_PROOF_REQUIRED.text()));
+            }
+        }
+
+        self.two_factor_repository.delete(user_id).await
+    }
+
+    async fn verify_login(&self, challenge_token: &str, code: &str) -> Result<TokenResponse, AppError> {
+        let claims = self.token_service.verify_challenge(challenge_token)?;
+        let user_id = Uuid::parse_str(&claims.sub)
+            .map_err(|_| AppError::AuthError(messages::TOKEN_USER_INVALID.text()))?;
+
+        // A verificação pode ter sido desativada depois do login
+        let two_factor = self
+            .two_factor_repository
+            .find(&user_id)
+            .await?
+            .filter(|two_factor| two_factor.enabled)
+            .ok_or_else(|| AppError::AuthError(messages::TWO_FACTOR_NOT_ENABLED.text()))?;
+
+        if !self.accept_code_throttled(&user_id, &two_factor.secret, code).await? {
+            return Err(AppError::AuthError(messages::TWO_FACTOR_CODE_INVALID.text()));
+        }
+
+        let user = self
+            .user_repository
+            .find_by_id(&user_id)
+            .await?
+            .ok_or_else(|| AppError::AuthError(messages::TOKEN_USER_INVALID.text()))?;
+
+        // A conta pode ter sido suspensa depois do login
+        if user.banned_at.is_some() {
+            return Err(AppError::Forbidden(messages::ACCOUNT_BANNED.text()));
+        }
+
+        Ok(TokenResponse {
+            access_token: self.token_service.issue(&user.id)?,
+            token_type: "Bearer".to_string(),
+            user: UserResponse::from(user),
+        })
+    }
+}
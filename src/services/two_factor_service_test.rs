@@ -0,0 +1,374 @@
+use std::sync::{Arc, Mutex};
+
+use chrono::{Duration, Utc};
+use mockall::predicate;
+use uuid::Uuid;
+
+use crate::error::{AppError, ErrorCode};
+use crate::models::two_factor::{DisableTwoFactorDto, TwoFactorSecret};
+use crate::models::user::User;
+use crate::repositories::rate_limit_repository::InMemoryRateLimitRepository;
+use crate::services::auth_service_test::{create_mock_password_service, create_test_user, MockUserRepository};
+use crate::services::rate_limit_service::{RateLimitServiceImpl, FAILED_TWO_FACTOR_LIMIT};
+use crate::services::test_mocks::MockTwoFactorRepository;
+use crate::services::token_service::{JwtTokenService, TokenService, TokenSettings};
+use crate::services::totp::{base32_decode, code_at, step_at};
+use crate::services::two_factor_service::{
+    hash_recovery_code, TwoFactorService, TwoFactorServiceImpl, RECOVERY_CODE_COUNT,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+
+    fn token_service() -> Arc<JwtTokenService> {
+        Arc::new(JwtTokenService::with_secret(
+            "segredo",
+            TokenSettings {
+                issuer: "troca-livros-api".to_string(),
+                audience: "troca-livros".to_string(),
+                expires_in: Duration::hours(1),
+            },
+        ))
+    }
+
+    fn current_code() -> String {
+        code_at(&base32_decode(SECRET).unwrap(), step_at(Utc::now().timestamp() as u64))
+    }
+
+    fn enabled_secret() -> Option<TwoFactorSecret> {
+        Some(TwoFactorSecret {
+            secret: SECRET.to_string(),
+            enabled: true,
+        })
+    }
+
+    fn password(password: &str) -> DisableTwoFactorDto {
+        DisableTwoFactorDto {
+            password: Some(password.to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn user_repository_with(user_id: Uuid) -> MockUserRepository {
+        let mut user_repository = MockUserRepository::new();
+        user_repository.expect_find_by_id().returning(move |_| {
+            Ok(Some(User {
+                id: user_id,
+                ..create_test_user("Ana", "ana@example.com")
+            }))
+        });
+        user_repository
+    }
+
+    fn create_service(
+        repository: MockTwoFactorRepository,
+        user_repository: MockUserRepository,
+        password_valid: bool,
+    ) -> TwoFactorServiceImpl {
+        TwoFactorServiceImpl::new(
+            Arc::new(repository),
+            Arc::new(user_repository),
+            create_mock_password_service("hash".to_string(), password_valid),
+            token_service(),
+            Arc::new(RateLimitServiceImpl::new(Arc::new(InMemoryRateLimitRepository::new()))),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_setup_returns_secret_and_provisioning_uri() {
+        let user_id = Uuid::new_v4();
+        let saved = Arc::new(Mutex::new(String::new()));
+        let saved_clone = saved.clone();
+        let mut repository = MockTwoFactorRepository::new();
+        repository
+            .expect_save_pending_secret()
+            .withf(move |id, _| *id == user_id)
+            .times(1)
+            .returning(move |_, secret| {
+                *saved_clone.lock().unwrap() = secret.to_string();
+                Ok(true)
+            });
+
+        let service = create_service(repository, user_repository_with(user_id), true);
+        let setup = service.setup(&user_id).await.unwrap();
+
+        assert_eq!(setup.secret, *saved.lock().unwrap());
+        assert_eq!(base32_decode(&setup.secret).unwrap().len(), 20);
+        assert!(setup
+            .provisioning_uri
+            .starts_with("otpauth://totp/Troca%20Livros%3Aana%40example.com?secret="));
+        assert!(setup.provisioning_uri.contains(&setup.secret));
+    }
+
+    #[tokio::test]
+    async fn test_setup_conflicts_when_already_enabled() {
+        let user_id = Uuid::new_v4();
+        let mut repository = MockTwoFactorRepository::new();
+        repository.expect_save_pending_secret().returning(|_, _| Ok(false));
+
+        let service = create_service(repository, user_repository_with(user_id), true);
+
+        match service.setup(&user_id).await {
+            Err(AppError::Conflict(ErrorCode::TwoFactorAlreadyEnabled, _)) => {}
+            other => panic!("Esperava conflito, obteve {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_enable_stores_only_hashes_of_recovery_codes() {
+        let user_id = Uuid::new_v4();
+        let stored = Arc::new(Mutex::new(Vec::new()));
+        let stored_clone = stored.clone();
+        let mut repository = MockTwoFactorRepository::new();
+        repository.expect_find().returning(|_| {
+            Ok(Some(TwoFactorSecret {
+                secret: SECRET.to_string(),
+                enabled: false,
+            }))
+        });
+        repository
+            .expect_enable()
+            .times(1)
+            .returning(move |_, _, hashes| {
+                *stored_clone.lock().unwrap() = hashes.to_vec();
+                Ok(())
+            });
+
+        let service = create_service(repository, MockUserRepository::new(), true);
+        let codes = service.enable(&user_id, &current_code()).await.unwrap().recovery_codes;
+
+        let stored = stored.lock().unwrap();
+        assert_eq!(codes.len(), RECOVERY_CODE_COUNT);
+        assert_eq!(stored.len(), RECOVERY_CODE_COUNT);
+        for (code, hash) in codes.iter().zip(stored.iter()) {
+            assert_eq!(code.len(), 19, "{}", code);
+            assert_eq!(&hash_recovery_code(code), hash);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_enable_requires_setup_and_a_valid_code() {
+        let user_id = Uuid::new_v4();
+
+        let mut repository = MockTwoFactorRepository::new();
+        repository.expect_find().returning(|_| Ok(None));
+        let service = create_service(repository, MockUserRepository::new(), true);
+        assert!(matches!(
+            service.enable(&user_id, "123456").await,
+            Err(AppError::Rejected(ErrorCode::TwoFactorNotStarted, _))
+        ));
+
+        let mut repository = MockTwoFactorRepository::new();
+        repository.expect_find().returning(|_| {
+            Ok(Some(TwoFactorSecret {
+                secret: SECRET.to_string(),
+                enabled: false,
+            }))
+        });
+        repository.expect_enable().times(0);
+        let service = create_service(repository, MockUserRepository::new(), true);
+        let wrong_code = if current_code() == "000000" { "111111" } else { "000000" };
+        assert!(matches!(
+            service.enable(&user_id, wrong_code).await,
+            Err(AppError::Rejected(ErrorCode::TwoFactorCodeInvalid, _))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_disable_requires_the_password() {
+        let user_id = Uuid::new_v4();
+
+        let mut repository = MockTwoFactorRepository::new();
+        repository.expect_delete().times(0);
+        let service = create_service(repository, user_repository_with(user_id), false);
+        assert!(matches!(service.disable(&user_id, &password("errada")).await, Err(AppError::Forbidden(_))));
+
+        let mut repository = MockTwoFactorRepository::new();
+        repository.expect_find().returning(|_| Ok(enabled_secret()));
+        repository
+            .expect_delete()
+            .with(predicate::eq(user_id))
+            .times(1)
+            .returning(|_| Ok(()));
+        let service = create_service(repository, user_repository_with(user_id), true);
+        assert!(service.disable(&user_id, &password("correta")).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_disable_with_code_instead_of_password() {
+        let user_id = Uuid::new_v4();
+        let mut repository = MockTwoFactorRepository::new();
+        repository.expect_find().returning(|_| Ok(enabled_secret()));
+        repository
+            .expect_use_step()
+            .withf(move |id, _| *id == user_id)
+            .times(1)
+            .returning(|_, _| Ok(true));
+        repository.expect_delete().times(1).returning(|_| Ok(()));
+
+        // A senha nem é consultada: a conta pode não ter uma senha conhecida
+        let service = create_service(repository, MockUserRepository::new(), false);
+        let proof = DisableTwoFactorDto {
+            code: Some(current_code()),
+            ..Default::default()
+        };
+        assert!(service.disable(&user_id, &proof).await.is_ok());
+
+        let service = create_service(MockTwoFactorRepository::new(), MockUserRepository::new(), false);
+        assert!(matches!(
+            service.disable(&user_id, &DisableTwoFactorDto::default()).await,
+            Err(AppError::ValidationError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_disable_with_invalid_code_counts_towards_the_lockout() {
+        let user_id = Uuid::new_v4();
+        let mut repository = MockTwoFactorRepository::new();
+        repository.expect_find().returning(|_| Ok(enabled_secret()));
+        repository.expect_use_step().returning(|_, _| Ok(false));
+        repository.expect_use_recovery_code().returning(|_, _| Ok(false));
+        repository.expect_delete().times(0);
+
+        let service = create_service(repository, MockUserRepository::new(), false);
+        let proof = DisableTwoFactorDto {
+            code: Some("codigo-errado".to_string()),
+            ..Default::default()
+        };
+
+        for _ in 0..FAILED_TWO_FACTOR_LIMIT.max_attempts {
+            assert!(matches!(service.disable(&user_id, &proof).await, Err(AppError::Forbidden(_))));
+        }
+        assert!(matches!(
+            service.disable(&user_id, &proof).await,
+            Err(AppError::TooManyRequests(_, _))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_invalid_codes_do_not_bypass_the_lockout() {
+        let user_id = Uuid::new_v4();
+        let checked = Arc::new(Mutex::new(0));
+        let checked_clone = checked.clone();
+        let mut repository = MockTwoFactorRepository::new();
+        repository.expect_find().returning(|_| Ok(enabled_secret()));
+        repository.expect_use_step().returning(|_, _| Ok(false));
+        repository.expect_use_recovery_code().returning(move |_, _| {
+            *checked_clone.lock().unwrap() += 1;
+            Ok(false)
+        });
+
+        let service = Arc::new(create_service(repository, user_repository_with(user_id), true));
+        let challenge = token_service().issue_challenge(&user_id).unwrap();
+
+        let attempts: Vec<_> = (0..20)
+            .map(|_| {
+                let service = service.clone();
+                let challenge = challenge.clone();
+                tokio::spawn(async move { service.verify_login(&challenge, "codigo-errado").await })
+            })
+            .collect();
+        for attempt in attempts {
+            assert!(attempt.await.unwrap().is_err());
+        }
+
+        assert_eq!(*checked.lock().unwrap(), FAILED_TWO_FACTOR_LIMIT.max_attempts as usize);
+    }
+
+    #[tokio::test]
+    async fn test_verify_login_with_authenticator_code() {
+        let user_id = Uuid::new_v4();
+        let mut repository = MockTwoFactorRepository::new();
+        repository.expect_find().returning(|_| Ok(enabled_secret()));
+        repository
+            .expect_use_step()
+            .withf(move |id, _| *id == user_id)
+            .times(1)
+            .returning(|_, _| Ok(true));
+
+        let service = create_service(repository, user_repository_with(user_id), true);
+        let challenge = token_service().issue_challenge(&user_id).unwrap();
+        let token = service.verify_login(&challenge, &current_code()).await.unwrap();
+
+        assert_eq!(token.user.id, user_id);
+        let claims = token_service().verify(&token.access_token).unwrap();
+        assert_eq!(claims.sub, user_id.to_string());
+    }
+
+    #[tokio::test]
+    async fn test_verify_login_rejects_reused_code() {
+        let user_id = Uuid::new_v4();
+        let mut repository = MockTwoFactorRepository::new();
+        repository.expect_find().returning(|_| Ok(enabled_secret()));
+        repository.expect_use_step().returning(|_, _| Ok(false));
+        repository.expect_use_recovery_code().returning(|_, _| Ok(false));
+
+        let service = create_service(repository, user_repository_with(user_id), true);
+        let challenge = token_service().issue_challenge(&user_id).unwrap();
+
+        assert!(matches!(
+            service.verify_login(&challenge, &current_code()).await,
+            Err(AppError::AuthError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_verify_login_with_recovery_code() {
+        let user_id = Uuid::new_v4();
+        let mut repository = MockTwoFactorRepository::new();
+        repository.expect_find().returning(|_| Ok(enabled_secret()));
+        repository
+            .expect_use_recovery_code()
+            .with(predicate::eq(user_id), predicate::eq(hash_recovery_code("abcd-efgh-ijkl-mnop")))
+            .times(1)
+            .returning(|_, _| Ok(true));
+
+        let service = create_service(repository, user_repository_with(user_id), true);
+        let challenge = token_service().issue_challenge(&user_id).unwrap();
+
+        // Maiúsculas e sem hífens, como o usuário pode digitar
+        assert!(service.verify_login(&challenge, "ABCDEFGHIJKLMNOP").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_verify_login_locks_after_invalid_codes() {
+        let user_id = Uuid::new_v4();
+        let mut repository = MockTwoFactorRepository::new();
+        repository.expect_find().returning(|_| Ok(enabled_secret()));
+        repository.expect_use_step().returning(|_, _| Ok(false));
+        repository.expect_use_recovery_code().returning(|_, _| Ok(false));
+
+        let service = create_service(repository, user_repository_with(user_id), true);
+        let challenge = token_service().issue_challenge(&user_id).unwrap();
+
+        for _ in 0..FAILED_TWO_FACTOR_LIMIT.max_attempts {
+            assert!(matches!(
+                service.verify_login(&challenge, "codigo-errado").await,
+                Err(AppError::AuthError(_))
+            ));
+        }
+
+        assert!(matches!(
+            service.verify_login(&challenge, &current_code()).await,
+            Err(AppError::TooManyRequests(_, _))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_verify_login_rejects_access_token_as_challenge() {
+        let user_id = Uuid::new_v4();
+        let mut repository = MockTwoFactorRepository::new();
+        repository.expect_find().times(0);
+
+        let service = create_service(repository, user_repository_with(user_id), true);
+        let access_token = token_service().issue(&user_id).unwrap();
+
+        assert!(matches!(
+            service.verify_login(&access_token, &current_code()).await,
+            Err(AppError::AuthError(_))
+        ));
+    }
+}
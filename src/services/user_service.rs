@@ -0,0 +1,62 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::i18n::messages;
+use crate::models::user::{User, UserLocation};
+use crate::repositories::user_repository::UserRepository;
+
+#[async_trait]
+pub trait UserService: Send + Sync + 'static {
+    /// Usuário dono de um token válido; contas removidas ou suspensas são recusadas
+    async fn find_active_user(&self, user_id: &Uuid) -> Result<User, AppError>;
+    async fn update_location(&self, user_id: &Uuid, location: UserLocation) -> Result<UserLocation, AppError>;
+    async fn clear_location(&self, user_id: &Uuid) -> Result<(), AppError>;
+}
+
+pub struct UserServiceImpl {
+    user_repository: Arc<dyn UserRepository>,
+}
+
+impl UserServiceImpl {
+    pub fn new(user_repository: Arc<dyn UserRepository>) -> Self {
+        Self { user_repository }
+    }
+}
+
+#[async_trait]
+impl UserService for UserServiceImpl {
+    async fn find_active_user(&self, user_id: &Uuid) -> Result<User, AppError> {
+        let user = self
+            .user_repository
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::AuthError(messages::ACCOUNT_NOT_FOUND.text()))?;
+
+        if user.banned_at.is_some() {
+            return Err(AppError::Forbidden(messages::ACCOUNT_BANNED.text()));
+        }
+
+        Ok(user)
+    }
+
+    async fn update_location(&self, user_id: &Uuid, location: UserLocation) -> Result<UserLocation, AppError> {
+        location.validate_all()?;
+
+        if !self.user_repository.update_location(user_id, Some(location)).await? {
+            return Err(AppError::NotFoundError(messages::USER_NOT_FOUND.text()));
+        }
+
+        Ok(location)
+    }
+
+    async fn clear_location(&self, user_id: &Uuid) -> Result<(), AppError> {
+        if !self.user_repository.update_location(user_id, None).await? {
+            return Err(AppError::NotFoundError(messages::USER_NOT_FOUND.text()));
+        }
+
+        Ok(())
+    }
+}
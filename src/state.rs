@@ -0,0 +1,226 @@
+use std::sync::Arc;
+
+use sqlx::PgPool;
+
+use crate::config::Config;
+use crate::repositories::{
+    book_photo_repository::{BookPhotoRepository, PgBookPhotoRepository},
+    book_repository::{BookRepository, PgBookRepository},
+    books_offered_repository::{BooksOfferedRepository, PgBooksOfferedRepository},
+    books_wanted_repository::{BooksWantedRepository, PgBooksWantedRepository},
+    in_memory::{
+        InMemoryBookPhotoRepository, InMemoryBookRepository, InMemoryBooksOfferedRepository,
+        InMemoryBooksWantedRepository, InMemoryDatabase, InMemoryOAuthRepository, InMemoryTradeRepository,
+        InMemoryTwoFactorRepository, InMemoryUserRepository,
+    },
+    oauth_repository::{OAuthRepository, PgOAuthRepository},
+    rate_limit_repository::{create_rate_limit_repository, InMemoryRateLimitRepository, RateLimitRepository},
+    trade_repository::{PgTradeRepository, TradeRepository},
+    two_factor_repository::{PgTwoFactorRepository, TwoFactorRepository},
+    user_repository::{PgUserRepository, UserRepository},
+};
+use crate::server::StartupError;
+use crate::services::{
+    auth_service::{AuthService, AuthServiceImpl},
+    book_offered_service::{BookOfferedService, BookOfferedServiceImpl},
+    book_photo_service::{BookPhotoService, BookPhotoServiceImpl},
+    book_service::{BookService, BookServiceImpl},
+    book_wanted_service::{BookWantedService, BookWantedServiceImpl},
+    google_book_service::{GoogleBookService, GoogleBookServiceImpl},
+    health_service::{create_health_service, HealthService},
+    http_service::HttpServiceImpl,
+    login_throttle::ThrottledAuthService,
+    oauth_service::{create_oauth_service, OAuthService},
+    password_service::create_password_service,
+    photo_storage::create_photo_storage,
+    rate_limit_service::{RateLimitService, RateLimitServiceImpl},
+    shelf_service::{ShelfService, ShelfServiceImpl},
+    token_service::{create_token_service, TokenService},
+    trade_service::{TradeService, TradeServiceImpl},
+    two_factor_service::{TwoFactorService, TwoFactorServiceImpl},
+    user_service::{UserService, UserServiceImpl},
+};
+
+/// Repositórios usados pelos serviços da aplicação
+#[derive(Clone)]
+pub struct Repositories {
+    pub user: Arc<dyn UserRepository>,
+    pub book: Arc<dyn BookRepository>,
+    pub books_offered: Arc<dyn BooksOfferedRepository>,
+    pub books_wanted: Arc<dyn BooksWantedRepository>,
+    pub book_photo: Arc<dyn BookPhotoRepository>,
+    pub trade: Arc<dyn TradeRepository>,
+    pub two_factor: Arc<dyn TwoFactorRepository>,
+    pub oauth: Arc<dyn OAuthRepository>,
+    pub rate_limit: Arc<dyn RateLimitRepository>,
+}
+
+impl Repositories {
+    /// Repositórios no banco; os contadores de requisições seguem o backend configurado
+    pub fn postgres(config: &Config, pool: &PgPool) -> Self {
+        Self {
+            user: Arc::new(PgUserRepository::new(pool.clone())),
+            book: Arc::new(PgBookRepository::new(pool.clone())),
+            books_offered: Arc::new(PgBooksOfferedRepository::new(pool.clone())),
+            books_wanted: Arc::new(PgBooksWantedRepository::new(pool.clone())),
+            book_photo: Arc::new(PgBookPhotoRepository::new(pool.clone())),
+            trade: Arc::new(PgTradeRepository::new(pool.clone())),
+            two_factor: Arc::new(PgTwoFactorRepository::new(pool.clone())),
+            oauth: Arc::new(PgOAuthRepository::new(pool.clone())),
+            rate_limit: create_rate_limit_repository(config, pool),
+        }
+    }
+
+    /// Repositórios sobre um banco em memória novo, para testes sem Postgres
+    pub fn in_memory() -> Self {
+        let db = InMemoryDatabase::new();
+
+        Self {
+            user: Arc::new(InMemoryUserRepository::new(db.clone())),
+            book: Arc::new(InMemoryBookRepository::new(db.clone())),
+            books_offered: Arc::new(InMemoryBooksOfferedRepository::new(db.clone())),
+            books_wanted: Arc::new(InMemoryBooksWantedRepository::new(db.clone())),
+            book_photo: Arc::new(InMemoryBookPhotoRepository::new(db.clone())),
+            trade: Arc::new(InMemoryTradeRepository::new(db.clone())),
+            two_factor: Arc::new(InMemoryTwoFactorRepository::new(db.clone())),
+            oauth: Arc::new(InMemoryOAuthRepository::new(db)),
+            rate_limit: Arc::new(InMemoryRateLimitRepository::new()),
+        }
+    }
+}
+
+/// Dependências da aplicação, montadas uma única vez e compartilhadas pelas rotas
+#[derive(Clone)]
+pub struct AppState {
+    pub config: Arc<Config>,
+    pub token_service: Arc<dyn TokenService>,
+    pub rate_limit_service: Arc<dyn RateLimitService>,
+    /// Login e cadastro, com o bloqueio temporário após senhas erradas
+    pub auth_service: Arc<dyn AuthService>,
+    pub oauth_service: Arc<dyn OAuthService>,
+    pub two_factor_service: Arc<dyn TwoFactorService>,
+    pub google_book_service: Arc<dyn GoogleBookService>,
+    pub book_photo_service: Arc<dyn BookPhotoService>,
+    pub book_offered_service: Arc<dyn BookOfferedService>,
+    pub book_wanted_service: Arc<dyn BookWantedService>,
+    pub book_service: Arc<dyn BookService>,
+    pub shelf_service: Arc<dyn ShelfService>,
+    pub trade_service: Arc<dyn TradeService>,
+    pub user_service: Arc<dyn UserService>,
+    pub health_service: Arc<dyn HealthService>,
+}
+
+impl AppState {
+    /// Monta a aplicação sobre o banco e a API real do Google Books
+    pub fn from_config(config: Config, pool: Arc<PgPool>) -> Result<Self, StartupError> {
+        let repositories = Repositories::postgres(&config, &pool);
+        let google_book_service = Arc::new(GoogleBookServiceImpl::new(Arc::new(HttpServiceImpl::new())));
+        let health_service = create_health_service(&config, pool);
+
+        Self::new(config, repositories, google_book_service, health_service)
+    }
+
+    /// Monta os serviços sobre as dependências recebidas, permitindo trocar
+    /// repositórios e serviços externos (por exemplo, nos testes)
+    ///
+    /// Falha se as chaves do JWT ou os parâmetros do Argon2 forem inválidos.
+    pub fn new(
+        config: Config,
+        repositories: Repositories,
+        google_book_service: Arc<dyn GoogleBookService>,
+        health_service: Arc<dyn HealthService>,
+    ) -> Result<Self, StartupError> {
+        // As chaves do JWT são carregadas uma vez e compartilhadas por login e middleware
+        let token_service = create_token_service(&config).map_err(StartupError::JwtKeys)?;
+        let password_service = create_password_service(&config).map_err(StartupError::PasswordHashing)?;
+        let rate_limit_service: Arc<dyn RateLimitService> =
+            Arc::new(RateLimitServiceImpl::new(repositories.rate_limit.clone()));
+
+        let auth_service = Arc::new(AuthServiceImpl::with_token_service(
+            repositories.user.clone(),
+            password_service.clone(),
+            token_service.clone(),
+        ));
+
+        let oauth_service = create_oauth_service(
+            &config,
+            repositories.oauth.clone(),
+            repositories.user.clone(),
+            password_service.clone(),
+            token_service.clone(),
+        );
+
+        let two_factor_service = Arc::new(TwoFactorServiceImpl::new(
+            repositories.two_factor.clone(),
+            repositories.user.clone(),
+            password_service,
+            token_service.clone(),
+            rate_limit_service.clone(),
+        ));
+
+        let book_photo_service: Arc<dyn BookPhotoService> = Arc::new(BookPhotoServiceImpl::new(
+            repositories.books_offered.clone(),
+            repositories.book_photo.clone(),
+            create_photo_storage(&config),
+            config.photo_max_bytes,
+        ));
+
+        let book_offered_service = Arc::new(BookOfferedServiceImpl::new(
+            repositories.book.clone(),
+            repositories.books_offered.clone(),
+            repositories.books_wanted.clone(),
+            google_book_service.clone(),
+            book_photo_service.clone(),
+        ));
+
+        let book_wanted_service = Arc::new(BookWantedServiceImpl::new(
+            repositories.book.clone(),
+            repositories.books_wanted.clone(),
+            repositories.books_offered.clone(),
+            google_book_service.clone(),
+        ));
+
+        let book_service = Arc::new(BookServiceImpl::new(
+            repositories.book.clone(),
+            repositories.books_offered.clone(),
+            repositories.books_wanted.clone(),
+            repositories.book_photo.clone(),
+            google_book_service.clone(),
+            config.book_metadata_max_age,
+        ));
+
+        let shelf_service = Arc::new(ShelfServiceImpl::new(
+            repositories.book.clone(),
+            repositories.books_offered.clone(),
+            repositories.books_wanted.clone(),
+            google_book_service.clone(),
+        ));
+
+        let trade_service = Arc::new(TradeServiceImpl::new(
+            repositories.trade.clone(),
+            repositories.books_offered.clone(),
+            book_photo_service.clone(),
+        ));
+
+        let user_service = Arc::new(UserServiceImpl::new(repositories.user.clone()));
+
+        Ok(Self {
+            config: Arc::new(config),
+            token_service,
+            rate_limit_service: rate_limit_service.clone(),
+            // Bloqueio temporário da conta após várias senhas erradas
+            auth_service: Arc::new(ThrottledAuthService::new(auth_service, rate_limit_service)),
+            oauth_service,
+            two_factor_service,
+            google_book_service,
+            book_photo_service,
+            book_offered_service,
+            book_wanted_service,
+            book_service,
+            shelf_service,
+            trade_service,
+            user_service,
+            health_service,
+        })
+    }
+}
@@ -0,0 +1,82 @@
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::sync::{Arc, Mutex};
+
+    use serde_json::Value;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    use crate::telemetry::json_layer;
+
+    // Escritor que guarda as linhas de log em memória
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Buffer {
+        fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(bytes);
+            Ok(bytes.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Buffer {
+        fn lines(&self) -> Vec<Value> {
+            String::from_utf8(self.0.lock().unwrap().clone())
+                .unwrap()
+                .lines()
+                .map(|line| serde_json::from_str(line).expect("Linha de log não é JSON"))
+                .collect()
+        }
+    }
+
+    fn capture(f: impl FnOnce()) -> Vec<Value> {
+        let buffer = Buffer::default();
+        let writer = buffer.clone();
+        let subscriber = tracing_subscriber::registry().with(json_layer(move || writer.clone()));
+
+        tracing::subscriber::with_default(subscriber, f);
+
+        buffer.lines()
+    }
+
+    #[test]
+    fn test_event_is_written_as_json_line() {
+        let lines = capture(|| {
+            tracing::warn!(tentativas = 3, email = "ana@example.com", "login bloqueado");
+        });
+
+        assert_eq!(lines.len(), 1);
+        let line = &lines[0];
+        assert_eq!(line["level"], "WARN");
+        assert_eq!(line["target"], "troca_livros_api::telemetry_test::tests");
+        assert_eq!(line["fields"]["message"], "login bloqueado");
+        assert_eq!(line["fields"]["tentativas"], 3);
+        assert_eq!(line["fields"]["email"], "ana@example.com");
+        assert!(line["timestamp"].as_str().unwrap().ends_with('Z'));
+        assert!(line.get("span").is_none());
+    }
+
+    #[test]
+    fn test_span_fields_include_values_recorded_later() {
+        let lines = capture(|| {
+            let request = tracing::info_span!("http_request", route = "/api/trades", status = tracing::field::Empty);
+            let _request = request.enter();
+            let query = tracing::info_span!("trade_repository.find_user_trades");
+            let _query = query.enter();
+
+            request.record("status", 200);
+            tracing::info!("consulta concluída");
+        });
+
+        let line = &lines[0];
+        assert_eq!(line["span"]["name"], "trade_repository.find_user_trades");
+        assert_eq!(line["spans"][0]["name"], "http_request");
+        assert_eq!(line["spans"][0]["route"], "/api/trades");
+        assert_eq!(line["spans"][0]["status"], 200);
+        assert_eq!(line["spans"].as_array().unwrap().len(), 2);
+    }
+}
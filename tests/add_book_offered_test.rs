@@ -1,15 +1,11 @@
 mod common;
 
-use crate::common::test_utils::{get_auth_token, get_test_mutex, setup_test_app};
+use crate::common::test_utils::{get_auth_token, setup_test_app};
 use reqwest::{header, StatusCode};
 use serde_json::{json, Value};
 
 #[tokio::test]
 async fn test_add_book_to_offered() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-    
     // Arrange - Configurar o aplicativo de teste e autenticar
     let app = setup_test_app().await;
     let token = get_auth_token(&app).await;
@@ -17,7 +13,7 @@ async fn test_add_book_to_offered() {
 
     // Primeiro, vamos buscar um livro para obter um ID válido
     let search_response = client
-        .post(&format!("http://localhost:{}/api/books/search", app.port))
+        .post(format!("http://localhost:{}/api/v1/books/search", app.port))
         .header(header::AUTHORIZATION, format!("Bearer {}", token))
         .json(&json!({
             "query": "Clean Code"
@@ -38,7 +34,7 @@ async fn test_add_book_to_offered() {
 
     // Act - Adicionar o livro à lista de possuídos
     let response = client
-        .post(&format!("http://localhost:{}/api/books/offered", app.port))
+        .post(format!("http://localhost:{}/api/v1/books/offered", app.port))
         .header(header::AUTHORIZATION, format!("Bearer {}", token))
         .json(&json!({
             "google_id": google_id
@@ -60,40 +56,38 @@ async fn test_add_book_to_offered() {
         body["message"],
         "Livro adicionado à lista de possuídos com sucesso"
     );
-    assert!(body["data"]["book_id"].is_string());
-    assert!(body["data"]["user_id"].is_string());
-
-    // Tentar adicionar o mesmo livro novamente deve falhar
-    let duplicate_response = client
-        .post(&format!("http://localhost:{}/api/books/offered", app.port))
+    assert_eq!(body["data"].as_array().unwrap().len(), 1);
+    assert!(body["data"][0]["id"].is_string());
+    assert!(body["data"][0]["book_id"].is_string());
+    assert!(body["data"][0]["user_id"].is_string());
+
+    // Adicionar o mesmo livro novamente cria outros exemplares
+    let second_response = client
+        .post(format!("http://localhost:{}/api/v1/books/offered", app.port))
         .header(header::AUTHORIZATION, format!("Bearer {}", token))
         .json(&json!({
-            "google_id": google_id
+            "google_id": google_id,
+            "quantity": 2
         }))
         .send()
         .await
-        .expect("Falha ao enviar requisição duplicada");
+        .expect("Falha ao enviar requisição com mais exemplares");
 
-    let duplicate_status = duplicate_response.status();
-    let duplicate_body: Value = duplicate_response
+    let second_status = second_response.status();
+    let second_body: Value = second_response
         .json()
         .await
-        .expect("Falha ao ler corpo da resposta duplicada");
+        .expect("Falha ao ler corpo da resposta");
 
-    assert_eq!(duplicate_status, StatusCode::BAD_REQUEST);
-    assert_eq!(duplicate_body["error"]["status"], 400);
-    assert!(duplicate_body["error"]["message"]
-        .as_str()
-        .unwrap()
-        .contains("já está na sua lista de possuídos"));
+    assert_eq!(second_status, StatusCode::CREATED);
+    let copies = second_body["data"].as_array().unwrap();
+    assert_eq!(copies.len(), 2);
+    assert_ne!(copies[0]["id"], copies[1]["id"]);
+    assert_eq!(copies[0]["book_id"], body["data"][0]["book_id"]);
 }
 
 #[tokio::test]
 async fn test_add_book_to_offered_invalid_google_id() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-    
     // Arrange - Configurar o aplicativo de teste e autenticar
     let app = setup_test_app().await;
     let token = get_auth_token(&app).await;
@@ -101,7 +95,7 @@ async fn test_add_book_to_offered_invalid_google_id() {
 
     // Act - Tentar adicionar um livro com ID inválido
     let response = client
-        .post(&format!("http://localhost:{}/api/books/offered", app.port))
+        .post(format!("http://localhost:{}/api/v1/books/offered", app.port))
         .header(header::AUTHORIZATION, format!("Bearer {}", token))
         .json(&json!({
             "google_id": "id_que_nao_existe_12345"
@@ -117,17 +111,13 @@ async fn test_add_book_to_offered_invalid_google_id() {
 
 #[tokio::test]
 async fn test_add_book_to_offered_without_authentication() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-    
     // Arrange
     let app = setup_test_app().await;
     let client = reqwest::Client::new();
 
     // Act - Tentar adicionar um livro sem autenticação
     let response = client
-        .post(&format!("http://localhost:{}/api/books/offered", app.port))
+        .post(format!("http://localhost:{}/api/v1/books/offered", app.port))
         .json(&json!({
             "google_id": "qualquerid"
         }))
@@ -153,10 +143,6 @@ async fn test_add_book_to_offered_without_authentication() {
 // Teste para verificar erro ao tentar adicionar livro que já está na lista de desejados
 #[tokio::test]
 async fn test_add_book_to_offered_already_in_wanted() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-    
     // Arrange - Configurar o aplicativo de teste e autenticar
     let app = setup_test_app().await;
     let token = get_auth_token(&app).await;
@@ -164,7 +150,7 @@ async fn test_add_book_to_offered_already_in_wanted() {
 
     // Primeiro, vamos buscar um livro para obter um ID válido
     let search_response = client
-        .post(&format!("http://localhost:{}/api/books/search", app.port))
+        .post(format!("http://localhost:{}/api/v1/books/search", app.port))
         .header(header::AUTHORIZATION, format!("Bearer {}", token))
         .json(&json!({
             "query": "Clean Code"
@@ -185,7 +171,7 @@ async fn test_add_book_to_offered_already_in_wanted() {
 
     // Adicionar à lista de desejados 
     let wanted_response = client
-        .post(&format!("http://localhost:{}/api/books/wanted", app.port))
+        .post(format!("http://localhost:{}/api/v1/books/wanted", app.port))
         .header(header::AUTHORIZATION, format!("Bearer {}", token))
         .json(&json!({
             "google_id": google_id
@@ -198,7 +184,7 @@ async fn test_add_book_to_offered_already_in_wanted() {
 
     // Act - Tentar adicionar o mesmo livro à list de possuídos
     let response = client
-        .post(&format!("http://localhost:{}/api/books/offered", app.port))
+        .post(format!("http://localhost:{}/api/v1/books/offered", app.port))
         .header(header::AUTHORIZATION, format!("Bearer {}", token))
         .json(&json!({
             "google_id": google_id
@@ -214,8 +200,9 @@ async fn test_add_book_to_offered_already_in_wanted() {
         .await
         .expect("Falha ao ler corpo da resposta");
 
-    assert_eq!(status, StatusCode::BAD_REQUEST);
-    assert_eq!(body["error"]["status"], 400);
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["error"]["status"], 409);
+    assert_eq!(body["error"]["code"], "BOOK_ALREADY_WANTED");
     assert!(body["error"]["message"]
         .as_str()
         .unwrap()
@@ -1,15 +1,11 @@
 mod common;
 
-use crate::common::test_utils::{get_auth_token, get_test_mutex, setup_test_app};
+use crate::common::test_utils::{get_auth_token, setup_test_app};
 use reqwest::{header, StatusCode};
 use serde_json::{json, Value};
 
 #[tokio::test]
 async fn test_add_book_to_wanted() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-    
     // Arrange - Configurar o aplicativo de teste e autenticar
     let app = setup_test_app().await;
     let token = get_auth_token(&app).await;
@@ -17,7 +13,7 @@ async fn test_add_book_to_wanted() {
 
     // Primeiro, vamos buscar um livro para obter um ID válido
     let search_response = client
-        .post(&format!("http://localhost:{}/api/books/search", app.port))
+        .post(format!("http://localhost:{}/api/v1/books/search", app.port))
         .header(header::AUTHORIZATION, format!("Bearer {}", token))
         .json(&json!({
             "query": "Clean Code"
@@ -38,7 +34,7 @@ async fn test_add_book_to_wanted() {
 
     // Act - Adicionar o livro à lista de desejados
     let response = client
-        .post(&format!("http://localhost:{}/api/books/wanted", app.port))
+        .post(format!("http://localhost:{}/api/v1/books/wanted", app.port))
         .header(header::AUTHORIZATION, format!("Bearer {}", token))
         .json(&json!({
             "google_id": google_id
@@ -65,7 +61,7 @@ async fn test_add_book_to_wanted() {
 
     // Tentar adicionar o mesmo livro novamente deve falhar
     let duplicate_response = client
-        .post(&format!("http://localhost:{}/api/books/wanted", app.port))
+        .post(format!("http://localhost:{}/api/v1/books/wanted", app.port))
         .header(header::AUTHORIZATION, format!("Bearer {}", token))
         .json(&json!({
             "google_id": google_id
@@ -80,8 +76,9 @@ async fn test_add_book_to_wanted() {
         .await
         .expect("Falha ao ler corpo da resposta duplicada");
 
-    assert_eq!(duplicate_status, StatusCode::BAD_REQUEST);
-    assert_eq!(duplicate_body["error"]["status"], 400);
+    assert_eq!(duplicate_status, StatusCode::CONFLICT);
+    assert_eq!(duplicate_body["error"]["status"], 409);
+    assert_eq!(duplicate_body["error"]["code"], "BOOK_ALREADY_WANTED");
     assert!(duplicate_body["error"]["message"]
         .as_str()
         .unwrap()
@@ -90,10 +87,6 @@ async fn test_add_book_to_wanted() {
 
 #[tokio::test]
 async fn test_add_book_to_wanted_invalid_google_id() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-    
     // Arrange - Configurar o aplicativo de teste e autenticar
     let app = setup_test_app().await;
     let token = get_auth_token(&app).await;
@@ -101,7 +94,7 @@ async fn test_add_book_to_wanted_invalid_google_id() {
 
     // Act - Tentar adicionar um livro com ID inválido
     let response = client
-        .post(&format!("http://localhost:{}/api/books/wanted", app.port))
+        .post(format!("http://localhost:{}/api/v1/books/wanted", app.port))
         .header(header::AUTHORIZATION, format!("Bearer {}", token))
         .json(&json!({
             "google_id": "id_que_nao_existe_12345"
@@ -117,17 +110,13 @@ async fn test_add_book_to_wanted_invalid_google_id() {
 
 #[tokio::test]
 async fn test_add_book_to_wanted_without_authentication() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-    
     // Arrange
     let app = setup_test_app().await;
     let client = reqwest::Client::new();
 
     // Act - Tentar adicionar um livro sem autenticação
     let response = client
-        .post(&format!("http://localhost:{}/api/books/wanted", app.port))
+        .post(format!("http://localhost:{}/api/v1/books/wanted", app.port))
         .json(&json!({
             "google_id": "qualquerid"
         }))
@@ -153,10 +142,6 @@ async fn test_add_book_to_wanted_without_authentication() {
 // Teste para verificar erro ao tentar adicionar livro que já está na lista de possuídos
 #[tokio::test]
 async fn test_add_book_to_wanted_already_in_offered() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-    
     // Arrange - Configurar o aplicativo de teste e autenticar
     let app = setup_test_app().await;
     let token = get_auth_token(&app).await;
@@ -164,7 +149,7 @@ async fn test_add_book_to_wanted_already_in_offered() {
 
     // Primeiro, vamos buscar um livro para obter um ID válido
     let search_response = client
-        .post(&format!("http://localhost:{}/api/books/search", app.port))
+        .post(format!("http://localhost:{}/api/v1/books/search", app.port))
         .header(header::AUTHORIZATION, format!("Bearer {}", token))
         .json(&json!({
             "query": "Clean Code"
@@ -185,7 +170,7 @@ async fn test_add_book_to_wanted_already_in_offered() {
 
     // Adicionar à lista de possuídos 
     let offered_response = client
-        .post(&format!("http://localhost:{}/api/books/offered", app.port))
+        .post(format!("http://localhost:{}/api/v1/books/offered", app.port))
         .header(header::AUTHORIZATION, format!("Bearer {}", token))
         .json(&json!({
             "google_id": google_id
@@ -198,7 +183,7 @@ async fn test_add_book_to_wanted_already_in_offered() {
 
     // Act - Tentar adicionar o mesmo livro à lista de desejados
     let response = client
-        .post(&format!("http://localhost:{}/api/books/wanted", app.port))
+        .post(format!("http://localhost:{}/api/v1/books/wanted", app.port))
         .header(header::AUTHORIZATION, format!("Bearer {}", token))
         .json(&json!({
             "google_id": google_id
@@ -214,8 +199,9 @@ async fn test_add_book_to_wanted_already_in_offered() {
         .await
         .expect("Falha ao ler corpo da resposta");
 
-    assert_eq!(status, StatusCode::BAD_REQUEST);
-    assert_eq!(body["error"]["status"], 400);
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["error"]["status"], 409);
+    assert_eq!(body["error"]["code"], "BOOK_ALREADY_OFFERED");
     assert!(body["error"]["message"]
         .as_str()
         .unwrap()
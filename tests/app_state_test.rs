@@ -0,0 +1,133 @@
+mod common;
+
+use std::sync::Arc;
+
+use crate::common::test_utils::{get_auth_token, setup_test_app_with_state, FakeGoogleBookService};
+use reqwest::{header, StatusCode};
+use serde_json::{json, Value};
+use sqlx::postgres::PgPoolOptions;
+use troca_livros_api::config::{Config, RateLimitBackend};
+use troca_livros_api::server::StartupError;
+use troca_livros_api::services::health_service::create_health_service;
+use troca_livros_api::services::token_service::create_token_service;
+use troca_livros_api::state::{AppState, Repositories};
+use uuid::Uuid;
+
+fn create_fake_config() -> Config {
+    dotenv::dotenv().ok();
+
+    let mut config = Config::from_env().expect("Falha ao carregar configuração");
+    config.rate_limit_backend = RateLimitBackend::Memory;
+    config
+}
+
+/// Estado com o Google Books falso, repositórios em memória e um pool que nunca chega a conectar
+fn create_fake_state() -> AppState {
+    build_fake_state(create_fake_config()).expect("Falha ao montar o estado da aplicação")
+}
+
+fn build_fake_state(config: Config) -> Result<AppState, StartupError> {
+    let pool = PgPoolOptions::new()
+        .connect_lazy("postgres://ninguem@127.0.0.1:1/inexistente")
+        .expect("URL do banco inválida");
+    let health_service = create_health_service(&config, Arc::new(pool));
+
+    AppState::new(config, Repositories::in_memory(), Arc::new(FakeGoogleBookService), health_service)
+}
+
+#[tokio::test]
+async fn test_search_uses_injected_google_book_service() {
+    // Arrange - Nenhuma dependência externa: a conta fica nos repositórios em memória
+    let app = setup_test_app_with_state(create_fake_state());
+    let token = get_auth_token(&app).await;
+    let client = reqwest::Client::new();
+
+    // Act
+    let response = client
+        .post(format!("http://localhost:{}/api/v1/books/search", app.port))
+        .header(header::AUTHORIZATION, format!("Bearer {}", token))
+        .json(&json!({ "query": "qualquer coisa" }))
+        .send()
+        .await
+        .expect("Falha ao enviar requisição");
+
+    // Assert
+    let status = response.status();
+    let body: Value = response.json().await.expect("Falha ao ler corpo da resposta");
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"][0]["google_id"], "falso-1");
+    assert_eq!(body["data"][0]["title"], "Livro Falso");
+}
+
+#[tokio::test]
+async fn test_injected_state_rejects_tokens_from_other_keys() {
+    // Arrange - Token assinado com outro segredo
+    dotenv::dotenv().ok();
+    let mut other_config = Config::from_env().expect("Falha ao carregar configuração");
+    other_config.jwt_secret = "outro-segredo".to_string();
+    other_config.jwt_keys_dir = None;
+    let other_token = create_token_service(&other_config).unwrap().issue(&Uuid::new_v4()).unwrap();
+
+    let app = setup_test_app_with_state(create_fake_state());
+    let client = reqwest::Client::new();
+
+    // Act
+    let response = client
+        .post(format!("http://localhost:{}/api/v1/books/search", app.port))
+        .header(header::AUTHORIZATION, format!("Bearer {}", other_token))
+        .json(&json!({ "query": "qualquer coisa" }))
+        .send()
+        .await
+        .expect("Falha ao enviar requisição");
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_readiness_reports_unreachable_database() {
+    // Arrange
+    let app = setup_test_app_with_state(create_fake_state());
+    let client = reqwest::Client::new();
+
+    // Act
+    let response = client
+        .get(format!("http://localhost:{}/ready", app.port))
+        .send()
+        .await
+        .expect("Falha ao enviar requisição");
+
+    // Assert - O banco do estado injetado não existe
+    let status = response.status();
+    let body: Value = response.json().await.expect("Falha ao ler corpo da resposta");
+
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(body["checks"]["database"], "unavailable");
+}
+
+#[tokio::test]
+async fn test_missing_jwt_keys_are_a_startup_error() {
+    // Arrange
+    let mut config = create_fake_config();
+    config.jwt_keys_dir = Some("/diretorio/que/nao/existe".to_string());
+
+    // Act
+    let result = build_fake_state(config);
+
+    // Assert
+    assert!(matches!(result, Err(StartupError::JwtKeys(_))));
+}
+
+#[tokio::test]
+async fn test_invalid_argon2_params_are_a_startup_error() {
+    // Arrange - O Argon2 exige pelo menos uma iteração
+    let mut config = create_fake_config();
+    config.argon2_iterations = 0;
+
+    // Act
+    let result = build_fake_state(config);
+
+    // Assert
+    assert!(matches!(result, Err(StartupError::PasswordHashing(_))));
+}
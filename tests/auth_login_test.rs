@@ -1,15 +1,12 @@
 mod common;
 
-use crate::common::test_utils::{get_test_mutex, setup_test_app};
+use crate::common::test_utils::{setup_test_app};
 use reqwest::StatusCode;
 use serde_json::{json, Value};
+use troca_livros_api::services::totp::{base32_decode, code_at, step_at};
 
 #[tokio::test]
 async fn test_login_success() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-    
     // Arrange
     let app = setup_test_app().await;
     let client = reqwest::Client::new();
@@ -20,7 +17,7 @@ async fn test_login_success() {
 
     // Registrar um usuário primeiro
     let register_response = client
-        .post(&format!("http://localhost:{}/api/auth/register", app.port))
+        .post(format!("http://localhost:{}/api/v1/auth/register", app.port))
         .json(&json!({
             "name": "Usuário de Login",
             "email": email,
@@ -34,7 +31,7 @@ async fn test_login_success() {
 
     // Act - Fazer login com usuário criado
     let login_response = client
-        .post(&format!("http://localhost:{}/api/auth/login", app.port))
+        .post(format!("http://localhost:{}/api/v1/auth/login", app.port))
         .json(&json!({
             "email": email,
             "password": password
@@ -62,10 +59,6 @@ async fn test_login_success() {
 
 #[tokio::test]
 async fn test_login_invalid_credentials() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-    
     // Arrange
     let app = setup_test_app().await;
     let client = reqwest::Client::new();
@@ -78,7 +71,7 @@ async fn test_login_invalid_credentials() {
 
     // Registrar um usuário primeiro
     let register_response = client
-        .post(&format!("http://localhost:{}/api/auth/register", app.port))
+        .post(format!("http://localhost:{}/api/v1/auth/register", app.port))
         .json(&json!({
             "name": "Usuário para Teste de Login Inválido",
             "email": email,
@@ -92,7 +85,7 @@ async fn test_login_invalid_credentials() {
 
     // Act - Fazer login com senha incorreta
     let login_response = client
-        .post(&format!("http://localhost:{}/api/auth/login", app.port))
+        .post(format!("http://localhost:{}/api/v1/auth/login", app.port))
         .json(&json!({
             "email": email,
             "password": "senha_incorreta"
@@ -110,6 +103,7 @@ async fn test_login_invalid_credentials() {
 
     assert_eq!(status, StatusCode::UNAUTHORIZED);
     assert_eq!(body["error"]["status"], 401);
+    assert_eq!(body["error"]["code"], "INVALID_CREDENTIALS");
     assert!(body["error"]["message"]
         .as_str()
         .unwrap()
@@ -118,10 +112,6 @@ async fn test_login_invalid_credentials() {
 
 #[tokio::test]
 async fn test_login_nonexistent_user() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-    
     // Arrange
     let app = setup_test_app().await;
     let client = reqwest::Client::new();
@@ -131,7 +121,7 @@ async fn test_login_nonexistent_user() {
 
     // Act - Fazer login com usuário inexistente
     let login_response = client
-        .post(&format!("http://localhost:{}/api/auth/login", app.port))
+        .post(format!("http://localhost:{}/api/v1/auth/login", app.port))
         .json(&json!({
             "email": email,
             "password": "qualquer_senha"
@@ -154,3 +144,213 @@ async fn test_login_nonexistent_user() {
         .unwrap()
         .contains("Credenciais inválidas"));
 }
+
+#[tokio::test]
+async fn test_login_error_in_english() {
+    // Arrange
+    let app = setup_test_app().await;
+    let client = reqwest::Client::new();
+
+    let email = format!("nonexistent_{}@example.com", chrono::Utc::now().timestamp());
+
+    // Act - Fazer login pedindo as mensagens em inglês
+    let login_response = client
+        .post(format!("http://localhost:{}/api/v1/auth/login", app.port))
+        .header("Accept-Language", "en-US,en;q=0.9,pt;q=0.8")
+        .json(&json!({
+            "email": email,
+            "password": "qualquer_senha"
+        }))
+        .send()
+        .await
+        .expect("Falha ao enviar requisição de login");
+
+    // Assert
+    let status = login_response.status();
+    let content_language = login_response
+        .headers()
+        .get("content-language")
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let body: Value = login_response
+        .json()
+        .await
+        .expect("Falha ao ler corpo da resposta");
+
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(content_language.as_deref(), Some("en"));
+    assert_eq!(body["error"]["code"], "INVALID_CREDENTIALS");
+    assert_eq!(body["error"]["message"], "Invalid credentials");
+}
+
+#[tokio::test]
+async fn test_login_locked_after_failed_attempts() {
+    // Arrange
+    let app = setup_test_app().await;
+    let client = reqwest::Client::new();
+
+    let email = format!("locked_{}@example.com", uuid::Uuid::new_v4());
+    let login = || {
+        client
+            .post(format!("http://localhost:{}/api/v1/auth/login", app.port))
+            .json(&json!({
+                "email": email,
+                "password": "senha_errada"
+            }))
+            .send()
+    };
+
+    // Act - Errar a senha até atingir o limite
+    for _ in 0..5 {
+        let response = login().await.expect("Falha ao enviar requisição de login");
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    let locked_response = login().await.expect("Falha ao enviar requisição de login");
+
+    // Assert
+    let status = locked_response.status();
+    let retry_after = locked_response
+        .headers()
+        .get("retry-after")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok());
+    let body: Value = locked_response
+        .json()
+        .await
+        .expect("Falha ao ler corpo da resposta");
+
+    assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+    assert!(retry_after.is_some_and(|seconds| seconds > 0));
+    assert_eq!(body["error"]["code"], "RATE_LIMITED");
+}
+
+#[tokio::test]
+async fn test_oauth_login_with_unavailable_provider() {
+    // Arrange
+    let app = setup_test_app().await;
+    let client = reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .unwrap();
+    let url = |path: &str| format!("http://localhost:{}/api/v1/auth/oauth/{}", app.port, path);
+
+    // Act & Assert - Provedor sem cliente configurado
+    let response = client.get(url("facebook/authorize")).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["error"]["code"], "NOT_FOUND");
+
+    let response = client
+        .get(url("facebook/callback?code=abc&state=xyz"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    // Retorno sem code e state, e acesso negado pelo usuário no provedor
+    let response = client.get(url("google/callback")).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = client
+        .get(url("google/callback?error=access_denied"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_login_with_two_factor() {
+    // Arrange
+    let app = setup_test_app().await;
+    let client = reqwest::Client::new();
+    let url = |path: &str| format!("http://localhost:{}/api/v1/auth/{}", app.port, path);
+    let email = format!("two_factor_{}@example.com", uuid::Uuid::new_v4());
+    let credentials = json!({ "email": email, "password": "senha123" });
+
+    let response = client
+        .post(url("register"))
+        .json(&json!({ "name": "Usuária 2FA", "email": email, "password": "senha123" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let body: Value = client.post(url("login")).json(&credentials).send().await.unwrap().json().await.unwrap();
+    let token = body["data"]["access_token"].as_str().unwrap().to_string();
+
+    // Act - Ativar a verificação com o código do autenticador
+    let response = client.post(url("2fa/setup")).bearer_auth(&token).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: Value = response.json().await.unwrap();
+    let secret = base32_decode(body["data"]["secret"].as_str().unwrap()).unwrap();
+    assert!(body["data"]["provisioning_uri"].as_str().unwrap().starts_with("otpauth://totp/"));
+
+    let code = code_at(&secret, step_at(chrono::Utc::now().timestamp() as u64));
+    let response = client
+        .post(url("2fa/enable"))
+        .bearer_auth(&token)
+        .json(&json!({ "code": code }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: Value = response.json().await.unwrap();
+    let recovery_code = body["data"]["recovery_codes"][0].as_str().unwrap().to_string();
+
+    // Assert - O login passa a exigir o segundo fator
+    let response = client.post(url("login")).json(&credentials).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    let body: Value = response.json().await.unwrap();
+    assert!(body["data"]["access_token"].is_null());
+    let challenge = body["data"]["challenge_token"].as_str().unwrap().to_string();
+
+    // O desafio não dá acesso às rotas protegidas
+    let response = client
+        .post(url("2fa/setup"))
+        .bearer_auth(&challenge)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let verify = |code: String| {
+        client
+            .post(url("2fa/verify"))
+            .json(&json!({ "challenge_token": challenge, "code": code }))
+            .send()
+    };
+
+    let response = verify(recovery_code.clone()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: Value = response.json().await.unwrap();
+    assert!(body["data"]["access_token"].is_string());
+    assert_eq!(body["data"]["user"]["email"], email);
+
+    // Cada código de recuperação vale uma única vez
+    let response = verify(recovery_code).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    // Desativar exige a senha correta
+    let response = client
+        .post(url("2fa/disable"))
+        .bearer_auth(&token)
+        .json(&json!({ "password": "errada" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let response = client
+        .post(url("2fa/disable"))
+        .bearer_auth(&token)
+        .json(&json!({ "password": "senha123" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = client.post(url("login")).json(&credentials).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
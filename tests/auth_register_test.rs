@@ -1,15 +1,11 @@
 mod common;
 
-use crate::common::test_utils::{get_test_mutex, setup_test_app};
+use crate::common::test_utils::{setup_test_app};
 use reqwest::StatusCode;
 use serde_json::{json, Value};
 
 #[tokio::test]
 async fn test_register_user_success() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-    
     // Arrange - Configurar o aplicativo de teste
     let app = setup_test_app().await;
     let client = reqwest::Client::new();
@@ -19,7 +15,7 @@ async fn test_register_user_success() {
 
     // Act - Enviar requisição para registrar um usuário
     let response = client
-        .post(&format!("http://localhost:{}/api/auth/register", app.port))
+        .post(format!("http://localhost:{}/api/v1/auth/register", app.port))
         .json(&json!({
             "name": "Usuário de Teste",
             "email": email,
@@ -46,17 +42,13 @@ async fn test_register_user_success() {
 
 #[tokio::test]
 async fn test_register_user_invalid_email() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-    
     // Arrange
     let app = setup_test_app().await;
     let client = reqwest::Client::new();
 
     // Act - Enviar requisição com email inválido
     let response = client
-        .post(&format!("http://localhost:{}/api/auth/register", app.port))
+        .post(format!("http://localhost:{}/api/v1/auth/register", app.port))
         .json(&json!({
             "name": "Usuário de Teste",
             "email": "email_invalido",
@@ -83,17 +75,13 @@ async fn test_register_user_invalid_email() {
 
 #[tokio::test]
 async fn test_register_user_password_too_short() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-    
     // Arrange
     let app = setup_test_app().await;
     let client = reqwest::Client::new();
 
     // Act - Enviar requisição com senha muito curta
     let response = client
-        .post(&format!("http://localhost:{}/api/auth/register", app.port))
+        .post(format!("http://localhost:{}/api/v1/auth/register", app.port))
         .json(&json!({
             "name": "Usuário de Teste",
             "email": "usuario@example.com",
@@ -112,6 +100,8 @@ async fn test_register_user_password_too_short() {
 
     assert_eq!(status, StatusCode::BAD_REQUEST);
     assert_eq!(body["error"]["status"], 400);
+    assert_eq!(body["error"]["code"], "VALIDATION_FAILED");
+    assert_eq!(body["error"]["details"][0]["field"], "password");
     assert!(body["error"]["message"]
         .as_str()
         .unwrap()
@@ -120,10 +110,6 @@ async fn test_register_user_password_too_short() {
 
 #[tokio::test]
 async fn test_register_user_duplicate_email() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-    
     // Arrange
     let app = setup_test_app().await;
     let client = reqwest::Client::new();
@@ -133,7 +119,7 @@ async fn test_register_user_duplicate_email() {
 
     // Primeiro registro (deve ter sucesso)
     let _ = client
-        .post(&format!("http://localhost:{}/api/auth/register", app.port))
+        .post(format!("http://localhost:{}/api/v1/auth/register", app.port))
         .json(&json!({
             "name": "Primeiro Usuário",
             "email": email,
@@ -145,7 +131,7 @@ async fn test_register_user_duplicate_email() {
 
     // Act - Tentar registrar com o mesmo email
     let response = client
-        .post(&format!("http://localhost:{}/api/auth/register", app.port))
+        .post(format!("http://localhost:{}/api/v1/auth/register", app.port))
         .json(&json!({
             "name": "Segundo Usuário",
             "email": email,
@@ -162,8 +148,9 @@ async fn test_register_user_duplicate_email() {
         .await
         .expect("Falha ao ler corpo da resposta");
 
-    assert_eq!(status, StatusCode::BAD_REQUEST);
-    assert_eq!(body["error"]["status"], 400);
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["error"]["status"], 409);
+    assert_eq!(body["error"]["code"], "EMAIL_ALREADY_IN_USE");
     assert!(body["error"]["message"]
         .as_str()
         .unwrap()
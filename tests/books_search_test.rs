@@ -1,15 +1,11 @@
 mod common;
 
-use crate::common::test_utils::{get_auth_token, get_test_mutex, setup_test_app};
+use crate::common::test_utils::{get_auth_token, setup_test_app};
 use reqwest::{header, StatusCode};
 use serde_json::{json, Value};
 
 #[tokio::test]
 async fn test_search_books_success() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-    
     // Arrange - Configurar o aplicativo de teste e autenticar
     let app = setup_test_app().await;
     let token = get_auth_token(&app).await;
@@ -17,7 +13,7 @@ async fn test_search_books_success() {
 
     // Act - Buscar livros com um termo de busca válido
     let response = client
-        .post(&format!("http://localhost:{}/api/books/search", app.port))
+        .post(format!("http://localhost:{}/api/v1/books/search", app.port))
         .header(header::AUTHORIZATION, format!("Bearer {}", token))
         .json(&json!({
             "query": "Rust Programming"
@@ -55,17 +51,13 @@ async fn test_search_books_success() {
 
 #[tokio::test]
 async fn test_search_books_without_authentication() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-    
     // Arrange
     let app = setup_test_app().await;
     let client = reqwest::Client::new();
 
     // Act - Buscar livros sem fornecer token de autenticação
     let response = client
-        .post(&format!("http://localhost:{}/api/books/search", app.port))
+        .post(format!("http://localhost:{}/api/v1/books/search", app.port))
         .json(&json!({
             "query": "Clean Code"
         }))
@@ -90,10 +82,6 @@ async fn test_search_books_without_authentication() {
 
 #[tokio::test]
 async fn test_search_books_with_invalid_token() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-    
     // Arrange
     let app = setup_test_app().await;
     let client = reqwest::Client::new();
@@ -103,7 +91,7 @@ async fn test_search_books_with_invalid_token() {
 
     // Act - Buscar livros com token inválido
     let response = client
-        .post(&format!("http://localhost:{}/api/books/search", app.port))
+        .post(format!("http://localhost:{}/api/v1/books/search", app.port))
         .header(header::AUTHORIZATION, format!("Bearer {}", invalid_token))
         .json(&json!({
             "query": "Clean Code"
@@ -129,10 +117,6 @@ async fn test_search_books_with_invalid_token() {
 
 #[tokio::test]
 async fn test_search_books_empty_query() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-    
     // Arrange - Configurar o aplicativo de teste e autenticar
     let app = setup_test_app().await;
     let token = get_auth_token(&app).await;
@@ -140,7 +124,7 @@ async fn test_search_books_empty_query() {
 
     // Act - Enviar uma consulta vazia
     let response = client
-        .post(&format!("http://localhost:{}/api/books/search", app.port))
+        .post(format!("http://localhost:{}/api/v1/books/search", app.port))
         .header(header::AUTHORIZATION, format!("Bearer {}", token))
         .json(&json!({
             "query": ""
@@ -166,10 +150,6 @@ async fn test_search_books_empty_query() {
 
 #[tokio::test]
 async fn test_search_books_specific_book() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-    
     // Arrange
     let app = setup_test_app().await;
     let token = get_auth_token(&app).await;
@@ -177,7 +157,7 @@ async fn test_search_books_specific_book() {
 
     // Act - Buscar um livro específico com termos mais específicos
     let response = client
-        .post(&format!("http://localhost:{}/api/books/search", app.port))
+        .post(format!("http://localhost:{}/api/v1/books/search", app.port))
         .header(header::AUTHORIZATION, format!("Bearer {}", token))
         .json(&json!({
             "query": "Clean Code: A Handbook of Agile Software Craftsmanship Robert Martin"
@@ -217,10 +197,6 @@ async fn test_search_books_specific_book() {
 
 #[tokio::test]
 async fn test_search_books_nonexistent_title() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-    
     // Arrange
     let app = setup_test_app().await;
     let token = get_auth_token(&app).await;
@@ -229,7 +205,7 @@ async fn test_search_books_nonexistent_title() {
     // Act - Buscar um título improvável de existir
     let unique_query = format!("TítuloMuitoImprovável{}", chrono::Utc::now().timestamp());
     let response = client
-        .post(&format!("http://localhost:{}/api/books/search", app.port))
+        .post(format!("http://localhost:{}/api/v1/books/search", app.port))
         .header(header::AUTHORIZATION, format!("Bearer {}", token))
         .json(&json!({
             "query": unique_query
@@ -258,10 +234,6 @@ async fn test_search_books_nonexistent_title() {
 
 #[tokio::test]
 async fn test_search_books_with_author_filter() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-    
     // Arrange
     let app = setup_test_app().await;
     let token = get_auth_token(&app).await;
@@ -269,7 +241,7 @@ async fn test_search_books_with_author_filter() {
 
     // Act - Buscar livros de um autor específico
     let response = client
-        .post(&format!("http://localhost:{}/api/books/search", app.port))
+        .post(format!("http://localhost:{}/api/v1/books/search", app.port))
         .header(header::AUTHORIZATION, format!("Bearer {}", token))
         .json(&json!({
             "query": "author:Martin Fowler"
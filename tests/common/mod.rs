@@ -1 +1,5 @@
+// O fixture do banco isolado é compartilhado com os testes do crate
+#[allow(dead_code)]
+#[path = "../../src/repositories/test_database.rs"]
+pub mod test_database;
 pub mod test_utils;
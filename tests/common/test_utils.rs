@@ -1,33 +1,38 @@
 use reqwest::Client;
 use serde_json::{json, Value};
-use std::net::TcpListener;
+use sqlx::postgres::PgPoolOptions;
+use std::future::Future;
+use std::net::{SocketAddr, TcpListener};
+use std::pin::Pin;
 use std::sync::Arc;
-use tokio::sync::{Mutex, OnceCell};
 use troca_livros_api::app;
+use troca_livros_api::config::Config;
+use troca_livros_api::error::AppError;
+use troca_livros_api::models::book::GoogleBookDto;
+use troca_livros_api::services::google_book_service::GoogleBookService;
+use troca_livros_api::services::health_service::create_health_service;
+use troca_livros_api::state::{AppState, Repositories};
 use uuid::Uuid;
 
+use crate::common::test_database::TestDatabase;
+
 pub struct TestApp {
     pub port: u16,
-}
-
-// Mutex global para garantir que apenas um teste end-to-end execute por vez
-static TEST_MUTEX: OnceCell<Arc<Mutex<()>>> = OnceCell::const_new();
-
-/// Retorna um mutex para garantir a execução sequencial dos testes
-pub async fn get_test_mutex() -> Arc<Mutex<()>> {
-    TEST_MUTEX
-        .get_or_init(|| async { Arc::new(Mutex::new(())) })
-        .await
-        .clone()
+    /// Schema isolado usado pelo aplicativo, quando ele roda sobre o Postgres;
+    /// é removido junto com o TestApp, ao final do teste
+    pub database: Option<TestDatabase>,
 }
 
 /// Configura um aplicativo de teste com um banco de dados de teste
 ///
 /// Esta função:
-/// 1. Cria uma conexão com o banco de dados de teste
-/// 2. Configura o servidor Axum
+/// 1. Cria um schema exclusivo do teste no banco de dados de teste
+/// 2. Configura o servidor Axum sobre esse schema
 /// 3. Inicia o servidor em uma porta aleatória
 /// 4. Retorna o objeto TestApp com informações para os testes
+///
+/// Como cada teste tem o próprio schema, os testes podem rodar em paralelo
+#[allow(dead_code)]
 pub async fn setup_test_app() -> TestApp {
     // Carregar variáveis de ambiente
     dotenv::dotenv().ok();
@@ -42,21 +47,123 @@ pub async fn setup_test_app() -> TestApp {
     let test_db_url =
         std::env::var("TEST_DATABASE_URL").expect("TEST_DATABASE_URL não está definida");
 
+    let database = TestDatabase::create(&test_db_url).await;
+    let config = Config::from_env().expect("Falha ao carregar configuração");
+    let state = AppState::from_config(config, Arc::new(database.pool().clone()))
+        .expect("Falha ao montar o estado da aplicação");
+
+    let mut test_app = spawn_app(app::create_app_with_state(state));
+    test_app.database = Some(database);
+
+    test_app
+}
+
+impl TestApp {
+    /// Schema isolado do aplicativo, para preparar dados diretamente no banco
+    #[allow(dead_code)]
+    pub fn db(&self) -> &TestDatabase {
+        self.database
+            .as_ref()
+            .expect("O aplicativo de teste não usa o Postgres")
+    }
+}
+
+/// Configura um aplicativo de teste com dependências já montadas
+///
+/// Útil para trocar repositórios ou serviços externos por implementações
+/// em memória, sem depender do banco ou da rede
+#[allow(dead_code)]
+pub fn setup_test_app_with_state(state: AppState) -> TestApp {
+    spawn_app(app::create_app_with_state(state))
+}
+
+/// Google Books fixo, sem acesso à rede
+///
+/// A busca sempre devolve o livro "falso-1"; a consulta por ID devolve um
+/// livro com o ID pedido, permitindo cadastrar quantos livros o teste precisar
+#[allow(dead_code)]
+pub struct FakeGoogleBookService;
+
+#[allow(dead_code)]
+pub fn fake_book(google_id: &str) -> GoogleBookDto {
+    GoogleBookDto {
+        google_id: google_id.to_string(),
+        title: "Livro Falso".to_string(),
+        authors: Some("Autora Falsa".to_string()),
+        publisher: None,
+        published_date: None,
+        description: None,
+        image_url: None,
+        page_count: Some(100),
+    }
+}
+
+impl GoogleBookService for FakeGoogleBookService {
+    fn search_books<'a>(
+        &'a self,
+        _query: &'a str,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<GoogleBookDto>, AppError>> + Send + 'a>> {
+        Box::pin(async { Ok(vec![fake_book("falso-1")]) })
+    }
+
+    fn find_book_by_id<'a>(
+        &'a self,
+        google_id: &'a str,
+    ) -> Pin<Box<dyn Future<Output = Result<GoogleBookDto, AppError>> + Send + 'a>> {
+        Box::pin(async move { Ok(fake_book(google_id)) })
+    }
+}
+
+/// Configura um aplicativo de teste inteiramente em memória
+///
+/// Os repositórios usam o banco em memória e o Google Books é o
+/// `FakeGoogleBookService`; cada chamada cria um banco novo, de modo que os
+/// testes podem rodar em paralelo, sem mutex e sem Postgres
+#[allow(dead_code)]
+pub fn setup_in_memory_app() -> TestApp {
+    setup_test_app_with_state(create_in_memory_state(Repositories::in_memory()))
+}
+
+/// Estado da aplicação sobre os repositórios recebidos, com o Google Books falso
+///
+/// Permite que o teste guarde os repositórios para preparar ou alterar dados
+/// diretamente, sem passar pela API
+#[allow(dead_code)]
+pub fn create_in_memory_state(repositories: Repositories) -> AppState {
+    dotenv::dotenv().ok();
+
+    let config = Config::from_env().expect("Falha ao carregar configuração");
+
+    create_in_memory_state_with_config(config, repositories)
+}
+
+/// Como `create_in_memory_state`, mas com a configuração recebida
+#[allow(dead_code)]
+pub fn create_in_memory_state_with_config(config: Config, repositories: Repositories) -> AppState {
+    // Só a verificação de prontidão usaria o pool, que nunca chega a conectar
+    let pool = PgPoolOptions::new()
+        .connect_lazy("postgres://ninguem@127.0.0.1:1/inexistente")
+        .expect("URL do banco inválida");
+    let health_service = create_health_service(&config, Arc::new(pool));
+
+    AppState::new(config, repositories, Arc::new(FakeGoogleBookService), health_service)
+        .expect("Falha ao montar o estado da aplicação")
+}
+
+/// Inicia o servidor em uma porta aleatória
+fn spawn_app(created_app: axum::Router) -> TestApp {
     // Encontrar uma porta disponível
     let listener = TcpListener::bind("127.0.0.1:0").expect("Falha ao vincular a porta aleatória");
     let port = listener.local_addr().unwrap().port();
+    listener.set_nonblocking(true).unwrap();
+    let listener = tokio::net::TcpListener::from_std(listener)
+        .expect("Falha ao registrar o listener no runtime");
 
-    // Configurar rotas sem Swagger UI (não necessário para testes)
-    let created_app = app::create_app(&test_db_url).await;
-
-    // Iniciar o servidor em uma nova thread
-    let server = axum::Server::from_tcp(listener)
-        .expect("Falha ao criar servidor a partir do listener")
-        .serve(created_app.into_make_service());
-
-    tokio::spawn(server);
+    // Iniciar o servidor em uma nova task
+    let service = created_app.into_make_service_with_connect_info::<SocketAddr>();
+    tokio::spawn(async move { axum::serve(listener, service).await });
 
-    TestApp { port }
+    TestApp { port, database: None }
 }
 
 /// Cria um usuário de teste e retorna o token de autenticação
@@ -78,7 +185,7 @@ pub async fn get_auth_token(app: &TestApp) -> String {
 
     // Registrar usuário
     let register_response = client
-        .post(&format!("http://localhost:{}/api/auth/register", app.port))
+        .post(format!("http://localhost:{}/api/v1/auth/register", app.port))
         .json(&json!({
             "name": name,
             "email": email,
@@ -92,7 +199,7 @@ pub async fn get_auth_token(app: &TestApp) -> String {
 
     // Fazer login
     let login_response = client
-        .post(&format!("http://localhost:{}/api/auth/login", app.port))
+        .post(format!("http://localhost:{}/api/v1/auth/login", app.port))
         .json(&json!({
             "email": email,
             "password": password
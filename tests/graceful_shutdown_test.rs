@@ -0,0 +1,105 @@
+use std::time::{Duration, Instant};
+
+use axum::{routing::get, Router};
+use reqwest::StatusCode;
+use tokio::{net::TcpListener, sync::oneshot};
+use troca_livros_api::app::create_database_pool;
+use troca_livros_api::config::DatabaseConfig;
+use troca_livros_api::server::serve;
+
+// Rota que demora o tempo informado, para simular uma requisição em andamento
+fn slow_app(delay: Duration) -> Router {
+    Router::new().route(
+        "/slow",
+        get(move || async move {
+            tokio::time::sleep(delay).await;
+            "ok"
+        }),
+    )
+}
+
+struct RunningServer {
+    port: u16,
+    shutdown: oneshot::Sender<()>,
+    server: tokio::task::JoinHandle<Result<(), troca_livros_api::server::StartupError>>,
+}
+
+async fn start(app: Router, drain_timeout: Duration) -> RunningServer {
+    let listener = TcpListener::bind("127.0.0.1:0").await.expect("Falha ao vincular à porta");
+    let port = listener.local_addr().unwrap().port();
+    let (shutdown, signal) = oneshot::channel::<()>();
+
+    let server = tokio::spawn(serve(
+        listener,
+        app,
+        async {
+            signal.await.ok();
+        },
+        drain_timeout,
+    ));
+
+    RunningServer { port, shutdown, server }
+}
+
+#[tokio::test]
+async fn test_in_flight_request_finishes_after_shutdown_starts() {
+    // Arrange
+    let running = start(slow_app(Duration::from_millis(300)), Duration::from_secs(5)).await;
+    let request = tokio::spawn(reqwest::get(format!("http://localhost:{}/slow", running.port)));
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    // Act
+    running.shutdown.send(()).unwrap();
+
+    // Assert - A requisição termina normalmente e o servidor encerra em seguida
+    let response = request.await.unwrap().expect("A requisição em andamento deveria terminar");
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.text().await.unwrap(), "ok");
+
+    let result = tokio::time::timeout(Duration::from_secs(2), running.server)
+        .await
+        .expect("O servidor deveria encerrar depois de drenar as requisições");
+    assert!(result.unwrap().is_ok());
+}
+
+#[tokio::test]
+async fn test_shutdown_stops_waiting_after_drain_timeout() {
+    // Arrange
+    let running = start(slow_app(Duration::from_secs(30)), Duration::from_millis(200)).await;
+    let request = tokio::spawn(reqwest::get(format!("http://localhost:{}/slow", running.port)));
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    // Act
+    let started = Instant::now();
+    running.shutdown.send(()).unwrap();
+    let result = tokio::time::timeout(Duration::from_secs(5), running.server)
+        .await
+        .expect("O servidor deveria encerrar ao fim do prazo de desligamento");
+
+    // Assert
+    assert!(result.unwrap().is_ok());
+    assert!(started.elapsed() < Duration::from_secs(5));
+    request.abort();
+}
+
+#[tokio::test]
+async fn test_pool_applies_statement_timeout() {
+    // Arrange
+    dotenv::dotenv().ok();
+    let database_url = std::env::var("TEST_DATABASE_URL").expect("TEST_DATABASE_URL não está definida");
+    let config = DatabaseConfig {
+        max_connections: 1,
+        statement_timeout: Some(Duration::from_millis(100)),
+        ..DatabaseConfig::default()
+    };
+
+    // Act
+    let pool = create_database_pool(&database_url, &config).await.expect("Falha ao conectar ao banco de teste");
+    let result = sqlx::query("SELECT pg_sleep(2)").execute(pool.as_ref()).await;
+
+    // Assert - O Postgres cancela o comando (57014 = query_canceled)
+    let error = result.expect_err("O comando deveria exceder o statement timeout");
+    let code = error.as_database_error().and_then(|e| e.code()).map(|code| code.into_owned());
+    assert_eq!(code.as_deref(), Some("57014"));
+    pool.close().await;
+}
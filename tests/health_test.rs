@@ -0,0 +1,76 @@
+mod common;
+
+use crate::common::test_utils::{get_auth_token, setup_test_app};
+use reqwest::{header, StatusCode};
+use serde_json::Value;
+
+#[tokio::test]
+async fn test_health_and_readiness() {
+    // Arrange
+    let app = setup_test_app().await;
+    let client = reqwest::Client::new();
+
+    // Act & Assert - Liveness
+    let response = client
+        .get(format!("http://localhost:{}/health", app.port))
+        .send()
+        .await
+        .expect("Falha ao enviar requisição");
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["status"], "ok");
+
+    // Readiness com o banco de teste disponível
+    let response = client
+        .get(format!("http://localhost:{}/ready", app.port))
+        .send()
+        .await
+        .expect("Falha ao enviar requisição");
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["status"], "ok");
+    assert_eq!(body["checks"]["database"], "ok");
+}
+
+#[tokio::test]
+async fn test_metrics_use_route_patterns() {
+    // Arrange
+    let app = setup_test_app().await;
+    let client = reqwest::Client::new();
+    let token = get_auth_token(&app).await;
+
+    // Uma requisição com ID no caminho
+    let response = client
+        .post(format!(
+            "http://localhost:{}/api/v1/trades/00000000-0000-0000-0000-000000000000/accept",
+            app.port
+        ))
+        .header(header::AUTHORIZATION, format!("Bearer {}", token))
+        .send()
+        .await
+        .expect("Falha ao enviar requisição");
+    let status = response.status().as_u16();
+
+    // Act
+    let response = client
+        .get(format!("http://localhost:{}/metrics", app.port))
+        .send()
+        .await
+        .expect("Falha ao enviar requisição");
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers()[header::CONTENT_TYPE]
+        .to_str()
+        .unwrap()
+        .starts_with("text/plain; version=0.0.4"));
+
+    let text = response.text().await.unwrap();
+    assert!(text.contains(&format!(
+        "http_request_duration_seconds_count{{method=\"POST\",route=\"/api/v1/trades/:trade_id/accept\",status=\"{}\"}}",
+        status
+    )));
+    assert!(text.contains("route=\"/api/v1/auth/login\""));
+    assert!(!text.contains("00000000-0000-0000-0000-000000000000"));
+    assert!(text.contains("db_pool_max_connections"));
+}
@@ -0,0 +1,208 @@
+mod common;
+
+use crate::common::test_utils::{get_auth_token, setup_in_memory_app, TestApp};
+use reqwest::{Client, StatusCode};
+use serde_json::{json, Value};
+
+// Estes testes usam apenas o banco em memória e não dependem do Postgres
+
+// Envia uma requisição autenticada e devolve a situação e o corpo da resposta
+async fn send(
+    app: &TestApp,
+    method: reqwest::Method,
+    path: &str,
+    token: &str,
+    body: Option<Value>,
+) -> (StatusCode, Value) {
+    let mut request = Client::new()
+        .request(method, format!("http://localhost:{}{}", app.port, path))
+        .header("Authorization", format!("Bearer {}", token));
+    if let Some(body) = body {
+        request = request.json(&body);
+    }
+
+    let response = request.send().await.expect("Falha ao enviar requisição");
+    let status = response.status();
+    let body = response.json().await.unwrap_or(Value::Null);
+
+    (status, body)
+}
+
+async fn add_offered(app: &TestApp, token: &str, google_id: &str) -> Value {
+    let (status, body) = send(
+        app,
+        reqwest::Method::POST,
+        "/api/v1/books/offered",
+        token,
+        Some(json!({ "google_id": google_id })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{}", body);
+
+    body["data"][0].clone()
+}
+
+async fn add_wanted(app: &TestApp, token: &str, google_id: &str) {
+    let (status, body) = send(
+        app,
+        reqwest::Method::POST,
+        "/api/v1/books/wanted",
+        token,
+        Some(json!({ "google_id": google_id })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{}", body);
+}
+
+#[tokio::test]
+async fn test_in_memory_trade_flow() {
+    // Arrange - Ana oferece o livro A e quer o B; Bruno oferece o B e quer o A
+    let app = setup_in_memory_app();
+    let ana = get_auth_token(&app).await;
+    let bruno = get_auth_token(&app).await;
+
+    let ana_copy = add_offered(&app, &ana, "livro-a").await;
+    add_wanted(&app, &ana, "livro-b").await;
+    let bruno_copy = add_offered(&app, &bruno, "livro-b").await;
+    add_wanted(&app, &bruno, "livro-a").await;
+
+    // Act - Ana encontra a troca e a propõe
+    let (status, possible) = send(&app, reqwest::Method::GET, "/api/v1/trades/possible", &ana, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(possible.as_array().unwrap().len(), 1);
+    assert_eq!(possible[0]["offered_copy"]["id"], ana_copy["id"]);
+    assert_eq!(possible[0]["wanted_copy"]["id"], bruno_copy["id"]);
+
+    let (status, proposed) = send(
+        &app,
+        reqwest::Method::POST,
+        "/api/v1/trades",
+        &ana,
+        Some(json!({
+            "offered_copy_id": ana_copy["id"],
+            "wanted_copy_id": bruno_copy["id"]
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{}", proposed);
+    let trade_id = proposed["data"]["id"].as_str().unwrap().to_string();
+
+    // Exemplares prometidos saem das sugestões
+    let (_, possible) = send(&app, reqwest::Method::GET, "/api/v1/trades/possible", &ana, None).await;
+    assert!(possible.as_array().unwrap().is_empty());
+
+    // Act - Bruno aceita e a troca é concluída
+    let (status, _) = send(
+        &app,
+        reqwest::Method::POST,
+        &format!("/api/v1/trades/{}/accept", trade_id),
+        &bruno,
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, completed) = send(
+        &app,
+        reqwest::Method::POST,
+        &format!("/api/v1/trades/{}/complete", trade_id),
+        &ana,
+        None,
+    )
+    .await;
+
+    // Assert - Cada um passa a possuir o livro recebido
+    assert_eq!(status, StatusCode::OK, "{}", completed);
+    assert_eq!(completed["data"]["status"], "completed");
+
+    let (_, books) = send(&app, reqwest::Method::GET, "/api/v1/books", &ana, None).await;
+    let offered = books["data"]["offered_books"].as_array().unwrap();
+    assert_eq!(offered.len(), 1);
+    assert_eq!(offered[0]["book"]["google_id"], "livro-b");
+    assert!(books["data"]["wanted_books"].as_array().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_in_memory_rejects_wanting_an_offered_book() {
+    // Arrange
+    let app = setup_in_memory_app();
+    let token = get_auth_token(&app).await;
+    add_offered(&app, &token, "livro-a").await;
+
+    // Act
+    let (status, body) = send(
+        &app,
+        reqwest::Method::POST,
+        "/api/v1/books/wanted",
+        &token,
+        Some(json!({ "google_id": "livro-a" })),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["error"]["code"], "BOOK_ALREADY_OFFERED");
+}
+
+#[tokio::test]
+async fn test_in_memory_book_details() {
+    // Arrange - Ana e Bruno oferecem o livro A, que Carla deseja
+    let app = setup_in_memory_app();
+    let ana = get_auth_token(&app).await;
+    let bruno = get_auth_token(&app).await;
+    let carla = get_auth_token(&app).await;
+
+    let copy = add_offered(&app, &ana, "livro-a").await;
+    add_offered(&app, &bruno, "livro-a").await;
+    add_wanted(&app, &carla, "livro-a").await;
+    let path = format!("/api/v1/books/{}", copy["book_id"].as_str().unwrap());
+
+    // Act
+    let (status, for_ana) = send(&app, reqwest::Method::GET, &path, &ana, None).await;
+    let (_, for_carla) = send(&app, reqwest::Method::GET, &path, &carla, None).await;
+    let (missing, _) = send(
+        &app,
+        reqwest::Method::GET,
+        "/api/v1/books/00000000-0000-0000-0000-000000000000",
+        &ana,
+        None,
+    )
+    .await;
+
+    // Assert
+    assert_eq!(status, StatusCode::OK, "{}", for_ana);
+    assert_eq!(for_ana["data"]["book"]["google_id"], "livro-a");
+    assert_eq!(for_ana["data"]["offered_count"], 2);
+    assert_eq!(for_ana["data"]["wanted_count"], 1);
+    assert_eq!(for_ana["data"]["user_list"], "offered");
+    assert_eq!(for_carla["data"]["user_list"], "wanted");
+    assert_eq!(missing, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_in_memory_apps_do_not_share_data() {
+    // Arrange - Mesmo email em dois aplicativos diferentes
+    let first = setup_in_memory_app();
+    let second = setup_in_memory_app();
+    let client = Client::new();
+    let user = json!({
+        "name": "Ana",
+        "email": "ana@example.com",
+        "password": "Senha@123"
+    });
+
+    // Act
+    let mut statuses = Vec::new();
+    for app in [&first, &second] {
+        let response = client
+            .post(format!("http://localhost:{}/api/v1/auth/register", app.port))
+            .json(&user)
+            .send()
+            .await
+            .expect("Falha ao registrar usuário");
+        statuses.push(response.status());
+    }
+
+    // Assert
+    assert_eq!(statuses, vec![StatusCode::CREATED, StatusCode::CREATED]);
+}
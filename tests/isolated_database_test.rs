@@ -0,0 +1,83 @@
+mod common;
+
+use crate::common::test_database::TestDatabase;
+use crate::common::test_utils::{get_auth_token, setup_test_app};
+use reqwest::StatusCode;
+use serde_json::Value;
+use sqlx::{Connection, PgConnection};
+
+fn test_database_url() -> String {
+    dotenv::dotenv().ok();
+    std::env::var("TEST_DATABASE_URL").expect("TEST_DATABASE_URL não está definida")
+}
+
+// Consulta se o schema ainda existe no banco de teste
+async fn schema_exists(schema: &str) -> bool {
+    let mut conn = PgConnection::connect(&test_database_url()).await.expect("Falha ao conectar ao banco de teste");
+
+    sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM information_schema.schemata WHERE schema_name = $1)")
+        .bind(schema)
+        .fetch_one(&mut conn)
+        .await
+        .expect("Falha ao consultar os schemas")
+}
+
+#[tokio::test]
+async fn test_seeded_data_is_visible_only_to_its_app() {
+    // Arrange - Dados preparados direto no schema do primeiro aplicativo
+    let app = setup_test_app().await;
+    let other_app = setup_test_app().await;
+    let db = app.db();
+    let owner = db.create_user("Dona do Livro").await;
+    let book_id = db.create_book("Dom Casmurro").await;
+    let copy_id = db.add_offered(owner, book_id).await;
+    let reader = db.create_user("Leitora").await;
+    db.add_wanted(reader, book_id, 5).await;
+
+    let client = reqwest::Client::new();
+    let token = get_auth_token(&app).await;
+    let other_token = get_auth_token(&other_app).await;
+
+    // Act
+    let offers: Value = client
+        .get(format!("http://localhost:{}/api/v1/books/{}/offers", app.port, book_id))
+        .header("Authorization", format!("Bearer {}", token))
+        .send()
+        .await
+        .expect("Falha ao enviar requisição")
+        .json()
+        .await
+        .expect("Falha ao ler corpo da resposta");
+    let other_response = client
+        .get(format!("http://localhost:{}/api/v1/books/{}/offers", other_app.port, book_id))
+        .header("Authorization", format!("Bearer {}", other_token))
+        .send()
+        .await
+        .expect("Falha ao enviar requisição");
+    let other_status = other_response.status();
+    let other_offers: Value = other_response.json().await.unwrap_or(Value::Null);
+
+    // Assert - O segundo aplicativo não enxerga o schema do primeiro
+    assert_eq!(offers["data"].as_array().unwrap().len(), 1);
+    assert_eq!(offers["data"][0]["copy"]["id"], copy_id.to_string());
+    assert!(
+        other_status != StatusCode::OK || other_offers["data"].as_array().unwrap().is_empty(),
+        "{}",
+        other_offers
+    );
+}
+
+#[tokio::test]
+async fn test_schema_is_dropped_with_the_database() {
+    // Arrange
+    let database = TestDatabase::create(&test_database_url()).await;
+    let schema = database.schema().to_string();
+    database.create_user("Ana").await;
+    assert!(schema_exists(&schema).await);
+
+    // Act
+    drop(database);
+
+    // Assert
+    assert!(!schema_exists(&schema).await);
+}
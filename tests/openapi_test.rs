@@ -0,0 +1,107 @@
+mod common;
+
+use std::collections::BTreeSet;
+
+use crate::common::test_utils::{create_in_memory_state, setup_in_memory_app};
+use reqwest::{header, Method, StatusCode};
+use serde_json::Value;
+use troca_livros_api::{docs::api_doc, routes::api_routes, state::Repositories};
+use utoipa::openapi::OpenApi;
+
+/// Especificação montada a partir da tabela de rotas, como o aplicativo faz
+fn spec() -> OpenApi {
+    let state = create_in_memory_state(Repositories::in_memory());
+    api_doc(api_routes(&state).paths().clone())
+}
+
+/// Pares (método, caminho) presentes na especificação
+fn documented_routes(spec: &OpenApi) -> BTreeSet<(String, String)> {
+    let spec = serde_json::to_value(spec).unwrap();
+    let paths = spec["paths"].as_object().expect("especificação sem paths");
+
+    paths
+        .iter()
+        .flat_map(|(path, operations)| {
+            operations
+                .as_object()
+                .unwrap()
+                .keys()
+                .map(move |method| (method.clone(), path.clone()))
+        })
+        .collect()
+}
+
+/// Substitui os parâmetros do caminho (`{trade_id}`) por um valor qualquer
+fn concrete_path(openapi_path: &str) -> String {
+    openapi_path
+        .split('/')
+        .map(|segment| if segment.starts_with('{') { uuid::Uuid::nil().to_string() } else { segment.to_string() })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[tokio::test]
+async fn test_every_documented_route_is_registered() {
+    // Arrange
+    let app = setup_in_memory_app();
+    let client = reqwest::Client::new();
+    let documented = documented_routes(&spec());
+
+    // Assert - A tabela precisa conter as rotas da API e as de fora do prefixo
+    assert!(documented.contains(&("post".to_string(), "/api/v1/auth/login".to_string())));
+    assert!(documented.contains(&("post".to_string(), "/api/v1/trades/{trade_id}/accept".to_string())));
+    assert!(documented.contains(&("get".to_string(), "/.well-known/jwks.json".to_string())));
+
+    for (method, path) in &documented {
+        // Act
+        let response = client
+            .request(
+                Method::from_bytes(method.to_uppercase().as_bytes()).unwrap(),
+                format!("http://localhost:{}{}", app.port, concrete_path(path)),
+            )
+            .send()
+            .await
+            .expect("Falha ao enviar requisição");
+
+        // Assert - Sem a rota, o Axum responde 405 ou 404 sem corpo; as
+        // respostas dos handlers e dos middlewares sempre têm corpo
+        let status = response.status();
+        let body = response.bytes().await.unwrap();
+        assert_ne!(status, StatusCode::METHOD_NOT_ALLOWED, "{} {} não está registrada", method, path);
+        assert!(
+            status != StatusCode::NOT_FOUND || !body.is_empty(),
+            "{} {} não está registrada",
+            method,
+            path
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_spec_is_served_as_json_and_yaml() {
+    // Arrange
+    let app = setup_in_memory_app();
+    let client = reqwest::Client::new();
+    let spec = spec();
+
+    // Act
+    let json_response = client
+        .get(format!("http://localhost:{}/api-docs/openapi.json", app.port))
+        .send()
+        .await
+        .expect("Falha ao enviar requisição");
+    let yaml_response = client
+        .get(format!("http://localhost:{}/api-docs/openapi.yaml", app.port))
+        .send()
+        .await
+        .expect("Falha ao enviar requisição");
+
+    // Assert
+    assert_eq!(json_response.status(), StatusCode::OK);
+    let body: Value = json_response.json().await.unwrap();
+    assert_eq!(body, serde_json::to_value(&spec).unwrap());
+
+    assert_eq!(yaml_response.status(), StatusCode::OK);
+    assert_eq!(yaml_response.headers()[header::CONTENT_TYPE], "application/yaml");
+    assert_eq!(yaml_response.text().await.unwrap(), spec.to_yaml().unwrap());
+}
@@ -0,0 +1,64 @@
+mod common;
+
+use std::net::IpAddr;
+
+use crate::common::test_utils::{create_in_memory_state_with_config, setup_test_app_with_state, TestApp};
+use reqwest::{Client, StatusCode};
+use serde_json::json;
+use troca_livros_api::config::Config;
+use troca_livros_api::services::rate_limit_service::REGISTER_LIMIT;
+use troca_livros_api::state::Repositories;
+
+// Estes testes usam apenas o banco em memória e não dependem do Postgres
+
+fn setup_app(trusted_proxies: Vec<IpAddr>) -> TestApp {
+    dotenv::dotenv().ok();
+    let mut config = Config::from_env().expect("Falha ao carregar configuração");
+    config.trusted_proxies = trusted_proxies;
+
+    setup_test_app_with_state(create_in_memory_state_with_config(config, Repositories::in_memory()))
+}
+
+async fn register_from(app: &TestApp, forwarded_for: &str) -> StatusCode {
+    Client::new()
+        .post(format!("http://localhost:{}/api/v1/auth/register", app.port))
+        .header("x-forwarded-for", forwarded_for)
+        // Corpo incompleto: a requisição é contada e recusada antes de chegar ao cadastro
+        .json(&json!({}))
+        .send()
+        .await
+        .expect("Falha ao enviar requisição")
+        .status()
+}
+
+#[tokio::test]
+async fn test_forwarded_client_is_limited_behind_trusted_proxy() {
+    // Arrange - Os testes conectam a partir de 127.0.0.1, tratado como o proxy
+    let app = setup_app(vec!["127.0.0.1".parse().unwrap()]);
+
+    // Act
+    for _ in 0..REGISTER_LIMIT.max_attempts {
+        assert_ne!(register_from(&app, "203.0.113.1").await, StatusCode::TOO_MANY_REQUESTS);
+    }
+    let same_client = register_from(&app, "203.0.113.1").await;
+    let other_client = register_from(&app, "203.0.113.2").await;
+
+    // Assert - Cada cliente atrás do proxy tem o seu limite
+    assert_eq!(same_client, StatusCode::TOO_MANY_REQUESTS);
+    assert_ne!(other_client, StatusCode::TOO_MANY_REQUESTS);
+}
+
+#[tokio::test]
+async fn test_forwarded_header_is_ignored_without_trusted_proxy() {
+    // Arrange
+    let app = setup_app(vec![]);
+
+    // Act
+    for _ in 0..REGISTER_LIMIT.max_attempts {
+        assert_ne!(register_from(&app, "203.0.113.1").await, StatusCode::TOO_MANY_REQUESTS);
+    }
+    let spoofed_client = register_from(&app, "203.0.113.2").await;
+
+    // Assert - Trocar o header não escapa do limite da conexão
+    assert_eq!(spoofed_client, StatusCode::TOO_MANY_REQUESTS);
+}
@@ -1,16 +1,12 @@
 mod common;
 
-use crate::common::test_utils::{get_auth_token, get_test_mutex, setup_test_app};
+use crate::common::test_utils::{get_auth_token, setup_test_app};
 use reqwest::{header, StatusCode};
 use serde_json::{json, Value};
 
 
 #[tokio::test]
 async fn test_remove_book_from_offered() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-    
     // Arrange - Configurar o aplicativo de teste e autenticar
     let app = setup_test_app().await;
     let token = get_auth_token(&app).await;
@@ -18,7 +14,7 @@ async fn test_remove_book_from_offered() {
 
     // Primeiro, vamos buscar um livro para obter um ID válido
     let search_response = client
-        .post(&format!("http://localhost:{}/api/books/search", app.port))
+        .post(format!("http://localhost:{}/api/v1/books/search", app.port))
         .header(header::AUTHORIZATION, format!("Bearer {}", token))
         .json(&json!({
             "query": "Clean Code"
@@ -39,7 +35,7 @@ async fn test_remove_book_from_offered() {
 
     // Adicionar o livro à lista de possuídos
     let add_response = client
-        .post(&format!("http://localhost:{}/api/books/offered", app.port))
+        .post(format!("http://localhost:{}/api/v1/books/offered", app.port))
         .header(header::AUTHORIZATION, format!("Bearer {}", token))
         .json(&json!({
             "google_id": google_id
@@ -57,8 +53,8 @@ async fn test_remove_book_from_offered() {
 
     // Act - Remover o livro da lista de possuídos
     let response = client
-        .delete(&format!(
-            "http://localhost:{}/api/books/offered/{}",
+        .delete(format!(
+            "http://localhost:{}/api/v1/books/offered/{}",
             app.port, book_id
         ))
         .header(header::AUTHORIZATION, format!("Bearer {}", token))
@@ -82,8 +78,8 @@ async fn test_remove_book_from_offered() {
 
     // Tentar remover o mesmo livro novamente deve falhar
     let second_delete_response = client
-        .delete(&format!(
-            "http://localhost:{}/api/books/offered/{}",
+        .delete(format!(
+            "http://localhost:{}/api/v1/books/offered/{}",
             app.port, book_id
         ))
         .header(header::AUTHORIZATION, format!("Bearer {}", token))
@@ -99,6 +95,7 @@ async fn test_remove_book_from_offered() {
 
     assert_eq!(second_delete_status, StatusCode::BAD_REQUEST);
     assert_eq!(second_delete_body["error"]["status"], 400);
+    assert_eq!(second_delete_body["error"]["code"], "BOOK_NOT_OFFERED");
     assert!(second_delete_body["error"]["message"]
         .as_str()
         .unwrap()
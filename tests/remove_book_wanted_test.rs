@@ -1,16 +1,12 @@
 mod common;
 
-use crate::common::test_utils::{get_auth_token, get_test_mutex, setup_test_app};
+use crate::common::test_utils::{get_auth_token, setup_test_app};
 use reqwest::{header, StatusCode};
 use serde_json::{json, Value};
 
 
 #[tokio::test]
 async fn test_remove_book_from_wanted() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-    
     // Arrange - Configurar o aplicativo de teste e autenticar
     let app = setup_test_app().await;
     let token = get_auth_token(&app).await;
@@ -18,7 +14,7 @@ async fn test_remove_book_from_wanted() {
 
     // Primeiro, vamos buscar um livro para obter um ID válido
     let search_response = client
-        .post(&format!("http://localhost:{}/api/books/search", app.port))
+        .post(format!("http://localhost:{}/api/v1/books/search", app.port))
         .header(header::AUTHORIZATION, format!("Bearer {}", token))
         .json(&json!({
             "query": "Domain-Driven Design"
@@ -39,7 +35,7 @@ async fn test_remove_book_from_wanted() {
 
     // Adicionar o livro à lista de desejados
     let add_response = client
-        .post(&format!("http://localhost:{}/api/books/wanted", app.port))
+        .post(format!("http://localhost:{}/api/v1/books/wanted", app.port))
         .header(header::AUTHORIZATION, format!("Bearer {}", token))
         .json(&json!({
             "google_id": google_id
@@ -57,8 +53,8 @@ async fn test_remove_book_from_wanted() {
 
     // Act - Remover o livro da lista de desejados
     let response = client
-        .delete(&format!(
-            "http://localhost:{}/api/books/wanted/{}",
+        .delete(format!(
+            "http://localhost:{}/api/v1/books/wanted/{}",
             app.port, book_id
         ))
         .header(header::AUTHORIZATION, format!("Bearer {}", token))
@@ -82,8 +78,8 @@ async fn test_remove_book_from_wanted() {
 
     // Tentar remover o mesmo livro novamente deve falhar
     let second_delete_response = client
-        .delete(&format!(
-            "http://localhost:{}/api/books/wanted/{}",
+        .delete(format!(
+            "http://localhost:{}/api/v1/books/wanted/{}",
             app.port, book_id
         ))
         .header(header::AUTHORIZATION, format!("Bearer {}", token))
@@ -99,6 +95,7 @@ async fn test_remove_book_from_wanted() {
 
     assert_eq!(second_delete_status, StatusCode::BAD_REQUEST);
     assert_eq!(second_delete_body["error"]["status"], 400);
+    assert_eq!(second_delete_body["error"]["code"], "BOOK_NOT_WANTED");
     assert!(second_delete_body["error"]["message"]
         .as_str()
         .unwrap()
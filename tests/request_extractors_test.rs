@@ -0,0 +1,206 @@
+mod common;
+
+use crate::common::test_utils::{
+    create_in_memory_state, get_auth_token, setup_in_memory_app, setup_test_app_with_state,
+};
+use reqwest::{header, Client, StatusCode};
+use serde_json::{json, Value};
+use troca_livros_api::state::Repositories;
+use uuid::Uuid;
+
+// Estes testes usam apenas o banco em memória e não dependem do Postgres
+
+#[tokio::test]
+async fn test_malformed_json_uses_error_envelope() {
+    // Arrange
+    let app = setup_in_memory_app();
+
+    // Act - JSON cortado no meio
+    let response = Client::new()
+        .post(format!("http://localhost:{}/api/v1/auth/register", app.port))
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(r#"{"name": "Ana", "email": "#)
+        .send()
+        .await
+        .expect("Falha ao enviar requisição");
+
+    // Assert
+    let status = response.status();
+    let body: Value = response.json().await.expect("O erro deveria vir em JSON");
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"]["code"], "MALFORMED_BODY");
+    assert_eq!(body["error"]["status"], 400);
+    assert!(body["error"]["request_id"].is_string());
+}
+
+#[tokio::test]
+async fn test_wrong_field_type_returns_unprocessable_entity() {
+    // Arrange
+    let app = setup_in_memory_app();
+    let token = get_auth_token(&app).await;
+
+    // Act - Prioridade enviada como texto
+    let response = Client::new()
+        .post(format!("http://localhost:{}/api/v1/books/wanted", app.port))
+        .header(header::AUTHORIZATION, format!("Bearer {}", token))
+        .json(&json!({ "google_id": "livro-a", "priority": "alta" }))
+        .send()
+        .await
+        .expect("Falha ao enviar requisição");
+
+    // Assert
+    let status = response.status();
+    let body: Value = response.json().await.expect("O erro deveria vir em JSON");
+
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["error"]["code"], "INVALID_BODY");
+    assert!(body["error"]["message"].as_str().unwrap().contains("priority"));
+}
+
+#[tokio::test]
+async fn test_body_without_json_content_type_is_rejected() {
+    // Arrange
+    let app = setup_in_memory_app();
+
+    // Act
+    let response = Client::new()
+        .post(format!("http://localhost:{}/api/v1/auth/login", app.port))
+        .body(r#"{"email": "ana@example.com", "password": "Senha@123"}"#)
+        .send()
+        .await
+        .expect("Falha ao enviar requisição");
+
+    // Assert
+    let status = response.status();
+    let body: Value = response.json().await.expect("O erro deveria vir em JSON");
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"]["code"], "MALFORMED_BODY");
+}
+
+#[tokio::test]
+async fn test_validation_rules_return_field_details() {
+    // Arrange
+    let app = setup_in_memory_app();
+
+    // Act
+    let response = Client::new()
+        .post(format!("http://localhost:{}/api/v1/auth/register", app.port))
+        .json(&json!({ "name": "Ana", "email": "email_invalido", "password": "Senha@123" }))
+        .send()
+        .await
+        .expect("Falha ao enviar requisição");
+
+    // Assert
+    let status = response.status();
+    let body: Value = response.json().await.expect("O erro deveria vir em JSON");
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"]["code"], "VALIDATION_FAILED");
+    assert_eq!(body["error"]["details"][0]["field"], "email");
+    assert_eq!(body["error"]["details"][0]["code"], "email_invalid");
+}
+
+#[tokio::test]
+async fn test_token_of_removed_account_is_rejected() {
+    // Arrange - Token válido de um usuário que não existe no banco
+    let state = create_in_memory_state(Repositories::in_memory());
+    let token = state.token_service.issue(&Uuid::new_v4()).unwrap();
+    let app = setup_test_app_with_state(state);
+
+    // Act
+    let response = Client::new()
+        .get(format!("http://localhost:{}/api/v1/trades", app.port))
+        .header(header::AUTHORIZATION, format!("Bearer {}", token))
+        .send()
+        .await
+        .expect("Falha ao enviar requisição");
+
+    // Assert
+    let status = response.status();
+    let body: Value = response.json().await.expect("O erro deveria vir em JSON");
+
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(body["error"]["code"], "UNAUTHORIZED");
+}
+
+#[tokio::test]
+async fn test_banned_account_is_forbidden_until_reactivated() {
+    // Arrange
+    let repositories = Repositories::in_memory();
+    let state = create_in_memory_state(repositories.clone());
+    let app = setup_test_app_with_state(state.clone());
+    let token = get_auth_token(&app).await;
+    let user_id = Uuid::parse_str(&state.token_service.verify(&token).unwrap().sub).unwrap();
+
+    let list_trades = || {
+        Client::new()
+            .get(format!("http://localhost:{}/api/v1/trades", app.port))
+            .header(header::AUTHORIZATION, format!("Bearer {}", token))
+            .send()
+    };
+
+    // Act - Conta suspensa
+    assert!(repositories.user.set_banned(&user_id, true).await.unwrap());
+    let banned = list_trades().await.expect("Falha ao enviar requisição");
+    let banned_status = banned.status();
+    let banned_body: Value = banned.json().await.expect("O erro deveria vir em JSON");
+
+    // Act - Conta reativada
+    assert!(repositories.user.set_banned(&user_id, false).await.unwrap());
+    let reactivated = list_trades().await.expect("Falha ao enviar requisição");
+
+    // Assert
+    assert_eq!(banned_status, StatusCode::FORBIDDEN);
+    assert_eq!(banned_body["error"]["code"], "FORBIDDEN");
+    assert_eq!(reactivated.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_banned_account_cannot_log_in_or_search() {
+    // Arrange
+    let repositories = Repositories::in_memory();
+    let state = create_in_memory_state(repositories.clone());
+    let app = setup_test_app_with_state(state.clone());
+    let client = Client::new();
+    let credentials = json!({ "email": "suspensa@example.com", "password": "Senha@123" });
+
+    let registered = client
+        .post(format!("http://localhost:{}/api/v1/auth/register", app.port))
+        .json(&json!({ "name": "Suspensa", "email": "suspensa@example.com", "password": "Senha@123" }))
+        .send()
+        .await
+        .expect("Falha ao registrar usuário");
+    assert_eq!(registered.status(), StatusCode::CREATED);
+
+    let login = || {
+        client
+            .post(format!("http://localhost:{}/api/v1/auth/login", app.port))
+            .json(&credentials)
+            .send()
+    };
+    let login_body: Value = login().await.unwrap().json().await.expect("Falha ao ler resposta de login");
+    let token = login_body["data"]["access_token"].as_str().unwrap().to_string();
+    let user_id = Uuid::parse_str(&state.token_service.verify(&token).unwrap().sub).unwrap();
+
+    // Act - Conta suspensa depois de emitido o token
+    assert!(repositories.user.set_banned(&user_id, true).await.unwrap());
+    let banned_login = login().await.expect("Falha ao enviar requisição");
+    let banned_login_status = banned_login.status();
+    let banned_login_body: Value = banned_login.json().await.expect("O erro deveria vir em JSON");
+
+    let search = client
+        .post(format!("http://localhost:{}/api/v1/books/search", app.port))
+        .header(header::AUTHORIZATION, format!("Bearer {}", token))
+        .json(&json!({ "query": "Clean Code" }))
+        .send()
+        .await
+        .expect("Falha ao enviar requisição");
+
+    // Assert
+    assert_eq!(banned_login_status, StatusCode::FORBIDDEN);
+    assert_eq!(banned_login_body["error"]["code"], "FORBIDDEN");
+    assert!(banned_login_body["data"].is_null());
+    assert_eq!(search.status(), StatusCode::FORBIDDEN);
+}
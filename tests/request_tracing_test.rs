@@ -0,0 +1,104 @@
+mod common;
+
+use std::io;
+use std::sync::{Arc, Mutex};
+
+use crate::common::test_utils::{get_auth_token, setup_in_memory_app};
+use reqwest::{header, Client, StatusCode};
+use serde_json::Value;
+use tracing_subscriber::layer::SubscriberExt;
+use troca_livros_api::telemetry::json_layer;
+
+// Escritor que guarda as linhas de log em memória
+#[derive(Clone, Default)]
+struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+impl io::Write for Buffer {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(bytes);
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+// Linha de fim da requisição feita à rota informada
+fn completed_request(buffer: &Buffer, route: &str) -> Value {
+    String::from_utf8(buffer.0.lock().unwrap().clone())
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str::<Value>(line).expect("Linha de log não é JSON"))
+        .find(|line| line["fields"]["message"] == "requisição concluída" && line["span"]["route"] == route)
+        .unwrap_or_else(|| panic!("Nenhum log de fim de requisição para {}", route))
+}
+
+#[tokio::test]
+async fn test_request_span_records_route_status_user_and_request_id() {
+    // Arrange - O servidor roda na mesma thread do teste, com o mesmo subscriber
+    let buffer = Buffer::default();
+    let writer = buffer.clone();
+    let _guard = tracing::subscriber::set_default(
+        tracing_subscriber::registry().with(json_layer(move || writer.clone())),
+    );
+
+    let app = setup_in_memory_app();
+    let token = get_auth_token(&app).await;
+
+    // Act
+    let response = Client::new()
+        .get(format!("http://localhost:{}/api/v1/trades", app.port))
+        .header(header::AUTHORIZATION, format!("Bearer {}", token))
+        .header("x-request-id", "rastreio-123")
+        .send()
+        .await
+        .expect("Falha ao enviar requisição");
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["x-request-id"], "rastreio-123");
+
+    let line = completed_request(&buffer, "/api/v1/trades");
+    let span = &line["span"];
+    assert_eq!(line["level"], "INFO");
+    assert_eq!(span["name"], "http_request");
+    assert_eq!(span["method"], "GET");
+    assert_eq!(span["request_id"], "rastreio-123");
+    assert_eq!(span["status"], 200);
+    assert!(span["latency_ms"].is_u64());
+    assert!(uuid::Uuid::parse_str(span["user_id"].as_str().unwrap()).is_ok());
+}
+
+#[tokio::test]
+async fn test_request_span_uses_route_pattern() {
+    // Arrange
+    let buffer = Buffer::default();
+    let writer = buffer.clone();
+    let _guard = tracing::subscriber::set_default(
+        tracing_subscriber::registry().with(json_layer(move || writer.clone())),
+    );
+
+    let app = setup_in_memory_app();
+    let token = get_auth_token(&app).await;
+
+    // Act - Troca inexistente
+    let response = Client::new()
+        .post(format!(
+            "http://localhost:{}/api/v1/trades/{}/accept",
+            app.port,
+            uuid::Uuid::new_v4()
+        ))
+        .header(header::AUTHORIZATION, format!("Bearer {}", token))
+        .send()
+        .await
+        .expect("Falha ao enviar requisição");
+
+    // Assert - A rota aparece pelo padrão, sem o id da troca
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let line = completed_request(&buffer, "/api/v1/trades/:trade_id/accept");
+    assert_eq!(line["span"]["status"], 404);
+    assert_eq!(line["span"]["method"], "POST");
+    assert!(line["span"]["request_id"].as_str().is_some_and(|id| id != "-"));
+}
@@ -0,0 +1,138 @@
+mod common;
+
+use std::time::Duration;
+
+use crate::common::test_utils::{create_in_memory_state_with_config, setup_test_app_with_state, TestApp};
+use axum::http::HeaderValue;
+use reqwest::{header, Client, Method, StatusCode};
+use serde_json::{json, Value};
+use troca_livros_api::config::{Config, CorsConfig};
+use troca_livros_api::state::Repositories;
+
+// Estes testes usam apenas o banco em memória e não dependem do Postgres
+
+const ALLOWED_ORIGIN: &str = "https://app.example.com";
+
+fn setup_app(configure: impl FnOnce(&mut Config)) -> TestApp {
+    dotenv::dotenv().ok();
+    let mut config = Config::from_env().expect("Falha ao carregar configuração");
+    configure(&mut config);
+
+    setup_test_app_with_state(create_in_memory_state_with_config(config, Repositories::in_memory()))
+}
+
+fn restricted_cors(config: &mut Config) {
+    config.cors = CorsConfig {
+        allowed_origins: Some(vec![HeaderValue::from_static(ALLOWED_ORIGIN)]),
+        allow_credentials: true,
+        max_age: Duration::from_secs(120),
+        ..CorsConfig::default()
+    };
+}
+
+async fn preflight(app: &TestApp, origin: &str) -> reqwest::Response {
+    Client::new()
+        .request(Method::OPTIONS, format!("http://localhost:{}/api/v1/trades", app.port))
+        .header(header::ORIGIN, origin)
+        .header(header::ACCESS_CONTROL_REQUEST_METHOD, "PATCH")
+        .header(header::ACCESS_CONTROL_REQUEST_HEADERS, "authorization,content-type")
+        .send()
+        .await
+        .expect("Falha ao enviar requisição")
+}
+
+#[tokio::test]
+async fn test_api_responses_include_security_headers() {
+    // Arrange
+    let app = setup_app(|config| config.hsts_max_age_secs = 3600);
+
+    // Act
+    let response = Client::new()
+        .get(format!("http://localhost:{}/health", app.port))
+        .send()
+        .await
+        .expect("Falha ao enviar requisição");
+
+    // Assert
+    let headers = response.headers();
+    assert_eq!(headers["strict-transport-security"], "max-age=3600; includeSubDomains");
+    assert_eq!(headers["x-content-type-options"], "nosniff");
+    assert_eq!(headers["x-frame-options"], "DENY");
+    assert_eq!(headers["referrer-policy"], "no-referrer");
+    assert_eq!(headers["content-security-policy"], "default-src 'none'; frame-ancestors 'none'");
+}
+
+#[tokio::test]
+async fn test_swagger_ui_gets_its_own_content_security_policy() {
+    // Arrange - Sem HSTS
+    let app = setup_app(|config| config.hsts_max_age_secs = 0);
+
+    // Act
+    let response = Client::new()
+        .get(format!("http://localhost:{}/docs/", app.port))
+        .send()
+        .await
+        .expect("Falha ao enviar requisição");
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::OK);
+    let headers = response.headers();
+    let csp = headers["content-security-policy"].to_str().unwrap();
+    assert!(csp.contains("default-src 'self'"), "{}", csp);
+    assert!(csp.contains("style-src 'self' 'unsafe-inline'"), "{}", csp);
+    assert_eq!(headers["x-frame-options"], "DENY");
+    assert!(headers.get("strict-transport-security").is_none());
+}
+
+#[tokio::test]
+async fn test_preflight_follows_configured_cors() {
+    // Arrange
+    let app = setup_app(restricted_cors);
+
+    // Act
+    let response = preflight(&app, ALLOWED_ORIGIN).await;
+
+    // Assert
+    let headers = response.headers();
+    assert_eq!(headers["access-control-allow-origin"], ALLOWED_ORIGIN);
+    assert_eq!(headers["access-control-allow-credentials"], "true");
+    assert_eq!(headers["access-control-max-age"], "120");
+    assert!(headers["access-control-allow-methods"].to_str().unwrap().contains("PATCH"));
+}
+
+#[tokio::test]
+async fn test_preflight_rejects_unknown_origin() {
+    // Arrange
+    let app = setup_app(restricted_cors);
+
+    // Act
+    let response = preflight(&app, "https://outro.example.com").await;
+
+    // Assert
+    assert!(response.headers().get("access-control-allow-origin").is_none());
+}
+
+#[tokio::test]
+async fn test_json_body_over_the_limit_is_rejected() {
+    // Arrange
+    let app = setup_app(|config| config.json_body_limit_bytes = 1024);
+
+    // Act
+    let response = Client::new()
+        .post(format!("http://localhost:{}/api/v1/auth/register", app.port))
+        .json(&json!({
+            "name": "A".repeat(2048),
+            "email": "ana@example.com",
+            "password": "Senha@123"
+        }))
+        .send()
+        .await
+        .expect("Falha ao enviar requisição");
+
+    // Assert
+    let status = response.status();
+    let body: Value = response.json().await.expect("O erro deveria vir em JSON");
+
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(body["error"]["code"], "BODY_TOO_LARGE");
+}
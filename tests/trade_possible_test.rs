@@ -1,15 +1,11 @@
 mod common;
 
-use crate::common::test_utils::{get_test_mutex, setup_test_app, get_auth_token};
+use crate::common::test_utils::{setup_test_app, get_auth_token};
 use reqwest::StatusCode;
 use serde_json::{json, Value};
 
 #[tokio::test]
 async fn test_get_possible_trades_success() {
-    // Usa mutex para garantir execução sequencial dos testes
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-    
     // Arrange
     let app = setup_test_app().await;
     let client = reqwest::Client::new();
@@ -20,7 +16,7 @@ async fn test_get_possible_trades_success() {
 
     // Primeiro, buscar livros através da API do Google Books
     let search_response = client
-        .post(&format!("http://localhost:{}/api/books/search", app.port))
+        .post(format!("http://localhost:{}/api/v1/books/search", app.port))
         .header("Authorization", format!("Bearer {}", user1_token))
         .json(&json!({
             "query": "Clean Code"
@@ -41,7 +37,7 @@ async fn test_get_possible_trades_success() {
     
     // Buscar um segundo livro
     let search_response2 = client
-        .post(&format!("http://localhost:{}/api/books/search", app.port))
+        .post(format!("http://localhost:{}/api/v1/books/search", app.port))
         .header("Authorization", format!("Bearer {}", user1_token))
         .json(&json!({
             "query": "Design Patterns"
@@ -62,7 +58,7 @@ async fn test_get_possible_trades_success() {
 
     // User 1 adiciona livro 1 aos oferecidos
     let _add_offered_response1 = client
-        .post(&format!("http://localhost:{}/api/books/offered", app.port))
+        .post(format!("http://localhost:{}/api/v1/books/offered", app.port))
         .header("Authorization", format!("Bearer {}", user1_token))
         .json(&json!({
             "google_id": google_id_1
@@ -73,7 +69,7 @@ async fn test_get_possible_trades_success() {
 
     // User 1 adiciona livro 2 aos desejados  
     let _add_wanted_response1 = client
-        .post(&format!("http://localhost:{}/api/books/wanted", app.port))
+        .post(format!("http://localhost:{}/api/v1/books/wanted", app.port))
         .header("Authorization", format!("Bearer {}", user1_token))
         .json(&json!({
             "google_id": google_id_2
@@ -84,7 +80,7 @@ async fn test_get_possible_trades_success() {
 
     // User 2 adiciona livro 2 aos oferecidos (o que user1 quer)
     let _add_offered_response2 = client
-        .post(&format!("http://localhost:{}/api/books/offered", app.port))
+        .post(format!("http://localhost:{}/api/v1/books/offered", app.port))
         .header("Authorization", format!("Bearer {}", user2_token))
         .json(&json!({
             "google_id": google_id_2
@@ -95,7 +91,7 @@ async fn test_get_possible_trades_success() {
 
     // User 2 adiciona livro 1 aos desejados (o que user1 oferece)
     let _add_wanted_response2 = client
-        .post(&format!("http://localhost:{}/api/books/wanted", app.port))
+        .post(format!("http://localhost:{}/api/v1/books/wanted", app.port))
         .header("Authorization", format!("Bearer {}", user2_token))
         .json(&json!({
             "google_id": google_id_1
@@ -106,7 +102,7 @@ async fn test_get_possible_trades_success() {
 
     // Act - Buscar trocas possíveis para user1 (usando seu token)
     let trades_response = client
-        .get(&format!("http://localhost:{}/api/trades/possible", app.port))
+        .get(format!("http://localhost:{}/api/v1/trades/possible", app.port))
         .header("Authorization", format!("Bearer {}", user1_token))
         .send()
         .await
@@ -133,8 +129,6 @@ async fn test_get_possible_trades_success() {
 async fn test_get_possible_trades_invalid_user_id() {
     // Este teste não se aplica mais pois não há parâmetro user_id na URL
     // Vou manter apenas para compatibilidade
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
     
     let app = setup_test_app().await;
     let client = reqwest::Client::new();
@@ -142,7 +136,7 @@ async fn test_get_possible_trades_invalid_user_id() {
 
     // Act - Buscar trocas com rota inválida (path que não existe)
     let trades_response = client
-        .get(&format!("http://localhost:{}/api/trades/possible/invalid-id", app.port))
+        .get(format!("http://localhost:{}/api/v1/trades/possible/invalid-id", app.port))
         .header("Authorization", format!("Bearer {}", token))
         .send()
         .await
@@ -156,15 +150,12 @@ async fn test_get_possible_trades_invalid_user_id() {
 
 #[tokio::test]
 async fn test_get_possible_trades_unauthorized() {
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-    
     let app = setup_test_app().await;
     let client = reqwest::Client::new();
 
     // Act - Buscar trocas sem autenticação
     let trades_response = client
-        .get(&format!("http://localhost:{}/api/trades/possible", app.port))
+        .get(format!("http://localhost:{}/api/v1/trades/possible", app.port))
         .send()
         .await
         .expect("Falha ao buscar trocas sem auth");
@@ -176,16 +167,13 @@ async fn test_get_possible_trades_unauthorized() {
 
 #[tokio::test]
 async fn test_get_possible_trades_valid_user_authenticated() {
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-    
     let app = setup_test_app().await;
     let client = reqwest::Client::new();
     let token = get_auth_token(&app).await;
 
     // Act - Buscar trocas com usuário autenticado válido
     let trades_response = client
-        .get(&format!("http://localhost:{}/api/trades/possible", app.port))
+        .get(format!("http://localhost:{}/api/v1/trades/possible", app.port))
         .header("Authorization", format!("Bearer {}", token))
         .send()
         .await
@@ -208,15 +196,12 @@ async fn test_get_possible_trades_valid_user_authenticated() {
 
 #[tokio::test]
 async fn test_get_possible_trades_with_invalid_token() {
-    let mutex = get_test_mutex().await;
-    let _lock = mutex.lock().await;
-    
     let app = setup_test_app().await;
     let client = reqwest::Client::new();
 
     // Act - Buscar trocas com token inválido
     let trades_response = client
-        .get(&format!("http://localhost:{}/api/trades/possible", app.port))
+        .get(format!("http://localhost:{}/api/v1/trades/possible", app.port))
         .header("Authorization", "Bearer token_invalido")
         .send()
         .await